@@ -4,18 +4,17 @@ use std::path::{Path, PathBuf};
 
 use bumpalo::Bump;
 use roc_error_macros::{internal_error, user_error};
-use roc_fmt::def::fmt_defs;
-use roc_fmt::module::fmt_module;
-use roc_fmt::spaces::RemoveSpaces;
-use roc_fmt::{Ast, Buf};
-use roc_parse::module::parse_module_defs;
-use roc_parse::{module, parser::SyntaxError, state::State};
+pub use roc_fmt::verify::FormatProblem;
+use roc_fmt::verify::{format_and_verify, minimize_counterexample};
+use roc_reporting::error::expect::SNAPSHOT_DIR;
 
 #[derive(Copy, Clone, Debug)]
 pub enum FormatMode {
     WriteToFile,
     WriteToStdout,
     CheckOnly,
+    /// Check that the formatter handles the files correctly, without changing them
+    Verify,
 }
 
 fn flatten_directories(files: std::vec::Vec<PathBuf>) -> std::vec::Vec<PathBuf> {
@@ -31,7 +30,9 @@ fn flatten_directories(files: std::vec::Vec<PathBuf>) -> std::vec::Vec<PathBuf>
                             Ok(file) => {
                                 let file_path = file.path();
                                 if file_path.is_dir() {
-                                    to_flatten.push(file_path);
+                                    if !is_snapshot_dir(&file_path) {
+                                        to_flatten.push(file_path);
+                                    }
                                 } else if is_roc_file(&file_path) {
                                     files.push(file_path);
                                 }
@@ -62,21 +63,40 @@ fn is_roc_file(path: &Path) -> bool {
     matches!(path.extension().and_then(OsStr::to_str), Some("roc"))
 }
 
+/// `roc test` generates everything in here, so it's not formatted, even when it's in a
+/// directory that is.
+fn is_snapshot_dir(path: &Path) -> bool {
+    path.file_name() == Some(OsStr::new(SNAPSHOT_DIR))
+}
+
 pub fn format_files(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(), String> {
     let arena = Bump::new();
     let mut files_to_reformat = Vec::new(); // to track which files failed `roc format --check`
+    let mut verify_failures = Vec::new(); // to track which files failed `roc format --verify`
 
     for file in flatten_directories(files) {
         let src = std::fs::read_to_string(&file).unwrap();
 
+        if let FormatMode::Verify = mode {
+            if let Err(problem) = format_src(&arena, &src) {
+                verify_failures.push(verify_failure_report(&file, &src, &problem));
+            }
+
+            continue;
+        }
+
         match format_src(&arena, &src) {
             Ok(buf) => {
                 match mode {
                     FormatMode::CheckOnly => {
-                        // If a file fails `format --check`, add it to the file
-                        // list for reporting afterwards.
+                        // If a file fails `format --check`, show what would change, and
+                        // add it to the file list for reporting afterwards.
                         if buf.as_str() != src {
-                            files_to_reformat.push(file.display().to_string());
+                            let path = file.display().to_string();
+                            let diff = unified_diff(&path, &src, &buf);
+
+                            std::io::stdout().lock().write_all(diff.as_bytes()).unwrap();
+                            files_to_reformat.push(path);
                         }
                     }
                     FormatMode::WriteToFile => {
@@ -86,9 +106,17 @@ pub fn format_files(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(
                     FormatMode::WriteToStdout => {
                         std::io::stdout().lock().write_all(buf.as_bytes()).unwrap()
                     }
+                    FormatMode::Verify => {
+                        // We `continue`d before formatting the file
+                        unreachable!()
+                    }
                 }
             }
             Err(err) => match err {
+                FormatProblem::InvalidSource { .. } => {
+                    // format_src reports these itself
+                    unreachable!()
+                }
                 FormatProblem::ParsingFailed {
                     formatted_src,
                     parse_err,
@@ -154,6 +182,10 @@ pub fn format_files(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(
             },
         }
     }
+    if !verify_failures.is_empty() {
+        return Err(verify_failures.join("\n\n"));
+    }
+
     // After processing all files, check if any files failed `format --check`
     if !files_to_reformat.is_empty() {
         let file_list = files_to_reformat.join(", ");
@@ -165,88 +197,224 @@ pub fn format_files(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(
     Ok(())
 }
 
-#[derive(Debug)]
-pub enum FormatProblem {
-    ParsingFailed {
-        formatted_src: String,
-        parse_err: String,
-    },
-    ReformattingChangedAst {
-        formatted_src: String,
-        ast_before: String,
-        ast_after: String,
-    },
-    ReformattingUnstable {
-        formatted_src: String,
-        reformatted_src: String,
-    },
+/// Describes how formatting a file went wrong, with the smallest part of the file that it
+/// goes wrong for in the same way, to make it easier to report the bug.
+fn verify_failure_report(file: &Path, src: &str, problem: &FormatProblem) -> String {
+    let mut report = format!(
+        "Formatting bug in {}: {}.",
+        file.display(),
+        problem.summary()
+    );
+
+    if let Some(counterexample) = minimize_counterexample(src) {
+        report.push_str(&format!(
+            "\n\nThis is the smallest part of the file I found that has the same problem:\n\n{counterexample}"
+        ));
+    }
+
+    report
 }
 
 pub fn format_src(arena: &Bump, src: &str) -> Result<String, FormatProblem> {
-    let ast = arena.alloc(parse_all(arena, src).unwrap_or_else(|e| {
-        user_error!("Unexpected parse failure when parsing this formatting:\n\n{:?}\n\nParse error was:\n\n{:?}\n\n", src, e)
-    }));
-    let mut buf = Buf::new_in(arena);
-    fmt_all(&mut buf, ast);
-
-    let reparsed_ast = match arena.alloc(parse_all(arena, buf.as_str())) {
-        Ok(ast) => ast,
-        Err(e) => {
-            return Err(FormatProblem::ParsingFailed {
-                formatted_src: buf.as_str().to_string(),
-                parse_err: format!("{:?}", e),
-            });
+    match format_and_verify(arena, src) {
+        Err(FormatProblem::InvalidSource { parse_err }) => user_error!(
+            "Unexpected parse failure when parsing this formatting:\n\n{:?}\n\nParse error was:\n\n{}\n\n",
+            src,
+            parse_err
+        ),
+        result => result,
+    }
+}
+
+/// How many unchanged lines to show around each change in a diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Renders the changes from `before` to `after` as a unified diff, like `diff -u` does.
+/// Returns an empty string if they are the same.
+pub fn unified_diff(path: &str, before: &str, after: &str) -> String {
+    // Keep the newlines, so that a missing newline at the end of the file is a change too
+    let before_lines: std::vec::Vec<&str> = before.split_inclusive('\n').collect();
+    let after_lines: std::vec::Vec<&str> = after.split_inclusive('\n').collect();
+    let diff = diff_lines(&before_lines, &after_lines);
+
+    // Where each line of the diff is, in the old and the new file
+    let mut positions = std::vec::Vec::with_capacity(diff.len());
+    let (mut old_line, mut new_line) = (0, 0);
+
+    for line in diff.iter() {
+        positions.push((old_line, new_line));
+
+        match line {
+            DiffLine::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
         }
-    };
-
-    let ast_normalized = ast.remove_spaces(arena);
-    let reparsed_ast_normalized = reparsed_ast.remove_spaces(arena);
-
-    // HACK!
-    // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
-    // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
-    // I don't have the patience to debug this right now, so let's leave it for another day...
-    // TODO: fix PartialEq impl on ast types
-    if format!("{ast_normalized:?}") != format!("{reparsed_ast_normalized:?}") {
-        return Err(FormatProblem::ReformattingChangedAst {
-            formatted_src: buf.as_str().to_string(),
-            ast_before: format!("{ast_normalized:#?}\n"),
-            ast_after: format!("{reparsed_ast_normalized:#?}\n"),
-        });
     }
 
-    // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
-    let mut reformatted_buf = Buf::new_in(arena);
+    // Changes that are close together share a hunk, so that context lines are not repeated
+    let mut hunks: std::vec::Vec<(usize, usize)> = std::vec::Vec::new();
+
+    for (index, line) in diff.iter().enumerate() {
+        if matches!(line, DiffLine::Same(_)) {
+            continue;
+        }
 
-    fmt_all(&mut reformatted_buf, reparsed_ast);
+        let start = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (index + 1 + DIFF_CONTEXT_LINES).min(diff.len());
 
-    if buf.as_str() != reformatted_buf.as_str() {
-        return Err(FormatProblem::ReformattingUnstable {
-            formatted_src: buf.as_str().to_string(),
-            reformatted_src: reformatted_buf.as_str().to_string(),
-        });
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
     }
 
-    Ok(buf.as_str().to_string())
-}
+    if hunks.is_empty() {
+        return String::new();
+    }
 
-fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
-    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
-        .map_err(|e| SyntaxError::Header(e.problem))?;
+    let mut out = format!("--- {path}\n+++ {path}\n");
+
+    for (start, end) in hunks {
+        let lines = &diff[start..end];
+        let (old_start, new_start) = positions[start];
+        let old_count = lines
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = lines
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
 
-    let (module, defs) = module.upgrade_header_imports(arena);
+        for line in lines {
+            let (prefix, text) = match line {
+                DiffLine::Same(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
 
-    let defs = parse_module_defs(arena, state, defs)?;
+            out.push(prefix);
+            out.push_str(text);
 
-    Ok(Ast { module, defs })
+            if !text.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    out
 }
 
-fn fmt_all<'a>(buf: &mut Buf<'a>, ast: &'a Ast) {
-    fmt_module(buf, &ast.module);
+/// Formats where a hunk starts and how many lines it has, the way `diff -u` does
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        // An empty range refers to the line before it
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+/// The shortest edit script from `before` to `after`, using Myers' algorithm.
+fn diff_lines<'a>(before: &[&'a str], after: &[&'a str]) -> std::vec::Vec<DiffLine<'a>> {
+    let n = before.len() as isize;
+    let m = after.len() as isize;
+    let max = n + m;
+    let offset = max as usize;
+
+    // For each diagonal k, how far along `before` the furthest path on it got
+    let mut furthest = vec![0isize; 2 * offset + 2];
+    // What `furthest` looked like before each round, for the diagonals that round could use
+    let mut trace: std::vec::Vec<std::vec::Vec<isize>> = std::vec::Vec::new();
+
+    'rounds: for d in 0..=max {
+        let low = offset - d as usize;
+        trace.push(furthest[low..=offset + d as usize].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let index = (k + offset as isize) as usize;
+            let mut x = if k == -d || (k != d && furthest[index - 1] < furthest[index + 1]) {
+                furthest[index + 1]
+            } else {
+                furthest[index - 1] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && before[x as usize] == after[y as usize] {
+                x += 1;
+                y += 1;
+            }
 
-    fmt_defs(buf, &ast.defs, 0);
+            furthest[index] = x;
+
+            if x >= n && y >= m {
+                break 'rounds;
+            }
+        }
+    }
 
-    buf.fmt_end_of_file();
+    // Walk back from the end to find the path that got there
+    let mut diff = std::vec::Vec::with_capacity(max as usize);
+    let (mut x, mut y) = (n, m);
+
+    for (d, furthest) in trace.iter().enumerate().rev() {
+        if d == 0 {
+            // Only the lines at the start that were the same are left
+            while x > 0 {
+                diff.push(DiffLine::Same(before[x as usize - 1]));
+                x -= 1;
+            }
+
+            break;
+        }
+
+        let d = d as isize;
+        let k = x - y;
+        let at = |k: isize| furthest[(k + d) as usize];
+
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            diff.push(DiffLine::Same(before[x as usize - 1]));
+            x -= 1;
+            y -= 1;
+        }
+
+        if x == prev_x {
+            diff.push(DiffLine::Added(after[y as usize - 1]));
+        } else {
+            diff.push(DiffLine::Removed(before[x as usize - 1]));
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    diff.reverse();
+
+    diff
 }
 
 #[cfg(test)]
@@ -349,4 +517,50 @@ main =
 
         cleanup_temp_dir(dir);
     }
+
+    #[test]
+    fn test_snapshot_dirs_are_skipped() {
+        let dir = tempdir().unwrap();
+        let snapshot_dir = dir.path().join(SNAPSHOT_DIR);
+        std::fs::create_dir(&snapshot_dir).unwrap();
+        setup_test_file(dir.path(), "formatted.roc", FORMATTED_ROC);
+        setup_test_file(&snapshot_dir, "generated.roc", UNFORMATTED_ROC);
+
+        let result = format_files(vec![dir.path().to_path_buf()], FormatMode::CheckOnly);
+        assert!(result.is_ok());
+
+        cleanup_temp_dir(dir);
+    }
+
+    #[test]
+    fn test_verify_does_not_change_files() {
+        let dir = tempdir().unwrap();
+        let file_formatted = setup_test_file(dir.path(), "formatted.roc", FORMATTED_ROC);
+        let file_unformatted = setup_test_file(dir.path(), "test1.roc", UNFORMATTED_ROC);
+
+        let result = format_files(
+            vec![file_formatted, file_unformatted.clone()],
+            FormatMode::Verify,
+        );
+        assert!(result.is_ok());
+
+        let src = std::fs::read_to_string(file_unformatted).unwrap();
+        assert_eq!(src, format!("{UNFORMATTED_ROC}\n"));
+
+        cleanup_temp_dir(dir);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let before = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let after = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk";
+
+        assert_eq!(
+            unified_diff("test.roc", before, after),
+            "--- test.roc\n+++ test.roc\n\
+            @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+            @@ -8,3 +8,4 @@\n h\n i\n j\n+k\n\\ No newline at end of file\n"
+        );
+        assert_eq!(unified_diff("test.roc", before, before), "");
+    }
 }
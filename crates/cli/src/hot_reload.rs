@@ -0,0 +1,375 @@
+//! `roc dev --hot`: rebuild the app as a shared library whenever one of its files changes, and
+//! have the platform's host load each new build without restarting, so it keeps its own state.
+//!
+//! Platforms opt in by providing a hot-reload host next to their main.roc (see
+//! [roc_build::link::hot_reload_host_file]). We start it with `ROC_HOT_RELOAD_MANIFEST` set to
+//! the path of a file that holds the path of the newest build. After each successful rebuild we
+//! replace that file, and on Unix we also send the host SIGUSR1. The host then finds the entry
+//! points of the new build with the `RocEntryPoints` table declared in the build's C header.
+//!
+//! Loading a new build in place only works while the entry points stay the same, so whenever
+//! the header changes, we restart the host instead.
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+
+use bumpalo::Bump;
+use clap::ArgMatches;
+use roc_build::link::{hot_reload_host_file, LinkType};
+use roc_build::program::{
+    build_file, handle_error_module, handle_loading_problem, standard_load_config, BuildFileError,
+    BuildOrdering, BuiltFile, CodeGenBackend, CodeGenOptions, EmitArtifacts,
+};
+use roc_error_macros::user_error;
+use roc_gen_dev::AssemblyBackendMode;
+use roc_gen_llvm::llvm::build::{AliasAnalysisOptions, LlvmBackendMode};
+use roc_load::Threading;
+use roc_mono::inline_hints::DEFAULT_INLINE_THRESHOLD;
+use roc_mono::ir::OptLevel;
+use roc_packaging::cache::RocCacheDir;
+use roc_reporting::cli::OutputFormat;
+use roc_target::Target;
+
+use crate::targets::BuildTarget;
+use crate::watch::{snapshot, Snapshot, POLL_INTERVAL};
+use crate::{
+    update_lockfile, ARGS_FOR_APP, FLAG_DEV, FLAG_LINKER, FLAG_LOCKED, FLAG_MAX_THREADS, FLAG_TIME,
+    ROC_FILE,
+};
+
+/// Tells the host where to find the newest build. The C header declares it as
+/// `ROC_HOT_RELOAD_MANIFEST_ENV`.
+const MANIFEST_ENV: &str = "ROC_HOT_RELOAD_MANIFEST";
+
+/// The directory next to the app's .roc file that the builds go in
+const OUTPUT_DIR: &str = ".roc-hot";
+
+/// How many builds to keep around. The host may still be running the previous one while it
+/// loads the newest one, so that has to stay.
+const KEPT_BUILDS: usize = 2;
+
+struct Host {
+    child: Child,
+    /// The C header of the build the host was started with, which describes its entry points
+    header: String,
+}
+
+struct Build {
+    library: PathBuf,
+    /// The main.roc of the platform the app was built with
+    platform_path: PathBuf,
+}
+
+pub fn dev_hot_reload(
+    matches: &ArgMatches,
+    target: Target,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> io::Result<i32> {
+    let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+    if !roc_file_path.exists() {
+        user_error!("This file was not found: {}", roc_file_path.display());
+    }
+
+    let app_dir = match roc_file_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let out_dir = app_dir.join(OUTPUT_DIR);
+
+    std::fs::create_dir_all(&out_dir)?;
+
+    let manifest_path = out_dir.join("current");
+    let file_stem = roc_file_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+    // Where `roc build` would put the binary, which `roc run` uses as argv[0]
+    let program_name = roc_file_path.with_extension(std::env::consts::EXE_EXTENSION);
+    let args: Vec<OsString> = matches
+        .get_many::<OsString>(ARGS_FOR_APP)
+        .unwrap_or_default()
+        .cloned()
+        .collect();
+
+    let mut host: Option<Host> = None;
+    let mut builds: Vec<PathBuf> = Vec::new();
+    let mut generation = 0;
+
+    loop {
+        let arena = Bump::new();
+        let out_path = out_dir.join(format!("{file_stem}-{generation}"));
+
+        generation += 1;
+
+        // Taken before building, so changes made while we build still trigger a rebuild
+        let mut watched_paths = Vec::new();
+        roc_files(app_dir, &mut watched_paths);
+        let before = snapshot(&watched_paths);
+
+        if let Some(Build {
+            library,
+            platform_path,
+        }) = build_library(
+            &arena,
+            matches,
+            target,
+            roc_file_path,
+            &out_path,
+            roc_cache_dir,
+        )? {
+            let host_path = hot_reload_host_file(target, &platform_path);
+
+            if !host_path.exists() {
+                user_error!(
+                    "`roc dev --hot` needs the platform to provide a hot-reload host, but there is no {} next to its main.roc.",
+                    host_path.file_name().unwrap_or_default().to_string_lossy()
+                );
+            }
+
+            let header = std::fs::read_to_string(library.with_extension("h"))?;
+
+            publish(&manifest_path, &library)?;
+
+            match host.as_mut() {
+                Some(running) if running.header == header => {
+                    signal_reload(&running.child);
+
+                    println!("Reloaded {}", library.display());
+                }
+                Some(running) => {
+                    println!("The app's entry points changed, so the host has to restart…");
+
+                    running.child.kill()?;
+                    running.child.wait()?;
+
+                    host = Some(Host {
+                        child: spawn_host(&host_path, &program_name, &manifest_path, &args)?,
+                        header,
+                    });
+                }
+                None => {
+                    host = Some(Host {
+                        child: spawn_host(&host_path, &program_name, &manifest_path, &args)?,
+                        header,
+                    });
+                }
+            }
+
+            builds.push(library);
+
+            if builds.len() > KEPT_BUILDS {
+                for stale in builds.drain(..builds.len() - KEPT_BUILDS) {
+                    let _ = std::fs::remove_file(stale.with_extension("h"));
+                    let _ = std::fs::remove_file(stale);
+                }
+            }
+        } else if host.is_some() {
+            println!("The host keeps running the previous build.");
+        }
+
+        if let Some(exit_code) = wait_for_change_or_exit(&watched_paths, before, host.as_mut())? {
+            return Ok(exit_code);
+        }
+    }
+}
+
+/// Builds the app as a shared library, along with its C header. Returns `None` if the app had
+/// errors, after printing them.
+fn build_library(
+    arena: &Bump,
+    matches: &ArgMatches,
+    target: Target,
+    roc_file_path: &Path,
+    out_path: &Path,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> io::Result<Option<Build>> {
+    let backend = if matches.get_flag(FLAG_DEV) {
+        CodeGenBackend::Assembly(AssemblyBackendMode::Binary)
+    } else {
+        CodeGenBackend::Llvm(LlvmBackendMode::BinaryDev)
+    };
+
+    let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(*n),
+    };
+
+    let code_gen_options = CodeGenOptions {
+        backend,
+        opt_level: OptLevel::Development,
+        emit_debug_info: true,
+        emit_debug_locations: false,
+        emit: EmitArtifacts::default(),
+        fuzz: false,
+        frame_pointers: false,
+        inline_threshold: DEFAULT_INLINE_THRESHOLD,
+        alias_analysis: AliasAnalysisOptions::default(),
+        verbose_layouts: false,
+        reproducible: false,
+    };
+
+    let link_type = LinkType::Dylib;
+    let linking_strategy = BuildTarget::new(target).linking_strategy(
+        link_type,
+        false,
+        matches.get_one::<String>(FLAG_LINKER).map(|s| s.as_str()),
+    );
+
+    let built = build_file(
+        arena,
+        target,
+        roc_file_path.to_path_buf(),
+        code_gen_options,
+        matches.get_flag(FLAG_TIME),
+        link_type,
+        linking_strategy,
+        false,
+        None,
+        roc_cache_dir,
+        standard_load_config(target, BuildOrdering::BuildIfChecks, threading),
+        OutputFormat::Human,
+        Some(out_path),
+    );
+
+    match built {
+        Ok(BuiltFile {
+            binary_path,
+            platform_path,
+            package_urls,
+            problems,
+            total_time,
+            ..
+        }) => {
+            if !update_lockfile(
+                roc_file_path,
+                &package_urls,
+                matches.get_flag(FLAG_LOCKED),
+                false,
+            )? {
+                return Ok(None);
+            }
+
+            if problems.errors > 0 || problems.warnings > 0 {
+                problems.print_error_warning_count(total_time);
+                println!(".");
+            }
+
+            if problems.fatally_errored || problems.errors > 0 {
+                return Ok(None);
+            }
+
+            roc_glue::c_header::write_c_header(
+                &platform_path,
+                &binary_path.with_extension("h"),
+                target,
+            )?;
+
+            Ok(Some(Build {
+                library: binary_path,
+                platform_path,
+            }))
+        }
+        Err(BuildFileError::ErrorModule { module, total_time }) => {
+            handle_error_module(
+                module,
+                total_time,
+                roc_file_path.as_os_str(),
+                false,
+                OutputFormat::Human,
+            )?;
+
+            Ok(None)
+        }
+        Err(BuildFileError::LoadingProblem(problem)) => {
+            handle_loading_problem(problem, OutputFormat::Human)?;
+
+            Ok(None)
+        }
+    }
+}
+
+fn spawn_host(
+    host_path: &Path,
+    program_name: &Path,
+    manifest_path: &Path,
+    args: &[OsString],
+) -> io::Result<Child> {
+    let mut command = Command::new(host_path);
+
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::arg0(&mut command, program_name);
+    #[cfg(not(unix))]
+    let _ = program_name;
+
+    command.args(args).env(MANIFEST_ENV, manifest_path).spawn()
+}
+
+/// Points the manifest at `library`. The manifest is replaced rather than written in place, so
+/// the host never reads half of a path.
+fn publish(manifest_path: &Path, library: &Path) -> io::Result<()> {
+    let temp_path = manifest_path.with_extension("tmp");
+
+    std::fs::write(
+        &temp_path,
+        library.canonicalize()?.as_os_str().as_encoded_bytes(),
+    )?;
+    std::fs::rename(temp_path, manifest_path)
+}
+
+#[cfg(unix)]
+fn signal_reload(child: &Child) {
+    // If the host has already exited, we find out the next time we wait for it
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGUSR1);
+    }
+}
+
+#[cfg(not(unix))]
+fn signal_reload(_child: &Child) {
+    // There are no signals here, so hosts notice the new build by polling the manifest
+}
+
+/// The .roc files in `dir` and its subdirectories, except for hidden ones like [OUTPUT_DIR]
+fn roc_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                roc_files(&path, files);
+            }
+        } else if path.extension() == Some(OsStr::new("roc")) {
+            files.push(path);
+        }
+    }
+}
+
+/// Waits until one of `paths` differs from `before` and returns `None`, or until the host exits
+/// and returns its exit code.
+fn wait_for_change_or_exit(
+    paths: &[PathBuf],
+    before: Snapshot,
+    mut host: Option<&mut Host>,
+) -> io::Result<Option<i32>> {
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        if let Some(host) = host.as_mut() {
+            if let Some(status) = host.child.try_wait()? {
+                return Ok(Some(status.code().unwrap_or(1)));
+            }
+        }
+
+        if snapshot(paths) != before {
+            return Ok(None);
+        }
+    }
+}
@@ -10,20 +10,29 @@ use clap::{
 };
 use roc_build::link::{LinkType, LinkingStrategy};
 use roc_build::program::{
-    handle_error_module, handle_loading_problem, standard_load_config, BuildFileError,
-    BuildOrdering, BuiltFile, CodeGenBackend, CodeGenOptions, DEFAULT_ROC_FILENAME,
+    check_file_rendered, handle_error_module, handle_loading_problem, standard_load_config,
+    BuildFileError, BuildOrdering, BuiltFile, CheckedFile, CodeGenBackend, CodeGenOptions,
+    EmitArtifacts, DEFAULT_ROC_FILENAME,
 };
 use roc_collections::MutMap;
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_dev::AssemblyBackendMode;
-use roc_gen_llvm::llvm::build::LlvmBackendMode;
+use roc_gen_llvm::llvm::build::{AliasAnalysisOptions, LlvmBackendMode};
 use roc_load::{ExpectMetadata, Threading};
 use roc_module::symbol::ModuleId;
+use roc_mono::build_info::BuildInfo;
+use roc_mono::inline_hints::DEFAULT_INLINE_THRESHOLD;
 use roc_mono::ir::OptLevel;
 use roc_packaging::cache::RocCacheDir;
+use roc_packaging::lockfile::{lockfile_path, Lockfile, LOCKFILE_NAME};
 use roc_packaging::tarball::Compression;
-use roc_reporting::report::ANSI_STYLE_CODES;
-use roc_target::{Architecture, Target};
+use roc_region::all::LineColumn;
+#[cfg(not(windows))]
+use roc_repl_expect::run::ExpectResults;
+use roc_reporting::cli::{Diagnostic, OutputFormat};
+use roc_reporting::report::{RenderTarget, ANSI_STYLE_CODES};
+use roc_reporting::sarif::sarif_log;
+use roc_target::{Architecture, OperatingSystem, Target};
 use std::env;
 use std::ffi::{CString, OsStr, OsString};
 use std::io;
@@ -32,12 +41,20 @@ use std::os::raw::{c_char, c_int};
 use std::path::{Path, PathBuf};
 use std::process;
 use std::time::{Duration, Instant};
-use strum::IntoEnumIterator;
+use targets::{target_flag_help, target_names, BuildTarget};
 #[cfg(not(target_os = "linux"))]
 use tempfile::TempDir;
 
 mod format;
-pub use format::{format_files, format_src, FormatMode};
+mod hot_reload;
+mod package;
+mod profile;
+mod targets;
+mod watch;
+pub use format::{format_files, format_src, unified_diff, FormatMode, FormatProblem};
+pub use hot_reload::dev_hot_reload;
+pub use package::package;
+pub use watch::check_watch;
 
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
@@ -51,9 +68,16 @@ pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_GEN_STUB_LIB: &str = "gen-stub-lib";
 pub const CMD_PREPROCESS_HOST: &str = "preprocess-host";
+pub const CMD_EXPLAIN: &str = "explain";
+pub const CMD_INSTALL: &str = "install";
+pub const CMD_PACKAGE: &str = "package";
+pub const CMD_INSPECT_BINARY: &str = "inspect-binary";
 
 pub const FLAG_EMIT_LLVM_IR: &str = "emit-llvm-ir";
 pub const FLAG_PROFILING: &str = "profiling";
+pub const FLAG_DEBUG_INFO: &str = "debug-info";
+pub const FLAG_EMIT: &str = "emit";
+pub const FLAG_EMIT_DIR: &str = "emit-dir";
 pub const FLAG_BUNDLE: &str = "bundle";
 pub const FLAG_DEV: &str = "dev";
 pub const FLAG_OPTIMIZE: &str = "optimize";
@@ -61,6 +85,8 @@ pub const FLAG_MAX_THREADS: &str = "max-threads";
 pub const FLAG_OPT_SIZE: &str = "opt-size";
 pub const FLAG_LIB: &str = "lib";
 pub const FLAG_NO_LINK: &str = "no-link";
+pub const FLAG_STATIC: &str = "static";
+pub const FLAG_LOCKED: &str = "locked";
 pub const FLAG_TARGET: &str = "target";
 pub const FLAG_TIME: &str = "time";
 pub const FLAG_VERBOSE: &str = "verbose";
@@ -69,19 +95,64 @@ pub const FLAG_PREBUILT: &str = "prebuilt-platform";
 pub const FLAG_CHECK: &str = "check";
 pub const FLAG_STDIN: &str = "stdin";
 pub const FLAG_STDOUT: &str = "stdout";
+pub const FLAG_VERIFY: &str = "verify";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
 pub const FLAG_OUTPUT: &str = "output";
 pub const FLAG_FUZZ: &str = "fuzz";
+pub const FLAG_INLINE_THRESHOLD: &str = "inline-threshold";
+pub const FLAG_NO_ALIAS_ANALYSIS: &str = "no-alias-analysis";
+pub const FLAG_REPORT_ALIAS_ANALYSIS: &str = "report-alias-analysis";
+pub const FLAG_VERBOSE_LAYOUTS: &str = "verbose-layouts";
+pub const FLAG_WATCH: &str = "watch";
+pub const FLAG_TAILCALLS: &str = "tailcalls";
+pub const FLAG_EXPLAIN_TYPE: &str = "explain-type";
+pub const FLAG_TIMINGS: &str = "timings";
+pub const FLAG_PROFILE: &str = "profile";
+pub const FLAG_OUTPUT_FORMAT: &str = "output-format";
+pub const FLAG_SARIF: &str = "sarif";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_MODULE: &str = "module";
+pub const FLAG_LINE: &str = "line";
+pub const FLAG_DOCTESTS: &str = "doctests";
+pub const FLAG_LEAK_CHECK: &str = "leak-check";
+pub const FLAG_COVERAGE: &str = "coverage";
+pub const FLAG_UPDATE_SNAPSHOTS: &str = "update-snapshots";
+pub const FLAG_HOT: &str = "hot";
+pub const FLAG_REPRODUCIBLE: &str = "reproducible";
+pub const FLAG_CHECK_REPRODUCIBLE: &str = "check-reproducible";
+pub const FLAG_EXTERNS: &str = "externs";
+pub const FLAG_NO_STD: &str = "no-std";
+pub const FLAG_JSON: &str = "json";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
+pub const ERROR_CODE: &str = "ERROR_CODE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
+pub const BINARY_FILE: &str = "BINARY_FILE";
 
 const VERSION: &str = include_str!("../../../version.txt");
 const DEFAULT_GENERATED_DOCS_DIR: &str = "generated-docs";
 
+/// Parses the 1-based `LINE:COLUMN` given to `--explain-type` into a 0-based [LineColumn].
+fn parse_line_column(position: &str) -> Result<LineColumn, String> {
+    let invalid = || {
+        format!("expected a position like 4:9 (lines and columns start at 1), but got {position}")
+    };
+    let parse = |part: &str| match part.trim().parse::<u32>() {
+        Ok(n) if n > 0 => Ok(n - 1),
+        _ => Err(invalid()),
+    };
+
+    let (line, column) = position.split_once(':').ok_or_else(invalid)?;
+
+    Ok(LineColumn {
+        line: parse(line)?,
+        column: parse(column)?,
+    })
+}
+
 pub fn build_app() -> Command {
     let flag_optimize = Arg::new(FLAG_OPTIMIZE)
         .long(FLAG_OPTIMIZE)
@@ -119,6 +190,12 @@ pub fn build_app() -> Command {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_debug_info = Arg::new(FLAG_DEBUG_INFO)
+        .long(FLAG_DEBUG_INFO)
+        .help("Attach the Roc file and line of each function to the debug info of the generated program, for debuggers and profilers")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
     let flag_time = Arg::new(FLAG_TIME)
         .long(FLAG_TIME)
         .help("Print detailed compilation time information")
@@ -149,6 +226,43 @@ pub fn build_app() -> Command {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_inline_threshold = Arg::new(FLAG_INLINE_THRESHOLD)
+        .long(FLAG_INLINE_THRESHOLD)
+        .help("In optimized LLVM builds, hint that functions with at most this many statements should be inlined. Use 0 to turn the hints off.")
+        .value_parser(value_parser!(u32))
+        .required(false);
+
+    let flag_no_alias_analysis = Arg::new(FLAG_NO_ALIAS_ANALYSIS)
+        .long(FLAG_NO_ALIAS_ANALYSIS)
+        .help("Don't trust alias analysis to update lists and tags in place; check at runtime whether each value is unique instead. (An escape hatch in case the analysis is wrong.)")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_report_alias_analysis = Arg::new(FLAG_REPORT_ALIAS_ANALYSIS)
+        .long(FLAG_REPORT_ALIAS_ANALYSIS)
+        .help("Print which list and tag updates alias analysis proved can happen in place, per function. The others check at runtime, and copy the value if it's shared.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_locked = Arg::new(FLAG_LOCKED)
+        .long(FLAG_LOCKED)
+        .help("Fail instead of updating roc.lock if the packages loaded over HTTPS don't match it\n(Useful in CI, to check that roc.lock is up to date.)")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_verbose_layouts = Arg::new(FLAG_VERBOSE_LAYOUTS)
+        .long(FLAG_VERBOSE_LAYOUTS)
+        .help("Print how many memory layouts the program uses, which are largest, and how their sizes are distributed. Large layouts are copied around a lot, and often make the binary bigger.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_output_format = Arg::new(FLAG_OUTPUT_FORMAT)
+        .long(FLAG_OUTPUT_FORMAT)
+        .help("How to print problems\n(`json` prints one JSON object per problem, for editors and CI systems.)")
+        .value_parser(OutputFormat::NAMES)
+        .default_value("human")
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .value_parser(value_parser!(PathBuf))
@@ -156,13 +270,12 @@ pub fn build_app() -> Command {
         .default_value(DEFAULT_ROC_FILENAME);
 
     let args_for_app = Arg::new(ARGS_FOR_APP)
-        .help("Arguments to pass into the app being run\ne.g. `roc run -- arg1 arg2`")
+        .help("Arguments to pass into the app being run, after the path of its binary\ne.g. `roc run -- arg1 arg2`")
         .value_parser(value_parser!(OsString))
         .num_args(0..)
         .allow_hyphen_values(true);
 
-    let build_target_values_parser =
-        PossibleValuesParser::new(Target::iter().map(Into::<&'static str>::into));
+    let build_target_values_parser = PossibleValuesParser::new(target_names());
 
     Command::new("roc")
         .version(concatcp!(VERSION, "\n"))
@@ -182,15 +295,39 @@ pub fn build_app() -> Command {
             .arg(flag_dev.clone())
             .arg(flag_emit_llvm_ir.clone())
             .arg(flag_profiling.clone())
+            .arg(flag_debug_info)
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_inline_threshold.clone())
+            .arg(flag_no_alias_analysis.clone())
+            .arg(flag_report_alias_analysis.clone())
+            .arg(flag_verbose_layouts.clone())
+            .arg(flag_locked.clone())
+            .arg(flag_output_format.clone())
             .arg(flag_wasm_stack_size_kb)
+            .arg(
+                Arg::new(FLAG_EMIT)
+                    .long(FLAG_EMIT)
+                    .help("Also write out intermediate artifacts: the optimized LLVM IR (`.ll`), the generated assembly (`.s`), or the app's object file before linking\n(Can be given more than once, or as a comma-separated list.)")
+                    .value_parser(["llvm-ir", "asm", "obj"])
+                    .value_delimiter(',')
+                    .action(ArgAction::Append)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_EMIT_DIR)
+                    .long(FLAG_EMIT_DIR)
+                    .help("The directory to write the artifacts from --emit to. By default they go next to the .roc file.")
+                    .value_parser(value_parser!(PathBuf))
+                    .requires(FLAG_EMIT)
+                    .required(false),
+            )
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
-                    .help("Choose a different target")
+                    .help(target_flag_help())
                     .default_value(Into::<&'static str>::into(Target::default()))
                     .value_parser(build_target_values_parser.clone())
                     .required(false),
@@ -198,7 +335,7 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_LIB)
                     .long(FLAG_LIB)
-                    .help("Build a C library instead of an executable")
+                    .help("Build a C library instead of an executable, along with a C header declaring its entry points")
                     .action(ArgAction::SetTrue)
                     .required(false),
             )
@@ -217,6 +354,29 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_STATIC)
+                    .long(FLAG_STATIC)
+                    .help("Build a fully static Linux executable, linked against musl\n(This uses the legacy linker, and needs the platform to provide a static archive built against musl, like `linux-x64-musl.a` next to its main.roc.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([FLAG_LIB, FLAG_NO_LINK])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_REPRODUCIBLE)
+                    .long(FLAG_REPRODUCIBLE)
+                    .help("Build the same bytes from the same sources, wherever and whenever they're built\n(Paths are embedded relative to the app's directory, and the linker writes no timestamps.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_CHECK_REPRODUCIBLE)
+                    .long(FLAG_CHECK_REPRODUCIBLE)
+                    .help("Build with --reproducible twice, and fail if the two builds differ")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with(FLAG_BUNDLE)
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to build")
@@ -236,7 +396,17 @@ pub fn build_app() -> Command {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
-            .arg(flag_fuzz.clone())
+            .arg(
+                Arg::new(FLAG_FUZZ)
+                    .long(FLAG_FUZZ)
+                    .help("Also run the properties: top-level functions like `fuzzAddCommutes : I64, I64 -> Bool`\n(Each one is called with many generated arguments, and the smallest failing call is reported.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(flag_inline_threshold.clone())
+            .arg(flag_no_alias_analysis.clone())
+            .arg(flag_report_alias_analysis.clone())
+            .arg(flag_verbose_layouts.clone())
             .arg(
                 Arg::new(FLAG_VERBOSE)
                     .long(FLAG_VERBOSE)
@@ -244,6 +414,56 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false)
             )
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the expects that follow a def whose name contains this text")
+                    .value_parser(value_parser!(String))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_MODULE)
+                    .long(FLAG_MODULE)
+                    .help("Only run the expects in this module, e.g. `--module Parser` or `--module Parser.roc`\n(Can be given more than once.)")
+                    .value_parser(value_parser!(String))
+                    .action(ArgAction::Append)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_LINE)
+                    .long(FLAG_LINE)
+                    .help("Only run the expect on this line\n(Usually combined with --module.)")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_DOCTESTS)
+                    .long(FLAG_DOCTESTS)
+                    .help("Also run the ```roc code blocks in doc comments as expects\n(Each block has to end in a Bool.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_LEAK_CHECK)
+                    .long(FLAG_LEAK_CHECK)
+                    .help("Fail any pure expect that passes without freeing everything it allocated\n(Reports the size, alignment, and backtrace of each allocation it leaked.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_COVERAGE)
+                    .long(FLAG_COVERAGE)
+                    .help("Report how much of each module in this package the expects reached\n(Lists the top-level defs and `when` branches that never ran.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_UPDATE_SNAPSHOTS)
+                    .long(FLAG_UPDATE_SNAPSHOTS)
+                    .help("Replace the snapshot of every `Inspect.expectSnapshot` that runs with its current value\n(Snapshots are kept in a roc-snapshots directory next to the .roc file.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file for the main module")
@@ -268,6 +488,19 @@ pub fn build_app() -> Command {
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_inline_threshold.clone())
+            .arg(flag_no_alias_analysis.clone())
+            .arg(flag_report_alias_analysis.clone())
+            .arg(flag_verbose_layouts.clone())
+            .arg(flag_locked.clone())
+            .arg(
+                Arg::new(FLAG_PROFILE)
+                    .long(FLAG_PROFILE)
+                    .help("Sample the program with `perf` while it runs, and write a flamegraph of where it spent its time to this .svg file\n(This is only supported on Linux.)")
+                    .value_name("SVG")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+            )
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -283,6 +516,19 @@ pub fn build_app() -> Command {
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_inline_threshold.clone())
+            .arg(flag_no_alias_analysis.clone())
+            .arg(flag_report_alias_analysis.clone())
+            .arg(flag_verbose_layouts.clone())
+            .arg(flag_locked.clone())
+            .arg(
+                Arg::new(FLAG_HOT)
+                    .long(FLAG_HOT)
+                    .help("Build the app as a shared library, and rebuild it whenever one of its files changes, so the platform's host can load the new build without restarting\n(The platform has to provide a hot-reload host, like `linux-x64-hot-host` next to its main.roc.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([FLAG_FUZZ, FLAG_EMIT_LLVM_IR])
+                    .required(false)
+            )
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -297,8 +543,16 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_CHECK)
                     .long(FLAG_CHECK)
-                    .help("Checks that specified files are formatted\n(If formatting is needed, return a non-zero exit code.)")
+                    .help("Checks that specified files are formatted, without changing them\n(If formatting is needed, print a diff of what would change and return a non-zero exit code.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_VERIFY)
+                    .long(FLAG_VERIFY)
+                    .help("Checks that the formatter handles the specified files correctly, without changing them\n(If formatting changes what the code means, or formatting twice changes it again, print the smallest part of the file with that problem and return a non-zero exit code.)")
                     .action(ArgAction::SetTrue)
+                    .conflicts_with_all([FLAG_CHECK, FLAG_STDIN, FLAG_STDOUT])
                     .required(false),
             )
             .arg(
@@ -318,11 +572,96 @@ pub fn build_app() -> Command {
             .after_help("If DIRECTORY_OR_FILES is omitted, the .roc files in the current working\ndirectory are formatted.")
         )
         .subcommand(Command::new(CMD_VERSION)
-            .about(concatcp!("Print the Roc compiler’s version, which is currently ", VERSION)))
+            .about(concatcp!("Print the Roc compiler’s version, which is currently ", VERSION))
+            .arg(
+                Arg::new(FLAG_JSON)
+                    .long(FLAG_JSON)
+                    .help("Print the version, the target roc runs on, and the targets it can build for as JSON")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+        )
+        .subcommand(Command::new(CMD_INSPECT_BINARY)
+            .about("Print which compiler built a Roc app, for which target, and with which flags")
+            .arg(
+                Arg::new(FLAG_JSON)
+                    .long(FLAG_JSON)
+                    .help("Print it as JSON")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(BINARY_FILE)
+                    .help("The executable (or library) that `roc build` made")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(true),
+            )
+        )
+        .subcommand(Command::new(CMD_EXPLAIN)
+            .about("Explain a problem the compiler reported, given its code (e.g. E0107)")
+            .arg(
+                Arg::new(ERROR_CODE)
+                    .help("The code of the problem to explain")
+                    .required(true),
+            )
+        )
+        .subcommand(Command::new(CMD_INSTALL)
+            .about("Download the packages a .roc file uses into the cache, and record them in roc.lock")
+            .arg(flag_locked.clone())
+            .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of an app or package")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
         .subcommand(Command::new(CMD_CHECK)
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_output_format)
+            .arg(
+                Arg::new(FLAG_SARIF)
+                    .long(FLAG_SARIF)
+                    .help("Also write all errors and warnings to the given file as SARIF 2.1\n(For tools like GitHub code scanning to annotate the code with.)")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_WATCH)
+                    .long(FLAG_WATCH)
+                    .help("Keep running, and check again whenever one of the checked files changes\n(Only problems that weren't already shown are printed again.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([FLAG_OUTPUT_FORMAT, FLAG_SARIF])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TAILCALLS)
+                    .long(FLAG_TAILCALLS)
+                    .help("Also list every place a function calls itself, and whether that's a tail call\n(Tail calls reuse the stack frame, so they can recurse any number of times.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([FLAG_OUTPUT_FORMAT, FLAG_WATCH])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_EXPLAIN_TYPE)
+                    .long(FLAG_EXPLAIN_TYPE)
+                    .value_name("LINE:COLUMN")
+                    .help("Also print the type inferred for the expression or definition at this position in the checked file\n(Lines and columns start at 1, like in error messages.)")
+                    .value_parser(parse_line_column)
+                    .conflicts_with_all([FLAG_OUTPUT_FORMAT, FLAG_WATCH])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TIMINGS)
+                    .long(FLAG_TIMINGS)
+                    .help("Also print a table of how long each module spent in each phase, and the chain of imports that took the longest\n(Modules on that chain can't be checked in parallel, so splitting them up speeds up the whole build most.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([FLAG_OUTPUT_FORMAT, FLAG_WATCH])
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -341,6 +680,13 @@ pub fn build_app() -> Command {
                     .required(false)
                     .default_value(DEFAULT_GENERATED_DOCS_DIR),
                 )
+                .arg(Arg::new(FLAG_OUTPUT_FORMAT)
+                    .long(FLAG_OUTPUT_FORMAT)
+                    .help("What to generate\n(`json` writes the documentation model to docs.json, for other site generators and package registries to render.)")
+                    .value_parser(["html", "json"])
+                    .required(false)
+                    .default_value("html"),
+                )
                 .arg(Arg::new(ROC_FILE)
                     .help("The package's main .roc file")
                     .value_parser(value_parser!(PathBuf))
@@ -363,6 +709,20 @@ pub fn build_app() -> Command {
                     .value_parser(value_parser!(PathBuf))
                     .required(true)
             )
+            .arg(
+                Arg::new(FLAG_EXTERNS)
+                    .long(FLAG_EXTERNS)
+                    .help("Also write the functions every host has to provide, like roc_alloc and roc_panic, to the glue directory\n(`rust` writes roc_externs.rs, and `c` writes roc_externs.c and roc_externs.h.)")
+                    .value_parser(["rust", "c"])
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_NO_STD)
+                    .long(FLAG_NO_STD)
+                    .help("Make the Rust glue depend on only `core` and `alloc` by default, for hosts without `std`\n(This turns off the `std` feature of the generated roc_app crate.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file whose exposed types should be translated.")
@@ -382,11 +742,45 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
-                    .help("Choose a different target")
+                    .help(target_flag_help())
+                    .default_value(Into::<&'static str>::into(Target::default()))
+                    .value_parser(build_target_values_parser.clone())
+                    .required(false),
+            )
+        )
+        .subcommand(Command::new(CMD_PACKAGE)
+            .about("Bundle a package or platform into a .tar.br file that apps can depend on over HTTPS\n(For a platform, this also builds its host for this machine, and checks that it has a prebuilt host for every --target.)")
+            .arg(
+                Arg::new(FLAG_TARGET)
+                    .long(FLAG_TARGET)
+                    .help("A target the platform supports; pass it once per target\n(Ignored for packages, which don't have hosts.)")
                     .default_value(Into::<&'static str>::into(Target::default()))
                     .value_parser(build_target_values_parser.clone())
+                    .action(ArgAction::Append)
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_PREBUILT)
+                    .long(FLAG_PREBUILT)
+                    .help("Don't rebuild the host for this machine, just check that every target has one")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_BUNDLE)
+                    .long(FLAG_BUNDLE)
+                    .help("The kind of archive to create")
+                    .default_value(".tar.br")
+                    .value_parser([".tar", ".tar.gz", ".tar.br"])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The main.roc of the package or platform")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
         )
         .subcommand(Command::new(CMD_PREPROCESS_HOST)
             .about("Runs the surgical linker preprocessor to generate `.rh` and `.rm` files.")
@@ -399,7 +793,7 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
-                    .help("Choose a different target")
+                    .help(target_flag_help())
                     .default_value(Into::<&'static str>::into(Target::default()))
                     .value_parser(build_target_values_parser)
                     .required(false),
@@ -415,6 +809,11 @@ pub fn build_app() -> Command {
         .arg(flag_linker)
         .arg(flag_prebuilt)
         .arg(flag_fuzz)
+        .arg(flag_inline_threshold)
+        .arg(flag_no_alias_analysis)
+        .arg(flag_report_alias_analysis)
+        .arg(flag_verbose_layouts)
+        .arg(flag_locked)
         .arg(roc_file_to_run)
         .arg(args_for_app.trailing_var_arg(true))
 }
@@ -440,15 +839,241 @@ fn opt_level_from_flags(matches: &ArgMatches) -> OptLevel {
     }
 }
 
+/// Records the packages `root_module` loaded over HTTPS in its roc.lock, or with `locked`,
+/// checks that roc.lock already matches them. Returns whether that succeeded.
+///
+/// A roc.lock is only created for modules that use packages over HTTPS, unless `install` is set.
+pub fn update_lockfile(
+    root_module: &Path,
+    package_urls: &[String],
+    locked: bool,
+    install: bool,
+) -> io::Result<bool> {
+    let path = lockfile_path(root_module);
+    let loaded =
+        Lockfile::from_urls(package_urls.iter().map(String::as_str)).unwrap_or_else(|problem| {
+            internal_error!("The loader accepted an invalid package URL: {problem:?}")
+        });
+
+    let existing = match Lockfile::read(&path)? {
+        Some(Ok(lockfile)) => Some(lockfile),
+        Some(Err(problem)) if locked => {
+            eprintln!("{} is invalid: {problem}", path.display());
+
+            return Ok(false);
+        }
+        // It's generated, so we can just replace it
+        Some(Err(_)) | None => None,
+    };
+
+    let diff = existing.unwrap_or_default().diff(&loaded);
+
+    if diff.is_empty() && (path.exists() || !install) {
+        return Ok(true);
+    }
+
+    if locked {
+        eprintln!(
+            "{} doesn't match the packages that were loaded:\n",
+            path.display()
+        );
+
+        for url in diff.added {
+            eprintln!("    + {url}");
+        }
+
+        for url in diff.removed {
+            eprintln!("    - {url}");
+        }
+
+        eprintln!("\nRun `roc install` to update it.");
+
+        return Ok(false);
+    }
+
+    loaded.write(&path)?;
+
+    Ok(true)
+}
+
+/// Downloads the packages a module uses, as in `roc install`, and records them in its roc.lock.
+pub fn install(matches: &ArgMatches) -> io::Result<i32> {
+    use roc_packaging::cache;
+
+    let arena = Bump::new();
+    let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+    let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(*n),
+    };
+
+    // Loading the module downloads all of its packages
+    match check_file_rendered(
+        &arena,
+        roc_file_path.to_owned(),
+        false,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        threading,
+        OutputFormat::Human,
+        false,
+        None,
+        false,
+    ) {
+        Ok(CheckedFile {
+            problems: rendered,
+            package_urls,
+            total_time,
+            ..
+        }) => {
+            if !rendered.errors.is_empty() {
+                let problems = rendered.print();
+
+                problems.print_error_warning_count(total_time);
+
+                return Ok(problems.exit_code());
+            }
+
+            if !update_lockfile(
+                roc_file_path,
+                &package_urls,
+                matches.get_flag(FLAG_LOCKED),
+                true,
+            )? {
+                return Ok(1);
+            }
+
+            let count = package_urls.len();
+            let packages = if count == 1 { "package" } else { "packages" };
+
+            println!(
+                "Installed {count} {packages} from HTTPS, and recorded them in {LOCKFILE_NAME}."
+            );
+
+            Ok(0)
+        }
+        Err(problem) => handle_loading_problem(problem, OutputFormat::Human),
+    }
+}
+
+/// Prints the extended explanation for an error code, as in `roc explain E0107`.
+pub fn explain(code: &str) -> i32 {
+    const CYAN: &str = ANSI_STYLE_CODES.cyan;
+    const RESET: &str = ANSI_STYLE_CODES.reset;
+
+    match roc_reporting::error::code::lookup(code) {
+        Some(error_code) => {
+            println!("{CYAN}{}: {}{RESET}\n", error_code.code, error_code.title);
+            println!("{}", error_code.summary);
+
+            if let Some(explanation) = error_code.explanation {
+                println!("\n{}", explanation.trim_end());
+            }
+
+            0
+        }
+        None => {
+            eprintln!("\n{code} is not a Roc error code.\n\nError codes look like E0107; they're included in the output of `roc check --output-format json`.\n");
+
+            1
+        }
+    }
+}
+
+/// Prints the compiler's version, as in `roc version`. With `json`, it also says which target
+/// roc runs on and which targets it can build for, which is handy to paste into a bug report.
+pub fn print_version(json: bool) -> i32 {
+    if json {
+        let version = serde_json::json!({
+            "version": VERSION.trim(),
+            "host": target_lexicon::Triple::host().to_string(),
+            "targets": target_names().collect::<Vec<_>>(),
+        });
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&version).expect("a JSON value can always be serialized")
+        );
+    } else {
+        print!("roc {VERSION}");
+    }
+
+    0
+}
+
+/// Prints the [BuildInfo] that `roc build` embedded in the binary at `path`, as in
+/// `roc inspect-binary`.
+pub fn inspect_binary(path: &Path, json: bool) -> io::Result<i32> {
+    const YELLOW: &str = ANSI_STYLE_CODES.yellow;
+    const RESET: &str = ANSI_STYLE_CODES.reset;
+
+    let binary = std::fs::read(path)?;
+
+    let build_info = match BuildInfo::find_in(&binary) {
+        Some(build_info) => build_info,
+        None => {
+            eprintln!(
+                "\n{} has no Roc build info. Either it wasn't built by `roc build`, it was built by a compiler too old to embed it, or it was built with the Wasm dev backend.\n",
+                path.display()
+            );
+
+            return Ok(1);
+        }
+    };
+
+    if json {
+        let build_info = serde_json::json!({
+            "compiler": build_info.compiler_version,
+            "target": build_info.target_triple,
+            "flags": build_info.flags,
+        });
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&build_info)
+                .expect("a JSON value can always be serialized")
+        );
+    } else {
+        println!("compiler: {}", build_info.compiler_version);
+        println!("target: {}", build_info.target_triple);
+        println!("flags: {}", build_info.flags.join(" "));
+
+        if build_info.compiler_version != VERSION.trim() {
+            println!(
+                "\n{YELLOW}This is not the compiler that built it, which is {}.{RESET}",
+                VERSION.trim()
+            );
+        }
+    }
+
+    Ok(0)
+}
+
+/// Writes the given problems to `path` as a SARIF log (see `roc check --sarif`).
+pub fn write_sarif(path: &Path, diagnostics: &[Diagnostic]) -> io::Result<()> {
+    std::fs::write(path, sarif_log(diagnostics, VERSION))
+}
+
+pub fn output_format_from_flags(matches: &ArgMatches) -> OutputFormat {
+    // Not every subcommand that builds (e.g. `roc run`) has this flag.
+    matches
+        .try_get_one::<String>(FLAG_OUTPUT_FORMAT)
+        .ok()
+        .flatten()
+        .and_then(|name| OutputFormat::from_name(name))
+        .unwrap_or_default()
+}
+
 #[cfg(windows)]
 pub fn test(_matches: &ArgMatches, _target: Target) -> io::Result<i32> {
     todo!("running tests does not work on windows right now")
 }
 
+#[cfg(not(windows))]
 struct ModuleTestResults {
     module_id: ModuleId,
-    failed_count: usize,
-    passed_count: usize,
+    results: ExpectResults,
     tests_duration: Duration,
 }
 
@@ -457,11 +1082,21 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     use roc_build::program::report_problems_monomorphized;
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
     use roc_packaging::cache;
+    use roc_repl_expect::run::ExpectFilter;
 
     let start_time = Instant::now();
     let arena = Bump::new();
     let opt_level = opt_level_from_flags(matches);
 
+    let filter = ExpectFilter {
+        name: matches.get_one::<String>(FLAG_FILTER).cloned(),
+        modules: matches
+            .get_many::<String>(FLAG_MODULE)
+            .map(|modules| modules.cloned().collect())
+            .unwrap_or_default(),
+        line: matches.get_one::<u32>(FLAG_LINE).copied(),
+    };
+
     let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
         None => Threading::AllAvailable,
         Some(0) => user_error!("cannot build with at most 0 threads"),
@@ -504,7 +1139,13 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading,
-        exec_mode: ExecutionMode::Test,
+        exec_mode: if matches.get_flag(FLAG_DOCTESTS) {
+            ExecutionMode::DocTest
+        } else {
+            ExecutionMode::Test
+        },
+        reproducible: false,
+        coverage: matches.get_flag(FLAG_COVERAGE),
     };
     let load_result = roc_load::load_and_monomorphize(
         arena,
@@ -516,13 +1157,19 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     let mut loaded = match load_result {
         Ok(loaded) => loaded,
         Err(LoadMonomorphizedError::LoadingProblem(problem)) => {
-            return handle_loading_problem(problem);
+            return handle_loading_problem(problem, OutputFormat::Human);
         }
         Err(LoadMonomorphizedError::ErrorModule(module)) => {
-            return handle_error_module(module, start_time.elapsed(), path.as_os_str(), false);
+            return handle_error_module(
+                module,
+                start_time.elapsed(),
+                path.as_os_str(),
+                false,
+                OutputFormat::Human,
+            );
         }
     };
-    let problems = report_problems_monomorphized(&mut loaded);
+    let problems = report_problems_monomorphized(&mut loaded, OutputFormat::Human);
 
     let mut expectations = std::mem::take(&mut loaded.expectations);
 
@@ -536,9 +1183,15 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             loaded,
             opt_level,
             LlvmBackendMode::CliTest,
+            matches.get_flag(FLAG_LEAK_CHECK),
         )
         .unwrap();
 
+    roc_repl_expect::snapshot::configure(
+        path.with_file_name(roc_reporting::error::expect::SNAPSHOT_DIR),
+        matches.get_flag(FLAG_UPDATE_SNAPSHOTS),
+    );
+
     // Print warnings before running tests.
     {
         debug_assert_eq!(
@@ -557,18 +1210,39 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
 
     let mut writer = std::io::stdout();
 
-    let mut total_failed_count = 0;
-    let mut total_passed_count = 0;
+    let mut total = ExpectResults::default();
 
     let mut results_by_module = Vec::new();
     let global_layout_interner = layout_interner.into_global();
 
+    // Each expect runs in its own process, so run as many at once as we'd use threads to load
+    let jobs = match std::thread::available_parallelism().map(|v| v.get()) {
+        Err(_) => 1,
+        Ok(reported) => match threading {
+            Threading::Single => 1,
+            Threading::AllAvailable => reported,
+            Threading::AtMost(at_most) => Ord::min(reported, at_most),
+        },
+    };
+
     let compilation_duration = start_time.elapsed();
 
-    for (module_id, expects) in expects_by_module.into_iter() {
+    for (module_id, mut expects) in expects_by_module.into_iter() {
+        if !filter.is_empty() {
+            let (path, source) = sources.get(&module_id).unwrap();
+            filter.apply(interns, module_id, path, source, &mut expects);
+        }
+
+        // With --coverage, modules without expects are still here, for their coverage
+        if expects.is_empty() {
+            continue;
+        }
+
         let test_start_time = Instant::now();
 
-        let (failed_count, passed_count) = roc_repl_expect::run::run_toplevel_expects(
+        let properties = expects.properties.to_vec();
+
+        let mut results = roc_repl_expect::run::run_toplevel_expects_parallel(
             &mut writer,
             roc_reporting::report::RenderTarget::ColorTerminal,
             arena,
@@ -577,27 +1251,53 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             &dyn_lib,
             &mut expectations,
             expects,
+            jobs,
         )
         .unwrap();
 
+        if matches.get_flag(FLAG_FUZZ) {
+            let property_results = roc_repl_expect::fuzz::run_properties(
+                &mut writer,
+                roc_reporting::report::RenderTarget::ColorTerminal,
+                arena,
+                interns,
+                &global_layout_interner,
+                &dyn_lib,
+                &mut expectations,
+                &properties,
+                roc_repl_expect::fuzz::DEFAULT_FUZZ_RUNS,
+            )
+            .unwrap();
+
+            results.failed += property_results.failed;
+            results.passed += property_results.passed;
+            results.skipped += property_results.skipped;
+            results.xfailed += property_results.xfailed;
+        }
+
         let tests_duration = test_start_time.elapsed();
 
         results_by_module.push(ModuleTestResults {
             module_id,
-            failed_count,
-            passed_count,
+            results,
             tests_duration,
         });
 
-        total_failed_count += failed_count;
-        total_passed_count += passed_count;
+        total.failed += results.failed;
+        total.passed += results.passed;
+        total.skipped += results.skipped;
+        total.xfailed += results.xfailed;
     }
 
     let total_duration = start_time.elapsed();
 
-    if total_failed_count == 0 && total_passed_count == 0 {
+    if total == ExpectResults::default() {
         // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+        if filter.is_empty() {
+            println!("No expectations were found.");
+        } else {
+            println!("No expectations matched the given filters.");
+        }
 
         // If no tests ran, treat that as an error. This is perhaps
         // briefly annoying at the very beginning of a project when
@@ -612,27 +1312,34 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
                 print_test_results(module_test_results, &sources);
             }
         } else {
-            let test_summary_str =
-                test_summary(total_failed_count, total_passed_count, total_duration);
+            let test_summary_str = test_summary(total, total_duration);
             println!("{test_summary_str}");
         }
 
-        Ok((total_failed_count > 0) as i32)
+        if matches.get_flag(FLAG_COVERAGE) {
+            print_coverage(
+                roc_repl_expect::coverage::take_coverage(),
+                &sources,
+                interns,
+            );
+        }
+
+        Ok((total.failed > 0) as i32)
     }
 }
 
+#[cfg(not(windows))]
 fn print_test_results(
     module_test_results: ModuleTestResults,
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
 ) {
     let ModuleTestResults {
         module_id,
-        failed_count,
-        passed_count,
+        results,
         tests_duration,
     } = module_test_results;
 
-    let test_summary_str = test_summary(failed_count, passed_count, tests_duration);
+    let test_summary_str = test_summary(results, tests_duration);
 
     let (module_path, _) = sources.get(&module_id).unwrap();
     let module_name = module_path.file_name().unwrap().to_str().unwrap();
@@ -640,17 +1347,90 @@ fn print_test_results(
     println!("\n{module_name}:\n    {test_summary_str}",);
 }
 
-fn test_summary(failed_count: usize, passed_count: usize, tests_duration: Duration) -> String {
-    let failed_color = if failed_count == 0 {
+#[cfg(not(windows))]
+fn print_coverage(
+    mut coverage: Vec<roc_repl_expect::coverage::ModuleCoverage>,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &roc_module::symbol::Interns,
+) {
+    use roc_mono::coverage::CoverageSite;
+    use roc_region::all::LineInfo;
+
+    coverage.sort_by(|a, b| sources[&a.module_id].0.cmp(&sources[&b.module_id].0));
+
+    println!("\nCoverage of top-level defs and `when` branches:");
+
+    for module in coverage {
+        let (module_path, source) = &sources[&module.module_id];
+        let module_name = module_path.file_name().unwrap().to_str().unwrap();
+        let line_info = LineInfo::new(source);
+
+        let percent_color = if module.unreached.is_empty() {
+            ANSI_STYLE_CODES.green
+        } else {
+            ANSI_STYLE_CODES.yellow
+        };
+        let reset = ANSI_STYLE_CODES.reset;
+
+        println!(
+            "\n{module_name}: {percent_color}{:.1}%{reset} ({} of {} reached)",
+            module.percent(),
+            module.reached,
+            module.total
+        );
+
+        for (site, region) in module.unreached {
+            let region = line_info.convert_region(region);
+            let start = format!("{}:{}", region.start.line + 1, region.start.column + 1);
+
+            match site {
+                CoverageSite::Def(symbol) => {
+                    println!("    {start}: `{}` never ran", symbol.as_str(interns));
+                }
+                CoverageSite::Branch { .. } => {
+                    let end = format!("{}:{}", region.end.line + 1, region.end.column + 1);
+
+                    println!("    {start}-{end}: `when` branch never ran");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn test_summary(results: ExpectResults, tests_duration: Duration) -> String {
+    let ExpectResults {
+        failed,
+        passed,
+        skipped,
+        xfailed,
+    } = results;
+
+    let failed_color = if failed == 0 {
         ANSI_STYLE_CODES.green
     } else {
         ANSI_STYLE_CODES.red
     };
     let passed_color = ANSI_STYLE_CODES.green;
+    let marked_color = ANSI_STYLE_CODES.yellow;
     let reset = ANSI_STYLE_CODES.reset;
 
+    // Only mention skipped and expected failures when there are some, so the usual
+    // summary stays the same.
+    let mut marked = String::new();
+
+    if skipped > 0 {
+        marked.push_str(&format!(", {marked_color}{skipped}{reset} skipped"));
+    }
+
+    if xfailed > 0 {
+        marked.push_str(&format!(
+            ", {marked_color}{xfailed}{reset} failed as expected"
+        ));
+    }
+
     format!(
-        "{failed_color}{failed_count}{reset} failed and {passed_color}{passed_count}{reset} passed in {} ms.",
+        "{failed_color}{failed}{reset} failed and {passed_color}{passed}{reset} passed{marked} in {} ms.",
         tests_duration.as_millis()
     )
 }
@@ -794,12 +1574,97 @@ pub fn build(
         CodeGenBackend::Llvm(backend_mode)
     };
 
-    let emit_llvm_ir = matches.get_flag(FLAG_EMIT_LLVM_IR);
-    if emit_llvm_ir && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
+    // Only `roc build` has --emit and --emit-dir
+    let emit_kinds: Vec<&String> = matches
+        .try_get_many::<String>(FLAG_EMIT)
+        .ok()
+        .flatten()
+        .map(|kinds| kinds.collect())
+        .unwrap_or_default();
+    let emit_has = |kind: &str| emit_kinds.iter().any(|k| k.as_str() == kind);
+
+    let emit = EmitArtifacts {
+        llvm_ir: matches.get_flag(FLAG_EMIT_LLVM_IR) || emit_has("llvm-ir"),
+        asm: emit_has("asm"),
+        obj: emit_has("obj"),
+        dir: matches
+            .try_get_one::<PathBuf>(FLAG_EMIT_DIR)
+            .ok()
+            .flatten()
+            .cloned(),
+    };
+
+    if emit.llvm_ir && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
         user_error!("Cannot emit llvm ir while using a dev backend.");
     }
 
+    if emit.asm && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
+        user_error!("Cannot emit assembly while using a dev backend.");
+    }
+
+    if let Some(dir) = &emit.dir {
+        if let Err(err) = std::fs::create_dir_all(dir) {
+            user_error!("Could not create the --emit-dir {}: {}", dir.display(), err);
+        }
+    }
+
+    // Only `roc build` has this flag
+    let emit_debug_locations = matches
+        .try_get_one::<bool>(FLAG_DEBUG_INFO)
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+
+    // The dev backend only knows how to write DWARF into ELF objects
+    if emit_debug_locations
+        && matches!(code_gen_backend, CodeGenBackend::Assembly(_))
+        && matches!(
+            target.operating_system(),
+            OperatingSystem::Mac | OperatingSystem::Windows
+        )
+    {
+        user_error!(
+            "--{FLAG_DEBUG_INFO} with --{FLAG_DEV} only works for Linux targets, because the dev backend can't write debug info into {} objects yet. Leave out --{FLAG_DEV} to get debug info from the LLVM backend instead.",
+            if target.operating_system() == OperatingSystem::Mac { "Mach-O" } else { "COFF" }
+        );
+    }
+
+    // Only `roc build` has these flags
+    let check_reproducible = matches
+        .try_get_one::<bool>(FLAG_CHECK_REPRODUCIBLE)
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+    let reproducible = check_reproducible
+        || matches
+            .try_get_one::<bool>(FLAG_REPRODUCIBLE)
+            .ok()
+            .flatten()
+            .copied()
+            .unwrap_or(false);
+
+    if reproducible {
+        // Linkers and archivers write the current time into what they make unless told not to
+        env::set_var("SOURCE_DATE_EPOCH", "0");
+        env::set_var("ZERO_AR_DATE", "1");
+    }
+
+    // Only `roc run` has this flag
+    let profile_svg = matches
+        .try_get_one::<PathBuf>(FLAG_PROFILE)
+        .ok()
+        .flatten()
+        .cloned();
+
+    if profile_svg.is_some() && !cfg!(target_os = "linux") {
+        user_error!("--profile samples the program with `perf`, which is only available on Linux.");
+    }
+
     let emit_debug_info = matches.get_flag(FLAG_PROFILING)
+        || emit_debug_locations
+        || profile_svg.is_some()
         || matches!(opt_level, OptLevel::Development | OptLevel::Normal);
     let emit_timings = matches.get_flag(FLAG_TIME);
 
@@ -812,33 +1677,55 @@ pub fn build(
 
     let wasm_dev_backend = matches!(code_gen_backend, CodeGenBackend::Wasm);
 
-    let linking_strategy = if wasm_dev_backend {
-        LinkingStrategy::Additive
-    } else if !roc_linker::supported(link_type, target)
-        || matches.get_one::<String>(FLAG_LINKER).map(|s| s.as_str()) == Some("legacy")
-    {
-        LinkingStrategy::Legacy
-    } else {
-        LinkingStrategy::Surgical
-    };
+    let build_target = BuildTarget::new(target);
+
+    let linking_strategy = build_target.linking_strategy(
+        link_type,
+        wasm_dev_backend,
+        matches.get_one::<String>(FLAG_LINKER).map(|s| s.as_str()),
+    );
 
-    let prebuilt = {
-        let cross_compile = target != Target::default();
-        let targeting_wasm = matches!(target.architecture(), Architecture::Wasm32);
+    if link_type == LinkType::StaticExecutable {
+        if target.operating_system() != OperatingSystem::Linux {
+            user_error!("--static only supports Linux targets, not {target}.");
+        }
 
-        matches.get_flag(FLAG_PREBUILT) ||
-            // When compiling for a different target, assume a prebuilt platform.
-            // Otherwise compilation would most likely fail because many toolchains
-            // assume you're compiling for the current machine. We make an exception
-            // for Wasm, because cross-compiling is the norm in that case.
-            (cross_compile && !targeting_wasm)
-    };
+        if linking_strategy != LinkingStrategy::Legacy {
+            user_error!(
+                "--static needs the legacy linker, so it can't be used with --linker=surgical."
+            );
+        }
+    }
+
+    // We only know how to rebuild a platform for the system's libc, so a static build always
+    // uses the platform's prebuilt static archive.
+    let prebuilt = matches.get_flag(FLAG_PREBUILT)
+        || build_target.assumes_prebuilt_platform()
+        || link_type == LinkType::StaticExecutable;
 
     let fuzz = matches.get_flag(FLAG_FUZZ);
+
+    let alias_analysis = AliasAnalysisOptions {
+        disabled: matches.get_flag(FLAG_NO_ALIAS_ANALYSIS),
+        report: matches.get_flag(FLAG_REPORT_ALIAS_ANALYSIS),
+    };
+    if (alias_analysis.disabled || alias_analysis.report)
+        && !matches!(code_gen_backend, CodeGenBackend::Llvm(_))
+    {
+        user_error!("Alias analysis is only used by the LLVM backend, not by a dev backend.");
+    }
+    let inline_threshold = matches
+        .get_one::<u32>(FLAG_INLINE_THRESHOLD)
+        .copied()
+        .unwrap_or(DEFAULT_INLINE_THRESHOLD);
     if fuzz && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
         user_error!("Cannot instrument binary for fuzzing while using a dev backend.");
     }
 
+    if target == Target::Wasm32Wasi && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
+        user_error!("The wasm32-wasi target is only supported by the LLVM backend, so it can't be used with --dev.");
+    }
+
     let wasm_dev_stack_bytes: Option<u32> = matches
         .try_get_one::<u32>(FLAG_WASM_STACK_SIZE_KB)
         .ok()
@@ -854,11 +1741,30 @@ pub fn build(
         backend: code_gen_backend,
         opt_level,
         emit_debug_info,
-        emit_llvm_ir,
+        emit_debug_locations,
+        emit,
         fuzz,
+        frame_pointers: profile_svg.is_some(),
+        inline_threshold,
+        alias_analysis,
+        verbose_layouts: matches.get_flag(FLAG_VERBOSE_LAYOUTS),
+        reproducible,
     };
 
-    let load_config = standard_load_config(target, build_ordering, threading);
+    let output_format = output_format_from_flags(matches);
+
+    let mut load_config = standard_load_config(target, build_ordering, threading);
+
+    if let OutputFormat::Json = output_format {
+        // Problems the loader reports as pre-rendered text end up in a JSON string,
+        // so they must not contain escape codes.
+        load_config.render = RenderTarget::Generic;
+    }
+
+    load_config.reproducible = reproducible;
+
+    let rebuild_options =
+        check_reproducible.then(|| (code_gen_options.clone(), load_config.clone()));
 
     let res_binary_path = build_file(
         &arena,
@@ -872,18 +1778,36 @@ pub fn build(
         wasm_dev_stack_bytes,
         roc_cache_dir,
         load_config,
+        output_format,
         out_path,
     );
 
     match res_binary_path {
         Ok(BuiltFile {
             binary_path,
+            platform_path,
+            package_urls,
             problems,
             total_time,
             expect_metadata,
         }) => {
+            if !update_lockfile(path, &package_urls, matches.get_flag(FLAG_LOCKED), false)? {
+                return Ok(1);
+            }
+
             match config {
                 BuildOnly => {
+                    // A library gets a C header declaring its entry points, so C programs
+                    // (and anything with a C FFI) can call into it.
+                    let header_path = (link_type == LinkType::Dylib && !problems.fatally_errored)
+                        .then(|| {
+                            let header_path = binary_path.with_extension("h");
+
+                            roc_glue::c_header::write_c_header(&platform_path, &header_path, target)
+                                .map(|()| header_path)
+                        })
+                        .transpose()?;
+
                     // If possible, report the generated executable name relative to the current dir.
                     let generated_filename = binary_path
                         .strip_prefix(env::current_dir().unwrap())
@@ -895,8 +1819,64 @@ pub fn build(
                     // since the process is about to exit anyway.
                     // std::mem::forget(arena);
 
-                    problems.print_error_warning_count(total_time);
-                    println!(" while successfully building:\n\n    {generated_filename}");
+                    output_format.print_human(format_args!(
+                        "{}\n while successfully building:\n\n    {generated_filename}\n",
+                        problems.error_warning_count(total_time)
+                    ));
+
+                    if let Some(header_path) = header_path {
+                        output_format.print_human(format_args!("    {}\n", header_path.display()));
+                    }
+
+                    if let Some((code_gen_options, load_config)) = rebuild_options {
+                        if problems.fatally_errored {
+                            return Ok(problems.exit_code());
+                        }
+
+                        // Build again somewhere else, so nothing is reused from the first build
+                        let rebuild_dir = tempfile::tempdir()?;
+                        let rebuild_path =
+                            rebuild_dir.path().join(binary_path.file_name().unwrap());
+                        let rebuild_arena = Bump::new();
+                        let rebuilt = build_file(
+                            &rebuild_arena,
+                            target,
+                            path.to_owned(),
+                            code_gen_options,
+                            false,
+                            link_type,
+                            linking_strategy,
+                            prebuilt,
+                            wasm_dev_stack_bytes,
+                            roc_cache_dir,
+                            load_config,
+                            output_format,
+                            Some(&rebuild_path),
+                        );
+
+                        if rebuilt.is_err() {
+                            user_error!("The second build for --check-reproducible failed, but the first one didn't.");
+                        }
+
+                        let first = std::fs::read(&binary_path)?;
+                        let second = std::fs::read(&rebuild_path)?;
+
+                        match first_difference(&first, &second) {
+                            None => output_format.print_human(format_args!(
+                                "\nBuilding it again gave the same {} bytes.\n",
+                                first.len()
+                            )),
+                            Some(offset) => {
+                                eprintln!(
+                                    "\nBuilding it again gave a different binary: the two builds first differ at byte {offset} (of {} and {} bytes).",
+                                    first.len(),
+                                    second.len()
+                                );
+
+                                return Ok(1);
+                            }
+                        }
+                    }
 
                     // Return a nonzero exit code if there were problems
                     Ok(problems.exit_code())
@@ -925,11 +1905,28 @@ pub fn build(
                         .unwrap_or_default()
                         .map(|s| s.as_os_str());
 
+                    if let Some(svg_path) = profile_svg {
+                        return profile::profile(
+                            &binary_path,
+                            args,
+                            &expect_metadata.interns,
+                            &svg_path,
+                        );
+                    }
+
                     // don't waste time deallocating; the process ends anyway
                     // ManuallyDrop will leak the bytes because we don't drop manually
                     let bytes = &ManuallyDrop::new(std::fs::read(&binary_path).unwrap());
 
-                    roc_run(&arena, opt_level, target, args, bytes, expect_metadata)
+                    roc_run(
+                        &arena,
+                        opt_level,
+                        target,
+                        &binary_path,
+                        args,
+                        bytes,
+                        expect_metadata,
+                    )
                 }
                 BuildAndRunIfNoErrors => {
                     if problems.fatally_errored {
@@ -964,25 +1961,52 @@ pub fn build(
                     // ManuallyDrop will leak the bytes because we don't drop manually
                     let bytes = &ManuallyDrop::new(std::fs::read(&binary_path).unwrap());
 
-                    roc_run(&arena, opt_level, target, args, bytes, expect_metadata)
+                    roc_run(
+                        &arena,
+                        opt_level,
+                        target,
+                        &binary_path,
+                        args,
+                        bytes,
+                        expect_metadata,
+                    )
                 }
             }
         }
         Err(BuildFileError::ErrorModule { module, total_time }) => {
-            handle_error_module(module, total_time, path.as_os_str(), true)
+            handle_error_module(module, total_time, path.as_os_str(), true, output_format)
         }
-        Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
+        Err(BuildFileError::LoadingProblem(problem)) => {
+            handle_loading_problem(problem, output_format)
+        }
+    }
+}
+
+/// The offset of the first byte at which `a` and `b` differ, if they differ at all
+fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    match a.iter().zip(b).position(|(x, y)| x != y) {
+        Some(offset) => Some(offset),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
     }
 }
 
+/// Runs the binary that was built at `binary_path`, from `binary_bytes`.
+///
+/// Native and Wasm apps are started the same way (see [roc_mono::app_name]): `argv[0]` is
+/// `binary_path`, just like when the binary is run directly, and the rest of `argv` are `args`.
+/// The app gets this process's environment.
 fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     arena: &Bump,
     opt_level: OptLevel,
     target: Target,
+    binary_path: &Path,
     args: I,
     binary_bytes: &[u8],
     expect_metadata: ExpectMetadata,
 ) -> io::Result<i32> {
+    let program_name = binary_path.as_os_str();
+
     match target.architecture() {
         Architecture::Wasm32 => {
             let executable = roc_run_executable_file_path(binary_bytes)?;
@@ -998,7 +2022,9 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
 
                 run_wasm(
                     generated_filename,
-                    args.into_iter().map(|os_str| os_str.as_bytes()),
+                    std::iter::once(program_name)
+                        .chain(args)
+                        .map(|os_str| os_str.as_bytes()),
                 );
             }
 
@@ -1006,7 +2032,7 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
             {
                 run_wasm(
                     generated_filename,
-                    args.into_iter().map(|os_str| {
+                    std::iter::once(program_name).chain(args).map(|os_str| {
                         os_str.to_str().expect(
                             "Roc does not currently support passing non-UTF8 arguments to Wasm.",
                         )
@@ -1016,7 +2042,14 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
 
             Ok(0)
         }
-        _ => roc_run_native(arena, opt_level, args, binary_bytes, expect_metadata),
+        _ => roc_run_native(
+            arena,
+            opt_level,
+            program_name,
+            args,
+            binary_bytes,
+            expect_metadata,
+        ),
     }
 }
 
@@ -1033,7 +2066,7 @@ fn os_str_as_utf8_bytes(os_str: &OsStr) -> &[u8] {
 
 fn make_argv_envp<'a, I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     arena: &'a Bump,
-    executable: &ExecutableFile,
+    program_name: &OsStr,
     args: I,
 ) -> (
     bumpalo::collections::Vec<'a, CString>,
@@ -1041,20 +2074,23 @@ fn make_argv_envp<'a, I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
 ) {
     use bumpalo::collections::CollectIn;
 
-    let path = executable.as_path();
-    let path_cstring = CString::new(os_str_as_utf8_bytes(path.as_os_str())).unwrap();
+    let program_name_cstring = CString::new(os_str_as_utf8_bytes(program_name)).unwrap();
 
     // argv is an array of pointers to strings passed to the new program
     // as its command-line arguments.  By convention, the first of these
     // strings (i.e., argv[0]) should contain the filename associated
     // with the file being executed.  The argv array must be terminated
     // by a NULL pointer. (Thus, in the new program, argv[argc] will be NULL.)
+    //
+    // The file we execute is a temporary copy (or an in-memory file), so argv[0]
+    // is the path the binary was built at instead, as if it had been run directly.
     let it = args
         .into_iter()
         .map(|x| CString::new(os_str_as_utf8_bytes(x.as_ref())).unwrap());
 
-    let argv_cstrings: bumpalo::collections::Vec<CString> =
-        std::iter::once(path_cstring).chain(it).collect_in(arena);
+    let argv_cstrings: bumpalo::collections::Vec<CString> = std::iter::once(program_name_cstring)
+        .chain(it)
+        .collect_in(arena);
 
     // envp is an array of pointers to strings, conventionally of the
     // form key=value, which are passed as the environment of the new
@@ -1081,6 +2117,7 @@ fn make_argv_envp<'a, I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
 fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     arena: &Bump,
     opt_level: OptLevel,
+    program_name: &OsStr,
     args: I,
     binary_bytes: &[u8],
     expect_metadata: ExpectMetadata,
@@ -1089,7 +2126,7 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
 
     unsafe {
         let executable = roc_run_executable_file_path(binary_bytes)?;
-        let (argv_cstrings, envp_cstrings) = make_argv_envp(arena, &executable, args);
+        let (argv_cstrings, envp_cstrings) = make_argv_envp(arena, program_name, args);
 
         let argv: bumpalo::collections::Vec<*const c_char> = argv_cstrings
             .iter()
@@ -1336,6 +2373,7 @@ fn roc_run_executable_file_path(binary_bytes: &[u8]) -> std::io::Result<Executab
 fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     arena: &Bump, // This should be passed an owned value, not a reference, so we can usefully mem::forget it!
     opt_level: OptLevel,
+    program_name: &OsStr,
     args: I,
     binary_bytes: &[u8],
     _expect_metadata: ExpectMetadata,
@@ -1346,7 +2384,7 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
         let executable = roc_run_executable_file_path(binary_bytes)?;
 
         // TODO forward the arguments
-        let (argv_cstrings, envp_cstrings) = make_argv_envp(&arena, &executable, args);
+        let (argv_cstrings, envp_cstrings) = make_argv_envp(&arena, program_name, args);
 
         let argv: bumpalo::collections::Vec<*const c_char> = argv_cstrings
             .iter()
@@ -1388,7 +2426,19 @@ fn run_wasm<I: Iterator<Item = S>, S: AsRef<[u8]>>(wasm_path: &std::path::Path,
         arg_copy.extend_from_slice(arg.as_ref());
         argv.push(arg_copy.into_bump_slice());
     }
-    let import_dispatcher = DefaultImportDispatcher::new(&argv);
+
+    // Like a native app, a Wasm app gets the environment `roc` was run in
+    let mut envp = Vec::<&[u8]>::new_in(&arena);
+    for (key, value) in std::env::vars_os() {
+        let mut var = Vec::<u8>::new_in(&arena);
+        var.extend_from_slice(os_str_as_utf8_bytes(&key));
+        var.push(b'=');
+        var.extend_from_slice(os_str_as_utf8_bytes(&value));
+        envp.push(var.into_bump_slice());
+    }
+
+    let mut import_dispatcher = DefaultImportDispatcher::new(&argv);
+    import_dispatcher.wasi.env = &envp;
 
     let mut instance = Instance::from_bytes(&arena, &bytes, import_dispatcher, false).unwrap();
 
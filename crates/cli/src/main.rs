@@ -1,20 +1,30 @@
 //! The `roc` binary that brings together all functionality in the Roc toolset.
 use bumpalo::Bump;
 use roc_build::link::LinkType;
-use roc_build::program::{check_file, CodeGenBackend};
+use roc_build::program::{
+    check_file_rendered, handle_loading_problem, CheckedFile, CodeGenBackend,
+};
 use roc_cli::{
-    build_app, format_files, format_src, test, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK,
-    CMD_DEV, CMD_DOCS, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_PREPROCESS_HOST, CMD_REPL,
-    CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_LIB,
-    FLAG_NO_LINK, FLAG_OUTPUT, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME, GLUE_DIR,
-    GLUE_SPEC, ROC_FILE,
+    build_app, check_watch, dev_hot_reload, explain, format_files, format_src, inspect_binary,
+    install, output_format_from_flags, package, print_version, test, unified_diff, write_sarif,
+    BuildConfig, FormatMode, BINARY_FILE, CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DOCS, CMD_EXPLAIN,
+    CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_INSPECT_BINARY, CMD_INSTALL, CMD_PACKAGE,
+    CMD_PREPROCESS_HOST, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, ERROR_CODE,
+    FLAG_CHECK, FLAG_DEV, FLAG_EXPLAIN_TYPE, FLAG_EXTERNS, FLAG_HOT, FLAG_JSON, FLAG_LIB,
+    FLAG_NO_LINK, FLAG_NO_STD, FLAG_OUTPUT, FLAG_OUTPUT_FORMAT, FLAG_SARIF, FLAG_STATIC,
+    FLAG_STDIN, FLAG_STDOUT, FLAG_TAILCALLS, FLAG_TARGET, FLAG_TIME, FLAG_TIMINGS, FLAG_VERIFY,
+    FLAG_WATCH, GLUE_DIR, GLUE_SPEC, ROC_FILE,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, generate_docs_json};
 use roc_error_macros::user_error;
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
+use roc_glue::externs::HostLanguage;
 use roc_load::{FunctionKind, LoadingProblem, Threading};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_region::all::LineColumn;
+use roc_reporting::cli::{Diagnostic, OutputFormat};
+use roc_reporting::report::strip_colors;
 use roc_target::Target;
 use std::fs::{self, FileType};
 use std::io::{self, Read, Write};
@@ -22,9 +32,6 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use target_lexicon::Triple;
 
-#[macro_use]
-extern crate const_format;
-
 #[global_allocator]
 static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
@@ -85,7 +92,13 @@ fn main() -> io::Result<()> {
             }
         }
         Some((CMD_DEV, matches)) => {
-            if matches.contains_id(ROC_FILE) {
+            if matches.contains_id(ROC_FILE) && matches.get_flag(FLAG_HOT) {
+                dev_hot_reload(
+                    matches,
+                    Triple::host().into(),
+                    RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+                )
+            } else if matches.contains_id(ROC_FILE) {
                 build(
                     matches,
                     &subcommands,
@@ -112,8 +125,24 @@ fn main() -> io::Result<()> {
                 false => CodeGenBackend::Llvm(LlvmBackendMode::BinaryGlue),
             };
 
+            let externs = matches
+                .get_one::<String>(FLAG_EXTERNS)
+                .and_then(|s| HostLanguage::from_str(s).ok());
+
             if !output_path.exists() || output_path.is_dir() {
-                roc_glue::generate(input_path, output_path, spec_path, backend)
+                let exit_code = roc_glue::generate(input_path, output_path, spec_path, backend)?;
+
+                if exit_code == 0 {
+                    if matches.get_flag(FLAG_NO_STD) {
+                        roc_glue::no_std::disable_std(output_path)?;
+                    }
+
+                    if let Some(language) = externs {
+                        roc_glue::externs::write_externs(output_path, language)?;
+                    }
+                }
+
+                Ok(exit_code)
             } else {
                 eprintln!("`roc glue` must be given a directory to output into, because the glue might generate multiple files.");
 
@@ -172,6 +201,7 @@ fn main() -> io::Result<()> {
                 (true, false) => LinkType::Dylib,
                 (true, true) => user_error!("build can only be one of `--lib` or `--no-link`"),
                 (false, true) => LinkType::None,
+                (false, false) if matches.get_flag(FLAG_STATIC) => LinkType::StaticExecutable,
                 (false, false) => LinkType::Executable,
             };
             let out_path = matches
@@ -188,6 +218,8 @@ fn main() -> io::Result<()> {
                 link_type,
             )?)
         }
+        Some((CMD_INSTALL, matches)) => install(matches),
+        Some((CMD_PACKAGE, matches)) => package(matches),
         Some((CMD_CHECK, matches)) => {
             let arena = Bump::new();
 
@@ -200,22 +232,66 @@ fn main() -> io::Result<()> {
                 Some(n) => Threading::AtMost(*n),
             };
 
-            match check_file(
+            if matches.get_flag(FLAG_WATCH) {
+                check_watch(roc_file_path, emit_timings, threading);
+            }
+
+            let output_format = output_format_from_flags(matches);
+
+            let sarif_path = matches.get_one::<PathBuf>(FLAG_SARIF);
+
+            match check_file_rendered(
                 &arena,
                 roc_file_path.to_owned(),
                 emit_timings,
                 RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
                 threading,
+                output_format,
+                matches.get_flag(FLAG_TAILCALLS),
+                matches.get_one::<LineColumn>(FLAG_EXPLAIN_TYPE).copied(),
+                matches.get_flag(FLAG_TIMINGS),
             ) {
-                Ok((problems, total_time)) => {
-                    problems.print_error_warning_count(total_time);
+                Ok(CheckedFile {
+                    problems: rendered,
+                    total_time,
+                    tail_calls,
+                    explained_type,
+                    timings_table,
+                    ..
+                }) => {
+                    if let Some(tail_calls) = tail_calls {
+                        output_format.print_human(tail_calls);
+                    }
+
+                    if let Some(explained_type) = explained_type {
+                        output_format.print_human(explained_type);
+                    }
+
+                    if let Some(timings_table) = timings_table {
+                        output_format.print_human(timings_table);
+                    }
+
+                    let problems = rendered.print();
+
+                    if let Some(sarif_path) = sarif_path {
+                        write_sarif(sarif_path, &rendered.diagnostics)?;
+                    }
+
+                    if let OutputFormat::Human = output_format {
+                        problems.print_error_warning_count(total_time);
+                    }
+
                     Ok(problems.exit_code())
                 }
 
                 Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{report}");
+                    if let Some(sarif_path) = sarif_path {
+                        let diagnostic = Diagnostic::from_formatted_report(&strip_colors(&report));
+
+                        write_sarif(sarif_path, &[diagnostic])?;
+                    }
 
-                    Ok(1)
+                    handle_loading_problem(LoadingProblem::FormattedReport(report), output_format)
                 }
                 Err(other) => {
                     panic!("build_file failed with error:\n{other:?}");
@@ -227,7 +303,13 @@ fn main() -> io::Result<()> {
             let root_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
             let out_dir = matches.get_one::<OsString>(FLAG_OUTPUT).unwrap();
 
-            generate_docs_html(root_path.to_owned(), out_dir.as_ref());
+            match matches
+                .get_one::<String>(FLAG_OUTPUT_FORMAT)
+                .map(String::as_str)
+            {
+                Some("json") => generate_docs_json(root_path.to_owned(), out_dir.as_ref()),
+                _ => generate_docs_html(root_path.to_owned(), out_dir.as_ref()),
+            }
 
             Ok(0)
         }
@@ -236,6 +318,8 @@ fn main() -> io::Result<()> {
             let to_stdout = matches.get_flag(FLAG_STDOUT);
             let format_mode = if to_stdout {
                 FormatMode::WriteToStdout
+            } else if matches.get_flag(FLAG_VERIFY) {
+                FormatMode::Verify
             } else {
                 match matches.get_flag(FLAG_CHECK) {
                     true => FormatMode::CheckOnly,
@@ -299,10 +383,13 @@ fn main() -> io::Result<()> {
                         match format_mode {
                             FormatMode::CheckOnly => {
                                 if src == formatted_src {
+                                    0
+                                } else {
+                                    let diff = unified_diff("<stdin>", src, &formatted_src);
+
+                                    std::io::stdout().lock().write_all(diff.as_bytes()).unwrap();
                                     eprintln!("One or more files need to be reformatted.");
                                     1
-                                } else {
-                                    0
                                 }
                             }
                             FormatMode::WriteToStdout => {
@@ -313,9 +400,10 @@ fn main() -> io::Result<()> {
 
                                 0
                             }
-                            FormatMode::WriteToFile => {
+                            FormatMode::WriteToFile | FormatMode::Verify => {
                                 // We would have errored out already if you specified --stdin
-                                // without either --stdout or --check specified as well.
+                                // without either --stdout or --check specified as well,
+                                // and clap doesn't allow --verify with --stdin.
                                 unreachable!()
                             }
                         }
@@ -337,13 +425,16 @@ fn main() -> io::Result<()> {
 
             Ok(format_exit_code)
         }
-        Some((CMD_VERSION, _)) => {
-            print!(
-                "{}",
-                concatcp!("roc ", include_str!("../../../version.txt"))
-            );
+        Some((CMD_EXPLAIN, matches)) => {
+            let code = matches.get_one::<String>(ERROR_CODE).unwrap();
 
-            Ok(0)
+            Ok(explain(code))
+        }
+        Some((CMD_VERSION, matches)) => Ok(print_version(matches.get_flag(FLAG_JSON))),
+        Some((CMD_INSPECT_BINARY, matches)) => {
+            let binary_path = matches.get_one::<PathBuf>(BINARY_FILE).unwrap();
+
+            inspect_binary(binary_path, matches.get_flag(FLAG_JSON))
         }
         _ => unreachable!(),
     }?;
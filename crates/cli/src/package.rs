@@ -0,0 +1,350 @@
+//! `roc package` turns a package or platform into a `.tar.br` bundle that can be uploaded and
+//! then depended on over HTTPS.
+//!
+//! Apps can't rebuild a platform they download, so a platform bundle has to include a prebuilt
+//! host for every target it supports. This rebuilds the host for the current machine, and checks
+//! that the hosts for the other targets are already next to the platform's main.roc before
+//! bundling anything.
+//!
+//! Before any of that, it checks that the header's requires, exposes, and provides agree with
+//! each other and with the files next to main.roc, since a mistake there only shows up once
+//! someone depends on the bundle.
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Instant;
+
+use bumpalo::Bump;
+use clap::ArgMatches;
+use roc_build::link::{legacy_host_file, rebuild_host};
+use roc_collections::MutSet;
+use roc_mono::ir::OptLevel;
+use roc_packaging::tarball::{self, BundleKind, Compression};
+use roc_parse::ast::{Collection, Header, Spaced};
+use roc_parse::header::ModuleName;
+use roc_parse::module::parse_header;
+use roc_parse::state::State;
+use roc_region::all::Loc;
+use roc_target::Target;
+
+use crate::{FLAG_BUNDLE, FLAG_PREBUILT, FLAG_TARGET, ROC_FILE};
+
+pub fn package(matches: &ArgMatches) -> io::Result<i32> {
+    let path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+    let kind = match tarball::bundle_kind(path) {
+        Ok(kind) => kind,
+        Err(err) => {
+            eprintln!("\n{err}\n");
+
+            return Ok(1);
+        }
+    };
+
+    let problems = header_problems(path)?;
+
+    if !problems.is_empty() {
+        eprintln!(
+            "\nThe header of {} has some problems, so I can't bundle it:\n",
+            path.display()
+        );
+
+        for problem in problems {
+            eprintln!("    - {problem}");
+        }
+
+        eprintln!();
+
+        return Ok(1);
+    }
+
+    if kind == BundleKind::Platform {
+        let targets: Vec<Target> = matches
+            .get_many::<String>(FLAG_TARGET)
+            .unwrap()
+            .map(|name| Target::from_str(name).unwrap())
+            .collect();
+
+        if !prebuild_hosts(path, &targets, matches.get_flag(FLAG_PREBUILT)) {
+            return Ok(1);
+        }
+    }
+
+    let compression =
+        Compression::try_from(matches.get_one::<String>(FLAG_BUNDLE).unwrap().as_str()).unwrap();
+
+    let start_time = Instant::now();
+    let filename = tarball::build(path, compression)?;
+    let created_path = path.with_file_name(&filename);
+
+    println!(
+        "\nBundled \x1B[33m{}\x1B[39m into \x1B[33m{}\x1B[39m in {} ms.\n\nUpload it, and then depend on it with a URL ending in its content hash:\n\n\t\x1B[32m\"https://your-url-goes-here/{filename}\"\x1B[39m\n",
+        path.display(),
+        created_path.display(),
+        start_time.elapsed().as_millis()
+    );
+
+    Ok(0)
+}
+
+/// Everything in the header of a package or platform's main.roc that doesn't add up: exposed
+/// modules without a file, names that are listed twice, and a platform that provides nothing
+/// to its host, or provides the value it requires from the app.
+fn header_problems(main_roc: &Path) -> io::Result<Vec<String>> {
+    let arena = Bump::new();
+    let src = std::fs::read(main_roc)?;
+    let (module, _) = parse_header(&arena, State::new(&src)).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} has a malformed header: {:?}", main_roc.display(), err),
+        )
+    })?;
+
+    let mut problems = Vec::new();
+
+    let exposes = match &module.header {
+        Header::Package(header) => &header.exposes,
+        Header::Platform(header) => {
+            let required = header.requires.item.signature.value.item().ident.value;
+            let provides = &header.provides.item;
+
+            if provides.is_empty() {
+                problems.push(
+                    "It doesn't provide anything, so the host has nothing to call.".to_string(),
+                );
+            }
+
+            for name in duplicates(provides.iter().map(|name| name.value.item().as_str())) {
+                problems.push(format!("It provides {name} more than once."));
+            }
+
+            if provides
+                .iter()
+                .any(|name| name.value.item().as_str() == required)
+            {
+                problems.push(format!(
+                    "It both requires {required} from the app and provides it to the host. Provide a different name, e.g. {required}ForHost."
+                ));
+            }
+
+            let rigids = header.requires.item.rigids.iter();
+            for name in duplicates(rigids.map(|rigid| <&str>::from(rigid.value.item()))) {
+                problems.push(format!("It requires the type {name} more than once."));
+            }
+
+            &header.exposes.item
+        }
+        Header::App(_) | Header::Module(_) | Header::Hosted(_) => return Ok(problems),
+    };
+
+    problems.extend(exposes_problems(main_roc, exposes));
+
+    Ok(problems)
+}
+
+fn exposes_problems(
+    main_roc: &Path,
+    exposes: &Collection<'_, Loc<Spaced<'_, ModuleName<'_>>>>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for name in duplicates(exposes.iter().map(|name| name.value.item().as_str())) {
+        problems.push(format!("It exposes {name} more than once."));
+    }
+
+    for name in exposes.iter().map(|name| name.value.item()) {
+        let mut module_path = main_roc.with_file_name("");
+        module_path.extend(name.parts());
+        module_path.set_extension("roc");
+
+        if !module_path.is_file() {
+            problems.push(format!(
+                "It exposes {}, but there's no {} for it.",
+                name.as_str(),
+                module_path.display()
+            ));
+        }
+    }
+
+    problems
+}
+
+/// The names that appear more than once, in the order of their second appearance
+fn duplicates<'a>(names: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut seen = MutSet::default();
+
+    names.filter(|name| !seen.insert(*name)).collect()
+}
+
+/// Rebuilds the host for the current machine (unless `prebuilt`), and then checks that there's
+/// a host for every target. Returns whether they were all there.
+fn prebuild_hosts(platform_main_roc: &Path, targets: &[Target], prebuilt: bool) -> bool {
+    let native = Target::default();
+
+    if !prebuilt && targets.contains(&native) {
+        println!("Building the host for {native}…");
+
+        rebuild_host(OptLevel::Optimize, native, platform_main_roc, None);
+    }
+
+    let missing: Vec<(Target, PathBuf)> = targets
+        .iter()
+        .map(|target| (*target, prebuilt_host(*target, platform_main_roc)))
+        .filter(|(_, host)| !host.exists())
+        .collect();
+
+    if missing.is_empty() {
+        return true;
+    }
+
+    eprintln!(
+        "\nThis platform doesn't have a prebuilt host for every target, so apps built for them won't link:\n"
+    );
+
+    for (target, host) in missing {
+        let name: &'static str = target.into();
+
+        eprintln!("    {name:<14} {}", host.display());
+    }
+
+    eprintln!("\nBuild those hosts on (or for) their targets, put them next to {}, and then run `roc package` again. Or, leave those targets out with --{FLAG_TARGET}.\n", platform_main_roc.display());
+
+    false
+}
+
+/// The host a platform provides for `target`: a surgical linker host if it has one, and
+/// otherwise the one the legacy linker uses.
+fn prebuilt_host(target: Target, platform_main_roc: &Path) -> PathBuf {
+    let surgical = platform_main_roc.with_file_name(roc_linker::preprocessed_host_filename(target));
+
+    if surgical.exists() {
+        surgical
+    } else {
+        legacy_host_file(target, platform_main_roc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    /// Writes `main_roc`, plus an empty `<Name>.roc` for each of `modules`, and returns the
+    /// problems with the header.
+    fn problems(main_roc: &str, modules: &[&str]) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.roc");
+
+        std::fs::write(&path, main_roc).unwrap();
+
+        for module in modules {
+            std::fs::write(dir.path().join(format!("{module}.roc")), "").unwrap();
+        }
+
+        header_problems(&path)
+            .unwrap()
+            .into_iter()
+            .map(|problem| problem.replace(&format!("{}", dir.path().display()), "<dir>"))
+            .collect()
+    }
+
+    fn platform(requires: &str, exposes: &str, provides: &str) -> String {
+        format!(
+            indoc!(
+                r#"
+                platform "test"
+                    requires {}
+                    exposes {}
+                    packages {{}}
+                    imports []
+                    provides {}
+                "#
+            ),
+            requires, exposes, provides
+        )
+    }
+
+    #[test]
+    fn consistent_platform() {
+        assert_eq!(
+            problems(
+                &platform("{} { main : Str }", "[Stuff]", "[mainForHost]"),
+                &["Stuff"]
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn consistent_package() {
+        assert_eq!(
+            problems("package [Csv, Json] {}", &["Csv", "Json"]),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn platform_provides_nothing() {
+        assert_eq!(
+            problems(&platform("{} { main : Str }", "[]", "[]"), &[]),
+            ["It doesn't provide anything, so the host has nothing to call."]
+        );
+    }
+
+    #[test]
+    fn platform_provides_twice() {
+        assert_eq!(
+            problems(
+                &platform("{} { main : Str }", "[]", "[mainForHost, mainForHost]"),
+                &[]
+            ),
+            ["It provides mainForHost more than once."]
+        );
+    }
+
+    #[test]
+    fn platform_provides_what_it_requires() {
+        assert_eq!(
+            problems(&platform("{} { main : Str }", "[]", "[main]"), &[]),
+            ["It both requires main from the app and provides it to the host. Provide a different name, e.g. mainForHost."]
+        );
+    }
+
+    #[test]
+    fn platform_requires_type_twice() {
+        assert_eq!(
+            problems(
+                &platform("{ Model, Model } { main : Model }", "[]", "[mainForHost]"),
+                &[]
+            ),
+            ["It requires the type Model more than once."]
+        );
+    }
+
+    #[test]
+    fn platform_exposes_missing_module() {
+        assert_eq!(
+            problems(
+                &platform("{} { main : Str }", "[Stuff]", "[mainForHost]"),
+                &[]
+            ),
+            ["It exposes Stuff, but there's no <dir>/Stuff.roc for it."]
+        );
+    }
+
+    #[test]
+    fn package_exposes_missing_module() {
+        assert_eq!(
+            problems("package [Csv, Json] {}", &["Csv"]),
+            ["It exposes Json, but there's no <dir>/Json.roc for it."]
+        );
+    }
+
+    #[test]
+    fn package_exposes_twice() {
+        assert_eq!(
+            problems("package [Csv, Csv] {}", &["Csv"]),
+            ["It exposes Csv more than once."]
+        );
+    }
+}
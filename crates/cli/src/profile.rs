@@ -0,0 +1,406 @@
+//! `roc run --profile` samples a program with `perf` while it runs, and then draws a flamegraph
+//! of where it spent its time.
+//!
+//! Roc procs are compiled with frame pointers when profiling, so `perf` can walk the stack
+//! without DWARF unwinding. Their symbols are specialization names like `List_map_3a1f…`, so
+//! before anything is drawn, each frame is mapped back to the Roc name it came from, like
+//! `List.map`.
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use roc_module::symbol::Interns;
+
+/// Samples per second. It's deliberately not a round number, so sampling doesn't fall into
+/// lockstep with timers in the program.
+const SAMPLE_FREQUENCY: &str = "997";
+
+const SVG_WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: f64 = 16.0;
+const TITLE_HEIGHT: f64 = 32.0;
+/// Frames narrower than this (in pixels) aren't drawn at all
+const MIN_FRAME_WIDTH: f64 = 0.1;
+/// Roughly how wide a character of the 12px monospace labels is
+const CHAR_WIDTH: f64 = 7.2;
+
+/// Runs the executable at `binary_path` under `perf`, and writes a flamegraph of the samples to
+/// `svg_path`. Returns the program's exit code.
+pub fn profile<'a, I: IntoIterator<Item = &'a OsStr>>(
+    binary_path: &Path,
+    args: I,
+    interns: &Interns,
+    svg_path: &Path,
+) -> io::Result<i32> {
+    let temp_dir = tempfile::tempdir()?;
+    let perf_data = temp_dir.path().join("perf.data");
+
+    let record = Command::new("perf")
+        .args([
+            "record",
+            "--quiet",
+            "--call-graph",
+            "fp",
+            "-F",
+            SAMPLE_FREQUENCY,
+        ])
+        .arg("-o")
+        .arg(&perf_data)
+        .arg("--")
+        .arg(binary_path)
+        .args(args)
+        .status();
+
+    let status = match record {
+        Ok(status) => status,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            eprintln!("\n--profile samples the program with `perf`, but I couldn't find it. It's usually in your distribution's `perf` or `linux-tools` package.\n");
+
+            return Ok(1);
+        }
+        Err(err) => return Err(err),
+    };
+
+    if !perf_data.exists() {
+        eprintln!("\n`perf record` didn't write any samples. If it said it wasn't allowed to, try lowering /proc/sys/kernel/perf_event_paranoid to 1 or less.\n");
+
+        return Ok(1);
+    }
+
+    let script = Command::new("perf")
+        .args(["script", "--fields", "ip,sym", "-i"])
+        .arg(&perf_data)
+        .output()?;
+
+    if !script.status.success() {
+        eprintln!(
+            "\n`perf script` couldn't read the samples:\n\n{}",
+            String::from_utf8_lossy(&script.stderr)
+        );
+
+        return Ok(1);
+    }
+
+    let symbolizer = Symbolizer::new(interns);
+    let root = fold_samples(&String::from_utf8_lossy(&script.stdout), &symbolizer);
+
+    std::fs::write(svg_path, flamegraph_svg(&root, binary_path))?;
+
+    println!(
+        "\nWrote a flamegraph of {} samples to \x1B[33m{}\x1B[39m\n",
+        root.samples,
+        svg_path.display()
+    );
+
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Maps the symbol of a Roc proc specialization back to the name of the def it came from.
+///
+/// Specializations are named `{module}_{ident}_{spec}`, where the spec is hex digits (or
+/// `erased`), so everything up to the last `_` identifies the def.
+struct Symbolizer {
+    names: HashMap<String, String>,
+}
+
+impl Symbolizer {
+    fn new(interns: &Interns) -> Self {
+        let mut names = HashMap::new();
+
+        for module_id in interns.all_ident_ids.keys() {
+            let module_name = interns.module_name(*module_id);
+            let ident_ids = interns.all_ident_ids.get(module_id).unwrap();
+
+            for (_, ident) in ident_ids.ident_strs() {
+                names.insert(
+                    format!("{module_name}_{ident}_"),
+                    format!("{module_name}.{ident}"),
+                );
+            }
+        }
+
+        Self { names }
+    }
+
+    fn source_name<'a>(&'a self, symbol: &'a str) -> &'a str {
+        let Some(spec_start) = symbol.rfind('_').map(|index| index + 1) else {
+            return symbol;
+        };
+
+        let spec = &symbol[spec_start..];
+        let is_spec = spec == "erased" || spec.bytes().all(|byte| byte.is_ascii_hexdigit());
+
+        match self.names.get(&symbol[..spec_start]) {
+            Some(name) if is_spec => name,
+            _ => symbol,
+        }
+    }
+}
+
+/// A frame in the flamegraph, and the frames that were called from it
+#[derive(Debug, Default, PartialEq)]
+struct Frame {
+    name: String,
+    samples: usize,
+    children: Vec<Frame>,
+}
+
+impl Frame {
+    fn add_stack<'a>(&mut self, stack: impl Iterator<Item = &'a str>) {
+        self.samples += 1;
+
+        let mut frame = self;
+
+        for name in stack {
+            let index = match frame.children.iter().position(|child| child.name == name) {
+                Some(index) => index,
+                None => {
+                    frame.children.push(Frame {
+                        name: name.to_string(),
+                        ..Frame::default()
+                    });
+
+                    frame.children.len() - 1
+                }
+            };
+
+            frame = &mut frame.children[index];
+            frame.samples += 1;
+        }
+    }
+
+    fn depth(&self) -> usize {
+        1 + self.children.iter().map(Frame::depth).max().unwrap_or(0)
+    }
+}
+
+/// Folds the output of `perf script --fields ip,sym` into a tree of frames. Each sample is a
+/// block of `address symbol` lines, innermost call first, and blocks are separated by blank
+/// lines.
+fn fold_samples(script: &str, symbolizer: &Symbolizer) -> Frame {
+    let mut root = Frame {
+        name: "all".to_string(),
+        ..Frame::default()
+    };
+    let mut stack = Vec::new();
+
+    for line in script.lines().chain([""]) {
+        let line = line.trim();
+
+        if line.is_empty() {
+            if !stack.is_empty() {
+                root.add_stack(stack.drain(..).rev());
+            }
+
+            continue;
+        }
+
+        let symbol = match line.split_once(char::is_whitespace) {
+            Some((_address, symbol)) => symbol.trim(),
+            None => "[unknown]",
+        };
+
+        stack.push(symbolizer.source_name(symbol));
+    }
+
+    root
+}
+
+fn flamegraph_svg(root: &Frame, binary_path: &Path) -> String {
+    let height = TITLE_HEIGHT + root.depth() as f64 * FRAME_HEIGHT;
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg version="1.1" width="{SVG_WIDTH}" height="{height}" xmlns="http://www.w3.org/2000/svg" font-family="monospace" font-size="12">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#f8f8f8"/><text x="{}" y="20" text-anchor="middle" font-size="16">{}</text>"##,
+        SVG_WIDTH / 2.0,
+        escape_xml(&binary_path.display().to_string())
+    )
+    .unwrap();
+
+    if root.samples > 0 {
+        let pixels_per_sample = SVG_WIDTH / root.samples as f64;
+
+        write_frame(&mut svg, root, root.samples, 0.0, height, pixels_per_sample);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Draws `frame` with its bottom edge at `bottom`, and then its children on top of it
+fn write_frame(
+    svg: &mut String,
+    frame: &Frame,
+    total_samples: usize,
+    x: f64,
+    bottom: f64,
+    pixels_per_sample: f64,
+) {
+    let width = frame.samples as f64 * pixels_per_sample;
+
+    if width < MIN_FRAME_WIDTH {
+        return;
+    }
+
+    let y = bottom - FRAME_HEIGHT;
+    let name = escape_xml(&frame.name);
+    let percent = frame.samples as f64 * 100.0 / total_samples as f64;
+
+    writeln!(
+        svg,
+        r#"<g><title>{name} ({} samples, {percent:.2}%)</title><rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{}" fill="{}" rx="2"/>"#,
+        frame.samples,
+        FRAME_HEIGHT - 1.0,
+        frame_color(&frame.name)
+    )
+    .unwrap();
+
+    let max_chars = ((width - 6.0) / CHAR_WIDTH) as usize;
+
+    if max_chars >= 3 {
+        let label = if frame.name.chars().count() <= max_chars {
+            frame.name.clone()
+        } else {
+            let mut label: String = frame.name.chars().take(max_chars - 2).collect();
+            label.push_str("..");
+            label
+        };
+
+        writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}">{}</text>"#,
+            x + 3.0,
+            y + FRAME_HEIGHT - 4.0,
+            escape_xml(&label)
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</g>\n");
+
+    let mut child_x = x;
+
+    for child in frame.children.iter() {
+        write_frame(svg, child, total_samples, child_x, y, pixels_per_sample);
+
+        child_x += child.samples as f64 * pixels_per_sample;
+    }
+}
+
+/// A warm color that's always the same for the same name, so a function keeps its color
+/// wherever it shows up in the graph
+fn frame_color(name: &str) -> String {
+    let hash = name.bytes().fold(0u32, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(byte as u32)
+    });
+
+    let red = 205 + hash % 50;
+    let green = (hash >> 8) % 230;
+    let blue = (hash >> 16) % 55;
+
+    format!("rgb({red},{green},{blue})")
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use roc_module::ident::ModuleName;
+
+    fn symbolizer() -> Symbolizer {
+        let mut interns = Interns::default();
+        let module_id = interns.module_id(&ModuleName::from("Main"));
+        let ident_ids = interns.all_ident_ids.get_or_insert(module_id);
+
+        ident_ids.add_str("main");
+        ident_ids.add_str("parse_line");
+
+        Symbolizer::new(&interns)
+    }
+
+    #[test]
+    fn maps_specializations_to_source_names() {
+        let symbolizer = symbolizer();
+
+        assert_eq!(symbolizer.source_name("Main_main_1f0a"), "Main.main");
+        assert_eq!(
+            symbolizer.source_name("Main_parse_line_erased"),
+            "Main.parse_line"
+        );
+        assert_eq!(
+            symbolizer.source_name("Main_main_notaspec"),
+            "Main_main_notaspec"
+        );
+        assert_eq!(
+            symbolizer.source_name("roc_builtins.list.map"),
+            "roc_builtins.list.map"
+        );
+    }
+
+    #[test]
+    fn folds_samples_outermost_first() {
+        let script = indoc::indoc!(
+            "
+                    55d0c8a1b2c3 Main_parse_line_3e
+                    55d0c8a1b000 Main_main_1f
+                    7f0000001000 __libc_start_main
+
+                    55d0c8a1b000 Main_main_1f
+                    7f0000001000 __libc_start_main
+            "
+        );
+
+        let root = fold_samples(script, &symbolizer());
+
+        assert_eq!(root.samples, 2);
+        assert_eq!(root.depth(), 4);
+
+        let start = &root.children[0];
+        assert_eq!(
+            (start.name.as_str(), start.samples),
+            ("__libc_start_main", 2)
+        );
+
+        let main = &start.children[0];
+        assert_eq!((main.name.as_str(), main.samples), ("Main.main", 2));
+        assert_eq!(main.children[0].name, "Main.parse_line");
+        assert_eq!(main.children[0].samples, 1);
+    }
+
+    #[test]
+    fn escapes_names_in_svg() {
+        let mut root = Frame {
+            name: "all".to_string(),
+            ..Frame::default()
+        };
+        root.add_stack(["<Vec<u8> as Drop>::drop"].into_iter());
+
+        let svg = flamegraph_svg(&root, Path::new("app"));
+
+        assert!(svg.contains("&lt;Vec&lt;u8&gt; as Drop&gt;::drop (1 samples"));
+        assert!(!svg.contains("<Vec"));
+    }
+}
@@ -0,0 +1,75 @@
+//! The targets `--target` accepts, and how we build for each of them from this machine.
+//!
+//! Building for a different machine can't use this machine's toolchains to rebuild the platform,
+//! so cross builds use the platform's prebuilt host for the target instead. That host gets linked
+//! with the surgical linker when it supports the target, and otherwise with zig, which bundles a
+//! libc for every target (see [roc_build::target::target_zig_str]).
+use roc_build::link::{LinkType, LinkingStrategy};
+use roc_build::target::target_zig_str;
+use roc_target::{Architecture, Target};
+use strum::IntoEnumIterator;
+
+/// The name of every target, as accepted by `--target`.
+pub fn target_names() -> impl Iterator<Item = &'static str> {
+    Target::iter().map(Into::<&'static str>::into)
+}
+
+/// The help text for `--target`, listing the targets and the triples we link them for.
+pub fn target_flag_help() -> String {
+    let mut help = String::from(
+        "Choose a different target\n(Targets other than this machine need a prebuilt platform.)\n",
+    );
+
+    for target in Target::iter() {
+        let name: &'static str = target.into();
+
+        help.push_str(&format!("\n  {name:<14} {}", target_zig_str(target)));
+    }
+
+    help
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildTarget {
+    pub target: Target,
+    /// The machine the compiler is running on
+    pub host: Target,
+}
+
+impl BuildTarget {
+    pub fn new(target: Target) -> Self {
+        Self {
+            target,
+            host: Target::default(),
+        }
+    }
+
+    pub fn is_cross_compile(&self) -> bool {
+        self.target != self.host
+    }
+
+    /// When compiling for a different target, assume a prebuilt platform. Otherwise compilation
+    /// would most likely fail because many toolchains assume you're compiling for the current
+    /// machine. We make an exception for Wasm, because cross-compiling is the norm in that case.
+    pub fn assumes_prebuilt_platform(&self) -> bool {
+        self.is_cross_compile() && self.target.architecture() != Architecture::Wasm32
+    }
+
+    pub fn linking_strategy(
+        &self,
+        link_type: LinkType,
+        wasm_dev_backend: bool,
+        requested_linker: Option<&str>,
+    ) -> LinkingStrategy {
+        if wasm_dev_backend {
+            LinkingStrategy::Additive
+        } else if !roc_linker::supported(link_type, self.target)
+            || requested_linker == Some("legacy")
+        {
+            // For a cross build, the legacy linker links with zig for the target
+            LinkingStrategy::Legacy
+        } else {
+            LinkingStrategy::Surgical
+        }
+    }
+}
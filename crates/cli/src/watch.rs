@@ -0,0 +1,195 @@
+//! `roc check --watch`: re-check whenever one of the loaded modules changes on disk.
+//!
+//! There is no incremental module graph: each re-check is a full load, so every module is
+//! read, parsed and canonicalized again. The only work that is saved is solving: the solved
+//! types of plain modules whose source (and whose imports' sources) did not change come
+//! back from the type cache instead of being constrained and solved again.
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use bumpalo::Bump;
+use roc_build::program::{check_file_rendered, CheckedFile};
+use roc_collections::MutMap;
+use roc_load::{LoadingProblem, Threading};
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::cli::{print_horizontal_rule, OutputFormat};
+use roc_reporting::report::ANSI_STYLE_CODES;
+
+/// How often to look at the watched files' modification times.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Modification times of the files we're watching; `None` if a file couldn't be read
+/// (e.g. because an editor deleted it in order to replace it).
+pub(crate) type Snapshot = MutMap<PathBuf, Option<SystemTime>>;
+
+pub fn check_watch(roc_file_path: &Path, emit_timings: bool, threading: Threading) -> ! {
+    let roc_cache_dir = cache::roc_cache_dir();
+
+    let mut watched_paths = vec![roc_file_path.to_path_buf()];
+    let mut previously_shown: Option<HashSet<String>> = None;
+
+    loop {
+        let arena = Bump::new();
+
+        // Taken before checking, so that a save made while the check runs isn't missed
+        let check_started = SystemTime::now();
+        let before = snapshot(&watched_paths);
+
+        let shown = match check_file_rendered(
+            &arena,
+            roc_file_path.to_path_buf(),
+            emit_timings,
+            RocCacheDir::Persistent(roc_cache_dir.as_path()),
+            threading,
+            OutputFormat::Human,
+            false,
+            None,
+            false,
+        ) {
+            Ok(CheckedFile {
+                problems,
+                module_paths,
+                total_time,
+                ..
+            }) => {
+                let shown: HashSet<String> = problems.shown().iter().cloned().collect();
+
+                print_delta(problems.shown(), previously_shown.as_ref());
+                problems.problems().print_error_warning_count(total_time);
+
+                watched_paths = module_paths;
+
+                shown
+            }
+            Err(LoadingProblem::FormattedReport(report)) => {
+                // We couldn't get far enough to know what the modules are (e.g. a header
+                // failed to parse), so keep watching the files from the last good run.
+                print!("{report}");
+
+                HashSet::from([report])
+            }
+            Err(other) => {
+                // Not a problem we have a report for (e.g. a file vanished while an editor
+                // was saving it), but the next change may well fix it.
+                let message = format!("Failed with error: {other:?}\n");
+                print!("{message}");
+
+                HashSet::from([message])
+            }
+        };
+
+        previously_shown = Some(shown);
+
+        if !watched_paths.iter().any(|path| path == roc_file_path) {
+            watched_paths.push(roc_file_path.to_path_buf());
+        }
+
+        println!(
+            "\nWatching {} {} for changes...",
+            watched_paths.len(),
+            if watched_paths.len() == 1 {
+                "file"
+            } else {
+                "files"
+            }
+        );
+
+        wait_for_change(&watched_paths, before, check_started);
+    }
+}
+
+/// On the first run, print everything. After that, only print reports we haven't
+/// already shown, and say how many of the ones we did show have gone away.
+fn print_delta(shown: &[String], previously_shown: Option<&HashSet<String>>) {
+    const GREEN: &str = ANSI_STYLE_CODES.green;
+    const RESET: &str = ANSI_STYLE_CODES.reset;
+
+    let new_reports: Vec<&String> = match previously_shown {
+        None => shown.iter().collect(),
+        Some(previous) => shown
+            .iter()
+            .filter(|report| !previous.contains(*report))
+            .collect(),
+    };
+
+    for report in new_reports.iter() {
+        println!("\n{report}\n");
+    }
+
+    if !new_reports.is_empty() {
+        print_horizontal_rule();
+    }
+
+    if let Some(previous) = previously_shown {
+        let current: HashSet<&String> = shown.iter().collect();
+        let resolved = previous
+            .iter()
+            .filter(|report| !current.contains(report))
+            .count();
+        let unchanged = shown.len() - new_reports.len();
+
+        if resolved > 0 {
+            println!(
+                "{GREEN}{resolved}{RESET} {} from the previous check no longer {}.",
+                if resolved == 1 { "problem" } else { "problems" },
+                if resolved == 1 { "applies" } else { "apply" },
+            );
+        }
+
+        if unchanged > 0 {
+            println!(
+                "{unchanged} {} from the previous check still {} (not shown again).",
+                if unchanged == 1 {
+                    "problem"
+                } else {
+                    "problems"
+                },
+                if unchanged == 1 { "applies" } else { "apply" },
+            );
+        }
+    }
+}
+
+pub(crate) fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+fn wait_for_change(paths: &[PathBuf], before: Snapshot, check_started: SystemTime) {
+    let mut before: Snapshot = paths
+        .iter()
+        .map(|path| (path.clone(), before.get(path).copied().flatten()))
+        .collect();
+
+    // Modules we only found out about during the check have no snapshot from before it,
+    // so go by whether they were modified after it started.
+    for path in paths {
+        if before[path].is_none() {
+            let modified = snapshot(std::slice::from_ref(path)).remove(path).flatten();
+
+            match modified {
+                Some(time) if time >= check_started => return,
+                _ => {
+                    before.insert(path.clone(), modified);
+                }
+            }
+        }
+    }
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        if snapshot(paths) != before {
+            return;
+        }
+    }
+}
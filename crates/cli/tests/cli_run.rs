@@ -17,7 +17,7 @@ mod cli_run {
     use const_format::concatcp;
     use indoc::indoc;
     use regex::Regex;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_FORMAT, CMD_RUN, CMD_TEST};
+    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_EXPLAIN, CMD_FORMAT, CMD_RUN, CMD_TEST};
     use roc_reporting::report::strip_colors;
     use roc_test_utils::assert_multiline_str_eq;
     use serial_test::serial;
@@ -683,6 +683,116 @@ mod cli_run {
         );
     }
 
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn filtered_expects() {
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--filter", "addAnd"],
+            indoc!(
+                r#"
+                0 failed and 2 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--module", "Transitive"],
+            indoc!(
+                r#"
+                0 failed and 1 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--module", "Direct.roc", "--line", "14"],
+            indoc!(
+                r#"
+                0 failed and 1 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn skipped_and_expected_failure_expects() {
+        test_roc_expect(
+            "crates/cli/tests/expects_marked",
+            "main.roc",
+            &[],
+            indoc!(
+                r#"
+                0 failed and 1 passed, 1 skipped, 1 failed as expected in <ignored for test> ms.
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn parallel_expects_report_in_source_order() {
+        let path = file_path_from_root("crates/cli/tests/expects_parallel", "main.roc");
+
+        let out = run_roc(
+            [CMD_TEST, path.to_str().unwrap(), "--max-threads", "4"],
+            &[],
+            &[],
+        );
+
+        assert!(!out.status.success());
+
+        let stdout = ignore_test_timings(&strip_colors(&out.stdout));
+
+        // each expect runs in its own process, but the reports come out in source order
+        let failed_first = stdout.find("expect double 2 == 5").unwrap();
+        let crashed = stdout.find("this expect crashes").unwrap();
+        let failed_last = stdout.find("expect double 4 == 9").unwrap();
+
+        assert!(failed_first < crashed, "{stdout}");
+        assert!(crashed < failed_last, "{stdout}");
+
+        // the crash only took down its own expect
+        assert_eq!(stdout.matches("EXPECT FAILED").count(), 2, "{stdout}");
+        assert_eq!(stdout.matches("EXPECT PANICKED").count(), 1, "{stdout}");
+        assert!(
+            stdout.ends_with("3 failed and 2 passed in <ignored for test> ms.\n"),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn doctests() {
+        test_roc_expect(
+            "crates/cli/tests/expects_doctests",
+            "main.roc",
+            &[],
+            indoc!(
+                r#"
+                0 failed and 1 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+
+        test_roc_expect(
+            "crates/cli/tests/expects_doctests",
+            "main.roc",
+            &["--doctests"],
+            indoc!(
+                r#"
+                0 failed and 3 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+    }
+
     #[test]
     #[cfg_attr(
         windows,
@@ -1335,6 +1445,190 @@ mod cli_run {
         );
     }
 
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg_attr(windows, ignore)]
+    fn stack_overflow_is_reported_through_roc_panic() {
+        // Only apps that a system linker links get stack checks
+        let file = fixture_file("runtime-errors", "StackOverflow.roc");
+        let out = run_roc(
+            [CMD_RUN, LINKER_FLAG, "legacy", file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        assert!(!out.status.success(), "unexpected success {out:?}");
+        assert!(
+            out.stderr.contains("crashed with message")
+                && out.stderr.contains("stack overflow in ")
+                && out.stderr.contains(".sumTo"),
+            "expected a stack overflow in sumTo, but got {out:?}"
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg_attr(windows, ignore)]
+    fn recursive_app_runs_with_either_linker() {
+        let file = fixture_file("runtime-errors", "DeepRecursion.roc");
+
+        // With the legacy linker the app checks its stack, against the limit it worked out
+        // from the thread's stack; the surgical linker must link it without those checks.
+        let linkers: &[&str] = if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+            &["legacy", "surgical"]
+        } else {
+            &["legacy"]
+        };
+
+        for linker in linkers {
+            let out = run_roc(
+                [CMD_RUN, LINKER_FLAG, *linker, file.to_str().unwrap()],
+                &[],
+                &[],
+            );
+
+            assert!(out.status.success(), "{linker} linker: {out:?}");
+            assert!(
+                out.stdout.ends_with("50005000\n"),
+                "{linker} linker: {out:?}"
+            );
+        }
+    }
+
+    /// Checks that the crash message in `out` is followed by a backtrace with shout innermost.
+    fn assert_crash_backtrace(out: &Out) {
+        assert!(!out.status.success(), "unexpected success {out:?}");
+
+        let (_, backtrace) = out
+            .stderr
+            .split_once("too long to shout\n\nBacktrace:\n")
+            .unwrap_or_else(|| panic!("expected a backtrace after the message, but got {out:?}"));
+        let frames: Vec<&str> = backtrace
+            .lines()
+            .take_while(|line| line.starts_with("    ") && !line.trim().is_empty())
+            .map(str::trim)
+            .collect();
+
+        assert!(
+            frames
+                .first()
+                .is_some_and(|frame| frame.ends_with(".shout")),
+            "expected the innermost frame to be shout, but got {frames:?}"
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg_attr(windows, ignore)]
+    fn crash_message_ends_with_backtrace() {
+        let file = fixture_file("runtime-errors", "CrashBacktrace.roc");
+
+        // Only dev builds have frame pointers to follow
+        assert_crash_backtrace(&run_roc([CMD_DEV, file.to_str().unwrap()], &[], &[]));
+
+        let out = run_roc([CMD_RUN, file.to_str().unwrap()], &[], &[]);
+
+        assert!(!out.status.success(), "unexpected success {out:?}");
+        assert!(out.stderr.contains("too long to shout"), "{out:?}");
+        assert!(!out.stderr.contains("Backtrace:"), "{out:?}");
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn crash_backtrace_links_with_surgical_linker() {
+        // The backtrace table must not need relocations that the surgical linker refuses
+        let file = fixture_file("runtime-errors", "CrashBacktrace.roc");
+        let out = run_roc(
+            [CMD_DEV, LINKER_FLAG, "surgical", file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        assert!(!out.stderr.contains("#3609"), "{out:?}");
+        assert_crash_backtrace(&out);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn hot_reload_loads_the_changed_app() {
+        use cli_utils::helpers::path_to_roc_binary;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Child, Command, Stdio};
+        use std::sync::mpsc::Receiver;
+        use std::time::Duration;
+
+        struct KillOnDrop(Child);
+
+        impl Drop for KillOnDrop {
+            fn drop(&mut self) {
+                let _ = self.0.kill();
+            }
+        }
+
+        fn wait_for_line(lines: &Receiver<String>, expected: &str) {
+            loop {
+                match lines.recv_timeout(Duration::from_secs(300)) {
+                    Ok(line) if line == expected => return,
+                    Ok(_) => {}
+                    Err(err) => panic!("gave up waiting for {expected:?}: {err}"),
+                }
+            }
+        }
+
+        // The test changes the app, so it works on a copy
+        let fixture_dir = fixtures_dir("hot-reload");
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("platform")).unwrap();
+
+        for file in ["app.roc", "platform/main.roc"] {
+            std::fs::copy(fixture_dir.join(file), dir.path().join(file)).unwrap();
+        }
+
+        // Platforms would ship their hot-reload host prebuilt, but this one is small enough to
+        // build here.
+        let status = Command::new("cc")
+            .arg("-rdynamic")
+            .arg("-o")
+            .arg(dir.path().join("platform/linux-x64-hot-host"))
+            .arg(fixture_dir.join("platform/hot-host.c"))
+            .arg("-ldl")
+            .status()
+            .unwrap();
+        assert!(status.success(), "failed to build the hot-reload host");
+
+        let app = dir.path().join("app.roc");
+        let mut roc = KillOnDrop(
+            Command::new(path_to_roc_binary())
+                .args([CMD_DEV, "--hot"])
+                .arg(&app)
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap(),
+        );
+
+        let (sender, lines) = std::sync::mpsc::channel();
+        let stdout = roc.0.stdout.take().unwrap();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        wait_for_line(&lines, "1: Hello from the first build");
+
+        let source = std::fs::read_to_string(&app).unwrap();
+        std::fs::write(&app, source.replace("first build", "second build")).unwrap();
+
+        // The host counts the builds it loaded, so this is the same host, with the new build
+        wait_for_line(&lines, "2: Hello from the second build");
+
+        let status = roc.0.wait().unwrap();
+        assert!(status.success(), "roc dev --hot exited with {status}");
+    }
+
     #[test]
     #[serial(multi_dep_thunk)]
     #[cfg_attr(windows, ignore)]
@@ -1461,6 +1755,272 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn known_type_error_with_json_output() {
+        let out = run_roc(
+            [
+                CMD_CHECK,
+                known_bad_file("UnusedImportButWithALongFileNameForTesting.roc")
+                    .to_str()
+                    .unwrap(),
+                "--output-format",
+                "json",
+            ],
+            &[],
+            &[],
+        );
+
+        let lines: Vec<&str> = out.stdout.trim().lines().collect();
+        assert_eq!(
+            lines.len(),
+            1,
+            "expected one diagnostic, got:\n{}",
+            out.stdout
+        );
+
+        let diagnostic: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+
+        assert_eq!(diagnostic["severity"], "warning");
+        assert_eq!(diagnostic["code"], "E0139");
+        assert_eq!(diagnostic["title"], "UNUSED IMPORT");
+        assert_eq!(
+            diagnostic["region"],
+            serde_json::json!({
+                "start": { "offset": 98, "line": 3, "column": 14 },
+                "end": { "offset": 114, "line": 3, "column": 30 },
+            })
+        );
+        assert!(diagnostic["body"]
+            .as_str()
+            .unwrap()
+            .starts_with("Symbol is imported but not used."));
+        assert!(diagnostic["filename"]
+            .as_str()
+            .unwrap()
+            .ends_with("UnusedImportButWithALongFileNameForTesting.roc"));
+    }
+
+    #[test]
+    fn known_type_error_with_sarif_output() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let sarif_path = out_dir.path().join("problems.sarif");
+
+        let out = run_roc(
+            [
+                CMD_CHECK,
+                known_bad_file("UnusedImportButWithALongFileNameForTesting.roc")
+                    .to_str()
+                    .unwrap(),
+                "--sarif",
+                sarif_path.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        // The usual report is still printed
+        assert!(out.stdout.contains("UNUSED IMPORT"));
+
+        let sarif: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&sarif_path).unwrap()).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"],
+            serde_json::json!({
+                "startLine": 3,
+                "startColumn": 14,
+                "endLine": 3,
+                "endColumn": 30,
+            })
+        );
+    }
+
+    #[test]
+    fn run_has_no_interpreter_mode() {
+        // There is no IR evaluator to run an app without code gen, so `roc run` must not
+        // pretend to have one.
+        let out = run_roc(
+            [
+                CMD_RUN,
+                "--interp",
+                file_path_from_root("crates/cli/tests/algorithms", "fibonacci.roc")
+                    .to_str()
+                    .unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(!out.status.success());
+        assert!(
+            out.stderr.contains("unexpected argument '--interp'"),
+            "{}",
+            out.stderr
+        );
+    }
+
+    #[test]
+    fn explain_error_code() {
+        let out = run_roc([CMD_EXPLAIN, "e139"], &[], &[]);
+
+        assert!(out.status.success());
+        assert!(strip_colors(&out.stdout).starts_with("E0139: UNUSED IMPORT\n"));
+
+        let out = run_roc([CMD_EXPLAIN, "E9999"], &[], &[]);
+
+        assert!(!out.status.success());
+    }
+
+    #[test]
+    fn version_json() {
+        let out = run_roc([roc_cli::CMD_VERSION, "--json"], &[], &[]);
+
+        assert!(out.status.success());
+
+        let version: serde_json::Value = serde_json::from_str(&out.stdout).unwrap();
+
+        assert!(version["version"].is_string());
+        assert!(version["host"].is_string());
+        assert!(version["targets"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("wasm32")));
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    // uses C platform
+    fn inspect_binary_shows_build_flags() {
+        let file = file_path_from_root("examples/platform-switching", "main.roc");
+        let out_dir = tempfile::tempdir().unwrap();
+        let binary = out_dir.path().join("inspected");
+        let output_flag = format!("--{}={}", roc_cli::FLAG_OUTPUT, binary.display());
+
+        run_roc_on_failure_is_panic(
+            &file,
+            [CMD_BUILD, OPTIMIZE_FLAG, &output_flag],
+            &[],
+            &[],
+            &[],
+        );
+
+        let out = run_roc(
+            [
+                roc_cli::CMD_INSPECT_BINARY,
+                "--json",
+                binary.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}", out.stderr);
+
+        let build_info: serde_json::Value = serde_json::from_str(&out.stdout).unwrap();
+
+        assert_eq!(build_info["flags"], serde_json::json!([OPTIMIZE_FLAG]));
+        assert!(build_info["target"].is_string());
+
+        let out = run_roc(
+            [roc_cli::CMD_INSPECT_BINARY, file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        assert!(!out.status.success());
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    // uses C platform
+    fn build_with_json_output_only_prints_json() {
+        let file = file_path_from_root("examples/platform-switching", "main.roc");
+        let out_dir = tempfile::tempdir().unwrap();
+        let binary = out_dir.path().join("built");
+        let output_flag = format!("--{}={}", roc_cli::FLAG_OUTPUT, binary.display());
+
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                file.to_str().unwrap(),
+                &output_flag,
+                "--output-format",
+                "json",
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "bad status {out:?}");
+
+        for line in out.stdout.lines() {
+            assert!(
+                serde_json::from_str::<serde_json::Value>(line).is_ok(),
+                "stdout should only contain JSON, but it had:\n{line}"
+            );
+        }
+
+        assert!(out.stderr.contains("while successfully building"));
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn build_is_reproducible() {
+        let file = file_path_from_root("examples/platform-switching", "main.roc");
+        let out_dir = tempfile::tempdir().unwrap();
+        let binary = out_dir.path().join("built");
+        let output_flag = format!("--{}={}", roc_cli::FLAG_OUTPUT, binary.display());
+        let check_flag = format!("--{}", roc_cli::FLAG_CHECK_REPRODUCIBLE);
+
+        let out = run_roc(
+            [CMD_BUILD, file.to_str().unwrap(), &output_flag, &check_flag],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "bad status {out:?}");
+        assert!(
+            out.stdout.contains("Building it again gave the same"),
+            "unexpected output {out:?}"
+        );
+    }
+
+    #[test]
+    fn explain_type_at_position() {
+        let file = file_path_from_root("examples/platform-switching", "main.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), "--explain-type", "3:1"],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "bad status {out:?}");
+        assert!(out.stdout.contains(": Str"), "unexpected output {out:?}");
+    }
+
+    #[test]
+    fn explain_type_rejects_column_past_end_of_line() {
+        let file = file_path_from_root("examples/platform-switching", "main.roc");
+
+        // Line 1 is 49 columns long, so this would otherwise land on line 3
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), "--explain-type", "1:52"],
+            &[],
+            &[],
+        );
+
+        assert!(
+            out.stdout
+                .contains("only has 49 columns, so there's no column 52."),
+            "unexpected output {out:?}"
+        );
+    }
+
     #[test]
     fn exposed_not_defined() {
         check_compile_error(
@@ -0,0 +1,28 @@
+interface Doubling
+    exposes [
+        double,
+    ]
+    imports []
+
+## Doubles a number.
+##
+## ```roc
+## double 2 == 4
+## ```
+##
+## Doubling twice quadruples it:
+##
+## ```roc
+## four = double 2
+##
+## double four == 8
+## ```
+##
+## Only `roc` code blocks run:
+##
+## ```
+## double "two"
+## ```
+double = \num -> num * 2
+
+expect double 3 == 6
@@ -0,0 +1,5 @@
+package "doctests"
+    exposes [
+        Doubling,
+    ]
+    packages {}
@@ -0,0 +1,15 @@
+interface Marked
+    exposes [
+        double,
+    ]
+    imports []
+
+double = \num -> num * 2
+
+expect double 2 == 4
+
+# roc-test: skip
+expect double 2 == 5
+
+# roc-test: xfail
+expect double 3 == 7
@@ -0,0 +1,5 @@
+package "marked-tests"
+    exposes [
+        Marked,
+    ]
+    packages {}
@@ -0,0 +1,19 @@
+interface Parallel
+    exposes [
+        double,
+    ]
+    imports []
+
+double = \num -> num * 2
+
+explode = \_ -> crash "this expect crashes"
+
+expect double 1 == 2
+
+expect double 2 == 5
+
+expect explode {} == 1
+
+expect double 3 == 6
+
+expect double 4 == 9
@@ -0,0 +1,5 @@
+package "parallel-tests"
+    exposes [
+        Parallel,
+    ]
+    packages {}
@@ -0,0 +1,7 @@
+app "hot-reload"
+    packages { pf: "platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+main : Str
+main = "Hello from the first build"
@@ -0,0 +1,127 @@
+// A hot-reload host for `roc dev --hot`. It loads the newest build of the app, prints what the
+// app returns along with how many builds it has loaded so far, and then waits for the next
+// build. It exits after the second one, so a test can tell that it kept its count in between.
+//
+// It has to be linked with -rdynamic, so the app's library can find roc_alloc and friends.
+#include <dlfcn.h>
+#include <fcntl.h>
+#include <signal.h>
+#include <stdbool.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <sys/types.h>
+#include <unistd.h>
+
+void* roc_alloc(size_t size, unsigned int alignment) { return malloc(size); }
+
+void* roc_realloc(void* ptr, size_t new_size, size_t old_size, unsigned int alignment) {
+  return realloc(ptr, new_size);
+}
+
+void roc_dealloc(void* ptr, unsigned int alignment) { free(ptr); }
+
+void roc_panic(void* ptr, unsigned int alignment) {
+  char* msg = (char*)ptr;
+  fprintf(stderr,
+          "Application crashed with message\n\n    %s\n\nShutting down\n", msg);
+  exit(1);
+}
+
+void roc_dbg(char* loc, char* msg, char* src) {
+  fprintf(stderr, "[%s] %s = %s\n", loc, src, msg);
+}
+
+void* roc_memset(void* str, int c, size_t n) { return memset(str, c, n); }
+
+int roc_shm_open(char* name, int oflag, int mode) { return shm_open(name, oflag, mode); }
+
+void* roc_mmap(void* addr, int length, int prot, int flags, int fd, int offset) {
+  return mmap(addr, length, prot, flags, fd, offset);
+}
+
+int roc_getppid() { return getppid(); }
+
+struct RocStr {
+  char* bytes;
+  size_t len;
+  size_t capacity;
+};
+
+bool is_small_str(struct RocStr* str) { return ((ssize_t)str->capacity) < 0; }
+
+static volatile sig_atomic_t reload_requested = 0;
+
+static void request_reload(int signal) { reload_requested = 1; }
+
+// Loads the build that the manifest points at
+static void* load_newest_build(const char* manifest_path) {
+  char path[4096] = {0};
+  FILE* manifest = fopen(manifest_path, "rb");
+
+  if (manifest == NULL || fread(path, 1, sizeof(path) - 1, manifest) == 0) {
+    fprintf(stderr, "Could not read the manifest at %s\n", manifest_path);
+    exit(1);
+  }
+
+  fclose(manifest);
+
+  // The previous build stays loaded, in case anything still points into it
+  void* library = dlopen(path, RTLD_NOW | RTLD_LOCAL);
+
+  if (library == NULL) {
+    fprintf(stderr, "Could not load %s: %s\n", path, dlerror());
+    exit(1);
+  }
+
+  return library;
+}
+
+int main() {
+  const char* manifest_path = getenv("ROC_HOT_RELOAD_MANIFEST");
+
+  if (manifest_path == NULL) {
+    fprintf(stderr, "This host is meant to be started by `roc dev --hot`\n");
+    return 1;
+  }
+
+  signal(SIGUSR1, request_reload);
+
+  // The host's own state, which has to survive reloading the app
+  int loaded_builds = 0;
+
+  while (true) {
+    void* library = load_newest_build(manifest_path);
+    void (*main_for_host)(struct RocStr*) =
+        (void (*)(struct RocStr*))dlsym(library, "roc__mainForHost_1_exposed_generic");
+
+    if (main_for_host == NULL) {
+      fprintf(stderr, "The app has no mainForHost: %s\n", dlerror());
+      return 1;
+    }
+
+    struct RocStr str;
+    main_for_host(&str);
+    loaded_builds += 1;
+
+    if (is_small_str(&str)) {
+      size_t len = ((char*)&str)[sizeof(str) - 1] ^ 0x80;
+      printf("%d: %.*s\n", loaded_builds, (int)len, (char*)&str);
+    } else {
+      printf("%d: %.*s\n", loaded_builds, (int)str.len, str.bytes);
+    }
+
+    fflush(stdout);
+
+    if (loaded_builds == 2) {
+      return 0;
+    }
+
+    while (!reload_requested) {
+      usleep(10 * 1000);
+    }
+
+    reload_requested = 0;
+  }
+}
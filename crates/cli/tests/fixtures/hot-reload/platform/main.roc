@@ -0,0 +1,9 @@
+platform "hot-reload"
+    requires {}{ main : Str }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+mainForHost : Str
+mainForHost = main
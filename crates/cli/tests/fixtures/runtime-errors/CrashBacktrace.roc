@@ -0,0 +1,21 @@
+app "crash-backtrace"
+    packages { pf: "../multi-dep-str/platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+main : Str
+main = greet "World"
+
+greet : Str -> Str
+greet = \name ->
+    if Str.isEmpty name then
+        "Hello!"
+    else
+        shout name
+
+shout : Str -> Str
+shout = \name ->
+    if Str.countUtf8Bytes name > 3 then
+        crash "too long to shout"
+    else
+        name
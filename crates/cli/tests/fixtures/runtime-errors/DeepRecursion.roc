@@ -0,0 +1,15 @@
+app "deep-recursion"
+    packages { pf: "../multi-dep-str/platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+main : Str
+main = Num.toStr (sumTo 10_000)
+
+# Not tail recursive, so this gets a stack check wherever those are supported
+sumTo : U64 -> U64
+sumTo = \n ->
+    if n == 0 then
+        0
+    else
+        n + sumTo (n - 1)
@@ -0,0 +1,15 @@
+app "stack-overflow"
+    packages { pf: "../multi-dep-str/platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+main : Str
+main = Num.toStr (sumTo 100_000_000)
+
+# Not tail recursive, so this needs a stack frame for every number
+sumTo : U64 -> U64
+sumTo = \n ->
+    if n == 0 then
+        0
+    else
+        n + sumTo (n - 1)
@@ -181,6 +181,18 @@ where
         };
         m.add_const(STATIC_LIST_NAME, static_list_def)?;
 
+        // the results of these thunks are memoized by the test runner, and thus shared
+        let fixtures: MutSet<_> = match entry_point {
+            EntryPoint::Single(_) => MutSet::default(),
+            EntryPoint::Expects { fixtures, .. } => fixtures
+                .iter()
+                .map(|(symbol, layout)| {
+                    let it = layout.arguments.iter().copied();
+                    func_name_bytes_help(*symbol, it, layout.niche, layout.result)
+                })
+                .collect(),
+        };
+
         let mut type_definitions = MutSet::default();
         let mut host_exposed_functions = Vec::new();
         let mut erased_functions = Vec::new();
@@ -224,7 +236,8 @@ where
                 );
             }
 
-            let (spec, type_names) = proc_spec(arena, interner, proc)?;
+            let shared_result = fixtures.contains(&bytes);
+            let (spec, type_names) = proc_spec(arena, interner, proc, shared_result)?;
 
             if proc.is_erased {
                 let args = &*arena.alloc_slice_fill_iter(proc.args.iter().map(|(lay, _)| *lay));
@@ -266,8 +279,12 @@ where
                 let entry_point_name = FuncName(ENTRY_POINT_NAME);
                 m.add_func(entry_point_name, entry_point_function)?;
             }
-            EntryPoint::Expects { symbols } => {
-                // construct a big pattern match picking one of the expects at random
+            EntryPoint::Expects {
+                symbols,
+                properties,
+                fixtures: _,
+            } => {
+                // construct a big pattern match picking one of the expects (or properties) at random
                 let layout: ProcLayout<'a> = ProcLayout {
                     arguments: &[],
                     result: Layout::UNIT,
@@ -282,6 +299,17 @@ where
                             [].as_slice(),
                         )
                     })
+                    .chain(properties.iter().map(|(symbol, proc_layout)| {
+                        let it = proc_layout.arguments.iter().copied();
+                        let bytes = func_name_bytes_help(
+                            *symbol,
+                            it,
+                            proc_layout.niche,
+                            proc_layout.result,
+                        );
+
+                        (bytes, proc_layout.arguments)
+                    }))
                     .collect();
 
                 let mut env = Env::new();
@@ -448,6 +476,7 @@ fn proc_spec<'a>(
     arena: &'a Bump,
     interner: &STLayoutInterner<'a>,
     proc: &Proc<'a>,
+    shared_result: bool,
 ) -> Result<(FuncDef, MutSet<UnionLayout<'a>>)> {
     let mut builder = FuncDefBuilder::new();
     let mut env = Env::new();
@@ -472,7 +501,6 @@ fn proc_spec<'a>(
         &proc.body,
     )?;
 
-    let root = BlockExpr(block, value_id);
     let args_struct_layout = LayoutRepr::struct_(argument_layouts.into_bump_slice());
     let arg_type_id = layout_spec(&mut env, &mut builder, interner, args_struct_layout)?;
     let ret_type_id = layout_spec(
@@ -482,6 +510,16 @@ fn proc_spec<'a>(
         interner.get_repr(proc.ret_layout),
     )?;
 
+    let value_id = if shared_result {
+        // a value that may contain the heap cells of a constant is never updated in place
+        let constant = new_static_string(&mut builder, block)?;
+        builder.add_unknown_with(block, &[value_id, constant], ret_type_id)?
+    } else {
+        value_id
+    };
+
+    let root = BlockExpr(block, value_id);
+
     let spec = builder.build(arg_type_id, ret_type_id, root)?;
 
     Ok((spec, env.type_names))
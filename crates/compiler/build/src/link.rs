@@ -1,4 +1,4 @@
-use crate::target::arch_str;
+use crate::target::{arch_str, target_zig_str};
 use libloading::{Error, Library};
 use roc_command_utils::{cargo, clang, rustup, zig};
 use roc_debug_flags;
@@ -33,7 +33,15 @@ pub fn link(
     input_paths: &[&str],
     link_type: LinkType,
 ) -> io::Result<(Child, PathBuf)> {
+    if link_type == LinkType::StaticExecutable {
+        return link_static(target, output_path, input_paths);
+    }
+
     match target.arch_os() {
+        // The system linkers below look for this machine's libc, so use zig's bundled one instead
+        (arch, _) if arch != Architecture::Wasm32 && target != Target::default() => {
+            link_cross(target, output_path, input_paths, link_type)
+        }
         (Architecture::Wasm32, _) => link_wasm32(target, output_path, input_paths, link_type),
         (_, OperatingSystem::Linux) => link_linux(target, output_path, input_paths, link_type),
         (_, OperatingSystem::Mac) => link_macos(target, output_path, input_paths, link_type),
@@ -42,6 +50,25 @@ pub fn link(
     }
 }
 
+/// The static archive a platform provides for fully static builds, e.g. `linux-x64-musl.a`.
+/// It has to be built against musl, so it can be linked without the system's libc.
+pub fn static_host_file(target: Target, platform_main_roc: &Path) -> PathBuf {
+    platform_main_roc.with_file_name(format!("{target}-musl.a"))
+}
+
+/// The host a platform provides for `roc dev --hot`, e.g. `linux-x64-hot-host`. Instead of being
+/// linked with the app, it loads the app as a shared library, and loads it again after each rebuild.
+pub fn hot_reload_host_file(target: Target, platform_main_roc: &Path) -> PathBuf {
+    let file_name = format!("{target}-hot-host");
+
+    match target.executable_file_ext() {
+        Some(ext) => platform_main_roc
+            .with_file_name(file_name)
+            .with_extension(ext),
+        None => platform_main_roc.with_file_name(file_name),
+    }
+}
+
 /// Same format as the precompiled host filename, except with a file extension like ".o" or ".obj"
 pub fn legacy_host_file(target: Target, platform_main_roc: &Path) -> PathBuf {
     let lib_ext = target.static_library_file_ext();
@@ -997,6 +1024,9 @@ fn link_linux(
             )
         }
         LinkType::None => internal_error!("link_linux should not be called with link type of none"),
+        LinkType::StaticExecutable => {
+            internal_error!("static executables are linked by link_static")
+        }
     };
 
     let env_path = env::var("PATH").unwrap_or_else(|_| "".to_string());
@@ -1069,6 +1099,9 @@ fn link_macos(
             (vec!["-dylib", "-undefined", "dynamic_lookup"], output_path)
         }
         LinkType::None => internal_error!("link_macos should not be called with link type of none"),
+        LinkType::StaticExecutable => {
+            internal_error!("static executables are linked by link_static")
+        }
     };
 
     let arch = match target.architecture() {
@@ -1184,17 +1217,30 @@ fn get_macos_version() -> String {
         .join(".")
 }
 
+/// Default WASI implementations of the functions every host has to provide to a Roc app.
+const WASI_SHIM_SRC: &str = include_str!("wasi_shim.c");
+
 fn link_wasm32(
-    _target: Target,
+    target: Target,
     output_path: PathBuf,
     input_paths: &[&str],
     _link_type: LinkType,
 ) -> io::Result<(Child, PathBuf)> {
-    let child = zig()
+    let wasi_shim = if target == Target::Wasm32Wasi {
+        let mut file = tempfile::Builder::new().suffix(".c").tempfile()?;
+        io::Write::write_all(&mut file, WASI_SHIM_SRC.as_bytes())?;
+
+        Some(file)
+    } else {
+        None
+    };
+
+    let mut child = zig()
         // .env_clear()
         // .env("PATH", &env_path)
         .args(["build-exe"])
         .args(input_paths)
+        .args(wasi_shim.iter().map(|file| file.path()))
         .args([
             // include wasi libc
             // TOOD: This now compiles fine with `-lc`. That said, the output file doesn't work.
@@ -1216,9 +1262,63 @@ fn link_wasm32(
         ])
         .spawn()?;
 
+    if wasi_shim.is_some() {
+        // Zig has to finish reading the shim before the tempfile gets dropped (and thus deleted).
+        // The exit status is kept, so the caller waiting on the child again still sees it.
+        child.wait()?;
+    }
+
     Ok((child, output_path))
 }
 
+/// Link for a machine other than this one, using the libc zig bundles for the target.
+fn link_cross(
+    target: Target,
+    output_path: PathBuf,
+    input_paths: &[&str],
+    link_type: LinkType,
+) -> io::Result<(Child, PathBuf)> {
+    let mut zig_cmd = zig();
+
+    let output_path = match link_type {
+        LinkType::Executable => {
+            zig_cmd.args(["build-exe"]);
+
+            if target.operating_system() == OperatingSystem::Windows {
+                zig_cmd.args(["--subsystem", "console"]);
+            }
+
+            output_path
+        }
+        LinkType::Dylib => {
+            zig_cmd.args(["build-lib", "-dynamic"]);
+
+            let extension = match target.operating_system() {
+                OperatingSystem::Windows => "dll",
+                OperatingSystem::Mac => "dylib",
+                _ => "so",
+            };
+
+            output_path.with_extension(extension)
+        }
+        LinkType::None => internal_error!("link_cross should not be called with link type of none"),
+        LinkType::StaticExecutable => {
+            internal_error!("static executables are linked by link_static")
+        }
+    };
+
+    zig_cmd.args(input_paths).args([
+        "-target",
+        target_zig_str(target),
+        "-lc",
+        &format!("-femit-bin={}", output_path.to_str().unwrap()),
+    ]);
+
+    debug_print_command(&zig_cmd);
+
+    Ok((zig_cmd.spawn()?, output_path))
+}
+
 fn link_windows(
     output_path: PathBuf,
     input_paths: &[&str],
@@ -1262,10 +1362,43 @@ fn link_windows(
 
             Ok((child, output_path))
         }
-        LinkType::None => todo!(),
+        LinkType::None => {
+            internal_error!("link_windows should not be called with link type of none")
+        }
+        LinkType::StaticExecutable => {
+            internal_error!("static executables are linked by link_static")
+        }
     }
 }
 
+/// Link a Linux executable with no dynamic dependencies, using the musl that zig bundles.
+fn link_static(
+    target: Target,
+    output_path: PathBuf,
+    input_paths: &[&str],
+) -> io::Result<(Child, PathBuf)> {
+    let zig_target = match target {
+        Target::LinuxX32 => "i386-linux-musl",
+        Target::LinuxX64 => "x86_64-linux-musl",
+        Target::LinuxArm64 => "aarch64-linux-musl",
+        _ => internal_error!("static linking is only supported for Linux, not {target}"),
+    };
+
+    let mut zig_cmd = zig();
+
+    zig_cmd.args(["build-exe"]).args(input_paths).args([
+        "-target",
+        zig_target,
+        "-static",
+        "-lc",
+        &format!("-femit-bin={}", output_path.to_str().unwrap()),
+    ]);
+
+    debug_print_command(&zig_cmd);
+
+    Ok((zig_cmd.spawn()?, output_path))
+}
+
 pub fn llvm_module_to_dylib(
     module: &inkwell::module::Module,
     target: Target,
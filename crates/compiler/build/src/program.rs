@@ -1,20 +1,29 @@
 use crate::link::{
-    legacy_host_file, link, preprocess_host_wasm32, rebuild_host, LinkType, LinkingStrategy,
+    legacy_host_file, link, preprocess_host_wasm32, rebuild_host, static_host_file, LinkType,
+    LinkingStrategy,
 };
 use bumpalo::Bump;
 use inkwell::memory_buffer::MemoryBuffer;
+use roc_collections::MutMap;
 use roc_error_macros::internal_error;
 use roc_gen_dev::AssemblyBackendMode;
-use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
+use roc_gen_llvm::llvm::build::{module_from_builtins, AliasAnalysisOptions, LlvmBackendMode};
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{
     EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig, LoadMonomorphizedError,
     LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
 };
+use roc_module::symbol::ModuleId;
+use roc_mono::build_info::BuildInfo;
+use roc_mono::debug_info::{reproducible_path, DebugLocations};
+use roc_mono::inline_hints::DEFAULT_INLINE_THRESHOLD;
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
+use roc_mono::layout_stats::LayoutStats;
 use roc_packaging::cache::RocCacheDir;
+use roc_region::all::{LineColumn, LineInfo};
 use roc_reporting::{
-    cli::{report_problems, Problems},
+    cli::{render_problems, OutputFormat, Problems, RenderedProblems},
+    diagnostic::Diagnostic,
     report::{RenderTarget, DEFAULT_PALETTE},
 };
 use roc_target::{Architecture, Target};
@@ -38,22 +47,29 @@ pub struct CodeGenTiming {
     pub total: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
-    report_problems(
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    format: OutputFormat,
+) -> Problems {
+    render_problems(
+        format,
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
     )
+    .print()
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
-    report_problems(
+pub fn report_problems_typechecked(loaded: &mut LoadedModule, format: OutputFormat) -> Problems {
+    render_problems(
+        format,
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
     )
+    .print()
 }
 
 pub enum CodeObject {
@@ -79,13 +95,89 @@ pub enum CodeGenBackend {
     Wasm,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct CodeGenOptions {
     pub backend: CodeGenBackend,
     pub opt_level: OptLevel,
     pub emit_debug_info: bool,
-    pub emit_llvm_ir: bool,
+    /// Attach the Roc file and line of each procedure to its debug info
+    pub emit_debug_locations: bool,
+    pub emit: EmitArtifacts,
     pub fuzz: bool,
+    /// Keep a frame pointer in every function, so a sampling profiler can walk the stack
+    pub frame_pointers: bool,
+    /// The largest proc (in mono statements) that LLVM is hinted to inline; 0 turns hints off
+    pub inline_threshold: u32,
+    pub alias_analysis: AliasAnalysisOptions,
+    /// Print a summary of the interned layouts after mono
+    pub verbose_layouts: bool,
+    /// Embed no paths that depend on where the app is, so identical sources build to identical
+    /// binaries
+    pub reproducible: bool,
+}
+
+impl CodeGenOptions {
+    /// The flags that make `roc build` compile the same way these options do, which the binary
+    /// records in its [BuildInfo]
+    fn build_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+
+        if !matches!(self.backend, CodeGenBackend::Llvm(_)) {
+            flags.push("--dev".to_string());
+        }
+
+        match self.opt_level {
+            OptLevel::Development | OptLevel::Normal => {}
+            OptLevel::Size => flags.push("--opt-size".to_string()),
+            OptLevel::Optimize => flags.push("--optimize".to_string()),
+        }
+
+        if self.emit_debug_locations {
+            flags.push("--debug-info".to_string());
+        }
+
+        if self.fuzz {
+            flags.push("--fuzz".to_string());
+        }
+
+        if self.inline_threshold != DEFAULT_INLINE_THRESHOLD {
+            flags.push(format!("--inline-threshold={}", self.inline_threshold));
+        }
+
+        if self.alias_analysis.disabled {
+            flags.push("--no-alias-analysis".to_string());
+        }
+
+        if self.reproducible {
+            flags.push("--reproducible".to_string());
+        }
+
+        flags
+    }
+}
+
+/// Intermediate artifacts of code gen to write out, so they can be inspected
+#[derive(Debug, Clone, Default)]
+pub struct EmitArtifacts {
+    /// The LLVM IR after optimization, as a `.ll` file
+    pub llvm_ir: bool,
+    /// The assembly LLVM generates for the app, as a `.s` file
+    pub asm: bool,
+    /// The app's object file, before it's linked with the platform
+    pub obj: bool,
+    /// Where to write the artifacts. By default they go next to the `.roc` file.
+    pub dir: Option<PathBuf>,
+}
+
+impl EmitArtifacts {
+    fn path(&self, roc_file_path: &Path, extension: &str) -> PathBuf {
+        let path = match &self.dir {
+            Some(dir) => dir.join(roc_file_path.file_name().unwrap_or_default()),
+            None => roc_file_path.to_path_buf(),
+        };
+
+        path.with_extension(extension)
+    }
 }
 
 type GenFromMono<'a> = (CodeObject, CodeGenTiming, ExpectMetadata<'a>);
@@ -96,15 +188,33 @@ pub fn gen_from_mono_module<'a>(
     loaded: MonomorphizedModule<'a>,
     roc_file_path: &Path,
     target: Target,
-    code_gen_options: CodeGenOptions,
+    code_gen_options: &CodeGenOptions,
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
+    linking_strategy: LinkingStrategy,
 ) -> GenFromMono<'a> {
+    let _span = roc_tracing::info_span!("codegen", backend = ?code_gen_options.backend).entered();
+
+    let mut loaded = loaded;
+
+    if code_gen_options.reproducible {
+        // The debug info of every backend gets its file names from here
+        let root_dir = roc_file_path.parent().unwrap_or(Path::new(""));
+
+        for (module_path, _) in loaded.sources.values_mut() {
+            *module_path = reproducible_path(module_path, root_dir);
+        }
+    }
+
     let path = roc_file_path;
     let debug = code_gen_options.emit_debug_info;
-    let emit_llvm_ir = code_gen_options.emit_llvm_ir;
+    let emit = &code_gen_options.emit;
     let fuzz = code_gen_options.fuzz;
     let opt = code_gen_options.opt_level;
+    let build_info = BuildInfo::new(
+        crate::target::target_triple_str(target),
+        code_gen_options.build_flags(),
+    );
 
     match code_gen_options.backend {
         CodeGenBackend::Wasm => gen_from_mono_module_dev(
@@ -114,6 +224,7 @@ pub fn gen_from_mono_module<'a>(
             preprocessed_host_path,
             wasm_dev_stack_bytes,
             AssemblyBackendMode::Binary, // dummy value, unused in practice
+            &build_info,
         ),
         CodeGenBackend::Assembly(backend_mode) => gen_from_mono_module_dev(
             arena,
@@ -122,18 +233,33 @@ pub fn gen_from_mono_module<'a>(
             preprocessed_host_path,
             wasm_dev_stack_bytes,
             backend_mode,
+            &build_info,
         ),
-        CodeGenBackend::Llvm(backend_mode) => gen_from_mono_module_llvm(
-            arena,
-            loaded,
-            path,
-            target,
-            opt,
-            backend_mode,
-            debug,
-            emit_llvm_ir,
-            fuzz,
-        ),
+        CodeGenBackend::Llvm(backend_mode) => {
+            let debug_locations = if code_gen_options.emit_debug_locations {
+                DebugLocations::new(&loaded.sources, &loaded.def_regions)
+            } else {
+                DebugLocations::default()
+            };
+
+            gen_from_mono_module_llvm(
+                arena,
+                loaded,
+                path,
+                target,
+                opt,
+                backend_mode,
+                debug,
+                debug_locations,
+                emit,
+                fuzz,
+                code_gen_options.frame_pointers,
+                code_gen_options.inline_threshold,
+                code_gen_options.alias_analysis,
+                linking_strategy,
+                &build_info,
+            )
+        }
     }
 }
 
@@ -149,8 +275,14 @@ fn gen_from_mono_module_llvm<'a>(
     opt_level: OptLevel,
     backend_mode: LlvmBackendMode,
     emit_debug_info: bool,
-    emit_llvm_ir: bool,
+    debug_locations: DebugLocations,
+    emit: &EmitArtifacts,
     fuzz: bool,
+    frame_pointers: bool,
+    inline_threshold: u32,
+    alias_analysis: AliasAnalysisOptions,
+    linking_strategy: LinkingStrategy,
+    build_info: &BuildInfo,
 ) -> GenFromMono<'a> {
     use crate::target::{self, convert_opt_level};
     use inkwell::attributes::{Attribute, AttributeLoc};
@@ -164,12 +296,7 @@ fn gen_from_mono_module_llvm<'a>(
     let context = Context::create();
     let module = arena.alloc(module_from_builtins(target, &context, "app"));
 
-    let app_ll_file = {
-        let mut roc_file_path_buf = PathBuf::from(roc_file_path);
-        roc_file_path_buf.set_extension("ll");
-
-        roc_file_path_buf
-    };
+    let app_ll_file = emit.path(roc_file_path, "ll");
 
     let kind_id = Attribute::get_named_enum_kind_id("alwaysinline");
     debug_assert!(kind_id > 0);
@@ -210,6 +337,11 @@ fn gen_from_mono_module_llvm<'a>(
         module,
         target,
         mode: backend_mode,
+        debug_locations,
+        inline_threshold,
+        alias_analysis,
+        // the surgical linker supports neither thread-local storage nor calls into libc
+        stack_checks: linking_strategy != LinkingStrategy::Surgical,
 
         exposed_to_host: loaded
             .exposed_to_host
@@ -227,14 +359,22 @@ fn gen_from_mono_module_llvm<'a>(
         EntryPoint::Executable {
             exposed_to_host,
             platform_path: _,
+            app_name,
         } => {
+            roc_gen_llvm::llvm::build::expose_app_name(&env, &app_name);
+            roc_gen_llvm::llvm::build::expose_build_info(&env, build_info);
+
             // TODO support multiple of these!
             debug_assert_eq!(exposed_to_host.len(), 1);
             let (symbol, layout) = exposed_to_host[0];
 
             roc_mono::ir::EntryPoint::Single(SingleEntryPoint { symbol, layout })
         }
-        EntryPoint::Test => roc_mono::ir::EntryPoint::Expects { symbols: &[] },
+        EntryPoint::Test => roc_mono::ir::EntryPoint::Expects {
+            symbols: &[],
+            properties: &[],
+            fixtures: &[],
+        },
     };
 
     roc_gen_llvm::llvm::build::build_procedures(
@@ -248,6 +388,16 @@ fn gen_from_mono_module_llvm<'a>(
         &loaded.glue_layouts,
     );
 
+    if frame_pointers {
+        let frame_pointer_attr = context.create_string_attribute("frame-pointer", "all");
+
+        for function in module.get_functions() {
+            if function.count_basic_blocks() > 0 {
+                function.add_attribute(AttributeLoc::Function, frame_pointer_attr);
+            }
+        }
+    }
+
     // We are now finished building the LLVM IR.
     let generate_final_ir = all_code_gen_start.elapsed();
     let code_gen_object_start = Instant::now();
@@ -350,10 +500,28 @@ fn gen_from_mono_module_llvm<'a>(
 
         assert!(opt_output.stderr.is_empty(), "{opt_output:#?}");
 
-        if emit_llvm_ir {
+        if emit.llvm_ir {
             eprintln!("Emitting LLVM IR to {}", &app_ll_file.display());
 
-            std::fs::copy(temp_app_processed_file, app_ll_file).unwrap();
+            std::fs::copy(&temp_app_processed_file, &app_ll_file).unwrap();
+        }
+
+        if emit.asm {
+            let app_s_file = emit.path(roc_file_path, "s");
+            eprintln!("Emitting assembly to {}", app_s_file.display());
+
+            let asm_output = Command::new("llc")
+                .args([
+                    "-relocation-model=pic",
+                    "-filetype=asm",
+                    &temp_app_processed_file_str,
+                    "-o",
+                    app_s_file.to_str().unwrap(),
+                ])
+                .output()
+                .unwrap();
+
+            assert!(asm_output.status.success(), "{asm_output:#?}");
         }
 
         // write the .o file. Note that this builds the .o for the local machine,
@@ -379,7 +547,7 @@ fn gen_from_mono_module_llvm<'a>(
 
         MemoryBuffer::create_from_file(&temp_app_o_file).expect("memory buffer creation works")
     } else {
-        if emit_llvm_ir {
+        if emit.llvm_ir {
             eprintln!("Emitting LLVM IR to {}", &app_ll_file.display());
             module.print_to_file(&app_ll_file).unwrap();
         }
@@ -391,11 +559,24 @@ fn gen_from_mono_module_llvm<'a>(
                 let target_machine =
                     target::target_machine(target, convert_opt_level(opt_level), reloc).unwrap();
 
+                if emit.asm {
+                    let app_s_file = emit.path(roc_file_path, "s");
+                    eprintln!("Emitting assembly to {}", app_s_file.display());
+
+                    target_machine
+                        .write_to_file(env.module, FileType::Assembly, &app_s_file)
+                        .expect("Writing .s file failed");
+                }
+
                 target_machine
                     .write_to_memory_buffer(env.module, FileType::Object)
                     .expect("Writing .o file failed")
             }
             Architecture::Wasm32 => {
+                if emit.asm {
+                    eprintln!("Emitting assembly is not supported when targeting wasm32");
+                }
+
                 // Useful for debugging
                 // module.print_to_file(app_ll_file);
                 module.write_bitcode_to_memory()
@@ -433,6 +614,7 @@ fn gen_from_mono_module_dev<'a>(
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
     backend_mode: AssemblyBackendMode,
+    build_info: &BuildInfo,
 ) -> GenFromMono<'a> {
     match target.architecture() {
         Architecture::Wasm32 => gen_from_mono_module_dev_wasm32(
@@ -442,7 +624,7 @@ fn gen_from_mono_module_dev<'a>(
             wasm_dev_stack_bytes,
         ),
         Architecture::X86_64 | Architecture::Aarch64 => {
-            gen_from_mono_module_dev_assembly(arena, loaded, target, backend_mode)
+            gen_from_mono_module_dev_assembly(arena, loaded, target, backend_mode, build_info)
         }
         _ => todo!(),
     }
@@ -456,10 +638,11 @@ pub fn gen_from_mono_module_dev<'a>(
     _host_input_path: &Path,
     _wasm_dev_stack_bytes: Option<u32>,
     backend_mode: AssemblyBackendMode,
+    build_info: &BuildInfo,
 ) -> GenFromMono<'a> {
     match target.architecture() {
         Architecture::X86_64 | Architecture::Aarch64 => {
-            gen_from_mono_module_dev_assembly(arena, loaded, target, backend_mode)
+            gen_from_mono_module_dev_assembly(arena, loaded, target, backend_mode, build_info)
         }
         _ => todo!(),
     }
@@ -544,6 +727,7 @@ fn gen_from_mono_module_dev_assembly<'a>(
     loaded: MonomorphizedModule<'a>,
     target: Target,
     backend_mode: AssemblyBackendMode,
+    build_info: &BuildInfo,
 ) -> GenFromMono<'a> {
     let all_code_gen_start = Instant::now();
 
@@ -555,6 +739,8 @@ fn gen_from_mono_module_dev_assembly<'a>(
         mut interns,
         exposed_to_host,
         mut layout_interner,
+        sources,
+        def_regions,
         ..
     } = loaded;
 
@@ -564,11 +750,17 @@ fn gen_from_mono_module_dev_assembly<'a>(
         exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
         lazy_literals,
         mode: backend_mode,
+        debug_locations: DebugLocations::new(&sources, &def_regions),
     };
 
-    let module_object =
+    let mut module_object =
         roc_gen_dev::build_module(&env, &mut interns, &mut layout_interner, target, procedures);
 
+    if let EntryPoint::Executable { app_name, .. } = &loaded.entry_point {
+        roc_gen_dev::define_app_name(&mut module_object, app_name);
+        roc_gen_dev::define_build_info(&mut module_object, build_info);
+    }
+
     let generate_final_ir = all_code_gen_start.elapsed();
     let code_gen_object_start = Instant::now();
 
@@ -608,6 +800,10 @@ fn report_timing(buf: &mut String, label: &str, duration: Duration) {
 
 pub struct BuiltFile<'a> {
     pub binary_path: PathBuf,
+    /// The main.roc of the platform the app was built with
+    pub platform_path: PathBuf,
+    /// The URL of every package loaded over HTTPS, for roc.lock
+    pub package_urls: Vec<String>,
     pub problems: Problems,
     pub total_time: Duration,
     pub expect_metadata: ExpectMetadata<'a>,
@@ -649,10 +845,15 @@ pub fn handle_error_module(
     total_time: std::time::Duration,
     filename: &OsStr,
     print_run_anyway_hint: bool,
+    format: OutputFormat,
 ) -> std::io::Result<i32> {
     debug_assert!(module.total_problems() > 0);
 
-    let problems = report_problems_typechecked(&mut module);
+    let problems = report_problems_typechecked(&mut module, format);
+
+    if let OutputFormat::Json = format {
+        return Ok(problems.exit_code());
+    }
 
     problems.print_error_warning_count(total_time);
 
@@ -671,16 +872,24 @@ pub fn handle_error_module(
     Ok(problems.exit_code())
 }
 
-pub fn handle_loading_problem(problem: LoadingProblem) -> std::io::Result<i32> {
+pub fn handle_loading_problem(
+    problem: LoadingProblem,
+    format: OutputFormat,
+) -> std::io::Result<i32> {
     match problem {
         LoadingProblem::FormattedReport(report) => {
-            print!("{report}");
+            match format {
+                OutputFormat::Human => print!("{report}"),
+                OutputFormat::Json => {
+                    println!("{}", Diagnostic::from_formatted_report(&report).to_json())
+                }
+            }
             Ok(1)
         }
         _ => {
             // TODO: tighten up the types here, we should always end up with a
             // formatted report from load.
-            println!("Failed with error: {problem:?}");
+            format.print_human(format_args!("Failed with error: {problem:?}\n"));
             Ok(1)
         }
     }
@@ -714,6 +923,8 @@ pub fn standard_load_config(
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode,
+        reproducible: false,
+        coverage: false,
     }
 }
 
@@ -730,6 +941,7 @@ pub fn build_file<'a>(
     wasm_dev_stack_bytes: Option<u32>,
     roc_cache_dir: RocCacheDir<'_>,
     load_config: LoadConfig,
+    output_format: OutputFormat,
     out_path: Option<&Path>,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let compilation_start = Instant::now();
@@ -751,6 +963,7 @@ pub fn build_file<'a>(
         wasm_dev_stack_bytes,
         loaded,
         compilation_start,
+        output_format,
         out_path,
     )
 }
@@ -768,6 +981,7 @@ fn build_loaded_file<'a>(
     wasm_dev_stack_bytes: Option<u32>,
     loaded: roc_load::MonomorphizedModule<'a>,
     compilation_start: Instant,
+    output_format: OutputFormat,
     out_path: Option<&Path>,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let platform_main_roc = match &loaded.entry_point {
@@ -775,6 +989,8 @@ fn build_loaded_file<'a>(
         _ => unreachable!(),
     };
 
+    let package_urls = loaded.package_urls.iter().cloned().collect();
+
     // For example, if we're loading the platform from a URL, it's automatically prebuilt
     // even if the --prebuilt-platform CLI flag wasn't set.
     let is_platform_prebuilt = prebuilt_requested || loaded.uses_prebuilt_platform;
@@ -791,10 +1007,12 @@ fn build_loaded_file<'a>(
 
     // the preprocessed host is stored beside the platform's main.roc
     let preprocessed_host_path = if linking_strategy == LinkingStrategy::Legacy {
-        if target == Target::Wasm32 {
+        if target.architecture() == Architecture::Wasm32 {
             // when compiling a wasm application, we implicitly assume here that the host is in zig
             // and has a file called "host.zig"
             platform_main_roc.with_file_name("host.zig")
+        } else if link_type == LinkType::StaticExecutable {
+            static_host_file(target, &platform_main_roc)
         } else {
             legacy_host_file(target, &platform_main_roc)
         }
@@ -907,9 +1125,14 @@ fn build_loaded_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = report_problems_monomorphized(&mut loaded);
+    let problems = report_problems_monomorphized(&mut loaded, output_format);
     let loaded = loaded;
 
+    if code_gen_options.verbose_layouts {
+        let stats = LayoutStats::new(&loaded.layout_interner);
+        eprintln!("{}", stats.report(&loaded.layout_interner));
+    }
+
     enum HostRebuildTiming {
         BeforeApp(u128),
         ConcurrentWithApp(JoinHandle<u128>),
@@ -922,7 +1145,9 @@ fn build_loaded_file<'a>(
                 .expect("Failed to (re)build platform.");
 
             if emit_timings && !is_platform_prebuilt {
-                println!("Finished rebuilding the platform in {rebuild_duration} ms\n");
+                output_format.print_human(format_args!(
+                    "Finished rebuilding the platform in {rebuild_duration} ms\n\n"
+                ));
             }
 
             Some(HostRebuildTiming::BeforeApp(rebuild_duration))
@@ -938,11 +1163,21 @@ fn build_loaded_file<'a>(
         loaded,
         &app_module_path,
         target,
-        code_gen_options,
+        &code_gen_options,
         &preprocessed_host_path,
         wasm_dev_stack_bytes,
+        linking_strategy,
     );
 
+    if code_gen_options.emit.obj {
+        let app_o_file = code_gen_options
+            .emit
+            .path(&app_module_path, target.object_file_ext());
+        eprintln!("Emitting object file to {}", app_o_file.display());
+
+        std::fs::write(&app_o_file, &*roc_app_bytes).unwrap();
+    }
+
     buf.push('\n');
     buf.push_str("    ");
     buf.push_str("Code Generation");
@@ -961,27 +1196,30 @@ fn build_loaded_file<'a>(
     let size = roc_app_bytes.len();
 
     if emit_timings {
-        println!(
-            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{buf}"
-        );
+        output_format.print_human(format_args!(
+            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{buf}\n"
+        ));
 
-        println!(
-            "Finished compilation and code gen in {} ms\n\nProduced a app.o file of size {:?}\n",
+        output_format.print_human(format_args!(
+            "Finished compilation and code gen in {} ms\n\nProduced a app.o file of size {:?}\n\n",
             compilation_end.as_millis(),
             size,
-        );
+        ));
     }
 
     if let Some(HostRebuildTiming::ConcurrentWithApp(thread)) = opt_rebuild_timing {
         let rebuild_duration = thread.join().expect("Failed to (re)build platform.");
 
         if emit_timings && !is_platform_prebuilt {
-            println!("Finished rebuilding the platform in {rebuild_duration} ms\n");
+            output_format.print_human(format_args!(
+                "Finished rebuilding the platform in {rebuild_duration} ms\n\n"
+            ));
         }
     }
 
     // Step 2: link the prebuilt platform and compiled app
     let link_start = Instant::now();
+    let link_span = roc_tracing::info_span!("link").entered();
 
     match (linking_strategy, link_type) {
         (LinkingStrategy::Surgical, _) => {
@@ -997,7 +1235,7 @@ fn build_loaded_file<'a>(
             std::fs::write(&output_exe_path, &*roc_app_bytes).unwrap();
         }
         (LinkingStrategy::Legacy, _) => {
-            let extension = if target == Target::Wasm32 {
+            let extension = if target.architecture() == Architecture::Wasm32 {
                 // Legacy linker is only by used llvm wasm backend, not dev.
                 // llvm wasm backend directly emits a bitcode file when targeting wasi, not a `.o` or `.wasm` file.
                 // If we set the extension wrong, zig will print a ton of warnings when linking.
@@ -1049,15 +1287,21 @@ fn build_loaded_file<'a>(
     }
 
     let linking_time = link_start.elapsed();
+    link_span.exit();
 
     if emit_timings {
-        println!("Finished linking in {} ms\n", linking_time.as_millis());
+        output_format.print_human(format_args!(
+            "Finished linking in {} ms\n\n",
+            linking_time.as_millis()
+        ));
     }
 
     let total_time = compilation_start.elapsed();
 
     Ok(BuiltFile {
         binary_path: output_exe_path,
+        platform_path: platform_main_roc,
+        package_urls,
         problems,
         total_time,
         expect_metadata,
@@ -1113,6 +1357,7 @@ fn spawn_rebuild_thread(
         eprintln!("🔨 Rebuilding platform...");
 
         let rebuild_host_start = Instant::now();
+        let _span = roc_tracing::info_span!("rebuild host").entered();
 
         match linking_strategy {
             LinkingStrategy::Additive => {
@@ -1190,7 +1435,51 @@ pub fn check_file<'a>(
     emit_timings: bool,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
+    format: OutputFormat,
 ) -> Result<(Problems, Duration), LoadingProblem<'a>> {
+    let checked = check_file_rendered(
+        arena,
+        roc_file_path,
+        emit_timings,
+        roc_cache_dir,
+        threading,
+        format,
+        false,
+        None,
+        false,
+    )?;
+
+    Ok((checked.problems.print(), checked.total_time))
+}
+
+/// The outcome of checking a file, with its problems rendered but not yet printed.
+pub struct CheckedFile {
+    pub problems: RenderedProblems,
+    /// The paths of all the (non-builtin) modules that were loaded while checking
+    pub module_paths: Vec<PathBuf>,
+    pub total_time: Duration,
+    /// Every self-recursive call and whether it's a tail call, if that was asked for
+    pub tail_calls: Option<String>,
+    /// The type inferred at the position given to `--explain-type`, if that was asked for
+    pub explained_type: Option<String>,
+    /// How long each module spent in each phase, if that was asked for
+    pub timings_table: Option<String>,
+    /// The URLs of the packages that were loaded over HTTPS
+    pub package_urls: Vec<String>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn check_file_rendered<'a>(
+    arena: &'a Bump,
+    roc_file_path: PathBuf,
+    emit_timings: bool,
+    roc_cache_dir: RocCacheDir<'_>,
+    threading: Threading,
+    format: OutputFormat,
+    report_tail_calls: bool,
+    explain_type_at: Option<LineColumn>,
+    report_timings_table: bool,
+) -> Result<CheckedFile, LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
     // only used for generating errors. We don't do code generation, so hardcoding should be fine
@@ -1204,10 +1493,17 @@ pub fn check_file<'a>(
         // TODO: we may not want this for just checking.
         function_kind: FunctionKind::LambdaSet,
         // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render: match format {
+            OutputFormat::Human => RenderTarget::ColorTerminal,
+            // Problems the loader reports as pre-rendered text end up in a JSON string,
+            // so they must not contain escape codes.
+            OutputFormat::Json => RenderTarget::Generic,
+        },
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
+        reproducible: false,
+        coverage: false,
     };
     let mut loaded =
         roc_load::load_and_typecheck(arena, roc_file_path, roc_cache_dir, load_config)?;
@@ -1247,14 +1543,306 @@ pub fn check_file<'a>(
     let compilation_end = compilation_start.elapsed();
 
     if emit_timings {
-        println!(
-            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{buf}"
+        format.print_human(format_args!(
+            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{buf}\n"
+        ));
+
+        format.print_human(format_args!(
+            "Finished checking in {} ms\n\n",
+            compilation_end.as_millis()
+        ));
+    }
+
+    let module_paths = loaded
+        .sources
+        .iter()
+        .filter(|(module_id, _)| !module_id.is_builtin())
+        .map(|(_, (path, _))| path.clone())
+        .collect();
+
+    let tail_calls = report_tail_calls.then(|| render_tail_calls(&loaded));
+    let explained_type = explain_type_at.map(|position| render_type_at(&mut loaded, position));
+    let timings_table = report_timings_table.then(|| render_timings_table(&loaded));
+    let package_urls = loaded.package_urls.iter().cloned().collect();
+
+    let problems = render_problems(
+        format,
+        &loaded.sources,
+        &loaded.interns,
+        &mut loaded.can_problems,
+        &mut loaded.type_problems,
+    );
+
+    Ok(CheckedFile {
+        problems,
+        module_paths,
+        total_time: compilation_end,
+        tail_calls,
+        explained_type,
+        timings_table,
+        package_urls,
+    })
+}
+
+/// The type inferred for the narrowest expression or definition in the root module that
+/// contains `position` (which is 0-based), like `main.roc:4:9 x + 1 : Num *`
+///
+/// This finds the type the same way the language server does for a hover.
+fn render_type_at(loaded: &mut LoadedModule, position: LineColumn) -> String {
+    let module_id = loaded.module_id;
+    let (path, src) = &loaded.sources[&module_id];
+    let line_info = LineInfo::new(src);
+
+    // `convert_line_column` would carry a column past the end of a line onto the next one
+    let Some(line_len) = src.lines().nth(position.line as usize).map(str::len) else {
+        return format!(
+            "{} only has {} lines, so there's no line {}.\n",
+            path.display(),
+            line_info.num_lines(),
+            position.line + 1,
         );
+    };
+
+    if position.column as usize >= line_len {
+        return format!(
+            "Line {} of {} only has {line_len} columns, so there's no column {}.\n",
+            position.line + 1,
+            path.display(),
+            position.column + 1,
+        );
+    }
+
+    let found = loaded.declarations_by_id.get(&module_id).and_then(|decls| {
+        roc_can::traverse::find_closest_type_at(line_info.convert_line_column(position), decls)
+    });
+
+    let Some((region, var)) = found else {
+        return format!(
+            "There's no expression or definition at {}:{}:{}.\n",
+            path.display(),
+            position.line + 1,
+            position.column + 1,
+        );
+    };
+
+    let subs = loaded.solved.inner_mut();
+    let snapshot = subs.snapshot();
+    let type_str = roc_types::pretty_print::name_and_print_var(
+        var,
+        subs,
+        module_id,
+        &loaded.interns,
+        roc_types::pretty_print::DebugPrint::NOTHING,
+    );
+    subs.rollback_to(snapshot);
+
+    let LineColumn { line, column } = line_info.convert_pos(region.start());
+    let code = &src[region.start().offset as usize..region.end().offset as usize];
+
+    // Only show the code when it fits on the same line as its type
+    if code.contains('\n') {
+        format!(
+            "{}:{}:{} : {type_str}\n",
+            path.display(),
+            line + 1,
+            column + 1
+        )
+    } else {
+        format!(
+            "{}:{}:{} {code} : {type_str}\n",
+            path.display(),
+            line + 1,
+            column + 1
+        )
+    }
+}
+
+/// One line per self-recursive call in the checked (non-builtin) modules, like
+/// `main.roc:12:13 sum: not tail (its result is passed to another function)`
+fn render_tail_calls(loaded: &LoadedModule) -> String {
+    use std::fmt::Write;
+
+    let mut modules: Vec<_> = loaded
+        .declarations_by_id
+        .iter()
+        .filter(|(module_id, _)| !module_id.is_builtin())
+        .filter_map(|(module_id, decls)| Some((loaded.sources.get(module_id)?, decls)))
+        .collect();
+    modules.sort_by(|((a, _), _), ((b, _), _)| a.cmp(b));
+
+    let mut buf = String::new();
+
+    for ((path, src), decls) in modules {
+        let line_info = LineInfo::new(src);
+
+        for call in roc_can::tail_calls::tail_calls(decls) {
+            let LineColumn { line, column } = line_info.convert_pos(call.region.start());
+
+            writeln!(
+                buf,
+                "{}:{}:{} {}: {}",
+                path.display(),
+                line + 1,
+                column + 1,
+                call.function.as_str(&loaded.interns),
+                call.position,
+            )
+            .unwrap();
+        }
+    }
 
-        println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
+    if buf.is_empty() {
+        buf.push_str("No function calls itself.\n");
+    }
+
+    buf
+}
+
+/// A table of how long each (non-builtin) module spent in each phase, slowest first, followed by
+/// the critical path: the chain of imports that took the longest to get through. A module's types
+/// can't be solved until the ones of the modules it imports have been, so however many threads
+/// there are, the modules on that path are mostly checked one after another.
+fn render_timings_table(loaded: &LoadedModule) -> String {
+    use std::fmt::Write;
+
+    fn ms(duration: Duration) -> String {
+        format!("{:.2}", duration.as_secs_f64() * 1000.0)
+    }
+
+    let module_name = |module_id: ModuleId| match loaded.interns.module_name(module_id).as_str() {
+        "" => "Application Module",
+        name => name,
+    };
+
+    let mut modules: Vec<_> = loaded
+        .timings
+        .iter()
+        .filter(|(module_id, _)| !module_id.is_builtin())
+        .collect();
+    modules.sort_by_key(|(module_id, timing)| {
+        (std::cmp::Reverse(timing.busy()), module_name(**module_id))
+    });
+
+    let name_width = modules
+        .iter()
+        .map(|(module_id, _)| module_name(**module_id).len())
+        .chain(["Module (ms)".len(), "Total".len()])
+        .max()
+        .unwrap();
+
+    let mut buf = String::new();
+
+    let row = |buf: &mut String, name: &str, cells: [String; 6]| {
+        write!(buf, "{name:<name_width$}").unwrap();
+
+        for cell in cells {
+            write!(buf, "  {cell:>12}").unwrap();
+        }
+
+        buf.push('\n');
+    };
+
+    row(
+        &mut buf,
+        "Module (ms)",
+        [
+            "Parse",
+            "Canonicalize",
+            "Constrain",
+            "Solve",
+            "Mono",
+            "Total",
+        ]
+        .map(String::from),
+    );
+
+    let mut totals = [Duration::ZERO; 6];
+
+    for (module_id, timing) in modules.iter() {
+        let phases = [
+            timing.read_roc_file + timing.parse_header + timing.parse_body,
+            timing.canonicalize,
+            timing.constrain,
+            timing.solve,
+            timing.find_specializations + timing.make_specializations.iter().sum::<Duration>(),
+            timing.busy(),
+        ];
+
+        for (total, phase) in totals.iter_mut().zip(phases) {
+            *total += phase;
+        }
+
+        row(&mut buf, module_name(**module_id), phases.map(ms));
+    }
+
+    buf.push('\n');
+    row(&mut buf, "Total", totals.map(ms));
+
+    // The longest (busiest) chain of imports that ends at each module, found by going through
+    // the modules so that everything a module imports comes before it.
+    let mut longest_chain: MutMap<ModuleId, (Duration, Option<ModuleId>)> = MutMap::default();
+    let mut pending: Vec<_> = modules.iter().map(|(module_id, _)| **module_id).collect();
+
+    while !pending.is_empty() {
+        let before = pending.len();
+
+        pending.retain(|module_id| {
+            let imports = loaded.imports.get(module_id);
+            let deps = imports
+                .into_iter()
+                .flatten()
+                .filter(|dep| loaded.timings.contains_key(dep) && !dep.is_builtin());
+
+            let mut dep_chains = Vec::new();
+
+            for dep in deps {
+                match longest_chain.get(dep) {
+                    Some((chain, _)) => dep_chains.push((*chain, *dep)),
+                    // not ready yet
+                    None => return true,
+                }
+            }
+
+            let slowest_dep = dep_chains.into_iter().max_by_key(|(chain, _)| *chain);
+
+            let chain = slowest_dep.map_or(Duration::ZERO, |(chain, _)| chain)
+                + loaded.timings[module_id].busy();
+
+            longest_chain.insert(*module_id, (chain, slowest_dep.map(|(_, dep)| dep)));
+
+            false
+        });
+
+        if pending.len() == before {
+            // Only an import cycle could get here, and that would have been reported already
+            break;
+        }
+    }
+
+    let critical_end = longest_chain.iter().max_by_key(|(module_id, (chain, _))| {
+        (*chain, std::cmp::Reverse(module_name(**module_id)))
+    });
+
+    if let Some((end, (critical_time, _))) = critical_end {
+        let mut path = vec![module_name(*end)];
+        let mut current = *end;
+
+        while let Some((_, Some(dep))) = longest_chain.get(&current) {
+            path.push(module_name(*dep));
+            current = *dep;
+        }
+
+        writeln!(
+            buf,
+            "\nCritical path ({} of {} ms): {}",
+            ms(*critical_time),
+            ms(totals[5]),
+            path.join(" → ")
+        )
+        .unwrap();
     }
 
-    Ok((report_problems_typechecked(&mut loaded), compilation_end))
+    buf
 }
 
 pub fn build_str_test<'a>(
@@ -1269,8 +1857,14 @@ pub fn build_str_test<'a>(
         backend: CodeGenBackend::Llvm(LlvmBackendMode::Binary),
         opt_level: OptLevel::Normal,
         emit_debug_info: false,
-        emit_llvm_ir: false,
+        emit_debug_locations: false,
+        emit: EmitArtifacts::default(),
         fuzz: false,
+        frame_pointers: false,
+        inline_threshold: DEFAULT_INLINE_THRESHOLD,
+        alias_analysis: AliasAnalysisOptions::default(),
+        verbose_layouts: false,
+        reproducible: false,
     };
 
     let emit_timings = false;
@@ -1309,6 +1903,7 @@ pub fn build_str_test<'a>(
         wasm_dev_stack_bytes,
         loaded,
         compilation_start,
+        OutputFormat::Human,
         None,
     )
 }
@@ -20,6 +20,7 @@ pub fn target_triple_str(target: Target) -> &'static str {
         Target::MacArm64 => "aarch64-apple-darwin",
         Target::MacX64 => "x86_64-unknown-darwin10",
         Target::Wasm32 => "wasm32-unknown-unknown",
+        Target::Wasm32Wasi => "wasm32-unknown-wasi",
         Target::WinX64 => "x86_64-pc-windows-gnu",
         _ => internal_error!("TODO gracefully handle unsupported target: {:?}", target),
     }
@@ -31,13 +32,19 @@ pub fn target_zig_str(target: Target) -> &'static str {
     //
     // and an open proposal to unify them with the more typical "target triples":
     // https://github.com/ziglang/zig/issues/4911
+    //
+    // Zig bundles a libc for each of these, which is what lets us link for them from any machine.
     match target {
         Target::LinuxArm64 => "aarch64-linux-gnu",
         Target::LinuxX32 => "i386-linux-gnu",
         Target::LinuxX64 => "x86_64-linux-gnu",
         Target::MacArm64 => "aarch64-macos-none",
         Target::MacX64 => "x86_64-macos-none",
-        _ => internal_error!("TODO gracefully handle unsupported target: {:?}", target),
+        Target::WinX32 => "x86-windows-gnu",
+        Target::WinX64 => "x86_64-windows-gnu",
+        Target::WinArm64 => "aarch64-windows-gnu",
+        Target::Wasm32 => "wasm32-freestanding-musl",
+        Target::Wasm32Wasi => "wasm32-wasi-musl",
     }
 }
 
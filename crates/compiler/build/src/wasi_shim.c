@@ -0,0 +1,82 @@
+// Linked into every `--target wasm32-wasi` build, so that a platform host doesn't need a
+// custom JS host to provide the functions every Roc app needs. They're implemented with
+// wasi-libc, which gives hosts stdio, environment variables and clocks through WASI.
+//
+// Every definition is weak, so a host that provides its own takes precedence.
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#define WEAK __attribute__((weak))
+
+struct RocStr {
+  char* bytes;
+  size_t len;
+  size_t capacity;
+};
+
+static int is_small_str(const struct RocStr* str) { return ((ssize_t)str->capacity) < 0; }
+
+static size_t roc_str_len(const struct RocStr* str) {
+  if (is_small_str(str)) {
+    const char* bytes = (const char*)str;
+    return (size_t)(bytes[sizeof(*str) - 1] ^ 0x80);
+  } else {
+    return str->len;
+  }
+}
+
+static const char* roc_str_bytes(const struct RocStr* str) {
+  if (is_small_str(str)) {
+    return (const char*)str;
+  } else {
+    return str->bytes;
+  }
+}
+
+static void write_roc_str(const struct RocStr* str, FILE* file) {
+  fwrite(roc_str_bytes(str), 1, roc_str_len(str), file);
+}
+
+WEAK void* roc_alloc(size_t size, unsigned int alignment) { return malloc(size); }
+
+WEAK void* roc_realloc(void* ptr, size_t new_size, size_t old_size, unsigned int alignment) {
+  return realloc(ptr, new_size);
+}
+
+WEAK void roc_dealloc(void* ptr, unsigned int alignment) { free(ptr); }
+
+WEAK void* roc_memset(void* str, int c, size_t n) { return memset(str, c, n); }
+
+WEAK void roc_panic(const struct RocStr* msg, unsigned int tag_id) {
+  if (tag_id == 0) {
+    fputs("Roc crashed with:\n\n\t", stderr);
+  } else {
+    fputs("The program crashed with:\n\n\t", stderr);
+  }
+
+  write_roc_str(msg, stderr);
+  fputs("\n", stderr);
+  exit(1);
+}
+
+WEAK void roc_dbg(const struct RocStr* loc, const struct RocStr* msg, const struct RocStr* src) {
+  fputs("[", stderr);
+  write_roc_str(loc, stderr);
+  fputs("] ", stderr);
+  write_roc_str(src, stderr);
+  fputs(" = ", stderr);
+  write_roc_str(msg, stderr);
+  fputs("\n", stderr);
+}
+
+// WASI has no processes or shared memory, so `expect` can't report to a parent process.
+WEAK int roc_getppid(void) { return 0; }
+
+WEAK int roc_shm_open(char* name, int oflag, int mode) { return -1; }
+
+WEAK void* roc_mmap(void* addr, int length, int prot, int flags, int fd, int offset) {
+  return (void*)-1;
+}
@@ -79,6 +79,10 @@ fn generate_bc_file(bitcode_path: &Path, zig_object: &str, file_name: &str) {
         .current_dir(bitcode_path)
         .args(["build", zig_object, "-Drelease=true"]);
 
+    if env::var_os("CARGO_FEATURE_ATOMIC_REFCOUNT").is_some() {
+        zig_cmd.arg("-Datomic-refcount=true");
+    }
+
     run_command(zig_cmd, 0);
 }
 
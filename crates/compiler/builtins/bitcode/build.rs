@@ -76,6 +76,10 @@ fn generate_object_file(bitcode_path: &Path, zig_object: &str, object_file_name:
         .current_dir(bitcode_path)
         .args(["build", zig_object, "-Drelease=true"]);
 
+    if env::var_os("CARGO_FEATURE_ATOMIC_REFCOUNT").is_some() {
+        zig_cmd.arg("-Datomic-refcount=true");
+    }
+
     run_command(zig_cmd, 0);
 
     println!("Moving zig object `{zig_object}` to: {dest_obj}");
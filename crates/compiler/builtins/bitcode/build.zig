@@ -11,7 +11,8 @@ pub fn build(b: *Build) void {
     const mode = b.standardOptimizeOption(.{ .preferred_optimize_mode = .ReleaseFast });
 
     // Options
-    const fallback_main_path = "./src/main.zig";
+    const atomic_refcount = b.option(bool, "atomic-refcount", "Use atomic refcounts, so Roc values can be shared between threads. Defaults to false.") orelse false;
+    const fallback_main_path = if (atomic_refcount) "./src/atomic_refcount.zig" else "./src/main.zig";
     const main_path_desc = b.fmt("Override path to main.zig. Used by \"ir\" and \"test\". Defaults to \"{s}\". ", .{fallback_main_path});
     const main_path = .{ .path = b.option([]const u8, "main-path", main_path_desc) orelse fallback_main_path };
 
@@ -0,0 +1,163 @@
+#!/usr/bin/env python3
+"""Generates src/grapheme_table.zig, the Grapheme_Cluster_Break lookup table
+used by Str.graphemes and friends.
+
+The properties are derived from the Unicode Character Database that ships with
+Python's `unicodedata` module, following the definitions in Table 2 of UAX #29
+(https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Break_Property_Values).
+Extended_Pictographic is not part of `unicodedata`, so its ranges are listed
+below, taken from emoji-data.txt.
+
+Hangul syllables (LV and LVT) are computed arithmetically in grapheme.zig
+rather than stored here, which keeps the table small.
+
+Usage: python3 gen_grapheme_table.py > src/grapheme_table.zig
+"""
+
+import sys
+import unicodedata
+
+# https://www.unicode.org/Public/UCD/latest/ucd/PropList.txt
+OTHER_GRAPHEME_EXTEND = [
+    (0x09BE, 0x09BE), (0x09D7, 0x09D7), (0x0B3E, 0x0B3E), (0x0B57, 0x0B57),
+    (0x0BBE, 0x0BBE), (0x0BD7, 0x0BD7), (0x0CC2, 0x0CC2), (0x0CD5, 0x0CD6),
+    (0x0D3E, 0x0D3E), (0x0D57, 0x0D57), (0x0DCF, 0x0DCF), (0x0DDF, 0x0DDF),
+    (0x1B35, 0x1B35), (0x200C, 0x200C), (0x302E, 0x302F), (0xFF9E, 0xFF9F),
+    (0x1133E, 0x1133E), (0x11357, 0x11357), (0x114B0, 0x114B0),
+    (0x114BD, 0x114BD), (0x115AF, 0x115AF), (0x11930, 0x11930),
+    (0x1D165, 0x1D165), (0x1D16E, 0x1D172), (0xE0020, 0xE007F),
+]
+
+# Emoji_Modifier, which UAX #29 folds into Extend.
+EMOJI_MODIFIER = [(0x1F3FB, 0x1F3FF)]
+
+# Prepended_Concatenation_Mark plus the Indic_Syllabic_Category values
+# Consonant_Preceding_Repha and Consonant_Prefixed.
+PREPEND = [
+    (0x0600, 0x0605), (0x06DD, 0x06DD), (0x070F, 0x070F), (0x0890, 0x0891),
+    (0x08E2, 0x08E2), (0x0D4E, 0x0D4E), (0x110BD, 0x110BD),
+    (0x110CD, 0x110CD), (0x111C2, 0x111C3), (0x1193F, 0x1193F),
+    (0x11941, 0x11941), (0x11A3A, 0x11A3A), (0x11A84, 0x11A89),
+    (0x11D46, 0x11D46),
+]
+
+# Spacing marks that UAX #29 explicitly excludes from SpacingMark.
+NOT_SPACING_MARK = [
+    (0x102B, 0x102C), (0x1038, 0x1038), (0x1062, 0x1064), (0x1067, 0x106D),
+    (0x1083, 0x1083), (0x1087, 0x108C), (0x108F, 0x108F), (0x109A, 0x109C),
+    (0x1A61, 0x1A61), (0x1A63, 0x1A64), (0xAA7B, 0xAA7B), (0xAA7D, 0xAA7D),
+    (0x11720, 0x11721),
+]
+
+# https://www.unicode.org/Public/UCD/latest/ucd/emoji/emoji-data.txt
+EXTENDED_PICTOGRAPHIC = [
+    (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049),
+    (0x2122, 0x2122), (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA),
+    (0x231A, 0x231B), (0x2328, 0x2328), (0x2388, 0x2388), (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2), (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x2605),
+    (0x2607, 0x2612), (0x2614, 0x2685), (0x2690, 0x2705), (0x2708, 0x2712),
+    (0x2714, 0x2714), (0x2716, 0x2716), (0x271D, 0x271D), (0x2721, 0x2721),
+    (0x2728, 0x2728), (0x2733, 0x2734), (0x2744, 0x2744), (0x2747, 0x2747),
+    (0x274C, 0x274C), (0x274E, 0x274E), (0x2753, 0x2755), (0x2757, 0x2757),
+    (0x2763, 0x2767), (0x2795, 0x2797), (0x27A1, 0x27A1), (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF), (0x2934, 0x2935), (0x2B05, 0x2B07), (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50), (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D),
+    (0x3297, 0x3297), (0x3299, 0x3299), (0x1F000, 0x1F0FF),
+    (0x1F10D, 0x1F10F), (0x1F12F, 0x1F12F), (0x1F16C, 0x1F171),
+    (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A),
+    (0x1F1AD, 0x1F1E5), (0x1F201, 0x1F20F), (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F), (0x1F232, 0x1F23A), (0x1F23C, 0x1F23F),
+    (0x1F249, 0x1F3FA), (0x1F400, 0x1F53D), (0x1F546, 0x1F64F),
+    (0x1F680, 0x1F6FF), (0x1F774, 0x1F77F), (0x1F7D5, 0x1F7FF),
+    (0x1F80C, 0x1F80F), (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F),
+    (0x1F888, 0x1F88F), (0x1F8AE, 0x1F8FF), (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945), (0x1F947, 0x1FAFF), (0x1FC00, 0x1FFFD),
+]
+
+
+def in_ranges(ranges, cp):
+    return any(lo <= cp <= hi for lo, hi in ranges)
+
+
+def property_of(cp):
+    if cp == 0x000D:
+        return "CR"
+    if cp == 0x000A:
+        return "LF"
+    if cp == 0x200D:
+        return "ZWJ"
+    if 0x1F1E6 <= cp <= 0x1F1FF:
+        return "RegionalIndicator"
+    if in_ranges(PREPEND, cp):
+        return "Prepend"
+    if 0x1100 <= cp <= 0x115F or 0xA960 <= cp <= 0xA97C:
+        return "L"
+    if 0x1160 <= cp <= 0x11A7 or 0xD7B0 <= cp <= 0xD7C6:
+        return "V"
+    if 0x11A8 <= cp <= 0x11FF or 0xD7CB <= cp <= 0xD7FB:
+        return "T"
+
+    category = unicodedata.category(chr(cp))
+
+    if (
+        category in ("Mn", "Me")
+        or in_ranges(OTHER_GRAPHEME_EXTEND, cp)
+        or in_ranges(EMOJI_MODIFIER, cp)
+    ):
+        return "Extend"
+    if category in ("Zl", "Zp", "Cc", "Cf"):
+        return "Control"
+    if category == "Mc" and not in_ranges(NOT_SPACING_MARK, cp):
+        return "SpacingMark"
+    if cp in (0x0E33, 0x0EB3):
+        return "SpacingMark"
+    if in_ranges(EXTENDED_PICTOGRAPHIC, cp):
+        return "ExtendedPictographic"
+
+    return None
+
+
+def ranges():
+    current = None
+
+    for cp in range(0x110000):
+        # Surrogates never appear in a valid Str, and Hangul syllables are
+        # handled in grapheme.zig.
+        if 0xD800 <= cp <= 0xDFFF or 0xAC00 <= cp <= 0xD7A3:
+            prop = None
+        else:
+            prop = property_of(cp)
+
+        if current is not None and current[2] == prop and current[1] == cp - 1:
+            current[1] = cp
+            continue
+
+        if current is not None and current[2] is not None:
+            yield current
+
+        current = [cp, cp, prop]
+
+    if current is not None and current[2] is not None:
+        yield current
+
+
+def main():
+    out = sys.stdout
+    out.write("// Generated by gen_grapheme_table.py from Unicode ")
+    out.write(unicodedata.unidata_version)
+    out.write(" data. Do not edit by hand.\n\n")
+    out.write('const Property = @import("grapheme.zig").Property;\n\n')
+    out.write("pub const Range = struct { lo: u21, hi: u21, prop: Property };\n\n")
+    out.write("pub const table = [_]Range{\n")
+
+    for lo, hi, prop in ranges():
+        out.write(
+            "    .{ .lo = 0x%X, .hi = 0x%X, .prop = .%s },\n" % (lo, hi, prop)
+        )
+
+    out.write("};\n")
+
+
+if __name__ == "__main__":
+    main()
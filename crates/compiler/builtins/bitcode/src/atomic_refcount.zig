@@ -0,0 +1,11 @@
+//! The root of the builtins when they're built with atomic refcounts,
+//! using `zig build -Datomic-refcount=true`. See RC_TYPE in utils.zig.
+const main = @import("main.zig");
+
+pub const roc_atomic_refcount = true;
+
+pub const panic = main.panic;
+
+comptime {
+    _ = main;
+}
@@ -0,0 +1,122 @@
+const std = @import("std");
+const RocStr = @import("str.zig").RocStr;
+
+// A function in the app, as codegen lists them for backtraces. The offsets are relative to the
+// start of the table, so that the table needs no relocations.
+pub const BacktraceProc = extern struct {
+    offset: i32,
+    name_offset: i32,
+    // 0 for functions that aren't Roc procs, like builtins
+    name_len: u32,
+    kind: Kind,
+
+    pub const Kind = enum(u8) {
+        function = 0,
+        // the functions the host calls; the frames above them belong to the host
+        entry_point = 1,
+        // an empty function after all the others, which marks the end of the app's code
+        end = 2,
+    };
+};
+
+const MAX_FRAMES = 64;
+
+const HEADER = "\n\nBacktrace:";
+const INDENT = "\n    ";
+
+// Appends the Roc procs on the stack to `message`, innermost first. When codegen calls this,
+// it gives every function in the app a frame pointer, so we can follow those until we reach
+// the entry point that the host called.
+pub fn withBacktrace(message: RocStr, procs_ptr: ?[*]const BacktraceProc, procs_len: usize) callconv(.C) RocStr {
+    const procs = (procs_ptr orelse return message)[0..procs_len];
+    const base = @intFromPtr(procs.ptr);
+
+    var names: [MAX_FRAMES][]const u8 = undefined;
+    var count: usize = 0;
+
+    var frame_pointer = @frameAddress();
+    var frames: usize = 0;
+
+    while (frame_pointer != 0 and frame_pointer % @alignOf(usize) == 0 and frames < MAX_FRAMES) : (frames += 1) {
+        const frame: [*]const usize = @ptrFromInt(frame_pointer);
+        const caller_frame_pointer = frame[0];
+        const return_address = frame[1];
+
+        // The return address is right after the call, which can be the start of the next
+        // function if the call was the last instruction of this one.
+        const proc = findProc(procs, base, return_address -% 1) orelse break;
+
+        if (proc.kind == .entry_point) {
+            break;
+        }
+
+        if (proc.name_len > 0) {
+            const name: [*]const u8 = @ptrFromInt(relative(base, proc.name_offset));
+            names[count] = name[0..proc.name_len];
+            count += 1;
+        }
+
+        // the stack grows down, so the caller's frame is always above this one
+        if (caller_frame_pointer <= frame_pointer) {
+            break;
+        }
+
+        frame_pointer = caller_frame_pointer;
+    }
+
+    if (count == 0) {
+        return message;
+    }
+
+    var length = message.len() + HEADER.len;
+    for (names[0..count]) |name| {
+        length += INDENT.len + name.len;
+    }
+
+    // The message is about to be passed to roc_panic, so there's no need to free it.
+    var result = RocStr.allocate(length);
+    const bytes = result.asU8ptrMut()[0..length];
+    var offset: usize = 0;
+
+    for ([_][]const u8{ message.asSlice(), HEADER }) |part| {
+        @memcpy(bytes[offset..][0..part.len], part);
+        offset += part.len;
+    }
+
+    for (names[0..count]) |name| {
+        @memcpy(bytes[offset..][0..INDENT.len], INDENT);
+        offset += INDENT.len;
+        @memcpy(bytes[offset..][0..name.len], name);
+        offset += name.len;
+    }
+
+    return result;
+}
+
+fn relative(base: usize, offset: i32) usize {
+    return base +% @as(usize, @bitCast(@as(isize, offset)));
+}
+
+// The function that `address` is in: the one that starts closest below it. Addresses past the
+// end of the app's code (in the host, say) aren't in any of them.
+fn findProc(procs: []const BacktraceProc, base: usize, address: usize) ?*const BacktraceProc {
+    var found: ?*const BacktraceProc = null;
+    var found_address: usize = 0;
+
+    for (procs) |*proc| {
+        const proc_address = relative(base, proc.offset);
+
+        if (proc_address <= address and (found == null or proc_address > found_address)) {
+            found = proc;
+            found_address = proc_address;
+        }
+    }
+
+    if (found) |proc| {
+        if (proc.kind == .end) {
+            return null;
+        }
+    }
+
+    return found;
+}
@@ -222,6 +222,55 @@ pub const RocDec = extern struct {
         return RocStr.init(&str_bytes, position);
     }
 
+    pub fn toStrWith(self: RocDec, separator: RocStr, decimals: u8) RocStr {
+        if (decimals == str.SHORTEST_DECIMALS) {
+            const shortest = self.toStr();
+            defer shortest.decref();
+
+            return str.initWithSeparators(shortest.asSlice(), separator, 3);
+        }
+
+        // Round the magnitude to the requested number of decimal places (at most
+        // the 18 we store), rounding halfway cases away from zero.
+        const stored_places: u5 = @intCast(@min(decimals, decimal_places));
+        const factor = math.pow(u128, 10, decimal_places - stored_places);
+        const magnitude = math.absCast(self.num);
+        var rounded = magnitude / factor;
+        if ((magnitude % factor) * 2 >= factor) {
+            rounded += 1;
+        }
+
+        const scale = math.pow(u128, 10, stored_places);
+        const before = rounded / scale;
+        const after = rounded % scale;
+
+        // sign, digits before the point, the point, and the digits after it
+        var str_bytes: [1 + max_digits + 1 + str.MAX_DECIMALS]u8 = undefined;
+        var position: usize = 0;
+
+        if (self.num < 0 and rounded != 0) {
+            str_bytes[position] = '-';
+            position += 1;
+        }
+
+        position += std.fmt.formatIntBuf(str_bytes[position..], before, 10, .lower, .{});
+
+        if (decimals > 0) {
+            str_bytes[position] = '.';
+            position += 1;
+
+            if (stored_places > 0) {
+                position += std.fmt.formatIntBuf(str_bytes[position..], after, 10, .lower, .{ .width = stored_places, .fill = '0' });
+            }
+
+            const extra_zeros = decimals - stored_places;
+            @memset(str_bytes[position .. position + extra_zeros], '0');
+            position += extra_zeros;
+        }
+
+        return str.initWithSeparators(str_bytes[0..position], separator, 3);
+    }
+
     pub fn toI128(self: RocDec) i128 {
         return self.num;
     }
@@ -1164,6 +1213,33 @@ test "toStr: 0" {
     try expectEqualSlices(u8, res_slice, res_roc_str.asSlice());
 }
 
+test "toStrWith: rounds to the requested decimals" {
+    var dec: RocDec = .{ .num = -1234567850000000000000 };
+    const comma = RocStr.init(",", 1);
+    defer comma.decref();
+
+    var res_roc_str = dec.toStrWith(comma, 2);
+    defer res_roc_str.decref();
+
+    try expectEqualSlices(u8, "-1,234.57", res_roc_str.asSlice());
+}
+
+test "toStrWith: pads past the stored decimal places" {
+    var dec: RocDec = .{ .num = 1500000000000000000 };
+    var res_roc_str = dec.toStrWith(RocStr.empty(), 20);
+    defer res_roc_str.decref();
+
+    try expectEqualSlices(u8, "1.50000000000000000000", res_roc_str.asSlice());
+}
+
+test "toStrWith: no decimals" {
+    var dec: RocDec = .{ .num = -400000000000000000 };
+    var res_roc_str = dec.toStrWith(RocStr.empty(), 0);
+    defer res_roc_str.decref();
+
+    try expectEqualSlices(u8, "0", res_roc_str.asSlice());
+}
+
 test "add: 0" {
     var dec: RocDec = .{ .num = 0 };
 
@@ -1434,6 +1510,10 @@ pub fn toStr(arg: RocDec) callconv(.C) RocStr {
     return @call(.always_inline, RocDec.toStr, .{arg});
 }
 
+pub fn toStrWith(separator: RocStr, arg: RocDec, _: u8, decimals: u8) callconv(.C) RocStr {
+    return @call(.always_inline, RocDec.toStrWith, .{ arg, separator, decimals });
+}
+
 pub fn fromF64C(arg: f64) callconv(.C) i128 {
     if (@call(.always_inline, RocDec.fromF64, .{arg})) |dec| {
         return dec.num;
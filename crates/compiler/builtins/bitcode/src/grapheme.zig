@@ -0,0 +1,234 @@
+// Extended grapheme cluster boundaries, as specified in
+// https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundary_Rules
+const std = @import("std");
+const grapheme_table = @import("grapheme_table.zig");
+const testing = std.testing;
+const expectEqual = testing.expectEqual;
+
+pub const Property = enum(u8) {
+    Other,
+    CR,
+    LF,
+    Control,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    LV,
+    LVT,
+    ExtendedPictographic,
+};
+
+const HANGUL_SYLLABLE_START: u21 = 0xAC00;
+const HANGUL_SYLLABLE_END: u21 = 0xD7A3;
+const HANGUL_T_COUNT: u21 = 28;
+
+fn propertyOf(codepoint: u21) Property {
+    if (codepoint >= HANGUL_SYLLABLE_START and codepoint <= HANGUL_SYLLABLE_END) {
+        if ((codepoint - HANGUL_SYLLABLE_START) % HANGUL_T_COUNT == 0) {
+            return .LV;
+        } else {
+            return .LVT;
+        }
+    }
+
+    const table = grapheme_table.table;
+    var low: usize = 0;
+    var high: usize = table.len;
+
+    while (low < high) {
+        const mid = low + (high - low) / 2;
+        const range = table[mid];
+
+        if (codepoint < range.lo) {
+            high = mid;
+        } else if (codepoint > range.hi) {
+            low = mid + 1;
+        } else {
+            return range.prop;
+        }
+    }
+
+    return .Other;
+}
+
+const Decoded = struct {
+    codepoint: u21,
+    len: usize,
+};
+
+// Roc strings are always valid UTF-8, so this does not validate its input.
+fn decode(bytes: []const u8, index: usize) Decoded {
+    const first = bytes[index];
+
+    if (first < 0x80) {
+        return .{ .codepoint = first, .len = 1 };
+    } else if (first < 0xE0) {
+        const codepoint = (@as(u21, first & 0x1F) << 6) | (bytes[index + 1] & 0x3F);
+        return .{ .codepoint = codepoint, .len = 2 };
+    } else if (first < 0xF0) {
+        const codepoint = (@as(u21, first & 0x0F) << 12) |
+            (@as(u21, bytes[index + 1] & 0x3F) << 6) |
+            (bytes[index + 2] & 0x3F);
+        return .{ .codepoint = codepoint, .len = 3 };
+    } else {
+        const codepoint = (@as(u21, first & 0x07) << 18) |
+            (@as(u21, bytes[index + 1] & 0x3F) << 12) |
+            (@as(u21, bytes[index + 2] & 0x3F) << 6) |
+            (bytes[index + 3] & 0x3F);
+        return .{ .codepoint = codepoint, .len = 4 };
+    }
+}
+
+// The state needed by the rules that look further back than one codepoint.
+const State = struct {
+    // GB11: the cluster ends in ExtendedPictographic Extend*
+    in_pictographic_sequence: bool = false,
+    // GB11: the cluster ends in ExtendedPictographic Extend* ZWJ
+    after_pictographic_zwj: bool = false,
+    // GB12 and GB13: the number of RegionalIndicators at the end of the cluster
+    regional_indicators: usize = 0,
+};
+
+fn isBoundary(previous: Property, next: Property, state: State) bool {
+    // GB3
+    if (previous == .CR and next == .LF) return false;
+
+    // GB4 and GB5
+    switch (previous) {
+        .CR, .LF, .Control => return true,
+        else => {},
+    }
+    switch (next) {
+        .CR, .LF, .Control => return true,
+        else => {},
+    }
+
+    // GB6, GB7 and GB8
+    switch (previous) {
+        .L => switch (next) {
+            .L, .V, .LV, .LVT => return false,
+            else => {},
+        },
+        .LV, .V => switch (next) {
+            .V, .T => return false,
+            else => {},
+        },
+        .LVT, .T => if (next == .T) return false,
+        else => {},
+    }
+
+    // GB9, GB9a and GB9b
+    switch (next) {
+        .Extend, .ZWJ, .SpacingMark => return false,
+        else => {},
+    }
+    if (previous == .Prepend) return false;
+
+    // GB11
+    if (next == .ExtendedPictographic and state.after_pictographic_zwj) {
+        return false;
+    }
+
+    // GB12 and GB13
+    if (previous == .RegionalIndicator and next == .RegionalIndicator) {
+        return state.regional_indicators % 2 == 0;
+    }
+
+    // GB999
+    return true;
+}
+
+fn advance(state: State, next: Property) State {
+    return .{
+        .in_pictographic_sequence = switch (next) {
+            .ExtendedPictographic => true,
+            .Extend => state.in_pictographic_sequence,
+            else => false,
+        },
+        .after_pictographic_zwj = next == .ZWJ and state.in_pictographic_sequence,
+        .regional_indicators = if (next == .RegionalIndicator) state.regional_indicators + 1 else 0,
+    };
+}
+
+/// The byte index just past the end of the grapheme cluster that starts at `start`.
+pub fn clusterEnd(bytes: []const u8, start: usize) usize {
+    if (start >= bytes.len) {
+        return bytes.len;
+    }
+
+    const first = decode(bytes, start);
+    var previous = propertyOf(first.codepoint);
+    var state = advance(.{}, previous);
+    var index = start + first.len;
+
+    while (index < bytes.len) {
+        const decoded = decode(bytes, index);
+        const next = propertyOf(decoded.codepoint);
+
+        if (isBoundary(previous, next, state)) {
+            break;
+        }
+
+        state = advance(state, next);
+        previous = next;
+        index += decoded.len;
+    }
+
+    return index;
+}
+
+fn countClusters(bytes: []const u8) usize {
+    var count: usize = 0;
+    var index: usize = 0;
+
+    while (index < bytes.len) {
+        index = clusterEnd(bytes, index);
+        count += 1;
+    }
+
+    return count;
+}
+
+test "clusterEnd: ascii" {
+    try expectEqual(countClusters("abc"), 3);
+    try expectEqual(clusterEnd("abc", 1), 2);
+    try expectEqual(clusterEnd("abc", 3), 3);
+}
+
+test "clusterEnd: CRLF is one cluster" {
+    try expectEqual(countClusters("a\r\nb"), 3);
+    try expectEqual(clusterEnd("a\r\nb", 1), 3);
+}
+
+test "clusterEnd: combining accent" {
+    // e followed by U+0301 COMBINING ACUTE ACCENT
+    try expectEqual(countClusters("e\u{301}"), 1);
+}
+
+test "clusterEnd: emoji ZWJ sequence" {
+    // 👩‍👩‍👦‍👦
+    try expectEqual(countClusters("\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F466}\u{200D}\u{1F466}"), 1);
+}
+
+test "clusterEnd: emoji with skin tone" {
+    // 👍🏽
+    try expectEqual(countClusters("\u{1F44D}\u{1F3FD}"), 1);
+}
+
+test "clusterEnd: flags" {
+    // 🇺🇸🇫🇷
+    try expectEqual(countClusters("\u{1F1FA}\u{1F1F8}\u{1F1EB}\u{1F1F7}"), 2);
+    // A lone regional indicator after a pair is its own cluster
+    try expectEqual(countClusters("\u{1F1FA}\u{1F1F8}\u{1F1EB}"), 2);
+}
+
+test "clusterEnd: hangul" {
+    // 한 as a precomposed syllable, and as jamo
+    try expectEqual(countClusters("\u{D55C}"), 1);
+    try expectEqual(countClusters("\u{1112}\u{1161}\u{11AB}"), 1);
+}
@@ -0,0 +1,642 @@
+// Generated by gen_grapheme_table.py from Unicode 14.0.0 data. Do not edit by hand.
+
+const Property = @import("grapheme.zig").Property;
+
+pub const Range = struct { lo: u21, hi: u21, prop: Property };
+
+pub const table = [_]Range{
+    .{ .lo = 0x0, .hi = 0x9, .prop = .Control },
+    .{ .lo = 0xA, .hi = 0xA, .prop = .LF },
+    .{ .lo = 0xB, .hi = 0xC, .prop = .Control },
+    .{ .lo = 0xD, .hi = 0xD, .prop = .CR },
+    .{ .lo = 0xE, .hi = 0x1F, .prop = .Control },
+    .{ .lo = 0x7F, .hi = 0x9F, .prop = .Control },
+    .{ .lo = 0xA9, .hi = 0xA9, .prop = .ExtendedPictographic },
+    .{ .lo = 0xAD, .hi = 0xAD, .prop = .Control },
+    .{ .lo = 0xAE, .hi = 0xAE, .prop = .ExtendedPictographic },
+    .{ .lo = 0x300, .hi = 0x36F, .prop = .Extend },
+    .{ .lo = 0x483, .hi = 0x489, .prop = .Extend },
+    .{ .lo = 0x591, .hi = 0x5BD, .prop = .Extend },
+    .{ .lo = 0x5BF, .hi = 0x5BF, .prop = .Extend },
+    .{ .lo = 0x5C1, .hi = 0x5C2, .prop = .Extend },
+    .{ .lo = 0x5C4, .hi = 0x5C5, .prop = .Extend },
+    .{ .lo = 0x5C7, .hi = 0x5C7, .prop = .Extend },
+    .{ .lo = 0x600, .hi = 0x605, .prop = .Prepend },
+    .{ .lo = 0x610, .hi = 0x61A, .prop = .Extend },
+    .{ .lo = 0x61C, .hi = 0x61C, .prop = .Control },
+    .{ .lo = 0x64B, .hi = 0x65F, .prop = .Extend },
+    .{ .lo = 0x670, .hi = 0x670, .prop = .Extend },
+    .{ .lo = 0x6D6, .hi = 0x6DC, .prop = .Extend },
+    .{ .lo = 0x6DD, .hi = 0x6DD, .prop = .Prepend },
+    .{ .lo = 0x6DF, .hi = 0x6E4, .prop = .Extend },
+    .{ .lo = 0x6E7, .hi = 0x6E8, .prop = .Extend },
+    .{ .lo = 0x6EA, .hi = 0x6ED, .prop = .Extend },
+    .{ .lo = 0x70F, .hi = 0x70F, .prop = .Prepend },
+    .{ .lo = 0x711, .hi = 0x711, .prop = .Extend },
+    .{ .lo = 0x730, .hi = 0x74A, .prop = .Extend },
+    .{ .lo = 0x7A6, .hi = 0x7B0, .prop = .Extend },
+    .{ .lo = 0x7EB, .hi = 0x7F3, .prop = .Extend },
+    .{ .lo = 0x7FD, .hi = 0x7FD, .prop = .Extend },
+    .{ .lo = 0x816, .hi = 0x819, .prop = .Extend },
+    .{ .lo = 0x81B, .hi = 0x823, .prop = .Extend },
+    .{ .lo = 0x825, .hi = 0x827, .prop = .Extend },
+    .{ .lo = 0x829, .hi = 0x82D, .prop = .Extend },
+    .{ .lo = 0x859, .hi = 0x85B, .prop = .Extend },
+    .{ .lo = 0x890, .hi = 0x891, .prop = .Prepend },
+    .{ .lo = 0x898, .hi = 0x89F, .prop = .Extend },
+    .{ .lo = 0x8CA, .hi = 0x8E1, .prop = .Extend },
+    .{ .lo = 0x8E2, .hi = 0x8E2, .prop = .Prepend },
+    .{ .lo = 0x8E3, .hi = 0x902, .prop = .Extend },
+    .{ .lo = 0x903, .hi = 0x903, .prop = .SpacingMark },
+    .{ .lo = 0x93A, .hi = 0x93A, .prop = .Extend },
+    .{ .lo = 0x93B, .hi = 0x93B, .prop = .SpacingMark },
+    .{ .lo = 0x93C, .hi = 0x93C, .prop = .Extend },
+    .{ .lo = 0x93E, .hi = 0x940, .prop = .SpacingMark },
+    .{ .lo = 0x941, .hi = 0x948, .prop = .Extend },
+    .{ .lo = 0x949, .hi = 0x94C, .prop = .SpacingMark },
+    .{ .lo = 0x94D, .hi = 0x94D, .prop = .Extend },
+    .{ .lo = 0x94E, .hi = 0x94F, .prop = .SpacingMark },
+    .{ .lo = 0x951, .hi = 0x957, .prop = .Extend },
+    .{ .lo = 0x962, .hi = 0x963, .prop = .Extend },
+    .{ .lo = 0x981, .hi = 0x981, .prop = .Extend },
+    .{ .lo = 0x982, .hi = 0x983, .prop = .SpacingMark },
+    .{ .lo = 0x9BC, .hi = 0x9BC, .prop = .Extend },
+    .{ .lo = 0x9BE, .hi = 0x9BE, .prop = .Extend },
+    .{ .lo = 0x9BF, .hi = 0x9C0, .prop = .SpacingMark },
+    .{ .lo = 0x9C1, .hi = 0x9C4, .prop = .Extend },
+    .{ .lo = 0x9C7, .hi = 0x9C8, .prop = .SpacingMark },
+    .{ .lo = 0x9CB, .hi = 0x9CC, .prop = .SpacingMark },
+    .{ .lo = 0x9CD, .hi = 0x9CD, .prop = .Extend },
+    .{ .lo = 0x9D7, .hi = 0x9D7, .prop = .Extend },
+    .{ .lo = 0x9E2, .hi = 0x9E3, .prop = .Extend },
+    .{ .lo = 0x9FE, .hi = 0x9FE, .prop = .Extend },
+    .{ .lo = 0xA01, .hi = 0xA02, .prop = .Extend },
+    .{ .lo = 0xA03, .hi = 0xA03, .prop = .SpacingMark },
+    .{ .lo = 0xA3C, .hi = 0xA3C, .prop = .Extend },
+    .{ .lo = 0xA3E, .hi = 0xA40, .prop = .SpacingMark },
+    .{ .lo = 0xA41, .hi = 0xA42, .prop = .Extend },
+    .{ .lo = 0xA47, .hi = 0xA48, .prop = .Extend },
+    .{ .lo = 0xA4B, .hi = 0xA4D, .prop = .Extend },
+    .{ .lo = 0xA51, .hi = 0xA51, .prop = .Extend },
+    .{ .lo = 0xA70, .hi = 0xA71, .prop = .Extend },
+    .{ .lo = 0xA75, .hi = 0xA75, .prop = .Extend },
+    .{ .lo = 0xA81, .hi = 0xA82, .prop = .Extend },
+    .{ .lo = 0xA83, .hi = 0xA83, .prop = .SpacingMark },
+    .{ .lo = 0xABC, .hi = 0xABC, .prop = .Extend },
+    .{ .lo = 0xABE, .hi = 0xAC0, .prop = .SpacingMark },
+    .{ .lo = 0xAC1, .hi = 0xAC5, .prop = .Extend },
+    .{ .lo = 0xAC7, .hi = 0xAC8, .prop = .Extend },
+    .{ .lo = 0xAC9, .hi = 0xAC9, .prop = .SpacingMark },
+    .{ .lo = 0xACB, .hi = 0xACC, .prop = .SpacingMark },
+    .{ .lo = 0xACD, .hi = 0xACD, .prop = .Extend },
+    .{ .lo = 0xAE2, .hi = 0xAE3, .prop = .Extend },
+    .{ .lo = 0xAFA, .hi = 0xAFF, .prop = .Extend },
+    .{ .lo = 0xB01, .hi = 0xB01, .prop = .Extend },
+    .{ .lo = 0xB02, .hi = 0xB03, .prop = .SpacingMark },
+    .{ .lo = 0xB3C, .hi = 0xB3C, .prop = .Extend },
+    .{ .lo = 0xB3E, .hi = 0xB3F, .prop = .Extend },
+    .{ .lo = 0xB40, .hi = 0xB40, .prop = .SpacingMark },
+    .{ .lo = 0xB41, .hi = 0xB44, .prop = .Extend },
+    .{ .lo = 0xB47, .hi = 0xB48, .prop = .SpacingMark },
+    .{ .lo = 0xB4B, .hi = 0xB4C, .prop = .SpacingMark },
+    .{ .lo = 0xB4D, .hi = 0xB4D, .prop = .Extend },
+    .{ .lo = 0xB55, .hi = 0xB57, .prop = .Extend },
+    .{ .lo = 0xB62, .hi = 0xB63, .prop = .Extend },
+    .{ .lo = 0xB82, .hi = 0xB82, .prop = .Extend },
+    .{ .lo = 0xBBE, .hi = 0xBBE, .prop = .Extend },
+    .{ .lo = 0xBBF, .hi = 0xBBF, .prop = .SpacingMark },
+    .{ .lo = 0xBC0, .hi = 0xBC0, .prop = .Extend },
+    .{ .lo = 0xBC1, .hi = 0xBC2, .prop = .SpacingMark },
+    .{ .lo = 0xBC6, .hi = 0xBC8, .prop = .SpacingMark },
+    .{ .lo = 0xBCA, .hi = 0xBCC, .prop = .SpacingMark },
+    .{ .lo = 0xBCD, .hi = 0xBCD, .prop = .Extend },
+    .{ .lo = 0xBD7, .hi = 0xBD7, .prop = .Extend },
+    .{ .lo = 0xC00, .hi = 0xC00, .prop = .Extend },
+    .{ .lo = 0xC01, .hi = 0xC03, .prop = .SpacingMark },
+    .{ .lo = 0xC04, .hi = 0xC04, .prop = .Extend },
+    .{ .lo = 0xC3C, .hi = 0xC3C, .prop = .Extend },
+    .{ .lo = 0xC3E, .hi = 0xC40, .prop = .Extend },
+    .{ .lo = 0xC41, .hi = 0xC44, .prop = .SpacingMark },
+    .{ .lo = 0xC46, .hi = 0xC48, .prop = .Extend },
+    .{ .lo = 0xC4A, .hi = 0xC4D, .prop = .Extend },
+    .{ .lo = 0xC55, .hi = 0xC56, .prop = .Extend },
+    .{ .lo = 0xC62, .hi = 0xC63, .prop = .Extend },
+    .{ .lo = 0xC81, .hi = 0xC81, .prop = .Extend },
+    .{ .lo = 0xC82, .hi = 0xC83, .prop = .SpacingMark },
+    .{ .lo = 0xCBC, .hi = 0xCBC, .prop = .Extend },
+    .{ .lo = 0xCBE, .hi = 0xCBE, .prop = .SpacingMark },
+    .{ .lo = 0xCBF, .hi = 0xCBF, .prop = .Extend },
+    .{ .lo = 0xCC0, .hi = 0xCC1, .prop = .SpacingMark },
+    .{ .lo = 0xCC2, .hi = 0xCC2, .prop = .Extend },
+    .{ .lo = 0xCC3, .hi = 0xCC4, .prop = .SpacingMark },
+    .{ .lo = 0xCC6, .hi = 0xCC6, .prop = .Extend },
+    .{ .lo = 0xCC7, .hi = 0xCC8, .prop = .SpacingMark },
+    .{ .lo = 0xCCA, .hi = 0xCCB, .prop = .SpacingMark },
+    .{ .lo = 0xCCC, .hi = 0xCCD, .prop = .Extend },
+    .{ .lo = 0xCD5, .hi = 0xCD6, .prop = .Extend },
+    .{ .lo = 0xCE2, .hi = 0xCE3, .prop = .Extend },
+    .{ .lo = 0xD00, .hi = 0xD01, .prop = .Extend },
+    .{ .lo = 0xD02, .hi = 0xD03, .prop = .SpacingMark },
+    .{ .lo = 0xD3B, .hi = 0xD3C, .prop = .Extend },
+    .{ .lo = 0xD3E, .hi = 0xD3E, .prop = .Extend },
+    .{ .lo = 0xD3F, .hi = 0xD40, .prop = .SpacingMark },
+    .{ .lo = 0xD41, .hi = 0xD44, .prop = .Extend },
+    .{ .lo = 0xD46, .hi = 0xD48, .prop = .SpacingMark },
+    .{ .lo = 0xD4A, .hi = 0xD4C, .prop = .SpacingMark },
+    .{ .lo = 0xD4D, .hi = 0xD4D, .prop = .Extend },
+    .{ .lo = 0xD4E, .hi = 0xD4E, .prop = .Prepend },
+    .{ .lo = 0xD57, .hi = 0xD57, .prop = .Extend },
+    .{ .lo = 0xD62, .hi = 0xD63, .prop = .Extend },
+    .{ .lo = 0xD81, .hi = 0xD81, .prop = .Extend },
+    .{ .lo = 0xD82, .hi = 0xD83, .prop = .SpacingMark },
+    .{ .lo = 0xDCA, .hi = 0xDCA, .prop = .Extend },
+    .{ .lo = 0xDCF, .hi = 0xDCF, .prop = .Extend },
+    .{ .lo = 0xDD0, .hi = 0xDD1, .prop = .SpacingMark },
+    .{ .lo = 0xDD2, .hi = 0xDD4, .prop = .Extend },
+    .{ .lo = 0xDD6, .hi = 0xDD6, .prop = .Extend },
+    .{ .lo = 0xDD8, .hi = 0xDDE, .prop = .SpacingMark },
+    .{ .lo = 0xDDF, .hi = 0xDDF, .prop = .Extend },
+    .{ .lo = 0xDF2, .hi = 0xDF3, .prop = .SpacingMark },
+    .{ .lo = 0xE31, .hi = 0xE31, .prop = .Extend },
+    .{ .lo = 0xE33, .hi = 0xE33, .prop = .SpacingMark },
+    .{ .lo = 0xE34, .hi = 0xE3A, .prop = .Extend },
+    .{ .lo = 0xE47, .hi = 0xE4E, .prop = .Extend },
+    .{ .lo = 0xEB1, .hi = 0xEB1, .prop = .Extend },
+    .{ .lo = 0xEB3, .hi = 0xEB3, .prop = .SpacingMark },
+    .{ .lo = 0xEB4, .hi = 0xEBC, .prop = .Extend },
+    .{ .lo = 0xEC8, .hi = 0xECD, .prop = .Extend },
+    .{ .lo = 0xF18, .hi = 0xF19, .prop = .Extend },
+    .{ .lo = 0xF35, .hi = 0xF35, .prop = .Extend },
+    .{ .lo = 0xF37, .hi = 0xF37, .prop = .Extend },
+    .{ .lo = 0xF39, .hi = 0xF39, .prop = .Extend },
+    .{ .lo = 0xF3E, .hi = 0xF3F, .prop = .SpacingMark },
+    .{ .lo = 0xF71, .hi = 0xF7E, .prop = .Extend },
+    .{ .lo = 0xF7F, .hi = 0xF7F, .prop = .SpacingMark },
+    .{ .lo = 0xF80, .hi = 0xF84, .prop = .Extend },
+    .{ .lo = 0xF86, .hi = 0xF87, .prop = .Extend },
+    .{ .lo = 0xF8D, .hi = 0xF97, .prop = .Extend },
+    .{ .lo = 0xF99, .hi = 0xFBC, .prop = .Extend },
+    .{ .lo = 0xFC6, .hi = 0xFC6, .prop = .Extend },
+    .{ .lo = 0x102D, .hi = 0x1030, .prop = .Extend },
+    .{ .lo = 0x1031, .hi = 0x1031, .prop = .SpacingMark },
+    .{ .lo = 0x1032, .hi = 0x1037, .prop = .Extend },
+    .{ .lo = 0x1039, .hi = 0x103A, .prop = .Extend },
+    .{ .lo = 0x103B, .hi = 0x103C, .prop = .SpacingMark },
+    .{ .lo = 0x103D, .hi = 0x103E, .prop = .Extend },
+    .{ .lo = 0x1056, .hi = 0x1057, .prop = .SpacingMark },
+    .{ .lo = 0x1058, .hi = 0x1059, .prop = .Extend },
+    .{ .lo = 0x105E, .hi = 0x1060, .prop = .Extend },
+    .{ .lo = 0x1071, .hi = 0x1074, .prop = .Extend },
+    .{ .lo = 0x1082, .hi = 0x1082, .prop = .Extend },
+    .{ .lo = 0x1084, .hi = 0x1084, .prop = .SpacingMark },
+    .{ .lo = 0x1085, .hi = 0x1086, .prop = .Extend },
+    .{ .lo = 0x108D, .hi = 0x108D, .prop = .Extend },
+    .{ .lo = 0x109D, .hi = 0x109D, .prop = .Extend },
+    .{ .lo = 0x1100, .hi = 0x115F, .prop = .L },
+    .{ .lo = 0x1160, .hi = 0x11A7, .prop = .V },
+    .{ .lo = 0x11A8, .hi = 0x11FF, .prop = .T },
+    .{ .lo = 0x135D, .hi = 0x135F, .prop = .Extend },
+    .{ .lo = 0x1712, .hi = 0x1714, .prop = .Extend },
+    .{ .lo = 0x1715, .hi = 0x1715, .prop = .SpacingMark },
+    .{ .lo = 0x1732, .hi = 0x1733, .prop = .Extend },
+    .{ .lo = 0x1734, .hi = 0x1734, .prop = .SpacingMark },
+    .{ .lo = 0x1752, .hi = 0x1753, .prop = .Extend },
+    .{ .lo = 0x1772, .hi = 0x1773, .prop = .Extend },
+    .{ .lo = 0x17B4, .hi = 0x17B5, .prop = .Extend },
+    .{ .lo = 0x17B6, .hi = 0x17B6, .prop = .SpacingMark },
+    .{ .lo = 0x17B7, .hi = 0x17BD, .prop = .Extend },
+    .{ .lo = 0x17BE, .hi = 0x17C5, .prop = .SpacingMark },
+    .{ .lo = 0x17C6, .hi = 0x17C6, .prop = .Extend },
+    .{ .lo = 0x17C7, .hi = 0x17C8, .prop = .SpacingMark },
+    .{ .lo = 0x17C9, .hi = 0x17D3, .prop = .Extend },
+    .{ .lo = 0x17DD, .hi = 0x17DD, .prop = .Extend },
+    .{ .lo = 0x180B, .hi = 0x180D, .prop = .Extend },
+    .{ .lo = 0x180E, .hi = 0x180E, .prop = .Control },
+    .{ .lo = 0x180F, .hi = 0x180F, .prop = .Extend },
+    .{ .lo = 0x1885, .hi = 0x1886, .prop = .Extend },
+    .{ .lo = 0x18A9, .hi = 0x18A9, .prop = .Extend },
+    .{ .lo = 0x1920, .hi = 0x1922, .prop = .Extend },
+    .{ .lo = 0x1923, .hi = 0x1926, .prop = .SpacingMark },
+    .{ .lo = 0x1927, .hi = 0x1928, .prop = .Extend },
+    .{ .lo = 0x1929, .hi = 0x192B, .prop = .SpacingMark },
+    .{ .lo = 0x1930, .hi = 0x1931, .prop = .SpacingMark },
+    .{ .lo = 0x1932, .hi = 0x1932, .prop = .Extend },
+    .{ .lo = 0x1933, .hi = 0x1938, .prop = .SpacingMark },
+    .{ .lo = 0x1939, .hi = 0x193B, .prop = .Extend },
+    .{ .lo = 0x1A17, .hi = 0x1A18, .prop = .Extend },
+    .{ .lo = 0x1A19, .hi = 0x1A1A, .prop = .SpacingMark },
+    .{ .lo = 0x1A1B, .hi = 0x1A1B, .prop = .Extend },
+    .{ .lo = 0x1A55, .hi = 0x1A55, .prop = .SpacingMark },
+    .{ .lo = 0x1A56, .hi = 0x1A56, .prop = .Extend },
+    .{ .lo = 0x1A57, .hi = 0x1A57, .prop = .SpacingMark },
+    .{ .lo = 0x1A58, .hi = 0x1A5E, .prop = .Extend },
+    .{ .lo = 0x1A60, .hi = 0x1A60, .prop = .Extend },
+    .{ .lo = 0x1A62, .hi = 0x1A62, .prop = .Extend },
+    .{ .lo = 0x1A65, .hi = 0x1A6C, .prop = .Extend },
+    .{ .lo = 0x1A6D, .hi = 0x1A72, .prop = .SpacingMark },
+    .{ .lo = 0x1A73, .hi = 0x1A7C, .prop = .Extend },
+    .{ .lo = 0x1A7F, .hi = 0x1A7F, .prop = .Extend },
+    .{ .lo = 0x1AB0, .hi = 0x1ACE, .prop = .Extend },
+    .{ .lo = 0x1B00, .hi = 0x1B03, .prop = .Extend },
+    .{ .lo = 0x1B04, .hi = 0x1B04, .prop = .SpacingMark },
+    .{ .lo = 0x1B34, .hi = 0x1B3A, .prop = .Extend },
+    .{ .lo = 0x1B3B, .hi = 0x1B3B, .prop = .SpacingMark },
+    .{ .lo = 0x1B3C, .hi = 0x1B3C, .prop = .Extend },
+    .{ .lo = 0x1B3D, .hi = 0x1B41, .prop = .SpacingMark },
+    .{ .lo = 0x1B42, .hi = 0x1B42, .prop = .Extend },
+    .{ .lo = 0x1B43, .hi = 0x1B44, .prop = .SpacingMark },
+    .{ .lo = 0x1B6B, .hi = 0x1B73, .prop = .Extend },
+    .{ .lo = 0x1B80, .hi = 0x1B81, .prop = .Extend },
+    .{ .lo = 0x1B82, .hi = 0x1B82, .prop = .SpacingMark },
+    .{ .lo = 0x1BA1, .hi = 0x1BA1, .prop = .SpacingMark },
+    .{ .lo = 0x1BA2, .hi = 0x1BA5, .prop = .Extend },
+    .{ .lo = 0x1BA6, .hi = 0x1BA7, .prop = .SpacingMark },
+    .{ .lo = 0x1BA8, .hi = 0x1BA9, .prop = .Extend },
+    .{ .lo = 0x1BAA, .hi = 0x1BAA, .prop = .SpacingMark },
+    .{ .lo = 0x1BAB, .hi = 0x1BAD, .prop = .Extend },
+    .{ .lo = 0x1BE6, .hi = 0x1BE6, .prop = .Extend },
+    .{ .lo = 0x1BE7, .hi = 0x1BE7, .prop = .SpacingMark },
+    .{ .lo = 0x1BE8, .hi = 0x1BE9, .prop = .Extend },
+    .{ .lo = 0x1BEA, .hi = 0x1BEC, .prop = .SpacingMark },
+    .{ .lo = 0x1BED, .hi = 0x1BED, .prop = .Extend },
+    .{ .lo = 0x1BEE, .hi = 0x1BEE, .prop = .SpacingMark },
+    .{ .lo = 0x1BEF, .hi = 0x1BF1, .prop = .Extend },
+    .{ .lo = 0x1BF2, .hi = 0x1BF3, .prop = .SpacingMark },
+    .{ .lo = 0x1C24, .hi = 0x1C2B, .prop = .SpacingMark },
+    .{ .lo = 0x1C2C, .hi = 0x1C33, .prop = .Extend },
+    .{ .lo = 0x1C34, .hi = 0x1C35, .prop = .SpacingMark },
+    .{ .lo = 0x1C36, .hi = 0x1C37, .prop = .Extend },
+    .{ .lo = 0x1CD0, .hi = 0x1CD2, .prop = .Extend },
+    .{ .lo = 0x1CD4, .hi = 0x1CE0, .prop = .Extend },
+    .{ .lo = 0x1CE1, .hi = 0x1CE1, .prop = .SpacingMark },
+    .{ .lo = 0x1CE2, .hi = 0x1CE8, .prop = .Extend },
+    .{ .lo = 0x1CED, .hi = 0x1CED, .prop = .Extend },
+    .{ .lo = 0x1CF4, .hi = 0x1CF4, .prop = .Extend },
+    .{ .lo = 0x1CF7, .hi = 0x1CF7, .prop = .SpacingMark },
+    .{ .lo = 0x1CF8, .hi = 0x1CF9, .prop = .Extend },
+    .{ .lo = 0x1DC0, .hi = 0x1DFF, .prop = .Extend },
+    .{ .lo = 0x200B, .hi = 0x200B, .prop = .Control },
+    .{ .lo = 0x200C, .hi = 0x200C, .prop = .Extend },
+    .{ .lo = 0x200D, .hi = 0x200D, .prop = .ZWJ },
+    .{ .lo = 0x200E, .hi = 0x200F, .prop = .Control },
+    .{ .lo = 0x2028, .hi = 0x202E, .prop = .Control },
+    .{ .lo = 0x203C, .hi = 0x203C, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2049, .hi = 0x2049, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2060, .hi = 0x2064, .prop = .Control },
+    .{ .lo = 0x2066, .hi = 0x206F, .prop = .Control },
+    .{ .lo = 0x20D0, .hi = 0x20F0, .prop = .Extend },
+    .{ .lo = 0x2122, .hi = 0x2122, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2139, .hi = 0x2139, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2194, .hi = 0x2199, .prop = .ExtendedPictographic },
+    .{ .lo = 0x21A9, .hi = 0x21AA, .prop = .ExtendedPictographic },
+    .{ .lo = 0x231A, .hi = 0x231B, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2328, .hi = 0x2328, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2388, .hi = 0x2388, .prop = .ExtendedPictographic },
+    .{ .lo = 0x23CF, .hi = 0x23CF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x23E9, .hi = 0x23F3, .prop = .ExtendedPictographic },
+    .{ .lo = 0x23F8, .hi = 0x23FA, .prop = .ExtendedPictographic },
+    .{ .lo = 0x24C2, .hi = 0x24C2, .prop = .ExtendedPictographic },
+    .{ .lo = 0x25AA, .hi = 0x25AB, .prop = .ExtendedPictographic },
+    .{ .lo = 0x25B6, .hi = 0x25B6, .prop = .ExtendedPictographic },
+    .{ .lo = 0x25C0, .hi = 0x25C0, .prop = .ExtendedPictographic },
+    .{ .lo = 0x25FB, .hi = 0x25FE, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2600, .hi = 0x2605, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2607, .hi = 0x2612, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2614, .hi = 0x2685, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2690, .hi = 0x2705, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2708, .hi = 0x2712, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2714, .hi = 0x2714, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2716, .hi = 0x2716, .prop = .ExtendedPictographic },
+    .{ .lo = 0x271D, .hi = 0x271D, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2721, .hi = 0x2721, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2728, .hi = 0x2728, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2733, .hi = 0x2734, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2744, .hi = 0x2744, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2747, .hi = 0x2747, .prop = .ExtendedPictographic },
+    .{ .lo = 0x274C, .hi = 0x274C, .prop = .ExtendedPictographic },
+    .{ .lo = 0x274E, .hi = 0x274E, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2753, .hi = 0x2755, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2757, .hi = 0x2757, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2763, .hi = 0x2767, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2795, .hi = 0x2797, .prop = .ExtendedPictographic },
+    .{ .lo = 0x27A1, .hi = 0x27A1, .prop = .ExtendedPictographic },
+    .{ .lo = 0x27B0, .hi = 0x27B0, .prop = .ExtendedPictographic },
+    .{ .lo = 0x27BF, .hi = 0x27BF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2934, .hi = 0x2935, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2B05, .hi = 0x2B07, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2B1B, .hi = 0x2B1C, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2B50, .hi = 0x2B50, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2B55, .hi = 0x2B55, .prop = .ExtendedPictographic },
+    .{ .lo = 0x2CEF, .hi = 0x2CF1, .prop = .Extend },
+    .{ .lo = 0x2D7F, .hi = 0x2D7F, .prop = .Extend },
+    .{ .lo = 0x2DE0, .hi = 0x2DFF, .prop = .Extend },
+    .{ .lo = 0x302A, .hi = 0x302F, .prop = .Extend },
+    .{ .lo = 0x3030, .hi = 0x3030, .prop = .ExtendedPictographic },
+    .{ .lo = 0x303D, .hi = 0x303D, .prop = .ExtendedPictographic },
+    .{ .lo = 0x3099, .hi = 0x309A, .prop = .Extend },
+    .{ .lo = 0x3297, .hi = 0x3297, .prop = .ExtendedPictographic },
+    .{ .lo = 0x3299, .hi = 0x3299, .prop = .ExtendedPictographic },
+    .{ .lo = 0xA66F, .hi = 0xA672, .prop = .Extend },
+    .{ .lo = 0xA674, .hi = 0xA67D, .prop = .Extend },
+    .{ .lo = 0xA69E, .hi = 0xA69F, .prop = .Extend },
+    .{ .lo = 0xA6F0, .hi = 0xA6F1, .prop = .Extend },
+    .{ .lo = 0xA802, .hi = 0xA802, .prop = .Extend },
+    .{ .lo = 0xA806, .hi = 0xA806, .prop = .Extend },
+    .{ .lo = 0xA80B, .hi = 0xA80B, .prop = .Extend },
+    .{ .lo = 0xA823, .hi = 0xA824, .prop = .SpacingMark },
+    .{ .lo = 0xA825, .hi = 0xA826, .prop = .Extend },
+    .{ .lo = 0xA827, .hi = 0xA827, .prop = .SpacingMark },
+    .{ .lo = 0xA82C, .hi = 0xA82C, .prop = .Extend },
+    .{ .lo = 0xA880, .hi = 0xA881, .prop = .SpacingMark },
+    .{ .lo = 0xA8B4, .hi = 0xA8C3, .prop = .SpacingMark },
+    .{ .lo = 0xA8C4, .hi = 0xA8C5, .prop = .Extend },
+    .{ .lo = 0xA8E0, .hi = 0xA8F1, .prop = .Extend },
+    .{ .lo = 0xA8FF, .hi = 0xA8FF, .prop = .Extend },
+    .{ .lo = 0xA926, .hi = 0xA92D, .prop = .Extend },
+    .{ .lo = 0xA947, .hi = 0xA951, .prop = .Extend },
+    .{ .lo = 0xA952, .hi = 0xA953, .prop = .SpacingMark },
+    .{ .lo = 0xA960, .hi = 0xA97C, .prop = .L },
+    .{ .lo = 0xA980, .hi = 0xA982, .prop = .Extend },
+    .{ .lo = 0xA983, .hi = 0xA983, .prop = .SpacingMark },
+    .{ .lo = 0xA9B3, .hi = 0xA9B3, .prop = .Extend },
+    .{ .lo = 0xA9B4, .hi = 0xA9B5, .prop = .SpacingMark },
+    .{ .lo = 0xA9B6, .hi = 0xA9B9, .prop = .Extend },
+    .{ .lo = 0xA9BA, .hi = 0xA9BB, .prop = .SpacingMark },
+    .{ .lo = 0xA9BC, .hi = 0xA9BD, .prop = .Extend },
+    .{ .lo = 0xA9BE, .hi = 0xA9C0, .prop = .SpacingMark },
+    .{ .lo = 0xA9E5, .hi = 0xA9E5, .prop = .Extend },
+    .{ .lo = 0xAA29, .hi = 0xAA2E, .prop = .Extend },
+    .{ .lo = 0xAA2F, .hi = 0xAA30, .prop = .SpacingMark },
+    .{ .lo = 0xAA31, .hi = 0xAA32, .prop = .Extend },
+    .{ .lo = 0xAA33, .hi = 0xAA34, .prop = .SpacingMark },
+    .{ .lo = 0xAA35, .hi = 0xAA36, .prop = .Extend },
+    .{ .lo = 0xAA43, .hi = 0xAA43, .prop = .Extend },
+    .{ .lo = 0xAA4C, .hi = 0xAA4C, .prop = .Extend },
+    .{ .lo = 0xAA4D, .hi = 0xAA4D, .prop = .SpacingMark },
+    .{ .lo = 0xAA7C, .hi = 0xAA7C, .prop = .Extend },
+    .{ .lo = 0xAAB0, .hi = 0xAAB0, .prop = .Extend },
+    .{ .lo = 0xAAB2, .hi = 0xAAB4, .prop = .Extend },
+    .{ .lo = 0xAAB7, .hi = 0xAAB8, .prop = .Extend },
+    .{ .lo = 0xAABE, .hi = 0xAABF, .prop = .Extend },
+    .{ .lo = 0xAAC1, .hi = 0xAAC1, .prop = .Extend },
+    .{ .lo = 0xAAEB, .hi = 0xAAEB, .prop = .SpacingMark },
+    .{ .lo = 0xAAEC, .hi = 0xAAED, .prop = .Extend },
+    .{ .lo = 0xAAEE, .hi = 0xAAEF, .prop = .SpacingMark },
+    .{ .lo = 0xAAF5, .hi = 0xAAF5, .prop = .SpacingMark },
+    .{ .lo = 0xAAF6, .hi = 0xAAF6, .prop = .Extend },
+    .{ .lo = 0xABE3, .hi = 0xABE4, .prop = .SpacingMark },
+    .{ .lo = 0xABE5, .hi = 0xABE5, .prop = .Extend },
+    .{ .lo = 0xABE6, .hi = 0xABE7, .prop = .SpacingMark },
+    .{ .lo = 0xABE8, .hi = 0xABE8, .prop = .Extend },
+    .{ .lo = 0xABE9, .hi = 0xABEA, .prop = .SpacingMark },
+    .{ .lo = 0xABEC, .hi = 0xABEC, .prop = .SpacingMark },
+    .{ .lo = 0xABED, .hi = 0xABED, .prop = .Extend },
+    .{ .lo = 0xD7B0, .hi = 0xD7C6, .prop = .V },
+    .{ .lo = 0xD7CB, .hi = 0xD7FB, .prop = .T },
+    .{ .lo = 0xFB1E, .hi = 0xFB1E, .prop = .Extend },
+    .{ .lo = 0xFE00, .hi = 0xFE0F, .prop = .Extend },
+    .{ .lo = 0xFE20, .hi = 0xFE2F, .prop = .Extend },
+    .{ .lo = 0xFEFF, .hi = 0xFEFF, .prop = .Control },
+    .{ .lo = 0xFF9E, .hi = 0xFF9F, .prop = .Extend },
+    .{ .lo = 0xFFF9, .hi = 0xFFFB, .prop = .Control },
+    .{ .lo = 0x101FD, .hi = 0x101FD, .prop = .Extend },
+    .{ .lo = 0x102E0, .hi = 0x102E0, .prop = .Extend },
+    .{ .lo = 0x10376, .hi = 0x1037A, .prop = .Extend },
+    .{ .lo = 0x10A01, .hi = 0x10A03, .prop = .Extend },
+    .{ .lo = 0x10A05, .hi = 0x10A06, .prop = .Extend },
+    .{ .lo = 0x10A0C, .hi = 0x10A0F, .prop = .Extend },
+    .{ .lo = 0x10A38, .hi = 0x10A3A, .prop = .Extend },
+    .{ .lo = 0x10A3F, .hi = 0x10A3F, .prop = .Extend },
+    .{ .lo = 0x10AE5, .hi = 0x10AE6, .prop = .Extend },
+    .{ .lo = 0x10D24, .hi = 0x10D27, .prop = .Extend },
+    .{ .lo = 0x10EAB, .hi = 0x10EAC, .prop = .Extend },
+    .{ .lo = 0x10F46, .hi = 0x10F50, .prop = .Extend },
+    .{ .lo = 0x10F82, .hi = 0x10F85, .prop = .Extend },
+    .{ .lo = 0x11000, .hi = 0x11000, .prop = .SpacingMark },
+    .{ .lo = 0x11001, .hi = 0x11001, .prop = .Extend },
+    .{ .lo = 0x11002, .hi = 0x11002, .prop = .SpacingMark },
+    .{ .lo = 0x11038, .hi = 0x11046, .prop = .Extend },
+    .{ .lo = 0x11070, .hi = 0x11070, .prop = .Extend },
+    .{ .lo = 0x11073, .hi = 0x11074, .prop = .Extend },
+    .{ .lo = 0x1107F, .hi = 0x11081, .prop = .Extend },
+    .{ .lo = 0x11082, .hi = 0x11082, .prop = .SpacingMark },
+    .{ .lo = 0x110B0, .hi = 0x110B2, .prop = .SpacingMark },
+    .{ .lo = 0x110B3, .hi = 0x110B6, .prop = .Extend },
+    .{ .lo = 0x110B7, .hi = 0x110B8, .prop = .SpacingMark },
+    .{ .lo = 0x110B9, .hi = 0x110BA, .prop = .Extend },
+    .{ .lo = 0x110BD, .hi = 0x110BD, .prop = .Prepend },
+    .{ .lo = 0x110C2, .hi = 0x110C2, .prop = .Extend },
+    .{ .lo = 0x110CD, .hi = 0x110CD, .prop = .Prepend },
+    .{ .lo = 0x11100, .hi = 0x11102, .prop = .Extend },
+    .{ .lo = 0x11127, .hi = 0x1112B, .prop = .Extend },
+    .{ .lo = 0x1112C, .hi = 0x1112C, .prop = .SpacingMark },
+    .{ .lo = 0x1112D, .hi = 0x11134, .prop = .Extend },
+    .{ .lo = 0x11145, .hi = 0x11146, .prop = .SpacingMark },
+    .{ .lo = 0x11173, .hi = 0x11173, .prop = .Extend },
+    .{ .lo = 0x11180, .hi = 0x11181, .prop = .Extend },
+    .{ .lo = 0x11182, .hi = 0x11182, .prop = .SpacingMark },
+    .{ .lo = 0x111B3, .hi = 0x111B5, .prop = .SpacingMark },
+    .{ .lo = 0x111B6, .hi = 0x111BE, .prop = .Extend },
+    .{ .lo = 0x111BF, .hi = 0x111C0, .prop = .SpacingMark },
+    .{ .lo = 0x111C2, .hi = 0x111C3, .prop = .Prepend },
+    .{ .lo = 0x111C9, .hi = 0x111CC, .prop = .Extend },
+    .{ .lo = 0x111CE, .hi = 0x111CE, .prop = .SpacingMark },
+    .{ .lo = 0x111CF, .hi = 0x111CF, .prop = .Extend },
+    .{ .lo = 0x1122C, .hi = 0x1122E, .prop = .SpacingMark },
+    .{ .lo = 0x1122F, .hi = 0x11231, .prop = .Extend },
+    .{ .lo = 0x11232, .hi = 0x11233, .prop = .SpacingMark },
+    .{ .lo = 0x11234, .hi = 0x11234, .prop = .Extend },
+    .{ .lo = 0x11235, .hi = 0x11235, .prop = .SpacingMark },
+    .{ .lo = 0x11236, .hi = 0x11237, .prop = .Extend },
+    .{ .lo = 0x1123E, .hi = 0x1123E, .prop = .Extend },
+    .{ .lo = 0x112DF, .hi = 0x112DF, .prop = .Extend },
+    .{ .lo = 0x112E0, .hi = 0x112E2, .prop = .SpacingMark },
+    .{ .lo = 0x112E3, .hi = 0x112EA, .prop = .Extend },
+    .{ .lo = 0x11300, .hi = 0x11301, .prop = .Extend },
+    .{ .lo = 0x11302, .hi = 0x11303, .prop = .SpacingMark },
+    .{ .lo = 0x1133B, .hi = 0x1133C, .prop = .Extend },
+    .{ .lo = 0x1133E, .hi = 0x1133E, .prop = .Extend },
+    .{ .lo = 0x1133F, .hi = 0x1133F, .prop = .SpacingMark },
+    .{ .lo = 0x11340, .hi = 0x11340, .prop = .Extend },
+    .{ .lo = 0x11341, .hi = 0x11344, .prop = .SpacingMark },
+    .{ .lo = 0x11347, .hi = 0x11348, .prop = .SpacingMark },
+    .{ .lo = 0x1134B, .hi = 0x1134D, .prop = .SpacingMark },
+    .{ .lo = 0x11357, .hi = 0x11357, .prop = .Extend },
+    .{ .lo = 0x11362, .hi = 0x11363, .prop = .SpacingMark },
+    .{ .lo = 0x11366, .hi = 0x1136C, .prop = .Extend },
+    .{ .lo = 0x11370, .hi = 0x11374, .prop = .Extend },
+    .{ .lo = 0x11435, .hi = 0x11437, .prop = .SpacingMark },
+    .{ .lo = 0x11438, .hi = 0x1143F, .prop = .Extend },
+    .{ .lo = 0x11440, .hi = 0x11441, .prop = .SpacingMark },
+    .{ .lo = 0x11442, .hi = 0x11444, .prop = .Extend },
+    .{ .lo = 0x11445, .hi = 0x11445, .prop = .SpacingMark },
+    .{ .lo = 0x11446, .hi = 0x11446, .prop = .Extend },
+    .{ .lo = 0x1145E, .hi = 0x1145E, .prop = .Extend },
+    .{ .lo = 0x114B0, .hi = 0x114B0, .prop = .Extend },
+    .{ .lo = 0x114B1, .hi = 0x114B2, .prop = .SpacingMark },
+    .{ .lo = 0x114B3, .hi = 0x114B8, .prop = .Extend },
+    .{ .lo = 0x114B9, .hi = 0x114B9, .prop = .SpacingMark },
+    .{ .lo = 0x114BA, .hi = 0x114BA, .prop = .Extend },
+    .{ .lo = 0x114BB, .hi = 0x114BC, .prop = .SpacingMark },
+    .{ .lo = 0x114BD, .hi = 0x114BD, .prop = .Extend },
+    .{ .lo = 0x114BE, .hi = 0x114BE, .prop = .SpacingMark },
+    .{ .lo = 0x114BF, .hi = 0x114C0, .prop = .Extend },
+    .{ .lo = 0x114C1, .hi = 0x114C1, .prop = .SpacingMark },
+    .{ .lo = 0x114C2, .hi = 0x114C3, .prop = .Extend },
+    .{ .lo = 0x115AF, .hi = 0x115AF, .prop = .Extend },
+    .{ .lo = 0x115B0, .hi = 0x115B1, .prop = .SpacingMark },
+    .{ .lo = 0x115B2, .hi = 0x115B5, .prop = .Extend },
+    .{ .lo = 0x115B8, .hi = 0x115BB, .prop = .SpacingMark },
+    .{ .lo = 0x115BC, .hi = 0x115BD, .prop = .Extend },
+    .{ .lo = 0x115BE, .hi = 0x115BE, .prop = .SpacingMark },
+    .{ .lo = 0x115BF, .hi = 0x115C0, .prop = .Extend },
+    .{ .lo = 0x115DC, .hi = 0x115DD, .prop = .Extend },
+    .{ .lo = 0x11630, .hi = 0x11632, .prop = .SpacingMark },
+    .{ .lo = 0x11633, .hi = 0x1163A, .prop = .Extend },
+    .{ .lo = 0x1163B, .hi = 0x1163C, .prop = .SpacingMark },
+    .{ .lo = 0x1163D, .hi = 0x1163D, .prop = .Extend },
+    .{ .lo = 0x1163E, .hi = 0x1163E, .prop = .SpacingMark },
+    .{ .lo = 0x1163F, .hi = 0x11640, .prop = .Extend },
+    .{ .lo = 0x116AB, .hi = 0x116AB, .prop = .Extend },
+    .{ .lo = 0x116AC, .hi = 0x116AC, .prop = .SpacingMark },
+    .{ .lo = 0x116AD, .hi = 0x116AD, .prop = .Extend },
+    .{ .lo = 0x116AE, .hi = 0x116AF, .prop = .SpacingMark },
+    .{ .lo = 0x116B0, .hi = 0x116B5, .prop = .Extend },
+    .{ .lo = 0x116B6, .hi = 0x116B6, .prop = .SpacingMark },
+    .{ .lo = 0x116B7, .hi = 0x116B7, .prop = .Extend },
+    .{ .lo = 0x1171D, .hi = 0x1171F, .prop = .Extend },
+    .{ .lo = 0x11722, .hi = 0x11725, .prop = .Extend },
+    .{ .lo = 0x11726, .hi = 0x11726, .prop = .SpacingMark },
+    .{ .lo = 0x11727, .hi = 0x1172B, .prop = .Extend },
+    .{ .lo = 0x1182C, .hi = 0x1182E, .prop = .SpacingMark },
+    .{ .lo = 0x1182F, .hi = 0x11837, .prop = .Extend },
+    .{ .lo = 0x11838, .hi = 0x11838, .prop = .SpacingMark },
+    .{ .lo = 0x11839, .hi = 0x1183A, .prop = .Extend },
+    .{ .lo = 0x11930, .hi = 0x11930, .prop = .Extend },
+    .{ .lo = 0x11931, .hi = 0x11935, .prop = .SpacingMark },
+    .{ .lo = 0x11937, .hi = 0x11938, .prop = .SpacingMark },
+    .{ .lo = 0x1193B, .hi = 0x1193C, .prop = .Extend },
+    .{ .lo = 0x1193D, .hi = 0x1193D, .prop = .SpacingMark },
+    .{ .lo = 0x1193E, .hi = 0x1193E, .prop = .Extend },
+    .{ .lo = 0x1193F, .hi = 0x1193F, .prop = .Prepend },
+    .{ .lo = 0x11940, .hi = 0x11940, .prop = .SpacingMark },
+    .{ .lo = 0x11941, .hi = 0x11941, .prop = .Prepend },
+    .{ .lo = 0x11942, .hi = 0x11942, .prop = .SpacingMark },
+    .{ .lo = 0x11943, .hi = 0x11943, .prop = .Extend },
+    .{ .lo = 0x119D1, .hi = 0x119D3, .prop = .SpacingMark },
+    .{ .lo = 0x119D4, .hi = 0x119D7, .prop = .Extend },
+    .{ .lo = 0x119DA, .hi = 0x119DB, .prop = .Extend },
+    .{ .lo = 0x119DC, .hi = 0x119DF, .prop = .SpacingMark },
+    .{ .lo = 0x119E0, .hi = 0x119E0, .prop = .Extend },
+    .{ .lo = 0x119E4, .hi = 0x119E4, .prop = .SpacingMark },
+    .{ .lo = 0x11A01, .hi = 0x11A0A, .prop = .Extend },
+    .{ .lo = 0x11A33, .hi = 0x11A38, .prop = .Extend },
+    .{ .lo = 0x11A39, .hi = 0x11A39, .prop = .SpacingMark },
+    .{ .lo = 0x11A3A, .hi = 0x11A3A, .prop = .Prepend },
+    .{ .lo = 0x11A3B, .hi = 0x11A3E, .prop = .Extend },
+    .{ .lo = 0x11A47, .hi = 0x11A47, .prop = .Extend },
+    .{ .lo = 0x11A51, .hi = 0x11A56, .prop = .Extend },
+    .{ .lo = 0x11A57, .hi = 0x11A58, .prop = .SpacingMark },
+    .{ .lo = 0x11A59, .hi = 0x11A5B, .prop = .Extend },
+    .{ .lo = 0x11A84, .hi = 0x11A89, .prop = .Prepend },
+    .{ .lo = 0x11A8A, .hi = 0x11A96, .prop = .Extend },
+    .{ .lo = 0x11A97, .hi = 0x11A97, .prop = .SpacingMark },
+    .{ .lo = 0x11A98, .hi = 0x11A99, .prop = .Extend },
+    .{ .lo = 0x11C2F, .hi = 0x11C2F, .prop = .SpacingMark },
+    .{ .lo = 0x11C30, .hi = 0x11C36, .prop = .Extend },
+    .{ .lo = 0x11C38, .hi = 0x11C3D, .prop = .Extend },
+    .{ .lo = 0x11C3E, .hi = 0x11C3E, .prop = .SpacingMark },
+    .{ .lo = 0x11C3F, .hi = 0x11C3F, .prop = .Extend },
+    .{ .lo = 0x11C92, .hi = 0x11CA7, .prop = .Extend },
+    .{ .lo = 0x11CA9, .hi = 0x11CA9, .prop = .SpacingMark },
+    .{ .lo = 0x11CAA, .hi = 0x11CB0, .prop = .Extend },
+    .{ .lo = 0x11CB1, .hi = 0x11CB1, .prop = .SpacingMark },
+    .{ .lo = 0x11CB2, .hi = 0x11CB3, .prop = .Extend },
+    .{ .lo = 0x11CB4, .hi = 0x11CB4, .prop = .SpacingMark },
+    .{ .lo = 0x11CB5, .hi = 0x11CB6, .prop = .Extend },
+    .{ .lo = 0x11D31, .hi = 0x11D36, .prop = .Extend },
+    .{ .lo = 0x11D3A, .hi = 0x11D3A, .prop = .Extend },
+    .{ .lo = 0x11D3C, .hi = 0x11D3D, .prop = .Extend },
+    .{ .lo = 0x11D3F, .hi = 0x11D45, .prop = .Extend },
+    .{ .lo = 0x11D46, .hi = 0x11D46, .prop = .Prepend },
+    .{ .lo = 0x11D47, .hi = 0x11D47, .prop = .Extend },
+    .{ .lo = 0x11D8A, .hi = 0x11D8E, .prop = .SpacingMark },
+    .{ .lo = 0x11D90, .hi = 0x11D91, .prop = .Extend },
+    .{ .lo = 0x11D93, .hi = 0x11D94, .prop = .SpacingMark },
+    .{ .lo = 0x11D95, .hi = 0x11D95, .prop = .Extend },
+    .{ .lo = 0x11D96, .hi = 0x11D96, .prop = .SpacingMark },
+    .{ .lo = 0x11D97, .hi = 0x11D97, .prop = .Extend },
+    .{ .lo = 0x11EF3, .hi = 0x11EF4, .prop = .Extend },
+    .{ .lo = 0x11EF5, .hi = 0x11EF6, .prop = .SpacingMark },
+    .{ .lo = 0x13430, .hi = 0x13438, .prop = .Control },
+    .{ .lo = 0x16AF0, .hi = 0x16AF4, .prop = .Extend },
+    .{ .lo = 0x16B30, .hi = 0x16B36, .prop = .Extend },
+    .{ .lo = 0x16F4F, .hi = 0x16F4F, .prop = .Extend },
+    .{ .lo = 0x16F51, .hi = 0x16F87, .prop = .SpacingMark },
+    .{ .lo = 0x16F8F, .hi = 0x16F92, .prop = .Extend },
+    .{ .lo = 0x16FE4, .hi = 0x16FE4, .prop = .Extend },
+    .{ .lo = 0x16FF0, .hi = 0x16FF1, .prop = .SpacingMark },
+    .{ .lo = 0x1BC9D, .hi = 0x1BC9E, .prop = .Extend },
+    .{ .lo = 0x1BCA0, .hi = 0x1BCA3, .prop = .Control },
+    .{ .lo = 0x1CF00, .hi = 0x1CF2D, .prop = .Extend },
+    .{ .lo = 0x1CF30, .hi = 0x1CF46, .prop = .Extend },
+    .{ .lo = 0x1D165, .hi = 0x1D165, .prop = .Extend },
+    .{ .lo = 0x1D166, .hi = 0x1D166, .prop = .SpacingMark },
+    .{ .lo = 0x1D167, .hi = 0x1D169, .prop = .Extend },
+    .{ .lo = 0x1D16D, .hi = 0x1D16D, .prop = .SpacingMark },
+    .{ .lo = 0x1D16E, .hi = 0x1D172, .prop = .Extend },
+    .{ .lo = 0x1D173, .hi = 0x1D17A, .prop = .Control },
+    .{ .lo = 0x1D17B, .hi = 0x1D182, .prop = .Extend },
+    .{ .lo = 0x1D185, .hi = 0x1D18B, .prop = .Extend },
+    .{ .lo = 0x1D1AA, .hi = 0x1D1AD, .prop = .Extend },
+    .{ .lo = 0x1D242, .hi = 0x1D244, .prop = .Extend },
+    .{ .lo = 0x1DA00, .hi = 0x1DA36, .prop = .Extend },
+    .{ .lo = 0x1DA3B, .hi = 0x1DA6C, .prop = .Extend },
+    .{ .lo = 0x1DA75, .hi = 0x1DA75, .prop = .Extend },
+    .{ .lo = 0x1DA84, .hi = 0x1DA84, .prop = .Extend },
+    .{ .lo = 0x1DA9B, .hi = 0x1DA9F, .prop = .Extend },
+    .{ .lo = 0x1DAA1, .hi = 0x1DAAF, .prop = .Extend },
+    .{ .lo = 0x1E000, .hi = 0x1E006, .prop = .Extend },
+    .{ .lo = 0x1E008, .hi = 0x1E018, .prop = .Extend },
+    .{ .lo = 0x1E01B, .hi = 0x1E021, .prop = .Extend },
+    .{ .lo = 0x1E023, .hi = 0x1E024, .prop = .Extend },
+    .{ .lo = 0x1E026, .hi = 0x1E02A, .prop = .Extend },
+    .{ .lo = 0x1E130, .hi = 0x1E136, .prop = .Extend },
+    .{ .lo = 0x1E2AE, .hi = 0x1E2AE, .prop = .Extend },
+    .{ .lo = 0x1E2EC, .hi = 0x1E2EF, .prop = .Extend },
+    .{ .lo = 0x1E8D0, .hi = 0x1E8D6, .prop = .Extend },
+    .{ .lo = 0x1E944, .hi = 0x1E94A, .prop = .Extend },
+    .{ .lo = 0x1F000, .hi = 0x1F0FF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F10D, .hi = 0x1F10F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F12F, .hi = 0x1F12F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F16C, .hi = 0x1F171, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F17E, .hi = 0x1F17F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F18E, .hi = 0x1F18E, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F191, .hi = 0x1F19A, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F1AD, .hi = 0x1F1E5, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F1E6, .hi = 0x1F1FF, .prop = .RegionalIndicator },
+    .{ .lo = 0x1F201, .hi = 0x1F20F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F21A, .hi = 0x1F21A, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F22F, .hi = 0x1F22F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F232, .hi = 0x1F23A, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F23C, .hi = 0x1F23F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F249, .hi = 0x1F3FA, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F3FB, .hi = 0x1F3FF, .prop = .Extend },
+    .{ .lo = 0x1F400, .hi = 0x1F53D, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F546, .hi = 0x1F64F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F680, .hi = 0x1F6FF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F774, .hi = 0x1F77F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F7D5, .hi = 0x1F7FF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F80C, .hi = 0x1F80F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F848, .hi = 0x1F84F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F85A, .hi = 0x1F85F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F888, .hi = 0x1F88F, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F8AE, .hi = 0x1F8FF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F90C, .hi = 0x1F93A, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F93C, .hi = 0x1F945, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1F947, .hi = 0x1FAFF, .prop = .ExtendedPictographic },
+    .{ .lo = 0x1FC00, .hi = 0x1FFFD, .prop = .ExtendedPictographic },
+    .{ .lo = 0xE0001, .hi = 0xE0001, .prop = .Control },
+    .{ .lo = 0xE0020, .hi = 0xE007F, .prop = .Extend },
+    .{ .lo = 0xE0100, .hi = 0xE01EF, .prop = .Extend },
+};
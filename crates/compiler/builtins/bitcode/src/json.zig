@@ -0,0 +1,275 @@
+// Scanning for the builtin JSON format, as specified in
+// https://www.rfc-editor.org/rfc/rfc8259
+//
+// These only find where things end; JsonFormat.roc does the decoding itself.
+const std = @import("std");
+const RocList = @import("list.zig").RocList;
+const testing = std.testing;
+const expectEqual = testing.expectEqual;
+
+// Arrays and objects nested deeper than this are rejected rather than scanned.
+const MAX_DEPTH = 512;
+
+fn listBytes(list: RocList) []const u8 {
+    if (list.bytes) |bytes| {
+        return bytes[0..list.len()];
+    } else {
+        return &[_]u8{};
+    }
+}
+
+fn isWhitespace(byte: u8) bool {
+    return switch (byte) {
+        ' ', '\t', '\n', '\r' => true,
+        else => false,
+    };
+}
+
+fn whitespaceEnd(bytes: []const u8, start: usize) usize {
+    var index = start;
+
+    while (index < bytes.len and isWhitespace(bytes[index])) {
+        index += 1;
+    }
+
+    return index;
+}
+
+fn isHex(byte: u8) bool {
+    return switch (byte) {
+        '0'...'9', 'a'...'f', 'A'...'F' => true,
+        else => false,
+    };
+}
+
+// `start` is the opening quote. Returns the index just past the closing quote.
+fn stringEnd(bytes: []const u8, start: usize) ?usize {
+    var index = start + 1;
+
+    while (index < bytes.len) {
+        switch (bytes[index]) {
+            '"' => return index + 1,
+            '\\' => {
+                if (index + 1 >= bytes.len) return null;
+
+                switch (bytes[index + 1]) {
+                    '"', '\\', '/', 'b', 'f', 'n', 'r', 't' => index += 2,
+                    'u' => {
+                        if (index + 6 > bytes.len) return null;
+
+                        for (bytes[index + 2 .. index + 6]) |byte| {
+                            if (!isHex(byte)) return null;
+                        }
+
+                        index += 6;
+                    },
+                    else => return null,
+                }
+            },
+            // control characters must be escaped
+            0...0x1F => return null,
+            else => index += 1,
+        }
+    }
+
+    return null;
+}
+
+fn digitsEnd(bytes: []const u8, start: usize) usize {
+    var index = start;
+
+    while (index < bytes.len and std.ascii.isDigit(bytes[index])) {
+        index += 1;
+    }
+
+    return index;
+}
+
+fn numberEnd(bytes: []const u8, start: usize) ?usize {
+    var index = start;
+
+    if (index < bytes.len and bytes[index] == '-') {
+        index += 1;
+    }
+
+    // no leading zeros
+    if (index < bytes.len and bytes[index] == '0') {
+        index += 1;
+    } else {
+        const digits_start = index;
+        index = digitsEnd(bytes, index);
+        if (index == digits_start) return null;
+    }
+
+    if (index < bytes.len and bytes[index] == '.') {
+        const fraction_start = index + 1;
+        index = digitsEnd(bytes, fraction_start);
+        if (index == fraction_start) return null;
+    }
+
+    if (index < bytes.len and (bytes[index] == 'e' or bytes[index] == 'E')) {
+        index += 1;
+
+        if (index < bytes.len and (bytes[index] == '+' or bytes[index] == '-')) {
+            index += 1;
+        }
+
+        const exponent_start = index;
+        index = digitsEnd(bytes, exponent_start);
+        if (index == exponent_start) return null;
+    }
+
+    return index;
+}
+
+fn literalEnd(bytes: []const u8, start: usize, literal: []const u8) ?usize {
+    if (std.mem.startsWith(u8, bytes[start..], literal)) {
+        return start + literal.len;
+    } else {
+        return null;
+    }
+}
+
+// Skips an object key and the colon after it, returning the index just past the colon.
+fn keyEnd(bytes: []const u8, start: usize) ?usize {
+    const key_start = whitespaceEnd(bytes, start);
+    if (key_start >= bytes.len or bytes[key_start] != '"') return null;
+
+    const colon = whitespaceEnd(bytes, stringEnd(bytes, key_start) orelse return null);
+    if (colon >= bytes.len or bytes[colon] != ':') return null;
+
+    return colon + 1;
+}
+
+fn closing(open: u8) u8 {
+    return if (open == '[') ']' else '}';
+}
+
+// Returns the index just past the JSON value that starts at `start`, or null
+// if there is no valid value there. Nested arrays and objects are tracked with
+// an explicit stack, so deeply nested input can't overflow the native stack.
+fn valueEnd(bytes: []const u8, start: usize) ?usize {
+    var stack: [MAX_DEPTH]u8 = undefined;
+    var depth: usize = 0;
+    var index = start;
+
+    while (true) {
+        // Scan one value.
+        index = whitespaceEnd(bytes, index);
+        if (index >= bytes.len) return null;
+
+        const first = bytes[index];
+
+        switch (first) {
+            '"' => index = stringEnd(bytes, index) orelse return null,
+            '-', '0'...'9' => index = numberEnd(bytes, index) orelse return null,
+            't' => index = literalEnd(bytes, index, "true") orelse return null,
+            'f' => index = literalEnd(bytes, index, "false") orelse return null,
+            'n' => index = literalEnd(bytes, index, "null") orelse return null,
+            '[', '{' => {
+                if (depth == MAX_DEPTH) return null;
+
+                const inner = whitespaceEnd(bytes, index + 1);
+
+                if (inner < bytes.len and bytes[inner] == closing(first)) {
+                    index = inner + 1;
+                } else {
+                    stack[depth] = first;
+                    depth += 1;
+
+                    if (first == '{') {
+                        index = keyEnd(bytes, inner) orelse return null;
+                    } else {
+                        index = inner;
+                    }
+
+                    // Scan the first element.
+                    continue;
+                }
+            },
+            else => return null,
+        }
+
+        // After a value, close any finished arrays and objects, and move on to
+        // the next element if there is one.
+        while (true) {
+            if (depth == 0) return index;
+
+            index = whitespaceEnd(bytes, index);
+            if (index >= bytes.len) return null;
+
+            const open = stack[depth - 1];
+
+            if (bytes[index] == ',') {
+                if (open == '{') {
+                    index = keyEnd(bytes, index + 1) orelse return null;
+                } else {
+                    index += 1;
+                }
+
+                break;
+            } else if (bytes[index] == closing(open)) {
+                depth -= 1;
+                index += 1;
+            } else {
+                return null;
+            }
+        }
+    }
+}
+
+// The index of the first non-whitespace byte at or after `index`.
+pub fn skipWhitespaceC(list: RocList, index: u64) callconv(.C) u64 {
+    const bytes = listBytes(list);
+    const start: usize = @intCast(@min(index, bytes.len));
+
+    return whitespaceEnd(bytes, start);
+}
+
+// The index just past the JSON value starting at `index` (after any
+// whitespace), or `index` itself if there is no valid value there.
+pub fn valueEndC(list: RocList, index: u64) callconv(.C) u64 {
+    const bytes = listBytes(list);
+    if (index >= bytes.len) return index;
+
+    return valueEnd(bytes, @intCast(index)) orelse index;
+}
+
+test "valueEnd: literals and numbers" {
+    try expectEqual(valueEnd("true,", 0), 4);
+    try expectEqual(valueEnd("nul", 0), null);
+    try expectEqual(valueEnd("-12.5e+3]", 0), 8);
+    try expectEqual(valueEnd("0123", 0), 1);
+    try expectEqual(valueEnd("1.", 0), null);
+    try expectEqual(valueEnd("-", 0), null);
+}
+
+test "valueEnd: strings" {
+    try expectEqual(valueEnd("\"a\\\"b\" ", 0), 6);
+    try expectEqual(valueEnd("\"\\u00e9\"", 0), 8);
+    try expectEqual(valueEnd("\"\\u00g9\"", 0), null);
+    try expectEqual(valueEnd("\"unterminated", 0), null);
+    try expectEqual(valueEnd("\"tab\there\"", 0), null);
+}
+
+test "valueEnd: arrays and objects" {
+    try expectEqual(valueEnd("[]", 0), 2);
+    try expectEqual(valueEnd("{ }", 0), 3);
+    try expectEqual(valueEnd("[1, [2, {\"a\": [3]}], \"]\"] tail", 0), 25);
+    try expectEqual(valueEnd("{\"a\": 1, \"b\": {\"c\": null}}", 0), 26);
+    try expectEqual(valueEnd("[1, 2", 0), null);
+    try expectEqual(valueEnd("{\"a\" 1}", 0), null);
+    try expectEqual(valueEnd("{1: 2}", 0), null);
+    try expectEqual(valueEnd("[1,]", 0), null);
+}
+
+test "valueEnd: nesting limit" {
+    const too_deep = "[" ** (MAX_DEPTH + 1);
+    try expectEqual(valueEnd(too_deep, 0), null);
+}
+
+test "whitespaceEnd" {
+    try expectEqual(whitespaceEnd(" \t\r\n x", 0), 5);
+    try expectEqual(whitespaceEnd("x", 0), 0);
+    try expectEqual(whitespaceEnd("  ", 0), 2);
+}
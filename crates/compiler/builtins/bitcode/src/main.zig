@@ -5,6 +5,8 @@ const utils = @import("utils.zig");
 const expect = @import("expect.zig");
 const panic_utils = @import("panic.zig");
 const dbg_utils = @import("dbg.zig");
+const backtrace = @import("backtrace.zig");
+const stack = @import("stack.zig");
 
 const ROC_BUILTINS = "roc_builtins";
 const NUM = "num";
@@ -51,6 +53,7 @@ comptime {
     exportDecFn(dec.toI128, "to_i128");
     exportDecFn(dec.fromI128, "from_i128");
     exportDecFn(dec.toStr, "to_str");
+    exportDecFn(dec.toStrWith, "to_str_with");
 
     inline for (INTEGERS) |T| {
         dec.exportFromInt(T, ROC_BUILTINS ++ ".dec.from_int.");
@@ -199,6 +202,7 @@ comptime {
     exportStrFn(str.strEqual, "equal");
     exportStrFn(str.substringUnsafeC, "substring_unsafe");
     exportStrFn(str.getUnsafeC, "get_unsafe");
+    exportStrFn(str.graphemeEndC, "grapheme_end");
     exportStrFn(str.reserveC, "reserve");
     exportStrFn(str.strToUtf8C, "to_utf8");
     exportStrFn(str.fromUtf8C, "from_utf8");
@@ -213,15 +217,24 @@ comptime {
 
     inline for (INTEGERS) |T| {
         str.exportFromInt(T, ROC_BUILTINS ++ "." ++ STR ++ ".from_int.");
+        str.exportFromIntWith(T, ROC_BUILTINS ++ "." ++ STR ++ ".from_int_with.");
         num.exportParseInt(T, ROC_BUILTINS ++ "." ++ STR ++ ".to_int.");
     }
 
     inline for (FLOATS) |T| {
         str.exportFromFloat(T, ROC_BUILTINS ++ "." ++ STR ++ ".from_float.");
+        str.exportFromFloatWith(T, ROC_BUILTINS ++ "." ++ STR ++ ".from_float_with.");
         num.exportParseFloat(T, ROC_BUILTINS ++ "." ++ STR ++ ".to_float.");
     }
 }
 
+// Json Module
+const json = @import("json.zig");
+comptime {
+    exportJsonFn(json.skipWhitespaceC, "skip_whitespace");
+    exportJsonFn(json.valueEndC, "value_end");
+}
+
 // Utils
 comptime {
     exportUtilsFn(utils.test_dbg, "test_dbg");
@@ -236,6 +249,8 @@ comptime {
     exportUtilsFn(utils.decrefCheckNullC, "decref_check_null");
     exportUtilsFn(utils.allocateWithRefcountC, "allocate_with_refcount");
     exportUtilsFn(utils.dictPseudoSeed, "dict_pseudo_seed");
+    exportUtilsFn(backtrace.withBacktrace, "with_backtrace");
+    exportUtilsFn(stack.stackLimit, "stack_limit");
 
     @export(panic_utils.panic, .{ .name = "roc_builtins.utils." ++ "panic", .linkage = .Weak });
     @export(dbg_utils.dbg_impl, .{ .name = "roc_builtins.utils." ++ "dbg_impl", .linkage = .Weak });
@@ -389,6 +404,10 @@ fn exportDecFn(comptime func: anytype, comptime func_name: []const u8) void {
     exportBuiltinFn(func, "dec." ++ func_name);
 }
 
+fn exportJsonFn(comptime func: anytype, comptime func_name: []const u8) void {
+    exportBuiltinFn(func, "json." ++ func_name);
+}
+
 fn exportUtilsFn(comptime func: anytype, comptime func_name: []const u8) void {
     exportBuiltinFn(func, "utils." ++ func_name);
 }
@@ -0,0 +1,72 @@
+const std = @import("std");
+const builtin = @import("builtin");
+
+// How much of the stack to leave below the limit, for the host and for the builtins that run
+// below the last check, like the ones that report the overflow. Small stacks leave a quarter.
+const MARGIN: usize = 256 * 1024;
+
+// The address that a stack check reports an overflow below, on the thread this runs on: a
+// margin above the lowest address of its stack. When we can't tell where that is, it's 0, so
+// no check ever fails.
+pub fn stackLimit() callconv(.C) usize {
+    const bounds = stackBounds() orelse return 0;
+    const size = bounds.high - bounds.low;
+
+    return bounds.low + @min(MARGIN, size / 4);
+}
+
+const Bounds = struct {
+    low: usize,
+    high: usize,
+};
+
+// Both glibc and musl have this, and it's smaller than this on every target they support.
+const PthreadAttr = extern struct {
+    bytes: [128]u8 align(16),
+};
+
+// pthread_t is an integer on Linux and a pointer on macOS, and passed the same way either way
+extern fn pthread_self() usize;
+extern fn pthread_getattr_np(thread: usize, attr: *PthreadAttr) c_int;
+extern fn pthread_attr_getstack(attr: *const PthreadAttr, addr: *usize, size: *usize) c_int;
+extern fn pthread_attr_destroy(attr: *PthreadAttr) c_int;
+extern fn pthread_get_stackaddr_np(thread: usize) usize;
+extern fn pthread_get_stacksize_np(thread: usize) usize;
+extern "kernel32" fn GetCurrentThreadStackLimits(low: *usize, high: *usize) callconv(std.os.windows.WINAPI) void;
+
+fn stackBounds() ?Bounds {
+    switch (builtin.os.tag) {
+        .linux => {
+            // For the main thread, this works out the size from RLIMIT_STACK and the mappings
+            // below the stack.
+            var attr: PthreadAttr = undefined;
+            if (pthread_getattr_np(pthread_self(), &attr) != 0) {
+                return null;
+            }
+            defer _ = pthread_attr_destroy(&attr);
+
+            var low: usize = 0;
+            var size: usize = 0;
+            if (pthread_attr_getstack(&attr, &low, &size) != 0) {
+                return null;
+            }
+
+            return .{ .low = low, .high = low + size };
+        },
+        .macos => {
+            const thread = pthread_self();
+            // this is the top of the stack, which grows down from there
+            const high = pthread_get_stackaddr_np(thread);
+
+            return .{ .low = high - pthread_get_stacksize_np(thread), .high = high };
+        },
+        .windows => {
+            var low: usize = 0;
+            var high: usize = 0;
+            GetCurrentThreadStackLimits(&low, &high);
+
+            return .{ .low = low, .high = high };
+        },
+        else => return null,
+    }
+}
@@ -1,5 +1,6 @@
 const utils = @import("utils.zig");
 const RocList = @import("list.zig").RocList;
+const grapheme = @import("grapheme.zig");
 const UpdateMode = utils.UpdateMode;
 const std = @import("std");
 const mem = std.mem;
@@ -8,6 +9,7 @@ const testing = std.testing;
 const expectEqual = testing.expectEqual;
 const expectError = testing.expectError;
 const expect = testing.expect;
+const expectEqualSlices = testing.expectEqualSlices;
 
 const InPlace = enum(u8) {
     InPlace,
@@ -597,6 +599,89 @@ fn strFromFloatHelp(comptime T: type, float: T) RocStr {
     return RocStr.init(&buf, result.len);
 }
 
+// Num.toStrWith
+// `decimals` is either the number of digits to print after the decimal point
+// (at most MAX_DECIMALS), or SHORTEST_DECIMALS to print the shortest string that
+// round-trips, just like Num.toStr.
+pub const SHORTEST_DECIMALS: u8 = 255;
+pub const MAX_DECIMALS: u8 = 100;
+
+pub fn exportFromIntWith(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(separator: RocStr, int: T, base: u8, decimals: u8) callconv(.C) RocStr {
+            return @call(.always_inline, strFromIntWithHelp, .{ T, separator, int, base, decimals });
+        }
+    }.func;
+
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+fn strFromIntWithHelp(comptime T: type, separator: RocStr, int: T, base: u8, decimals: u8) RocStr {
+    // base 2 uses one character per bit, plus the sign and the decimal point
+    var buf: [@bitSizeOf(T) + 2 + MAX_DECIMALS]u8 = undefined;
+    var len = std.fmt.formatIntBuf(&buf, int, base, .lower, .{});
+
+    if (decimals != SHORTEST_DECIMALS and decimals > 0) {
+        buf[len] = '.';
+        @memset(buf[len + 1 .. len + 1 + decimals], '0');
+        len += 1 + decimals;
+    }
+
+    const group_size: usize = if (base == 10) 3 else 4;
+
+    return initWithSeparators(buf[0..len], separator, group_size);
+}
+
+pub fn exportFromFloatWith(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(separator: RocStr, float: T, _: u8, decimals: u8) callconv(.C) RocStr {
+            return @call(.always_inline, strFromFloatWithHelp, .{ T, separator, float, decimals });
+        }
+    }.func;
+
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+fn strFromFloatWithHelp(comptime T: type, separator: RocStr, float: T, decimals: u8) RocStr {
+    var buf: [400 + MAX_DECIMALS]u8 = undefined;
+    const result = if (decimals == SHORTEST_DECIMALS)
+        std.fmt.bufPrint(&buf, "{d}", .{float}) catch unreachable
+    else
+        std.fmt.bufPrint(&buf, "{d:.[1]}", .{ float, decimals }) catch unreachable;
+
+    return initWithSeparators(result, separator, 3);
+}
+
+// Copies a formatted number into a new RocStr, inserting the separator between
+// every `group_size` digits before the decimal point.
+pub fn initWithSeparators(bytes: []const u8, separator: RocStr, group_size: usize) RocStr {
+    const sep = separator.asSlice();
+    const digits_start: usize = if (bytes.len > 0 and bytes[0] == '-') 1 else 0;
+    const digits_end = std.mem.indexOfScalarPos(u8, bytes, digits_start, '.') orelse bytes.len;
+    const digit_count = digits_end - digits_start;
+
+    if (sep.len == 0 or digit_count <= group_size) {
+        return RocStr.init(bytes.ptr, bytes.len);
+    }
+
+    const separator_count = (digit_count - 1) / group_size;
+    var result = RocStr.allocate(bytes.len + separator_count * sep.len);
+    const out = result.asU8ptrMut();
+    var position: usize = 0;
+
+    for (bytes, 0..) |byte, index| {
+        if (index > digits_start and index < digits_end and (digits_end - index) % group_size == 0) {
+            @memcpy(out[position .. position + sep.len], sep);
+            position += sep.len;
+        }
+
+        out[position] = byte;
+        position += 1;
+    }
+
+    return result;
+}
+
 // Str.split
 pub fn strSplit(string: RocStr, delimiter: RocStr) callconv(.C) RocList {
     const segment_count = countSegments(string, delimiter);
@@ -648,6 +733,45 @@ fn strSplitHelp(array: [*]RocStr, string: RocStr, delimiter: RocStr) void {
     string.incref(i); // i == array.len()
 }
 
+test "strFromIntWithHelp: separators" {
+    const comma = RocStr.init(",", 1);
+    defer comma.decref();
+
+    var result = strFromIntWithHelp(i64, comma, -1234567, 10, SHORTEST_DECIMALS);
+    defer result.decref();
+    try expectEqualSlices(u8, "-1,234,567", result.asSlice());
+
+    var short = strFromIntWithHelp(i64, comma, 123, 10, SHORTEST_DECIMALS);
+    defer short.decref();
+    try expectEqualSlices(u8, "123", short.asSlice());
+}
+
+test "strFromIntWithHelp: base and decimals" {
+    const underscore = RocStr.init("_", 1);
+    defer underscore.decref();
+
+    var hex = strFromIntWithHelp(u32, underscore, 0xdeadbeef, 16, SHORTEST_DECIMALS);
+    defer hex.decref();
+    try expectEqualSlices(u8, "dead_beef", hex.asSlice());
+
+    var decimals = strFromIntWithHelp(u8, RocStr.empty(), 5, 10, 2);
+    defer decimals.decref();
+    try expectEqualSlices(u8, "5.00", decimals.asSlice());
+}
+
+test "strFromFloatWithHelp: decimals" {
+    const comma = RocStr.init(",", 1);
+    defer comma.decref();
+
+    var rounded = strFromFloatWithHelp(f64, comma, 1234.5678, 2);
+    defer rounded.decref();
+    try expectEqualSlices(u8, "1,234.57", rounded.asSlice());
+
+    var shortest = strFromFloatWithHelp(f64, comma, 0.1, SHORTEST_DECIMALS);
+    defer shortest.decref();
+    try expectEqualSlices(u8, "0.1", shortest.asSlice());
+}
+
 test "strSplitHelp: empty delimiter" {
     // Str.split "abc" "" == ["abc"]
     const str_arr = "abc";
@@ -1123,6 +1247,10 @@ pub fn getUnsafeC(string: RocStr, index: u64) callconv(.C) u8 {
     return string.getUnchecked(@intCast(index));
 }
 
+pub fn graphemeEndC(string: RocStr, index: u64) callconv(.C) u64 {
+    return @intCast(grapheme.clusterEnd(string.asSlice(), @intCast(index)));
+}
+
 test "substringUnsafe: start" {
     const str = RocStr.fromSlice("abcdef");
     defer str.decref();
@@ -1,6 +1,8 @@
 const std = @import("std");
 const builtin = @import("builtin");
 const Monotonic = std.builtin.AtomicOrder.Monotonic;
+const Release = std.builtin.AtomicOrder.Release;
+const Acquire = std.builtin.AtomicOrder.Acquire;
 
 const DEBUG_INCDEC = false;
 const DEBUG_TESTING_ALLOC = false;
@@ -181,7 +183,13 @@ const Refcount = enum {
     atomic,
 };
 
-const RC_TYPE = Refcount.normal;
+// Builds that share Roc values between threads get atomic refcounts by declaring
+// `pub const roc_atomic_refcount = true;` in their root file, like atomic_refcount.zig does
+// for `zig build -Datomic-refcount=true`. Zig hosts that import glue.zig can do the same.
+// Atomic operations are noticeably slower than plain ones, even without contention, and every
+// incref and decref pays for them; that's why they're opt-in.
+const root = @import("root");
+const RC_TYPE = if (@hasDecl(root, "roc_atomic_refcount") and root.roc_atomic_refcount) Refcount.atomic else Refcount.normal;
 
 pub fn increfRcPtrC(ptr_to_refcount: *isize, amount: isize) callconv(.C) void {
     if (RC_TYPE == Refcount.none) return;
@@ -357,8 +365,10 @@ inline fn decref_ptr_to_refcount(
                 }
             },
             Refcount.atomic => {
-                var last = @atomicRmw(isize, &refcount_ptr[0], std.builtin.AtomicRmwOp.Sub, 1, Monotonic);
+                var last = @atomicRmw(isize, &refcount_ptr[0], std.builtin.AtomicRmwOp.Sub, 1, Release);
                 if (last == REFCOUNT_ONE_ISIZE) {
+                    // Make sure every other thread is done with the value before it's freed.
+                    @fence(Acquire);
                     free_ptr_to_refcount(refcount_ptr, alignment);
                 }
             },
@@ -289,11 +289,16 @@ clear = \@Dict { buckets, data, maxBucketCapacity, maxLoadFactor, shifts } ->
 ## function on each of them which receives both the key and the old value. Then return a
 ## new dictionary containing the same keys and the converted values.
 map : Dict k a, (k, a -> b) -> Dict k b
-map = \dict, transform ->
-    init = withCapacity (capacity dict)
-
-    walk dict init \answer, k, v ->
-        insert answer k (transform k v)
+map = \@Dict { buckets, data, maxBucketCapacity, maxLoadFactor, shifts }, transform ->
+    # The keys don't change, so the buckets stay valid and nothing is rehashed.
+    # Only the data is copied, since List.map always allocates a new list.
+    @Dict {
+        buckets,
+        data: List.map data \(k, v) -> (k, transform k v),
+        maxBucketCapacity,
+        maxLoadFactor,
+        shifts,
+    }
 
 ## Like [Dict.map], except the transformation function wraps the return value
 ## in a dictionary. At the end, all the dictionaries get joined together
@@ -673,17 +678,9 @@ keepShared = \xs0, ys0 ->
             (ys0, xs0)
         else
             (xs0, ys0)
-    walk
-        xs1
-        (withCapacity (len xs1))
-        (\state, k, v ->
-            when get ys1 k is
-                Ok yv if v == yv ->
-                    insert state k v
 
-                _ ->
-                    state
-        )
+    # keepIf removes in place, so a unique xs1 is reused rather than rebuilt
+    keepIf xs1 \(k, v) -> get ys1 k == Ok v
 
 ## Remove the key-value pairs in the first input that are also in the second
 ## using the [set difference](https://en.wikipedia.org/wiki/Complement_(set_theory)#Relative_complement)
@@ -712,7 +709,7 @@ removeAll = \xs, ys ->
 # Below here is a list of generic helpers and internal data types for Dict
 Bucket : {
     distAndFingerprint : U32, # upper 3 byte: distance to original bucket. lower byte: fingerprint from hash
-    dataIndex : U32, # index into the data list.
+    dataIndex : U32,          # index into the data list.
 }
 
 emptyBucket = { distAndFingerprint: 0, dataIndex: 0 }
@@ -33,6 +33,8 @@ module [
     apply,
     toInspector,
     toStr,
+    toJson,
+    expectSnapshot,
 ]
 
 import Bool exposing [Bool]
@@ -100,6 +102,39 @@ toStr = \val ->
     |> inspect
     |> toDbgStr
 
+## Inspect a value as compact JSON, for output that tools and golden tests can parse.
+##
+## Records become objects, and lists, sets and tuples become arrays. A dict becomes an
+## array of `[key, value]` pairs, so that keys of any type are supported. A tag becomes
+## an object like `{"tag":"Ok","payload":[42]}`, where `payload` is always present.
+## Non-finite [F32] and [F64] values become `null`, and opaque types and functions
+## become the strings `"<opaque>"` and `"<function>"`.
+## ```roc
+## Inspect.toJson { name: "Roc", tags: [Fast, Friendly] }
+## # {"name":"Roc","tags":[{"tag":"Fast","payload":[]},{"tag":"Friendly","payload":[]}]}
+## ```
+toJson : val -> Str where val implements Inspect
+toJson = \val ->
+    val
+    |> inspect
+    |> toJsonStr
+
+## Check a value against a stored snapshot of what it looked like before, for golden tests.
+##
+## The snapshot is [toStr] of the value. `roc test` keeps it in a file named after the
+## snapshot, in a `roc-snapshots` directory next to the file it was given. When there is no
+## such file yet, `roc test` creates it, and `roc test --update-snapshots` replaces every
+## snapshot with the current value. Outside of `roc test`, every value matches.
+## ```roc
+## expect Inspect.expectSnapshot (parse "1 + 2") "parse-addition"
+## ```
+expectSnapshot : val, Str -> Bool where val implements Inspect
+expectSnapshot = \val, name ->
+    matchesSnapshot name (toStr val)
+
+# Whether the snapshot with the given name matches the given string, see expectSnapshot.
+matchesSnapshot : Str, Str -> Bool
+
 # The current default formatter for inspect.
 # This just returns a simple string for debugging.
 # More powerful formatters will likely be wanted in the future.
@@ -342,3 +377,271 @@ dbgWrite = \@DbgFormatter { data }, added ->
 
 toDbgStr : DbgFormatter -> Str
 toDbgStr = \@DbgFormatter { data } -> data
+
+# A formatter that writes compact JSON, used by toJson.
+# The output is meant to be stable, so changes to it are breaking changes.
+JsonFormatter := { data : Str }
+    implements [
+        InspectFormatter {
+            init: jsonInit,
+            list: jsonList,
+            set: jsonSet,
+            dict: jsonDict,
+            tag: jsonTag,
+            tuple: jsonTuple,
+            record: jsonRecord,
+            bool: jsonBool,
+            str: jsonStr,
+            opaque: jsonOpaque,
+            function: jsonFunction,
+            u8: jsonU8,
+            i8: jsonI8,
+            u16: jsonU16,
+            i16: jsonI16,
+            u32: jsonU32,
+            i32: jsonI32,
+            u64: jsonU64,
+            i64: jsonI64,
+            u128: jsonU128,
+            i128: jsonI128,
+            f32: jsonF32,
+            f64: jsonF64,
+            dec: jsonDec,
+        },
+    ]
+
+jsonInit : {} -> JsonFormatter
+jsonInit = \{} -> @JsonFormatter { data: "" }
+
+jsonList : list, ElemWalker (JsonFormatter, Bool) list elem, (elem -> Inspector JsonFormatter) -> Inspector JsonFormatter
+jsonList = \content, walkFn, toJsonInspector ->
+    f0 <- custom
+    jsonWrite f0 "["
+    |> \f1 ->
+        (f2, prependSep), elem <- walkFn content (f1, Bool.false)
+        f3 =
+            if prependSep then
+                jsonWrite f2 ","
+            else
+                f2
+
+        elem
+        |> toJsonInspector
+        |> apply f3
+        |> \f4 -> (f4, Bool.true)
+    |> .0
+    |> jsonWrite "]"
+
+jsonSet : set, ElemWalker (JsonFormatter, Bool) set elem, (elem -> Inspector JsonFormatter) -> Inspector JsonFormatter
+jsonSet = \content, walkFn, toJsonInspector ->
+    jsonList content walkFn toJsonInspector
+
+jsonDict : dict, KeyValWalker (JsonFormatter, Bool) dict key value, (key -> Inspector JsonFormatter), (value -> Inspector JsonFormatter) -> Inspector JsonFormatter
+jsonDict = \d, walkFn, keyToInspector, valueToInspector ->
+    f0 <- custom
+    jsonWrite f0 "["
+    |> \f1 ->
+        (f2, prependSep), key, value <- walkFn d (f1, Bool.false)
+        f3 =
+            if prependSep then
+                jsonWrite f2 ","
+            else
+                f2
+
+        jsonWrite f3 "["
+        |> \x -> apply (keyToInspector key) x
+        |> jsonWrite ","
+        |> \x -> apply (valueToInspector value) x
+        |> jsonWrite "]"
+        |> \f4 -> (f4, Bool.true)
+    |> .0
+    |> jsonWrite "]"
+
+jsonTag : Str, List (Inspector JsonFormatter) -> Inspector JsonFormatter
+jsonTag = \name, fields ->
+    f0 <- custom
+    jsonWrite f0 "{\"tag\":"
+    |> jsonWriteStr name
+    |> jsonWrite ",\"payload\":"
+    |> \x -> apply (jsonTuple fields) x
+    |> jsonWrite "}"
+
+jsonTuple : List (Inspector JsonFormatter) -> Inspector JsonFormatter
+jsonTuple = \fields ->
+    f0 <- custom
+    jsonWrite f0 "["
+    |> \f1 ->
+        (f2, prependSep), inspector <- List.walk fields (f1, Bool.false)
+        f3 =
+            if prependSep then
+                jsonWrite f2 ","
+            else
+                f2
+
+        apply inspector f3
+        |> \f4 -> (f4, Bool.true)
+    |> .0
+    |> jsonWrite "]"
+
+jsonRecord : List { key : Str, value : Inspector JsonFormatter } -> Inspector JsonFormatter
+jsonRecord = \fields ->
+    f0 <- custom
+    jsonWrite f0 "{"
+    |> \f1 ->
+        (f2, prependSep), { key, value } <- List.walk fields (f1, Bool.false)
+        f3 =
+            if prependSep then
+                jsonWrite f2 ","
+            else
+                f2
+
+        jsonWriteStr f3 key
+        |> jsonWrite ":"
+        |> \x -> apply value x
+        |> \f4 -> (f4, Bool.true)
+    |> .0
+    |> jsonWrite "}"
+
+jsonBool : Bool -> Inspector JsonFormatter
+jsonBool = \b ->
+    if b then
+        f0 <- custom
+        jsonWrite f0 "true"
+    else
+        f0 <- custom
+        jsonWrite f0 "false"
+
+jsonStr : Str -> Inspector JsonFormatter
+jsonStr = \s ->
+    f0 <- custom
+    jsonWriteStr f0 s
+
+jsonOpaque : * -> Inspector JsonFormatter
+jsonOpaque = \_ ->
+    f0 <- custom
+    jsonWrite f0 "\"<opaque>\""
+
+jsonFunction : * -> Inspector JsonFormatter
+jsonFunction = \_ ->
+    f0 <- custom
+    jsonWrite f0 "\"<function>\""
+
+jsonU8 : U8 -> Inspector JsonFormatter
+jsonU8 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonI8 : I8 -> Inspector JsonFormatter
+jsonI8 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonU16 : U16 -> Inspector JsonFormatter
+jsonU16 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonI16 : I16 -> Inspector JsonFormatter
+jsonI16 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonU32 : U32 -> Inspector JsonFormatter
+jsonU32 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonI32 : I32 -> Inspector JsonFormatter
+jsonI32 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonU64 : U64 -> Inspector JsonFormatter
+jsonU64 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonI64 : I64 -> Inspector JsonFormatter
+jsonI64 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonU128 : U128 -> Inspector JsonFormatter
+jsonU128 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonI128 : I128 -> Inspector JsonFormatter
+jsonI128 = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonF32 : F32 -> Inspector JsonFormatter
+jsonF32 = \num ->
+    f0 <- custom
+    if Num.isFinite num then
+        jsonWrite f0 (num |> Num.toStr)
+    else
+        jsonWrite f0 "null"
+
+jsonF64 : F64 -> Inspector JsonFormatter
+jsonF64 = \num ->
+    f0 <- custom
+    if Num.isFinite num then
+        jsonWrite f0 (num |> Num.toStr)
+    else
+        jsonWrite f0 "null"
+
+jsonDec : Dec -> Inspector JsonFormatter
+jsonDec = \num ->
+    f0 <- custom
+    jsonWrite f0 (num |> Num.toStr)
+
+jsonWrite : JsonFormatter, Str -> JsonFormatter
+jsonWrite = \@JsonFormatter { data }, added ->
+    @JsonFormatter { data: Str.concat data added }
+
+# Writes a quoted JSON string, escaping quotes, backslashes and control characters.
+jsonWriteStr : JsonFormatter, Str -> JsonFormatter
+jsonWriteStr = \f0, s ->
+    bytes = Str.toUtf8 s
+    escaped =
+        if List.any bytes jsonNeedsEscape then
+            escapedBytes = List.walk bytes (List.withCapacity (List.len bytes + 8)) jsonEscapeByte
+
+            when Str.fromUtf8 escapedBytes is
+                Ok escapedStr -> escapedStr
+                Err _ -> crash "Escaping ASCII bytes cannot produce invalid UTF-8"
+        else
+            s
+
+    f0
+    |> jsonWrite "\""
+    |> jsonWrite escaped
+    |> jsonWrite "\""
+
+jsonNeedsEscape : U8 -> Bool
+jsonNeedsEscape = \b -> b == '"' || b == '\\' || b < 0x20
+
+jsonEscapeByte : List U8, U8 -> List U8
+jsonEscapeByte = \bytes, b ->
+    when b is
+        '"' -> List.concat bytes ['\\', '"']
+        '\\' -> List.concat bytes ['\\', '\\']
+        0x08 -> List.concat bytes ['\\', 'b']
+        0x0c -> List.concat bytes ['\\', 'f']
+        0x0a -> List.concat bytes ['\\', 'n']
+        0x0d -> List.concat bytes ['\\', 'r']
+        0x09 -> List.concat bytes ['\\', 't']
+        _ if b < 0x20 -> List.concat bytes ['\\', 'u', '0', '0', jsonHexDigit (b // 16), jsonHexDigit (b % 16)]
+        _ -> List.append bytes b
+
+jsonHexDigit : U8 -> U8
+jsonHexDigit = \n ->
+    if n < 10 then
+        '0' + n
+    else
+        'a' + n - 10
+
+toJsonStr : JsonFormatter -> Str
+toJsonStr = \@JsonFormatter { data } -> data
@@ -0,0 +1,583 @@
+## A [JSON](https://www.rfc-editor.org/rfc/rfc8259) format for the
+## [Encode] and [Decode] abilities.
+##
+## ```roc
+## import JsonFormat
+##
+## expect
+##     bytes = Encode.toBytes { name: "Roc", tags: ["fast", "friendly"] } JsonFormat.utf8
+##     bytes == Str.toUtf8 "{\"name\":\"Roc\",\"tags\":[\"fast\",\"friendly\"]}"
+## ```
+##
+## Encoding produces compact JSON with no whitespace. Tags without a payload
+## are encoded as strings, and tags with a payload as an object with a single
+## key, so `Circle 1.5` becomes `{"Circle":[1.5]}`.
+##
+## Decoding accepts any whitespace between values. Fields of an object that
+## the decoded record does not have are skipped.
+module [
+    Json,
+    utf8,
+]
+
+import List
+import Str
+import Result exposing [Result]
+import Encode exposing [EncoderFormatting, appendWith]
+import Decode exposing [DecoderFormatting, DecodeResult]
+import Num exposing [U8, U32, U64, I64]
+import Bool exposing [Bool]
+
+## An opaque type with the `EncoderFormatting` and
+## `DecoderFormatting` abilities.
+Json := {}
+    implements [
+        EncoderFormatting {
+            u8: encodeU8,
+            u16: encodeU16,
+            u32: encodeU32,
+            u64: encodeU64,
+            u128: encodeU128,
+            i8: encodeI8,
+            i16: encodeI16,
+            i32: encodeI32,
+            i64: encodeI64,
+            i128: encodeI128,
+            f32: encodeF32,
+            f64: encodeF64,
+            dec: encodeDec,
+            bool: encodeBool,
+            string: encodeString,
+            list: encodeList,
+            record: encodeRecord,
+            tuple: encodeTuple,
+            tag: encodeTag,
+        },
+        DecoderFormatting {
+            u8: decodeU8,
+            u16: decodeU16,
+            u32: decodeU32,
+            u64: decodeU64,
+            u128: decodeU128,
+            i8: decodeI8,
+            i16: decodeI16,
+            i32: decodeI32,
+            i64: decodeI64,
+            i128: decodeI128,
+            f32: decodeF32,
+            f64: decodeF64,
+            dec: decodeDec,
+            bool: decodeBool,
+            string: decodeString,
+            list: decodeList,
+            record: decodeRecord,
+            tuple: decodeTuple,
+        },
+    ]
+
+## Encodes and decodes UTF-8 JSON.
+utf8 : Json
+utf8 = @Json {}
+
+# The index of the first non-whitespace byte at or after the given index.
+skipWhitespace : List U8, U64 -> U64
+
+# The index just past the JSON value starting at the given index (after any
+# whitespace), or the given index itself if there is no valid value there.
+valueEnd : List U8, U64 -> U64
+
+# ENCODING ---------------------------------------------------------------------
+
+numToBytes = \n ->
+    n |> Num.toStr |> Str.toUtf8
+
+# JSON has no representation for NaN or the infinities
+fracToBytes = \n ->
+    if Num.isFinite n then
+        numToBytes n
+    else
+        Str.toUtf8 "null"
+
+encodeU8 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeU16 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeU32 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeU64 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeU128 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeI8 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeI16 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeI32 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeI64 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeI128 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeF32 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (fracToBytes n)
+
+encodeF64 = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (fracToBytes n)
+
+encodeDec = \n ->
+    Encode.custom \bytes, @Json {} ->
+        List.concat bytes (numToBytes n)
+
+encodeBool = \b ->
+    Encode.custom \bytes, @Json {} ->
+        if b then
+            List.concat bytes (Str.toUtf8 "true")
+        else
+            List.concat bytes (Str.toUtf8 "false")
+
+encodeString = \str ->
+    Encode.custom \bytes, @Json {} ->
+        appendString bytes str
+
+encodeList = \lst, encodeElem ->
+    Encode.custom \bytes, @Json {} ->
+        appendArray bytes lst encodeElem
+
+encodeTuple = \elems ->
+    Encode.custom \bytes, @Json {} ->
+        appendArray bytes elems \encoder -> encoder
+
+encodeRecord = \fields ->
+    Encode.custom \bytes, @Json {} ->
+        { buffer } =
+            List.walk fields { buffer: List.append bytes '{', first: Bool.true } \{ buffer: before, first }, { key, value } ->
+                withComma = if first then before else List.append before ','
+
+                withField =
+                    appendString withComma key
+                    |> List.append ':'
+                    |> appendWith value utf8
+
+                { buffer: withField, first: Bool.false }
+
+        List.append buffer '}'
+
+encodeTag = \name, payload ->
+    Encode.custom \bytes, @Json {} ->
+        if List.isEmpty payload then
+            appendString bytes name
+        else
+            List.append bytes '{'
+            |> appendString name
+            |> List.append ':'
+            |> appendArray payload \encoder -> encoder
+            |> List.append '}'
+
+appendArray = \bytes, elems, toEncoder ->
+    { buffer } =
+        List.walk elems { buffer: List.append bytes '[', first: Bool.true } \{ buffer: before, first }, elem ->
+            withComma = if first then before else List.append before ','
+
+            { buffer: appendWith withComma (toEncoder elem) utf8, first: Bool.false }
+
+    List.append buffer ']'
+
+appendString : List U8, Str -> List U8
+appendString = \bytes, str ->
+    strBytes = Str.toUtf8 str
+    withQuote = List.append bytes '"'
+
+    withContent =
+        if List.any strBytes needsEscape then
+            List.walk strBytes (List.reserve withQuote (List.len strBytes + 8)) escapeByte
+        else
+            List.concat withQuote strBytes
+
+    List.append withContent '"'
+
+needsEscape : U8 -> Bool
+needsEscape = \b -> b == '"' || b == '\\' || b < 0x20
+
+escapeByte : List U8, U8 -> List U8
+escapeByte = \bytes, b ->
+    when b is
+        '"' -> List.concat bytes ['\\', '"']
+        '\\' -> List.concat bytes ['\\', '\\']
+        0x08 -> List.concat bytes ['\\', 'b']
+        0x0c -> List.concat bytes ['\\', 'f']
+        0x0a -> List.concat bytes ['\\', 'n']
+        0x0d -> List.concat bytes ['\\', 'r']
+        0x09 -> List.concat bytes ['\\', 't']
+        _ if b < 0x20 -> List.concat bytes ['\\', 'u', '0', '0', hexDigit (b // 16), hexDigit (b % 16)]
+        _ -> List.append bytes b
+
+hexDigit : U8 -> U8
+hexDigit = \n ->
+    if n < 10 then
+        '0' + n
+    else
+        'a' + n - 10
+
+expect
+    actual = Encode.toBytes { name: "Roc", tags: ["fast", "friendly"] } utf8
+    expected = Str.toUtf8 "{\"name\":\"Roc\",\"tags\":[\"fast\",\"friendly\"]}"
+
+    actual == expected
+
+expect
+    actual = Encode.toBytes "say \"hi\"\n\u(7)" utf8
+    expected = Str.toUtf8 "\"say \\\"hi\\\"\\n\\u0007\""
+
+    actual == expected
+
+expect
+    actual = Encode.toBytes (Num.div 1f64 0, 2.5f64) utf8
+    expected = Str.toUtf8 "[null,2.5]"
+
+    actual == expected
+
+# DECODING ---------------------------------------------------------------------
+
+# Splits off the JSON value at the start of `bytes`, after any whitespace.
+takeValue : List U8 -> Result { taken : List U8, rest : List U8 } [TooShort]
+takeValue = \bytes ->
+    start = skipWhitespace bytes 0
+    end = valueEnd bytes start
+
+    if end == start then
+        Err TooShort
+    else
+        Ok {
+            taken: List.sublist bytes { start, len: end - start },
+            rest: List.dropFirst bytes end,
+        }
+
+# Drops any whitespace and then `byte` from the start of `bytes`.
+takeByte : List U8, U8 -> Result (List U8) [TooShort]
+takeByte = \bytes, byte ->
+    start = skipWhitespace bytes 0
+
+    if List.get bytes start == Ok byte then
+        Ok (List.dropFirst bytes (start + 1))
+    else
+        Err TooShort
+
+decodeNum : List U8, (Str -> Result num err) -> DecodeResult num
+decodeNum = \bytes, parse ->
+    when takeValue bytes is
+        Ok { taken, rest } ->
+            isNumber =
+                when List.first taken is
+                    Ok b -> b == '-' || (b >= '0' && b <= '9')
+                    Err _ -> Bool.false
+
+            parsed =
+                when Str.fromUtf8 taken is
+                    Ok str if isNumber -> Result.mapErr (parse str) \_ -> TooShort
+                    _ -> Err TooShort
+
+            when parsed is
+                Ok n -> { result: Ok n, rest }
+                Err TooShort -> { result: Err TooShort, rest: bytes }
+
+        Err TooShort -> { result: Err TooShort, rest: bytes }
+
+decodeU8 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toU8
+decodeU16 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toU16
+decodeU32 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toU32
+decodeU64 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toU64
+decodeU128 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toU128
+decodeI8 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toI8
+decodeI16 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toI16
+decodeI32 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toI32
+decodeI64 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toI64
+decodeI128 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toI128
+decodeF32 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toF32
+decodeF64 = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toF64
+decodeDec = Decode.custom \bytes, @Json {} -> decodeNum bytes Str.toDec
+
+expect
+    actual = Str.toUtf8 " 255 ]" |> Decode.fromBytesPartial utf8
+    actual == { result: Ok 255u8, rest: Str.toUtf8 " ]" }
+
+expect
+    actual : Result I64 _
+    actual = Str.toUtf8 "-12" |> Decode.fromBytes utf8
+    actual == Ok -12
+
+expect
+    actual : Result U8 _
+    actual = Str.toUtf8 "\"12\"" |> Decode.fromBytes utf8
+    actual == Err TooShort
+
+decodeBool = Decode.custom \bytes, @Json {} ->
+    start = skipWhitespace bytes 0
+
+    when List.dropFirst bytes start is
+        ['f', 'a', 'l', 's', 'e', ..] -> { result: Ok Bool.false, rest: List.dropFirst bytes (start + 5) }
+        ['t', 'r', 'u', 'e', ..] -> { result: Ok Bool.true, rest: List.dropFirst bytes (start + 4) }
+        _ -> { result: Err TooShort, rest: bytes }
+
+expect
+    actual = Str.toUtf8 "\n true" |> Decode.fromBytes utf8
+    actual == Ok Bool.true
+
+decodeString = Decode.custom \bytes, @Json {} ->
+    when takeValue bytes is
+        Ok { taken, rest } if List.first taken == Ok '"' ->
+            # The scanner has already checked the escapes, so all that's left
+            # is to drop the quotes and replace any escapes.
+            content = List.sublist taken { start: 1, len: List.len taken - 2 }
+
+            unescaped =
+                if List.contains content '\\' then
+                    unescape content 0 (List.withCapacity (List.len content))
+                else
+                    content
+
+            when Str.fromUtf8 unescaped is
+                Ok str -> { result: Ok str, rest }
+                Err _ -> { result: Err TooShort, rest: bytes }
+
+        _ -> { result: Err TooShort, rest: bytes }
+
+unescape : List U8, U64, List U8 -> List U8
+unescape = \content, index, unescaped ->
+    when List.get content index is
+        Ok '\\' ->
+            when List.get content (index + 1) is
+                Ok 'b' -> unescape content (index + 2) (List.append unescaped 0x08)
+                Ok 'f' -> unescape content (index + 2) (List.append unescaped 0x0c)
+                Ok 'n' -> unescape content (index + 2) (List.append unescaped 0x0a)
+                Ok 'r' -> unescape content (index + 2) (List.append unescaped 0x0d)
+                Ok 't' -> unescape content (index + 2) (List.append unescaped 0x09)
+                Ok 'u' ->
+                    { scalar, len } = unicodeEscape content index
+                    unescape content (index + len) (appendScalar unescaped scalar)
+
+                Ok b -> unescape content (index + 2) (List.append unescaped b)
+                Err OutOfBounds -> unescaped
+
+        Ok b -> unescape content (index + 1) (List.append unescaped b)
+        Err OutOfBounds -> unescaped
+
+# Reads the `\uXXXX` escape at `index`, and the low half of a surrogate pair
+# after it if there is one. Unpaired surrogates become U+FFFD.
+unicodeEscape : List U8, U64 -> { scalar : U32, len : U64 }
+unicodeEscape = \content, index ->
+    high = hexValue content (index + 2)
+
+    if high >= 0xD800 && high <= 0xDBFF then
+        low =
+            when List.sublist content { start: index + 6, len: 2 } is
+                ['\\', 'u'] -> hexValue content (index + 8)
+                _ -> 0
+
+        if low >= 0xDC00 && low <= 0xDFFF then
+            { scalar: 0x10000 + (high - 0xD800) * 0x400 + (low - 0xDC00), len: 12 }
+        else
+            { scalar: 0xFFFD, len: 6 }
+    else if high >= 0xDC00 && high <= 0xDFFF then
+        { scalar: 0xFFFD, len: 6 }
+    else
+        { scalar: high, len: 6 }
+
+hexValue : List U8, U64 -> U32
+hexValue = \content, start ->
+    List.sublist content { start, len: 4 }
+    |> List.walk 0 \value, b ->
+        digit =
+            if b >= 'a' then
+                b - 'a' + 10
+            else if b >= 'A' then
+                b - 'A' + 10
+            else
+                b - '0'
+
+        value * 16 + Num.toU32 digit
+
+appendScalar : List U8, U32 -> List U8
+appendScalar = \bytes, scalar ->
+    continuation = \shift -> Num.toU8 (Num.bitwiseOr 0x80 (Num.bitwiseAnd (Num.shiftRightZfBy scalar shift) 0x3F))
+
+    if scalar < 0x80 then
+        List.append bytes (Num.toU8 scalar)
+    else if scalar < 0x800 then
+        bytes
+        |> List.append (Num.toU8 (Num.bitwiseOr 0xC0 (Num.shiftRightZfBy scalar 6)))
+        |> List.append (continuation 0)
+    else if scalar < 0x10000 then
+        bytes
+        |> List.append (Num.toU8 (Num.bitwiseOr 0xE0 (Num.shiftRightZfBy scalar 12)))
+        |> List.append (continuation 6)
+        |> List.append (continuation 0)
+    else
+        bytes
+        |> List.append (Num.toU8 (Num.bitwiseOr 0xF0 (Num.shiftRightZfBy scalar 18)))
+        |> List.append (continuation 12)
+        |> List.append (continuation 6)
+        |> List.append (continuation 0)
+
+expect
+    actual = Str.toUtf8 " \"a\\tb\\\"c\" ," |> Decode.fromBytesPartial utf8
+    actual == { result: Ok "a\tb\"c", rest: Str.toUtf8 " ," }
+
+expect
+    actual = Str.toUtf8 "\"\\u00e9\\ud83d\\ude00\\ud800\"" |> Decode.fromBytes utf8
+    actual == Ok "é😀\u(FFFD)"
+
+expect
+    actual : Result Str _
+    actual = Str.toUtf8 "\"unterminated" |> Decode.fromBytes utf8
+    actual == Err TooShort
+
+decodeList = \elemDecoder -> Decode.custom \bytes, @Json {} ->
+        when takeByte bytes '[' is
+            Ok afterBracket ->
+                when takeByte afterBracket ']' is
+                    Ok rest -> { result: Ok [], rest }
+                    Err TooShort -> decodeElems elemDecoder afterBracket []
+
+            Err TooShort -> { result: Err TooShort, rest: bytes }
+
+decodeElems = \elemDecoder, bytes, elems ->
+    { result, rest } = Decode.decodeWith bytes elemDecoder utf8
+
+    when result is
+        Ok elem ->
+            when takeByte rest ',' is
+                Ok nextBytes -> decodeElems elemDecoder nextBytes (List.append elems elem)
+                Err TooShort ->
+                    when takeByte rest ']' is
+                        Ok afterList -> { result: Ok (List.append elems elem), rest: afterList }
+                        Err TooShort -> { result: Err TooShort, rest }
+
+        Err e -> { result: Err e, rest }
+
+expect
+    actual : DecodeResult (List U64)
+    actual = Str.toUtf8 "\n[ 1 ,2,\t3 ]" |> Decode.fromBytesPartial utf8
+    actual.result == Ok [1, 2, 3]
+
+expect
+    actual : DecodeResult (List Str)
+    actual = Str.toUtf8 "[ ]" |> Decode.fromBytesPartial utf8
+    actual.result == Ok []
+
+decodeTuple = \initialState, stepElem, finalizer -> Decode.custom \bytes, @Json {} ->
+        when takeByte bytes '[' is
+            Ok afterBracket ->
+                { result, rest } =
+                    when takeByte afterBracket ']' is
+                        Ok afterTuple -> { result: Ok initialState, rest: afterTuple }
+                        Err TooShort -> decodeTupleElems stepElem initialState 0 afterBracket
+
+                when result is
+                    Ok state -> { result: finalizer state, rest }
+                    Err e -> { result: Err e, rest }
+
+            Err TooShort -> { result: Err TooShort, rest: bytes }
+
+# NB: the stepper function must be passed explicitly until #2894 is resolved.
+decodeTupleElems = \stepElem, state, index, bytes ->
+    { result, rest } =
+        when stepElem state index is
+            Next decoder -> Decode.decodeWith bytes decoder utf8
+            TooLong -> skipValue bytes state
+
+    when result is
+        Ok newState ->
+            when takeByte rest ',' is
+                Ok nextBytes -> decodeTupleElems stepElem newState (index + 1) nextBytes
+                Err TooShort ->
+                    when takeByte rest ']' is
+                        Ok afterTuple -> { result: Ok newState, rest: afterTuple }
+                        Err TooShort -> { result: Err TooShort, rest }
+
+        Err e -> { result: Err e, rest }
+
+# Skips over the next value, whatever it is, keeping `state` as it was.
+skipValue : List U8, state -> DecodeResult state
+skipValue = \bytes, state ->
+    when takeValue bytes is
+        Ok { rest } -> { result: Ok state, rest }
+        Err TooShort -> { result: Err TooShort, rest: bytes }
+
+expect
+    actual = Str.toUtf8 "[\"The Answer is\", 42]" |> Decode.fromBytes utf8
+    actual == Ok ("The Answer is", 42u8)
+
+decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Json {} ->
+        decodeFields = \state, fieldBytes ->
+            { result: keyResult, rest: afterKey } = Decode.decodeWith fieldBytes decodeString utf8
+
+            when (keyResult, takeByte afterKey ':') is
+                (Ok key, Ok valueBytes) ->
+                    { result: valueResult, rest: afterValue } =
+                        when stepField state key is
+                            Keep valueDecoder -> Decode.decodeWith valueBytes valueDecoder utf8
+                            Skip -> skipValue valueBytes state
+
+                    when valueResult is
+                        Ok newState ->
+                            when takeByte afterValue ',' is
+                                Ok nextBytes -> decodeFields newState nextBytes
+                                Err TooShort ->
+                                    when takeByte afterValue '}' is
+                                        Ok afterObject -> { result: Ok newState, rest: afterObject }
+                                        Err TooShort -> { result: Err TooShort, rest: afterValue }
+
+                        Err e -> { result: Err e, rest: afterValue }
+
+                _ -> { result: Err TooShort, rest: fieldBytes }
+
+        when takeByte bytes '{' is
+            Ok afterBrace ->
+                { result, rest } =
+                    when takeByte afterBrace '}' is
+                        Ok afterObject -> { result: Ok initialState, rest: afterObject }
+                        Err TooShort -> decodeFields initialState afterBrace
+
+                when result is
+                    Ok state -> { result: finalizer state utf8, rest }
+                    Err e -> { result: Err e, rest }
+
+            Err TooShort -> { result: Err TooShort, rest: bytes }
+
+expect
+    input = Str.toUtf8 "{ \"extra\": {\"a\": [1, {}]}, \"name\" : \"Roc\", \"age\": 8 }"
+
+    actual : Result { name : Str, age : U8 } _
+    actual = Decode.fromBytes input utf8
+
+    actual == Ok { name: "Roc", age: 8 }
+
+expect
+    input = Str.toUtf8 "{\"name\": \"Roc\" \"age\": 8}"
+
+    actual : Result { name : Str, age : U8 } _
+    actual = Decode.fromBytes input utf8
+
+    actual == Err TooShort
@@ -69,6 +69,8 @@ module [
     walkBackwardsUntil,
     countIf,
     chunksOf,
+    windows,
+    groupBy,
 ]
 
 import Bool exposing [Bool, Eq]
@@ -1258,6 +1260,57 @@ chunksOfHelp = \listRest, chunkSize, chunks ->
         { before, others } = List.split listRest chunkSize
         chunksOfHelp others chunkSize (List.append chunks before)
 
+## Returns every run of consecutive elements of the given window size, in order.
+## Each window starts one element after the previous one. If the window size is 0
+## or is larger than the list, the result is an empty list.
+## ```roc
+## List.windows [1, 2, 3, 4] 2 == [[1, 2], [2, 3], [3, 4]]
+## ```
+windows : List a, U64 -> List (List a)
+windows = \list, windowSize ->
+    length = List.len list
+
+    if windowSize == 0 || windowSize > length then
+        []
+    else
+        count = Num.subWrap length windowSize |> Num.addWrap 1
+        windowsHelp list windowSize 0 count (List.withCapacity count)
+
+windowsHelp : List a, U64, U64, U64, List (List a) -> List (List a)
+windowsHelp = \list, windowSize, index, count, answer ->
+    if index < count then
+        window = List.sublist list { start: index, len: windowSize }
+        windowsHelp list windowSize (Num.addWrap index 1) count (List.append answer window)
+    else
+        answer
+
+## Splits the list into groups of adjacent elements. A new group starts whenever
+## the given function returns `Bool.false` for an element and the one before it.
+## If the provided list is empty, the result is an empty list.
+## ```roc
+## List.groupBy [1, 1, 2, 3, 3, 3] (\a, b -> a == b) == [[1, 1], [2], [3, 3, 3]]
+## ```
+groupBy : List a, (a, a -> Bool) -> List (List a)
+groupBy = \list, sameGroup ->
+    if List.isEmpty list then
+        []
+    else
+        groupByHelp list sameGroup 0 1 []
+
+groupByHelp : List a, (a, a -> Bool), U64, U64, List (List a) -> List (List a)
+groupByHelp = \list, sameGroup, start, index, groups ->
+    if index < List.len list then
+        previous = List.getUnsafe list (Num.subWrap index 1)
+        current = List.getUnsafe list index
+
+        if sameGroup previous current then
+            groupByHelp list sameGroup start (Num.addWrap index 1) groups
+        else
+            group = List.sublist list { start, len: Num.subWrap index start }
+            groupByHelp list sameGroup index (Num.addWrap index 1) (List.append groups group)
+    else
+        List.append groups (List.sublist list { start, len: Num.subWrap index start })
+
 ## Like [List.map], except the transformation function returns a [Result].
 ## If that function ever returns `Err`, [mapTry] immediately returns that `Err`.
 ## If it returns `Ok` for every element, [mapTry] returns `Ok` with the transformed list.
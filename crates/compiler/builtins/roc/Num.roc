@@ -100,6 +100,7 @@ module [
     divTrunc,
     divTruncChecked,
     toStr,
+    toStrWith,
     isMultipleOf,
     minI8,
     maxI8,
@@ -548,6 +549,32 @@ tau = 2 * pi
 ## [F64] or [F32] value, the returned string will be `"NaN"`, `"∞"`, or `"-∞"`.
 ##
 toStr : Num * -> Str
+
+## Convert a number to a [Str], with control over how it is formatted.
+##
+## - `base` is the radix [Int] values are written in, from 2 to 36. It defaults to 10. [Frac] values are always written in base 10.
+## - `decimals` defaults to `Shortest`, which gives the same digits as [Num.toStr]. `Exactly n` rounds to `n` digits after the decimal point (at most 100), padding with zeros if needed.
+## - `separator` is inserted between groups of digits before the decimal point: every 3 digits in base 10, and every 4 in other bases. It defaults to `""`.
+## ```roc
+## Num.toStrWith 1234567 { separator: "," } # "1,234,567"
+## Num.toStrWith 255 { base: 16 } # "ff"
+## Num.toStrWith 3.14159 { decimals: Exactly 2 } # "3.14"
+## ```
+## This will crash if `base` is not between 2 and 36.
+toStrWith : Num *, { base ? U8, decimals ? [Shortest, Exactly U8], separator ? Str } -> Str
+toStrWith = \num, { base ? 10, decimals ? Shortest, separator ? "" } ->
+    if base < 2 || base > 36 then
+        crash "Num.toStrWith: base must be between 2 and 36"
+    else
+        decimalPlaces =
+            when decimals is
+                Shortest -> 255
+                Exactly places -> Num.min places 100
+
+        toStrWithLowlevel separator num base decimalPlaces
+
+toStrWithLowlevel : Str, Num *, U8, U8 -> Str
+
 intCast : Int a -> Int b
 
 compare : Num a, Num a -> [LT, EQ, GT]
@@ -1,6 +1,6 @@
 ## Strings represent text. For example, `"Hi!"` is a string.
 ##
-## This guide starts at a high level and works down to the in-memory representation of strings and their [performance characteristics](#performance). For reasons that will be explained later in this guide, some string operations are in the `Str` module while others (notably [capitalization](#capitalization), [code points](#code-points), [graphemes](#graphemes), and sorting) are in separate packages. There's also a list of recommendations for [when to use code points, graphemes, and UTF-8](#when-to-use).
+## This guide starts at a high level and works down to the in-memory representation of strings and their [performance characteristics](#performance). For reasons that will be explained later in this guide, some string operations are in the `Str` module while others (notably [capitalization](#capitalization), [code points](#code-points), and sorting) are in separate packages. There's also a list of recommendations for [when to use code points, graphemes, and UTF-8](#when-to-use).
 ##
 ## ## Syntax
 ##
@@ -121,7 +121,7 @@
 ## * `鹏`
 ## * `👩‍👩‍👦‍👦`
 ##
-## Note that although *grapheme* is less ambiguous than *character*, its definition is still open to interpretation. To address this, Unicode has formally specified [text segmentation rules](https://www.unicode.org/reports/tr29/) which define grapheme boundaries in precise technical terms. We won't get into those rules here, but since they can change with new Unicode releases, functions for working with graphemes are in the [roc-lang/unicode](https://github.com/roc-lang/unicode) package rather than in the builtin [`Str`](https://www.roc-lang.org/builtins/Str) module. This allows them to be updated without being blocked on a new release of the Roc language.
+## Note that although *grapheme* is less ambiguous than *character*, its definition is still open to interpretation. To address this, Unicode has formally specified [text segmentation rules](https://www.unicode.org/reports/tr29/) which define grapheme boundaries in precise technical terms. We won't get into those rules here, but [`Str.graphemes`](https://www.roc-lang.org/builtins/Str#graphemes), [`Str.countGraphemes`](https://www.roc-lang.org/builtins/Str#countGraphemes), and [`Str.walkGraphemes`](https://www.roc-lang.org/builtins/Str#walkGraphemes) follow them. Since the rules can change with new Unicode releases, these functions follow the version of Unicode that the Roc compiler was built with.
 ##
 ## ### Code Points
 ##
@@ -258,7 +258,7 @@
 ## * If you are implementing a Unicode library like [roc-lang/unicode](https://github.com/roc-lang/unicode), working in terms of code points will be unavoidable. Aside from basic readability considerations like `\u(...)` in string literals, if you have the option to avoid working in terms of code points, it is almost always correct to avoid them.
 ## * If it seems like a good idea to split a string into "characters" (graphemes), you should definitely stop and reconsider whether this is really the best design. Almost always, doing this is some combination of more error-prone or slower (usually both) than doing something else that does not require taking graphemes into consideration.
 ##
-## For this reason (among others), grapheme functions live in [roc-lang/unicode](https://github.com/roc-lang/unicode) rather than in [`Str`](https://www.roc-lang.org/builtins/Str). They are more niche than they seem, so they should not be reached for all the time!
+## For this reason (among others), the grapheme functions in [`Str`](https://www.roc-lang.org/builtins/Str) are more niche than they seem, so they should not be reached for all the time!
 ##
 ## ## Performance
 ##
@@ -362,6 +362,9 @@ module [
     splitLast,
     walkUtf8,
     walkUtf8WithIndex,
+    walkGraphemes,
+    graphemes,
+    countGraphemes,
     reserve,
     releaseExcessCapacity,
     withCapacity,
@@ -1018,6 +1021,53 @@ walkUtf8Help = \str, state, step, index, length ->
 expect (walkUtf8 "ABC" [] List.append) == [65, 66, 67]
 expect (walkUtf8 "鹏" [] List.append) == [233, 185, 143]
 
+## Gives the byte index just past the end of the [grapheme](#graphemes) that
+## starts at the given byte index, without performing a bounds check.
+graphemeEnd : Str, U64 -> U64
+
+## Walks over the [graphemes](#graphemes) of the given [Str] and calls a function
+## to update state for each one. Each grapheme is given as a [Str].
+## ```roc
+## expect Str.walkGraphemes "🇺🇸é!" [] List.append == ["🇺🇸", "é", "!"]
+## ```
+walkGraphemes : Str, state, (state, Str -> state) -> state
+walkGraphemes = \str, initial, step ->
+    walkGraphemesHelp str initial step 0 (Str.countUtf8Bytes str)
+
+walkGraphemesHelp : Str, state, (state, Str -> state), U64, U64 -> state
+walkGraphemesHelp = \str, state, step, index, length ->
+    if index < length then
+        end = graphemeEnd str index
+        grapheme = Str.substringUnsafe str index (Num.subWrap end index)
+        newState = step state grapheme
+
+        walkGraphemesHelp str newState step end length
+    else
+        state
+
+## Splits a [Str] into its [graphemes](#graphemes), according to the Unicode
+## [text segmentation rules](https://www.unicode.org/reports/tr29/).
+## ```roc
+## expect Str.graphemes "👩‍👩‍👦‍👦 and é" == ["👩‍👩‍👦‍👦", " ", "a", "n", "d", " ", "é"]
+## ```
+graphemes : Str -> List Str
+graphemes = \str ->
+    walkGraphemes str [] List.append
+
+## Counts the [graphemes](#graphemes) in a [Str]. This can be less than the
+## number of code points, and is often less than the number of UTF-8 bytes.
+## ```roc
+## expect Str.countGraphemes "👩‍👩‍👦‍👦" == 1
+## expect Str.countUtf8Bytes "👩‍👩‍👦‍👦" == 25
+## ```
+countGraphemes : Str -> U64
+countGraphemes = \str ->
+    walkGraphemes str 0 \count, _ -> Num.addWrap count 1
+
+expect countGraphemes "" == 0
+expect countGraphemes "e\u(301)" == 1
+expect graphemes "a\r\nb" == ["a", "\r\n", "b"]
+
 ## Shrink the memory footprint of a str such that its capacity and length are equal.
 ## Note: This will also convert seamless slices to regular lists.
 releaseExcessCapacity : Str -> Str
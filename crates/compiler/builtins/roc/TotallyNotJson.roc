@@ -1,5 +1,5 @@
 ## THIS MODULE IS DEPRECATED AND CURRENTLY IN THE PROCESS OF BEING REMOVED
-## FROM STD LIBRARY
+## FROM STD LIBRARY, use [JsonFormat] instead
 module [
     Json,
     json,
@@ -196,11 +196,11 @@ escapedByteToJson = \b ->
         0x22 -> [0x5c, 0x22] # U+0022 Quotation mark
         0x5c -> [0x5c, 0x5c] # U+005c Reverse solidus
         0x2f -> [0x5c, 0x2f] # U+002f Solidus
-        0x08 -> [0x5c, 'b'] # U+0008 Backspace
-        0x0c -> [0x5c, 'f'] # U+000c Form feed
-        0x0a -> [0x5c, 'n'] # U+000a Line feed
-        0x0d -> [0x5c, 'r'] # U+000d Carriage return
-        0x09 -> [0x5c, 'r'] # U+0009 Tab
+        0x08 -> [0x5c, 'b']  # U+0008 Backspace
+        0x0c -> [0x5c, 'f']  # U+000c Form feed
+        0x0a -> [0x5c, 'n']  # U+000a Line feed
+        0x0d -> [0x5c, 'r']  # U+000d Carriage return
+        0x09 -> [0x5c, 'r']  # U+0009 Tab
         _ -> [b]
 
 encodeList = \lst, encodeElem ->
@@ -903,14 +903,14 @@ isEscapedChar = \b ->
 escapedCharFromJson : U8 -> U8
 escapedCharFromJson = \b ->
     when b is
-        '"' -> 0x22 # U+0022 Quotation mark
+        '"' -> 0x22  # U+0022 Quotation mark
         '\\' -> 0x5c # U+005c Reverse solidus
-        '/' -> 0x2f # U+002f Solidus
-        'b' -> 0x08 # U+0008 Backspace
-        'f' -> 0x0c # U+000c Form feed
-        'n' -> 0x0a # U+000a Line feed
-        'r' -> 0x0d # U+000d Carriage return
-        't' -> 0x09 # U+0009 Tab
+        '/' -> 0x2f  # U+002f Solidus
+        'b' -> 0x08  # U+0008 Backspace
+        'f' -> 0x0c  # U+000c Form feed
+        'n' -> 0x0a  # U+000a Line feed
+        'r' -> 0x0d  # U+000d Carriage return
+        't' -> 0x09  # U+0009 Tab
         _ -> b
 
 expect escapedCharFromJson 'n' == '\n'
@@ -11,5 +11,6 @@ package [
     Hash,
     Box,
     TotallyNotJson,
+    JsonFormat,
     Inspect,
 ] {}
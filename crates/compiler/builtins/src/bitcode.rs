@@ -352,6 +352,8 @@ pub const STR_ENDS_WITH: &str = "roc_builtins.str.ends_with";
 pub const STR_NUMBER_OF_BYTES: &str = "roc_builtins.str.number_of_bytes";
 pub const STR_FROM_INT: IntrinsicName = int_intrinsic!("roc_builtins.str.from_int");
 pub const STR_FROM_FLOAT: IntrinsicName = float_intrinsic!("roc_builtins.str.from_float");
+pub const STR_FROM_INT_WITH: IntrinsicName = int_intrinsic!("roc_builtins.str.from_int_with");
+pub const STR_FROM_FLOAT_WITH: IntrinsicName = float_intrinsic!("roc_builtins.str.from_float_with");
 pub const STR_TO_INT: IntrinsicName = int_intrinsic!("roc_builtins.str.to_int");
 pub const STR_TO_FLOAT: IntrinsicName = float_intrinsic!("roc_builtins.str.to_float");
 pub const STR_TO_DECIMAL: &str = "roc_builtins.str.to_decimal";
@@ -364,6 +366,7 @@ pub const STR_TRIM: &str = "roc_builtins.str.trim";
 pub const STR_TRIM_START: &str = "roc_builtins.str.trim_start";
 pub const STR_TRIM_END: &str = "roc_builtins.str.trim_end";
 pub const STR_GET_UNSAFE: &str = "roc_builtins.str.get_unsafe";
+pub const STR_GRAPHEME_END: &str = "roc_builtins.str.grapheme_end";
 pub const STR_RESERVE: &str = "roc_builtins.str.reserve";
 pub const STR_CLONE_TO: &str = "roc_builtins.str.clone_to";
 pub const STR_WITH_CAPACITY: &str = "roc_builtins.str.with_capacity";
@@ -418,13 +421,18 @@ pub const DEC_SUB_OR_PANIC: &str = "roc_builtins.dec.sub_or_panic";
 pub const DEC_SUB_SATURATED: &str = "roc_builtins.dec.sub_saturated";
 pub const DEC_SUB_WITH_OVERFLOW: &str = "roc_builtins.dec.sub_with_overflow";
 pub const DEC_TAN: &str = "roc_builtins.dec.tan";
+pub const DEC_TO_F64: &str = "roc_builtins.dec.to_f64";
 pub const DEC_TO_I128: &str = "roc_builtins.dec.to_i128";
 pub const DEC_FROM_I128: &str = "roc_builtins.dec.from_i128";
 pub const DEC_TO_STR: &str = "roc_builtins.dec.to_str";
+pub const DEC_TO_STR_WITH: &str = "roc_builtins.dec.to_str_with";
 pub const DEC_ROUND: IntrinsicName = int_intrinsic!("roc_builtins.dec.round");
 pub const DEC_FLOOR: IntrinsicName = int_intrinsic!("roc_builtins.dec.floor");
 pub const DEC_CEILING: IntrinsicName = int_intrinsic!("roc_builtins.dec.ceiling");
 
+pub const JSON_SKIP_WHITESPACE: &str = "roc_builtins.json.skip_whitespace";
+pub const JSON_VALUE_END: &str = "roc_builtins.json.value_end";
+
 pub const UTILS_DBG_IMPL: &str = "roc_builtins.utils.dbg_impl";
 pub const UTILS_TEST_PANIC: &str = "roc_builtins.utils.test_panic";
 pub const UTILS_ALLOCATE_WITH_REFCOUNT: &str = "roc_builtins.utils.allocate_with_refcount";
@@ -437,6 +445,8 @@ pub const UTILS_FREE_DATA_PTR: &str = "roc_builtins.utils.free_data_ptr";
 pub const UTILS_IS_UNIQUE: &str = "roc_builtins.utils.is_unique";
 pub const UTILS_DECREF_CHECK_NULL: &str = "roc_builtins.utils.decref_check_null";
 pub const UTILS_DICT_PSEUDO_SEED: &str = "roc_builtins.utils.dict_pseudo_seed";
+pub const UTILS_WITH_BACKTRACE: &str = "roc_builtins.utils.with_backtrace";
+pub const UTILS_STACK_LIMIT: &str = "roc_builtins.utils.stack_limit";
 
 pub const UTILS_EXPECT_FAILED_START_SHARED_BUFFER: &str =
     "roc_builtins.utils.expect_failed_start_shared_buffer";
@@ -123,6 +123,7 @@ map_symbol_to_lowlevel_and_arity! {
     StrTrimStart; STR_TRIM_START; 1,
     StrTrimEnd; STR_TRIM_END; 1,
     StrGetUnsafe; STR_GET_UNSAFE; 2,
+    StrGraphemeEnd; STR_GRAPHEME_END; 2,
     StrSubstringUnsafe; STR_SUBSTRING_UNSAFE; 3,
     StrReserve; STR_RESERVE; 2,
     StrToNum; STR_TO_NUM; 1,
@@ -135,6 +136,9 @@ map_symbol_to_lowlevel_and_arity! {
     ListReserve; LIST_RESERVE; 2,
     ListIsUnique; LIST_IS_UNIQUE; 1,
     ListClone; LIST_CLONE; 1,
+    JsonSkipWhitespace; JSON_FORMAT_SKIP_WHITESPACE; 2,
+    JsonValueEnd; JSON_FORMAT_VALUE_END; 2,
+    InspectMatchesSnapshot; INSPECT_MATCHES_SNAPSHOT; 2,
     ListAppendUnsafe; LIST_APPEND_UNSAFE; 2,
     ListPrepend; LIST_PREPEND; 2,
     ListGetUnsafe; LIST_GET_UNSAFE; 2,
@@ -201,6 +205,7 @@ map_symbol_to_lowlevel_and_arity! {
     NumShiftRightBy; NUM_SHIFT_RIGHT; 2,
     NumShiftRightZfBy; NUM_SHIFT_RIGHT_ZERO_FILL; 2,
     NumToStr; NUM_TO_STR; 1,
+    NumToStrWith; NUM_TO_STR_WITH_LOWLEVEL; 4,
     NumCountLeadingZeroBits; NUM_COUNT_LEADING_ZERO_BITS; 1,
     NumCountTrailingZeroBits; NUM_COUNT_TRAILING_ZERO_BITS; 1,
     NumCountOneBits; NUM_COUNT_ONE_BITS; 1,
@@ -5,12 +5,13 @@ use std::sync::Arc;
 use crate::abilities::SpecializationId;
 use crate::exhaustive::{ExhaustiveContext, SketchedRows};
 use crate::expected::{Expected, PExpected};
+use crate::pattern::Pattern;
 use roc_collections::soa::{EitherIndex, Index, Slice};
 use roc_module::ident::TagName;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::{Loc, Region};
 use roc_types::subs::{ExhaustiveMark, IllegalCycleMark, Variable};
-use roc_types::types::{Category, PatternCategory, TypeTag, Types};
+use roc_types::types::{AnnotationSource, Category, PatternCategory, TypeTag, Types};
 
 pub struct Constraints {
     pub constraints: Vec<Constraint>,
@@ -301,6 +302,19 @@ impl Constraints {
         self.and_constraint([equal, storage])
     }
 
+    /// The breadcrumb for an `Eq` constraint against this expectation.
+    pub fn provenance(&self, expected_index: ExpectedTypeIndex) -> Provenance {
+        match &self.expectations[expected_index.index()] {
+            Expected::FromAnnotation(loc_pattern, _, AnnotationSource::TypedBody { region }, _) => {
+                match loc_pattern.value {
+                    Pattern::Identifier(symbol) => Provenance::Annotation(symbol, *region),
+                    _ => Provenance::None,
+                }
+            }
+            _ => Provenance::None,
+        }
+    }
+
     pub fn equal_pattern_types(
         &mut self,
         type_index: TypeOrVar,
@@ -714,6 +728,19 @@ pub struct Eq(
     pub Region,
 );
 
+/// A breadcrumb recording where the type an `Eq` constraint requires came from.
+///
+/// The solver remembers these as it goes, so that when a type they pinned down later conflicts
+/// with something (like a number literal that doesn't fit in it), the error can show the chain
+/// of requirements that led there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Provenance {
+    /// Nothing worth mentioning.
+    None,
+    /// The type comes from the annotation on this symbol, at this region.
+    Annotation(Symbol, Region),
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct PatternEq(
     pub TypeOrVar,
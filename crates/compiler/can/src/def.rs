@@ -15,6 +15,7 @@ use crate::expr::AnnotatedMark;
 use crate::expr::ClosureData;
 use crate::expr::Declarations;
 use crate::expr::Expr::{self, *};
+use crate::expr::IncompleteImpl;
 use crate::expr::StructAccessorData;
 use crate::expr::{canonicalize_expr, Output, Recursive};
 use crate::pattern::{canonicalize_def_header_pattern, BindingsFromPattern, Pattern};
@@ -42,7 +43,7 @@ use roc_parse::ident::Accessor;
 use roc_parse::pattern::PatternType;
 use roc_problem::can::ShadowKind;
 use roc_problem::can::{CycleEntry, Problem, RuntimeError};
-use roc_region::all::{Loc, Region};
+use roc_region::all::{Loc, Position, Region};
 use roc_types::subs::IllegalCycleMark;
 use roc_types::subs::{VarStore, Variable};
 use roc_types::types::AliasCommon;
@@ -865,8 +866,14 @@ fn canonicalize_opaque<'a>(
                         impl_map.insert(*sym, Loc::at_zero(MemberImpl::Error));
                     }
 
-                    env.problem(Problem::DoesNotImplementAbility {
+                    // The missing members are reported once solving has figured out their
+                    // signatures, so that we can say what type each one should have.
+                    output.incomplete_impls.push(IncompleteImpl {
                         region,
+                        opaque: name.value,
+                        opaque_vars: (alias.type_variables.iter())
+                            .map(|var| var.value.name.clone())
+                            .collect(),
                         ability,
                         not_implemented,
                     });
@@ -1116,6 +1123,7 @@ fn canonicalize_value_defs<'a>(
             }
             PendingValue::InvalidIngestedFile => { /* skip */ }
             PendingValue::ImportNameConflict => { /* skip */ }
+            PendingValue::Malformed => { /* skip */ }
         }
     }
 
@@ -1644,6 +1652,27 @@ pub(crate) fn sort_can_defs_new(
     // will get ordering [ y, x ]
     let mut declarations = Declarations::with_capacity(defs.len());
 
+    // the defs that come before each expect in the source, for `roc test --filter`
+    let mut named_def_starts: Vec<(Position, Symbol)> = defs
+        .iter()
+        .filter_map(|def| match def.loc_pattern.value {
+            Pattern::Identifier(symbol)
+            | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => {
+                Some((def.loc_pattern.region.start(), symbol))
+            }
+            _ => None,
+        })
+        .collect();
+    named_def_starts.sort_by_key(|(start, _)| *start);
+
+    let preceding_def = |region: Region| {
+        named_def_starts
+            .iter()
+            .take_while(|(start, _)| *start < region.start())
+            .last()
+            .map(|(_, symbol)| *symbol)
+    };
+
     // because of the ordering of declarations, expects should come first because they are
     // independent, but can rely on all other top-level symbols in the module
     let it = expects
@@ -1656,7 +1685,14 @@ pub(crate) fn sort_can_defs_new(
         // an `expect` does not have a user-defined name, but we'll need a name to call the expectation
         let name = scope.gen_unique_symbol();
 
-        declarations.push_expect(preceding_comment, name, Loc::at(region, condition));
+        let def_name = preceding_def(preceding_comment);
+
+        declarations.push_expect(
+            preceding_comment,
+            name,
+            def_name,
+            Loc::at(region, condition),
+        );
     }
 
     let it = expects_fx
@@ -1669,7 +1705,14 @@ pub(crate) fn sort_can_defs_new(
         // an `expect` does not have a user-defined name, but we'll need a name to call the expectation
         let name = scope.gen_unique_symbol();
 
-        declarations.push_expect_fx(preceding_comment, name, Loc::at(region, condition));
+        let def_name = preceding_def(preceding_comment);
+
+        declarations.push_expect_fx(
+            preceding_comment,
+            name,
+            def_name,
+            Loc::at(region, condition),
+        );
     }
 
     for (symbol, alias) in aliases.into_iter() {
@@ -2899,6 +2942,8 @@ enum PendingValue<'a> {
     SignatureDefMismatch,
     InvalidIngestedFile,
     ImportNameConflict,
+    /// The parser already reported why this def is malformed
+    Malformed,
 }
 
 struct PendingExpectOrDbg<'a> {
@@ -3152,6 +3197,7 @@ fn to_pending_value_def<'a>(
             PendingValue::Def(PendingValueDef::IngestedFile(loc_pattern, ingested_file.annotation.map(|ann| ann.annotation), ingested_file.path))
         }
         Stmt(_) => internal_error!("a Stmt was not desugared correctly, should have been converted to a Body(...) in desguar"),
+        Malformed(_) => PendingValue::Malformed,
     }
 }
 
@@ -9,8 +9,8 @@ use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::ModuleName;
 use roc_parse::ast::Expr::{self, *};
 use roc_parse::ast::{
-    AssignedField, Collection, ModuleImportParams, Pattern, RecordBuilderField, StrLiteral,
-    StrSegment, ValueDef, WhenBranch,
+    AssignedField, Collection, ExtractSpaces, ModuleImportParams, Pattern, RecordBuilderField,
+    StrLiteral, StrSegment, ValueDef, WhenBranch,
 };
 use roc_region::all::{LineInfo, Loc, Region};
 
@@ -68,6 +68,32 @@ fn new_op_call_expr<'a>(
     Loc { region, value }
 }
 
+/// `f x y = body` is sugar for `f = \x, y -> body`
+fn desugar_function_def<'a>(
+    arena: &'a Bump,
+    loc_pattern: &'a Loc<Pattern<'a>>,
+    loc_body: &'a Loc<Expr<'a>>,
+) -> (&'a Loc<Pattern<'a>>, &'a Loc<Expr<'a>>) {
+    match loc_pattern.value.extract_spaces().item {
+        Pattern::Apply(
+            loc_name @ Loc {
+                value: Pattern::Identifier { .. },
+                ..
+            },
+            loc_args,
+        ) => {
+            let region = match loc_args.first() {
+                Some(loc_arg) => Region::span_across(&loc_arg.region, &loc_body.region),
+                None => loc_body.region,
+            };
+            let loc_closure = arena.alloc(Loc::at(region, Closure(loc_args, loc_body)));
+
+            (loc_name, loc_closure)
+        }
+        _ => (loc_pattern, loc_body),
+    }
+}
+
 fn desugar_value_def<'a>(
     arena: &'a Bump,
     def: &'a ValueDef<'a>,
@@ -78,10 +104,15 @@ fn desugar_value_def<'a>(
     use ValueDef::*;
 
     match def {
-        Body(loc_pattern, loc_expr) => Body(
-            desugar_loc_pattern(arena, loc_pattern, src, line_info, module_path),
-            desugar_expr(arena, loc_expr, src, line_info, module_path),
-        ),
+        Body(loc_pattern, loc_expr) => {
+            let (loc_pattern, loc_expr) = desugar_function_def(
+                arena,
+                desugar_loc_pattern(arena, loc_pattern, src, line_info, module_path),
+                desugar_expr(arena, loc_expr, src, line_info, module_path),
+            );
+
+            Body(loc_pattern, loc_expr)
+        }
         ann @ Annotation(_, _) => *ann,
         AnnotatedBody {
             ann_pattern,
@@ -89,13 +120,21 @@ fn desugar_value_def<'a>(
             comment,
             body_pattern,
             body_expr,
-        } => AnnotatedBody {
-            ann_pattern,
-            ann_type,
-            comment: *comment,
-            body_pattern: desugar_loc_pattern(arena, body_pattern, src, line_info, module_path),
-            body_expr: desugar_expr(arena, body_expr, src, line_info, module_path),
-        },
+        } => {
+            let (body_pattern, body_expr) = desugar_function_def(
+                arena,
+                desugar_loc_pattern(arena, body_pattern, src, line_info, module_path),
+                desugar_expr(arena, body_expr, src, line_info, module_path),
+            );
+
+            AnnotatedBody {
+                ann_pattern,
+                ann_type,
+                comment: *comment,
+                body_pattern,
+                body_expr,
+            }
+        }
 
         Dbg {
             condition,
@@ -151,7 +190,7 @@ fn desugar_value_def<'a>(
                 exposed: *exposed,
             })
         }
-        IngestedFileImport(_) => *def,
+        IngestedFileImport(_) | Malformed(_) => *def,
 
         Stmt(stmt_expr) => {
             // desugar into a Body({}, stmt_expr)
@@ -269,7 +308,7 @@ pub fn desugar_value_def_suffixed<'a>(arena: &'a Bump, value_def: ValueDef<'a>)
 
         // TODO support desugaring of Dbg, Expect, and ExpectFx
         Dbg { .. } | Expect { .. } | ExpectFx { .. } => value_def,
-        ModuleImport { .. } | IngestedFileImport(_) => value_def,
+        ModuleImport { .. } | IngestedFileImport(_) | Malformed(_) => value_def,
 
         Stmt(..) => {
             internal_error!(
@@ -307,7 +346,18 @@ pub fn desugar_expr<'a>(
         | Crash => loc_expr,
 
         Str(str_literal) => match str_literal {
-            StrLiteral::PlainLine(_) => loc_expr,
+            StrLiteral::PlainLine(text) => {
+                // A `"""` string with a single line of text parses as a PlainLine too
+                match block_str_starts_with_newline(src, loc_expr.region) {
+                    Some(true) if text.contains(|ch| ch != ' ') => {
+                        let region = loc_expr.region;
+                        let value = Str(StrLiteral::PlainLine(text.trim_start_matches(' ')));
+
+                        arena.alloc(Loc { region, value })
+                    }
+                    _ => loc_expr,
+                }
+            }
             StrLiteral::Line(segments) => {
                 let region = loc_expr.region;
                 let value = Str(StrLiteral::Line(desugar_str_segments(
@@ -328,7 +378,13 @@ pub fn desugar_expr<'a>(
                     }),
                     arena,
                 );
-                let value = Str(StrLiteral::Block(new_lines.into_bump_slice()));
+                let first_line_is_indented =
+                    block_str_starts_with_newline(src, region).unwrap_or(true);
+                let value = Str(StrLiteral::Block(strip_common_indent(
+                    arena,
+                    new_lines.into_bump_slice(),
+                    first_line_is_indented,
+                )));
 
                 arena.alloc(Loc { region, value })
             }
@@ -782,6 +838,105 @@ pub fn desugar_expr<'a>(
     }
 }
 
+/// If the string literal at this region is a `"""` block, whether its text starts
+/// on the line after the opening `"""` (and so is indented like the lines after it).
+fn block_str_starts_with_newline(src: &str, region: Region) -> Option<bool> {
+    let rest = src.get(region.start().offset as usize..)?;
+
+    rest.strip_prefix("\"\"\"")
+        .map(|text| text.starts_with('\n'))
+}
+
+/// Removes the leading spaces that every non-blank line of a `"""` block has in
+/// common, so the block can be indented along with the code around it.
+///
+/// The parser already strips the indentation of the opening `"""` itself; this takes
+/// care of any indentation beyond that. Interpolations and escapes count as text, so
+/// a line starting with one of them has no indentation to strip.
+fn strip_common_indent<'a>(
+    arena: &'a Bump,
+    lines: &'a [&'a [StrSegment<'a>]],
+    first_line_is_indented: bool,
+) -> &'a [&'a [StrSegment<'a>]] {
+    let mut common_indent: Option<usize> = None;
+    let mut at_line_start = first_line_is_indented;
+    let mut indent = 0;
+
+    for segment in lines.iter().flat_map(|segments| segments.iter()) {
+        let bytes = match segment {
+            StrSegment::Plaintext(text) => text.as_bytes(),
+            // Interpolations and escapes are text, not indentation
+            _ => b"$",
+        };
+
+        for byte in bytes {
+            match byte {
+                b'\n' => {
+                    at_line_start = true;
+                    indent = 0;
+                }
+                b' ' if at_line_start => indent += 1,
+                _ if at_line_start => {
+                    // Blank lines never get here, so they don't count
+                    common_indent = Some(common_indent.map_or(indent, |c| c.min(indent)));
+                    at_line_start = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let common_indent = match common_indent {
+        Some(0) | None => return lines,
+        Some(common_indent) => common_indent,
+    };
+
+    let mut to_strip = if first_line_is_indented {
+        common_indent
+    } else {
+        0
+    };
+
+    let new_lines = lines.iter().map(|segments| {
+        let mut new_segments = Vec::with_capacity_in(segments.len(), arena);
+
+        for segment in segments.iter() {
+            let text = match segment {
+                StrSegment::Plaintext(text) => text,
+                _ => {
+                    to_strip = 0;
+                    new_segments.push(*segment);
+                    continue;
+                }
+            };
+
+            let mut stripped = bumpalo::collections::String::with_capacity_in(text.len(), arena);
+
+            for ch in text.chars() {
+                match ch {
+                    ' ' if to_strip > 0 => to_strip -= 1,
+                    '\n' => {
+                        to_strip = common_indent;
+                        stripped.push(ch);
+                    }
+                    _ => {
+                        to_strip = 0;
+                        stripped.push(ch);
+                    }
+                }
+            }
+
+            if !stripped.is_empty() {
+                new_segments.push(StrSegment::Plaintext(stripped.into_bump_str()));
+            }
+        }
+
+        new_segments.into_bump_slice()
+    });
+
+    Vec::from_iter_in(new_lines, arena).into_bump_slice()
+}
+
 fn desugar_str_segments<'a>(
     arena: &'a Bump,
     segments: &'a [StrSegment<'a>],
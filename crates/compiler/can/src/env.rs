@@ -6,7 +6,7 @@ use bumpalo::Bump;
 use roc_collections::{MutMap, VecSet};
 use roc_module::ident::{Ident, ModuleName};
 use roc_module::symbol::{IdentIdsByModule, ModuleId, PQModuleName, PackageModuleIds, Symbol};
-use roc_problem::can::{Problem, RuntimeError};
+use roc_problem::can::{ExposedName, Problem, RuntimeError};
 use roc_region::all::{Loc, Region};
 
 /// The canonicalization environment for a particular module.
@@ -84,19 +84,26 @@ impl<'a> Env<'a> {
 
         match scope.modules.get_id(&module_name) {
             Some(module_id) => self.qualified_lookup_help(scope, module_id, ident, region),
-            None => Err(RuntimeError::ModuleNotImported {
-                module_name: module_name.clone(),
-                imported_modules: scope
-                    .modules
-                    .available_names()
-                    .map(|string| string.as_ref().into())
-                    .collect(),
-                region,
-                module_exists: self
+            None => {
+                let module_exists = self
                     .qualified_module_ids
-                    .get_id(&PQModuleName::Unqualified(module_name))
-                    .is_some(),
-            }),
+                    .get_id(&PQModuleName::Unqualified(module_name.clone()))
+                    .is_some();
+
+                Err(RuntimeError::ModuleNotImported {
+                    import_name: module_name.as_str().into(),
+                    module_name,
+                    imported_modules: scope
+                        .modules
+                        .available_names()
+                        .map(|string| string.as_ref().into())
+                        .collect(),
+                    region,
+                    module_exists,
+                    ident: Ident::from(ident),
+                    exposed_names: self.exposed_names(scope),
+                })
+            }
         }
     }
 
@@ -108,7 +115,7 @@ impl<'a> Env<'a> {
         region: Region,
     ) -> Result<Symbol, RuntimeError> {
         if !scope.modules.has_id(module_id) {
-            Err(self.module_exists_but_not_imported(scope, module_id, region))
+            Err(self.module_exists_but_not_imported(scope, module_id, ident, region))
         } else {
             self.qualified_lookup_help(scope, module_id, ident, region)
         }
@@ -182,7 +189,7 @@ impl<'a> Env<'a> {
                         exposed_values: exposed_ids.exposed_values(),
                     }),
                 },
-                _ => Err(self.module_exists_but_not_imported(scope, module_id, region)),
+                _ => Err(self.module_exists_but_not_imported(scope, module_id, ident, region)),
             }
         }
     }
@@ -191,6 +198,7 @@ impl<'a> Env<'a> {
         &self,
         scope: &Scope,
         module_id: ModuleId,
+        ident: &str,
         region: Region,
     ) -> RuntimeError {
         RuntimeError::ModuleNotImported {
@@ -207,6 +215,53 @@ impl<'a> Env<'a> {
                 .collect(),
             region,
             module_exists: true,
+            import_name: self.import_name(module_id),
+            ident: Ident::from(ident),
+            exposed_names: self.exposed_names(scope),
+        }
+    }
+
+    /// Every value and type exposed by the modules this one depends on (including all the
+    /// builtins), so that a reference to a module that isn't imported can suggest the
+    /// closest name that does exist.
+    fn exposed_names(&self, scope: &Scope) -> Vec<ExposedName> {
+        let mut exposed_names = Vec::new();
+
+        for module_id in self.dep_idents.keys() {
+            if *module_id == self.home {
+                continue;
+            }
+
+            let Some(module_name) = self.qualified_module_ids.get_name(*module_id) else {
+                continue;
+            };
+            let import_name = self.import_name(*module_id);
+            let imported = scope.modules.has_id(*module_id);
+
+            for (_, ident) in self.dep_idents.get(module_id).unwrap().ident_strs() {
+                exposed_names.push(ExposedName {
+                    module_name: module_name.as_inner().clone(),
+                    import_name: import_name.clone(),
+                    ident: Ident::from(ident),
+                    imported,
+                });
+            }
+        }
+
+        exposed_names
+    }
+
+    /// How the given module would be written in an `import`, e.g. `pf.Stdout`
+    fn import_name(&self, module_id: ModuleId) -> Box<str> {
+        match self
+            .qualified_module_ids
+            .get_name(module_id)
+            .expect("Module ID known, but not in the module IDs somehow")
+        {
+            PQModuleName::Qualified(shorthand, name) => {
+                format!("{shorthand}.{}", name.as_str()).into()
+            }
+            PQModuleName::Unqualified(name) => name.as_str().into(),
         }
     }
 
@@ -4,7 +4,8 @@ use roc_collections::all::HumanIndex;
 use roc_collections::VecMap;
 use roc_error_macros::internal_error;
 use roc_exhaustive::{
-    is_useful, Ctor, CtorName, Error, Guard, ListArity, Literal, Pattern, RenderAs, TagId, Union,
+    is_useful, CoveredBy, Ctor, CtorName, Error, Guard, ListArity, Literal, Pattern, RenderAs,
+    TagId, Union,
 };
 use roc_module::ident::{Lowercase, TagIdIntType, TagName};
 use roc_module::symbol::Symbol;
@@ -569,50 +570,97 @@ fn to_nonredundant_rows(
         overall_region,
     } = rows;
     let mut checked_rows = Vec::with_capacity(rows.len());
+    // The region, index, and guard of each row in `checked_rows`.
+    let mut checked_info: Vec<(Region, HumanIndex, Guard)> = Vec::with_capacity(rows.len());
 
     let mut redundancies = vec![];
     let mut errors = vec![];
 
-    for (
-        row_number,
-        SketchedRow {
-            patterns,
-            guard,
-            region,
-            redundant_mark,
-        },
-    ) in rows.into_iter().enumerate()
-    {
-        let next_row: Vec<Pattern> = patterns
-            .into_iter()
-            .map(|pattern| pattern.reify(subs, real_var))
-            .collect::<Result<_, _>>()?;
-
-        let redundant_err = if !is_inhabited_row(&next_row) {
-            Some(Error::Unmatchable {
-                overall_region,
-                branch_region: region,
-                index: HumanIndex::zero_based(row_number),
-            })
-        } else if !(matches!(guard, Guard::HasGuard)
-            || is_useful(checked_rows.clone(), next_row.clone()))
+    // The rows of each branch, numbered. Every pattern of a branch shares its redundant mark.
+    let mut branches: Vec<Vec<(usize, SketchedRow)>> = Vec::new();
+
+    for (row_number, row) in rows.into_iter().enumerate() {
+        match branches.last_mut() {
+            Some(branch) if branch[0].1.redundant_mark == row.redundant_mark => {
+                branch.push((row_number, row))
+            }
+            _ => branches.push(vec![(row_number, row)]),
+        }
+    }
+
+    for branch in branches {
+        let redundant_mark = branch[0].1.redundant_mark;
+
+        // The rows of this branch if it has a guard, along with what covers each of them.
+        let mut guarded_rows = Vec::new();
+
+        for (
+            row_number,
+            SketchedRow {
+                patterns,
+                guard,
+                region,
+                redundant_mark: _,
+            },
+        ) in branch
         {
-            Some(Error::Redundant {
-                overall_region,
-                branch_region: region,
-                index: HumanIndex::zero_based(row_number),
-            })
-        } else {
-            None
-        };
+            let next_row: Vec<Pattern> = patterns
+                .into_iter()
+                .map(|pattern| pattern.reify(subs, real_var))
+                .collect::<Result<_, _>>()?;
+
+            let index = HumanIndex::zero_based(row_number);
 
-        match redundant_err {
-            None => {
-                checked_rows.push(next_row);
+            if !is_inhabited_row(&next_row) {
+                redundancies.push(redundant_mark);
+                errors.push(Error::Unmatchable {
+                    overall_region,
+                    branch_region: region,
+                    index,
+                });
+
+                continue;
             }
-            Some(err) => {
+
+            let covered_by = find_covering(&checked_rows, &checked_info, &next_row, guard);
+
+            match (guard, covered_by) {
+                (Guard::HasGuard, _) => guarded_rows.push((next_row, region, index, covered_by)),
+                (Guard::NoGuard, None) => {
+                    checked_rows.push(next_row);
+                    checked_info.push((region, index, guard));
+                }
+                (Guard::NoGuard, Some(covered_by)) => {
+                    redundancies.push(redundant_mark);
+                    errors.push(Error::Redundant {
+                        overall_region,
+                        branch_region: region,
+                        index,
+                        covered_by,
+                    });
+                }
+            }
+        }
+
+        // A branch with a guard is redundant only if all of its patterns are. If any of them can
+        // still reach the guard, the branch has to stay, so none of them are reported.
+        if guarded_rows
+            .iter()
+            .all(|(_, _, _, covered_by)| covered_by.is_some())
+        {
+            for (_, region, index, covered_by) in guarded_rows {
                 redundancies.push(redundant_mark);
-                errors.push(err);
+                errors.push(Error::Redundant {
+                    overall_region,
+                    branch_region: region,
+                    index,
+                    covered_by: covered_by.unwrap(),
+                });
+            }
+        } else {
+            for (row, region, index, _) in guarded_rows {
+                checked_rows.push(row);
+                checked_info.push((region, index, Guard::HasGuard));
             }
         }
     }
@@ -624,6 +672,55 @@ fn to_nonredundant_rows(
     })
 }
 
+/// Finds what, if anything, makes `row` redundant given the rows before it.
+///
+/// Earlier rows with guards might not match, so they can never make a later row redundant. A row
+/// with a guard is only redundant if the earlier rows without guards already match everything it
+/// does, in which case its guard is never checked.
+fn find_covering(
+    checked_rows: &[Vec<Pattern>],
+    checked_info: &[(Region, HumanIndex, Guard)],
+    row: &[Pattern],
+    guard: Guard,
+) -> Option<CoveredBy> {
+    let candidates: Vec<(&Vec<Pattern>, Region, HumanIndex)> = checked_rows
+        .iter()
+        .zip(checked_info)
+        .filter(|(_, (_, _, earlier_guard))| {
+            guard == Guard::NoGuard || *earlier_guard == Guard::NoGuard
+        })
+        .map(|(earlier, (region, index, _))| (earlier, *region, *index))
+        .collect();
+
+    let earlier_rows = match guard {
+        Guard::NoGuard => checked_rows.to_vec(),
+        Guard::HasGuard => candidates
+            .iter()
+            .map(|(row, _, _)| (*row).clone())
+            .collect(),
+    };
+
+    if is_useful(earlier_rows, row.to_vec()) {
+        return None;
+    }
+
+    let single = candidates
+        .iter()
+        .find(|(earlier, _, _)| !is_useful(vec![(*earlier).clone()], row.to_vec()));
+
+    Some(match (single, guard) {
+        (Some((_, region, index)), Guard::NoGuard) => CoveredBy::Pattern {
+            region: *region,
+            index: *index,
+        },
+        (Some((_, region, index)), Guard::HasGuard) => CoveredBy::UnguardedPattern {
+            region: *region,
+            index: *index,
+        },
+        (None, _) => CoveredBy::Patterns,
+    })
+}
+
 fn is_inhabited_row(patterns: &[Pattern]) -> bool {
     patterns.iter().any(is_inhabited_pattern)
 }
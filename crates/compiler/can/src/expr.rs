@@ -14,7 +14,7 @@ use crate::traverse::{walk_expr, Visitor};
 use roc_collections::soa::Index;
 use roc_collections::{SendMap, VecMap, VecSet};
 use roc_error_macros::internal_error;
-use roc_module::called_via::CalledVia;
+use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::{ForeignSymbol, Lowercase, TagName};
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::Symbol;
@@ -34,6 +34,21 @@ use std::{char, u32};
 /// Derives that an opaque type has claimed, to checked and recorded after solving.
 pub type PendingDerives = VecMap<Symbol, (Type, Vec<Loc<Symbol>>)>;
 
+/// Abilities that an opaque type claimed to implement, but left members out of.
+/// These are reported after solving, once the signatures of the missing members are known.
+pub type IncompleteImpls = Vec<IncompleteImpl>;
+
+#[derive(Clone, Debug)]
+pub struct IncompleteImpl {
+    /// The region of the `Ability {...}` in the `implements` clause.
+    pub region: Region,
+    pub opaque: Symbol,
+    /// The names of the opaque type's variables, to show it applied in the member signatures.
+    pub opaque_vars: Vec<Lowercase>,
+    pub ability: Symbol,
+    pub not_implemented: Vec<Symbol>,
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct Output {
     pub references: References,
@@ -42,6 +57,7 @@ pub struct Output {
     pub aliases: VecMap<Symbol, Alias>,
     pub non_closures: VecSet<Symbol>,
     pub pending_derives: PendingDerives,
+    pub incomplete_impls: IncompleteImpls,
 }
 
 impl Output {
@@ -65,6 +81,8 @@ impl Output {
                 "Derives overwritten from nested scope - something is very wrong"
             );
         }
+
+        self.incomplete_impls.extend(other.incomplete_impls);
     }
 }
 
@@ -2664,6 +2682,10 @@ pub struct Declarations {
 
     pub host_exposed_annotations: VecMap<usize, (Variable, crate::def::Annotation)>,
 
+    /// For top-level expects, the def they follow in the source, which is how `roc test`
+    /// refers to them (an expect has no name of its own).
+    pub expect_def_names: VecMap<usize, Symbol>,
+
     pub function_bodies: Vec<Loc<FunctionDef>>,
     pub expressions: Vec<Loc<Expr>>,
     pub destructs: Vec<DestructureDef>,
@@ -2686,6 +2708,7 @@ impl Declarations {
             symbols: Vec::with_capacity(capacity),
             annotations: Vec::with_capacity(capacity),
             host_exposed_annotations: VecMap::new(),
+            expect_def_names: VecMap::default(),
             function_bodies: Vec::with_capacity(capacity),
             expressions: Vec::with_capacity(capacity),
             specializes: VecMap::default(), // number of specializations is probably low
@@ -2804,10 +2827,15 @@ impl Declarations {
         &mut self,
         preceding_comment: Region,
         name: Symbol,
+        def_name: Option<Symbol>,
         loc_expr: Loc<Expr>,
     ) -> usize {
         let index = self.declarations.len();
 
+        if let Some(def_name) = def_name {
+            self.expect_def_names.insert(index, def_name);
+        }
+
         self.declarations.push(DeclarationTag::Expectation);
         self.variables.push(Variable::BOOL);
         self.symbols.push(Loc::at(preceding_comment, name));
@@ -2822,10 +2850,15 @@ impl Declarations {
         &mut self,
         preceding_comment: Region,
         name: Symbol,
+        def_name: Option<Symbol>,
         loc_expr: Loc<Expr>,
     ) -> usize {
         let index = self.declarations.len();
 
+        if let Some(def_name) = def_name {
+            self.expect_def_names.insert(index, def_name);
+        }
+
         self.declarations.push(DeclarationTag::ExpectationFx);
         self.variables.push(Variable::BOOL);
         self.symbols.push(Loc::at(preceding_comment, name));
@@ -3003,6 +3036,7 @@ impl Declarations {
         let mut collector = ExpectCollector {
             expects: VecMap::default(),
             dbgs: VecMap::default(),
+            comparisons: VecMap::default(),
         };
 
         let var = Variable::EMPTY_RECORD;
@@ -3332,6 +3366,8 @@ fn toplevel_expect_to_inline_expect_help(mut loc_expr: Loc<Expr>, has_effects: b
 pub struct ExpectCollector {
     pub expects: VecMap<Region, Vec<ExpectLookup>>,
     pub dbgs: VecMap<Symbol, DbgLookup>,
+    /// For expects whose condition ends in `a == b`, the two variables being compared
+    pub comparisons: VecMap<Region, (Symbol, Symbol)>,
 }
 
 impl crate::traverse::Visitor for ExpectCollector {
@@ -3349,6 +3385,10 @@ impl crate::traverse::Visitor for ExpectCollector {
             } => {
                 self.expects
                     .insert(loc_condition.region, lookups_in_cond.to_vec());
+
+                if let Some(compared) = compared_variables(&loc_condition.value) {
+                    self.comparisons.insert(loc_condition.region, compared);
+                }
             }
             Expr::Dbg {
                 loc_message,
@@ -3371,3 +3411,18 @@ impl crate::traverse::Visitor for ExpectCollector {
         walk_expr(self, expr, var)
     }
 }
+
+/// The two variables compared by `a == b`, if that is what `expr` evaluates to last
+fn compared_variables(expr: &Expr) -> Option<(Symbol, Symbol)> {
+    match expr {
+        Expr::LetNonRec(_, body) | Expr::LetRec(_, body, _) => compared_variables(&body.value),
+        Expr::Call(_, args, CalledVia::BinOp(BinOp::Equals)) => match args.as_slice() {
+            [(_, left), (_, right)] => match (&left.value, &right.value) {
+                (Expr::Var(left, _), Expr::Var(right, _)) => Some((*left, *right)),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
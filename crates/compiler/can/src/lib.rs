@@ -26,6 +26,7 @@ pub mod procedure;
 pub mod scope;
 pub mod string;
 pub mod suffixed;
+pub mod tail_calls;
 pub mod traverse;
 
 pub use derive::DERIVED_REGION;
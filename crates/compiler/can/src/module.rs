@@ -6,7 +6,8 @@ use crate::def::{canonicalize_defs, report_unused_imports, Def};
 use crate::effect_module::HostedGeneratedFunctions;
 use crate::env::Env;
 use crate::expr::{
-    ClosureData, DbgLookup, Declarations, ExpectLookup, Expr, Output, PendingDerives,
+    ClosureData, DbgLookup, Declarations, ExpectLookup, Expr, IncompleteImpls, Output,
+    PendingDerives,
 };
 use crate::pattern::{BindingsFromPattern, Pattern};
 use crate::procedure::References;
@@ -135,6 +136,7 @@ pub struct Module {
     pub rigid_variables: RigidVariables,
     pub abilities_store: PendingAbilitiesStore,
     pub loc_expects: VecMap<Region, Vec<ExpectLookup>>,
+    pub loc_expect_comparisons: VecMap<Region, (Symbol, Symbol)>,
     pub loc_dbgs: VecMap<Symbol, DbgLookup>,
 }
 
@@ -156,8 +158,10 @@ pub struct ModuleOutput {
     pub referenced_values: VecSet<Symbol>,
     pub symbols_from_requires: Vec<(Loc<Symbol>, Loc<Type>)>,
     pub pending_derives: PendingDerives,
+    pub incomplete_impls: IncompleteImpls,
     pub scope: Scope,
     pub loc_expects: VecMap<Region, Vec<ExpectLookup>>,
+    pub loc_expect_comparisons: VecMap<Region, (Symbol, Symbol)>,
     pub loc_dbgs: VecMap<Symbol, DbgLookup>,
 }
 
@@ -253,6 +257,22 @@ impl GeneratedInfo {
     }
 }
 
+/// Whether a hosted signature returns the module's generated effect type, which is the only
+/// kind of value the host can provide.
+fn returns_effect(typ: &Type, effect_symbol: Symbol) -> bool {
+    let ret = match typ.shallow_structural_dealias() {
+        Type::Function(_, _, ret) => ret.shallow_structural_dealias(),
+        other => other,
+    };
+
+    match ret {
+        Type::Alias { symbol, .. } => *symbol == effect_symbol,
+        // This has already been reported.
+        Type::Error => true,
+        _ => false,
+    }
+}
+
 fn has_no_implementation(expr: &Expr) -> bool {
     match expr {
         Expr::RuntimeError(RuntimeError::NoImplementationNamed { .. }) => true,
@@ -277,6 +297,8 @@ pub fn canonicalize_module_defs<'a>(
     header_type: &roc_parse::header::HeaderType,
     home: ModuleId,
     module_path: &'a str,
+    // The path `dbg` reports locations in, which reproducible builds make machine-independent
+    embedded_module_path: &'a str,
     src: &'a str,
     qualified_module_ids: &'a PackageModuleIds<'a>,
     exposed_ident_ids: IdentIds,
@@ -331,7 +353,14 @@ pub fn canonicalize_module_defs<'a>(
     // operators, and then again on *their* nested operators, ultimately applying the
     // rules multiple times unnecessarily.
 
-    crate::desugar::desugar_defs_node_values(arena, loc_defs, src, &mut None, module_path, true);
+    crate::desugar::desugar_defs_node_values(
+        arena,
+        loc_defs,
+        src,
+        &mut None,
+        embedded_module_path,
+        true,
+    );
 
     let mut rigid_variables = RigidVariables::default();
 
@@ -392,6 +421,7 @@ pub fn canonicalize_module_defs<'a>(
     );
 
     let pending_derives = output.pending_derives;
+    let incomplete_impls = output.incomplete_impls;
 
     // See if any of the new idents we defined went unused.
     // If any were unused and also not exposed, report it.
@@ -555,6 +585,16 @@ pub fn canonicalize_module_defs<'a>(
 
                             let def_annotation = declarations.annotations[index].clone().unwrap();
 
+                            if !returns_effect(&def_annotation.signature, effect_symbol) {
+                                env.problem(Problem::HostedSignatureNotEffect {
+                                    symbol: *symbol,
+                                    effect: effect_symbol,
+                                    region: def_annotation.region,
+                                });
+
+                                continue;
+                            }
+
                             let annotation = crate::annotation::Annotation {
                                 typ: def_annotation.signature,
                                 introduced_variables: def_annotation.introduced_variables,
@@ -613,6 +653,16 @@ pub fn canonicalize_module_defs<'a>(
 
                             let def_annotation = declarations.annotations[index].clone().unwrap();
 
+                            if !returns_effect(&def_annotation.signature, effect_symbol) {
+                                env.problem(Problem::HostedSignatureNotEffect {
+                                    symbol: *symbol,
+                                    effect: effect_symbol,
+                                    region: def_annotation.region,
+                                });
+
+                                continue;
+                            }
+
                             let annotation = crate::annotation::Annotation {
                                 typ: def_annotation.signature,
                                 introduced_variables: def_annotation.introduced_variables,
@@ -691,8 +741,24 @@ pub fn canonicalize_module_defs<'a>(
     // exposed_symbols and added to exposed_vars_by_symbol. If any were
     // not, that means they were declared as exposed but there was
     // no actual declaration with that name!
+    let provided: Vec<&str> = match header_type {
+        HeaderType::Platform { provides, .. } => provides
+            .iter()
+            .map(|(loc_name, _)| loc_name.value.as_str())
+            .collect(),
+        _ => Vec::new(),
+    };
+
     for symbol in exposed_but_not_defined {
-        env.problem(Problem::ExposedButNotDefined(symbol));
+        let name = scope.locals.ident_ids.get_name(symbol.ident_id());
+
+        // The host calls everything a platform provides, so report those separately: a missing
+        // one would otherwise only show up later, as a link error in the host.
+        if name.map_or(false, |name| provided.contains(&name)) {
+            env.problem(Problem::ProvidedButNotDefined(symbol));
+        } else {
+            env.problem(Problem::ExposedButNotDefined(symbol));
+        }
 
         // In case this exposed value is referenced by other modules,
         // create a decl for it whose implementation is a runtime error.
@@ -818,7 +884,9 @@ pub fn canonicalize_module_defs<'a>(
         problems: env.problems,
         symbols_from_requires,
         pending_derives,
+        incomplete_impls,
         loc_expects: collected.expects,
+        loc_expect_comparisons: collected.comparisons,
         loc_dbgs: collected.dbgs,
         exposed_symbols,
     }
@@ -0,0 +1,275 @@
+//! Finds the self-recursive calls in a module, and whether each one is a tail call.
+//!
+//! Roc guarantees that a function calling itself in tail position runs in constant stack space,
+//! but it's easy to write `n * fact (n - 1)` and think that's a tail call. `roc check --tailcalls`
+//! uses this to show which recursive calls will grow the stack, and why.
+//!
+//! Only direct self-recursion is optimized, so calls between mutually recursive functions are
+//! not reported.
+use std::fmt;
+
+use roc_module::symbol::Symbol;
+use roc_region::all::{Loc, Region};
+
+use crate::expr::{DeclarationTag, Declarations, Expr, Field};
+
+use CallPosition::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecursiveCall {
+    /// The function that calls itself
+    pub function: Symbol,
+    pub region: Region,
+    pub position: CallPosition,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallPosition {
+    Tail,
+    NotTail(NotTailReason),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotTailReason {
+    /// e.g. `n * fact (n - 1)`
+    Argument,
+    /// An `if` or `when` condition, a `when` guard, or an `expect` condition
+    Condition,
+    /// e.g. `x = f (n - 1)` followed by something that uses `x`
+    Definition,
+    /// e.g. `Ok (f (n - 1))` or `[f (n - 1)]`
+    InsideValue,
+    /// e.g. `(f (n - 1)).count`
+    Access,
+    /// Inside a lambda or a function defined within the recursive function
+    NestedFunction,
+}
+
+impl fmt::Display for NotTailReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            NotTailReason::Argument => "its result is passed to another function",
+            NotTailReason::Condition => "its result is used as a condition",
+            NotTailReason::Definition => "its result is given a name before returning",
+            NotTailReason::InsideValue => "its result is put inside another value",
+            NotTailReason::Access => "its result is accessed before returning",
+            NotTailReason::NestedFunction => "it's inside another function",
+        };
+
+        f.write_str(reason)
+    }
+}
+
+impl fmt::Display for CallPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallPosition::Tail => write!(f, "tail"),
+            CallPosition::NotTail(reason) => write!(f, "not tail ({reason})"),
+        }
+    }
+}
+
+/// The self-recursive calls in the top-level declarations, in the order they appear.
+pub fn tail_calls(decls: &Declarations) -> Vec<RecursiveCall> {
+    let mut finder = Finder::default();
+
+    for (index, tag) in decls.declarations.iter().enumerate() {
+        let loc_expr = &decls.expressions[index];
+
+        match tag {
+            DeclarationTag::Function(_)
+            | DeclarationTag::Recursive(_)
+            | DeclarationTag::TailRecursive(_) => {
+                // For functions the expression is the body
+                finder.function(decls.symbols[index].value, loc_expr);
+            }
+            DeclarationTag::Value
+            | DeclarationTag::Expectation
+            | DeclarationTag::ExpectationFx
+            | DeclarationTag::Destructure(_) => {
+                finder.expr(loc_expr, NotTail(NotTailReason::Definition));
+            }
+            DeclarationTag::MutualRecursion { .. } => {
+                // Just marks where a cycle starts; its functions are the next declarations
+            }
+        }
+    }
+
+    finder.calls.sort_by_key(|call| call.region.start());
+    finder.calls
+}
+
+/// The self-recursive calls of the functions defined in an expression.
+pub fn expr_tail_calls(loc_expr: &Loc<Expr>) -> Vec<RecursiveCall> {
+    let mut finder = Finder::default();
+
+    finder.expr(loc_expr, NotTail(NotTailReason::Definition));
+
+    finder.calls.sort_by_key(|call| call.region.start());
+    finder.calls
+}
+
+#[derive(Default)]
+struct Finder {
+    /// The function whose body we're in, and the ones it's nested inside
+    functions: Vec<Symbol>,
+    calls: Vec<RecursiveCall>,
+}
+
+impl Finder {
+    fn function(&mut self, name: Symbol, loc_body: &Loc<Expr>) {
+        self.functions.push(name);
+        self.expr(loc_body, Tail);
+        self.functions.pop();
+    }
+
+    fn expr(&mut self, loc_expr: &Loc<Expr>, position: CallPosition) {
+        self.expr_at(&loc_expr.value, loc_expr.region, position)
+    }
+
+    fn expr_at(&mut self, expr: &Expr, region: Region, position: CallPosition) {
+        use NotTailReason::*;
+
+        match expr {
+            Expr::Call(boxed, args, _) => {
+                let (_, loc_fn, _, _) = &**boxed;
+
+                if let Expr::Var(symbol, _) = loc_fn.value {
+                    self.call(symbol, region, position);
+                } else {
+                    self.expr(loc_fn, NotTail(Argument));
+                }
+
+                for (_, loc_arg) in args {
+                    self.expr(loc_arg, NotTail(Argument));
+                }
+            }
+            Expr::RunLowLevel { args, .. } | Expr::ForeignCall { args, .. } => {
+                for (_, arg) in args {
+                    self.expr_at(arg, region, NotTail(Argument));
+                }
+            }
+            Expr::When {
+                loc_cond, branches, ..
+            } => {
+                self.expr(loc_cond, NotTail(Condition));
+
+                for branch in branches {
+                    if let Some(guard) = &branch.guard {
+                        self.expr(guard, NotTail(Condition));
+                    }
+
+                    self.expr(&branch.value, position);
+                }
+            }
+            Expr::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                for (loc_cond, loc_then) in branches {
+                    self.expr(loc_cond, NotTail(Condition));
+                    self.expr(loc_then, position);
+                }
+
+                self.expr(final_else, position);
+            }
+            Expr::LetRec(defs, loc_continuation, _) => {
+                for def in defs {
+                    self.expr(&def.loc_expr, NotTail(Definition));
+                }
+
+                self.expr(loc_continuation, position);
+            }
+            Expr::LetNonRec(def, loc_continuation) => {
+                self.expr(&def.loc_expr, NotTail(Definition));
+                self.expr(loc_continuation, position);
+            }
+            Expr::Closure(closure_data) => {
+                self.function(closure_data.name, &closure_data.loc_body);
+            }
+            Expr::Expect {
+                loc_condition,
+                loc_continuation,
+                ..
+            }
+            | Expr::ExpectFx {
+                loc_condition,
+                loc_continuation,
+                ..
+            } => {
+                self.expr(loc_condition, NotTail(Condition));
+                self.expr(loc_continuation, position);
+            }
+            Expr::Dbg {
+                loc_message,
+                loc_continuation,
+                ..
+            } => {
+                self.expr(loc_message, NotTail(Argument));
+                self.expr(loc_continuation, position);
+            }
+            Expr::Crash { msg, .. } => self.expr(msg, NotTail(Argument)),
+            Expr::List { loc_elems, .. } => {
+                for loc_elem in loc_elems {
+                    self.expr(loc_elem, NotTail(InsideValue));
+                }
+            }
+            Expr::Record { fields, .. } => self.fields(fields.values()),
+            Expr::RecordUpdate { updates, .. } => self.fields(updates.values()),
+            Expr::Tuple { elems, .. } => {
+                for (_, loc_elem) in elems {
+                    self.expr(loc_elem, NotTail(InsideValue));
+                }
+            }
+            Expr::Tag { arguments, .. } => {
+                for (_, loc_arg) in arguments {
+                    self.expr(loc_arg, NotTail(InsideValue));
+                }
+            }
+            Expr::OpaqueRef { argument, .. } => self.expr(&argument.1, NotTail(InsideValue)),
+            Expr::RecordAccess { loc_expr, .. } | Expr::TupleAccess { loc_expr, .. } => {
+                self.expr(loc_expr, NotTail(Access))
+            }
+            Expr::Num(..)
+            | Expr::Int(..)
+            | Expr::Float(..)
+            | Expr::Str(..)
+            | Expr::SingleQuote(..)
+            | Expr::IngestedFile(..)
+            | Expr::Var(..)
+            | Expr::AbilityMember(..)
+            | Expr::EmptyRecord
+            | Expr::RecordAccessor(..)
+            | Expr::ZeroArgumentTag { .. }
+            | Expr::OpaqueWrapFunction(..)
+            | Expr::TypedHole(..)
+            | Expr::RuntimeError(..) => { /* terminal */ }
+        }
+    }
+
+    fn fields<'f>(&mut self, fields: impl Iterator<Item = &'f Field>) {
+        for field in fields {
+            self.expr(&field.loc_expr, NotTail(NotTailReason::InsideValue));
+        }
+    }
+
+    fn call(&mut self, symbol: Symbol, region: Region, position: CallPosition) {
+        let Some(depth) = self.functions.iter().rposition(|f| *f == symbol) else {
+            return;
+        };
+
+        // Calling a function from inside one nested in it can't reuse its stack frame
+        let position = if depth + 1 == self.functions.len() {
+            position
+        } else {
+            NotTail(NotTailReason::NestedFunction)
+        };
+
+        self.calls.push(RecursiveCall {
+            function: symbol,
+            region,
+            position,
+        });
+    }
+}
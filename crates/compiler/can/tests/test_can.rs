@@ -17,6 +17,7 @@ mod test_can {
     use core::panic;
     use roc_can::expr::Expr::{self, *};
     use roc_can::expr::{ClosureData, IntValue, Recursive};
+    use roc_can::tail_calls::{expr_tail_calls, CallPosition, NotTailReason};
     use roc_module::symbol::Symbol;
     use roc_problem::can::{CycleEntry, FloatErrorKind, IntErrorKind, Problem, RuntimeError};
     use roc_region::all::{Position, Region};
@@ -276,6 +277,40 @@ mod test_can {
         assert_can_int("-0b11", -0b11);
     }
 
+    // STRINGS
+    #[test]
+    fn block_str_strips_common_indent() {
+        let src = indoc!(
+            r#"
+                """
+                    Hello,
+
+                      World!
+                """
+            "#
+        );
+
+        assert_can_string(src, "Hello,\n\n  World!");
+    }
+
+    #[test]
+    fn block_str_single_line_strips_indent() {
+        let src = indoc!(
+            r#"
+                """
+                    Hello, World!
+                """
+            "#
+        );
+
+        assert_can_string(src, "Hello, World!");
+    }
+
+    #[test]
+    fn block_str_keeps_indent_on_opening_line() {
+        assert_can_string("\"\"\"  Hello,\n    World!\"\"\"", "  Hello,\nWorld!");
+    }
+
     // ANNOTATIONS
     #[test]
     fn correct_annotated_body() {
@@ -293,6 +328,22 @@ mod test_can {
         assert_eq!(problems, Vec::new());
     }
 
+    #[test]
+    fn correct_annotated_function_def_sugar() {
+        let src = indoc!(
+            r"
+                f : Num.Int * -> Num.Int *
+                f a = a
+
+                f
+            "
+        );
+        let arena = Bump::new();
+        let CanExprOut { problems, .. } = can_expr_with(&arena, test_home(), src);
+
+        assert_eq!(problems, Vec::new());
+    }
+
     #[test]
     fn correct_annotated_body_with_comments() {
         let src = indoc!(
@@ -959,6 +1010,27 @@ mod test_can {
         assert_eq!(p_detected, Recursive::TailRecursive);
     }
 
+    #[test]
+    fn function_def_sugar_is_a_closure() {
+        let src = indoc!(
+            r"
+                g x =
+                    when x is
+                        0 -> 0
+                        _ -> g (x - 1)
+
+                g 5
+            "
+        );
+        let arena = Bump::new();
+        let CanExprOut {
+            loc_expr, problems, ..
+        } = can_expr_with(&arena, test_home(), src);
+
+        assert_eq!(problems, Vec::new());
+        assert_eq!(get_closure(&loc_expr.value, 0), Recursive::TailRecursive);
+    }
+
     // TODO restore this test! It should report two unused defs (h and p), but only reports 1.
     // #[test]
     // fn reproduce_incorrect_unused_defs() {
@@ -1073,6 +1145,77 @@ mod test_can {
         assert_eq!(detected, Recursive::Recursive);
     }
 
+    #[test]
+    fn tail_call_positions() {
+        let src = indoc!(
+            r"
+            f = \x ->
+                    if x == 0 then
+                        f 1
+                    else
+                        when f (x - 1) is
+                            0 -> 1 + f (x - 2)
+                            _ -> f (x - 3)
+
+            f 0
+        "
+        );
+        let arena = Bump::new();
+        let CanExprOut {
+            loc_expr, problems, ..
+        } = can_expr_with(&arena, test_home(), src);
+
+        assert_eq!(problems, Vec::new());
+
+        let positions: Vec<_> = expr_tail_calls(&loc_expr)
+            .into_iter()
+            .map(|call| call.position)
+            .collect();
+
+        assert_eq!(
+            positions,
+            vec![
+                CallPosition::Tail,
+                CallPosition::NotTail(NotTailReason::Condition),
+                CallPosition::NotTail(NotTailReason::Argument),
+                CallPosition::Tail,
+            ]
+        );
+    }
+
+    #[test]
+    fn tail_call_in_nested_function() {
+        let src = indoc!(
+            r"
+            f = \x ->
+                    g = \y -> if y == 0 then f y else g (y - 1)
+
+                    g x
+
+            f 0
+        "
+        );
+        let arena = Bump::new();
+        let CanExprOut {
+            loc_expr, problems, ..
+        } = can_expr_with(&arena, test_home(), src);
+
+        assert_eq!(problems, Vec::new());
+
+        let positions: Vec<_> = expr_tail_calls(&loc_expr)
+            .into_iter()
+            .map(|call| call.position)
+            .collect();
+
+        assert_eq!(
+            positions,
+            vec![
+                CallPosition::NotTail(NotTailReason::NestedFunction),
+                CallPosition::Tail,
+            ]
+        );
+    }
+
     #[test]
     fn good_mutual_recursion() {
         let src = indoc!(
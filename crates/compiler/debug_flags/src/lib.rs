@@ -143,6 +143,10 @@ flags! {
     /// Which inlines drop functions to remove pairs of alloc/dealloc instructions of its children.
     ROC_PRINT_IR_AFTER_DROP_SPECIALIZATION
 
+    /// Writes the final mono IR to stderr in the textual format of `roc_mono::ir::text`,
+    /// which can be parsed back with `parse_mono_text` to reproduce backend bugs.
+    ROC_PRINT_MONO_TEXT
+
     /// Prints debug information during the alias analysis pass.
     ROC_DEBUG_ALIAS_ANALYSIS
 
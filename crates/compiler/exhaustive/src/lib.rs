@@ -143,6 +143,7 @@ pub enum Error {
         overall_region: Region,
         branch_region: Region,
         index: HumanIndex,
+        covered_by: CoveredBy,
     },
     Unmatchable {
         overall_region: Region,
@@ -170,6 +171,19 @@ impl Error {
     }
 }
 
+/// What makes a redundant pattern redundant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoveredBy {
+    /// A single earlier pattern matches every value this one would.
+    Pattern { region: Region, index: HumanIndex },
+    /// This pattern has a guard, but an earlier pattern without one matches every value this one
+    /// would, so the guard is never checked.
+    UnguardedPattern { region: Region, index: HumanIndex },
+    /// The earlier patterns match every value this one would between them, though no single one
+    /// of them does.
+    Patterns,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Context {
     BadArg,
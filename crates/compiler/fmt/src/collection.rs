@@ -2,7 +2,7 @@ use roc_parse::ast::{Collection, CommentOrNewline, ExtractSpaces};
 
 use crate::{
     annotation::{is_collection_multiline, Formattable, Newlines},
-    spaces::{fmt_comments_only, NewlineAt, INDENT},
+    spaces::{fmt_comments_only, split_trailing_comment, NewlineAt, TrailingComments, INDENT},
     Buf,
 };
 
@@ -43,13 +43,37 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
         buf.indent(braces_indent);
         buf.push(start);
 
+        let mut trailing_comments = TrailingComments::default();
+        let mut prev_item_start = 0;
+
         for (index, item) in items.iter().enumerate() {
             let is_first_item = index == 0;
             let item = item.extract_spaces();
             let is_only_newlines = item.before.iter().all(|s| s.is_newline());
 
+            // A comment right after the previous item's comma stays at the end of its line
+            let (trailing_comment, before) = if is_first_item {
+                (None, item.before)
+            } else {
+                split_trailing_comment(item.before)
+            };
+
+            match trailing_comment {
+                Some(comment) => trailing_comments.push(buf, comment, prev_item_start),
+                None if !is_first_item => trailing_comments.end_run(buf),
+                None => {}
+            }
+
             if item.before.is_empty() || is_only_newlines {
                 buf.ensure_ends_with_newline();
+            } else if trailing_comment.is_some() {
+                fmt_comments_only(buf, before.iter(), NewlineAt::Top, item_indent);
+
+                if item.before.ends_with(&[CommentOrNewline::Newline]) {
+                    buf.newline();
+                }
+
+                buf.newline();
             } else {
                 if is_first_item {
                     // The first item in a multiline collection always begins with exactly
@@ -85,6 +109,8 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
                 }
             }
 
+            prev_item_start = buf.len();
+
             buf.indent(item_indent);
             item.item.format(buf, item_indent);
 
@@ -99,23 +125,32 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
             }
         }
 
-        if items.final_comments().iter().any(|s| s.is_newline()) {
+        let final_comments: &[CommentOrNewline] =
+            match split_trailing_comment(items.final_comments()) {
+                (Some(comment), rest) if !items.is_empty() => {
+                    trailing_comments.push(buf, comment, prev_item_start);
+                    trailing_comments.end_run(buf);
+
+                    fmt_comments_only(buf, rest.iter(), NewlineAt::Top, item_indent);
+
+                    &[]
+                }
+                _ => {
+                    trailing_comments.end_run(buf);
+
+                    items.final_comments()
+                }
+            };
+
+        if final_comments.iter().any(|s| s.is_newline()) {
             buf.newline();
         }
 
-        if items
-            .final_comments()
-            .starts_with(&[CommentOrNewline::Newline, CommentOrNewline::Newline])
-        {
+        if final_comments.starts_with(&[CommentOrNewline::Newline, CommentOrNewline::Newline]) {
             buf.newline();
         }
 
-        fmt_comments_only(
-            buf,
-            items.final_comments().iter(),
-            NewlineAt::None,
-            item_indent,
-        );
+        fmt_comments_only(buf, final_comments.iter(), NewlineAt::None, item_indent);
 
         buf.ensure_ends_with_newline();
         buf.indent(braces_indent);
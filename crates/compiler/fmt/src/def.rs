@@ -11,6 +11,7 @@ use roc_parse::ast::{
 };
 use roc_parse::header::Keyword;
 use roc_region::all::Loc;
+use std::ops::Range;
 
 /// A Located formattable value is also formattable
 
@@ -50,6 +51,44 @@ impl<'a> Formattable for Defs<'a> {
     }
 }
 
+/// Formats only the defs at the given indices, e.g. to reformat part of a file.
+/// The spaces before the first def and after the last one are left out, because
+/// they are part of the code around the defs.
+pub fn fmt_defs_range(buf: &mut Buf, defs: &Defs, indices: Range<usize>, indent: u16) {
+    let mut prev_spaces = true;
+    let first = indices.start;
+    let last = indices.end.saturating_sub(1);
+
+    for (index, def) in defs.defs().enumerate() {
+        if !indices.contains(&index) {
+            continue;
+        }
+
+        if index != first {
+            let spaces_before = &defs.spaces[defs.space_before[index].indices()];
+
+            if prev_spaces {
+                fmt_spaces(buf, spaces_before.iter(), indent);
+            } else {
+                fmt_default_newline(buf, spaces_before, indent);
+            }
+        }
+
+        match def {
+            Ok(type_def) => type_def.format(buf, indent),
+            Err(value_def) => value_def.format(buf, indent),
+        }
+
+        if index != last {
+            let spaces_after = &defs.spaces[defs.space_after[index].indices()];
+
+            fmt_spaces(buf, spaces_after.iter(), indent);
+
+            prev_spaces = !spaces_after.is_empty();
+        }
+    }
+}
+
 impl<'a> Formattable for TypeDef<'a> {
     fn is_multiline(&self) -> bool {
         use roc_parse::ast::TypeDef::*;
@@ -423,6 +462,7 @@ impl<'a> Formattable for ValueDef<'a> {
             ModuleImport(module_import) => module_import.is_multiline(),
             IngestedFileImport(ingested_file_import) => ingested_file_import.is_multiline(),
             Stmt(loc_expr) => loc_expr.is_multiline(),
+            Malformed(text) => text.contains('\n'),
         }
     }
 
@@ -468,6 +508,23 @@ impl<'a> Formattable for ValueDef<'a> {
             ModuleImport(module_import) => module_import.format(buf, indent),
             IngestedFileImport(ingested_file_import) => ingested_file_import.format(buf, indent),
             Stmt(loc_expr) => loc_expr.format_with_options(buf, parens, newlines, indent),
+            Malformed(text) => fmt_malformed_def(buf, text, indent),
+        }
+    }
+}
+
+/// Writes out a def that doesn't parse just as it was written, since it can't be reformatted
+fn fmt_malformed_def(buf: &mut Buf, text: &str, indent: u16) {
+    for (index, line) in text.lines().enumerate() {
+        if index > 0 {
+            buf.newline();
+        }
+
+        let line = line.trim_end();
+
+        if !line.is_empty() {
+            buf.indent(indent);
+            buf.push_str_allow_spaces(line);
         }
     }
 }
@@ -594,9 +651,21 @@ pub fn fmt_body<'a>(buf: &mut Buf, pattern: &'a Pattern<'a>, body: &'a Expr<'a>,
         false
     };
 
+    // `f x y = body` defines a function, so it doesn't get parens like `(Ok x) = body` does
+    let parens = match pattern {
+        Pattern::Apply(
+            Loc {
+                value: Pattern::Identifier { .. },
+                ..
+            },
+            _,
+        ) => Parens::NotNeeded,
+        _ => Parens::InApply,
+    };
+
     // Don't format the `{} =` for defs with this pattern
     if !is_unit_assignment {
-        pattern.format_with_options(buf, Parens::InApply, Newlines::No, indent);
+        pattern.format_with_options(buf, parens, Newlines::No, indent);
         buf.indent(indent);
         buf.push_str(" =");
     }
@@ -3,8 +3,8 @@ use crate::collection::{fmt_collection, Braces};
 use crate::def::fmt_defs;
 use crate::pattern::fmt_pattern;
 use crate::spaces::{
-    count_leading_newlines, fmt_comments_only, fmt_spaces, fmt_spaces_no_blank_lines, NewlineAt,
-    INDENT,
+    count_leading_newlines, fmt_comment, fmt_comments_only, fmt_spaces, fmt_spaces_no_blank_lines,
+    split_trailing_comment, NewlineAt, TrailingComments, INDENT,
 };
 use crate::Buf;
 use roc_module::called_via::{self, BinOp};
@@ -870,6 +870,8 @@ fn fmt_when<'a>(
     buf.newline();
 
     let mut prev_branch_was_multiline = false;
+    let mut trailing_comments = TrailingComments::default();
+    let mut prev_branch_start = 0;
 
     for (branch_index, branch) in branches.iter().enumerate() {
         let expr = &branch.value;
@@ -881,9 +883,25 @@ fn fmt_when<'a>(
             if pattern_index == 0 {
                 match &pattern.value {
                     Pattern::SpaceBefore(sub_pattern, spaces) => {
+                        // A comment at the end of the previous branch's line stays there
+                        let (trailing_comment, spaces) = if branch_index > 0 {
+                            split_trailing_comment(spaces)
+                        } else {
+                            (None, *spaces)
+                        };
+
+                        match trailing_comment {
+                            Some(comment) => {
+                                trailing_comments.push(buf, comment, prev_branch_start);
+                                buf.newline();
+                            }
+                            None => trailing_comments.end_run(buf),
+                        }
+
                         let added_blank_line;
 
                         if branch_index > 0 // Never render newlines before the first branch.
+                            && trailing_comment.is_none()
                             && matches!(spaces.first(), Some(CommentOrNewline::Newline))
                         {
                             if prev_branch_was_multiline {
@@ -912,9 +930,12 @@ fn fmt_when<'a>(
                             }
                         }
 
+                        prev_branch_start = buf.len();
                         fmt_pattern(buf, sub_pattern, indent + INDENT, Parens::NotNeeded);
                     }
                     other => {
+                        trailing_comments.end_run(buf);
+
                         if branch_index > 0 {
                             if prev_branch_was_multiline {
                                 // Multiline branches always get a full blank line after them.
@@ -924,6 +945,7 @@ fn fmt_when<'a>(
                             }
                         }
 
+                        prev_branch_start = buf.len();
                         fmt_pattern(buf, other, indent + INDENT, Parens::NotNeeded);
                     }
                 }
@@ -985,6 +1007,8 @@ fn fmt_when<'a>(
 
         prev_branch_was_multiline = is_multiline_expr || is_multiline_patterns;
     }
+
+    trailing_comments.end_run(buf);
 }
 
 fn fmt_dbg<'a>(
@@ -1411,6 +1435,9 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
 
         if is_multiline {
             let field_indent = indent + INDENT;
+            let mut trailing_comments = TrailingComments::default();
+            let mut prev_field_start = 0;
+
             for (index, field) in loc_fields.iter().enumerate() {
                 // comma addition is handled by the `format_field_multiline` function
                 // since we can have stuff like:
@@ -1420,8 +1447,22 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
                 // In this case, we have to move the comma before the comment.
 
                 let is_first_item = index == 0;
+                let mut has_trailing_comment = false;
+
                 if let Some((_sub_field, spaces)) = to_space_before(&field.value) {
                     let is_only_newlines = spaces.iter().all(|s| s.is_newline());
+
+                    // A comment right after the previous field's comma stays at the end of its line
+                    let spaces = match split_trailing_comment(spaces) {
+                        (Some(comment), rest) if !is_first_item => {
+                            trailing_comments.push(buf, comment, prev_field_start);
+                            has_trailing_comment = true;
+
+                            rest
+                        }
+                        _ => spaces,
+                    };
+
                     if !is_first_item
                         && !is_only_newlines
                         && count_leading_newlines(spaces.iter()) > 1
@@ -1436,9 +1477,26 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
                     }
                 }
 
+                if !has_trailing_comment {
+                    trailing_comments.end_run(buf);
+                }
+
+                prev_field_start = buf.len();
+
                 format_field_multiline(buf, &field.value, field_indent, "");
             }
 
+            let final_comments = match split_trailing_comment(final_comments) {
+                (Some(comment), rest) if !loc_fields.is_empty() => {
+                    trailing_comments.push(buf, comment, prev_field_start);
+
+                    rest
+                }
+                _ => final_comments,
+            };
+
+            trailing_comments.end_run(buf);
+
             if count_leading_newlines(final_comments.iter()) > 1 {
                 buf.newline();
             }
@@ -1537,11 +1595,17 @@ fn format_assigned_field_multiline<T>(
             // ```
             // we'd like to transform it into:
             // ```
-            // field,
-            // # comment
+            // field, # comment
             // otherfield
             // ```
             format_assigned_field_multiline(buf, sub_field, indent, separator_prefix);
+
+            let (trailing_comment, spaces) = split_trailing_comment(spaces);
+
+            if let Some(comment) = trailing_comment {
+                fmt_comment(buf, comment);
+            }
+
             fmt_comments_only(buf, spaces.iter(), NewlineAt::Top, indent);
         }
         Malformed(raw) => {
@@ -9,9 +9,14 @@ pub mod expr;
 pub mod module;
 pub mod pattern;
 pub mod spaces;
+pub mod verify;
+
+use std::ops::Range;
 
 use bumpalo::{collections::String, Bump};
 use roc_parse::ast::Module;
+use roc_parse::module::parse_module_defs;
+use roc_parse::state::State;
 
 #[derive(Debug)]
 pub struct Ast<'a> {
@@ -19,6 +24,55 @@ pub struct Ast<'a> {
     pub defs: roc_parse::ast::Defs<'a>,
 }
 
+/// The result of [`fmt_range`]: replacing `range` in the source with `text` formats it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedRange {
+    /// The byte range of the defs that were formatted, which contains the requested range
+    pub range: Range<usize>,
+    pub text: std::string::String,
+}
+
+/// Formats the smallest run of top-level defs that contains `byte_range`, e.g. for an
+/// editor's "format selection". Returns `None` if `src` doesn't parse, or the range is
+/// not inside any def, e.g. because it's in the module header.
+pub fn fmt_range<'a>(
+    arena: &'a Bump,
+    src: &'a str,
+    byte_range: Range<usize>,
+) -> Option<FormattedRange> {
+    let (module, state) =
+        roc_parse::module::parse_header(arena, State::new(src.as_bytes())).ok()?;
+    let (_, defs) = module.upgrade_header_imports(arena);
+    let defs = parse_module_defs(arena, state, defs).ok()?;
+
+    let overlaps = |index: &usize| {
+        let region = defs.regions[*index];
+        let (start, end) = (region.start().offset as usize, region.end().offset as usize);
+
+        if byte_range.is_empty() {
+            // A cursor at either end of a def is still in it
+            start <= byte_range.start && byte_range.start <= end
+        } else {
+            start < byte_range.end && byte_range.start < end
+        }
+    };
+
+    let first = (0..defs.len()).find(overlaps)?;
+    let last = (0..defs.len()).rev().find(overlaps)?;
+
+    let mut buf = Buf::new_in(arena);
+
+    def::fmt_defs_range(&mut buf, &defs, first..last + 1, 0);
+
+    let range =
+        defs.regions[first].start().offset as usize..defs.regions[last].end().offset as usize;
+
+    Some(FormattedRange {
+        range,
+        text: buf.as_str().to_string(),
+    })
+}
+
 #[derive(Debug)]
 pub struct Buf<'a> {
     text: String<'a>,
@@ -165,4 +219,29 @@ impl<'a> Buf<'a> {
     fn is_empty(&self) -> bool {
         self.spaces_to_flush == 0 && self.text.is_empty()
     }
+
+    /// How many bytes have been written, not counting spaces and newlines that were not flushed yet
+    pub(crate) fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// The text that was written since the given byte offset
+    pub(crate) fn text_from(&self, offset: usize) -> &str {
+        &self.text[offset..]
+    }
+
+    /// The column of the given byte offset, counted in characters from the start of its line
+    pub(crate) fn column_at(&self, offset: usize) -> usize {
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+        before[line_start..].chars().count()
+    }
+
+    /// Inserts spaces at the given byte offset, e.g. to line up a comment that was already written
+    pub(crate) fn insert_spaces(&mut self, offset: usize, count: usize) {
+        for _ in 0..count {
+            self.text.insert(offset, ' ');
+        }
+    }
 }
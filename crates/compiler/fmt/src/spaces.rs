@@ -148,7 +148,62 @@ pub fn fmt_comments_only<'a, 'buf, I>(
     }
 }
 
-fn fmt_comment(buf: &mut Buf, comment: &str) {
+/// Splits off a comment that was on the same line as the code before it, e.g. `# one`
+/// in the spaces after `1,` in `[1, # one\n2]`, so that it can stay on that line.
+pub fn split_trailing_comment<'a>(
+    spaces: &'a [CommentOrNewline<'a>],
+) -> (Option<&'a str>, &'a [CommentOrNewline<'a>]) {
+    match spaces.split_first() {
+        Some((CommentOrNewline::LineComment(comment), rest)) => (Some(comment), rest),
+        _ => (None, spaces),
+    }
+}
+
+/// Writes comments at the end of the lines of items like the fields of a record, and lines
+/// up the comments of consecutive single-line items, so that they start in the same column.
+#[derive(Debug, Default)]
+pub struct TrailingComments {
+    /// Where the `#` of each comment in the current run of single-line items is
+    comment_offsets: std::vec::Vec<usize>,
+}
+
+impl TrailingComments {
+    /// Writes a comment at the end of the current line, which is the end of the item
+    /// that started at `item_start`, an offset from [`Buf::len`].
+    pub fn push(&mut self, buf: &mut Buf, comment: &str, item_start: usize) {
+        let before = buf.len();
+
+        fmt_comment(buf, comment);
+
+        let comment_offset = before + buf.text_from(before).find('#').unwrap_or(0);
+        let item = buf.text_from(item_start)[..comment_offset - item_start].trim_start();
+
+        if item.contains('\n') {
+            // A multiline item ends the run, and its comment isn't lined up with any others
+            self.end_run(buf);
+        } else {
+            self.comment_offsets.push(comment_offset);
+        }
+    }
+
+    /// The item that was just written has no comment at the end of its line, so the
+    /// comments before it and after it are not lined up with each other.
+    pub fn end_run(&mut self, buf: &mut Buf) {
+        let columns: std::vec::Vec<usize> = self
+            .comment_offsets
+            .iter()
+            .map(|offset| buf.column_at(*offset))
+            .collect();
+        let max_column = columns.iter().copied().max().unwrap_or(0);
+
+        // Insert from the back, so the offsets of the earlier comments stay the same
+        for (offset, column) in self.comment_offsets.drain(..).zip(columns).rev() {
+            buf.insert_spaces(offset, max_column - column);
+        }
+    }
+}
+
+pub(crate) fn fmt_comment(buf: &mut Buf, comment: &str) {
     // The '#' in a comment should always be preceded by a newline or a space,
     // unless it's the very beginning of the buffer.
     if !buf.is_empty() && !buf.ends_with_space() && !buf.ends_with_newline() {
@@ -591,6 +646,7 @@ impl<'a> RemoveSpaces<'a> for ValueDef<'a> {
                 IngestedFileImport(ingested_file_import.remove_spaces(arena))
             }
             Stmt(loc_expr) => Stmt(arena.alloc(loc_expr.remove_spaces(arena))),
+            Malformed(text) => Malformed(text),
         }
     }
 }
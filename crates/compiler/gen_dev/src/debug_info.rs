@@ -0,0 +1,496 @@
+//! Basic DWARF debug info, so that debuggers can map the machine code of a procedure back to
+//! the Roc file and line it was defined on. The granularity is one line per procedure,
+//! because mono IR does not keep the regions of individual statements.
+//!
+//! Only ELF objects get debug info for now. Mach-O expects DWARF in a separate `__DWARF`
+//! segment (and a dSYM bundle), and Windows expects PDB files, so `roc build --dev
+//! --debug-info` refuses those targets instead of quietly building without it.
+use object::write::{Object, Relocation, SectionId, StandardSegment, SymbolId};
+use object::{BinaryFormat, RelocationEncoding, RelocationKind, SectionKind};
+use roc_collections::all::MutMap;
+use roc_error_macros::internal_error;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_mono::debug_info::DebugLocations;
+
+const DWARF_VERSION: u16 = 4;
+const ADDRESS_SIZE: u8 = 8;
+
+// Tags, attributes and forms
+const DW_TAG_COMPILE_UNIT: u8 = 0x11;
+const DW_TAG_SUBPROGRAM: u8 = 0x2e;
+const DW_CHILDREN_NO: u8 = 0;
+const DW_CHILDREN_YES: u8 = 1;
+const DW_AT_NAME: u8 = 0x03;
+const DW_AT_STMT_LIST: u8 = 0x10;
+const DW_AT_LOW_PC: u8 = 0x11;
+const DW_AT_HIGH_PC: u8 = 0x12;
+const DW_AT_PRODUCER: u8 = 0x25;
+const DW_AT_DECL_FILE: u8 = 0x3a;
+const DW_AT_DECL_LINE: u8 = 0x3b;
+const DW_FORM_ADDR: u8 = 0x01;
+const DW_FORM_DATA4: u8 = 0x06;
+const DW_FORM_STRING: u8 = 0x08;
+const DW_FORM_UDATA: u8 = 0x0f;
+const DW_FORM_SEC_OFFSET: u8 = 0x17;
+
+// Abbreviation codes used in .debug_info
+const ABBREV_COMPILE_UNIT: u64 = 1;
+const ABBREV_SUBPROGRAM: u64 = 2;
+
+// Line number program opcodes
+const DW_LNS_COPY: u8 = 0x01;
+const DW_LNS_ADVANCE_PC: u8 = 0x02;
+const DW_LNS_ADVANCE_LINE: u8 = 0x03;
+const DW_LNS_SET_FILE: u8 = 0x04;
+const DW_LNE_END_SEQUENCE: u8 = 0x01;
+const DW_LNE_SET_ADDRESS: u8 = 0x02;
+const LINE_BASE: i8 = -5;
+const LINE_RANGE: u8 = 14;
+const OPCODE_BASE: u8 = 13;
+const STANDARD_OPCODE_LENGTHS: [u8; 12] = [0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1];
+
+/// A procedure that was written to the object, with the symbol it was generated for.
+pub(crate) struct DebugProc {
+    pub symbol: Symbol,
+    pub fn_name: String,
+    pub proc_id: SymbolId,
+}
+
+/// Adds `.debug_abbrev`, `.debug_info` and `.debug_line` sections describing the given
+/// procedures. Procedures without a known location (like generated helpers) are skipped.
+pub(crate) fn add_debug_info(
+    output: &mut Object,
+    locations: &DebugLocations,
+    root_module: ModuleId,
+    procs: &[DebugProc],
+) {
+    if output.format() != BinaryFormat::Elf {
+        return;
+    }
+
+    let mut file_indices: MutMap<ModuleId, u64> = MutMap::default();
+    let mut files = Vec::new();
+    let mut entries = Vec::new();
+
+    for proc in procs {
+        let Some((path, line)) = locations.get(proc.symbol) else {
+            continue;
+        };
+
+        let module_id = proc.symbol.module_id();
+
+        // File numbers in the line program start at 1
+        let file_index = *file_indices.entry(module_id).or_insert_with(|| {
+            files.push(path.to_string_lossy().into_owned());
+            files.len() as u64
+        });
+
+        let size = output.symbol(proc.proc_id).size;
+
+        entries.push((proc, file_index, line, size));
+    }
+
+    if entries.is_empty() {
+        return;
+    }
+
+    let unit_name = match locations.files.get(&root_module) {
+        Some(path) => path.to_string_lossy().into_owned(),
+        None => files[0].clone(),
+    };
+
+    let abbrev_section = add_debug_section(output, b".debug_abbrev");
+    let info_section = add_debug_section(output, b".debug_info");
+    let line_section = add_debug_section(output, b".debug_line");
+
+    // .debug_abbrev
+    let mut abbrev = Vec::new();
+
+    write_uleb128(&mut abbrev, ABBREV_COMPILE_UNIT);
+    abbrev.push(DW_TAG_COMPILE_UNIT);
+    abbrev.push(DW_CHILDREN_YES);
+    abbrev.extend([DW_AT_PRODUCER, DW_FORM_STRING]);
+    abbrev.extend([DW_AT_NAME, DW_FORM_STRING]);
+    abbrev.extend([DW_AT_STMT_LIST, DW_FORM_SEC_OFFSET]);
+    abbrev.extend([0, 0]);
+
+    write_uleb128(&mut abbrev, ABBREV_SUBPROGRAM);
+    abbrev.push(DW_TAG_SUBPROGRAM);
+    abbrev.push(DW_CHILDREN_NO);
+    abbrev.extend([DW_AT_NAME, DW_FORM_STRING]);
+    abbrev.extend([DW_AT_LOW_PC, DW_FORM_ADDR]);
+    abbrev.extend([DW_AT_HIGH_PC, DW_FORM_DATA4]);
+    abbrev.extend([DW_AT_DECL_FILE, DW_FORM_UDATA]);
+    abbrev.extend([DW_AT_DECL_LINE, DW_FORM_UDATA]);
+    abbrev.extend([0, 0]);
+
+    abbrev.push(0);
+
+    // .debug_info
+    let mut info = Vec::new();
+    let mut info_relocations = Vec::new();
+
+    info.extend(0u32.to_le_bytes()); // unit_length, patched below
+    info.extend(DWARF_VERSION.to_le_bytes());
+    info_relocations.push(section_offset_relocation(
+        output,
+        info.len(),
+        abbrev_section,
+    ));
+    info.extend(0u32.to_le_bytes());
+    info.push(ADDRESS_SIZE);
+
+    write_uleb128(&mut info, ABBREV_COMPILE_UNIT);
+    write_string(&mut info, "roc dev backend");
+    write_string(&mut info, &unit_name);
+    info_relocations.push(section_offset_relocation(output, info.len(), line_section));
+    info.extend(0u32.to_le_bytes());
+
+    for (proc, file_index, line, size) in entries.iter() {
+        write_uleb128(&mut info, ABBREV_SUBPROGRAM);
+        write_string(&mut info, &proc.fn_name);
+        info_relocations.push(address_relocation(info.len(), proc.proc_id));
+        info.extend(0u64.to_le_bytes());
+        info.extend((*size as u32).to_le_bytes());
+        write_uleb128(&mut info, *file_index);
+        write_uleb128(&mut info, *line as u64);
+    }
+
+    // End of the compile unit's children
+    info.push(0);
+
+    patch_unit_length(&mut info);
+
+    // .debug_line
+    let mut line_program = Vec::new();
+    let mut line_relocations = Vec::new();
+
+    line_program.extend(0u32.to_le_bytes()); // unit_length, patched below
+    line_program.extend(DWARF_VERSION.to_le_bytes());
+    line_program.extend(0u32.to_le_bytes()); // header_length, patched below
+    let header_start = line_program.len();
+
+    line_program.push(1); // minimum_instruction_length
+    line_program.push(1); // maximum_operations_per_instruction
+    line_program.push(1); // default_is_stmt
+    line_program.push(LINE_BASE as u8);
+    line_program.push(LINE_RANGE);
+    line_program.push(OPCODE_BASE);
+    line_program.extend(STANDARD_OPCODE_LENGTHS);
+
+    // No include directories; the file names are full paths
+    line_program.push(0);
+
+    for file in files.iter() {
+        write_string(&mut line_program, file);
+        write_uleb128(&mut line_program, 0); // directory
+        write_uleb128(&mut line_program, 0); // modification time
+        write_uleb128(&mut line_program, 0); // file length
+    }
+    line_program.push(0);
+
+    let header_length = (line_program.len() - header_start) as u32;
+    line_program[header_start - 4..header_start].copy_from_slice(&header_length.to_le_bytes());
+
+    // Every procedure lives in its own section, so each one gets its own sequence
+    for (proc, file_index, line, size) in entries.iter() {
+        line_program.push(0);
+        write_uleb128(&mut line_program, 1 + ADDRESS_SIZE as u64);
+        line_program.push(DW_LNE_SET_ADDRESS);
+        line_relocations.push(address_relocation(line_program.len(), proc.proc_id));
+        line_program.extend(0u64.to_le_bytes());
+
+        line_program.push(DW_LNS_SET_FILE);
+        write_uleb128(&mut line_program, *file_index);
+
+        // Lines start at 1
+        line_program.push(DW_LNS_ADVANCE_LINE);
+        write_sleb128(&mut line_program, *line as i64 - 1);
+
+        line_program.push(DW_LNS_COPY);
+
+        line_program.push(DW_LNS_ADVANCE_PC);
+        write_uleb128(&mut line_program, *size);
+
+        line_program.push(0);
+        write_uleb128(&mut line_program, 1);
+        line_program.push(DW_LNE_END_SEQUENCE);
+    }
+
+    patch_unit_length(&mut line_program);
+
+    output.append_section_data(abbrev_section, &abbrev, 1);
+    output.append_section_data(info_section, &info, 1);
+    output.append_section_data(line_section, &line_program, 1);
+
+    for (section_id, relocations) in [
+        (info_section, info_relocations),
+        (line_section, line_relocations),
+    ] {
+        for reloc in relocations {
+            if let Err(e) = output.add_relocation(section_id, reloc) {
+                internal_error!("{:?}", e);
+            }
+        }
+    }
+}
+
+fn add_debug_section(output: &mut Object, name: &[u8]) -> SectionId {
+    output.add_section(
+        output.segment_name(StandardSegment::Debug).to_vec(),
+        name.to_vec(),
+        SectionKind::Debug,
+    )
+}
+
+/// The address of a procedure, which the linker fills in.
+fn address_relocation(offset: usize, proc_id: SymbolId) -> Relocation {
+    Relocation {
+        offset: offset as u64,
+        size: 64,
+        kind: RelocationKind::Absolute,
+        encoding: RelocationEncoding::Generic,
+        symbol: proc_id,
+        addend: 0,
+    }
+}
+
+/// An offset into another debug section, which moves when the linker concatenates the debug
+/// sections of all the objects.
+fn section_offset_relocation(output: &mut Object, offset: usize, target: SectionId) -> Relocation {
+    Relocation {
+        offset: offset as u64,
+        size: 32,
+        kind: RelocationKind::Absolute,
+        encoding: RelocationEncoding::Generic,
+        symbol: output.section_symbol(target),
+        addend: 0,
+    }
+}
+
+/// Writes the length of a unit, which doesn't include the length field itself.
+fn patch_unit_length(buf: &mut [u8]) {
+    let unit_length = (buf.len() - 4) as u32;
+    buf[0..4].copy_from_slice(&unit_length.to_le_bytes());
+}
+
+fn write_string(buf: &mut Vec<u8>, string: &str) {
+    buf.extend(string.as_bytes());
+    buf.push(0);
+}
+
+fn write_uleb128(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            buf.push(byte);
+            return;
+        }
+
+        buf.push(byte | 0x80);
+    }
+}
+
+fn write_sleb128(buf: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        let sign_bit_clear = byte & 0x40 == 0;
+
+        if (value == 0 && sign_bit_clear) || (value == -1 && !sign_bit_clear) {
+            buf.push(byte);
+            return;
+        }
+
+        buf.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use object::read::{Object as _, ObjectSection as _, ObjectSymbol as _};
+    use object::write::{Symbol as ObjectSymbol, SymbolSection};
+    use object::{
+        Architecture, Endianness, RelocationTarget, SymbolFlags, SymbolKind, SymbolScope,
+    };
+    use std::path::PathBuf;
+
+    const PROC_NAME: &str = "roc_test_proc";
+
+    /// An object with one 16-byte procedure for `Symbol::NUM_ADD`, defined on line 3 of Num.roc
+    fn object_with_debug_info(format: BinaryFormat) -> Vec<u8> {
+        let mut output = Object::new(format, Architecture::X86_64, Endianness::Little);
+        let text = output.section_id(object::write::StandardSection::Text);
+        let proc_id = output.add_symbol(ObjectSymbol {
+            name: PROC_NAME.as_bytes().to_vec(),
+            value: 0,
+            size: 0,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: SymbolSection::Undefined,
+            flags: SymbolFlags::None,
+        });
+        output.add_symbol_data(proc_id, text, &[0xc3; 16], 16);
+
+        let mut locations = DebugLocations::default();
+        locations
+            .files
+            .insert(ModuleId::NUM, PathBuf::from("/roc/builtins/Num.roc"));
+        locations.lines.insert(Symbol::NUM_ADD, 3);
+
+        let procs = [DebugProc {
+            symbol: Symbol::NUM_ADD,
+            fn_name: PROC_NAME.to_string(),
+            proc_id,
+        }];
+
+        add_debug_info(&mut output, &locations, ModuleId::NUM, &procs);
+
+        output.write().unwrap()
+    }
+
+    fn read_uleb128(bytes: &[u8], offset: &mut usize) -> u64 {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = bytes[*offset];
+            *offset += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+
+    fn read_sleb128(bytes: &[u8], offset: &mut usize) -> i64 {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = bytes[*offset];
+            *offset += 1;
+            value |= i64::from(byte & 0x7f) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1 << shift;
+                }
+
+                return value;
+            }
+        }
+    }
+
+    fn read_string<'a>(bytes: &'a [u8], offset: &mut usize) -> &'a str {
+        let len = bytes[*offset..].iter().position(|b| *b == 0).unwrap();
+        let string = std::str::from_utf8(&bytes[*offset..*offset + len]).unwrap();
+        *offset += len + 1;
+
+        string
+    }
+
+    /// The (file, line) rows of a line program that only uses the opcodes we emit
+    fn line_table_rows(line_program: &[u8]) -> Vec<(String, u64)> {
+        let version = u16::from_le_bytes([line_program[4], line_program[5]]);
+        assert_eq!(version, DWARF_VERSION);
+
+        let header_length = u32::from_le_bytes(line_program[6..10].try_into().unwrap());
+        let program_start = 10 + header_length as usize;
+
+        // Skip the fixed fields, the standard opcode lengths, and the empty include directories
+        let mut offset = 10 + 6 + STANDARD_OPCODE_LENGTHS.len() + 1;
+        let mut files = Vec::new();
+
+        while line_program[offset] != 0 {
+            files.push(read_string(line_program, &mut offset).to_string());
+            read_uleb128(line_program, &mut offset);
+            read_uleb128(line_program, &mut offset);
+            read_uleb128(line_program, &mut offset);
+        }
+
+        assert_eq!(offset + 1, program_start);
+
+        let mut rows = Vec::new();
+        let mut offset = program_start;
+        let (mut file, mut line) = (1, 1);
+
+        while offset < line_program.len() {
+            let opcode = line_program[offset];
+            offset += 1;
+
+            match opcode {
+                0 => {
+                    let len = read_uleb128(line_program, &mut offset) as usize;
+
+                    if line_program[offset] == DW_LNE_END_SEQUENCE {
+                        (file, line) = (1, 1);
+                    }
+
+                    offset += len;
+                }
+                DW_LNS_SET_FILE => file = read_uleb128(line_program, &mut offset),
+                DW_LNS_ADVANCE_LINE => {
+                    line = (line as i64 + read_sleb128(line_program, &mut offset)) as u64;
+                }
+                DW_LNS_ADVANCE_PC => {
+                    read_uleb128(line_program, &mut offset);
+                }
+                DW_LNS_COPY => rows.push((files[file as usize - 1].clone(), line)),
+                other => panic!("unexpected opcode {other}"),
+            }
+        }
+
+        rows
+    }
+
+    #[test]
+    fn elf_line_table_maps_proc_to_its_line() {
+        let bytes = object_with_debug_info(BinaryFormat::Elf);
+        let file = object::File::parse(&*bytes).unwrap();
+        let line_section = file.section_by_name(".debug_line").unwrap();
+
+        assert_eq!(
+            line_table_rows(line_section.data().unwrap()),
+            [("/roc/builtins/Num.roc".to_string(), 3)]
+        );
+
+        // The only address in the line program is the start of the procedure
+        let targets: Vec<_> = line_section
+            .relocations()
+            .map(|(_, reloc)| match reloc.target() {
+                RelocationTarget::Symbol(index) => file
+                    .symbol_by_index(index)
+                    .unwrap()
+                    .name()
+                    .unwrap()
+                    .to_string(),
+                other => panic!("unexpected relocation target {other:?}"),
+            })
+            .collect();
+
+        assert_eq!(targets, [PROC_NAME]);
+    }
+
+    #[test]
+    fn no_debug_info_in_mach_o_or_coff() {
+        for format in [BinaryFormat::MachO, BinaryFormat::Coff] {
+            let bytes = object_with_debug_info(format);
+            let file = object::File::parse(&*bytes).unwrap();
+
+            assert!(
+                file.sections()
+                    .all(|section| !section.name().unwrap_or("").contains("debug_")),
+                "{format:?} got debug sections"
+            );
+        }
+    }
+}
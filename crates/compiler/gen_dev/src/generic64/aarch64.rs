@@ -1262,12 +1262,24 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         imm32: i32,
     ) {
-        if imm32 < 0 {
-            Self::sub_reg64_reg64_imm32(buf, dst, src, -imm32);
-        } else if imm32 < 0xFFF {
+        if (0..=0xFFF).contains(&imm32) {
             add_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
+        } else if (0..=0xFF_FFFF).contains(&imm32) {
+            // Add the upper 12 bits (shifted), and then the lower 12 bits
+            add_reg64_reg64_imm12_lsl12(buf, dst, src, (imm32 >> 12) as u16);
+
+            if imm32 & 0xFFF != 0 {
+                add_reg64_reg64_imm12(buf, dst, dst, (imm32 & 0xFFF) as u16);
+            }
+        } else if (-0xFF_FFFF..0).contains(&imm32) {
+            Self::sub_reg64_reg64_imm32(buf, dst, src, -imm32);
         } else {
-            todo!("immediate additions with values greater than 12bits");
+            // Too big for the immediate forms: load it into the scratch register. The extended
+            // register form is used because, unlike the shifted one, it can take the stack pointer.
+            let tmp = AArch64GeneralReg::X15;
+            debug_assert_ne!(src, tmp);
+            Self::mov_reg64_imm64(buf, tmp, imm32 as i64);
+            add_reg64_reg64_reg64_uxtx(buf, dst, src, tmp);
         }
     }
     #[inline(always)]
@@ -1280,6 +1292,26 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         add_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
+    #[inline(always)]
+    fn add_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        overflow: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+        signed: bool,
+    ) {
+        adds_reg64_reg64_reg64(buf, dst, src1, src2);
+
+        // V is set on signed overflow, C on unsigned overflow
+        let cond = if signed {
+            ConditionCode::VS
+        } else {
+            ConditionCode::CSHS
+        };
+        cset_reg64_cond(buf, overflow, cond);
+    }
+
     #[inline(always)]
     fn add_freg32_freg32_freg32(
         buf: &mut Vec<'_, u8>,
@@ -1380,6 +1412,28 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         mul_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
+    #[inline(always)]
+    fn imul_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        overflow: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        // MUL does not set flags. The product fits in 64 bits exactly when the
+        // upper half is the sign extension of the lower half.
+        smulh_reg64_reg64_reg64(buf, overflow, src1, src2);
+        mul_reg64_reg64_reg64(buf, dst, src1, src2);
+        subs_reg64_reg64_reg64_with_shift(
+            buf,
+            AArch64GeneralReg::ZRSP,
+            overflow,
+            dst,
+            (ShiftType::ASR, 63),
+        );
+        cset_reg64_cond(buf, overflow, ConditionCode::NE);
+    }
+
     fn umul_reg64_reg64_reg64<'a, ASM, CC>(
         buf: &mut Vec<'a, u8>,
         _storage_manager: &mut StorageManager<'a, '_, AArch64GeneralReg, AArch64FloatReg, ASM, CC>,
@@ -1686,9 +1740,8 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     ) {
         if (-256..256).contains(&offset) {
             ldur_reg_reg_imm9(buf, register_width, dst, src, offset as i16);
-        } else if (0..=u16::MAX as i32).contains(&offset) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg_reg_imm12(buf, register_width, dst, src, (offset as u16) >> 3);
+        } else if let Some(imm12) = scaled_offset(offset, 1 << register_width as i32) {
+            ldr_reg_reg_imm12(buf, register_width, dst, src, imm12);
         } else {
             let tmp = AArch64GeneralReg::X15;
             Self::mov_reg64_imm64(buf, tmp, offset as i64);
@@ -1713,7 +1766,7 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     }
     #[inline(always)]
     fn mov_base32_freg32(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64FloatReg) {
-        Self::mov_mem64_offset32_freg64(buf, AArch64GeneralReg::FP, offset, src)
+        Self::mov_mem32_offset32_freg32(buf, AArch64GeneralReg::FP, offset, src)
     }
     #[inline(always)]
     fn movesd_mem64_offset32_freg64(
@@ -1744,9 +1797,8 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     ) {
         if (-256..256).contains(&offset) {
             stur_reg_reg_imm9(buf, register_width, src, dst, offset as i16);
-        } else if (0..=u16::MAX as i32).contains(&offset) {
-            debug_assert_eq!(offset % 8, 0);
-            str_reg_reg_imm12(buf, register_width, src, dst, (offset as u16) >> 3);
+        } else if let Some(imm12) = scaled_offset(offset, 1 << register_width as i32) {
+            str_reg_reg_imm12(buf, register_width, src, dst, imm12);
         } else {
             let tmp = AArch64GeneralReg::X15;
             Self::mov_reg64_imm64(buf, tmp, offset as i64);
@@ -1764,9 +1816,8 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     ) {
         if (-256..256).contains(&offset) {
             stur_freg64_reg64_imm9(buf, src, dst, offset as i16)
-        } else if (0..=u16::MAX as i32).contains(&offset) {
-            debug_assert!(offset % 8 == 0);
-            str_freg64_reg64_imm12(buf, src, dst, (offset as u16) >> 3);
+        } else if let Some(imm12) = scaled_offset(offset, 8) {
+            str_freg64_reg64_imm12(buf, src, dst, imm12);
         } else {
             let tmp = AArch64GeneralReg::X15;
             Self::mov_reg64_imm64(buf, tmp, offset as i64);
@@ -1853,12 +1904,23 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         imm32: i32,
     ) {
-        if imm32 < 0 {
-            Self::add_reg64_reg64_imm32(buf, dst, src, -imm32)
-        } else if imm32 < 0xFFF {
+        if (0..=0xFFF).contains(&imm32) {
             sub_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
+        } else if (0..=0xFF_FFFF).contains(&imm32) {
+            // Subtract the upper 12 bits (shifted), and then the lower 12 bits
+            sub_reg64_reg64_imm12_lsl12(buf, dst, src, (imm32 >> 12) as u16);
+
+            if imm32 & 0xFFF != 0 {
+                sub_reg64_reg64_imm12(buf, dst, dst, (imm32 & 0xFFF) as u16);
+            }
+        } else if (-0xFF_FFFF..0).contains(&imm32) {
+            Self::add_reg64_reg64_imm32(buf, dst, src, -imm32)
         } else {
-            todo!("immediate subtractions with values greater than 12bits");
+            // Same as for additions: the extended register form can take the stack pointer
+            let tmp = AArch64GeneralReg::X15;
+            debug_assert_ne!(src, tmp);
+            Self::mov_reg64_imm64(buf, tmp, imm32 as i64);
+            sub_reg64_reg64_reg64_uxtx(buf, dst, src, tmp);
         }
     }
     #[inline(always)]
@@ -1871,6 +1933,26 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         sub_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
+    #[inline(always)]
+    fn sub_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        overflow: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+        signed: bool,
+    ) {
+        subs_reg64_reg64_reg64(buf, dst, src1, src2);
+
+        // V is set on signed overflow, and C is clear on an unsigned borrow
+        let cond = if signed {
+            ConditionCode::VS
+        } else {
+            ConditionCode::CCLO
+        };
+        cset_reg64_cond(buf, overflow, cond);
+    }
+
     #[inline(always)]
     fn eq_reg_reg_reg(
         buf: &mut Vec<'_, u8>,
@@ -2096,9 +2178,8 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     ) {
         if (-256..256).contains(&offset) {
             ldur_freg64_reg64_imm9(buf, dst, src, offset as i16)
-        } else if (0..=u16::MAX as i32).contains(&offset) {
-            debug_assert!(offset % 8 == 0);
-            ldr_freg64_reg64_imm12(buf, dst, src, (offset as u16) >> 3);
+        } else if let Some(imm12) = scaled_offset(offset, 8) {
+            ldr_freg64_reg64_imm12(buf, dst, src, imm12);
         } else {
             let tmp = AArch64GeneralReg::X15;
             Self::mov_reg64_imm64(buf, tmp, offset as i64);
@@ -2113,18 +2194,50 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         offset: i32,
     ) {
-        if offset < 0 {
-            ldur_freg64_reg64_imm9(buf, dst, src, offset as i16)
-        } else if (0..=u16::MAX as i32).contains(&offset) {
-            debug_assert!(offset % 8 == 0);
-            ldr_freg64_reg64_imm12(buf, dst, src, (offset as u16) >> 3);
+        if (-256..256).contains(&offset) {
+            ldur_freg32_reg64_imm9(buf, dst, src, offset as i16)
+        } else if let Some(imm12) = scaled_offset(offset, 4) {
+            ldr_freg32_reg64_imm12(buf, dst, src, imm12);
         } else {
-            todo!("base offsets over 32k for AArch64");
+            let tmp = AArch64GeneralReg::X15;
+            Self::mov_reg64_imm64(buf, tmp, offset as i64);
+            Self::add_reg64_reg64_reg64(buf, tmp, tmp, src);
+            ldr_freg32_reg64_imm12(buf, dst, tmp, 0);
         }
     }
 }
 
-impl AArch64Assembler {}
+impl AArch64Assembler {
+    #[inline(always)]
+    fn mov_mem32_offset32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        offset: i32,
+        src: AArch64FloatReg,
+    ) {
+        if (-256..256).contains(&offset) {
+            stur_freg32_reg64_imm9(buf, src, dst, offset as i16)
+        } else if let Some(imm12) = scaled_offset(offset, 4) {
+            str_freg32_reg64_imm12(buf, src, dst, imm12);
+        } else {
+            let tmp = AArch64GeneralReg::X15;
+            Self::mov_reg64_imm64(buf, tmp, offset as i64);
+            Self::add_reg64_reg64_reg64(buf, tmp, tmp, dst);
+            str_freg32_reg64_imm12(buf, src, tmp, 0);
+        }
+    }
+}
+
+/// Loads and stores with an unsigned immediate offset scale it by the size of what they load or
+/// store, so the offset has to be a multiple of that size, and at most 4095 times it.
+#[inline(always)]
+fn scaled_offset(offset: i32, size: i32) -> Option<u16> {
+    if offset >= 0 && offset % size == 0 && offset / size <= 0xFFF {
+        Some((offset / size) as u16)
+    } else {
+        None
+    }
+}
 
 // Instructions
 // ARM manual section C3
@@ -2321,6 +2434,44 @@ impl ArithmeticShifted {
     }
 }
 
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct ArithmeticExtended {
+    sf: bool,
+    op: bool, // add or subtract
+    s: bool,
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b01011,
+    opt: Integer<u8, packed_bits::Bits<2>>,   // = 0b00,
+    fixed2: bool,                             // = 0b1,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    option: Integer<u8, packed_bits::Bits<3>>,
+    imm3: Integer<u8, packed_bits::Bits<3>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for ArithmeticExtended {}
+
+impl ArithmeticExtended {
+    /// Xm is used as is (`UXTX` without a shift); Xd and Xn can be the stack pointer.
+    #[inline(always)]
+    fn new(op: bool, rm: AArch64GeneralReg, rn: AArch64GeneralReg, rd: AArch64GeneralReg) -> Self {
+        Self {
+            sf: true,
+            op,
+            s: false,
+            fixed: 0b01011.into(),
+            opt: 0b00.into(),
+            fixed2: true,
+            reg_m: rm.id().into(),
+            option: 0b011.into(),
+            imm3: 0.into(),
+            reg_n: rn.id().into(),
+            reg_d: rd.id().into(),
+        }
+    }
+}
+
 // ARM manual section C1.2.4
 #[derive(Copy, Clone, PartialEq)]
 #[allow(dead_code)]
@@ -3216,6 +3367,26 @@ fn add_reg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `ADD Xd, Xn, imm12, LSL #12` -> Add Xn and imm12 shifted left by 12 and place the result into Xd.
+#[inline(always)]
+fn add_reg64_reg64_imm12_lsl12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(ArithmeticImmediateParams {
+        op: false,
+        s: false,
+        sh: true,
+        imm12,
+        rd: dst,
+        rn: src,
+    });
+
+    buf.extend(inst.bytes());
+}
+
 /// `ADD Xd, Xm, Xn` -> Add Xm and Xn and place the result into Xd.
 #[inline(always)]
 fn add_reg64_reg64_reg64(
@@ -3237,6 +3408,40 @@ fn add_reg64_reg64_reg64(
     buf.extend(inst.bytes());
 }
 
+/// `ADD Xd, Xn, Xm, UXTX` -> Add Xn and Xm and place the result into Xd. Xd and Xn can be SP.
+#[inline(always)]
+fn add_reg64_reg64_reg64_uxtx(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticExtended::new(false, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ADDS Xd, Xn, Xm` -> Add Xn and Xm and place the result into Xd. Set condition flags.
+#[inline(always)]
+fn adds_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticShifted::new(ArithmeticShiftedParams {
+        op: false,
+        s: true,
+        shift: ShiftType::LSL,
+        imm6: 0,
+        rm: src2,
+        rn: src1,
+        rd: dst,
+    });
+
+    buf.extend(inst.bytes());
+}
+
 /// `AND Xd, Xn, Xm` -> Bitwise AND Xn and Xm and place the result into Xd.
 #[inline(always)]
 fn and_reg64_reg64_reg64(
@@ -3498,6 +3703,29 @@ fn ldr_freg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `LDR St, [Xn, #offset]` -> Load Xn + Offset St. ZRSP is SP.
+/// Note: imm12 is the offest divided by 4.
+#[inline(always)]
+fn ldr_freg32_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    base: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = LoadStoreRegisterImmediate {
+        size: 0b10.into(), // 32-bit
+        fixed: 0b111.into(),
+        fixed2: true,
+        fixed3: 0b01.into(),
+        opc: 0b01.into(), // load
+        imm12: imm12.into(),
+        rn: base.id().into(),
+        rt: dst.id().into(),
+    };
+
+    buf.extend(inst.bytes());
+}
+
 #[inline(always)]
 fn ldur_freg64_reg64_imm9(
     buf: &mut Vec<'_, u8>,
@@ -3525,6 +3753,33 @@ fn ldur_freg64_reg64_imm9(
     buf.extend(inst.bytes());
 }
 
+#[inline(always)]
+fn ldur_freg32_reg64_imm9(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    base: AArch64GeneralReg,
+    imm9: i16,
+) {
+    // the value must fit in 8 bits (1 bit for the sign)
+    assert!((-256..256).contains(&imm9));
+
+    let imm9 = u16::from_ne_bytes(imm9.to_ne_bytes());
+    let imm12 = (imm9 & 0b0001_1111_1111) << 2;
+
+    let inst = LoadStoreRegisterImmediate {
+        size: 0b10.into(), // 32-bit
+        fixed: 0b111.into(),
+        fixed2: true,
+        fixed3: 0b00.into(),
+        opc: 0b01.into(), // load
+        imm12: imm12.into(),
+        rn: base.id().into(),
+        rt: dst.id().into(),
+    };
+
+    buf.extend(inst.bytes());
+}
+
 /// `LSL Xd, Xn, Xm` -> Logical shift Xn left by Xm and place the result into Xd.
 #[inline(always)]
 fn lsl_reg64_reg64_reg64(
@@ -3672,6 +3927,26 @@ fn sdiv_reg64_reg64_reg64(
     buf.extend(inst.bytes());
 }
 
+/// `SMULH Xd, Xn, Xm` -> Multiply Xn and Xm as signed integers and place the
+/// upper 64 bits of the 128-bit product into Xd.
+#[inline(always)]
+fn smulh_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = DataProcessingThreeSource::new(DataProcessingThreeSourceParams {
+        op31: 0b010,
+        rm: src2,
+        ra: AArch64GeneralReg::ZRSP,
+        rn: src1,
+        rd: dst,
+    });
+
+    buf.extend(inst.bytes());
+}
+
 fn stur_reg_reg_imm9(
     buf: &mut Vec<'_, u8>,
     register_width: RegisterWidth,
@@ -3746,6 +4021,33 @@ fn stur_freg64_reg64_imm9(
     buf.extend(inst.bytes());
 }
 
+#[inline(always)]
+fn stur_freg32_reg64_imm9(
+    buf: &mut Vec<'_, u8>,
+    src: AArch64FloatReg,
+    base: AArch64GeneralReg,
+    imm9: i16,
+) {
+    // the value must fit in 8 bits (1 bit for the sign)
+    assert!((-256..256).contains(&imm9));
+
+    let imm9 = u16::from_ne_bytes(imm9.to_ne_bytes());
+    let imm12 = (imm9 & 0b0001_1111_1111) << 2;
+
+    let inst = LoadStoreRegisterImmediate {
+        size: 0b10.into(), // 32-bit
+        fixed: 0b111.into(),
+        fixed2: true,
+        fixed3: 0b00.into(),
+        opc: 0b00.into(), // store
+        imm12: imm12.into(),
+        rn: base.id().into(),
+        rt: src.id().into(),
+    };
+
+    buf.extend(inst.bytes());
+}
+
 #[inline(always)]
 fn str_freg64_reg64_imm12(
     buf: &mut Vec<'_, u8>,
@@ -3767,6 +4069,27 @@ fn str_freg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+#[inline(always)]
+fn str_freg32_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    src: AArch64FloatReg,
+    base: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = LoadStoreRegisterImmediate {
+        size: 0b10.into(), // 32-bit
+        fixed: 0b111.into(),
+        fixed2: true,
+        fixed3: 0b01.into(),
+        opc: 0b00.into(), // store
+        imm12: imm12.into(),
+        rn: base.id().into(),
+        rt: src.id().into(),
+    };
+
+    buf.extend(inst.bytes());
+}
+
 /// `SUB Xd, Xn, imm12` -> Subtract Xn and imm12 and place the result into Xd.
 #[inline(always)]
 fn sub_reg64_reg64_imm12(
@@ -3787,6 +4110,39 @@ fn sub_reg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `SUB Xd, Xn, imm12, LSL #12` -> Subtract imm12 shifted left by 12 from Xn and place the result into Xd.
+#[inline(always)]
+fn sub_reg64_reg64_imm12_lsl12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(ArithmeticImmediateParams {
+        op: true,
+        s: false,
+        rd: dst,
+        rn: src,
+        imm12,
+        sh: true,
+    });
+
+    buf.extend(inst.bytes());
+}
+
+/// `SUB Xd, Xn, Xm, UXTX` -> Subtract Xm from Xn and place the result into Xd. Xd and Xn can be SP.
+#[inline(always)]
+fn sub_reg64_reg64_reg64_uxtx(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticExtended::new(true, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
 /// `SUB Xd, Xm, Xn` -> Subtract Xm and Xn and place the result into Xd.
 #[inline(always)]
 fn sub_reg64_reg64_reg64(
@@ -4493,6 +4849,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_reg64_reg64_reg64_uxtx() {
+        disassembler_test!(
+            add_reg64_reg64_reg64_uxtx,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                // The extend is only left out when SP is involved
+                if reg1 == AArch64GeneralReg::ZRSP || reg2 == AArch64GeneralReg::ZRSP {
+                    format!(
+                        "add {}, {}, {}",
+                        reg1.capstone_string(UsesSP),
+                        reg2.capstone_string(UsesSP),
+                        reg3.capstone_string(UsesZR)
+                    )
+                } else {
+                    format!(
+                        "add {}, {}, {}, uxtx",
+                        reg1.capstone_string(UsesSP),
+                        reg2.capstone_string(UsesSP),
+                        reg3.capstone_string(UsesZR)
+                    )
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_adds_reg64_reg64_reg64() {
+        disassembler_test!(
+            adds_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                if reg1 == AArch64GeneralReg::ZRSP {
+                    // When the first register is ZR, it gets disassembled as cmn,
+                    // which is an alias for adds.
+                    format!(
+                        "cmn {}, {}",
+                        reg2.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                } else {
+                    format!(
+                        "adds {}, {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg2.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_add_reg64_reg64_imm12() {
         disassembler_test!(
@@ -4509,6 +4921,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_reg64_reg64_imm12_lsl12() {
+        disassembler_test!(
+            add_reg64_reg64_imm12_lsl12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "add {}, {}, #0x{:x}, lsl #12",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_imm32_over_24_bits() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>| AArch64Assembler::add_reg64_reg64_imm32(
+                buf,
+                AArch64GeneralReg::ZRSP,
+                AArch64GeneralReg::ZRSP,
+                0x1234_5678
+            ),
+            || "mov x15, #0x5678\nmovk x15, #0x1234, lsl #16\nadd sp, sp, x15"
+        );
+    }
+
     #[test]
     fn test_and_reg64_reg64_reg64() {
         disassembler_test!(
@@ -4758,6 +5199,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ldr_freg32_reg64_imm12() {
+        disassembler_test!(
+            ldr_freg32_reg64_imm12,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg, imm| format!(
+                "ldr {}, [{}, #0x{:x}]",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesSP),
+                imm << 2
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_ldur_freg32_reg64_imm9() {
+        disassembler_test!(
+            ldur_freg32_reg64_imm9,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg, imm| format!(
+                "ldur {}, [{}, {}]",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesSP),
+                signed_hex_i16(imm)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [4, -4]
+        );
+    }
+
     #[test]
     fn test_ludr_freg64_reg64_imm9() {
         disassembler_test!(
@@ -4977,6 +5450,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_smulh_reg64_reg64_reg64() {
+        disassembler_test!(
+            smulh_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "smulh {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_str_reg64_reg64_imm12() {
         disassembler_test!(
@@ -5010,6 +5499,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_str_freg32_reg64_imm12() {
+        disassembler_test!(
+            str_freg32_reg64_imm12,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg, imm| format!(
+                "str {}, [{}, #0x{:x}]",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesSP),
+                imm << 2
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_stur_freg32_reg64_imm9() {
+        disassembler_test!(
+            stur_freg32_reg64_imm9,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg, imm| format!(
+                "stur {}, [{}, {}]",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesSP),
+                signed_hex_i16(imm),
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [4, -4]
+        );
+    }
+
     #[test]
     fn test_stur_freg64_reg64_imm9() {
         disassembler_test!(
@@ -5059,6 +5580,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sub_reg64_reg64_imm12_lsl12() {
+        disassembler_test!(
+            sub_reg64_reg64_imm12_lsl12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "sub {}, {}, #0x{:x}, lsl #12",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_sub_reg64_reg64_imm32_over_24_bits() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>| AArch64Assembler::sub_reg64_reg64_imm32(
+                buf,
+                AArch64GeneralReg::X0,
+                AArch64GeneralReg::X1,
+                0x1234_5678
+            ),
+            || "mov x15, #0x5678\nmovk x15, #0x1234, lsl #16\nsub x0, x1, x15, uxtx"
+        );
+
+        // Can't be negated, so it mustn't be turned into an addition
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>| AArch64Assembler::sub_reg64_reg64_imm32(
+                buf,
+                AArch64GeneralReg::ZRSP,
+                AArch64GeneralReg::ZRSP,
+                i32::MIN
+            ),
+            || concat!(
+                "mov x15, #0\n",
+                "movk x15, #0x8000, lsl #16\n",
+                "movk x15, #0xffff, lsl #32\n",
+                "movk x15, #0xffff, lsl #48\n",
+                "sub sp, sp, x15"
+            )
+        );
+    }
+
     #[test]
     fn test_sub_reg64_reg64_reg64() {
         disassembler_test!(
@@ -5087,6 +5654,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sub_reg64_reg64_reg64_uxtx() {
+        disassembler_test!(
+            sub_reg64_reg64_reg64_uxtx,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                // The extend is only left out when SP is involved
+                if reg1 == AArch64GeneralReg::ZRSP || reg2 == AArch64GeneralReg::ZRSP {
+                    format!(
+                        "sub {}, {}, {}",
+                        reg1.capstone_string(UsesSP),
+                        reg2.capstone_string(UsesSP),
+                        reg3.capstone_string(UsesZR)
+                    )
+                } else {
+                    format!(
+                        "sub {}, {}, {}, uxtx",
+                        reg1.capstone_string(UsesSP),
+                        reg2.capstone_string(UsesSP),
+                        reg3.capstone_string(UsesZR)
+                    )
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
 
     fn test_subs_reg64_reg64_imm12() {
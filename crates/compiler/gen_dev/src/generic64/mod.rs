@@ -178,6 +178,16 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src1: GeneralReg,
         src2: GeneralReg,
     );
+    /// Like `add_reg64_reg64_reg64`, but also sets `overflow` to 1 if the signed
+    /// (or unsigned) addition overflowed, and to 0 otherwise.
+    fn add_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        overflow: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+        signed: bool,
+    );
 
     fn add_freg32_freg32_freg32(
         buf: &mut Vec<'_, u8>,
@@ -587,6 +597,15 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src1: GeneralReg,
         src2: GeneralReg,
     );
+    /// Like `imul_reg64_reg64_reg64`, but also sets `overflow` to 1 if the signed
+    /// multiplication overflowed, and to 0 otherwise.
+    fn imul_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        overflow: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
     fn umul_reg64_reg64_reg64<'a, ASM, CC>(
         buf: &mut Vec<'a, u8>,
         storage_manager: &mut StorageManager<'a, '_, GeneralReg, FloatReg, ASM, CC>,
@@ -657,6 +676,16 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src1: GeneralReg,
         src2: GeneralReg,
     );
+    /// Like `sub_reg64_reg64_reg64`, but also sets `overflow` to 1 if the signed
+    /// (or unsigned) subtraction overflowed, and to 0 otherwise.
+    fn sub_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        overflow: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+        signed: bool,
+    );
 
     fn eq_reg_reg_reg(
         buf: &mut Vec<'_, u8>,
@@ -1452,6 +1481,19 @@ impl<
         return_layout: &InLayout<'a>,
     ) {
         let function_name = match self.interner().get_repr(*num_layout) {
+            LayoutRepr::Builtin(Builtin::Int(width @ (IntWidth::I64 | IntWidth::U64))) => {
+                let signed = width.is_signed();
+
+                return self.build_int_checked(
+                    dst,
+                    src1,
+                    src2,
+                    return_layout,
+                    |buf, dst, overflow, src1, src2| {
+                        ASM::add_overflow_reg64_reg64_reg64(buf, dst, overflow, src1, src2, signed)
+                    },
+                );
+            }
             LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::NUM_ADD_CHECKED_INT[width],
             LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::NUM_ADD_CHECKED_FLOAT[width],
             LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_ADD_WITH_OVERFLOW,
@@ -1476,6 +1518,19 @@ impl<
         return_layout: &InLayout<'a>,
     ) {
         let function_name = match self.interner().get_repr(*num_layout) {
+            LayoutRepr::Builtin(Builtin::Int(width @ (IntWidth::I64 | IntWidth::U64))) => {
+                let signed = width.is_signed();
+
+                return self.build_int_checked(
+                    dst,
+                    src1,
+                    src2,
+                    return_layout,
+                    |buf, dst, overflow, src1, src2| {
+                        ASM::sub_overflow_reg64_reg64_reg64(buf, dst, overflow, src1, src2, signed)
+                    },
+                );
+            }
             LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::NUM_SUB_CHECKED_INT[width],
             LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::NUM_SUB_CHECKED_FLOAT[width],
             LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_SUB_WITH_OVERFLOW,
@@ -1639,6 +1694,15 @@ impl<
         return_layout: &InLayout<'a>,
     ) {
         let function_name = match self.interner().get_repr(*num_layout) {
+            LayoutRepr::Builtin(Builtin::Int(IntWidth::I64)) => {
+                return self.build_int_checked(
+                    dst,
+                    src1,
+                    src2,
+                    return_layout,
+                    ASM::imul_overflow_reg64_reg64_reg64,
+                );
+            }
             LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::NUM_MUL_CHECKED_INT[width],
             LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::NUM_MUL_CHECKED_FLOAT[width],
             LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_MUL_WITH_OVERFLOW,
@@ -5074,6 +5138,52 @@ impl<
         }
     }
 
+    /// Builds the `{ a : Num *, b : Bool }` result of a checked 64-bit integer operation
+    /// directly from the overflow flag, rather than calling into the zig builtins.
+    fn build_int_checked(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        return_layout: &InLayout<'a>,
+        op: impl FnOnce(&mut Vec<'a, u8>, GeneralReg, GeneralReg, GeneralReg, GeneralReg),
+    ) {
+        let value = self.debug_symbol("checked_value");
+        let overflowed = self.debug_symbol("checked_overflowed");
+
+        let value_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &value);
+        let overflow_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &overflowed);
+        let src1_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, src1);
+        let src2_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, src2);
+
+        op(&mut self.buf, value_reg, overflow_reg, src1_reg, src2_reg);
+
+        // the fields are sorted by alignment, so the Bool normally comes last
+        let fields = match self.layout_interner.get_repr(*return_layout) {
+            LayoutRepr::Struct([first, _]) if *first == Layout::BOOL => [overflowed, value],
+            _ => [value, overflowed],
+        };
+
+        self.storage_manager.create_struct(
+            self.layout_interner,
+            &mut self.buf,
+            dst,
+            return_layout,
+            self.env.arena.alloc(fields),
+        );
+
+        self.free_symbol(&value);
+        self.free_symbol(&overflowed);
+    }
+
     fn num_to_dec(&mut self, dst: &Symbol, src: &Symbol, arg_layout: &InLayout<'a>) {
         match self.layout_interner.get_repr(*arg_layout) {
             LayoutRepr::Builtin(Builtin::Int(int_width)) => {
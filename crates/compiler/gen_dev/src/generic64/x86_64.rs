@@ -1992,6 +1992,24 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         binop_move_src_to_dst_reg64(buf, add_reg64_reg64, dst, src1, src2)
     }
 
+    fn add_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: Reg64,
+        overflow: Reg64,
+        src1: Reg64,
+        src2: Reg64,
+        signed: bool,
+    ) {
+        binop_move_src_to_dst_reg64(buf, add_reg64_reg64, dst, src1, src2);
+
+        // OF is set on signed overflow, CF on unsigned overflow
+        if signed {
+            seto_reg64(buf, overflow);
+        } else {
+            setb_reg64(buf, overflow);
+        }
+    }
+
     #[inline(always)]
     fn add_freg32_freg32_freg32(
         buf: &mut Vec<'_, u8>,
@@ -2112,6 +2130,18 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         imul_reg64_reg64(buf, dst, src2);
     }
 
+    fn imul_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        overflow: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        mov_reg64_reg64(buf, dst, src1);
+        imul_reg64_reg64(buf, dst, src2);
+        seto_reg64(buf, overflow);
+    }
+
     fn umul_reg64_reg64_reg64<'a, ASM, CC>(
         buf: &mut Vec<'a, u8>,
         storage_manager: &mut StorageManager<'a, '_, X86_64GeneralReg, X86_64FloatReg, ASM, CC>,
@@ -2623,6 +2653,25 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         sub_reg64_reg64(buf, dst, src2);
     }
 
+    fn sub_overflow_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        overflow: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+        signed: bool,
+    ) {
+        mov_reg64_reg64(buf, dst, src1);
+        sub_reg64_reg64(buf, dst, src2);
+
+        // OF is set on signed overflow, CF on unsigned borrow
+        if signed {
+            seto_reg64(buf, overflow);
+        } else {
+            setb_reg64(buf, overflow);
+        }
+    }
+
     #[inline(always)]
     fn eq_reg_reg_reg(
         buf: &mut Vec<'_, u8>,
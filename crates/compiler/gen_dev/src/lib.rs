@@ -15,6 +15,7 @@ use roc_module::ident::ModuleName;
 use roc_module::low_level::{LowLevel, LowLevelWrapperType};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::{CallerProc, CodeGenHelp};
+use roc_mono::debug_info::DebugLocations;
 use roc_mono::ir::{
     BranchInfo, CallType, CrashTag, Expr, HigherOrderLowLevel, JoinPointId, ListLiteralElement,
     Literal, ModifyRc, Param, Proc, ProcLayout, SelfRecursive, Stmt,
@@ -25,9 +26,10 @@ use roc_mono::layout::{
 };
 use roc_mono::list_element_layout;
 
+mod debug_info;
 mod generic64;
 mod object_builder;
-pub use object_builder::build_module;
+pub use object_builder::{build_module, define_app_name, define_build_info};
 use roc_target::Target;
 mod run_roc;
 
@@ -73,6 +75,7 @@ pub struct Env<'a> {
     pub exposed_to_host: MutSet<Symbol>,
     pub lazy_literals: bool,
     pub mode: AssemblyBackendMode,
+    pub debug_locations: DebugLocations,
 }
 
 // These relocations likely will need a length.
@@ -1686,6 +1689,31 @@ trait Backend<'a> {
                 arg_layouts,
                 ret_layout,
             ),
+            LowLevel::JsonSkipWhitespace => self.build_fn_call(
+                sym,
+                bitcode::JSON_SKIP_WHITESPACE.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::JsonValueEnd => self.build_fn_call(
+                sym,
+                bitcode::JSON_VALUE_END.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::InspectMatchesSnapshot => {
+                // Only `roc test` checks snapshots, and it uses the LLVM backend
+                self.load_literal(sym, &Layout::BOOL, &Literal::Bool(true))
+            }
+            LowLevel::StrGraphemeEnd => self.build_fn_call(
+                sym,
+                bitcode::STR_GRAPHEME_END.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
             LowLevel::StrToNum => {
                 let number_layout = match self.interner().get_repr(*ret_layout) {
                     LayoutRepr::Struct(field_layouts) => field_layouts[0], // TODO: why is it sometimes a struct?
@@ -1819,6 +1847,19 @@ trait Backend<'a> {
 
                 self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
             }
+            LowLevel::NumToStrWith => {
+                let arg_layout = arg_layouts[1];
+                let intrinsic = match self.interner().get_repr(arg_layout) {
+                    LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::STR_FROM_INT_WITH[width],
+                    LayoutRepr::Builtin(Builtin::Float(width)) => {
+                        &bitcode::STR_FROM_FLOAT_WITH[width]
+                    }
+                    LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_TO_STR_WITH,
+                    x => internal_error!("NumToStrWith is not defined for {:?}", x),
+                };
+
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
             LowLevel::StrIsEmpty => {
                 let intrinsic = bitcode::STR_IS_EMPTY.to_string();
                 self.build_fn_call(sym, intrinsic, args, arg_layouts, ret_layout);
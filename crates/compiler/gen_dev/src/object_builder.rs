@@ -1,3 +1,4 @@
+use crate::debug_info::{add_debug_info, DebugProc};
 use crate::generic64::{aarch64, new_backend_64bit, x86_64};
 use crate::{AssemblyBackendMode, Backend, Env, Relocation};
 use bumpalo::collections::Vec;
@@ -11,6 +12,9 @@ use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_module::symbol;
 use roc_module::symbol::Interns;
+use roc_mono::app_name::{app_name_bytes, APP_NAME_SYMBOL};
+use roc_mono::build_info::{BuildInfo, BUILD_INFO_SYMBOL};
+use roc_mono::effects::{used_effects, used_effects_bytes, USED_EFFECTS_SYMBOL};
 use roc_mono::ir::{Call, CallSpecId, Expr, UpdateModeId};
 use roc_mono::ir::{Proc, ProcLayout, Stmt};
 use roc_mono::layout::{LambdaName, Layout, LayoutIds, LayoutInterner, STLayoutInterner};
@@ -425,6 +429,43 @@ fn create_relocation(target: Target, symbol: SymbolId, offset: u64) -> write::Re
     }
 }
 
+/// Lets the host check which of its effects the app uses (see [roc_mono::effects])
+fn define_used_effects(output: &mut Object, effects: &[String]) -> SymbolId {
+    define_bytes(output, USED_EFFECTS_SYMBOL, &used_effects_bytes(effects))
+}
+
+/// Lets the host know what the app is called (see [roc_mono::app_name]). Only executables (and
+/// libraries) do this, since they're the only ones with a host.
+pub fn define_app_name(output: &mut Object, app_name: &str) -> SymbolId {
+    define_bytes(output, APP_NAME_SYMBOL, &app_name_bytes(app_name))
+}
+
+/// Records how the app was built (see [roc_mono::build_info]). Like [define_app_name], only
+/// executables (and libraries) do this.
+pub fn define_build_info(output: &mut Object, build_info: &BuildInfo) -> SymbolId {
+    define_bytes(output, BUILD_INFO_SYMBOL, &build_info.to_bytes())
+}
+
+fn define_bytes(output: &mut Object, symbol_name: &str, bytes: &[u8]) -> SymbolId {
+    let rodata_section = output.section_id(StandardSection::ReadOnlyData);
+
+    let symbol = Symbol {
+        name: symbol_name.as_bytes().to_vec(),
+        value: 0,
+        size: bytes.len() as u64,
+        kind: SymbolKind::Data,
+        scope: SymbolScope::Dynamic,
+        weak: false,
+        section: SymbolSection::Section(rodata_section),
+        flags: SymbolFlags::None,
+    };
+
+    let symbol_id = output.add_symbol(symbol);
+    output.add_symbol_data(symbol_id, rodata_section, bytes, 1);
+
+    symbol_id
+}
+
 fn build_object<'a, B: Backend<'a>>(
     procedures: MutMap<(symbol::Symbol, ProcLayout<'a>), Proc<'a>>,
     mut backend: B,
@@ -432,6 +473,8 @@ fn build_object<'a, B: Backend<'a>>(
 ) -> Object<'a> {
     let data_section = output.section_id(StandardSection::Data);
 
+    define_used_effects(&mut output, &used_effects(procedures.values()));
+
     let arena = backend.env().arena;
 
     /*
@@ -623,7 +666,14 @@ fn build_object<'a, B: Backend<'a>>(
 
     // Build procedures from user code
     let mut relocations = bumpalo::vec![in arena];
+    let mut debug_procs = std::vec::Vec::with_capacity(procs.len());
     for (fn_name, section_id, proc_id, proc) in procs {
+        debug_procs.push(DebugProc {
+            symbol: proc.name.name(),
+            fn_name: fn_name.clone(),
+            proc_id,
+        });
+
         build_proc(
             &mut output,
             &mut backend,
@@ -735,6 +785,15 @@ fn build_object<'a, B: Backend<'a>>(
             Err(e) => internal_error!("{:?}", e),
         }
     }
+
+    // Debug info for user code, now that the size of every procedure is known
+    add_debug_info(
+        &mut output,
+        &backend.env().debug_locations,
+        backend.env().module_id,
+        &debug_procs,
+    );
+
     output
 }
 
@@ -0,0 +1,236 @@
+//! Backtraces of Roc procs in the messages of runtime errors, like a `crash` or a division by 0.
+//!
+//! In dev builds, every function in the app gets a frame pointer, and the app gets a table of its
+//! functions with the names of the Roc procs among them. When a runtime error happens, a builtin
+//! follows the frame pointers and looks up each return address in that table.
+//!
+//! The table only holds offsets relative to its own start, so it needs no absolute relocations:
+//! those would keep the surgical linker from linking the app.
+use inkwell::attributes::AttributeLoc;
+use inkwell::module::Linkage;
+use inkwell::values::{BasicValueEnum, FunctionValue, GlobalValue, PointerValue};
+use roc_builtins::bitcode;
+use roc_collections::all::MutMap;
+use roc_module::symbol::Symbol;
+use roc_target::{Architecture, OperatingSystem};
+use std::ffi::CStr;
+
+use super::bitcode::{call_str_bitcode_fn, BitcodeReturns};
+use super::build::{add_func, BuilderExt, Env, FunctionSpec, LlvmBackendMode};
+
+const PROCS: &str = "roc_backtrace_procs";
+const PROCS_LEN: &str = "roc_backtrace_procs_len";
+const END: &str = "roc_backtrace_end";
+
+/// The kinds of functions in the table; these match `BacktraceProc.Kind` in backtrace.zig.
+const KIND_FUNCTION: u64 = 0;
+const KIND_ENTRY_POINT: u64 = 1;
+const KIND_END: u64 = 2;
+
+/// Only dev builds get backtraces, because they need a frame pointer in every function. Tests
+/// and the REPL catch runtime errors themselves, and following frame pointers only works on
+/// targets that have a stack we can read.
+pub(crate) fn backtraces_enabled(env: &Env<'_, '_, '_>) -> bool {
+    matches!(env.mode, LlvmBackendMode::BinaryDev)
+        && matches!(
+            env.target.architecture(),
+            Architecture::X86_64 | Architecture::Aarch64
+        )
+}
+
+/// Appends a backtrace of the Roc procs on the stack to a runtime error's message.
+pub(crate) fn with_backtrace<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    message: BasicValueEnum<'ctx>,
+) -> BasicValueEnum<'ctx> {
+    let builder = env.builder;
+
+    let (procs, procs_len) = table_globals(env);
+    let procs_len = builder.new_build_load(
+        env.ptr_int(),
+        procs_len.as_pointer_value(),
+        "backtrace_procs_len",
+    );
+
+    call_str_bitcode_fn(
+        env,
+        &[message],
+        &[procs.as_pointer_value().into(), procs_len],
+        BitcodeReturns::Str,
+        bitcode::UTILS_WITH_BACKTRACE,
+    )
+}
+
+/// Fills in the table that backtraces look up return addresses in, and gives every function a
+/// frame pointer to follow. This goes after all the functions of the app are built.
+pub(crate) fn add_backtrace_table<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    procs: &[(FunctionValue<'ctx>, Symbol)],
+) {
+    // Nothing in the app can fail at runtime, so there's nothing to look up
+    let placeholder = match env.module.get_global(PROCS) {
+        Some(placeholder) => placeholder,
+        None => return,
+    };
+
+    let ctx = env.context;
+    let i32_type = ctx.i32_type();
+    let entry_type = ctx.struct_type(
+        &[
+            i32_type.into(),
+            i32_type.into(),
+            i32_type.into(),
+            ctx.i8_type().into(),
+        ],
+        false,
+    );
+
+    let frame_pointer_attr = ctx.create_string_attribute("frame-pointer", "all");
+    let proc_symbols: MutMap<&CStr, Symbol> = procs
+        .iter()
+        .map(|(function, symbol)| (function.get_name(), *symbol))
+        .collect();
+
+    let mut functions = Vec::new();
+
+    for function in env.module.get_functions() {
+        if function.count_basic_blocks() == 0 {
+            continue;
+        }
+
+        function.add_attribute(AttributeLoc::Function, frame_pointer_attr);
+
+        let symbol = proc_symbols.get(function.get_name()).copied();
+
+        // Everything the host calls is exposed with this prefix
+        let is_entry_point = function.get_linkage() == Linkage::External
+            && function.get_name().to_bytes().starts_with(b"roc__");
+
+        // Listing a builtin that nothing calls would keep it from being removed
+        if symbol.is_none() && !is_entry_point && function.get_first_use().is_none() {
+            continue;
+        }
+
+        let kind = if is_entry_point {
+            KIND_ENTRY_POINT
+        } else {
+            KIND_FUNCTION
+        };
+
+        functions.push((function, symbol, kind));
+    }
+
+    // Functions are emitted in the order they're in the module, so this one comes right after
+    // all the others, and tells return addresses in the app apart from those past its end.
+    let end = add_end_marker(env);
+    functions.push((end, None, KIND_END));
+
+    let table_type = entry_type.array_type(functions.len() as u32);
+    let table = env
+        .module
+        .add_global(table_type, None, "roc_backtrace_table");
+
+    let offset_in_table = |pointer: PointerValue<'ctx>| {
+        pointer
+            .const_to_int(env.ptr_int())
+            .const_sub(table.as_pointer_value().const_to_int(env.ptr_int()))
+            .const_truncate(i32_type)
+    };
+
+    let entries: Vec<_> = functions
+        .into_iter()
+        .map(|(function, symbol, kind)| {
+            let (name_offset, name_len) = match symbol {
+                Some(symbol) => {
+                    let name = format!(
+                        "{}.{}",
+                        &**symbol.module_string(&env.interns),
+                        symbol.as_str(&env.interns)
+                    );
+                    let bytes = ctx.const_string(name.as_bytes(), false);
+                    let global = env.module.add_global(bytes.get_type(), None, "proc_name");
+                    global.set_initializer(&bytes);
+                    global.set_linkage(Linkage::Private);
+                    global.set_constant(true);
+
+                    (
+                        offset_in_table(global.as_pointer_value()),
+                        i32_type.const_int(name.len() as u64, false),
+                    )
+                }
+                None => (i32_type.const_zero(), i32_type.const_zero()),
+            };
+
+            entry_type.const_named_struct(&[
+                offset_in_table(function.as_global_value().as_pointer_value()).into(),
+                name_offset.into(),
+                name_len.into(),
+                ctx.i8_type().const_int(kind, false).into(),
+            ])
+        })
+        .collect();
+
+    table.set_initializer(&entry_type.const_array(&entries));
+    table.set_linkage(Linkage::Internal);
+    table.set_constant(true);
+
+    // The offsets of the entry points, which the host links against, would otherwise put the
+    // table in a section for data that needs relocating, which the surgical linker doesn't copy.
+    if env.target.operating_system() == OperatingSystem::Linux {
+        table.set_section(Some(".rodata.roc_backtrace"));
+    }
+
+    placeholder.as_pointer_value().replace_all_uses_with(
+        table
+            .as_pointer_value()
+            .const_cast(placeholder.as_pointer_value().get_type()),
+    );
+    unsafe { placeholder.delete() };
+
+    let (_, procs_len) = table_globals(env);
+    procs_len.set_initializer(&env.ptr_int().const_int(entries.len() as u64, false));
+    procs_len.set_constant(true);
+}
+
+/// An empty function that marks the end of the app's code
+fn add_end_marker<'ctx>(env: &Env<'_, 'ctx, '_>) -> FunctionValue<'ctx> {
+    let fn_val = add_func(
+        env.context,
+        env.module,
+        END,
+        FunctionSpec::known_fastcc(env.context.void_type().fn_type(&[], false)),
+        Linkage::Private,
+    );
+
+    let entry = env.context.append_basic_block(fn_val, "entry");
+    let builder = env.context.create_builder();
+    builder.position_at_end(entry);
+    builder.new_build_return(None);
+
+    fn_val
+}
+
+/// Stand-ins for the table and its length, which are only known once all the functions are
+/// built. The table's stand-in is replaced with the table itself then.
+fn table_globals<'ctx>(env: &Env<'_, 'ctx, '_>) -> (GlobalValue<'ctx>, GlobalValue<'ctx>) {
+    let procs = env.module.get_global(PROCS).unwrap_or_else(|| {
+        let i8_type = env.context.i8_type();
+        let global = env.module.add_global(i8_type, None, PROCS);
+
+        global.set_initializer(&i8_type.const_zero());
+        global.set_linkage(Linkage::Internal);
+
+        global
+    });
+
+    let procs_len = env.module.get_global(PROCS_LEN).unwrap_or_else(|| {
+        let global = env.module.add_global(env.ptr_int(), None, PROCS_LEN);
+
+        global.set_initializer(&env.ptr_int().const_zero());
+        global.set_linkage(Linkage::Internal);
+
+        global
+    });
+
+    (procs, procs_len)
+}
@@ -1,3 +1,4 @@
+use crate::llvm::backtrace::{add_backtrace_table, backtraces_enabled, with_backtrace};
 use crate::llvm::bitcode::call_bitcode_fn;
 use crate::llvm::build_list::{self, allocate_list, empty_polymorphic_list};
 use crate::llvm::convert::{
@@ -8,6 +9,7 @@ use crate::llvm::memcpy::build_memcpy;
 use crate::llvm::refcounting::{
     build_reset, decrement_refcount_layout, increment_refcount_layout, PointerToRefcount,
 };
+use crate::llvm::stack_check::{add_stack_probes, build_stack_check};
 use crate::llvm::struct_::{struct_from_fields, RocStruct};
 use crate::llvm::{erased, fn_ptr};
 use bumpalo::collections::Vec;
@@ -17,7 +19,7 @@ use inkwell::basic_block::BasicBlock;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
 use inkwell::debug_info::{
-    AsDIScope, DICompileUnit, DIFlagsConstants, DISubprogram, DebugInfoBuilder,
+    AsDIScope, DICompileUnit, DIFile, DIFlagsConstants, DISubprogram, DebugInfoBuilder,
 };
 use inkwell::memory_buffer::MemoryBuffer;
 use inkwell::module::{Linkage, Module};
@@ -28,8 +30,8 @@ use inkwell::types::{
 };
 use inkwell::values::{
     BasicMetadataValueEnum, BasicValue, BasicValueEnum, CallSiteValue, FloatMathValue,
-    FunctionValue, InstructionOpcode, InstructionValue, IntMathValue, IntValue, PhiValue,
-    PointerMathValue, PointerValue, StructValue,
+    FunctionValue, GlobalValue, InstructionOpcode, InstructionValue, IntMathValue, IntValue,
+    PhiValue, PointerMathValue, PointerValue, StructValue,
 };
 use inkwell::{AddressSpace, IntPredicate};
 use inkwell::{FloatPredicate, OptimizationLevel};
@@ -43,14 +45,22 @@ use roc_debug_flags::dbg_do;
 use roc_debug_flags::ROC_PRINT_LLVM_FN_VERIFICATION;
 use roc_error_macros::{internal_error, todo_lambda_erasure};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_mono::app_name::{app_name_bytes, APP_NAME_SYMBOL};
+use roc_mono::build_info::{BuildInfo, BUILD_INFO_SYMBOL};
+use roc_mono::debug_info::DebugLocations;
+use roc_mono::effects::{used_effects, used_effects_bytes, USED_EFFECTS_SYMBOL};
+use roc_mono::expect_fixtures::{expect_fixtures, RESET_EXPECT_FIXTURES_SYMBOL};
+use roc_mono::inline_hints::{inline_hints, InlineHint};
 use roc_mono::ir::{
     BranchInfo, CallType, CrashTag, EntryPoint, GlueLayouts, HostExposedLambdaSet,
     HostExposedLambdaSets, ListLiteralElement, ModifyRc, OptLevel, ProcLayout, SingleEntryPoint,
+    UpdateModeId,
 };
 use roc_mono::layout::{
     Builtin, InLayout, LambdaName, LambdaSet, Layout, LayoutIds, LayoutInterner, LayoutRepr, Niche,
     RawFunctionLayout, STLayoutInterner, TagIdIntType, UnionLayout,
 };
+use roc_mono::stack_checks::recursive_procs;
 use roc_std::RocDec;
 use roc_target::{PtrWidth, Target};
 use std::convert::TryInto;
@@ -739,6 +749,24 @@ pub struct Env<'a, 'ctx, 'env> {
     pub target: Target,
     pub mode: LlvmBackendMode,
     pub exposed_to_host: MutSet<Symbol>,
+    pub debug_locations: DebugLocations,
+    /// The largest proc (in mono statements) that gets an inlining hint; 0 turns hints off
+    pub inline_threshold: u32,
+    pub alias_analysis: AliasAnalysisOptions,
+    /// Check for stack overflows in recursive procs. The checks need thread-local storage and
+    /// the C library, so this is only for apps that a system linker links.
+    pub stack_checks: bool,
+}
+
+/// How to use the results of alias analysis, which finds the list and union updates that can
+/// happen in place without checking at runtime whether the value is unique.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AliasAnalysisOptions {
+    /// Ignore the results, so every update checks for uniqueness at runtime. This is an escape
+    /// hatch for when the analysis is suspected to be wrong.
+    pub disabled: bool,
+    /// Print, per function, which updates the analysis proved can happen in place
+    pub report: bool,
 }
 
 impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
@@ -926,6 +954,58 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
         call.set_call_convention(C_CALL_CONV);
     }
 
+    /// Calls [`ROC_MATCHES_SNAPSHOT`](super::externs::ROC_MATCHES_SNAPSHOT), which `roc test`
+    /// defines, see [`route_snapshots_through_hook`](super::externs::route_snapshots_through_hook).
+    pub fn call_matches_snapshot(
+        &self,
+        env: &Env<'a, 'ctx, 'env>,
+        name: BasicValueEnum<'ctx>,
+        actual: BasicValueEnum<'ctx>,
+    ) -> IntValue<'ctx> {
+        use super::externs::ROC_MATCHES_SNAPSHOT;
+
+        let name = self.string_to_arg(env, name);
+        let actual = self.string_to_arg(env, actual);
+
+        let function = match self.module.get_function(ROC_MATCHES_SNAPSHOT) {
+            Some(function) => function,
+            None => {
+                // Returns a u8 rather than an i1, like a C bool
+                let fn_type = self
+                    .context
+                    .i8_type()
+                    .fn_type(&[name.get_type().into(), actual.get_type().into()], false);
+
+                let function = self.module.add_function(
+                    ROC_MATCHES_SNAPSHOT,
+                    fn_type,
+                    Some(Linkage::External),
+                );
+
+                function.set_call_conventions(C_CALL_CONV);
+
+                function
+            }
+        };
+
+        let call = self.builder.new_build_call(
+            function,
+            &[name.into(), actual.into()],
+            "roc_matches_snapshot",
+        );
+
+        call.set_call_convention(C_CALL_CONV);
+
+        let matched = call.try_as_basic_value().left().unwrap().into_int_value();
+
+        self.builder.new_build_int_compare(
+            IntPredicate::NE,
+            matched,
+            self.context.i8_type().const_zero(),
+            "matched_to_bool",
+        )
+    }
+
     fn string_to_arg(
         &self,
         env: &Env<'a, 'ctx, 'env>,
@@ -974,6 +1054,30 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
     }
 
     pub fn new_subprogram(&self, function_name: &str) -> DISubprogram<'ctx> {
+        self.new_subprogram_at(function_name, self.compile_unit.get_file(), 0)
+    }
+
+    /// A subprogram for the procedure generated for `symbol`, which points at the Roc file
+    /// and line the def starts on when we know them.
+    pub fn new_proc_subprogram(&self, symbol: Symbol, function_name: &str) -> DISubprogram<'ctx> {
+        match self.debug_locations.get(symbol) {
+            Some((path, line)) => {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let directory = path.parent().unwrap_or(Path::new(".")).to_string_lossy();
+                let file = self.dibuilder.create_file(&file_name, &directory);
+
+                self.new_subprogram_at(function_name, file, line)
+            }
+            None => self.new_subprogram(function_name),
+        }
+    }
+
+    fn new_subprogram_at(
+        &self,
+        function_name: &str,
+        file: DIFile<'ctx>,
+        line: u32,
+    ) -> DISubprogram<'ctx> {
         let dibuilder = self.dibuilder;
         let compile_unit = self.compile_unit;
 
@@ -994,15 +1098,15 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
         );
 
         dibuilder.create_function(
-            /* scope */ compile_unit.get_file().as_debug_info_scope(),
+            /* scope */ file.as_debug_info_scope(),
             /* func name */ function_name,
             /* linkage_name */ None,
-            /* file */ compile_unit.get_file(),
-            /* line_no */ 0,
+            /* file */ file,
+            /* line_no */ line,
             /* DIType */ subroutine_type,
             /* is_local_to_unit */ true,
             /* is_definition */ true,
-            /* scope_line */ 0,
+            /* scope_line */ line,
             /* flags */ inkwell::debug_info::DIFlags::PUBLIC,
             /* is_optimized */ false,
         )
@@ -1020,7 +1124,7 @@ pub fn module_from_builtins<'ctx>(
         include_bytes!("../../../builtins/bitcode/zig-out/builtins-host.bc")
     } else {
         match target {
-            Target::Wasm32 => {
+            Target::Wasm32 | Target::Wasm32Wasi => {
                 include_bytes!("../../../builtins/bitcode/zig-out/builtins-wasm32.bc")
             }
             Target::LinuxX32 => {
@@ -1552,11 +1656,7 @@ pub(crate) fn build_exp_call<'a, 'ctx>(
         }
 
         CallType::LowLevel { op, update_mode } => {
-            let bytes = update_mode.to_bytes();
-            let update_var = UpdateModeVar(&bytes);
-            let update_mode = func_spec_solutions
-                .update_mode(update_var)
-                .unwrap_or(UpdateMode::Immutable);
+            let update_mode = resolve_update_mode(env, func_spec_solutions, *update_mode);
 
             crate::llvm::lowlevel::run_low_level(
                 env,
@@ -1722,11 +1822,7 @@ pub(crate) fn build_exp_expr<'a, 'ctx>(
             symbol,
             update_mode,
         } => {
-            let bytes = update_mode.to_bytes();
-            let update_var = UpdateModeVar(&bytes);
-            let update_mode = func_spec_solutions
-                .update_mode(update_var)
-                .unwrap_or(UpdateMode::Immutable);
+            let update_mode = resolve_update_mode(env, func_spec_solutions, *update_mode);
 
             let (tag_ptr, layout) = scope.load_symbol_and_layout(symbol);
             let tag_ptr = tag_ptr.into_pointer_value();
@@ -1820,11 +1916,7 @@ pub(crate) fn build_exp_expr<'a, 'ctx>(
             symbol,
             update_mode,
         } => {
-            let bytes = update_mode.to_bytes();
-            let update_var = UpdateModeVar(&bytes);
-            let update_mode = func_spec_solutions
-                .update_mode(update_var)
-                .unwrap_or(UpdateMode::Immutable);
+            let update_mode = resolve_update_mode(env, func_spec_solutions, *update_mode);
 
             let (tag_ptr, layout) = scope.load_symbol_and_layout(symbol);
             let tag_ptr = tag_ptr.into_pointer_value();
@@ -5491,11 +5583,14 @@ pub(crate) fn build_proc_headers<'a, 'r, 'ctx>(
     layout_interner: &'r STLayoutInterner<'a>,
     mod_solutions: &'a ModSolutions,
     procedures: MutMap<(Symbol, ProcLayout<'a>), roc_mono::ir::Proc<'a>>,
+    inline_hints: &MutMap<(Symbol, ProcLayout<'a>), InlineHint>,
+    stack_checked: &MutSet<(Symbol, ProcLayout<'a>)>,
     scope: &mut Scope<'a, 'ctx>,
     layout_ids: &mut LayoutIds<'a>,
     // alias_analysis_solutions: AliasAnalysisSolutions,
 ) -> std::vec::Vec<(
     roc_mono::ir::Proc<'a>,
+    bool,
     std::vec::Vec<(&'a FuncSpecSolutions, FunctionValue<'ctx>)>,
 )> {
     // Populate Procs further and get the low-level Expr from the canonical Expr
@@ -5522,6 +5617,20 @@ pub(crate) fn build_proc_headers<'a, 'r, 'ctx>(
             let fn_val =
                 build_proc_header(env, layout_interner, func_spec, symbol, &proc, layout_ids);
 
+            if let Some(hint) = inline_hints.get(&(symbol, layout)) {
+                let attribute_name = match hint {
+                    InlineHint::Always => "alwaysinline",
+                    InlineHint::Hint => "inlinehint",
+                };
+
+                let kind_id = Attribute::get_named_enum_kind_id(attribute_name);
+                debug_assert!(kind_id > 0);
+                let attr = env.context.create_enum_attribute(kind_id, 0);
+                fn_val.add_attribute(AttributeLoc::Function, attr);
+            }
+
+            add_stack_probes(env, fn_val);
+
             if proc.args.is_empty() {
                 // this is a 0-argument thunk, i.e. a top-level constant definition
                 // it must be in-scope everywhere in the module!
@@ -5532,7 +5641,9 @@ pub(crate) fn build_proc_headers<'a, 'r, 'ctx>(
 
             function_values.push((func_spec_solutions, fn_val));
         }
-        headers.push((proc, function_values));
+        let check_stack = stack_checked.contains(&(symbol, layout));
+
+        headers.push((proc, check_stack, function_values));
     }
 
     headers
@@ -5548,6 +5659,10 @@ pub fn build_procedures<'a>(
     debug_output_file: Option<&Path>,
     glue_layouts: &GlueLayouts<'a>,
 ) {
+    let used_effects = used_effects(procedures.values());
+
+    expose_used_effects(env, &used_effects);
+
     let mod_solutions = build_procedures_help(
         env,
         layout_interner,
@@ -5556,6 +5671,7 @@ pub fn build_procedures<'a>(
         host_exposed_lambda_sets,
         entry_point,
         debug_output_file,
+        &mut LayoutIds::default(),
     );
 
     let niche = Niche::NONE;
@@ -5610,6 +5726,7 @@ pub fn build_wasm_test_wrapper<'a, 'ctx>(
         vec![],
         EntryPoint::Single(entry_point),
         Some(&std::env::temp_dir().join("test.ll")),
+        &mut LayoutIds::default(),
     );
 
     promote_to_wasm_test_wrapper(
@@ -5637,6 +5754,7 @@ pub fn build_procedures_return_main<'a, 'ctx>(
         host_exposed_lambda_sets,
         EntryPoint::Single(entry_point),
         Some(&std::env::temp_dir().join("test.ll")),
+        &mut LayoutIds::default(),
     );
 
     promote_to_main_function(
@@ -5648,21 +5766,72 @@ pub fn build_procedures_return_main<'a, 'ctx>(
     )
 }
 
+/// The C functions that a test runner calls to run a top-level expect.
+#[derive(Debug, Clone, Copy)]
+pub struct ExposedExpect<'a> {
+    /// Runs the expect
+    pub name: &'a str,
+    /// Evaluates the top-level constants the expect uses, if they have not been evaluated yet
+    pub setup_name: &'a str,
+}
+
+/// The C function that a test runner calls to run a property with some arguments, see
+/// [expose_fuzz_property].
+#[derive(Debug, Clone, Copy)]
+pub struct ExposedProperty<'a> {
+    pub name: &'a str,
+    pub arguments: &'a [InLayout<'a>],
+}
+
+#[allow(clippy::type_complexity)]
 pub fn build_procedures_expose_expects<'a>(
     env: &Env<'a, '_, '_>,
     layout_interner: &STLayoutInterner<'a>,
     opt_level: OptLevel,
     expects_by_module: MutMap<ModuleId, Vec<'a, Symbol>>,
+    properties_by_module: MutMap<ModuleId, Vec<'a, Symbol>>,
     procedures: MutMap<(Symbol, ProcLayout<'a>), roc_mono::ir::Proc<'a>>,
-) -> MutMap<ModuleId, Vec<'a, &'a str>> {
+) -> (
+    MutMap<ModuleId, Vec<'a, ExposedExpect<'a>>>,
+    MutMap<ModuleId, Vec<'a, ExposedProperty<'a>>>,
+) {
     // converts Vec<Vec<Symbol>> into Vec<Symbol>
     let flattened_symbols: Vec<Symbol> =
         Vec::from_iter_in(expects_by_module.values().flatten().copied(), env.arena);
 
+    let fixtures_by_expect = expect_fixtures(&procedures, &flattened_symbols);
+
+    // a property is specialized for the layout it has when exposed to the host
+    let mut property_layouts: MutMap<Symbol, ProcLayout<'a>> = MutMap::default();
+    for (symbol, layout) in procedures.keys() {
+        if properties_by_module.values().flatten().any(|s| s == symbol) {
+            property_layouts.entry(*symbol).or_insert(*layout);
+        }
+    }
+
+    let properties = Vec::from_iter_in(
+        property_layouts
+            .iter()
+            .map(|(symbol, layout)| (*symbol, *layout)),
+        env.arena,
+    );
+
+    let mut fixtures = Vec::new_in(env.arena);
+
+    for fixture in fixtures_by_expect.values().flatten() {
+        if !fixtures.contains(fixture) {
+            fixtures.push(*fixture);
+        }
+    }
+
     let entry_point = EntryPoint::Expects {
         symbols: &flattened_symbols,
+        properties: &properties,
+        fixtures: &fixtures,
     };
 
+    let mut layout_ids = LayoutIds::default();
+
     let mod_solutions = build_procedures_help(
         env,
         layout_interner,
@@ -5671,6 +5840,15 @@ pub fn build_procedures_expose_expects<'a>(
         vec![],
         entry_point,
         Some(&std::env::temp_dir().join("test.ll")),
+        &mut layout_ids,
+    );
+
+    let fixture_forcers = memoize_expect_fixtures(
+        env,
+        layout_interner,
+        &mut layout_ids,
+        mod_solutions,
+        fixtures.iter().copied(),
     );
 
     let captures_niche = Niche::NONE;
@@ -5719,7 +5897,6 @@ pub fn build_procedures_expose_expects<'a>(
 
             let expect_name = &format!("Expect_{name}");
             let expect_name_str = env.arena.alloc_str(expect_name);
-            expect_names.push(&*expect_name_str);
 
             // Add main to the module.
             let _ = expose_function_to_host_help_c_abi(
@@ -5731,11 +5908,392 @@ pub fn build_procedures_expose_expects<'a>(
                 top_level.result,
                 &format!("Expect_{name}"),
             );
+
+            let setup_name = &format!("{name}_setup");
+            let setup_fn = add_func(
+                env.context,
+                env.module,
+                setup_name,
+                FunctionSpec::known_fastcc(roc_main_fn.get_type()),
+                Linkage::Internal,
+            );
+
+            let forcers = fixtures_by_expect[&symbol]
+                .iter()
+                .flat_map(|fixture| fixture_forcers[fixture].iter().copied());
+            build_expect_setup(env, setup_fn, forcers);
+
+            let expect_setup_name = &format!("Expect_{setup_name}");
+            let expect_setup_name_str = env.arena.alloc_str(expect_setup_name);
+
+            let _ = expose_function_to_host_help_c_abi(
+                env,
+                layout_interner,
+                setup_name,
+                setup_fn,
+                top_level.arguments,
+                top_level.result,
+                expect_setup_name,
+            );
+
+            expect_names.push(ExposedExpect {
+                name: expect_name_str,
+                setup_name: expect_setup_name_str,
+            });
         }
         expect_names_by_module.insert(module_id, expect_names);
     }
 
-    expect_names_by_module
+    let mut property_names_by_module = MutMap::default();
+
+    for (module_id, properties) in properties_by_module {
+        let mut property_names = Vec::with_capacity_in(properties.len(), env.arena);
+
+        for symbol in properties.iter().copied() {
+            let Some(proc_layout) = property_layouts.get(&symbol).copied() else {
+                internal_error!("no specialization for property {symbol:?}");
+            };
+
+            let it = proc_layout.arguments.iter().copied();
+            let func_name_bytes = roc_alias_analysis::func_name_bytes_help(
+                symbol,
+                it,
+                proc_layout.niche,
+                proc_layout.result,
+            );
+            let func_solutions = mod_solutions
+                .func_solutions(FuncName(&func_name_bytes))
+                .unwrap();
+
+            let Some(func_spec) = func_solutions.specs().next() else {
+                internal_error!("no specialization for property {symbol:?}");
+            };
+
+            let roc_fn = function_value_by_func_spec(env, FuncBorrowSpec::Some(*func_spec), symbol);
+            let name = roc_fn.get_name().to_str().unwrap();
+
+            let property_name = env.arena.alloc_str(&format!("Fuzz_{name}"));
+
+            expose_fuzz_property(env, layout_interner, roc_fn, proc_layout, property_name);
+
+            property_names.push(ExposedProperty {
+                name: property_name,
+                arguments: proc_layout.arguments,
+            });
+        }
+
+        property_names_by_module.insert(module_id, property_names);
+    }
+
+    (expect_names_by_module, property_names_by_module)
+}
+
+/// Exposes a property as a C function that takes a pointer to its arguments, one per 8-byte
+/// slot, and the pointer that the [RocCallResult](crate::run_roc::RocCallResult) of the
+/// property is written to. The test runner only calls properties whose arguments are all
+/// numbers or `Bool`s, which each fit in a slot.
+fn expose_fuzz_property<'a, 'ctx>(
+    env: &Env<'a, 'ctx, '_>,
+    layout_interner: &STLayoutInterner<'a>,
+    roc_function: FunctionValue<'ctx>,
+    proc_layout: ProcLayout<'a>,
+    c_function_name: &str,
+) -> FunctionValue<'ctx> {
+    let builder = env.builder;
+
+    let name = roc_function.get_name().to_str().unwrap();
+    let adapter_name = format!("{name}_fuzz");
+
+    let slots_type = basic_type_from_layout(env, layout_interner, LayoutRepr::OPAQUE_PTR);
+    let result_repr = layout_interner.get_repr(proc_layout.result);
+    let result_type = basic_type_from_layout(env, layout_interner, result_repr);
+
+    let adapter = add_func(
+        env.context,
+        env.module,
+        &adapter_name,
+        FunctionSpec::known_fastcc(result_type.fn_type(&[slots_type.into()], false)),
+        Linkage::Internal,
+    );
+
+    let subprogram = env.new_subprogram(&adapter_name);
+    adapter.set_subprogram(subprogram);
+
+    debug_info_init!(env, adapter);
+
+    let entry = env.context.append_basic_block(adapter, "entry");
+    builder.position_at_end(entry);
+
+    let slots = adapter.get_nth_param(0).unwrap().into_pointer_value();
+    let slot_type = env.context.i64_type();
+
+    let mut arguments = Vec::with_capacity_in(proc_layout.arguments.len(), env.arena);
+
+    for (index, layout) in proc_layout.arguments.iter().enumerate() {
+        let slot = unsafe {
+            builder.new_build_in_bounds_gep(
+                slot_type,
+                slots,
+                &[env.ptr_int().const_int(index as u64, false)],
+                "slot",
+            )
+        };
+
+        let repr = layout_interner.get_repr(*layout);
+        arguments.push(load_roc_value(env, layout_interner, repr, slot, "argument"));
+    }
+
+    let result =
+        call_direct_roc_function(env, layout_interner, roc_function, result_repr, &arguments);
+    builder.new_build_return(Some(&result));
+
+    expose_function_to_host_help_c_abi(
+        env,
+        layout_interner,
+        &adapter_name,
+        adapter,
+        &[Layout::OPAQUE_PTR],
+        proc_layout.result,
+        c_function_name,
+    )
+}
+
+/// Makes each of the `fixtures` (specializations of top-level constants) get evaluated at most
+/// once. Every call to the thunk of a fixture goes through a wrapper instead, which evaluates
+/// the thunk into a global the first time, and from then on hands out the value in that global.
+///
+/// The global holds on to one reference, so the value is never freed, and is never unique
+/// (so it's never updated in place) either. Alias analysis knows this too, because the fixtures
+/// are in the [EntryPoint::Expects].
+///
+/// Returns, per fixture, the functions that evaluate it into its global if it isn't there yet.
+/// Also exports [RESET_EXPECT_FIXTURES_SYMBOL], which forgets all the memoized values.
+fn memoize_expect_fixtures<'a, 'ctx>(
+    env: &Env<'a, 'ctx, '_>,
+    layout_interner: &STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
+    mod_solutions: &'a ModSolutions,
+    fixtures: impl IntoIterator<Item = (Symbol, ProcLayout<'a>)>,
+) -> MutMap<(Symbol, ProcLayout<'a>), std::vec::Vec<FunctionValue<'ctx>>> {
+    let mut forcers_by_fixture = MutMap::default();
+    let mut is_cached_flags = std::vec::Vec::new();
+
+    for (symbol, layout) in fixtures {
+        if forcers_by_fixture.contains_key(&(symbol, layout)) {
+            continue;
+        }
+
+        let it = layout.arguments.iter().copied();
+        let bytes =
+            roc_alias_analysis::func_name_bytes_help(symbol, it, layout.niche, layout.result);
+        let func_solutions = mod_solutions.func_solutions(FuncName(&bytes)).unwrap();
+
+        let mut forcers = std::vec::Vec::new();
+
+        for func_spec in func_solutions.specs() {
+            let thunk = function_value_by_func_spec(env, FuncBorrowSpec::Some(*func_spec), symbol);
+            let (forcer, is_cached) =
+                memoize_thunk(env, layout_interner, layout_ids, thunk, layout.result);
+
+            forcers.push(forcer);
+            is_cached_flags.push(is_cached);
+        }
+
+        forcers_by_fixture.insert((symbol, layout), forcers);
+    }
+
+    // the C function that forgets every memoized value
+    let c_function_spec = FunctionSpec::cconv(env, CCReturn::Void, None, &[]);
+    let c_function = add_func(
+        env.context,
+        env.module,
+        RESET_EXPECT_FIXTURES_SYMBOL,
+        c_function_spec,
+        Linkage::External,
+    );
+
+    let subprogram = env.new_subprogram(RESET_EXPECT_FIXTURES_SYMBOL);
+    c_function.set_subprogram(subprogram);
+
+    debug_info_init!(env, c_function);
+
+    let entry = env.context.append_basic_block(c_function, "entry");
+    env.builder.position_at_end(entry);
+
+    for is_cached in is_cached_flags {
+        env.builder.new_build_store(
+            is_cached.as_pointer_value(),
+            env.context.bool_type().const_zero(),
+        );
+    }
+
+    env.builder.new_build_return(None);
+
+    forcers_by_fixture
+}
+
+/// Memoizes a single thunk, see [memoize_expect_fixtures]. Returns the function that evaluates
+/// the thunk into its global, and the flag that says whether it has been.
+fn memoize_thunk<'a, 'ctx>(
+    env: &Env<'a, 'ctx, '_>,
+    layout_interner: &STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
+    thunk: FunctionValue<'ctx>,
+    layout: InLayout<'a>,
+) -> (FunctionValue<'ctx>, GlobalValue<'ctx>) {
+    let builder = env.builder;
+    let context = env.context;
+
+    let name = thunk.get_name().to_str().unwrap();
+    let repr = layout_interner.get_repr(layout);
+    let value_type = basic_type_from_layout(env, layout_interner, repr);
+
+    let cache = env
+        .module
+        .add_global(value_type, None, &format!("{name}_cache"));
+    cache.set_linkage(Linkage::Internal);
+    cache.set_initializer(&value_type.const_zero());
+
+    let bool_type = context.bool_type();
+    let is_cached = env
+        .module
+        .add_global(bool_type, None, &format!("{name}_is_cached"));
+    is_cached.set_linkage(Linkage::Internal);
+    is_cached.set_initializer(&bool_type.const_zero());
+
+    // The wrapper has the same type as the thunk, so it can take over all of its calls
+    let memoized_name = format!("{name}_memoized");
+    let memoized = add_func(
+        context,
+        env.module,
+        &memoized_name,
+        FunctionSpec::known_fastcc(thunk.get_type()),
+        Linkage::Internal,
+    );
+
+    thunk
+        .as_global_value()
+        .as_pointer_value()
+        .replace_all_uses_with(memoized.as_global_value().as_pointer_value());
+
+    // The forcer evaluates the thunk into the cache, unless it is there already
+    let forcer_name = format!("{name}_force");
+    let forcer = add_func(
+        context,
+        env.module,
+        &forcer_name,
+        FunctionSpec::known_fastcc(context.void_type().fn_type(&[], false)),
+        Linkage::Internal,
+    );
+
+    let subprogram = env.new_subprogram(&forcer_name);
+    forcer.set_subprogram(subprogram);
+
+    debug_info_init!(env, forcer);
+
+    let entry = context.append_basic_block(forcer, "entry");
+    let evaluate = context.append_basic_block(forcer, "evaluate");
+    let done = context.append_basic_block(forcer, "done");
+
+    builder.position_at_end(entry);
+    let cached = builder
+        .new_build_load(bool_type, is_cached.as_pointer_value(), "is_cached")
+        .into_int_value();
+    builder.new_build_conditional_branch(cached, done, evaluate);
+
+    builder.position_at_end(evaluate);
+    let value = call_direct_roc_function(env, layout_interner, thunk, repr, &[]);
+    store_roc_value(env, layout_interner, repr, cache.as_pointer_value(), value);
+    builder.new_build_store(is_cached.as_pointer_value(), bool_type.const_all_ones());
+    builder.new_build_unconditional_branch(done);
+
+    builder.position_at_end(done);
+    builder.new_build_return(None);
+
+    // The wrapper hands out a new reference to the cached value
+    let subprogram = env.new_subprogram(&memoized_name);
+    memoized.set_subprogram(subprogram);
+
+    debug_info_init!(env, memoized);
+
+    let entry = context.append_basic_block(memoized, "entry");
+    builder.position_at_end(entry);
+
+    let call = builder.new_build_call(forcer, &[], "force");
+    call.set_call_convention(FAST_CALL_CONV);
+
+    let value = if repr.is_passed_by_reference(layout_interner) {
+        cache.as_pointer_value().into()
+    } else {
+        builder.new_build_load(value_type, cache.as_pointer_value(), "cached")
+    };
+
+    increment_refcount_layout(env, layout_interner, layout_ids, 1, value, layout);
+    build_return(env, layout_interner, repr, value, memoized);
+
+    (forcer, is_cached)
+}
+
+/// Builds the body of an expect's setup function, which runs the `forcers` of the top-level
+/// constants the expect uses.
+fn build_expect_setup<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    setup_fn: FunctionValue<'ctx>,
+    forcers: impl IntoIterator<Item = FunctionValue<'ctx>>,
+) {
+    let name = setup_fn.get_name().to_str().unwrap();
+    let subprogram = env.new_subprogram(name);
+    setup_fn.set_subprogram(subprogram);
+
+    debug_info_init!(env, setup_fn);
+
+    let entry = env.context.append_basic_block(setup_fn, "entry");
+    env.builder.position_at_end(entry);
+
+    for forcer in forcers {
+        let call = env.builder.new_build_call(forcer, &[], "force");
+        call.set_call_convention(FAST_CALL_CONV);
+    }
+
+    match setup_fn.get_type().get_return_type() {
+        Some(return_type) => env
+            .builder
+            .new_build_return(Some(&return_type.const_zero())),
+        None => env.builder.new_build_return(None),
+    };
+}
+
+/// Exports the effects the app uses as [USED_EFFECTS_SYMBOL], so the host can skip setting up
+/// the ones it doesn't.
+fn expose_used_effects(env: &Env, effects: &[String]) {
+    expose_bytes(env, USED_EFFECTS_SYMBOL, &used_effects_bytes(effects));
+}
+
+/// Exports the app's name as [APP_NAME_SYMBOL], for hosts that want to show it. Only
+/// executables (and libraries) do this, since they're the only ones with a host.
+pub fn expose_app_name(env: &Env, app_name: &str) {
+    expose_bytes(env, APP_NAME_SYMBOL, &app_name_bytes(app_name));
+}
+
+/// Exports how the app was built as [BUILD_INFO_SYMBOL], so `roc inspect-binary` can tell which
+/// compiler built it. Like [expose_app_name], only executables (and libraries) do this.
+pub fn expose_build_info(env: &Env, build_info: &BuildInfo) {
+    expose_bytes(env, BUILD_INFO_SYMBOL, &build_info.to_bytes());
+}
+
+fn expose_bytes(env: &Env, symbol_name: &str, bytes: &[u8]) {
+    let i8_type = env.context.i8_type();
+    let bytes: std::vec::Vec<_> = bytes
+        .iter()
+        .map(|byte| i8_type.const_int(*byte as u64, false))
+        .collect();
+
+    let typ = i8_type.array_type(bytes.len() as u32);
+    let global = env.module.add_global(typ, None, symbol_name);
+
+    global.set_constant(true);
+    global.set_linkage(Linkage::External);
+    global.set_initializer(&i8_type.const_array(&bytes));
 }
 
 fn build_procedures_help<'a>(
@@ -5746,8 +6304,8 @@ fn build_procedures_help<'a>(
     host_exposed_lambda_sets: HostExposedLambdaSets<'a>,
     entry_point: EntryPoint<'a>,
     debug_output_file: Option<&Path>,
+    layout_ids: &mut LayoutIds<'a>,
 ) -> &'a ModSolutions {
-    let mut layout_ids = roc_mono::layout::LayoutIds::default();
     let mut scope = Scope::default();
 
     let it1 = procedures.iter().map(|x| x.1);
@@ -5771,6 +6329,25 @@ fn build_procedures_help<'a>(
         .mod_solutions(roc_alias_analysis::MOD_APP)
         .unwrap();
 
+    if env.alias_analysis.report {
+        report_alias_analysis(env, mod_solutions, &procedures);
+    }
+
+    // Development builds favor compile times over runtime performance, so they get no hints
+    let inline_hints = match opt_level {
+        OptLevel::Development => MutMap::default(),
+        OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => {
+            inline_hints(&procedures, env.inline_threshold)
+        }
+    };
+
+    // Wasm has no guard page below the stack, so there's nothing to check against
+    let stack_checked = match env.target.architecture() {
+        roc_target::Architecture::Wasm32 => MutSet::default(),
+        _ if !env.stack_checks => MutSet::default(),
+        _ => recursive_procs(&procedures),
+    };
+
     // Add all the Proc headers to the module.
     // We have to do this in a separate pass first,
     // because their bodies may reference each other.
@@ -5779,14 +6356,22 @@ fn build_procedures_help<'a>(
         layout_interner,
         mod_solutions,
         procedures,
+        &inline_hints,
+        &stack_checked,
         &mut scope,
-        &mut layout_ids,
+        layout_ids,
     );
 
     let (_, function_pass) = construct_optimization_passes(env.module, opt_level);
 
-    for (proc, fn_vals) in headers {
+    let mut backtrace_procs = std::vec::Vec::new();
+
+    for (proc, check_stack, fn_vals) in headers {
         for (func_spec_solutions, fn_val) in fn_vals {
+            if backtraces_enabled(env) {
+                backtrace_procs.push((fn_val, proc.name.name()));
+            }
+
             let mut current_scope = scope.clone();
 
             // only have top-level thunks for this proc's module in scope
@@ -5797,10 +6382,11 @@ fn build_procedures_help<'a>(
             build_proc(
                 env,
                 layout_interner,
-                &mut layout_ids,
+                layout_ids,
                 func_spec_solutions,
                 scope.clone(),
                 &proc,
+                check_stack,
                 fn_val,
             );
 
@@ -5861,9 +6447,127 @@ fn build_procedures_help<'a>(
         }
     }
 
+    if backtraces_enabled(env) {
+        add_backtrace_table(env, &backtrace_procs);
+    }
+
     mod_solutions
 }
 
+fn resolve_update_mode(
+    env: &Env,
+    func_spec_solutions: &FuncSpecSolutions,
+    update_mode: UpdateModeId,
+) -> UpdateMode {
+    if env.alias_analysis.disabled {
+        return UpdateMode::Immutable;
+    }
+
+    let bytes = update_mode.to_bytes();
+    let update_var = UpdateModeVar(&bytes);
+
+    func_spec_solutions
+        .update_mode(update_var)
+        .unwrap_or(UpdateMode::Immutable)
+}
+
+/// Prints, for every specialization of a function that updates lists or unions, which of those
+/// updates alias analysis proved can happen in place. The others check at runtime whether the
+/// value is unique, and copy it if it isn't.
+fn report_alias_analysis<'a>(
+    env: &Env<'a, '_, '_>,
+    mod_solutions: &ModSolutions,
+    procedures: &MutMap<(Symbol, ProcLayout<'a>), roc_mono::ir::Proc<'a>>,
+) {
+    let mut lines = std::vec::Vec::new();
+
+    for proc in procedures.values() {
+        let update_sites = update_sites(&proc.body);
+
+        let name_bytes = roc_alias_analysis::func_name_bytes(proc);
+        let Ok(func_solutions) = mod_solutions.func_solutions(FuncName(&name_bytes)) else {
+            continue;
+        };
+
+        let symbol = proc.name.name();
+        let name = format!(
+            "{}.{}",
+            &**symbol.module_string(&env.interns),
+            symbol.as_str(&env.interns)
+        );
+        let specs: std::vec::Vec<_> = func_solutions.specs().collect();
+
+        for (index, spec) in specs.iter().enumerate() {
+            let func_spec_solutions = func_solutions.spec(spec).unwrap();
+
+            let mut in_place = 0;
+            let mut checked = std::vec::Vec::new();
+
+            for (op, update_mode) in update_sites.iter() {
+                let bytes = update_mode.to_bytes();
+
+                // Only the operations that alias analysis models as updates have a solution
+                match func_spec_solutions.update_mode(UpdateModeVar(&bytes)) {
+                    Ok(UpdateMode::InPlace) => in_place += 1,
+                    Ok(UpdateMode::Immutable) => checked.push(op.as_str()),
+                    Err(_) => {}
+                }
+            }
+
+            if in_place == 0 && checked.is_empty() {
+                continue;
+            }
+
+            let mut line = format!(
+                "{name} (specialization {} of {}): {} in place, {} checked at runtime",
+                index + 1,
+                specs.len(),
+                in_place,
+                checked.len()
+            );
+
+            if !checked.is_empty() {
+                line.push_str(&format!(" ({})", checked.join(", ")));
+            }
+
+            lines.push(line);
+        }
+    }
+
+    lines.sort();
+
+    eprintln!("── ALIAS ANALYSIS ──────────────────────────────────────────────────────────────\n");
+
+    if env.alias_analysis.disabled {
+        eprintln!("These results are not used, because alias analysis is disabled.\n");
+    }
+
+    for line in lines {
+        eprintln!("{line}");
+    }
+
+    eprintln!();
+}
+
+/// The operations in a proc body that might update a value in place, with their update mode ids
+fn update_sites(body: &roc_mono::ir::Stmt) -> std::vec::Vec<(String, UpdateModeId)> {
+    use roc_mono::ir::{Call, Expr};
+
+    let mut sites = std::vec::Vec::new();
+
+    roc_mono::inline_hints::visit_stmts(body, |expr| match expr {
+        Expr::Call(Call {
+            call_type: CallType::LowLevel { op, update_mode },
+            ..
+        }) => sites.push((format!("{op:?}"), *update_mode)),
+        Expr::Reset { update_mode, .. } => sites.push(("Reset".into(), *update_mode)),
+        Expr::ResetRef { update_mode, .. } => sites.push(("ResetRef".into(), *update_mode)),
+        _ => {}
+    });
+
+    sites
+}
+
 pub enum FuncBorrowSpec {
     /// This function has an specialization due to alias analysis.
     Some(FuncSpec),
@@ -5937,7 +6641,7 @@ fn build_proc_header<'a, 'ctx>(
         Linkage::Internal,
     );
 
-    let subprogram = env.new_subprogram(&fn_name);
+    let subprogram = env.new_proc_subprogram(symbol, &fn_name);
     fn_val.set_subprogram(subprogram);
 
     debug_info_init!(env, fn_val);
@@ -6233,6 +6937,7 @@ fn build_proc<'a, 'ctx>(
     func_spec_solutions: &FuncSpecSolutions,
     mut scope: Scope<'a, 'ctx>,
     proc: &roc_mono::ir::Proc<'a>,
+    check_stack: bool,
     fn_val: FunctionValue<'ctx>,
 ) {
     let args = proc.args;
@@ -6246,6 +6951,34 @@ fn build_proc<'a, 'ctx>(
 
     debug_info_init!(env, fn_val);
 
+    // mono IR has no regions below the def, so attribute the whole body to the def's line
+    if let Some((_, line)) = env.debug_locations.get(proc.name.name()) {
+        let subprogram = fn_val.get_subprogram().expect("subprogram");
+        let loc = env.dibuilder.create_debug_location(
+            env.context,
+            line,
+            /* column */ 0,
+            subprogram.as_debug_info_scope(),
+            /* inlined_at */ None,
+        );
+        builder.set_current_debug_location(loc);
+    }
+
+    if check_stack {
+        let symbol = proc.name.name();
+        let mut message = format!(
+            "stack overflow in {}.{}",
+            &**symbol.module_string(&env.interns),
+            symbol.as_str(&env.interns)
+        );
+
+        if let Some((path, line)) = env.debug_locations.get(symbol) {
+            message.push_str(&format!(" at {}:{}", path.display(), line));
+        }
+
+        build_stack_check(env, fn_val, &message);
+    }
+
     // Add args to scope
     for (arg_val, (layout, arg_symbol)) in fn_val.get_param_iter().zip(args) {
         arg_val.set_name(arg_symbol.as_str(&env.interns));
@@ -7067,7 +7800,11 @@ pub(crate) fn throw_exception<'a, 'ctx>(
     message: &Symbol,
     tag: CrashTag,
 ) {
-    let msg_val = scope.load_symbol(message);
+    let mut msg_val = scope.load_symbol(message);
+
+    if backtraces_enabled(env) {
+        msg_val = with_backtrace(env, msg_val);
+    }
 
     env.call_panic(env, msg_val, tag);
 
@@ -5,8 +5,10 @@ use crate::llvm::build::{CCReturn, Env, FunctionSpec};
 use crate::llvm::convert::zig_str_type;
 use inkwell::module::Linkage;
 use inkwell::types::BasicType;
+use inkwell::values::{BasicValueEnum, FunctionValue};
 use inkwell::AddressSpace;
 use roc_builtins::bitcode;
+use roc_mono::coverage::COVERAGE_HIT;
 
 use super::build::get_sjlj_buffer;
 use super::intrinsics::LLVM_LONGJMP;
@@ -210,6 +212,157 @@ pub fn add_default_roc_externs(env: &Env<'_, '_, '_>) {
     }
 }
 
+/// The globals that [`route_roc_allocators_through_hooks`] adds. Each holds a pointer to a
+/// function with the same signature as the allocator it stands in for.
+pub const ROC_ALLOC_HOOK: &str = "roc_alloc_hook";
+pub const ROC_REALLOC_HOOK: &str = "roc_realloc_hook";
+pub const ROC_DEALLOC_HOOK: &str = "roc_dealloc_hook";
+
+/// Replace the bodies of the roc_alloc, roc_realloc, and roc_dealloc that
+/// [`add_default_roc_externs`] defined, so they call whatever function the host stored in
+/// the corresponding `*_hook` global instead. This lets a host that loads the module as a
+/// dylib (like `roc test --leak-check`) see every allocation it makes.
+///
+/// The host must store all three hooks before calling into the module.
+pub fn route_roc_allocators_through_hooks(env: &Env<'_, '_, '_>) {
+    let ctx = env.context;
+    let module = env.module;
+    let builder = env.builder;
+
+    let i8_ptr_type = ctx.i8_type().ptr_type(AddressSpace::default());
+
+    for (fn_name, hook_name) in [
+        ("roc_alloc", ROC_ALLOC_HOOK),
+        ("roc_realloc", ROC_REALLOC_HOOK),
+        ("roc_dealloc", ROC_DEALLOC_HOOK),
+    ] {
+        let fn_val = module.get_function(fn_name).unwrap();
+
+        for block in fn_val.get_basic_blocks() {
+            unsafe { block.delete() }.unwrap();
+        }
+
+        let hook = module.add_global(i8_ptr_type, None, hook_name);
+        hook.set_linkage(Linkage::External);
+        hook.set_initializer(&i8_ptr_type.const_null());
+
+        let entry = ctx.append_basic_block(fn_val, "entry");
+
+        builder.position_at_end(entry);
+
+        let hook_ptr = builder
+            .new_build_load(i8_ptr_type, hook.as_pointer_value(), "load_hook")
+            .into_pointer_value();
+        let args: Vec<_> = fn_val.get_param_iter().map(|arg| arg.into()).collect();
+
+        let call = builder.new_build_indirect_call(fn_val.get_type(), hook_ptr, &args, "call_hook");
+
+        call.set_call_convention(C_CALL_CONV);
+
+        match call.try_as_basic_value().left() {
+            Some(retval) => builder.new_build_return(Some(&retval)),
+            None => builder.new_build_return(None),
+        };
+
+        if cfg!(debug_assertions) {
+            crate::llvm::build::verify_fn(fn_val);
+        }
+    }
+}
+
+/// The global that [`route_coverage_hits_through_hook`] adds. It holds a pointer to a
+/// function that takes the `u64` id of a coverage site.
+pub const ROC_COVERAGE_HOOK: &str = "roc_coverage_hook";
+
+/// Define the [`COVERAGE_HIT`] function that code compiled with coverage calls, so it calls
+/// whatever function the host stored in the [`ROC_COVERAGE_HOOK`] global, or does nothing
+/// if the host stored none. Does nothing if the module has no coverage instrumentation.
+pub fn route_coverage_hits_through_hook(env: &Env<'_, '_, '_>) {
+    if let Some(fn_val) = env.module.get_function(COVERAGE_HIT) {
+        route_through_optional_hook(env, fn_val, ROC_COVERAGE_HOOK, None);
+    }
+}
+
+/// The function that `Inspect.matchesSnapshot` calls in [`LlvmBackendMode::CliTest`]. It takes
+/// pointers to the snapshot's name and to the value's string, and returns a nonzero `u8` if
+/// they match.
+///
+/// [`LlvmBackendMode::CliTest`]: crate::llvm::build::LlvmBackendMode::CliTest
+pub const ROC_MATCHES_SNAPSHOT: &str = "roc_matches_snapshot";
+
+/// The global that [`route_snapshots_through_hook`] adds. It holds a pointer to a function
+/// with the same signature as [`ROC_MATCHES_SNAPSHOT`].
+pub const ROC_SNAPSHOT_HOOK: &str = "roc_snapshot_hook";
+
+/// Define the [`ROC_MATCHES_SNAPSHOT`] function, so it calls whatever function the host stored
+/// in the [`ROC_SNAPSHOT_HOOK`] global, or says every snapshot matches if the host stored none.
+/// Does nothing if the module never checks a snapshot.
+pub fn route_snapshots_through_hook(env: &Env<'_, '_, '_>) {
+    if let Some(fn_val) = env.module.get_function(ROC_MATCHES_SNAPSHOT) {
+        let matches = env.context.i8_type().const_int(1, false);
+
+        route_through_optional_hook(env, fn_val, ROC_SNAPSHOT_HOOK, Some(matches.into()));
+    }
+}
+
+/// Give `fn_val` a body that calls the function in a new `hook_name` global, which the host
+/// may store a function in, with the same arguments. If the host stored none, it returns
+/// `default` instead.
+fn route_through_optional_hook<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    fn_val: FunctionValue<'ctx>,
+    hook_name: &str,
+    default: Option<BasicValueEnum<'ctx>>,
+) {
+    let ctx = env.context;
+    let module = env.module;
+    let builder = env.builder;
+
+    fn_val.set_linkage(Linkage::Internal);
+
+    let i8_ptr_type = ctx.i8_type().ptr_type(AddressSpace::default());
+
+    let hook = module.add_global(i8_ptr_type, None, hook_name);
+    hook.set_linkage(Linkage::External);
+    hook.set_initializer(&i8_ptr_type.const_null());
+
+    let entry = ctx.append_basic_block(fn_val, "entry");
+    let call_block = ctx.append_basic_block(fn_val, "call_hook");
+    let default_block = ctx.append_basic_block(fn_val, "no_hook");
+
+    builder.position_at_end(entry);
+
+    let hook_ptr = builder
+        .new_build_load(i8_ptr_type, hook.as_pointer_value(), "load_hook")
+        .into_pointer_value();
+    let is_null = builder.new_build_is_null(hook_ptr, "hook_is_null");
+
+    builder.new_build_conditional_branch(is_null, default_block, call_block);
+
+    builder.position_at_end(call_block);
+
+    let args: Vec<_> = fn_val.get_param_iter().map(|arg| arg.into()).collect();
+    let call = builder.new_build_indirect_call(fn_val.get_type(), hook_ptr, &args, "call_hook");
+
+    call.set_call_convention(C_CALL_CONV);
+
+    match call.try_as_basic_value().left() {
+        Some(retval) => builder.new_build_return(Some(&retval)),
+        None => builder.new_build_return(None),
+    };
+
+    builder.position_at_end(default_block);
+
+    match default {
+        Some(retval) => builder.new_build_return(Some(&retval)),
+        None => builder.new_build_return(None),
+    };
+
+    if cfg!(debug_assertions) {
+        crate::llvm::build::verify_fn(fn_val);
+    }
+}
+
 fn unreachable_function(env: &Env, name: &str) {
     // The type of this function (but not the implementation) should have
     // already been defined by the builtins, which rely on it.
@@ -1,7 +1,7 @@
 use inkwell::{
     attributes::{Attribute, AttributeLoc},
     module::Linkage,
-    types::{BasicType, IntType},
+    types::{BasicType, FloatType, IntType},
     values::{
         BasicValue, BasicValueEnum, FloatValue, FunctionValue, InstructionOpcode, IntValue,
         StructValue,
@@ -59,7 +59,7 @@ use crate::llvm::{
 
 use super::{build::Env, convert::zig_dec_type};
 use super::{
-    build::{throw_internal_exception, use_roc_value, FAST_CALL_CONV},
+    build::{throw_internal_exception, use_roc_value, LlvmBackendMode, FAST_CALL_CONV},
     convert::zig_with_overflow_roc_dec,
     scope::Scope,
 };
@@ -782,6 +782,18 @@ pub(crate) fn run_low_level<'a, 'ctx>(
                 bitcode::STR_GET_UNSAFE,
             )
         }
+        StrGraphemeEnd => {
+            // Str.graphemeEnd : Str, U64 -> U64
+            arguments!(wrapper_struct, start_index);
+
+            call_str_bitcode_fn(
+                env,
+                &[wrapper_struct],
+                &[start_index],
+                BitcodeReturns::Basic,
+                bitcode::STR_GRAPHEME_END,
+            )
+        }
         ListGetUnsafe => {
             // List.getUnsafe : List elem, U64 -> elem
             arguments_with_layouts!((wrapper_struct, list_layout), (element_index, _l));
@@ -820,6 +832,40 @@ pub(crate) fn run_low_level<'a, 'ctx>(
                 bitcode::LIST_IS_UNIQUE,
             )
         }
+        JsonSkipWhitespace => {
+            // JsonFormat.skipWhitespace : List U8, U64 -> U64
+            arguments!(list, index);
+
+            call_list_bitcode_fn(
+                env,
+                &[list.into_struct_value()],
+                &[index],
+                BitcodeReturns::Basic,
+                bitcode::JSON_SKIP_WHITESPACE,
+            )
+        }
+        JsonValueEnd => {
+            // JsonFormat.valueEnd : List U8, U64 -> U64
+            arguments!(list, index);
+
+            call_list_bitcode_fn(
+                env,
+                &[list.into_struct_value()],
+                &[index],
+                BitcodeReturns::Basic,
+                bitcode::JSON_VALUE_END,
+            )
+        }
+        InspectMatchesSnapshot => {
+            // Inspect.matchesSnapshot : Str, Str -> Bool
+            arguments!(name, actual);
+
+            match env.mode {
+                LlvmBackendMode::CliTest => env.call_matches_snapshot(env, name, actual).into(),
+                // Only `roc test` checks snapshots
+                _ => env.context.bool_type().const_int(1, false).into(),
+            }
+        }
         ListClone => {
             // List.clone : List a -> List a
             arguments_with_layouts!((list, list_layout));
@@ -881,6 +927,36 @@ pub(crate) fn run_low_level<'a, 'ctx>(
                 _ => unreachable!(),
             }
         }
+        NumToStrWith => {
+            // Num.toStrWithLowlevel : Str, Num a, U8, U8 -> Str
+            arguments_with_layouts!(
+                (separator, _l1),
+                (num, num_layout),
+                (base, _l2),
+                (decimals, _l3)
+            );
+
+            match layout_interner.get_repr(num_layout) {
+                LayoutRepr::Builtin(Builtin::Int(int_width)) => call_str_bitcode_fn(
+                    env,
+                    &[separator],
+                    &[num, base, decimals],
+                    BitcodeReturns::Str,
+                    &bitcode::STR_FROM_INT_WITH[int_width],
+                ),
+                LayoutRepr::Builtin(Builtin::Float(float_width)) => call_str_bitcode_fn(
+                    env,
+                    &[separator],
+                    &[num, base, decimals],
+                    BitcodeReturns::Str,
+                    &bitcode::STR_FROM_FLOAT_WITH[float_width],
+                ),
+                LayoutRepr::Builtin(Builtin::Decimal) => {
+                    dec_to_str_with(env, separator, num, base, decimals)
+                }
+                _ => unreachable!(),
+            }
+        }
         NumAbs
         | NumNeg
         | NumRound
@@ -1126,52 +1202,74 @@ pub(crate) fn run_low_level<'a, 'ctx>(
         NumToFloatCast => {
             arguments_with_layouts!((arg, arg_layout));
 
-            match layout_interner.get_repr(arg_layout) {
-                LayoutRepr::Builtin(Builtin::Int(width)) => {
-                    // Converting from int to float
-                    let int_val = arg.into_int_value();
-                    let dest = basic_type_from_layout(
-                        env,
-                        layout_interner,
-                        layout_interner.get_repr(layout),
-                    )
+            let dest =
+                basic_type_from_layout(env, layout_interner, layout_interner.get_repr(layout))
                     .into_float_type();
 
-                    if width.is_signed() {
-                        env.builder
-                            .new_build_signed_int_to_float(int_val, dest, "signed_int_to_float")
-                            .into()
-                    } else {
-                        env.builder
-                            .new_build_unsigned_int_to_float(int_val, dest, "unsigned_int_to_float")
-                            .into()
-                    }
-                }
+            build_num_to_float(env, layout_interner, arg, arg_layout, dest).into()
+        }
+        NumToFloatChecked => {
+            // return_layout : Result F [OutOfBounds]* ~ { result: F, out_of_bounds: bool }
+            arguments_with_layouts!((arg, arg_layout));
+
+            use inkwell::FloatPredicate;
+
+            let return_type =
+                basic_type_from_layout(env, layout_interner, layout_interner.get_repr(layout))
+                    .into_struct_type();
+            let dest = return_type
+                .get_field_type_at_index(0)
+                .unwrap()
+                .into_float_type();
+
+            let converted = build_num_to_float(env, layout_interner, arg, arg_layout, dest);
+
+            let is_infinite = |value: FloatValue<'ctx>| {
+                let infinity = value.get_type().const_float(f64::INFINITY);
+                let negative_infinity = value.get_type().const_float(f64::NEG_INFINITY);
+                let is_positive_infinity = env.builder.new_build_float_compare(
+                    FloatPredicate::OEQ,
+                    value,
+                    infinity,
+                    "is_positive_infinity",
+                );
+                let is_negative_infinity = env.builder.new_build_float_compare(
+                    FloatPredicate::OEQ,
+                    value,
+                    negative_infinity,
+                    "is_negative_infinity",
+                );
+
+                env.builder
+                    .new_build_or(is_positive_infinity, is_negative_infinity, "is_infinite")
+            };
+
+            // It's out of bounds if a finite number became infinite
+            let out_of_bounds = match layout_interner.get_repr(arg_layout) {
                 LayoutRepr::Builtin(Builtin::Float(_)) => {
-                    // Converting from float to float - e.g. F64 to F32, or vice versa
-                    let dest = basic_type_from_layout(
-                        env,
-                        layout_interner,
-                        layout_interner.get_repr(layout),
-                    )
-                    .into_float_type();
+                    let became_infinite = is_infinite(converted);
+                    let was_infinite = is_infinite(arg.into_float_value());
 
-                    env.builder
-                        .new_build_float_cast(arg.into_float_value(), dest, "cast_float_to_float")
-                        .into()
-                }
-                LayoutRepr::Builtin(Builtin::Decimal) => {
-                    todo!("Support converting Dec values to floats.");
-                }
-                other => {
-                    unreachable!("Tried to do a float cast to non-float layout {:?}", other);
+                    env.builder.new_build_and(
+                        became_infinite,
+                        env.builder.new_build_not(was_infinite, "was_finite"),
+                        "out_of_bounds",
+                    )
                 }
-            }
-        }
-        NumToFloatChecked => {
-            // NOTE: There's a NumToIntChecked implementation above,
-            // which could be useful to look at when implementing this.
-            todo!("implement checked float conversion");
+                _ => is_infinite(converted),
+            };
+
+            let r = return_type.const_zero();
+            let r = env
+                .builder
+                .build_insert_value(r, converted, 0, "converted_float")
+                .unwrap();
+            let r = env
+                .builder
+                .build_insert_value(r, out_of_bounds, 1, "out_of_bounds")
+                .unwrap();
+
+            r.into_struct_value().into()
         }
         NumWithoutDecimalPoint | NumWithDecimalPoint => {
             // Dec uses an I128 under the hood, so no conversion is needed.
@@ -1382,6 +1480,44 @@ pub(crate) fn run_low_level<'a, 'ctx>(
     }
 }
 
+/// Converts an Int, a Frac or a Dec to a float of the `dest` type
+fn build_num_to_float<'a, 'ctx>(
+    env: &Env<'a, 'ctx, '_>,
+    layout_interner: &STLayoutInterner<'a>,
+    arg: BasicValueEnum<'ctx>,
+    arg_layout: InLayout<'a>,
+    dest: FloatType<'ctx>,
+) -> FloatValue<'ctx> {
+    match layout_interner.get_repr(arg_layout) {
+        LayoutRepr::Builtin(Builtin::Int(width)) => {
+            // Converting from int to float
+            let int_val = arg.into_int_value();
+
+            if width.is_signed() {
+                env.builder
+                    .new_build_signed_int_to_float(int_val, dest, "signed_int_to_float")
+            } else {
+                env.builder
+                    .new_build_unsigned_int_to_float(int_val, dest, "unsigned_int_to_float")
+            }
+        }
+        LayoutRepr::Builtin(Builtin::Float(_)) => {
+            // Converting from float to float - e.g. F64 to F32, or vice versa
+            env.builder
+                .new_build_float_cast(arg.into_float_value(), dest, "cast_float_to_float")
+        }
+        LayoutRepr::Builtin(Builtin::Decimal) => {
+            let f64_val = dec_unary_op(env, bitcode::DEC_TO_F64, arg).into_float_value();
+
+            env.builder
+                .new_build_float_cast(f64_val, dest, "cast_dec_to_float")
+        }
+        other => {
+            unreachable!("Tried to do a float cast to non-float layout {:?}", other);
+        }
+    }
+}
+
 fn intwidth_from_layout(layout: InLayout) -> IntWidth {
     layout.to_int_width()
 }
@@ -1992,7 +2128,7 @@ fn dec_to_str<'ctx>(env: &Env<'_, 'ctx, '_>, dec: BasicValueEnum<'ctx>) -> Basic
                 bitcode::DEC_TO_STR,
             )
         }
-        Target::Wasm32 => call_str_bitcode_fn(
+        Target::Wasm32 | Target::Wasm32Wasi => call_str_bitcode_fn(
             env,
             &[],
             &[dec.into()],
@@ -2009,6 +2145,44 @@ fn dec_to_str<'ctx>(env: &Env<'_, 'ctx, '_>, dec: BasicValueEnum<'ctx>) -> Basic
     }
 }
 
+fn dec_to_str_with<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    separator: BasicValueEnum<'ctx>,
+    dec: BasicValueEnum<'ctx>,
+    base: BasicValueEnum<'ctx>,
+    decimals: BasicValueEnum<'ctx>,
+) -> BasicValueEnum<'ctx> {
+    let dec = dec.into_int_value();
+
+    match env.target {
+        Target::LinuxX32 | Target::LinuxX64 | Target::MacX64 => {
+            let (low, high) = dec_split_into_words(env, dec);
+
+            call_str_bitcode_fn(
+                env,
+                &[separator],
+                &[low.into(), high.into(), base, decimals],
+                BitcodeReturns::Str,
+                bitcode::DEC_TO_STR_WITH,
+            )
+        }
+        Target::Wasm32 | Target::Wasm32Wasi => call_str_bitcode_fn(
+            env,
+            &[separator],
+            &[dec.into(), base, decimals],
+            BitcodeReturns::Str,
+            bitcode::DEC_TO_STR_WITH,
+        ),
+        _ => call_str_bitcode_fn(
+            env,
+            &[separator],
+            &[dec_alloca(env, dec), base, decimals],
+            BitcodeReturns::Str,
+            bitcode::DEC_TO_STR_WITH,
+        ),
+    }
+}
+
 fn dec_unary_op<'ctx>(
     env: &Env<'_, 'ctx, '_>,
     fn_name: &str,
@@ -2020,7 +2194,7 @@ fn dec_unary_op<'ctx>(
             let (low, high) = dec_split_into_words(env, dec);
             call_bitcode_fn(env, &[low.into(), high.into()], fn_name)
         }
-        Target::Wasm32 => call_bitcode_fn(env, &[dec.into()], fn_name),
+        Target::Wasm32 | Target::Wasm32Wasi => call_bitcode_fn(env, &[dec.into()], fn_name),
         _ => call_bitcode_fn(env, &[dec_alloca(env, dec)], fn_name),
     }
 }
@@ -2055,7 +2229,9 @@ fn dec_binary_op<'ctx>(
                 .build_load(env.context.i128_type(), ptr, "to_i128")
                 .unwrap()
         }
-        Target::Wasm32 => call_bitcode_fn(env, &[dec1.into(), dec2.into()], fn_name),
+        Target::Wasm32 | Target::Wasm32Wasi => {
+            call_bitcode_fn(env, &[dec1.into(), dec2.into()], fn_name)
+        }
         _ => call_bitcode_fn(
             env,
             &[dec_alloca(env, dec1), dec_alloca(env, dec2)],
@@ -2092,7 +2268,7 @@ fn dec_binop_with_overflow<'ctx>(
                 fn_name,
             );
         }
-        Target::Wasm32 => {
+        Target::Wasm32 | Target::Wasm32Wasi => {
             call_void_bitcode_fn(
                 env,
                 &[return_alloca.into(), lhs.into(), rhs.into()],
@@ -2141,7 +2317,9 @@ pub(crate) fn dec_binop_with_unchecked<'ctx>(
                 fn_name,
             )
         }
-        Target::Wasm32 => call_bitcode_fn(env, &[lhs.into(), rhs.into()], fn_name),
+        Target::Wasm32 | Target::Wasm32Wasi => {
+            call_bitcode_fn(env, &[lhs.into(), rhs.into()], fn_name)
+        }
         _ => call_bitcode_fn(env, &[dec_alloca(env, lhs), dec_alloca(env, rhs)], fn_name),
     }
 }
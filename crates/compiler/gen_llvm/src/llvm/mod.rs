@@ -11,8 +11,10 @@ mod lowlevel;
 pub mod refcounting;
 
 mod align;
+mod backtrace;
 mod erased;
 mod fn_ptr;
 mod memcpy;
 mod scope;
+mod stack_check;
 mod struct_;
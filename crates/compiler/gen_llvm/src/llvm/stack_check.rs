@@ -0,0 +1,149 @@
+//! Checks at the start of recursive procs that there is stack left, so running out of it is
+//! reported through `roc_panic` rather than as a segfault.
+//!
+//! Each thread gets a stack limit the first time it runs a check, worked out from where its
+//! stack really is. Until then the limit is the highest address, so the first check always
+//! takes the slow path, which sets it.
+//!
+//! The limit is thread-local, and finding the stack takes the C library, so only apps that a
+//! system linker links get these checks: the surgical linker supports neither.
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::module::Linkage;
+use inkwell::values::{FunctionValue, GlobalValue};
+use inkwell::IntPredicate;
+use roc_builtins::bitcode;
+use roc_target::{Architecture, OperatingSystem};
+
+use super::bitcode::call_bitcode_fn;
+use super::build::{throw_internal_exception, BuilderExt, Env, FAST_CALL_CONV};
+use super::intrinsics::LLVM_STACK_SAVE;
+
+const STACK_LIMIT: &str = "roc_stack_limit";
+const STACK_CHECK_SLOW: &str = "roc_stack_check_slow";
+
+/// Makes the function touch every page of a large stack frame as it grows the stack, so it
+/// can't skip over the guard page below the stack and write into whatever is below that.
+pub(crate) fn add_stack_probes(env: &Env<'_, '_, '_>, function: FunctionValue<'_>) {
+    // LLVM can only probe inline on x86-64. On Windows, large frames are always probed.
+    if env.target.architecture() == Architecture::X86_64
+        && env.target.operating_system() != OperatingSystem::Windows
+    {
+        let attr = env
+            .context
+            .create_string_attribute("probe-stack", "inline-asm");
+
+        function.add_attribute(AttributeLoc::Function, attr);
+    }
+}
+
+/// Branches to `roc_panic` with `message` if the stack is past the limit, and otherwise
+/// leaves the builder at the end of a new block in `parent` to build the rest of it in.
+pub(crate) fn build_stack_check<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    parent: FunctionValue<'ctx>,
+    message: &str,
+) {
+    let builder = env.builder;
+    let ptr_int = env.ptr_int();
+
+    let stack_pointer = env
+        .call_intrinsic(LLVM_STACK_SAVE, &[])
+        .into_pointer_value();
+    let stack_pointer = builder.new_build_ptr_to_int(stack_pointer, ptr_int, "stack_pointer");
+    let limit = builder
+        .new_build_load(ptr_int, stack_limit(env).as_pointer_value(), "stack_limit")
+        .into_int_value();
+    let past_limit =
+        builder.new_build_int_compare(IntPredicate::ULT, stack_pointer, limit, "past_limit");
+
+    let slow_block = env.context.append_basic_block(parent, "stack_check_slow");
+    let overflow_block = env.context.append_basic_block(parent, "stack_overflow");
+    let body_block = env.context.append_basic_block(parent, "body");
+
+    builder.new_build_conditional_branch(past_limit, slow_block, body_block);
+
+    builder.position_at_end(slow_block);
+    let call = builder.new_build_call(stack_check_slow(env), &[stack_pointer.into()], "overflowed");
+    call.set_call_convention(FAST_CALL_CONV);
+    let overflowed = call.try_as_basic_value().left().unwrap().into_int_value();
+    builder.new_build_conditional_branch(overflowed, overflow_block, body_block);
+
+    builder.position_at_end(overflow_block);
+    throw_internal_exception(env, parent, message);
+
+    builder.position_at_end(body_block);
+}
+
+fn stack_limit<'ctx>(env: &Env<'_, 'ctx, '_>) -> GlobalValue<'ctx> {
+    env.module.get_global(STACK_LIMIT).unwrap_or_else(|| {
+        let ptr_int = env.ptr_int();
+        let global = env.module.add_global(ptr_int, None, STACK_LIMIT);
+
+        global.set_initializer(&ptr_int.const_all_ones());
+        global.set_linkage(Linkage::Internal);
+        global.set_thread_local(true);
+
+        global
+    })
+}
+
+/// Decides what a check that found the stack past the limit means: if this thread hasn't set
+/// its limit yet, it sets it and checks against that; otherwise the stack really overflowed.
+fn stack_check_slow<'ctx>(env: &Env<'_, 'ctx, '_>) -> FunctionValue<'ctx> {
+    if let Some(function) = env.module.get_function(STACK_CHECK_SLOW) {
+        return function;
+    }
+
+    let builder = env.builder;
+    let block = builder.get_insert_block().expect("to be in a function");
+    let di_location = builder.get_current_debug_location();
+
+    let ptr_int = env.ptr_int();
+    let function_type = env.context.bool_type().fn_type(&[ptr_int.into()], false);
+    let function =
+        env.module
+            .add_function(STACK_CHECK_SLOW, function_type, Some(Linkage::Internal));
+
+    function.set_call_conventions(FAST_CALL_CONV);
+
+    for attribute_name in ["noinline", "cold"] {
+        let kind_id = Attribute::get_named_enum_kind_id(attribute_name);
+        debug_assert!(kind_id > 0);
+        let attr = env.context.create_enum_attribute(kind_id, 0);
+        function.add_attribute(AttributeLoc::Function, attr);
+    }
+
+    let entry = env.context.append_basic_block(function, "entry");
+    let set_limit_block = env.context.append_basic_block(function, "set_limit");
+    let overflowed_block = env.context.append_basic_block(function, "overflowed");
+
+    builder.position_at_end(entry);
+    builder.unset_current_debug_location();
+
+    let stack_pointer = function.get_nth_param(0).unwrap().into_int_value();
+    let limit_ptr = stack_limit(env).as_pointer_value();
+    let limit = builder
+        .new_build_load(ptr_int, limit_ptr, "stack_limit")
+        .into_int_value();
+    let unset =
+        builder.new_build_int_compare(IntPredicate::EQ, limit, ptr_int.const_all_ones(), "unset");
+    builder.new_build_conditional_branch(unset, set_limit_block, overflowed_block);
+
+    builder.position_at_end(set_limit_block);
+    let new_limit = call_bitcode_fn(env, &[], bitcode::UTILS_STACK_LIMIT).into_int_value();
+    builder.new_build_store(limit_ptr, new_limit);
+    let past_limit =
+        builder.new_build_int_compare(IntPredicate::ULT, stack_pointer, new_limit, "past_limit");
+    builder.new_build_return(Some(&past_limit));
+
+    builder.position_at_end(overflowed_block);
+    builder.new_build_return(Some(&env.context.bool_type().const_all_ones()));
+
+    builder.position_at_end(block);
+
+    if let Some(di_location) = di_location {
+        builder.set_current_debug_location(di_location);
+    }
+
+    function
+}
@@ -10,6 +10,7 @@ use roc_mono::layout::{Builtin, InLayout, Layout, LayoutInterner, LayoutRepr, Un
 use roc_mono::low_level::HigherOrder;
 
 use crate::backend::{ProcLookupData, ProcSource, WasmBackend};
+use crate::code_builder::CodeBuilder;
 use crate::layout::{StackMemoryFormat, WasmLayout};
 use crate::storage::{AddressValue, StackMemoryLocation, StoredValue};
 use crate::PTR_TYPE;
@@ -170,6 +171,112 @@ impl<'a> LowLevelCall<'a> {
     }
 
     ///  Main entrypoint from WasmBackend
+    /// The stack frame location of an argument that is a 128-bit number
+    fn arg_stack_location(&self, backend: &WasmBackend<'a, '_>) -> (LocalId, u32) {
+        match backend.storage.get(&self.arguments[0]) {
+            StoredValue::StackMemory { location, .. } => {
+                location.local_and_offset(backend.storage.stack_frame_pointer)
+            }
+            _ => internal_error!("{:?} argument should be in stack memory", self.lowlevel),
+        }
+    }
+
+    /// The stack frame location of a return value that is a 128-bit number
+    fn ret_stack_location(&self, backend: &WasmBackend<'a, '_>) -> (LocalId, u32) {
+        match &self.ret_storage {
+            StoredValue::StackMemory { location, .. } => {
+                location.local_and_offset(backend.storage.stack_frame_pointer)
+            }
+            _ => internal_error!("{:?} should return in stack memory", self.lowlevel),
+        }
+    }
+
+    /// Crash if the I128 argument is the minimum value, whose negation overflows
+    fn panic_if_i128_min(&self, backend: &mut WasmBackend<'a, '_>, msg: &'static str) {
+        let (frame_ptr, offset) = self.arg_stack_location(backend);
+
+        backend.code_builder.get_local(frame_ptr);
+        backend.code_builder.i64_load(Align::Bytes8, offset);
+        backend.code_builder.i64_eqz();
+        backend.code_builder.get_local(frame_ptr);
+        backend.code_builder.i64_load(Align::Bytes8, offset + 8);
+        backend.code_builder.i64_const(i64::MIN);
+        backend.code_builder.i64_eq();
+        backend.code_builder.i32_and();
+        backend.code_builder.if_();
+        backend.stmt_internal_error(msg);
+        backend.code_builder.end();
+    }
+
+    /// Load the numeric argument and convert it to a float of the given type
+    fn load_arg_as_float(&self, backend: &mut WasmBackend<'a, '_>, ret_type: CodeGenNumType) {
+        use CodeGenNumType::*;
+
+        let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
+        let arg_signed = match backend.layout_interner.get_repr(arg_layout) {
+            LayoutRepr::Builtin(Builtin::Int(w)) => w.is_signed(),
+            LayoutRepr::Builtin(Builtin::Float(_)) => true, // unused
+            LayoutRepr::Builtin(Builtin::Decimal) => true,  // unused
+            x => internal_error!("{:?} is not defined for {:?}", self.lowlevel, x),
+        };
+        let arg_type = CodeGenNumType::from(arg_layout);
+
+        backend
+            .storage
+            .load_symbols(&mut backend.code_builder, &[self.arguments[0]]);
+
+        match (ret_type, arg_type) {
+            (F32, F32) => {}
+            (F32, F64) => backend.code_builder.f32_demote_f64(),
+            (F32, I32) => {
+                if arg_signed {
+                    backend.code_builder.f32_convert_s_i32()
+                } else {
+                    backend.code_builder.f32_convert_u_i32()
+                }
+            }
+            (F32, I64) => {
+                if arg_signed {
+                    backend.code_builder.f32_convert_s_i64()
+                } else {
+                    backend.code_builder.f32_convert_u_i64()
+                }
+            }
+            (F64, F32) => backend.code_builder.f64_promote_f32(),
+            (F64, F64) => {}
+            (F64, I32) => {
+                if arg_signed {
+                    backend.code_builder.f64_convert_s_i32()
+                } else {
+                    backend.code_builder.f64_convert_u_i32()
+                }
+            }
+            (F64, I64) => {
+                if arg_signed {
+                    backend.code_builder.f64_convert_s_i64()
+                } else {
+                    backend.code_builder.f64_convert_u_i64()
+                }
+            }
+            (F32 | F64, I128) => {
+                // from compiler_rt
+                let name = match (ret_type, arg_signed) {
+                    (F32, true) => "__floattisf",
+                    (F32, false) => "__floatuntisf",
+                    (_, true) => "__floattidf",
+                    (_, false) => "__floatuntidf",
+                };
+                backend.call_host_fn_after_loading_args(name);
+            }
+            (F64, Decimal) => backend.call_host_fn_after_loading_args(bitcode::DEC_TO_F64),
+            (F32, Decimal) => {
+                backend.call_host_fn_after_loading_args(bitcode::DEC_TO_F64);
+                backend.code_builder.f32_demote_f64();
+            }
+            _ => internal_error!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
+        }
+    }
+
     pub fn generate(&self, backend: &mut WasmBackend<'a, '_>) {
         use CodeGenNumType::*;
         use LowLevel::*;
@@ -186,6 +293,7 @@ impl<'a> LowLevelCall<'a> {
             // Str
             StrConcat => self.load_args_and_call_zig(backend, bitcode::STR_CONCAT),
             StrGetUnsafe => self.load_args_and_call_zig(backend, bitcode::STR_GET_UNSAFE),
+            StrGraphemeEnd => self.load_args_and_call_zig(backend, bitcode::STR_GRAPHEME_END),
             StrJoinWith => self.load_args_and_call_zig(backend, bitcode::STR_JOIN_WITH),
             StrIsEmpty => match backend.storage.get(&self.arguments[0]) {
                 StoredValue::StackMemory { location, .. } => {
@@ -273,6 +381,14 @@ impl<'a> LowLevelCall<'a> {
 
             ListIsUnique => self.load_args_and_call_zig(backend, bitcode::LIST_IS_UNIQUE),
 
+            JsonSkipWhitespace => {
+                self.load_args_and_call_zig(backend, bitcode::JSON_SKIP_WHITESPACE)
+            }
+            JsonValueEnd => self.load_args_and_call_zig(backend, bitcode::JSON_VALUE_END),
+
+            // Only `roc test` checks snapshots, and it uses the LLVM backend
+            InspectMatchesSnapshot => backend.code_builder.i32_const(1),
+
             ListClone => {
                 let input_list: Symbol = self.arguments[0];
                 let elem_layout = unwrap_list_elem_layout(self.ret_layout_raw);
@@ -822,6 +938,7 @@ impl<'a> LowLevelCall<'a> {
             },
 
             NumToStr => self.num_to_str(backend),
+            NumToStrWith => self.num_to_str_with(backend),
             NumAddChecked => {
                 let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
                 match backend.layout_interner.get_repr(arg_layout) {
@@ -1238,8 +1355,8 @@ impl<'a> LowLevelCall<'a> {
                 match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
                     F32 => backend.code_builder.f32_div(),
                     F64 => backend.code_builder.f64_div(),
-                    Decimal => self.load_args_and_call_zig(backend, bitcode::DEC_DIV),
-                    x => todo!("{:?} for {:?}", self.lowlevel, x),
+                    Decimal => backend.call_host_fn_after_loading_args(bitcode::DEC_DIV),
+                    _ => panic_ret_type(),
                 }
             }
             NumDivTruncUnchecked => {
@@ -1260,7 +1377,12 @@ impl<'a> LowLevelCall<'a> {
                             backend.code_builder.i64_div_u()
                         }
                     }
-                    x => todo!("{:?} for {:?}", self.lowlevel, x),
+                    I128 => {
+                        // from compiler_rt
+                        let name = if is_signed { "__divti3" } else { "__udivti3" };
+                        backend.call_host_fn_after_loading_args(name);
+                    }
+                    _ => panic_ret_type(),
                 }
             }
             NumDivCeilUnchecked => match self.ret_layout_raw {
@@ -1272,10 +1394,28 @@ impl<'a> LowLevelCall<'a> {
 
             NumRemUnchecked => {
                 self.load_args(backend);
+                let is_signed = symbol_is_signed_int(backend, self.arguments[0]);
                 match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
-                    I32 => backend.code_builder.i32_rem_s(),
-                    I64 => backend.code_builder.i64_rem_s(),
-                    _ => todo!("{:?} for {:?}", self.lowlevel, self.ret_layout),
+                    I32 => {
+                        if is_signed {
+                            backend.code_builder.i32_rem_s()
+                        } else {
+                            backend.code_builder.i32_rem_u()
+                        }
+                    }
+                    I64 => {
+                        if is_signed {
+                            backend.code_builder.i64_rem_s()
+                        } else {
+                            backend.code_builder.i64_rem_u()
+                        }
+                    }
+                    I128 => {
+                        // from compiler_rt
+                        let name = if is_signed { "__modti3" } else { "__umodti3" };
+                        backend.call_host_fn_after_loading_args(name);
+                    }
+                    _ => panic_ret_type(),
                 }
             }
             NumIsMultipleOf => {
@@ -1381,10 +1521,9 @@ impl<'a> LowLevelCall<'a> {
                 const PANIC_MSG: &str =
                     "Integer absolute overflowed because its argument is the minimum value";
 
-                self.load_args(backend);
-
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => {
+                        self.load_args(backend);
                         if !layout_is_signed_int(self.ret_layout) {
                             return;
                         }
@@ -1411,6 +1550,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.select();
                     }
                     I64 => {
+                        self.load_args(backend);
                         if !layout_is_signed_int(self.ret_layout) {
                             return;
                         }
@@ -1436,18 +1576,53 @@ impl<'a> LowLevelCall<'a> {
                         // (x >= 0) ? x : -x
                         backend.code_builder.select();
                     }
-                    F32 => backend.code_builder.f32_abs(),
-                    F64 => backend.code_builder.f64_abs(),
-                    _ => todo!("{:?} for {:?}", self.lowlevel, self.ret_layout),
+                    I128 => {
+                        let (arg_frame_ptr, arg_offset) = self.arg_stack_location(backend);
+                        let (ret_frame_ptr, ret_offset) = self.ret_stack_location(backend);
+
+                        if layout_is_signed_int(self.ret_layout) {
+                            self.panic_if_i128_min(backend, PANIC_MSG);
+
+                            // x < 0
+                            backend.code_builder.get_local(arg_frame_ptr);
+                            backend.code_builder.i64_load(Align::Bytes8, arg_offset + 8);
+                            backend.code_builder.i64_const(0);
+                            backend.code_builder.i64_lt_s();
+
+                            backend.code_builder.if_();
+                            self.load_args_and_call_zig(backend, "__negti2"); // from compiler_rt
+                            backend.code_builder.else_();
+                        }
+
+                        backend.storage.copy_value_to_memory(
+                            &mut backend.code_builder,
+                            ret_frame_ptr,
+                            ret_offset,
+                            self.arguments[0],
+                        );
+
+                        if layout_is_signed_int(self.ret_layout) {
+                            backend.code_builder.end();
+                        }
+                    }
+                    F32 => {
+                        self.load_args(backend);
+                        backend.code_builder.f32_abs()
+                    }
+                    F64 => {
+                        self.load_args(backend);
+                        backend.code_builder.f64_abs()
+                    }
+                    Decimal => self.load_args_and_call_zig(backend, bitcode::DEC_ABS),
                 }
             }
             NumNeg => {
                 const PANIC_MSG: &str =
                     "Integer negation overflowed because its argument is the minimum value";
 
-                self.load_args(backend);
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => {
+                        self.load_args(backend);
                         backend.code_builder.i32_const(i32::MIN);
                         backend.code_builder.i32_eq();
                         backend.code_builder.if_();
@@ -1459,6 +1634,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i32_sub();
                     }
                     I64 => {
+                        self.load_args(backend);
                         backend.code_builder.i64_const(i64::MIN);
                         backend.code_builder.i64_eq();
                         backend.code_builder.if_();
@@ -1469,9 +1645,19 @@ impl<'a> LowLevelCall<'a> {
                         self.load_args(backend);
                         backend.code_builder.i64_sub();
                     }
-                    F32 => backend.code_builder.f32_neg(),
-                    F64 => backend.code_builder.f64_neg(),
-                    _ => todo!("{:?} for {:?}", self.lowlevel, self.ret_layout),
+                    I128 => {
+                        self.panic_if_i128_min(backend, PANIC_MSG);
+                        self.load_args_and_call_zig(backend, "__negti2"); // from compiler_rt
+                    }
+                    F32 => {
+                        self.load_args(backend);
+                        backend.code_builder.f32_neg()
+                    }
+                    F64 => {
+                        self.load_args(backend);
+                        backend.code_builder.f64_neg()
+                    }
+                    Decimal => self.load_args_and_call_zig(backend, bitcode::DEC_NEGATE),
                 }
             }
             NumSin => match self.ret_layout_raw {
@@ -1520,33 +1706,20 @@ impl<'a> LowLevelCall<'a> {
                 _ => panic_ret_type(),
             },
             NumToFrac => {
-                self.load_args(backend);
                 let ret_type = CodeGenNumType::from(self.ret_layout);
                 let arg_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
                 let arg_is_signed = symbol_is_signed_int(backend, self.arguments[0]);
                 match (ret_type, arg_type) {
-                    (F32, I32) => backend.code_builder.f32_convert_s_i32(),
-                    (F32, I64) => backend.code_builder.f32_convert_s_i64(),
-                    (F32, F32) => {}
-                    (F32, F64) => backend.code_builder.f32_demote_f64(),
-
-                    (F64, I32) => backend.code_builder.f64_convert_s_i32(),
-                    (F64, I64) => backend.code_builder.f64_convert_s_i64(),
-                    (F64, F32) => backend.code_builder.f64_promote_f32(),
-                    (F64, F64) => {}
-
-                    (Decimal, I32) => {
-                        let int_width = match arg_is_signed {
-                            true => IntWidth::I32,
-                            false => IntWidth::U32,
-                        };
-
-                        self.load_args_and_call_zig(backend, &bitcode::DEC_FROM_INT[int_width]);
-                    }
-                    (Decimal, I64) => {
-                        let int_width = match arg_is_signed {
-                            true => IntWidth::I64,
-                            false => IntWidth::U64,
+                    (F32 | F64, _) => self.load_arg_as_float(backend, ret_type),
+
+                    (Decimal, I32 | I64 | I128) => {
+                        let int_width = match (arg_type, arg_is_signed) {
+                            (I32, true) => IntWidth::I32,
+                            (I32, false) => IntWidth::U32,
+                            (I64, true) => IntWidth::I64,
+                            (I64, false) => IntWidth::U64,
+                            (_, true) => IntWidth::I128,
+                            (_, false) => IntWidth::U128,
                         };
 
                         self.load_args_and_call_zig(backend, &bitcode::DEC_FROM_INT[int_width]);
@@ -1563,9 +1736,17 @@ impl<'a> LowLevelCall<'a> {
                             &bitcode::DEC_FROM_FLOAT[FloatWidth::F64],
                         );
                     }
-                    (Decimal, Decimal) => {}
+                    (Decimal, Decimal) => {
+                        let (frame_ptr, offset) = self.ret_stack_location(backend);
+                        backend.storage.copy_value_to_memory(
+                            &mut backend.code_builder,
+                            frame_ptr,
+                            offset,
+                            self.arguments[0],
+                        );
+                    }
 
-                    _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
+                    _ => panic_ret_type(),
                 }
             }
             NumPow => match self.ret_layout_raw {
@@ -1613,15 +1794,11 @@ impl<'a> LowLevelCall<'a> {
                 _ => panic_ret_type(),
             },
             NumRound => {
-                self.load_args(backend);
                 let arg_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
-                let ret_type = CodeGenNumType::from(self.ret_layout);
 
-                let width = match ret_type {
-                    CodeGenNumType::I32 => IntWidth::I32,
-                    CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => internal_error!("Invalid return type for round: {:?}", ret_type),
+                let width = match self.ret_layout_raw {
+                    LayoutRepr::Builtin(Builtin::Int(width)) => width,
+                    _ => internal_error!("Invalid return type for round: {:?}", self.ret_layout),
                 };
 
                 match arg_type {
@@ -1632,17 +1809,41 @@ impl<'a> LowLevelCall<'a> {
                 }
             }
             NumCeiling | NumFloor => {
-                self.load_args(backend);
                 let arg_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
                 let ret_type = CodeGenNumType::from(self.ret_layout);
 
-                let width = match ret_type {
-                    CodeGenNumType::I32 => IntWidth::I32,
-                    CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => internal_error!("Invalid return type for round: {:?}", ret_type),
+                let width = match self.ret_layout_raw {
+                    LayoutRepr::Builtin(Builtin::Int(width)) => width,
+                    _ => internal_error!(
+                        "Invalid return type for {:?}: {:?}",
+                        self.lowlevel,
+                        ret_type
+                    ),
                 };
 
+                // Wasm has no instructions to convert floats to 128-bit integers
+                if ret_type == I128 {
+                    let intrinsic = match (arg_type, self.lowlevel) {
+                        (F32, NumCeiling) => &bitcode::NUM_CEILING_F32[width],
+                        (F64, NumCeiling) => &bitcode::NUM_CEILING_F64[width],
+                        (F32, NumFloor) => &bitcode::NUM_FLOOR_F32[width],
+                        (F64, NumFloor) => &bitcode::NUM_FLOOR_F64[width],
+                        (Decimal, NumCeiling) => &bitcode::DEC_CEILING[width],
+                        (Decimal, NumFloor) => &bitcode::DEC_FLOOR[width],
+                        _ => internal_error!(
+                            "Invalid argument type for {:?}: {:?}",
+                            self.lowlevel,
+                            arg_type
+                        ),
+                    };
+
+                    return self.load_args_and_call_zig(backend, intrinsic);
+                }
+
+                if arg_type != Decimal {
+                    self.load_args(backend);
+                }
+
                 match (arg_type, self.lowlevel) {
                     (F32, NumCeiling) => {
                         backend.code_builder.f32_ceil();
@@ -1671,12 +1872,10 @@ impl<'a> LowLevelCall<'a> {
                     (I32, F64) => backend.code_builder.i32_trunc_s_f64(),
                     (I64, F32) => backend.code_builder.i64_trunc_s_f32(),
                     (I64, F64) => backend.code_builder.i64_trunc_s_f64(),
-                    (I128, _) => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
             NumPowInt => {
-                self.load_args(backend);
                 let base_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
                 let exponent_type = CodeGenNumType::for_symbol(backend, self.arguments[1]);
                 let ret_type = CodeGenNumType::from(self.ret_layout);
@@ -1684,10 +1883,8 @@ impl<'a> LowLevelCall<'a> {
                 debug_assert!(base_type == exponent_type);
                 debug_assert!(exponent_type == ret_type);
 
-                let width = match ret_type {
-                    CodeGenNumType::I32 => IntWidth::I32,
-                    CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                let width = match self.ret_layout_raw {
+                    LayoutRepr::Builtin(Builtin::Int(width)) => width,
                     _ => internal_error!("Invalid return type for pow: {:?}", ret_type),
                 };
 
@@ -1725,46 +1922,60 @@ impl<'a> LowLevelCall<'a> {
                 }
                 _ => panic_ret_type(),
             },
-            NumBitwiseAnd => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_and(),
-                    I64 => backend.code_builder.i64_and(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+            NumBitwiseAnd => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_and();
                 }
-            }
-            NumBitwiseXor => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_xor(),
-                    I64 => backend.code_builder.i64_xor(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_and();
                 }
-            }
-            NumBitwiseOr => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_or(),
-                    I64 => backend.code_builder.i64_or(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_and),
+                _ => panic_ret_type(),
+            },
+            NumBitwiseXor => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_xor();
                 }
-            }
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_xor();
+                }
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_xor),
+                _ => panic_ret_type(),
+            },
+            NumBitwiseOr => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_or();
+                }
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_or();
+                }
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_or),
+                _ => panic_ret_type(),
+            },
             NumShiftLeftBy => {
                 let num = self.arguments[0];
                 let bits = self.arguments[1];
-                backend
-                    .storage
-                    .load_symbols(&mut backend.code_builder, &[num, bits]);
                 match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_shl(),
+                    I32 => {
+                        backend
+                            .storage
+                            .load_symbols(&mut backend.code_builder, &[num, bits]);
+                        backend.code_builder.i32_shl();
+                    }
                     I64 => {
+                        backend
+                            .storage
+                            .load_symbols(&mut backend.code_builder, &[num, bits]);
                         backend.code_builder.i64_extend_u_i32();
                         backend.code_builder.i64_shl();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
+                    I128 => self.load_args_and_call_zig(backend, "__ashlti3"), // from compiler_rt
                     _ => panic_ret_type(),
                 }
             }
@@ -1811,7 +2022,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i64_extend_u_i32();
                         backend.code_builder.i64_shr_s();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
+                    I128 => self.load_args_and_call_zig(backend, "__ashrti3"), // from compiler_rt
                     _ => panic_ret_type(),
                 }
             }
@@ -1882,8 +2093,11 @@ impl<'a> LowLevelCall<'a> {
                         self.wrap_small_int(backend, ret_width);
                     }
                     (I32, I128) => {
-                        self.load_args(backend);
-                        backend.code_builder.i32_load(Align::Bytes4, 0);
+                        let (frame_ptr, offset) = self.arg_stack_location(backend);
+                        // the least significant 32 bits
+                        backend.code_builder.get_local(frame_ptr);
+                        backend.code_builder.i32_load(Align::Bytes4, offset);
+                        self.wrap_small_int(backend, ret_width);
                     }
                     (I64, I32) => {
                         self.load_args(backend);
@@ -1906,73 +2120,53 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.get_local(frame_ptr);
                         backend.code_builder.i64_load(Align::Bytes8, offset);
                     }
-                    (I128, I64) => {
-                        // Symbols are loaded as if for a call, so the i128 "return address" and i64 value are on the value stack
-                        self.load_args(backend);
-                        backend.code_builder.i64_store(Align::Bytes8, 0);
-
-                        // Zero the most significant 64 bits
-                        let (frame_ptr, offset) = match &self.ret_storage {
-                            StoredValue::StackMemory { location, .. } => {
-                                location.local_and_offset(backend.storage.stack_frame_pointer)
+                    (I128, I32 | I64) => {
+                        let (frame_ptr, offset) = self.ret_stack_location(backend);
+                        let load_as_i64 = |backend: &mut WasmBackend<'a, '_>| {
+                            backend
+                                .storage
+                                .load_symbols(&mut backend.code_builder, self.arguments);
+                            if arg_type == I32 {
+                                if arg_width.is_signed() {
+                                    backend.code_builder.i64_extend_s_i32()
+                                } else {
+                                    backend.code_builder.i64_extend_u_i32()
+                                }
                             }
-                            _ => internal_error!("I128 should be in stack memory"),
                         };
-                        backend.code_builder.get_local(frame_ptr);
-                        backend.code_builder.i64_const(0);
-                        backend.code_builder.i64_store(Align::Bytes8, offset + 8);
-                    }
 
-                    _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
-                }
-            }
-            NumToFloatCast => {
-                self.load_args(backend);
-                let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
-                let arg_signed = match backend.layout_interner.get_repr(arg_layout) {
-                    LayoutRepr::Builtin(Builtin::Int(w)) => w.is_signed(),
-                    LayoutRepr::Builtin(Builtin::Float(_)) => true, // unused
-                    LayoutRepr::Builtin(Builtin::Decimal) => true,
-                    x => internal_error!("Num.intCast is not defined for {:?}", x),
-                };
-                let ret_type = CodeGenNumType::from(self.ret_layout);
-                let arg_type = CodeGenNumType::from(arg_layout);
+                        // the least significant 64 bits
+                        backend.code_builder.get_local(frame_ptr);
+                        load_as_i64(backend);
+                        backend.code_builder.i64_store(Align::Bytes8, offset);
 
-                match (ret_type, arg_type) {
-                    (F32, F32) => {}
-                    (F32, F64) => backend.code_builder.f32_demote_f64(),
-                    (F32, I32) => {
-                        if arg_signed {
-                            backend.code_builder.f32_convert_s_i32()
-                        } else {
-                            backend.code_builder.f32_convert_u_i32()
-                        }
-                    }
-                    (F32, I64) => {
-                        if arg_signed {
-                            backend.code_builder.f32_convert_s_i64()
-                        } else {
-                            backend.code_builder.f32_convert_u_i64()
-                        }
-                    }
-                    (F64, F64) => {}
-                    (F64, I32) => {
-                        if arg_signed {
-                            backend.code_builder.f64_convert_s_i32()
+                        // the most significant 64 bits are copies of the sign bit
+                        backend.code_builder.get_local(frame_ptr);
+                        if arg_width.is_signed() {
+                            load_as_i64(backend);
+                            backend.code_builder.i64_const(63);
+                            backend.code_builder.i64_shr_s();
                         } else {
-                            backend.code_builder.f64_convert_u_i32()
+                            backend.code_builder.i64_const(0);
                         }
+                        backend.code_builder.i64_store(Align::Bytes8, offset + 8);
                     }
-                    (F64, I64) => {
-                        if arg_signed {
-                            backend.code_builder.f64_convert_s_i64()
-                        } else {
-                            backend.code_builder.f64_convert_u_i64()
-                        }
+                    (I128, I128) => {
+                        let (frame_ptr, offset) = self.ret_stack_location(backend);
+                        backend.storage.copy_value_to_memory(
+                            &mut backend.code_builder,
+                            frame_ptr,
+                            offset,
+                            self.arguments[0],
+                        );
                     }
-                    _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
+
+                    _ => internal_error!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
                 }
             }
+            NumToFloatCast => {
+                self.load_arg_as_float(backend, CodeGenNumType::from(self.ret_layout))
+            }
             NumToIntChecked => {
                 let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
 
@@ -2015,7 +2209,59 @@ impl<'a> LowLevelCall<'a> {
                 }
             }
             NumToFloatChecked => {
-                todo!("implement toF32Checked and toF64Checked");
+                // return_layout : Result F [OutOfBounds]* ~ { result: F, out_of_bounds: bool }
+                let float_width = match self.ret_layout_raw {
+                    LayoutRepr::Struct(&[value, _]) => {
+                        match backend.layout_interner.get_repr(value) {
+                            LayoutRepr::Builtin(Builtin::Float(width)) => Some(width),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                let float_width = float_width.unwrap_or_else(|| {
+                    internal_error!(
+                        "{:?} is not defined for {:?}",
+                        self.lowlevel,
+                        self.ret_layout
+                    )
+                });
+                let (frame_ptr, offset) = self.ret_stack_location(backend);
+
+                backend.code_builder.get_local(frame_ptr);
+                match float_width {
+                    FloatWidth::F32 => {
+                        self.load_arg_as_float(backend, F32);
+                        backend.code_builder.f32_store(Align::Bytes4, offset);
+                    }
+                    FloatWidth::F64 => {
+                        self.load_arg_as_float(backend, F64);
+                        backend.code_builder.f64_store(Align::Bytes8, offset);
+                    }
+                }
+
+                // It's out of bounds if a finite number became infinite
+                backend.code_builder.get_local(frame_ptr);
+                backend.code_builder.get_local(frame_ptr);
+                match float_width {
+                    FloatWidth::F32 => {
+                        backend.code_builder.f32_load(Align::Bytes4, offset);
+                        backend.code_builder.f32_abs();
+                        backend.code_builder.f32_const(f32::INFINITY);
+                        backend.code_builder.f32_eq();
+                    }
+                    FloatWidth::F64 => {
+                        backend.code_builder.f64_load(Align::Bytes8, offset);
+                        backend.code_builder.f64_abs();
+                        backend.code_builder.f64_const(f64::INFINITY);
+                        backend.code_builder.f64_eq();
+                    }
+                }
+                num_is_finite(backend, self.arguments[0]);
+                backend.code_builder.i32_and();
+                backend
+                    .code_builder
+                    .i32_store8(Align::Bytes1, offset + float_width.stack_size());
             }
             NumWithoutDecimalPoint => self.load_args_and_call_zig(backend, bitcode::DEC_TO_I128),
             NumWithDecimalPoint => self.load_args_and_call_zig(backend, bitcode::DEC_FROM_I128),
@@ -2083,7 +2329,7 @@ impl<'a> LowLevelCall<'a> {
                 backend.code_builder.i32_and();
             }
 
-            Hash => todo!("{:?}", self.lowlevel),
+            Hash => internal_error!("{:?} is never generated", self.lowlevel),
 
             Eq | NotEq => self.eq_or_neq(backend),
 
@@ -2265,6 +2511,34 @@ impl<'a> LowLevelCall<'a> {
         }
     }
 
+    /// Apply a bitwise operator to two 128-bit integers, one 64-bit half at a time,
+    /// and write the result to the return value's stack memory
+    /// (Don't call "load arguments" or "load symbols" helpers before this)
+    fn bitwise_num128(&self, backend: &mut WasmBackend<'a, '_>, op: fn(&mut CodeBuilder<'a>)) {
+        let location_of = |storage: &StoredValue| match storage {
+            StoredValue::StackMemory { location, .. } => {
+                location.local_and_offset(backend.storage.stack_frame_pointer)
+            }
+            _ => internal_error!("128-bit integers should be in stack memory"),
+        };
+
+        let (ret_local, ret_offset) = location_of(&self.ret_storage);
+        let (local0, offset0) = location_of(backend.storage.get(&self.arguments[0]));
+        let (local1, offset1) = location_of(backend.storage.get(&self.arguments[1]));
+
+        for half in [0, 8] {
+            backend.code_builder.get_local(ret_local);
+            backend.code_builder.get_local(local0);
+            backend.code_builder.i64_load(Align::Bytes8, offset0 + half);
+            backend.code_builder.get_local(local1);
+            backend.code_builder.i64_load(Align::Bytes8, offset1 + half);
+            op(&mut backend.code_builder);
+            backend
+                .code_builder
+                .i64_store(Align::Bytes8, ret_offset + half);
+        }
+    }
+
     /// Check that two 128-bit numbers contain the same bytes
     /// Loads *half* an argument at a time
     /// (Don't call "load arguments" or "load symbols" helpers before this, it'll just waste instructions)
@@ -2312,6 +2586,18 @@ impl<'a> LowLevelCall<'a> {
             x => internal_error!("NumToStr is not defined for {:?}", x),
         }
     }
+
+    fn num_to_str_with(&self, backend: &mut WasmBackend<'a, '_>) {
+        let arg_layout = backend.storage.symbol_layouts[&self.arguments[1]];
+        let intrinsic = match backend.layout_interner.runtime_representation(arg_layout) {
+            LayoutRepr::Builtin(Builtin::Int(width)) => &bitcode::STR_FROM_INT_WITH[width],
+            LayoutRepr::Builtin(Builtin::Float(width)) => &bitcode::STR_FROM_FLOAT_WITH[width],
+            LayoutRepr::Builtin(Builtin::Decimal) => bitcode::DEC_TO_STR_WITH,
+            x => internal_error!("NumToStrWith is not defined for {:?}", x),
+        };
+
+        self.load_args_and_call_zig(backend, intrinsic);
+    }
 }
 
 /// Helper for NumIsNan op
@@ -2426,9 +2712,11 @@ fn num_is_finite(backend: &mut WasmBackend<'_, '_>, argument: Symbol) {
     let stored = backend.storage.get(&argument).to_owned();
     match stored {
         Local { value_type, .. } => {
-            backend
-                .storage
-                .load_symbols(&mut backend.code_builder, &[argument]);
+            if matches!(value_type, ValueType::F32 | ValueType::F64) {
+                backend
+                    .storage
+                    .load_symbols(&mut backend.code_builder, &[argument]);
+            }
             match value_type {
                 // Integers are always finite. Just return True.
                 ValueType::I32 | ValueType::I64 => backend.code_builder.i32_const(1),
@@ -26,6 +26,7 @@ const MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::HASH, "Hash.roc"),
     (ModuleId::INSPECT, "Inspect.roc"),
     (ModuleId::JSON, "TotallyNotJson.roc"),
+    (ModuleId::JSON_FORMAT, "JsonFormat.roc"),
 ];
 
 fn main() {
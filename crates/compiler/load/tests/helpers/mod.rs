@@ -48,6 +48,7 @@ pub fn infer_expr(
         root_constraint: constraint,
         home: ModuleId::ATTR,
         pending_derives,
+        incomplete_impls: Default::default(),
         exposed_by_module: &Default::default(),
         derived_module,
         function_kind: FunctionKind::LambdaSet,
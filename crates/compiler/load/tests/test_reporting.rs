@@ -14,7 +14,7 @@ mod test_reporting {
     use roc_can::abilities::AbilitiesStore;
     use roc_can::expr::PendingDerives;
     use roc_load::{self, ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
-    use roc_module::symbol::{Interns, ModuleId};
+    use roc_module::symbol::{Interns, ModuleId, Symbol};
     use roc_packaging::cache::RocCacheDir;
     use roc_parse::module::parse_header;
     use roc_parse::state::State;
@@ -29,6 +29,7 @@ mod test_reporting {
     use roc_solve::FunctionKind;
     use roc_solve_problem::TypeError;
     use roc_test_utils_dir::TmpDir;
+    use roc_types::pretty_print::Qualification;
     use roc_types::subs::Subs;
     use std::path::PathBuf;
 
@@ -130,6 +131,8 @@ mod test_reporting {
                 threading: Threading::Single,
                 exec_mode: ExecutionMode::Check,
                 function_kind: FunctionKind::LambdaSet,
+                reproducible: false,
+                coverage: false,
             };
             let result = roc_load::load_and_typecheck(
                 arena,
@@ -731,6 +734,91 @@ mod test_reporting {
     "#
     );
 
+    /// Every kind of report needs a stable code (see `roc_reporting::error::code`), so this
+    /// looks for every title in the source: any all-caps string literal in roc_reporting, and
+    /// any `title: "..."` elsewhere.
+    #[test]
+    fn every_report_title_has_an_error_code() {
+        use roc_reporting::error::code::code_for_title;
+
+        fn is_title(literal: &str) -> bool {
+            literal.len() > 1
+                && literal.starts_with(|c: char| c.is_ascii_uppercase())
+                && literal.ends_with(|c: char| c.is_ascii_uppercase())
+                && literal
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || " -'".contains(c))
+        }
+
+        fn titles_in(path: &std::path::Path, in_reporting: bool, titles: &mut Vec<String>) {
+            for entry in std::fs::read_dir(path).unwrap() {
+                let path = entry.unwrap().path();
+
+                if path.is_dir() {
+                    titles_in(&path, in_reporting, titles);
+                } else if path.extension() == Some(std::ffi::OsStr::new("rs"))
+                    && !path.ends_with("code.rs")
+                {
+                    let src = std::fs::read_to_string(&path).unwrap();
+
+                    for (index, line) in src.lines().enumerate() {
+                        // The odd pieces are the contents of string literals
+                        let literals = line.split('"').skip(1).step_by(2);
+
+                        let found: Vec<&str> = if in_reporting {
+                            literals.filter(|literal| is_title(literal)).collect()
+                        } else {
+                            line.split("title: \"")
+                                .skip(1)
+                                .filter_map(|rest| rest.split('"').next())
+                                .filter(|literal| is_title(literal))
+                                .collect()
+                        };
+
+                        titles.extend(
+                            found
+                                .into_iter()
+                                .map(|title| format!("{title} ({}:{})", path.display(), index + 1)),
+                        );
+                    }
+                }
+            }
+        }
+
+        let crates = PathBuf::from(std::env!("ROC_WORKSPACE_DIR")).join("crates");
+        let reporting = crates.join("reporting").join("src");
+        let mut titles = Vec::new();
+
+        titles_in(&reporting, true, &mut titles);
+
+        for entry in std::fs::read_dir(&crates).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() && !path.ends_with("reporting") {
+                titles_in(&path, false, &mut titles);
+            }
+        }
+
+        // Make sure the search is actually finding titles
+        assert!(titles
+            .iter()
+            .any(|title| title.starts_with("TYPE MISMATCH ")));
+
+        let missing: Vec<&String> = titles
+            .iter()
+            .filter(|title| {
+                let (title, _location) = title.split_once(" (").unwrap();
+
+                code_for_title(title).is_none()
+            })
+            .collect();
+
+        assert!(
+            missing.is_empty(),
+            "These report titles have no error code:\n{missing:#?}"
+        );
+    }
+
     #[test]
     fn report_value_color() {
         let src: &str = indoc!(
@@ -790,6 +878,41 @@ mod test_reporting {
         assert_eq!(human_readable(&buf), "<green>Util.Int<reset>");
     }
 
+    #[test]
+    fn report_type_name_qualification() {
+        let src: &str = indoc!(
+            r"
+                x = 1
+
+                x
+            "
+        );
+
+        let arena = Bump::new();
+        let (_type_problems, _can_problems, home, interns) =
+            infer_expr_help(&arena, src).expect("parse error");
+
+        let src_lines: Vec<&str> = src.split('\n').collect();
+
+        let render = |qualification| {
+            let mut buf = String::new();
+            let alloc =
+                RocDocAllocator::new(&src_lines, home, &interns).with_qualification(qualification);
+
+            to_simple_report(alloc.type_name(Symbol::LIST_LIST)).render_color_terminal(
+                &mut buf,
+                &alloc,
+                &DEFAULT_PALETTE,
+            );
+
+            human_readable(&buf)
+        };
+
+        assert_eq!(render(Qualification::Foreign), "<cyan>List<reset>");
+        assert_eq!(render(Qualification::Short), "<cyan>List<reset>");
+        assert_eq!(render(Qualification::Full), "<cyan>List.List<reset>");
+    }
+
     #[test]
     fn report_region_in_color() {
         color_report_problem_as(
@@ -1121,6 +1244,15 @@ mod test_reporting {
     infinitely.
 
         (∞ -> a) -> a
+
+    The type goes in a circle here:
+
+        b = b -> *
+
+    This function comes from here:
+
+    4│      f = \g -> g g
+                      ^
     "
     );
 
@@ -1146,6 +1278,54 @@ mod test_reporting {
     infinitely.
 
         List ∞ -> *
+
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    4│      f = \x -> f [x]
+                        ^^^
+    "
+    );
+
+    test_report!(
+        polymorphic_recursion_through_record,
+        indoc!(
+            r"
+            f = \x -> f [{ item: x }]
+
+            f
+            "
+        ),
+        @r"
+    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+
+    I'm inferring a weird self-referential type for `f`:
+
+    4│      f = \x -> f [{ item: x }]
+            ^
+
+    Here is my best effort at writing down the type. You will see ∞ for
+    parts of the type that repeat something already printed out
+    infinitely.
+
+        List { item : ∞ } -> *
+
+    The type goes in a circle here:
+
+        a = List { item : a }
+
+    This `List` comes from here:
+
+    4│      f = \x -> f [{ item: x }]
+                        ^^^^^^^^^^^^^
+
+    This record comes from here:
+
+    4│      f = \x -> f [{ item: x }]
+                         ^^^^^^^^^^^
     "
     );
 
@@ -1173,6 +1353,15 @@ mod test_reporting {
 
         List ∞ -> *
 
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    5│      g = \x -> f [x]
+                        ^^^
+
     ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
@@ -1185,6 +1374,15 @@ mod test_reporting {
     infinitely.
 
         List ∞ -> *
+
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    5│      g = \x -> f [x]
+                        ^^^
     "
     );
 
@@ -1282,6 +1480,15 @@ mod test_reporting {
     infinitely.
 
         List ∞ -> *
+
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    5│      f = \x -> f [x]
+                        ^^^
     "
     );
 
@@ -1308,6 +1515,15 @@ mod test_reporting {
     infinitely.
 
         List ∞ -> List *
+
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    5│      f = \x -> f [x]
+                        ^^^
     "
     );
 
@@ -1347,6 +1563,15 @@ mod test_reporting {
 
         List ∞ -> List *
 
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    6│      g = \x -> f [x]
+                        ^^^
+
     ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
@@ -1359,6 +1584,15 @@ mod test_reporting {
     infinitely.
 
         List ∞ -> List *
+
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    6│      g = \x -> f [x]
+                        ^^^
     "
     );
 
@@ -1387,6 +1621,15 @@ mod test_reporting {
 
         List ∞ -> List *
 
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    6│      g = \x -> f [x]
+                        ^^^
+
     ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
@@ -1399,6 +1642,15 @@ mod test_reporting {
     infinitely.
 
         List ∞ -> List *
+
+    The type goes in a circle here:
+
+        a = List a
+
+    This `List` comes from here:
+
+    6│      g = \x -> f [x]
+                        ^^^
     "
     );
 
@@ -2889,7 +3141,7 @@ mod test_reporting {
     6│>          2 -> 4
     7│           _ -> 5
 
-    Any value of this shape will be handled by a previous pattern, so this
+    Any value of this shape will be handled by the 1st pattern, so this
     one should be removed.
     "
     );
@@ -3370,7 +3622,7 @@ mod test_reporting {
         elm_function_syntax,
         indoc!(
             r"
-            f x y = x
+            Num.f x y = x
             "
         ),
         @r#"
@@ -3381,11 +3633,12 @@ mod test_reporting {
     1│  app "test" provides [main] to "./platform"
     2│
     3│  main =
-    4│      f x y = x
-              ^^^
+    4│      Num.f x y = x
+                  ^^^
 
-    Looks like you are trying to define a function. In roc, functions are
-    always written as a lambda, like increment = \n -> n + 1.
+    Looks like you are trying to define a function. A function's name has
+    to be a plain lowercase name, with its arguments on the same line,
+    like increment n = n + 1.
     "#
     );
 
@@ -3412,23 +3665,18 @@ mod test_reporting {
 
     This `Cons` tag application has the type:
 
-        [
-            Cons {} [
-                Cons Str [
-                    Cons {} a,
-                    Nil,
-                ]b as a,
-                Nil,
-            ]b,
-            Nil,
-        ]b
-
     But the type annotation on `x` says it should be:
 
-        [
-            Cons {} a,
-            Nil,
-        ] as a
+        [                      │ [
+            Cons {} [          │     Cons {} a,
+                Cons Str [     │     Nil,
+                    Cons {} a, │ ] as a
+                    Nil,       │
+                ]b as a,       │
+                Nil,           │
+            ]b,                │
+            Nil,               │
+        ]b                     │
     "#
     );
 
@@ -3832,6 +4080,25 @@ mod test_reporting {
     "
     );
 
+    test_report!(
+        module_not_imported_suggests_closest_exposed_name,
+        indoc!(
+            r"
+            Lst.map
+            "
+        ),
+        @r"
+    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+
+    The `Lst` module is not imported:
+
+    4│      Lst.map
+            ^^^^^^^
+
+    Did you mean `List.map`?
+    "
+    );
+
     test_report!(
         optional_record_default_type_error,
         indoc!(
@@ -3948,6 +4215,38 @@ mod test_reporting {
     "
     );
 
+    test_report!(
+        independent_mismatches_in_one_def_are_all_reported,
+        indoc!(
+            r"
+            \r ->
+                a : { x : Str, y : Str, z : Str }
+                a = r
+
+                b : { x : U8, y : Str, z : Str }
+                b = r
+
+                c : { x : Str, y : U8, z : Str }
+                c = r
+
+                d : { x : Str, y : Str, z : U8 }
+                d = r
+
+                { a, b, c, d }
+            "
+        ),
+        |golden| {
+            // Each annotation disagrees with `r` about a different field, so the first mismatch
+            // mustn't poison all of `r` and hide the other two.
+            assert_eq!(golden.matches("── TYPE MISMATCH").count(), 3, "{golden}");
+
+            for name in ["b", "c", "d"] {
+                let expected = format!("Something is off with the body of the `{name}` definition");
+                assert!(golden.contains(&expected), "{golden}");
+            }
+        }
+    );
+
     test_report!(
         optional_record_invalid_when,
         indoc!(
@@ -4168,7 +4467,7 @@ mod test_reporting {
     7│          _ -> 3
                 ^
 
-    Any value of this shape will be handled by a previous pattern, so this
+    Any value of this shape will be handled by the 2nd pattern, so this
     one should be removed.
     "
     );
@@ -4217,6 +4516,20 @@ mod test_reporting {
     "#
     );
 
+    test_no_problem!(
+        underscore_prefixed_names_are_not_unused,
+        indoc!(
+            r"
+            f = \_foo, bar ->
+                when bar is
+                    Ok _value -> 1
+                    Err _ -> 2
+
+            f {} (Ok {})
+            "
+        )
+    );
+
     test_report!(
         qualified_tag,
         indoc!(
@@ -6678,22 +6991,6 @@ In roc, functions are always written as a lambda, like{}
     But * needs its 2nd argument to be:
 
         Num *
-
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
-
-    Something is off with the body of the `mult` definition:
-
-    4│      mult : Num.Num *, Num.F64 -> Num.F64
-    5│      mult = \a, b -> a * b
-                            ^^^^^
-
-    This `mul` call produces:
-
-        Num *
-
-    But the type annotation on `mult` says it should be:
-
-        F64
     "
     );
 
@@ -6722,22 +7019,6 @@ In roc, functions are always written as a lambda, like{}
     But * needs its 2nd argument to be:
 
         Num a
-
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
-
-    Something is off with the body of the `mult` definition:
-
-    4│      mult : Num.Num a, Num.F64 -> Num.F64
-    5│      mult = \a, b -> a * b
-                            ^^^^^
-
-    This `mul` call produces:
-
-        Num a
-
-    But the type annotation on `mult` says it should be:
-
-        F64
     "
     );
 
@@ -7322,8 +7603,13 @@ In roc, functions are always written as a lambda, like{}
                         But `use` needs its 1st argument to be:
 
                             {}
+
+                        That requirement comes from the annotation on `use`:
+
+                        4│      use : Num.{} -> Num.U8
+                                      ^^^^^^^^^^^^^^^^
                         "
-                    ), number, $suffix, carets, kind, typ, bad_type);
+                    ), number, $suffix, carets, kind, typ, bad_type, bad_type);
 
                     assert_eq!(golden, real);
                 }
@@ -9028,7 +9314,36 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        le
+        le : Id, Id -> Bool
+    "
+    );
+
+    test_report!(
+        ability_specialization_is_incomplete_for_parameterized_opaque,
+        indoc!(
+            r#"
+            app "test" provides [Pair, pairEq] to "./platform"
+
+            MEq implements
+                eq : a, a -> Bool where a implements MEq
+                le : a, a -> Bool where a implements MEq
+
+            Pair x := (x, x) implements [MEq {eq: pairEq}]
+
+            pairEq = \@Pair _, @Pair _ -> Bool.true
+            "#
+        ),
+        @r"
+    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+
+    This type does not fully implement the `MEq` ability:
+
+    7│  Pair x := (x, x) implements [MEq {eq: pairEq}]
+                                     ^^^^^^^^^^^^^^^^
+
+    The following necessary members are missing implementations:
+
+        le : Pair x, Pair x -> Bool
     "
     );
 
@@ -9713,6 +10028,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in the `.x` field.
+
     Tip: `A` does not implement `Encoding`. Consider adding a custom
     implementation or `implements Encode.Encoding` to the definition of `A`.
     "
@@ -9783,7 +10100,7 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        eq
+        eq : A, A -> U64
     "
     );
 
@@ -9824,7 +10141,7 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        eq
+        eq : A, A -> Bool
     "
     );
 
@@ -9862,7 +10179,7 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        eq
+        eq : A, A -> Bool
     "
     );
 
@@ -9902,7 +10219,8 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        toEncoder
+        toEncoder : A ->
+        Encoder fmt where fmt implements EncoderFormatting
     "
     );
 
@@ -9937,7 +10255,7 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        eq
+        eq : A, A -> Bool
     "
     );
 
@@ -9974,7 +10292,7 @@ In roc, functions are always written as a lambda, like{}
 
     The following necessary members are missing implementations:
 
-        eq
+        eq : A, A -> Bool
     "
     );
 
@@ -11212,6 +11530,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in the `.x` field.
+
     Tip: `A` does not implement `Decoding`. Consider adding a custom
     implementation or `implements Decode.Decoding` to the definition of `A`.
     "###
@@ -11300,17 +11620,12 @@ In roc, functions are always written as a lambda, like{}
 
     The body is a record of type:
 
-        {
-            a : Str,
-            b : Str,
-        }
-
     But the type annotation on `f` says it should be:
 
-        {
-            a : Str,
-            b ? Str,
-        }
+        {            │ {
+            a : Str, │     a : Str,
+            b : Str, │     b ? Str,
+        }            │ }
 
     Tip: To extract the `.b` field it must be non-optional, but the type
     says this field is optional. Learn more about optional fields at TODO.
@@ -11909,6 +12224,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in the payload of the `A` tag.
+
     Note: `Hash` cannot be generated for functions.
     "
     );
@@ -11958,6 +12275,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in the 2nd element of a tuple.
+
     Note: `Hash` cannot be generated for functions.
     "#
     );
@@ -12295,6 +12614,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in the payload of the `A` tag.
+
     Note: `Eq` cannot be generated for functions.
     "
     );
@@ -12345,6 +12666,29 @@ In roc, functions are always written as a lambda, like{}
     "
     );
 
+    test_report!(
+        cannot_derive_eq_names_path_to_underivable_type,
+        indoc!(
+            r#"
+             app "test" provides [main] to "./platform"
+
+             foo : a -> {} where a implements Eq
+
+             main = foo { name: "", handler: Ok (\x -> x) }
+             "#
+        ),
+        |golden| {
+            // The sentence is reflowed, so compare it with the line breaks taken out.
+            let flowed = golden.split_whitespace().collect::<Vec<_>>().join(" ");
+            assert!(
+                flowed.contains(
+                    "It's found in the payload of the `Ok` tag, inside the `.handler` field."
+                ),
+                "{golden}"
+            );
+        }
+    );
+
     test_no_problem!(
         derive_eq_for_tuple,
         indoc!(
@@ -12390,6 +12734,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in the 2nd element of a tuple.
+
     Note: I can't derive `Bool.isEq` for floating-point types. That's
     because Roc's floating-point numbers cannot be compared for total
     equality - in Roc, `NaN` is never comparable to `NaN`. If a type
@@ -12974,7 +13320,7 @@ In roc, functions are always written as a lambda, like{}
     8│>          [.., A] -> ""
     9│           [..] -> ""
 
-    Any value of this shape will be handled by a previous pattern, so this
+    Any value of this shape will be handled by the 1st pattern, so this
     one should be removed.
     "#
     );
@@ -13333,7 +13679,7 @@ In roc, functions are always written as a lambda, like{}
      9│>          [_] -> ""
     10│           [..] -> ""
 
-    Any value of this shape will be handled by a previous pattern, so this
+    Any value of this shape will be handled by the 2nd pattern, so this
     one should be removed.
     "#
     );
@@ -13361,8 +13707,8 @@ In roc, functions are always written as a lambda, like{}
     9│          [..] -> ""
                 ^^^^
 
-    Any value of this shape will be handled by a previous pattern, so this
-    one should be removed.
+    Any value of this shape will be handled by the patterns before it, so
+    this one should be removed.
     "#
     );
 
@@ -13389,7 +13735,7 @@ In roc, functions are always written as a lambda, like{}
     9│          [.., _] -> ""
                 ^^^^^^^
 
-    Any value of this shape will be handled by a previous pattern, so this
+    Any value of this shape will be handled by the 2nd pattern, so this
     one should be removed.
     "#
     );
@@ -13416,7 +13762,7 @@ In roc, functions are always written as a lambda, like{}
     8│>          [_, .., {}] -> ""
     9│           [..] -> ""
 
-    Any value of this shape will be handled by a previous pattern, so this
+    Any value of this shape will be handled by the 1st pattern, so this
     one should be removed.
     "#
     );
@@ -13435,6 +13781,35 @@ In roc, functions are always written as a lambda, like{}
         )
     );
 
+    test_report!(
+        guarded_pattern_after_unguarded_is_redundant,
+        indoc!(
+            r#"
+            when 1 is
+                _ -> ""
+                n if n > 2 -> ""
+            "#
+        ),
+    @r#"
+    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+
+    The 2nd pattern is redundant:
+
+    4│      when 1 is
+    5│          _ -> ""
+    6│          n if n > 2 -> ""
+                ^
+
+    The 1st pattern has no guard, and handles any value of this shape:
+
+    5│          _ -> ""
+                ^
+
+    The guard is the only difference, so it will never be checked. Move
+    this pattern above that one, or remove it.
+    "#
+    );
+
     test_report!(
         suggest_binding_rigid_var_to_ability,
         indoc!(
@@ -13463,6 +13838,8 @@ In roc, functions are always written as a lambda, like{}
 
     cannot be generated.
 
+    It's found in a type argument of `List`.
+
     Tip: This type variable is not bound to `Eq`. Consider adding an
     `implements` clause to bind the type variable, like
     `where e implements Bool.Eq`
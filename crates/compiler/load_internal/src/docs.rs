@@ -7,6 +7,7 @@ use roc_module::symbol::{IdentIds, ModuleId, ModuleIds, Symbol};
 use roc_parse::ast::AssignedField;
 use roc_parse::ast::{self, ExtractSpaces, TypeHeader};
 use roc_parse::ast::{CommentOrNewline, TypeDef, ValueDef};
+use roc_region::all::Loc;
 
 // Documentation generation requirements
 
@@ -149,6 +150,81 @@ pub fn generate_module_docs(
     }
 }
 
+/// Turns each ```roc code block in the doc comments of a module into a top-level `expect`, so
+/// the examples in the docs run as tests. The block has to end in a `Bool`.
+///
+/// Only the defs, which start at `defs_start`, are rewritten. Every line stays the same length,
+/// so regions in the result still point at the doc comments the code came from.
+pub fn doctests_to_expects(source: &[u8], defs_start: usize) -> Vec<u8> {
+    let mut rewritten = source.to_vec();
+
+    // The start and end of the current block's opening fence, and whether it has any code yet
+    let mut open_block: Option<(usize, usize, bool)> = None;
+
+    let mut line_start = defs_start;
+
+    if line_start > 0 && source.get(line_start - 1) != Some(&b'\n') {
+        line_start = match source[line_start..].iter().position(|b| *b == b'\n') {
+            Some(newline) => line_start + newline + 1,
+            None => source.len(),
+        };
+    }
+
+    while line_start < source.len() {
+        let line_end = source[line_start..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(source.len(), |newline| line_start + newline);
+        let line = &source[line_start..line_end];
+
+        // Only doc comments at the top level, since those are the ones that document defs
+        let is_doc_comment = line.starts_with(b"##") && !line.starts_with(b"###");
+        let text = if is_doc_comment {
+            std::str::from_utf8(&line[2..]).unwrap_or_default().trim()
+        } else {
+            ""
+        };
+        let is_fence = text.starts_with("```");
+
+        match open_block {
+            None if is_fence && text[3..].trim() == "roc" => {
+                rewritten[line_start..line_start + 6].copy_from_slice(b"expect");
+                rewritten[line_start + 6..line_end].fill(b' ');
+
+                open_block = Some((line_start, line_end, false));
+            }
+            None => {}
+            Some((fence_start, fence_end, has_code)) if is_doc_comment && !is_fence => {
+                // Indent the code, so it's the body of the `expect`
+                rewritten[line_start..line_start + 2].fill(b' ');
+
+                open_block = Some((fence_start, fence_end, has_code || !text.is_empty()));
+            }
+            Some((fence_start, fence_end, has_code)) => {
+                // Either the closing fence, or a doc comment that ended without one
+                if is_doc_comment {
+                    rewritten[line_start..line_end].fill(b' ');
+                }
+
+                if !has_code {
+                    rewritten[fence_start..fence_end]
+                        .copy_from_slice(&source[fence_start..fence_end]);
+                }
+
+                open_block = None;
+            }
+        }
+
+        line_start = line_end + 1;
+    }
+
+    if let Some((fence_start, fence_end, false)) = open_block {
+        rewritten[fence_start..fence_end].copy_from_slice(&source[fence_start..fence_end]);
+    }
+
+    rewritten
+}
+
 fn detached_docs_from_comments_and_new_lines<'a>(
     comments_or_new_lines: impl Iterator<Item = &'a roc_parse::ast::CommentOrNewline<'a>>,
 ) -> Vec<String> {
@@ -249,7 +325,16 @@ fn generate_entry_docs(
                 }
 
                 ValueDef::Body(pattern, _) => {
-                    if let Pattern::Identifier { ident: identifier } = pattern.value {
+                    // Also a function defined like `f x y = body`
+                    if let Pattern::Identifier { ident: identifier }
+                    | Pattern::Apply(
+                        Loc {
+                            value: Pattern::Identifier { ident: identifier },
+                            ..
+                        },
+                        _,
+                    ) = &pattern.value
+                    {
                         // Check if this module exposes the def
                         if let Some(ident_id) = ident_ids.get_id(identifier) {
                             let doc_def = DocDef {
@@ -281,6 +366,9 @@ fn generate_entry_docs(
                 ValueDef::IngestedFileImport { .. } => {
                     // Don't generate docs for ingested file imports
                 }
+                ValueDef::Malformed(_) => {
+                    // There's nothing to document in a def that doesn't parse
+                }
 
                 ValueDef::Stmt(loc_expr) => {
                     if let roc_parse::ast::Expr::Var {
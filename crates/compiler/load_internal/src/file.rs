@@ -1,12 +1,13 @@
 #![allow(clippy::too_many_arguments)]
 
-use crate::docs::ModuleDocumentation;
+use crate::docs::{doctests_to_expects, ModuleDocumentation};
 use crate::module::{
     CheckedModule, ConstrainedModule, EntryPoint, Expectations, ExposedToHost,
     FoundSpecializationsModule, LateSpecializationsModule, LoadedModule, ModuleHeader,
     ModuleTiming, MonomorphizedModule, ParsedModule, ToplevelExpects, TypeCheckedModule,
 };
 use crate::module_cache::ModuleCache;
+use crate::type_cache::{specialization_cache, TypeCache, TypeCacheEntry, TypeCacheKey};
 use bumpalo::{collections::CollectIn, Bump};
 use crossbeam::channel::{bounded, Sender};
 use crossbeam::deque::{Injector, Stealer, Worker};
@@ -15,7 +16,7 @@ use parking_lot::Mutex;
 use roc_builtins::roc::module_source;
 use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints, TypeOrVar};
-use roc_can::expr::{DbgLookup, Declarations, ExpectLookup, PendingDerives};
+use roc_can::expr::{DbgLookup, Declarations, ExpectLookup, IncompleteImpls, PendingDerives};
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
     ResolvedImplementations, TypeState,
@@ -27,7 +28,7 @@ use roc_debug_flags::dbg_do;
 use roc_debug_flags::{
     ROC_CHECK_MONO_IR, ROC_PRINT_IR_AFTER_DROP_SPECIALIZATION, ROC_PRINT_IR_AFTER_REFCOUNT,
     ROC_PRINT_IR_AFTER_RESET_REUSE, ROC_PRINT_IR_AFTER_SPECIALIZATION, ROC_PRINT_IR_AFTER_TRMC,
-    ROC_PRINT_LOAD_LOG,
+    ROC_PRINT_LOAD_LOG, ROC_PRINT_MONO_TEXT,
 };
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::internal_error;
@@ -37,14 +38,17 @@ use roc_module::symbol::{
     IdentIds, IdentIdsByModule, Interns, ModuleId, ModuleIds, PQModuleName, PackageModuleIds,
     PackageQualified, Symbol,
 };
+use roc_mono::debug_info::reproducible_path;
 use roc_mono::ir::{
     CapturedSymbols, ExternalSpecializations, GlueLayouts, HostExposedLambdaSets, PartialProc,
     Proc, ProcLayout, Procs, ProcsBase, UpdateModeIds, UsageTrackingMap,
 };
 use roc_mono::layout::{
-    GlobalLayoutInterner, LambdaName, Layout, LayoutCache, LayoutProblem, Niche, STLayoutInterner,
+    GlobalLayoutInterner, LambdaName, Layout, LayoutCache, LayoutProblem, Niche, RawFunctionLayout,
+    STLayoutInterner,
 };
 use roc_mono::reset_reuse;
+use roc_mono::spec_cache::SpecializationCache;
 use roc_mono::{drop_specialization, inc_dec};
 use roc_packaging::cache::RocCacheDir;
 use roc_parse::ast::{self, CommentOrNewline, ExtractSpaces, Spaced, ValueDef};
@@ -52,13 +56,15 @@ use roc_parse::header::{
     self, ExposedName, HeaderType, ImportsKeywordItem, PackageEntry, PackageHeader, PlatformHeader,
     To, TypedIdent,
 };
-use roc_parse::module::parse_module_defs;
+use roc_parse::module::parse_module_defs_recovering;
 use roc_parse::parser::{FileError, SourceError, SyntaxError};
 use roc_problem::Severity;
 use roc_region::all::{LineInfo, Loc, Region};
 #[cfg(not(target_family = "wasm"))]
 use roc_reporting::report::to_https_problem_report_string;
-use roc_reporting::report::{to_file_problem_report_string, Palette, RenderTarget};
+use roc_reporting::report::{
+    to_file_problem_report_string, to_module_not_found_report_string, Palette, RenderTarget,
+};
 use roc_solve::module::{extract_module_owned_implementations, SolveConfig, Solved, SolvedModule};
 use roc_solve::FunctionKind;
 use roc_solve_problem::TypeError;
@@ -93,6 +99,7 @@ const ROC_FILE_EXTENSION: &str = "roc";
 
 /// The . in between module names like Foo.Bar.Baz
 const MODULE_SEPARATOR: char = '.';
+const DEFAULT_MAIN_NAME: &str = "main.roc";
 
 const EXPANDED_STACK_SIZE: usize = 8 * 1024 * 1024;
 
@@ -100,7 +107,7 @@ macro_rules! log {
     ($($arg:tt)*) => (dbg_do!(ROC_PRINT_LOAD_LOG, println!($($arg)*)))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LoadConfig {
     pub target: Target,
     pub render: RenderTarget,
@@ -108,6 +115,14 @@ pub struct LoadConfig {
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
     pub function_kind: FunctionKind,
+    /// Make the program come out the same wherever it's built: embed paths that don't depend
+    /// on where the source is (see [roc_mono::debug_info::reproducible_path]), and load
+    /// modules in the same order every time.
+    pub reproducible: bool,
+    /// In test mode, count how many times each top-level def and `when` branch in the root
+    /// package runs, so `roc test --coverage` can report what the tests didn't reach
+    /// (see [roc_mono::coverage]).
+    pub coverage: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -119,6 +134,9 @@ pub enum ExecutionMode {
     /// Test is like [`ExecutionMode::ExecutableIfCheck`], but rather than producing a proper
     /// executable, run tests.
     Test,
+    /// Like [`ExecutionMode::Test`], but the ```roc code blocks in the doc comments of the root
+    /// package's modules also run as tests.
+    DocTest,
 }
 
 impl ExecutionMode {
@@ -127,12 +145,16 @@ impl ExecutionMode {
 
         match self {
             Executable => Phase::MakeSpecializations,
-            Check | ExecutableIfCheck | Test => Phase::SolveTypes,
+            Check | ExecutableIfCheck | Test | DocTest => Phase::SolveTypes,
         }
     }
 
     fn build_if_checks(&self) -> bool {
-        matches!(self, Self::ExecutableIfCheck | Self::Test)
+        matches!(self, Self::ExecutableIfCheck | Self::Test | Self::DocTest)
+    }
+
+    fn is_test(&self) -> bool {
+        matches!(self, Self::Test | Self::DocTest)
     }
 }
 
@@ -190,10 +212,16 @@ fn start_phase<'a>(
                 // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
 
+                // Builtins and other packages' modules keep their doc examples to themselves
+                let doctests = matches!(state.exec_mode, ExecutionMode::DocTest)
+                    && header.opt_shorthand.is_none()
+                    && !module_id.is_builtin();
+
                 BuildTask::Parse {
                     header,
                     module_ids: Arc::clone(&state.arc_modules),
                     ident_ids_by_module: Arc::clone(&state.ident_ids_by_module),
+                    doctests,
                 }
             }
             Phase::CanonicalizeAndConstrain => {
@@ -288,14 +316,65 @@ fn start_phase<'a>(
                     }
                 }
 
+                let type_cache_key = {
+                    let mut imports: Vec<_> = parsed
+                        .available_modules
+                        .keys()
+                        .filter_map(|imported| {
+                            let key = state.module_cache.type_cache_keys.get(imported)?;
+                            Some((*imported, *key))
+                        })
+                        .collect();
+
+                    TypeCacheKey::new(
+                        module_id,
+                        parsed.src,
+                        state.target,
+                        state.function_kind,
+                        &mut imports,
+                    )
+                };
+
+                state
+                    .module_cache
+                    .type_cache_keys
+                    .insert(module_id, type_cache_key);
+
                 let skip_constraint_gen = {
                     // Give this its own scope to make sure that the Guard from the lock() is dropped
                     // immediately after contains_key returns
-                    state.cached_types.lock().contains_key(&module_id)
+                    let mut cached_types = state.cached_types.lock();
+
+                    // Builtins are cached in the compiler binary itself; plain modules
+                    // of an app or package may have been cached on disk by an earlier run.
+                    let use_disk_cache = !module_id.is_builtin()
+                        && matches!(parsed.header_type, HeaderType::Module { .. });
+
+                    if let (true, Some(type_cache)) = (use_disk_cache, &state.type_cache) {
+                        let entry = type_cache.entry(type_cache_key);
+
+                        match entry.read() {
+                            Some(type_state) => {
+                                log!("using cached types for {:?}", module_id);
+                                cached_types.insert(module_id, type_state);
+                            }
+                            None => {
+                                state
+                                    .module_cache
+                                    .type_cache_entries
+                                    .insert(module_id, entry);
+                            }
+                        }
+                    }
+
+                    cached_types.contains_key(&module_id)
                 };
 
+                let embedded_module_path = state.embedded_module_path(&parsed.module_path);
+
                 BuildTask::CanonicalizeAndConstrain {
                     parsed,
+                    embedded_module_path,
                     dep_idents,
                     exposed_symbols,
                     qualified_module_ids,
@@ -320,6 +399,7 @@ fn start_phase<'a>(
                     declarations,
                     dep_idents,
                     pending_derives,
+                    incomplete_impls,
                     types,
                     ..
                 } = constrained;
@@ -342,12 +422,14 @@ fn start_phase<'a>(
                     constraint,
                     state.function_kind,
                     pending_derives,
+                    incomplete_impls,
                     var_store,
                     available_modules,
                     &state.exposed_types,
                     dep_idents,
                     declarations,
                     state.cached_types.clone(),
+                    state.module_cache.type_cache_entries.remove(&module_id),
                     derived_module,
                     //
                     #[cfg(debug_assertions)]
@@ -371,6 +453,13 @@ fn start_phase<'a>(
                         checkmate: _,
                 } = typechecked;
 
+                state.def_regions.extend(
+                    decls
+                        .symbols
+                        .iter()
+                        .map(|loc_symbol| (loc_symbol.value, loc_symbol.region)),
+                );
+
                 let our_exposed_types = state
                     .exposed_types
                     .get(&module_id)
@@ -397,8 +486,8 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
-                let build_expects =
-                    matches!(state.exec_mode, ExecutionMode::Test) && expectations.is_some();
+                let build_expects = state.exec_mode.is_test() && expectations.is_some();
+                let coverage = state.coverage && is_in_root_package(state, module_id);
 
                 BuildTask::BuildPendingSpecializations {
                     layout_cache,
@@ -415,6 +504,7 @@ fn start_phase<'a>(
                     derived_module,
                     expectations,
                     build_expects,
+                    coverage,
                 }
             }
             Phase::MakeSpecializations => {
@@ -520,6 +610,7 @@ fn start_phase<'a>(
                 }
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
+                let coverage = state.coverage && is_in_root_package(state, module_id);
 
                 BuildTask::MakeSpecializations {
                     module_id,
@@ -534,6 +625,7 @@ fn start_phase<'a>(
                     exposed_by_module: state.exposed_types.clone(),
                     derived_module,
                     expectations,
+                    coverage,
                 }
             }
         }
@@ -542,6 +634,14 @@ fn start_phase<'a>(
     vec![task]
 }
 
+fn is_in_root_package(state: &State, module_id: ModuleId) -> bool {
+    state
+        .arc_modules
+        .lock()
+        .package_eq(module_id, state.root_id)
+        .expect("root or this module is not yet known - that's a bug!")
+}
+
 /// Values used to render expect output
 pub struct ExpectMetadata<'a> {
     pub interns: Interns,
@@ -550,6 +650,7 @@ pub struct ExpectMetadata<'a> {
 }
 
 type LocExpects = VecMap<Region, Vec<ExpectLookup>>;
+type LocExpectComparisons = VecMap<Region, (Symbol, Symbol)>;
 type LocDbgs = VecMap<Symbol, DbgLookup>;
 
 /// A message sent out _from_ a worker thread,
@@ -570,6 +671,7 @@ enum Msg<'a> {
         module_timing: ModuleTiming,
         abilities_store: AbilitiesStore,
         loc_expects: LocExpects,
+        loc_expect_comparisons: LocExpectComparisons,
         loc_dbgs: LocDbgs,
 
         #[cfg(debug_assertions)]
@@ -623,7 +725,8 @@ enum Msg<'a> {
         module_expectations: VecMap<ModuleId, Expectations>,
     },
 
-    FailedToParse(FileError<'a, SyntaxError<'a>>),
+    /// Every syntax error in one file
+    FailedToParse(Vec<FileError<'a, SyntaxError<'a>>>),
     FailedToReadFile {
         filename: PathBuf,
         error: io::ErrorKind,
@@ -686,6 +789,8 @@ struct State<'a> {
     pub root_subs: Option<Subs>,
     pub root_path: PathBuf,
     pub cache_dir: PathBuf,
+    /// Every package URL in a module header, for roc.lock
+    pub package_urls: MutSet<String>,
     /// If the root is an app module, the shorthand specified in its header's `to` field
     pub opt_platform_shorthand: Option<&'a str>,
     pub platform_data: Option<PlatformData<'a>>,
@@ -718,6 +823,9 @@ struct State<'a> {
 
     pub declarations_by_id: MutMap<ModuleId, Declarations>,
 
+    /// Where each top-level def was defined, so backends can emit debug info.
+    pub def_regions: MutMap<Symbol, Region>,
+
     pub exposed_symbols_by_module: MutMap<ModuleId, VecSet<Symbol>>,
 
     pub timings: MutMap<ModuleId, ModuleTiming>,
@@ -732,15 +840,22 @@ struct State<'a> {
     pub render: RenderTarget,
     pub palette: Palette,
     pub exec_mode: ExecutionMode,
+    /// Whether to embed paths in the program the way [LoadConfig::reproducible] describes
+    pub reproducible: bool,
+    /// Whether to count the defs and branches that run, as [LoadConfig::coverage] describes
+    pub coverage: bool,
 
     /// All abilities across all modules.
     pub world_abilities: WorldAbilities,
 
     make_specializations_pass: MakeSpecializationsPass,
 
-    // cached types (used for builtin modules, could include packages in the future too)
+    // cached types (used for builtin modules, and for modules found in the on-disk type cache)
     cached_types: CachedTypeState,
 
+    /// Where solved types of app and package modules are cached between runs, if anywhere
+    type_cache: Option<TypeCache>,
+
     layout_interner: GlobalLayoutInterner<'a>,
 }
 
@@ -751,6 +866,19 @@ impl<'a> State<'a> {
         self.exec_mode.goal_phase()
     }
 
+    /// The path of a module as it's embedded in the program, e.g. in the locations `dbg` prints
+    fn embedded_module_path(&self, module_path: &Path) -> String {
+        if self.reproducible {
+            let root_dir = self.root_path.parent().unwrap_or(Path::new(""));
+
+            reproducible_path(module_path, root_dir)
+                .to_string_lossy()
+                .into_owned()
+        } else {
+            module_path.to_string_lossy().into_owned()
+        }
+    }
+
     fn new(
         root_id: ModuleId,
         root_path: PathBuf,
@@ -761,10 +889,13 @@ impl<'a> State<'a> {
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
         cached_types: MutMap<ModuleId, TypeState>,
+        type_cache: Option<TypeCache>,
         render: RenderTarget,
         palette: Palette,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        reproducible: bool,
+        coverage: bool,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
         let cache_dir = roc_packaging::cache::roc_cache_dir();
@@ -776,6 +907,7 @@ impl<'a> State<'a> {
             root_subs: None,
             opt_platform_shorthand,
             cache_dir,
+            package_urls: MutSet::default(),
             target,
             function_kind,
             platform_data: None,
@@ -794,13 +926,17 @@ impl<'a> State<'a> {
             constrained_ident_ids: IdentIds::exposed_builtins(0),
             ident_ids_by_module,
             declarations_by_id: MutMap::default(),
+            def_regions: MutMap::default(),
             exposed_symbols_by_module: MutMap::default(),
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
+            type_cache,
             render,
             palette,
             exec_mode,
+            reproducible,
+            coverage: coverage && exec_mode.is_test(),
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
             layout_interner: GlobalLayoutInterner::with_capacity(128, target),
@@ -867,9 +1003,11 @@ enum BuildTask<'a> {
         header: ModuleHeader<'a>,
         module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
+        doctests: bool,
     },
     CanonicalizeAndConstrain {
         parsed: ParsedModule<'a>,
+        embedded_module_path: String,
         qualified_module_ids: PackageModuleIds<'a>,
         dep_idents: IdentIdsByModule,
         exposed_symbols: VecSet<Symbol>,
@@ -888,10 +1026,12 @@ enum BuildTask<'a> {
         constraint: ConstraintSoa,
         function_kind: FunctionKind,
         pending_derives: PendingDerives,
+        incomplete_impls: IncompleteImpls,
         var_store: VarStore,
         declarations: Declarations,
         dep_idents: IdentIdsByModule,
         cached_subs: CachedTypeState,
+        type_cache_entry: Option<TypeCacheEntry>,
         derived_module: SharedDerivedModule,
 
         #[cfg(debug_assertions)]
@@ -911,6 +1051,7 @@ enum BuildTask<'a> {
         derived_module: SharedDerivedModule,
         expectations: Option<Expectations>,
         build_expects: bool,
+        coverage: bool,
     },
     MakeSpecializations {
         module_id: ModuleId,
@@ -924,6 +1065,7 @@ enum BuildTask<'a> {
         world_abilities: WorldAbilities,
         derived_module: SharedDerivedModule,
         expectations: Option<Expectations>,
+        coverage: bool,
     },
 }
 
@@ -946,7 +1088,8 @@ pub enum LoadingProblem<'a> {
         filename: PathBuf,
         error: io::ErrorKind,
     },
-    ParsingFailed(FileError<'a, SyntaxError<'a>>),
+    /// Every syntax error in one file
+    ParsingFailed(Vec<FileError<'a, SyntaxError<'a>>>),
     UnexpectedHeader(String),
     MultiplePlatformPackages {
         filename: PathBuf,
@@ -1069,6 +1212,8 @@ pub fn load_and_typecheck_str<'a>(
         threading,
         exec_mode: ExecutionMode::Check,
         function_kind,
+        reproducible: false,
+        coverage: false,
     };
 
     match load(
@@ -1098,6 +1243,8 @@ pub struct LoadStart<'a> {
     root_msg: Msg<'a>,
     opt_platform_shorthand: Option<&'a str>,
     src_dir: PathBuf,
+    reproducible: bool,
+    coverage: bool,
 }
 
 impl<'a> LoadStart<'a> {
@@ -1157,6 +1304,8 @@ impl<'a> LoadStart<'a> {
             root_path: filename,
             root_msg: header_output.msg,
             opt_platform_shorthand: header_output.opt_platform_shorthand,
+            reproducible: false,
+            coverage: false,
         })
     }
 
@@ -1200,6 +1349,8 @@ impl<'a> LoadStart<'a> {
             root_path: filename,
             root_msg,
             opt_platform_shorthand: opt_platform_id,
+            reproducible: false,
+            coverage: false,
         })
     }
 }
@@ -1303,16 +1454,28 @@ pub fn load<'a>(
     roc_cache_dir: RocCacheDir<'_>,
     load_config: LoadConfig,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    let _span = roc_tracing::info_span!("load").entered();
+
     enum Threads {
         Single,
         Many(usize),
     }
 
+    let load_start = LoadStart {
+        reproducible: load_config.reproducible,
+        coverage: load_config.coverage,
+        ..load_start
+    };
+
     let threads = {
         if cfg!(target_family = "wasm") {
             // When compiling to wasm, we cannot spawn extra threads
             // so we have a single-threaded implementation
             Threads::Single
+        } else if load_config.reproducible {
+            // Module ids are handed out in the order modules are found, which depends on
+            // which threads get to them first
+            Threads::Single
         } else {
             match std::thread::available_parallelism().map(|v| v.get()) {
                 Err(_) => Threads::Single,
@@ -1377,6 +1540,8 @@ pub fn load_single_threaded<'a>(
         root_msg,
         src_dir,
         opt_platform_shorthand,
+        reproducible,
+        coverage,
         ..
     } = load_start;
 
@@ -1397,10 +1562,13 @@ pub fn load_single_threaded<'a>(
         arc_modules,
         ident_ids_by_module,
         cached_types,
+        TypeCache::new(roc_cache_dir),
         render,
         palette,
         number_of_workers,
         exec_mode,
+        reproducible,
+        coverage,
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
@@ -1732,6 +1900,8 @@ fn load_multi_threaded<'a>(
         root_msg,
         src_dir,
         opt_platform_shorthand,
+        reproducible,
+        coverage,
         ..
     } = load_start;
 
@@ -1767,10 +1937,13 @@ fn load_multi_threaded<'a>(
         arc_modules,
         ident_ids_by_module,
         cached_types,
+        TypeCache::new(roc_cache_dir),
         render,
         palette,
         num_workers,
         exec_mode,
+        reproducible,
+        coverage,
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -2014,6 +2187,13 @@ fn worker_task_step<'a>(
                             Err(LoadingProblem::IncorrectModuleName(err)) => {
                                 msg_tx.send(Msg::IncorrectModuleName(err)).unwrap();
                             }
+                            Err(LoadingProblem::FormattedReport(report)) => {
+                                msg_tx
+                                    .send(Msg::FailedToLoad(LoadingProblem::FormattedReport(
+                                        report,
+                                    )))
+                                    .unwrap();
+                            }
                             Err(other) => {
                                 return Err(other);
                             }
@@ -2113,6 +2293,11 @@ fn worker_task<'a>(
                         Err(LoadingProblem::IncorrectModuleName(err)) => {
                             msg_tx.send(Msg::IncorrectModuleName(err)).unwrap();
                         }
+                        Err(LoadingProblem::FormattedReport(report)) => {
+                            msg_tx
+                                .send(Msg::FailedToLoad(LoadingProblem::FormattedReport(report)))
+                                .unwrap();
+                        }
                         Err(other) => {
                             return Err(other);
                         }
@@ -2159,6 +2344,33 @@ macro_rules! debug_print_ir {
     };
 }
 
+macro_rules! debug_print_mono_text {
+    ($state:expr, $interner:expr, $flag:path) => {
+        dbg_do!($flag, {
+            let interns = Interns {
+                module_ids: $state.arc_modules.lock().clone().into_module_ids(),
+                all_ident_ids: $state.constrained_ident_ids.clone(),
+            };
+
+            // Sort the procs so that the output is the same from run to run
+            let mut procs = $state
+                .procedures
+                .values()
+                .map(|proc| (proc.to_pretty($interner, 200, true), proc))
+                .collect::<Vec<_>>();
+            procs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            let text = roc_mono::ir::procs_to_mono_text(
+                $interner,
+                &interns,
+                procs.iter().map(|(_, p)| *p),
+            );
+
+            eprintln!("{}", text);
+        })
+    };
+}
+
 macro_rules! debug_check_ir {
     ($state:expr, $arena:expr, $interner:expr, $flag:path) => {
         dbg_do!($flag, {
@@ -2236,12 +2448,16 @@ fn update<'a>(
                             match PackageMetadata::try_from(url) {
                                 Ok(url_metadata) => {
                                     // This was a valid URL
+                                    state.package_urls.insert(url.to_string());
+
                                     let root_module_dir = state
                                         .cache_dir
                                         .join(url_metadata.cache_subdir)
                                         .join(url_metadata.content_hash);
                                     let root_module = root_module_dir.join(
-                                        url_metadata.root_module_filename.unwrap_or("main.roc"),
+                                        url_metadata
+                                            .root_module_filename
+                                            .unwrap_or(DEFAULT_MAIN_NAME),
                                     );
 
                                     ShorthandPath::FromHttpsUrl {
@@ -2526,6 +2742,7 @@ fn update<'a>(
             mut module_timing,
             abilities_store,
             loc_expects,
+            loc_expect_comparisons,
             loc_dbgs,
 
             #[cfg(debug_assertions)]
@@ -2555,6 +2772,7 @@ fn update<'a>(
 
                 Some(Expectations {
                     expectations: loc_expects,
+                    comparisons: loc_expect_comparisons,
                     dbgs: loc_dbgs,
                     subs: solved_subs.clone().into_inner(),
                     path: path.to_owned(),
@@ -2575,7 +2793,7 @@ fn update<'a>(
 
             let add_to_host_exposed = is_host_exposed &&
                 // During testing, we don't need to expose anything to the host.
-                !matches!(state.exec_mode, ExecutionMode::Test);
+                !state.exec_mode.is_test();
 
             if add_to_host_exposed {
                 state.exposed_to_host.top_level_values.extend(
@@ -2738,7 +2956,11 @@ fn update<'a>(
 
             let subs = solved_subs.into_inner();
 
-            if !toplevel_expects.pure.is_empty() || !toplevel_expects.fx.is_empty() {
+            if !toplevel_expects.pure.is_empty()
+                || !toplevel_expects.fx.is_empty()
+                || !toplevel_expects.properties.is_empty()
+                || !toplevel_expects.coverage_sites.is_empty()
+            {
                 state.toplevel_expects.insert(module_id, toplevel_expects);
             }
 
@@ -2959,6 +3181,7 @@ fn update<'a>(
                     );
 
                     debug_print_ir!(state, &layout_interner, ROC_PRINT_IR_AFTER_RESET_REUSE);
+                    debug_print_mono_text!(state, &layout_interner, ROC_PRINT_MONO_TEXT);
 
                     // This is not safe with the new non-recursive RC updates that we do for tag unions
                     //
@@ -3152,7 +3375,7 @@ fn finish_specialization<'a>(
     let entry_point = {
         let interns: &mut Interns = &mut interns;
         match state.exec_mode {
-            ExecutionMode::Test => Ok(EntryPoint::Test),
+            ExecutionMode::Test | ExecutionMode::DocTest => Ok(EntryPoint::Test),
             ExecutionMode::Executable | ExecutionMode::ExecutableIfCheck => {
                 use PlatformPath::*;
 
@@ -3206,9 +3429,16 @@ fn finish_specialization<'a>(
                     }
                 };
 
+                let app_name = state
+                    .root_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+
                 Ok(EntryPoint::Executable {
                     exposed_to_host: exposed_symbols_and_layouts,
                     platform_path,
+                    app_name,
                 })
             }
             ExecutionMode::Check => unreachable!(),
@@ -3221,6 +3451,8 @@ fn finish_specialization<'a>(
         host_exposed_lambda_sets,
         module_cache,
         platform_data,
+        def_regions,
+        package_urls,
         ..
     } = state;
 
@@ -3257,10 +3489,12 @@ fn finish_specialization<'a>(
         host_exposed_lambda_sets,
         entry_point,
         sources,
+        def_regions,
         timings: state.timings,
         toplevel_expects,
         glue_layouts: GlueLayouts { getters: vec![] },
         uses_prebuilt_platform,
+        package_urls,
     })
 }
 
@@ -3353,6 +3587,7 @@ fn finish(
         exposed_imports: state.module_cache.exposed_imports,
         imports: state.module_cache.imports,
         exposes: state.module_cache.exposes,
+        package_urls: state.package_urls,
     }
 }
 
@@ -3464,10 +3699,9 @@ fn load_package_from_disk<'a>(
 
                     Ok(Msg::Header(platform_module_msg))
                 }
-                Err(fail) => Err(LoadingProblem::ParsingFailed(
-                    fail.map_problem(SyntaxError::Header)
-                        .into_file_error(filename.to_path_buf()),
-                )),
+                Err(fail) => Err(LoadingProblem::ParsingFailed(vec![fail
+                    .map_problem(SyntaxError::Header)
+                    .into_file_error(filename.to_path_buf())])),
             }
         }
 
@@ -3571,6 +3805,9 @@ fn load_module<'a>(
     roc_cache_dir: RocCacheDir<'_>,
     ident_ids_by_module: SharedIdentIdsByModule,
 ) -> Result<HeaderOutput<'a>, LoadingProblem<'a>> {
+    let _span =
+        roc_tracing::info_span!("load module", module = module_name.as_inner().as_str()).entered();
+
     let module_start_time = Instant::now();
 
     let parse_start = Instant::now();
@@ -3616,11 +3853,13 @@ fn load_module<'a>(
         "Hash", ModuleId::HASH
         "Inspect", ModuleId::INSPECT
         "TotallyNotJson", ModuleId::JSON
+        "JsonFormat", ModuleId::JSON_FORMAT
     }
 
     let (filename, opt_shorthand) = module_name_to_path(src_dir, &module_name, arc_shorthands);
+    let name = module_name.as_inner().as_str().to_string();
 
-    load_filename(
+    match load_filename(
         arena,
         filename,
         false,
@@ -3630,7 +3869,15 @@ fn load_module<'a>(
         ident_ids_by_module,
         roc_cache_dir,
         module_start_time,
-    )
+    ) {
+        Err(LoadingProblem::FileProblem {
+            filename,
+            error: io::ErrorKind::NotFound,
+        }) => Err(LoadingProblem::FormattedReport(
+            to_module_not_found_report_string(&name, &[filename]),
+        )),
+        result => result,
+    }
 }
 
 #[derive(Debug)]
@@ -3670,6 +3917,34 @@ impl ShorthandPath {
     }
 }
 
+/// The name of a module that was loaded directly, rather than imported: its path from the
+/// nearest directory above it with a `main.roc`, with dots in place of slashes
+/// (so `src/Foo/Bar.roc` is `Foo.Bar` if there's a `src/main.roc`).
+///
+/// That's the name other modules in the package import it by, which lets `roc check` and
+/// `roc test` resolve its imports from the package root, just like they would be when it's
+/// imported.
+fn root_module_name(filename: &Path) -> String {
+    let stem = filename.file_stem().unwrap().to_str().unwrap();
+    let mut parts = vec![stem];
+
+    for dir in filename.ancestors().skip(1) {
+        if dir.join(DEFAULT_MAIN_NAME).is_file() {
+            parts.reverse();
+
+            return parts.join(".");
+        }
+
+        // Only directories with module-like names can be part of a module name
+        match dir.file_name().and_then(|name| name.to_str()) {
+            Some(name) if name.starts_with(|c: char| c.is_ascii_uppercase()) => parts.push(name),
+            _ => break,
+        }
+    }
+
+    stem.to_string()
+}
+
 fn module_name_to_path<'a>(
     src_dir: &Path,
     module_name: &PQModuleName<'a>,
@@ -3809,10 +4084,7 @@ fn parse_header<'a>(
         )) => {
             let module_name = match opt_expected_module_name {
                 Some(pq_name) => arena.alloc_str(pq_name.as_inner().as_str()),
-                None => {
-                    // [modules-revamp] [privacy-changes] TODO: Support test/check on nested modules
-                    arena.alloc_str(filename.file_stem().unwrap().to_str().unwrap())
-                }
+                None => arena.alloc_str(&root_module_name(&filename)),
             };
 
             let info = HeaderInfo {
@@ -4033,10 +4305,9 @@ fn parse_header<'a>(
                 opt_platform_shorthand: None,
             })
         }
-        Err(fail) => Err(LoadingProblem::ParsingFailed(
-            fail.map_problem(SyntaxError::Header)
-                .into_file_error(filename),
-        )),
+        Err(fail) => Err(LoadingProblem::ParsingFailed(vec![fail
+            .map_problem(SyntaxError::Header)
+            .into_file_error(filename)])),
     }
 }
 
@@ -4083,7 +4354,7 @@ fn load_packages<'a>(
                         // (defaults to main.roc)
                         match opt_root_module {
                             Some(root_module) => package_dir.join(root_module),
-                            None => package_dir.join("main.roc"),
+                            None => package_dir.join(DEFAULT_MAIN_NAME),
                         }
                     }
                     Err(problem) => {
@@ -4278,12 +4549,14 @@ impl<'a> BuildTask<'a> {
         constraint: ConstraintSoa,
         function_kind: FunctionKind,
         pending_derives: PendingDerives,
+        incomplete_impls: IncompleteImpls,
         var_store: VarStore,
         imported_modules: MutMap<ModuleId, Region>,
         exposed_types: &ExposedByModule,
         dep_idents: IdentIdsByModule,
         declarations: Declarations,
         cached_subs: CachedTypeState,
+        type_cache_entry: Option<TypeCacheEntry>,
         derived_module: SharedDerivedModule,
 
         #[cfg(debug_assertions)] checkmate: Option<roc_checkmate::Collector>,
@@ -4302,11 +4575,13 @@ impl<'a> BuildTask<'a> {
             constraint,
             function_kind,
             pending_derives,
+            incomplete_impls,
             var_store,
             declarations,
             dep_idents,
             module_timing,
             cached_subs,
+            type_cache_entry,
             derived_module,
 
             #[cfg(debug_assertions)]
@@ -4581,6 +4856,7 @@ fn run_solve_solve(
     constraint: ConstraintSoa,
     function_kind: FunctionKind,
     pending_derives: PendingDerives,
+    incomplete_impls: IncompleteImpls,
     var_store: VarStore,
     module: Module,
     derived_module: SharedDerivedModule,
@@ -4635,6 +4911,7 @@ fn run_solve_solve(
             root_constraint: actual_constraint,
             function_kind,
             pending_derives,
+            incomplete_impls,
             exposed_by_module: &exposed_for_module.exposed_by_module,
             derived_module,
             #[cfg(debug_assertions)]
@@ -4708,10 +4985,12 @@ fn run_solve<'a>(
     constraint: ConstraintSoa,
     function_kind: FunctionKind,
     pending_derives: PendingDerives,
+    incomplete_impls: IncompleteImpls,
     var_store: VarStore,
     decls: Declarations,
     dep_idents: IdentIdsByModule,
     cached_types: CachedTypeState,
+    type_cache_entry: Option<TypeCacheEntry>,
     derived_module: SharedDerivedModule,
 
     #[cfg(debug_assertions)] checkmate: Option<roc_checkmate::Collector>,
@@ -4720,67 +4999,56 @@ fn run_solve<'a>(
 
     let module_id = module.module_id;
 
+    let _span = roc_tracing::info_span!("solve", module = ?module_id).entered();
+
     // TODO remove when we write builtins in roc
     let aliases = module.aliases.clone();
 
     let mut module = module;
     let loc_expects = std::mem::take(&mut module.loc_expects);
+    let loc_expect_comparisons = std::mem::take(&mut module.loc_expect_comparisons);
     let loc_dbgs = std::mem::take(&mut module.loc_dbgs);
     let module = module;
 
-    let solve_result = {
-        if module_id.is_builtin() {
-            match cached_types.lock().remove(&module_id) {
-                None => run_solve_solve(
-                    exposed_for_module,
-                    types,
-                    constraints,
-                    constraint,
-                    function_kind,
-                    pending_derives,
-                    var_store,
-                    module,
-                    derived_module,
-                    //
-                    #[cfg(debug_assertions)]
-                    checkmate,
-                ),
-                Some(TypeState {
-                    subs,
-                    exposed_vars_by_symbol,
-                    abilities,
-                    solved_implementations,
-                }) => SolveResult {
-                    solved: Solved(subs),
-                    solved_implementations,
-                    exposed_vars_by_symbol,
-                    problems: vec![],
-                    abilities_store: abilities,
+    // Builtins are always cached in the compiler binary; other modules may have been
+    // loaded from the on-disk type cache.
+    let opt_cached_types = { cached_types.lock().remove(&module_id) };
 
-                    #[cfg(debug_assertions)]
-                    checkmate: None,
-                },
-            }
-        } else {
-            run_solve_solve(
-                exposed_for_module,
-                types,
-                constraints,
-                constraint,
-                function_kind,
-                pending_derives,
-                var_store,
-                module,
-                derived_module,
-                //
-                #[cfg(debug_assertions)]
-                checkmate,
-            )
-        }
+    let solve_result = match opt_cached_types {
+        None => run_solve_solve(
+            exposed_for_module,
+            types,
+            constraints,
+            constraint,
+            function_kind,
+            pending_derives,
+            incomplete_impls,
+            var_store,
+            module,
+            derived_module,
+            //
+            #[cfg(debug_assertions)]
+            checkmate,
+        ),
+        Some(TypeState {
+            subs,
+            exposed_vars_by_symbol,
+            abilities,
+            solved_implementations,
+        }) => SolveResult {
+            solved: Solved(subs),
+            solved_implementations,
+            exposed_vars_by_symbol,
+            problems: vec![],
+            abilities_store: abilities,
+
+            #[cfg(debug_assertions)]
+            checkmate: None,
+        },
     };
 
     let SolveResult {
-        solved: mut solved_subs,
+        solved: solved_subs,
         solved_implementations,
         exposed_vars_by_symbol,
         problems,
@@ -4790,6 +5058,34 @@ fn run_solve<'a>(
         checkmate,
     } = solve_result;
 
+    // Only cache modules that solved cleanly; otherwise a cache hit would hide their problems.
+    let (mut solved_subs, exposed_vars_by_symbol, abilities_store, solved_implementations) =
+        match type_cache_entry {
+            Some(entry) if problems.is_empty() => {
+                let type_state = TypeState {
+                    subs: solved_subs.into_inner(),
+                    exposed_vars_by_symbol,
+                    abilities: abilities_store,
+                    solved_implementations,
+                };
+
+                entry.write(&type_state);
+
+                (
+                    Solved(type_state.subs),
+                    type_state.exposed_vars_by_symbol,
+                    type_state.abilities,
+                    type_state.solved_implementations,
+                )
+            }
+            _ => (
+                solved_subs,
+                exposed_vars_by_symbol,
+                abilities_store,
+                solved_implementations,
+            ),
+        };
+
     let exposed_types = roc_solve::module::exposed_types_storage_subs(
         module_id,
         &mut solved_subs,
@@ -4821,6 +5117,7 @@ fn run_solve<'a>(
         module_timing,
         abilities_store,
         loc_expects,
+        loc_expect_comparisons,
         loc_dbgs,
 
         #[cfg(debug_assertions)]
@@ -4901,11 +5198,13 @@ fn build_platform_header<'a>(
         header.requires.item.signature.region,
         header.requires.item.signature.extract_spaces().item,
     )]);
+    // Every provided value is paired with the app's `requires` value; zipping the two lists
+    // directly would silently drop everything after the first entry in `provides`.
     let provides = bumpalo::collections::Vec::from_iter_in(
         unspace(arena, header.provides.item.items)
             .iter()
             .copied()
-            .zip(requires.iter().copied()),
+            .zip(std::iter::repeat(requires[0])),
         arena,
     );
     let exposes = bumpalo::collections::Vec::from_iter_in(
@@ -4947,11 +5246,15 @@ fn canonicalize_and_constrain<'a>(
     aliases: MutMap<Symbol, Alias>,
     imported_abilities_state: PendingAbilitiesStore,
     parsed: ParsedModule<'a>,
+    embedded_module_path: &str,
     skip_constraint_gen: bool,
     exposed_module_ids: &[ModuleId],
 ) -> CanAndCon {
     let canonicalize_start = Instant::now();
 
+    let canonicalize_span =
+        roc_tracing::info_span!("canonicalize", module = ?parsed.module_id).entered();
+
     let ParsedModule {
         module_id,
         module_path,
@@ -4981,6 +5284,7 @@ fn canonicalize_and_constrain<'a>(
         &header_type,
         module_id,
         &*arena.alloc(module_path.to_string_lossy()),
+        arena.alloc_str(embedded_module_path),
         src,
         qualified_module_ids,
         exposed_ident_ids,
@@ -5011,6 +5315,8 @@ fn canonicalize_and_constrain<'a>(
 
     module_timing.canonicalize = canonicalize_end.duration_since(canonicalize_start);
 
+    canonicalize_span.exit();
+
     // Generate documentation information
     // TODO: store timing information?
     let module_docs = {
@@ -5036,6 +5342,8 @@ fn canonicalize_and_constrain<'a>(
 
     let mut constraints = Constraints::new();
 
+    let constrain_span = roc_tracing::info_span!("constrain", module = ?module_id).entered();
+
     let constraint = if skip_constraint_gen {
         roc_can::constraint::Constraint::True
     } else {
@@ -5049,6 +5357,8 @@ fn canonicalize_and_constrain<'a>(
         )
     };
 
+    constrain_span.exit();
+
     // _after has an underscore because it's unused in --release builds
     let _after = roc_types::types::get_type_clone_count();
 
@@ -5100,6 +5410,7 @@ fn canonicalize_and_constrain<'a>(
         rigid_variables: module_output.rigid_variables,
         abilities_store: module_output.scope.abilities_store,
         loc_expects: module_output.loc_expects,
+        loc_expect_comparisons: module_output.loc_expect_comparisons,
         loc_dbgs: module_output.loc_dbgs,
     };
 
@@ -5115,6 +5426,7 @@ fn canonicalize_and_constrain<'a>(
         module_timing,
         types,
         pending_derives: module_output.pending_derives,
+        incomplete_impls: module_output.incomplete_impls,
     };
 
     CanAndCon {
@@ -5129,23 +5441,49 @@ fn parse<'a>(
     header: ModuleHeader<'a>,
     module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
     ident_ids_by_module: SharedIdentIdsByModule,
+    doctests: bool,
 ) -> Result<Msg<'a>, LoadingProblem<'a>> {
+    // Module ids are all that later phases have to identify a module by, so this is where
+    // they can be matched up with files
+    let _span = roc_tracing::info_span!(
+        "parse",
+        module = ?header.module_id,
+        path = %header.module_path.display()
+    )
+    .entered();
+
     let mut module_timing = header.module_timing;
     let parse_start = Instant::now();
-    let source = header.parse_state.original_bytes();
-    let parse_state = header.parse_state;
+    let mut parse_state = header.parse_state;
+
+    if doctests {
+        // The rewritten source is just as long as the original, so the header's
+        // regions, and the parse state, still line up with it
+        let rewritten = doctests_to_expects(
+            parse_state.original_bytes(),
+            parse_state.pos().offset as usize,
+        );
+
+        parse_state = parse_state.with_original_bytes(arena.alloc_slice_copy(&rewritten));
+    }
+
+    let source = parse_state.original_bytes();
 
     let header_import_defs =
         roc_parse::ast::Module::header_imports_to_defs(arena, header.header_imports);
 
-    let parsed_defs = match parse_module_defs(arena, parse_state.clone(), header_import_defs) {
-        Ok(success) => success,
-        Err(fail) => {
-            return Err(LoadingProblem::ParsingFailed(
-                fail.into_file_error(header.module_path, &parse_state),
-            ));
-        }
-    };
+    // Keep parsing past a def that doesn't parse, so that every syntax error gets reported at once
+    let (parsed_defs, syntax_errors) =
+        parse_module_defs_recovering(arena, parse_state.clone(), header_import_defs);
+
+    if !syntax_errors.is_empty() {
+        let problems = syntax_errors
+            .into_iter()
+            .map(|fail| fail.into_file_error(header.module_path.clone(), &parse_state))
+            .collect();
+
+        return Err(LoadingProblem::ParsingFailed(problems));
+    }
 
     // Record the parse end time once, to avoid checking the time a second time
     // immediately afterward (for the beginning of canonicalization).
@@ -5409,7 +5747,11 @@ fn make_specializations<'a>(
     exposed_by_module: &ExposedByModule,
     derived_module: SharedDerivedModule,
     mut expectations: Option<Expectations>,
+    specialization_cache: Option<&SpecializationCache>,
+    coverage: bool,
 ) -> Msg<'a> {
+    let _span = roc_tracing::info_span!("make specializations", module = ?home).entered();
+
     let make_specializations_start = Instant::now();
     let mut update_mode_ids = UpdateModeIds::new();
     // do the thing
@@ -5427,6 +5769,8 @@ fn make_specializations<'a>(
         exposed_by_module,
         derived_module: &derived_module,
         struct_indexing: UsageTrackingMap::default(),
+        specialization_cache,
+        coverage,
     };
 
     let mut procs = Procs::new_in(arena);
@@ -5481,6 +5825,13 @@ fn make_specializations<'a>(
     }
 }
 
+/// Whether a top-level function is a property for `roc test --fuzz`, like `fuzzAddCommutes`
+fn is_fuzz_property_name(name: Option<&str>) -> bool {
+    name.and_then(|name| name.strip_prefix("fuzz"))
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(char::is_uppercase)
+}
+
 fn build_pending_specializations<'a>(
     arena: &'a Bump,
     solved_subs: Solved<Subs>,
@@ -5497,12 +5848,20 @@ fn build_pending_specializations<'a>(
     derived_module: SharedDerivedModule,
     mut expectations: Option<Expectations>,
     build_expects: bool,
+    coverage: bool,
 ) -> Msg<'a> {
+    let _span = roc_tracing::info_span!("find specializations", module = ?home).entered();
+
     let find_specializations_start = Instant::now();
 
     let mut module_thunks = bumpalo::collections::Vec::new_in(arena);
     let mut toplevel_expects = ToplevelExpects::default();
 
+    if coverage {
+        toplevel_expects.coverage_sites =
+            roc_mono::coverage::coverage_sites(home, &declarations, solved_subs.inner());
+    }
+
     let mut procs_base = ProcsBase {
         partial_procs: BumpMap::default(),
         module_thunks: &[],
@@ -5530,6 +5889,8 @@ fn build_pending_specializations<'a>(
         exposed_by_module,
         derived_module: &derived_module,
         struct_indexing: UsageTrackingMap::default(),
+        specialization_cache: None,
+        coverage,
     };
 
     let layout_cache_snapshot = layout_cache.snapshot();
@@ -5672,6 +6033,31 @@ fn build_pending_specializations<'a>(
                     );
                 }
 
+                // `roc test --fuzz` calls properties with generated arguments, so they need to be
+                // specialized just like functions exposed to the host
+                if build_expects
+                    && !is_host_exposed
+                    && is_fuzz_property_name(mono_env.ident_ids.get_name(symbol.ident_id()))
+                {
+                    let layout_result =
+                        layout_cache.raw_from_var(mono_env.arena, expr_var, mono_env.subs);
+
+                    if let Ok(RawFunctionLayout::Function(_, _, Layout::BOOL)) = layout_result {
+                        procs_base.host_specializations.insert_host_exposed(
+                            mono_env.subs,
+                            LambdaName::no_niche(symbol),
+                            None,
+                            expr_var,
+                        );
+
+                        let name_region = declarations.symbols[index].region;
+                        let expr_region = declarations.expressions[index].region;
+                        let region = Region::span_across(&name_region, &expr_region);
+
+                        toplevel_expects.properties.insert(symbol, region);
+                    }
+                }
+
                 let is_recursive = matches!(tag, Recursive(_) | TailRecursive(_));
 
                 register_toplevel_function_into_procs_base(
@@ -5834,6 +6220,10 @@ fn build_pending_specializations<'a>(
                 let region = Region::span_across(&name_region, &expr_region);
 
                 toplevel_expects.pure.insert(symbol, region);
+
+                if let Some(def_name) = declarations.expect_def_names.get(&index) {
+                    toplevel_expects.def_names.insert(symbol, *def_name);
+                }
                 procs_base.partial_procs.insert(symbol, proc);
             }
             ExpectationFx => {
@@ -5906,6 +6296,10 @@ fn build_pending_specializations<'a>(
                 let region = Region::span_across(&name_region, &expr_region);
 
                 toplevel_expects.fx.insert(symbol, region);
+
+                if let Some(def_name) = declarations.expect_def_names.get(&index) {
+                    toplevel_expects.def_names.insert(symbol, *def_name);
+                }
                 procs_base.partial_procs.insert(symbol, proc);
             }
         }
@@ -6011,6 +6405,8 @@ fn load_derived_partial_procs<'a>(
             exposed_by_module,
             derived_module,
             struct_indexing: UsageTrackingMap::default(),
+            specialization_cache: None,
+            coverage: false,
         };
 
         let partial_proc = match derived_expr {
@@ -6098,9 +6494,11 @@ fn run_task<'a>(
             header,
             module_ids,
             ident_ids_by_module,
-        } => parse(arena, header, module_ids, ident_ids_by_module),
+            doctests,
+        } => parse(arena, header, module_ids, ident_ids_by_module, doctests),
         CanonicalizeAndConstrain {
             parsed,
+            embedded_module_path,
             qualified_module_ids,
             dep_idents,
             exposed_symbols,
@@ -6117,6 +6515,7 @@ fn run_task<'a>(
                 aliases,
                 abilities_store,
                 parsed,
+                &embedded_module_path,
                 skip_constraint_gen,
                 exposed_module_ids,
             );
@@ -6132,11 +6531,13 @@ fn run_task<'a>(
             constraint,
             function_kind,
             pending_derives,
+            incomplete_impls,
             var_store,
             ident_ids,
             declarations,
             dep_idents,
             cached_subs,
+            type_cache_entry,
             derived_module,
 
             #[cfg(debug_assertions)]
@@ -6151,10 +6552,12 @@ fn run_task<'a>(
             constraint,
             function_kind,
             pending_derives,
+            incomplete_impls,
             var_store,
             declarations,
             dep_idents,
             cached_subs,
+            type_cache_entry,
             derived_module,
             //
             #[cfg(debug_assertions)]
@@ -6174,6 +6577,7 @@ fn run_task<'a>(
             derived_module,
             expectations,
             build_expects,
+            coverage,
         } => Ok(build_pending_specializations(
             arena,
             solved_subs,
@@ -6190,6 +6594,7 @@ fn run_task<'a>(
             derived_module,
             expectations,
             build_expects,
+            coverage,
         )),
         MakeSpecializations {
             module_id,
@@ -6203,6 +6608,7 @@ fn run_task<'a>(
             exposed_by_module,
             derived_module,
             expectations,
+            coverage,
         } => Ok(make_specializations(
             arena,
             module_id,
@@ -6217,6 +6623,8 @@ fn run_task<'a>(
             &exposed_by_module,
             derived_module,
             expectations,
+            specialization_cache(roc_cache_dir).as_ref(),
+            coverage,
         )),
     }?;
 
@@ -6426,7 +6834,7 @@ fn to_multiple_platform_packages_report(
 }
 
 fn to_parse_problem_report<'a>(
-    problem: FileError<'a, SyntaxError<'a>>,
+    problems: Vec<FileError<'a, SyntaxError<'a>>>,
     mut module_ids: ModuleIds,
     all_ident_ids: IdentIdsByModule,
     render: RenderTarget,
@@ -6434,8 +6842,9 @@ fn to_parse_problem_report<'a>(
 ) -> String {
     use roc_reporting::report::{parse_problem, RocDocAllocator};
 
+    // All of the problems are in the same file
     // TODO this is not in fact safe
-    let src = unsafe { from_utf8_unchecked(problem.problem.bytes) };
+    let src = unsafe { from_utf8_unchecked(problems[0].problem.bytes) };
     let src_lines = src.lines().collect::<Vec<_>>();
     // let mut src_lines: Vec<&str> = problem.prefix.lines().collect();
     // src_lines.extend(src.lines().skip(1));
@@ -6454,17 +6863,23 @@ fn to_parse_problem_report<'a>(
 
     let lines = LineInfo::new(src);
 
-    let report = parse_problem(
-        &alloc,
-        &lines,
-        problem.filename.clone(),
-        starting_line,
-        problem,
-    );
-
     let mut buf = String::new();
 
-    report.render(render, &mut buf, &alloc, &palette);
+    for problem in problems {
+        if !buf.is_empty() {
+            buf.push_str("\n\n");
+        }
+
+        let report = parse_problem(
+            &alloc,
+            &lines,
+            problem.filename.clone(),
+            starting_line,
+            problem,
+        );
+
+        report.render(render, &mut buf, &alloc, &palette);
+    }
 
     buf
 }
@@ -8,6 +8,7 @@ pub mod docs;
 pub mod file;
 pub mod module;
 mod module_cache;
+mod type_cache;
 mod work;
 
 #[cfg(target_family = "wasm")]
@@ -27,4 +28,5 @@ pub const BUILTIN_MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::HASH, "Hash"),
     (ModuleId::INSPECT, "Inspect"),
     (ModuleId::JSON, "TotallyNotJson"),
+    (ModuleId::JSON_FORMAT, "JsonFormat"),
 ];
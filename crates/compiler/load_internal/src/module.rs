@@ -3,7 +3,7 @@ use roc_can::constraint::{Constraint as ConstraintSoa, Constraints};
 use roc_can::expr::{DbgLookup, ExpectLookup};
 use roc_can::{
     abilities::AbilitiesStore,
-    expr::{Declarations, PendingDerives},
+    expr::{Declarations, IncompleteImpls, PendingDerives},
     module::{Module, ResolvedImplementations},
 };
 use roc_collections::{MutMap, MutSet, VecMap};
@@ -11,6 +11,7 @@ use roc_module::ident::Ident;
 use roc_module::symbol::{
     IdentIds, IdentIdsByModule, Interns, ModuleId, PQModuleName, PackageQualified, Symbol,
 };
+use roc_mono::coverage::CoverageSite;
 use roc_mono::ir::{GlueLayouts, HostExposedLambdaSets, LambdaSetId, Proc, ProcLayout, ProcsBase};
 use roc_mono::layout::{LayoutCache, STLayoutInterner};
 use roc_parse::ast::{CommentOrNewline, Defs, TypeAnnotation};
@@ -52,6 +53,9 @@ pub struct LoadedModule {
     pub imports: MutMap<ModuleId, MutSet<ModuleId>>,
     pub exposed_imports: MutMap<ModuleId, MutMap<Symbol, Region>>,
     pub exposes: MutMap<ModuleId, Vec<(Symbol, Variable)>>,
+
+    /// The URL of every package loaded over HTTPS
+    pub package_urls: MutSet<String>,
 }
 
 impl LoadedModule {
@@ -120,6 +124,9 @@ pub(crate) struct ConstrainedModule {
     // Rather than adding pending derives as constraints, hand them directly to solve because they
     // must be solved at the end of a module.
     pub(crate) pending_derives: PendingDerives,
+    // Likewise, incomplete ability implementations are reported once solving knows the types
+    // of the missing members.
+    pub(crate) incomplete_impls: IncompleteImpls,
 }
 
 #[derive(Debug)]
@@ -168,6 +175,14 @@ pub(crate) struct LateSpecializationsModule<'a> {
 pub struct ToplevelExpects {
     pub pure: VecMap<Symbol, Region>,
     pub fx: VecMap<Symbol, Region>,
+    /// The def each expect follows in the source, if any
+    pub def_names: VecMap<Symbol, Symbol>,
+    /// Top-level functions returning a `Bool` whose name starts with `fuzz` (like
+    /// `fuzzAddCommutes`), which `roc test --fuzz` calls with generated arguments
+    pub properties: VecMap<Symbol, Region>,
+    /// With [crate::file::LoadConfig::coverage], every def and `when` branch whose runs
+    /// `roc test --coverage` counts, in source order
+    pub coverage_sites: Vec<(CoverageSite, Region)>,
 }
 
 #[derive(Debug)]
@@ -184,10 +199,14 @@ pub struct MonomorphizedModule<'a> {
     pub entry_point: EntryPoint<'a>,
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+    /// Where each top-level def was defined
+    pub def_regions: MutMap<Symbol, Region>,
     pub timings: MutMap<ModuleId, ModuleTiming>,
     pub expectations: VecMap<ModuleId, Expectations>,
     pub uses_prebuilt_platform: bool,
     pub glue_layouts: GlueLayouts<'a>,
+    /// The URL of every package loaded over HTTPS
+    pub package_urls: MutSet<String>,
 }
 
 #[derive(Debug)]
@@ -215,6 +234,8 @@ pub enum EntryPoint<'a> {
     Executable {
         exposed_to_host: &'a [(Symbol, ProcLayout<'a>)],
         platform_path: PathBuf,
+        /// What the binary exports as [roc_mono::app_name::APP_NAME_SYMBOL]
+        app_name: String,
     },
     Test,
 }
@@ -224,6 +245,8 @@ pub struct Expectations {
     pub subs: roc_types::subs::Subs,
     pub path: PathBuf,
     pub expectations: VecMap<Region, Vec<ExpectLookup>>,
+    /// For expects whose condition ends in `a == b`, the two variables being compared
+    pub comparisons: VecMap<Region, (Symbol, Symbol)>,
     pub dbgs: VecMap<Symbol, DbgLookup>,
     pub ident_ids: IdentIds,
 }
@@ -277,6 +300,12 @@ impl ModuleTiming {
         self.end_time.duration_since(self.start_time)
     }
 
+    /// The time spent actually working on this module, i.e. [`Self::total`] without the time it
+    /// spent waiting (see [`Self::other`])
+    pub fn busy(&self) -> Duration {
+        self.total() - self.other()
+    }
+
     /// Subtract all the other fields from total_start_to_finish
     pub fn other(&self) -> Duration {
         let Self {
@@ -3,6 +3,7 @@ use crate::module::{
     CheckedModule, ConstrainedModule, FoundSpecializationsModule, LateSpecializationsModule,
     ModuleHeader, ParsedModule, TypeCheckedModule,
 };
+use crate::type_cache::{TypeCacheEntry, TypeCacheKey};
 use roc_can::abilities::PendingAbilitiesStore;
 use roc_collections::{MutMap, MutSet, VecMap};
 use roc_module::ident::ModuleName;
@@ -43,6 +44,11 @@ pub(crate) struct ModuleCache<'a> {
     pub(crate) type_problems: MutMap<ModuleId, Vec<TypeError>>,
 
     pub(crate) sources: MutMap<ModuleId, (PathBuf, &'a str)>,
+
+    /// On-disk type cache
+    pub(crate) type_cache_keys: MutMap<ModuleId, TypeCacheKey>,
+    /// Modules that missed the on-disk type cache, and should be written to it once solved
+    pub(crate) type_cache_entries: MutMap<ModuleId, TypeCacheEntry>,
 }
 
 impl<'a> ModuleCache<'a> {
@@ -92,6 +98,7 @@ impl Default for ModuleCache<'_> {
             HASH,
             INSPECT,
             JSON,
+            JSON_FORMAT,
         }
 
         Self {
@@ -114,6 +121,8 @@ impl Default for ModuleCache<'_> {
             can_problems: Default::default(),
             type_problems: Default::default(),
             sources: Default::default(),
+            type_cache_keys: Default::default(),
+            type_cache_entries: Default::default(),
         }
     }
 }
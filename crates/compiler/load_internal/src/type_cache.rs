@@ -0,0 +1,364 @@
+//! An on-disk cache of solved types for non-builtin modules.
+//!
+//! Builtins have their types baked into the compiler binary at build time (see
+//! `crates/compiler/load/build.rs`). This extends the same idea to the modules of an
+//! app or package: once a module has been solved without problems, its [`TypeState`]
+//! is written to `~/.cache/roc/modules`, keyed by a hash of its source, the keys of
+//! everything it imports, and the compiler that produced it. The next time we load
+//! that module with the same key, we skip constraint generation and solving for it.
+//!
+//! Entries are also kept in memory, so a long-running process like `roc check --watch`
+//! gets the modules it already solved straight back, and only solves again the modules
+//! whose key changed.
+//!
+//! Neither cache grows without bound: the least recently used entries are dropped from
+//! memory once they add up to [`IN_MEMORY_BUDGET`], and from disk once the directory
+//! holds more than [`ON_DISK_BUDGET`].
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+use roc_can::module::TypeState;
+use roc_collections::MutMap;
+use roc_module::symbol::ModuleId;
+use roc_mono::spec_cache::{SpecializationCache, CACHE_SPECIALIZATIONS_ENV};
+use roc_packaging::cache::RocCacheDir;
+use roc_solve::FunctionKind;
+use roc_target::Target;
+
+/// Bump this whenever the serialized format of [`TypeState`] changes.
+const FORMAT_VERSION: u32 = 2;
+
+const COMPILER_VERSION: &str = include_str!("../../../../version.txt");
+
+const MODULES_DIR_NAME: &str = "modules";
+
+const SPECIALIZATIONS_DIR_NAME: &str = "specializations";
+
+/// How many bytes of entries to keep in memory.
+const IN_MEMORY_BUDGET: usize = 256 * 1024 * 1024;
+
+/// How many bytes of entries to keep on disk.
+const ON_DISK_BUDGET: u64 = 1024 * 1024 * 1024;
+
+/// Look at the size of the directory on the first write, and then every this many writes.
+const PRUNE_EVERY: usize = 64;
+
+/// Every entry starts with this, followed by the length of the serialized [`TypeState`]
+/// and a blake3 hash of it, so that a truncated or corrupt entry is rejected before
+/// anything tries to deserialize it.
+const ENTRY_MAGIC: [u8; 8] = *b"roctypes";
+
+/// A multiple of 16, so the serialized types that follow stay aligned.
+const ENTRY_HEADER_LEN: usize = ENTRY_MAGIC.len() + 8 + 32;
+
+/// A content hash identifying one solved module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TypeCacheKey([u8; 32]);
+
+impl TypeCacheKey {
+    /// The key covers everything that can influence the solved types of a module:
+    /// its own source, the keys of its imports (so changing a dependency invalidates
+    /// all of its dependents), the module ids involved (which end up in the cached
+    /// symbols), and the compiler itself.
+    pub(crate) fn new(
+        module_id: ModuleId,
+        src: &str,
+        target: Target,
+        function_kind: FunctionKind,
+        imports: &mut [(ModuleId, TypeCacheKey)],
+    ) -> Self {
+        let mut hasher = KeyHasher(blake3::Hasher::new());
+
+        FORMAT_VERSION.hash(&mut hasher);
+        COMPILER_VERSION.hash(&mut hasher);
+        compiler_identity().hash(&mut hasher);
+
+        target.to_string().hash(&mut hasher);
+        match function_kind {
+            FunctionKind::LambdaSet => 0u8,
+            FunctionKind::Erased => 1u8,
+        }
+        .hash(&mut hasher);
+
+        module_id.hash(&mut hasher);
+        src.hash(&mut hasher);
+
+        // Imports are gathered from a hash set, so put them in a stable order first.
+        imports.sort_by_key(|(_, key)| key.0);
+
+        for (import_id, import_key) in imports.iter() {
+            import_id.hash(&mut hasher);
+            hasher.0.update(&import_key.0);
+        }
+
+        Self(*hasher.0.finalize().as_bytes())
+    }
+
+    fn file_name(&self) -> String {
+        let mut name = String::with_capacity(self.0.len() * 2 + 4);
+
+        for byte in self.0 {
+            name.push_str(&format!("{byte:02x}"));
+        }
+
+        name.push_str(".dat");
+
+        name
+    }
+}
+
+/// Feeds anything that implements [`Hash`] into blake3, so the key is stable across
+/// runs (unlike the randomly-seeded hashers in std).
+struct KeyHasher(blake3::Hasher);
+
+impl Hasher for KeyHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        unreachable!("only the full blake3 digest is used")
+    }
+}
+
+/// Builds from source all report the same version, so also take the binary itself
+/// into account; otherwise a rebuilt compiler could read types it didn't write.
+fn compiler_identity() -> Option<(PathBuf, std::time::SystemTime)> {
+    let exe = std::env::current_exe().ok()?;
+    let modified = std::fs::metadata(&exe).ok()?.modified().ok()?;
+
+    Some((exe, modified))
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TypeCache {
+    dir: PathBuf,
+}
+
+impl TypeCache {
+    /// Returns None unless we're allowed to use the on-disk cache; build.rs and tests
+    /// must never read or write the user's cache.
+    pub(crate) fn new(roc_cache_dir: RocCacheDir<'_>) -> Option<Self> {
+        match roc_cache_dir {
+            RocCacheDir::Persistent(packages_dir) => {
+                // packages_dir is e.g. ~/.cache/roc/packages - we want ~/.cache/roc/modules
+                let dir = packages_dir.parent()?.join(MODULES_DIR_NAME);
+
+                Some(Self { dir })
+            }
+            RocCacheDir::Disallowed => None,
+        }
+    }
+
+    pub(crate) fn entry(&self, key: TypeCacheKey) -> TypeCacheEntry {
+        TypeCacheEntry {
+            path: self.dir.join(key.file_name()),
+        }
+    }
+}
+
+/// The serialized types this process has read or written, by path.
+#[derive(Default)]
+struct InMemory {
+    entries: MutMap<PathBuf, (Arc<[u8]>, u64)>,
+    total_bytes: usize,
+    /// Counts up on every use, to tell which entry was used least recently.
+    clock: u64,
+}
+
+impl InMemory {
+    fn get(&mut self, path: &Path) -> Option<Arc<[u8]>> {
+        self.clock += 1;
+
+        let (bytes, last_used) = self.entries.get_mut(path)?;
+        *last_used = self.clock;
+
+        Some(bytes.clone())
+    }
+
+    fn insert(&mut self, path: PathBuf, bytes: Arc<[u8]>) {
+        self.clock += 1;
+        self.total_bytes += bytes.len();
+
+        if let Some((old, _)) = self.entries.insert(path, (bytes, self.clock)) {
+            self.total_bytes -= old.len();
+        }
+
+        while self.total_bytes > IN_MEMORY_BUDGET {
+            let least_recent = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(path, _)| path.clone());
+
+            match least_recent.and_then(|path| self.entries.remove(&path)) {
+                Some((bytes, _)) => self.total_bytes -= bytes.len(),
+                None => break,
+            }
+        }
+    }
+}
+
+fn in_memory() -> &'static Mutex<InMemory> {
+    static IN_MEMORY: OnceLock<Mutex<InMemory>> = OnceLock::new();
+
+    IN_MEMORY.get_or_init(Default::default)
+}
+
+/// The location of a single module's cached types.
+#[derive(Debug, Clone)]
+pub(crate) struct TypeCacheEntry {
+    path: PathBuf,
+}
+
+impl TypeCacheEntry {
+    pub(crate) fn read(&self) -> Option<TypeState> {
+        let cached = in_memory().lock().get(&self.path);
+
+        let bytes = match cached {
+            Some(bytes) => bytes,
+            None => {
+                let bytes: Arc<[u8]> = payload(&std::fs::read(&self.path).ok()?)?.into();
+                in_memory().lock().insert(self.path.clone(), bytes.clone());
+
+                // So that pruning the directory drops the entries that went unused longest
+                let _ = std::fs::File::options()
+                    .write(true)
+                    .open(&self.path)
+                    .and_then(|file| file.set_modified(SystemTime::now()));
+
+                bytes
+            }
+        };
+
+        // The serialized format relies on the buffer being aligned like the builtin
+        // caches are (see include_bytes_align_as! in roc_load), so copy into a u128 buffer.
+        let mut aligned = vec![0u128; (bytes.len() + 15) / 16];
+        let aligned_bytes =
+            unsafe { std::slice::from_raw_parts_mut(aligned.as_mut_ptr() as *mut u8, bytes.len()) };
+        aligned_bytes.copy_from_slice(&bytes);
+
+        let (type_state, offset) = TypeState::deserialize(aligned_bytes);
+
+        // The checksum matched, and the key covers the compiler, so these are exactly the
+        // bytes that this compiler wrote.
+        debug_assert_eq!(offset, bytes.len());
+
+        Some(type_state)
+    }
+
+    /// Failing to write is not a problem worth reporting; we'll just solve this module
+    /// again next time.
+    pub(crate) fn write(&self, type_state: &TypeState) {
+        let mut bytes = Vec::new();
+
+        if type_state.serialize(&mut bytes).is_err() {
+            return;
+        }
+
+        let _ = self.try_write(&bytes);
+
+        in_memory().lock().insert(self.path.clone(), bytes.into());
+    }
+
+    fn try_write(&self, bytes: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let dir = self
+            .path
+            .parent()
+            .expect("cache entries are always in a dir");
+        std::fs::create_dir_all(dir)?;
+
+        // Write to a temporary file and then move it into place, so that concurrent
+        // roc processes never observe a half-written entry.
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(&ENTRY_MAGIC)?;
+        file.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        file.write_all(blake3::hash(bytes).as_bytes())?;
+        file.write_all(bytes)?;
+        file.persist(&self.path).map_err(|err| err.error)?;
+
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+        if WRITES.fetch_add(1, Ordering::Relaxed) % PRUNE_EVERY == 0 {
+            prune(dir, ON_DISK_BUDGET)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The serialized types in an entry read from disk, if its header says they're all there
+/// and intact.
+fn payload(entry: &[u8]) -> Option<&[u8]> {
+    if entry.len() < ENTRY_HEADER_LEN || entry[..ENTRY_MAGIC.len()] != ENTRY_MAGIC {
+        return None;
+    }
+
+    let (header, payload) = entry.split_at(ENTRY_HEADER_LEN);
+    let (len, checksum) = header[ENTRY_MAGIC.len()..].split_at(8);
+
+    let len_matches = u64::from_le_bytes(len.try_into().unwrap()) == payload.len() as u64;
+
+    if len_matches && !payload.is_empty() && blake3::hash(payload).as_bytes() == checksum {
+        Some(payload)
+    } else {
+        None
+    }
+}
+
+/// Removes the least recently used entries until the ones left add up to at most `budget`.
+fn prune(dir: &Path, budget: u64) -> std::io::Result<()> {
+    let mut entries = Vec::new();
+    let mut total = 0;
+
+    for dir_entry in std::fs::read_dir(dir)? {
+        let dir_entry = dir_entry?;
+        let metadata = dir_entry.metadata()?;
+
+        if metadata.is_file() {
+            total += metadata.len();
+            entries.push((metadata.modified()?, metadata.len(), dir_entry.path()));
+        }
+    }
+
+    if total <= budget {
+        return Ok(());
+    }
+
+    entries.sort();
+
+    for (_, len, path) in entries {
+        if total <= budget {
+            break;
+        }
+
+        // another roc process may have already removed it
+        let _ = std::fs::remove_file(path);
+        total -= len;
+    }
+
+    Ok(())
+}
+
+/// Specializations of builtins are cached next to the solved types, and are invalidated by
+/// the same changes to the compiler. Unlike the types, they're only cached on request (see
+/// [roc_mono::spec_cache]).
+pub(crate) fn specialization_cache(roc_cache_dir: RocCacheDir<'_>) -> Option<SpecializationCache> {
+    std::env::var_os(CACHE_SPECIALIZATIONS_ENV)?;
+
+    match roc_cache_dir {
+        RocCacheDir::Persistent(packages_dir) => {
+            let dir = packages_dir.parent()?.join(SPECIALIZATIONS_DIR_NAME);
+            let compiler = format!("{COMPILER_VERSION} {:?}", compiler_identity());
+
+            Some(SpecializationCache::new(dir, compiler))
+        }
+        RocCacheDir::Disallowed => None,
+    }
+}
@@ -61,6 +61,8 @@ fn load_and_typecheck(
         palette: DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Check,
+        reproducible: false,
+        coverage: false,
     };
 
     match roc_load_internal::file::load(
@@ -177,6 +179,7 @@ fn multiple_modules_help<'a>(
         let filename = PathBuf::from(name);
         let file_path = dir.path().join(filename);
         let full_file_path = file_path.clone();
+        fs::create_dir_all(file_path.parent().unwrap())?;
         let mut file = File::create(file_path)?;
         writeln!(file, "{source}")?;
         file_handles.push(file);
@@ -644,6 +647,175 @@ fn app_dep_types() {
     );
 }
 
+/// Loads the module_with_deps fixture, with the type cache in the given cache dir
+fn load_with_type_cache(packages_dir: &std::path::Path) -> LoadedModule {
+    let arena = Bump::new();
+    let filename = fixtures_dir().join("module_with_deps").join("Primary.roc");
+    let roc_cache_dir = RocCacheDir::Persistent(packages_dir);
+
+    let load_start = LoadStart::from_path(
+        &arena,
+        filename,
+        RenderTarget::Generic,
+        roc_cache_dir,
+        DEFAULT_PALETTE,
+    )
+    .unwrap();
+    let load_config = LoadConfig {
+        target: TARGET,
+        function_kind: FunctionKind::LambdaSet,
+        render: RenderTarget::Generic,
+        palette: DEFAULT_PALETTE,
+        threading: Threading::Single,
+        exec_mode: ExecutionMode::Check,
+        reproducible: false,
+        coverage: false,
+    };
+
+    let loaded = roc_load_internal::file::load(
+        &arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        roc_cache_dir,
+        load_config,
+    );
+
+    match loaded {
+        Ok(LoadResult::TypeChecked(module)) => module,
+        Ok(LoadResult::Monomorphized(_)) => unreachable!(""),
+        Err(e) => panic!("{e:?}"),
+    }
+}
+
+#[test]
+fn iface_dep_types_from_type_cache() {
+    let dir = TmpDir::new("tmp/iface_dep_types_from_type_cache");
+    let packages_dir = dir.path().join("packages");
+    let modules_dir = dir.path().join("modules");
+
+    // The first load solves everything, and writes the solved types to the cache
+    load_with_type_cache(&packages_dir);
+
+    let cached_entries = std::fs::read_dir(&modules_dir).unwrap().count();
+    assert!(cached_entries > 0);
+
+    // The second load reads them back instead of solving again
+    let loaded_module = load_with_type_cache(&packages_dir);
+
+    assert_eq!(
+        std::fs::read_dir(&modules_dir).unwrap().count(),
+        cached_entries
+    );
+
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "blah2" => "Frac *",
+            "blah3" => "Str",
+            "str" => "Str",
+            "alwaysThree" => "* -> Frac *",
+            "identity" => "a -> a",
+            "z" => "Frac *",
+            "w" => "Dep1.Identity {}",
+            "succeed" => "a -> Dep1.Identity a",
+            "yay" => "Res.Res {} err",
+            "withDefault" => "Res.Res a err, a -> a",
+        },
+    );
+}
+
+#[test]
+fn truncated_type_cache_entries_are_misses() {
+    let dir = TmpDir::new("tmp/truncated_type_cache_entries_are_misses");
+    let written_modules_dir = dir.path().join("written").join("modules");
+    let packages_dir = dir.path().join("truncated").join("packages");
+    let modules_dir = dir.path().join("truncated").join("modules");
+
+    load_with_type_cache(&dir.path().join("written").join("packages"));
+
+    // This process keeps the entries it wrote in memory by path, so put the truncated
+    // copies in a different cache dir to make the next load read them from disk.
+    std::fs::create_dir_all(&modules_dir).unwrap();
+
+    let mut entry_lens = Vec::new();
+
+    for entry in std::fs::read_dir(&written_modules_dir).unwrap() {
+        let entry = entry.unwrap();
+        let bytes = std::fs::read(entry.path()).unwrap();
+
+        std::fs::write(
+            modules_dir.join(entry.file_name()),
+            &bytes[..bytes.len() / 2],
+        )
+        .unwrap();
+        entry_lens.push((entry.file_name(), bytes.len()));
+    }
+
+    assert!(!entry_lens.is_empty());
+
+    let loaded_module = load_with_type_cache(&packages_dir);
+
+    // Every module was solved again, and its entry written back in full
+    for (file_name, len) in entry_lens {
+        assert_eq!(
+            std::fs::metadata(modules_dir.join(file_name))
+                .unwrap()
+                .len() as usize,
+            len
+        );
+    }
+
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "blah2" => "Frac *",
+            "blah3" => "Str",
+            "str" => "Str",
+            "alwaysThree" => "* -> Frac *",
+            "identity" => "a -> a",
+            "z" => "Frac *",
+            "w" => "Dep1.Identity {}",
+            "succeed" => "a -> Dep1.Identity a",
+            "yay" => "Res.Res {} err",
+            "withDefault" => "Res.Res a err, a -> a",
+        },
+    );
+}
+
+#[test]
+fn iface_dep_types_from_in_memory_type_cache() {
+    let dir = TmpDir::new("tmp/iface_dep_types_from_in_memory_type_cache");
+    let packages_dir = dir.path().join("packages");
+    let modules_dir = dir.path().join("modules");
+
+    load_with_type_cache(&packages_dir);
+
+    // Later loads in the same process (e.g. in `roc check --watch`) don't need the disk;
+    // solving anything again would write its entry back.
+    std::fs::remove_dir_all(&modules_dir).unwrap();
+
+    let loaded_module = load_with_type_cache(&packages_dir);
+
+    assert!(!modules_dir.exists());
+
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "blah2" => "Frac *",
+            "blah3" => "Str",
+            "str" => "Str",
+            "alwaysThree" => "* -> Frac *",
+            "identity" => "a -> a",
+            "z" => "Frac *",
+            "w" => "Dep1.Identity {}",
+            "succeed" => "a -> Dep1.Identity a",
+            "yay" => "Res.Res {} err",
+            "withDefault" => "Res.Res a err, a -> a",
+        },
+    );
+}
+
 #[test]
 fn imported_dep_regression() {
     let subs_by_module = Default::default();
@@ -740,7 +912,7 @@ fn file_not_found() {
 }
 
 #[test]
-#[should_panic(expected = "FILE NOT FOUND")]
+#[should_panic(expected = "MODULE NOT FOUND")]
 fn imported_file_not_found() {
     let subs_by_module = Default::default();
     let loaded_module = load_fixture("no_deps", "MissingDep", subs_by_module);
@@ -1350,6 +1522,118 @@ fn import_with_alias() {
     assert!(loaded_module.is_ok(), "should check");
 }
 
+#[test]
+fn nested_module_imports() {
+    let modules = vec![
+        (
+            "Baz.roc",
+            indoc!(
+                r#"
+                module [baz]
+
+                baz = "baz"
+                "#
+            ),
+        ),
+        (
+            "Foo/Bar.roc",
+            indoc!(
+                r#"
+                module [bar]
+
+                import Baz exposing [baz]
+
+                bar = baz
+                "#
+            ),
+        ),
+        (
+            "Main.roc",
+            indoc!(
+                r#"
+                module [main]
+
+                import Foo.Bar exposing [bar]
+
+                main = bar
+                "#
+            ),
+        ),
+    ];
+    let loaded_module = multiple_modules("nested_module_imports", modules);
+    assert!(loaded_module.is_ok(), "should check");
+}
+
+#[test]
+fn nested_root_module_imports() {
+    let modules = vec![
+        (
+            "main.roc",
+            indoc!(
+                r#"
+                package [Baz, Foo.Bar] {}
+                "#
+            ),
+        ),
+        (
+            "Baz.roc",
+            indoc!(
+                r#"
+                module [baz]
+
+                baz = "baz"
+                "#
+            ),
+        ),
+        (
+            "Foo/Bar.roc",
+            indoc!(
+                r#"
+                module [bar]
+
+                import Baz exposing [baz]
+
+                bar = baz
+                "#
+            ),
+        ),
+    ];
+    let loaded_module = multiple_modules("nested_root_module_imports", modules).unwrap();
+    let name = loaded_module
+        .interns
+        .module_ids
+        .get_name(loaded_module.module_id)
+        .unwrap();
+
+    // Its imports are resolved from the package root, not from Foo/
+    assert_eq!(name.as_str(), "Foo.Bar");
+}
+
+#[test]
+fn nested_module_not_found() {
+    let modules = vec![(
+        "Main.roc",
+        indoc!(
+            r#"
+            module [main]
+
+            import Foo.Missing exposing [missing]
+
+            main = missing
+            "#
+        ),
+    )];
+
+    let err = strip_colors(&multiple_modules("nested_module_not_found", modules).unwrap_err());
+
+    assert!(err.contains("MODULE NOT FOUND"), "\n{}", err);
+    assert!(
+        err.contains("tmp/nested_module_not_found/Foo/Missing.roc"),
+        "\n{}",
+        err
+    );
+}
+
 #[test]
 fn duplicate_alias() {
     let modules = vec![
@@ -1662,6 +1946,32 @@ fn import_builtin_in_platform_and_check_app() {
     assert!(result.is_ok(), "should check");
 }
 
+#[test]
+fn platform_provides_undefined_value() {
+    let modules = vec![(
+        "main.roc",
+        indoc!(
+            r#"
+                platform "testplatform"
+                    requires {} { main : Str }
+                    exposes []
+                    packages {}
+                    imports []
+                    provides [mainForHost, helperForHost]
+
+                mainForHost : Str
+                mainForHost = main
+                "#
+        ),
+    )];
+
+    let err =
+        strip_colors(&multiple_modules("platform_provides_undefined_value", modules).unwrap_err());
+
+    assert!(err.contains("MISSING PROVIDED DEFINITION"), "\n{}", err);
+    assert!(err.contains("`helperForHost` is listed"), "\n{}", err);
+}
+
 #[test]
 fn module_cyclic_import_itself() {
     let modules = vec![(
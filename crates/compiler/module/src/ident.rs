@@ -132,6 +132,7 @@ impl ModuleName {
     pub const INSPECT: &'static str = "Inspect";
     pub const TASK: &'static str = "Task";
     pub const JSON: &'static str = "TotallyNotJson";
+    pub const JSON_FORMAT: &'static str = "JsonFormat";
 
     pub fn as_str(&self) -> &str {
         self.0.as_str()
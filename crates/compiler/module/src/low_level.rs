@@ -22,6 +22,7 @@ pub enum LowLevel {
     StrTrimEnd,
     StrToNum,
     StrGetUnsafe,
+    StrGraphemeEnd,
     StrSubstringUnsafe,
     StrReserve,
     StrWithCapacity,
@@ -47,6 +48,9 @@ pub enum LowLevel {
     ListGetCapacity,
     ListIsUnique,
     ListClone,
+    JsonSkipWhitespace,
+    JsonValueEnd,
+    InspectMatchesSnapshot,
     NumAdd,
     NumAddWrap,
     NumAddChecked,
@@ -99,6 +103,7 @@ pub enum LowLevel {
     NumToIntChecked,
     NumToFloatChecked,
     NumToStr,
+    NumToStrWith,
     NumCountLeadingZeroBits,
     NumCountTrailingZeroBits,
     NumCountOneBits,
@@ -161,6 +166,163 @@ impl LowLevel {
     }
 }
 
+/// We use a rust macro to ensure that every LowLevel can be looked up by name
+macro_rules! lowlevel_names {
+    ($($lowlevel:ident),* $(,)?) => {
+        impl LowLevel {
+            /// Looks up a lowlevel by the name of its variant, e.g. `NumAdd`.
+            /// This is the name that `Debug` prints, so the two round-trip.
+            pub fn from_name(name: &str) -> Option<LowLevel> {
+                match name {
+                    $(stringify!($lowlevel) => Some(LowLevel::$lowlevel),)*
+                    _ => None,
+                }
+            }
+        }
+
+        fn _enforce_names_exhaustiveness(lowlevel: LowLevel) {
+            // when adding a new lowlevel, this match will stop being exhaustive; add the new
+            // lowlevel to the `lowlevel_names!` invocation below.
+            match lowlevel {
+                $(LowLevel::$lowlevel => {})*
+            }
+        }
+    };
+}
+
+lowlevel_names! {
+    StrConcat,
+    StrJoinWith,
+    StrIsEmpty,
+    StrStartsWith,
+    StrEndsWith,
+    StrSplit,
+    StrCountUtf8Bytes,
+    StrFromInt,
+    StrFromUtf8,
+    StrToUtf8,
+    StrRepeat,
+    StrFromFloat,
+    StrTrim,
+    StrTrimStart,
+    StrTrimEnd,
+    StrToNum,
+    StrGetUnsafe,
+    StrGraphemeEnd,
+    StrSubstringUnsafe,
+    StrReserve,
+    StrWithCapacity,
+    StrReleaseExcessCapacity,
+    ListLenUsize,
+    ListLenU64,
+    ListWithCapacity,
+    ListReserve,
+    ListReleaseExcessCapacity,
+    ListAppendUnsafe,
+    ListGetUnsafe,
+    ListReplaceUnsafe,
+    ListConcat,
+    ListPrepend,
+    ListMap,
+    ListMap2,
+    ListMap3,
+    ListMap4,
+    ListSortWith,
+    ListSublist,
+    ListDropAt,
+    ListSwap,
+    ListGetCapacity,
+    ListIsUnique,
+    ListClone,
+    JsonSkipWhitespace,
+    JsonValueEnd,
+    InspectMatchesSnapshot,
+    NumAdd,
+    NumAddWrap,
+    NumAddChecked,
+    NumAddSaturated,
+    NumSub,
+    NumSubWrap,
+    NumSubChecked,
+    NumSubSaturated,
+    NumMul,
+    NumMulWrap,
+    NumMulSaturated,
+    NumMulChecked,
+    NumGt,
+    NumGte,
+    NumLt,
+    NumLte,
+    NumCompare,
+    NumDivFrac,
+    NumDivTruncUnchecked,
+    NumDivCeilUnchecked,
+    NumRemUnchecked,
+    NumIsMultipleOf,
+    NumAbs,
+    NumNeg,
+    NumSin,
+    NumCos,
+    NumTan,
+    NumSqrtUnchecked,
+    NumLogUnchecked,
+    NumRound,
+    NumToFrac,
+    NumPow,
+    NumCeiling,
+    NumPowInt,
+    NumFloor,
+    NumIsNan,
+    NumIsInfinite,
+    NumIsFinite,
+    NumAtan,
+    NumAcos,
+    NumAsin,
+    NumBitwiseAnd,
+    NumBitwiseXor,
+    NumBitwiseOr,
+    NumShiftLeftBy,
+    NumShiftRightBy,
+    NumShiftRightZfBy,
+    NumIntCast,
+    NumToFloatCast,
+    NumToIntChecked,
+    NumToFloatChecked,
+    NumToStr,
+    NumToStrWith,
+    NumCountLeadingZeroBits,
+    NumCountTrailingZeroBits,
+    NumCountOneBits,
+    NumWithoutDecimalPoint,
+    NumWithDecimalPoint,
+    NumF32ToParts,
+    NumF64ToParts,
+    NumF32FromParts,
+    NumF64FromParts,
+    Eq,
+    NotEq,
+    And,
+    Or,
+    Not,
+    Hash,
+    PtrCast,
+    PtrStore,
+    PtrLoad,
+    PtrClearTagId,
+    RefCountIncRcPtr,
+    RefCountDecRcPtr,
+    RefCountIncDataPtr,
+    RefCountDecDataPtr,
+    RefCountIsUnique,
+    BoxExpr,
+    UnboxExpr,
+    Unreachable,
+    DictPseudoSeed,
+    SetJmp,
+    LongJmp,
+    SetLongJmpBuffer,
+}
+
 /// Some wrapper functions can just be replaced by lowlevels in the backend for performance.
 /// For example, Num.add should be an instruction, not a function call.
 /// Variant names are chosen to help explain what to do when adding new lowlevels
@@ -269,6 +431,7 @@ map_symbol_to_lowlevel! {
     StrTrimStart <= STR_TRIM_START;
     StrTrimEnd <= STR_TRIM_END;
     StrGetUnsafe <= STR_GET_UNSAFE;
+    StrGraphemeEnd <= STR_GRAPHEME_END;
     StrSubstringUnsafe <= STR_SUBSTRING_UNSAFE;
     StrReserve <= STR_RESERVE;
     StrToNum <= STR_TO_NUM;
@@ -282,6 +445,9 @@ map_symbol_to_lowlevel! {
     ListReleaseExcessCapacity <= LIST_RELEASE_EXCESS_CAPACITY;
     ListIsUnique <= LIST_IS_UNIQUE;
     ListClone <= LIST_CLONE;
+    JsonSkipWhitespace <= JSON_FORMAT_SKIP_WHITESPACE;
+    JsonValueEnd <= JSON_FORMAT_VALUE_END;
+    InspectMatchesSnapshot <= INSPECT_MATCHES_SNAPSHOT;
     ListAppendUnsafe <= LIST_APPEND_UNSAFE;
     ListPrepend <= LIST_PREPEND;
     ListGetUnsafe <= LIST_GET_UNSAFE, DICT_LIST_GET_UNSAFE;
@@ -338,6 +504,7 @@ map_symbol_to_lowlevel! {
     NumShiftRightBy <= NUM_SHIFT_RIGHT;
     NumShiftRightZfBy <= NUM_SHIFT_RIGHT_ZERO_FILL;
     NumToStr <= NUM_TO_STR;
+    NumToStrWith <= NUM_TO_STR_WITH_LOWLEVEL;
     NumCountLeadingZeroBits <= NUM_COUNT_LEADING_ZERO_BITS;
     NumCountTrailingZeroBits <= NUM_COUNT_TRAILING_ZERO_BITS;
     NumCountOneBits <= NUM_COUNT_ONE_BITS;
@@ -395,8 +395,9 @@ impl ModuleId {
     }
 
     pub fn is_automatically_imported(self) -> bool {
-        // The deprecated TotallyNotJson module is not automatically imported.
-        self.is_builtin() && self != ModuleId::JSON
+        // The deprecated TotallyNotJson module is not automatically imported, and neither
+        // is JsonFormat, so that only modules which use it pay to load it.
+        self.is_builtin() && self != ModuleId::JSON && self != ModuleId::JSON_FORMAT
     }
 }
 
@@ -1374,6 +1375,8 @@ define_builtins! {
         166 NUM_NAN_F64: "nanF64"
         167 NUM_INFINITY_F32: "infinityF32"
         168 NUM_INFINITY_F64: "infinityF64"
+        169 NUM_TO_STR_WITH: "toStrWith"
+        170 NUM_TO_STR_WITH_LOWLEVEL: "toStrWithLowlevel"
     }
     4 BOOL: "Bool" => {
         0 BOOL_BOOL: "Bool" exposed_type=true // the Bool.Bool type alias
@@ -1440,6 +1443,10 @@ define_builtins! {
         46 STR_REPLACE_FIRST: "replaceFirst"
         47 STR_REPLACE_LAST: "replaceLast"
         48 STR_RELEASE_EXCESS_CAPACITY: "releaseExcessCapacity"
+        49 STR_GRAPHEME_END: "graphemeEnd"
+        50 STR_WALK_GRAPHEMES: "walkGraphemes"
+        51 STR_GRAPHEMES: "graphemes"
+        52 STR_COUNT_GRAPHEMES: "countGraphemes"
     }
     6 LIST: "List" => {
         0 LIST_LIST: "List" exposed_apply_type=true // the List.List type alias
@@ -1531,6 +1538,8 @@ define_builtins! {
         86 LIST_WALK_WITH_INDEX_UNTIL: "walkWithIndexUntil"
         87 LIST_CLONE: "clone"
         88 LIST_LEN_USIZE: "lenUsize"
+        89 LIST_WINDOWS: "windows"
+        90 LIST_GROUP_BY: "groupBy"
     }
     7 RESULT: "Result" => {
         0 RESULT_RESULT: "Result" exposed_type=true // the Result.Result type alias
@@ -1729,6 +1738,9 @@ define_builtins! {
         31 INSPECT_APPLY: "apply"
         32 INSPECT_TO_INSPECTOR: "toInspector"
         33 INSPECT_TO_STR: "toStr"
+        34 INSPECT_TO_JSON: "toJson"
+        35 INSPECT_EXPECT_SNAPSHOT: "expectSnapshot"
+        36 INSPECT_MATCHES_SNAPSHOT: "matchesSnapshot"
     }
     15 JSON: "TotallyNotJson" => {
         0 JSON_JSON: "TotallyNotJson"
@@ -1739,6 +1751,12 @@ define_builtins! {
         5 JSON_ARRAY_CLOSING_STATE: "ArrayClosingState"
         6 JSON_OBJECT_STATE: "ObjectState"
     }
+    16 JSON_FORMAT: "JsonFormat" => {
+        0 JSON_FORMAT_JSON: "Json" exposed_type=true
+        1 JSON_FORMAT_UTF8: "utf8"
+        2 JSON_FORMAT_SKIP_WHITESPACE: "skipWhitespace"
+        3 JSON_FORMAT_VALUE_END: "valueEnd"
+    }
 
-    num_modules: 16 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
+    num_modules: 17 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
 }
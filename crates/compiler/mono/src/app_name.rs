@@ -0,0 +1,38 @@
+//! Tells the host what the app is called, so it doesn't have to rely on how it was started.
+//!
+//! A host is started like any other program. Its `argv[0]` is the path it was started by, and
+//! the rest of `argv` are the arguments for the app; the environment is passed on unchanged.
+//! `roc run` and `roc dev` pass the arguments after `--`, with the path that `roc build` would
+//! have written the binary to as `argv[0]`, for both native and Wasm apps. The host then calls
+//! the app through its entry point, `roc__{name}_1_exposed_generic` (e.g.
+//! `roc__mainForHost_1_exposed_generic`), with whatever of those it wants the app to have.
+//!
+//! `argv[0]` depends on where the binary ended up, though, so the binary also exports
+//! [APP_NAME_SYMBOL] (except with the Wasm dev backend). Packages that parse arguments can use
+//! it for usage messages, and get the same name on every platform.
+
+/// The data symbol that holds the app's name, as a NUL-terminated string.
+///
+/// The name is the app module's file name without its extension, which is also the name
+/// `roc build` gives the binary by default.
+pub const APP_NAME_SYMBOL: &str = "roc__app_name";
+
+/// The bytes of [APP_NAME_SYMBOL] for the app with the given name.
+pub fn app_name_bytes(name: &str) -> Vec<u8> {
+    let mut bytes = name.as_bytes().to_vec();
+
+    bytes.push(0);
+
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn app_name_bytes_are_nul_terminated() {
+        assert_eq!(app_name_bytes("hello"), b"hello\0");
+        assert_eq!(app_name_bytes(""), b"\0");
+    }
+}
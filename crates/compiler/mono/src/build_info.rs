@@ -0,0 +1,129 @@
+//! Records how a binary was built, so a problem with a platform or an app can be traced back to
+//! the compiler that built it.
+//!
+//! Every executable exports [BUILD_INFO_SYMBOL] (except with the Wasm dev backend), a
+//! NUL-terminated block of text that starts with a fixed header, so `roc inspect-binary` can
+//! find it by scanning the file, even after the symbol table was stripped:
+//!
+//! ```text
+//! roc build info
+//! compiler: 0.0.1
+//! target: x86_64-unknown-linux-gnu
+//! flags: --optimize --debug-info
+//! ```
+//!
+//! Hosts can also print it as it is, e.g. behind a `--version` flag of their own.
+
+/// The data symbol that holds the [BuildInfo] of the binary, as a NUL-terminated string.
+pub const BUILD_INFO_SYMBOL: &str = "roc__build_info";
+
+/// The version of the compiler that's building the binary
+pub const COMPILER_VERSION: &str = include_str!("../../../../version.txt");
+
+/// The first line of a [BUILD_INFO_SYMBOL], which is what [BuildInfo::find_in] looks for
+const HEADER: &str = "roc build info\n";
+
+const COMPILER_KEY: &str = "compiler";
+const TARGET_KEY: &str = "target";
+const FLAGS_KEY: &str = "flags";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub compiler_version: String,
+    /// The LLVM target triple, e.g. `x86_64-unknown-linux-gnu`
+    pub target_triple: String,
+    /// The command line flags that changed how the app was compiled, e.g. `--optimize`
+    pub flags: Vec<String>,
+}
+
+impl BuildInfo {
+    /// The build info of a binary that this compiler builds
+    pub fn new(target_triple: &str, flags: Vec<String>) -> Self {
+        BuildInfo {
+            compiler_version: COMPILER_VERSION.trim().to_string(),
+            target_triple: target_triple.to_string(),
+            flags,
+        }
+    }
+
+    /// The bytes of [BUILD_INFO_SYMBOL]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut text = String::from(HEADER);
+
+        text.push_str(&format!("{COMPILER_KEY}: {}\n", self.compiler_version));
+        text.push_str(&format!("{TARGET_KEY}: {}\n", self.target_triple));
+        text.push_str(&format!("{FLAGS_KEY}: {}\n", self.flags.join(" ")));
+
+        let mut bytes = text.into_bytes();
+
+        bytes.push(0);
+
+        bytes
+    }
+
+    /// Finds the build info in the contents of a binary. `None` if it was built by a compiler
+    /// that didn't embed any, or by the Wasm dev backend.
+    pub fn find_in(binary: &[u8]) -> Option<Self> {
+        let header = HEADER.as_bytes();
+
+        // The compiler has the header in it too, but not followed by anything that parses
+        binary
+            .windows(header.len())
+            .enumerate()
+            .filter(|(_, window)| *window == header)
+            .find_map(|(start, _)| Self::parse(&binary[start + header.len()..]))
+    }
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let end = bytes.iter().position(|byte| *byte == 0)?;
+        let text = std::str::from_utf8(&bytes[..end]).ok()?;
+
+        let mut compiler_version = None;
+        let mut target_triple = None;
+        let mut flags = None;
+
+        for line in text.lines() {
+            let (key, value) = line.split_once(':')?;
+            let value = value.trim();
+
+            // Newer compilers may add keys, which older ones skip
+            match key {
+                COMPILER_KEY => compiler_version = Some(value.to_string()),
+                TARGET_KEY => target_triple = Some(value.to_string()),
+                FLAGS_KEY => flags = Some(value.split_whitespace().map(String::from).collect()),
+                _ => {}
+            }
+        }
+
+        Some(BuildInfo {
+            compiler_version: compiler_version?,
+            target_triple: target_triple?,
+            flags: flags.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn build_info_is_found_in_a_binary() {
+        let info = BuildInfo::new(
+            "x86_64-unknown-linux-gnu",
+            vec!["--optimize".to_string(), "--fuzz".to_string()],
+        );
+
+        let mut binary = b"\x7fELF\0\0roc build info\nnot really\0".to_vec();
+        binary.extend_from_slice(&info.to_bytes());
+        binary.extend_from_slice(b"\0\0roc__build_info\0");
+
+        assert_eq!(BuildInfo::find_in(&binary), Some(info));
+    }
+
+    #[test]
+    fn binaries_without_build_info() {
+        assert_eq!(BuildInfo::find_in(b"\x7fELF\0\0\0"), None);
+        assert_eq!(BuildInfo::find_in(b"roc build info\ncompiler: 1\0"), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Counts which parts of a program its tests reach, for `roc test --coverage`.
+//!
+//! With [crate::ir::Env::coverage] set, every top-level def's proc and every `when` branch
+//! starts by calling [COVERAGE_HIT] with the id of its [CoverageSite]. The test runner defines
+//! that function to count the calls, and compares the counts against [coverage_sites], the list
+//! of every site in a module, to find what the tests never ran.
+use crate::ir::{Call, CallType, Env, Expr, Literal, Stmt};
+use crate::layout::Layout;
+use roc_can::expr::{Declarations, Expr as CanExpr};
+use roc_can::traverse::{walk_decl, walk_expr, DeclarationInfo, Visitor};
+use roc_module::ident::ForeignSymbol;
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_region::all::Region;
+use roc_types::subs::{Subs, Variable};
+
+/// The function that instrumented code calls with the id of each site it reaches.
+/// It takes a `u64` and returns nothing.
+pub const COVERAGE_HIT: &str = "roc_coverage_hit";
+
+/// Set on the ident part of a branch's id, so it can't be mistaken for a def's symbol.
+/// Ident ids are handed out in order, so no module gets anywhere near this many.
+const BRANCH_BIT: u32 = 1 << 31;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CoverageSite {
+    /// A top-level def, which is reached when its proc (or thunk) runs
+    Def(Symbol),
+    /// The `when` branch whose body starts at this offset in the module's source
+    Branch { module_id: ModuleId, offset: u32 },
+}
+
+impl CoverageSite {
+    pub fn branch(module_id: ModuleId, body: Region) -> Self {
+        CoverageSite::Branch {
+            module_id,
+            offset: body.start().offset,
+        }
+    }
+
+    /// The number instrumented code passes to [COVERAGE_HIT] when it reaches this site
+    pub fn id(self) -> u64 {
+        match self {
+            CoverageSite::Def(symbol) => symbol.as_u64(),
+            CoverageSite::Branch { module_id, offset } => {
+                Interns::from_index(module_id, BRANCH_BIT | offset).as_u64()
+            }
+        }
+    }
+}
+
+/// Every def and `when` branch in `decls` that coverage counts, along with its region.
+/// Expects aren't included, since they're the tests rather than the code being tested, and
+/// neither are redundant branches, which never get any code.
+pub fn coverage_sites(
+    home: ModuleId,
+    decls: &Declarations,
+    subs: &Subs,
+) -> Vec<(CoverageSite, Region)> {
+    let mut collector = SiteCollector {
+        home,
+        subs,
+        sites: Vec::new(),
+    };
+
+    collector.visit_decls(decls);
+    collector.sites.sort_by_key(|(_, region)| region.start());
+
+    collector.sites
+}
+
+struct SiteCollector<'s> {
+    home: ModuleId,
+    subs: &'s Subs,
+    sites: Vec<(CoverageSite, Region)>,
+}
+
+impl Visitor for SiteCollector<'_> {
+    fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+        match &decl {
+            DeclarationInfo::Expectation { .. } => return,
+            DeclarationInfo::Value { loc_symbol, .. }
+            | DeclarationInfo::Function { loc_symbol, .. } => {
+                self.sites
+                    .push((CoverageSite::Def(loc_symbol.value), decl.region()));
+            }
+            DeclarationInfo::Destructure { .. } => {}
+        }
+
+        walk_decl(self, decl);
+    }
+
+    fn visit_expr(&mut self, expr: &CanExpr, _region: Region, var: Variable) {
+        if let CanExpr::When { branches, .. } = expr {
+            for branch in branches {
+                if !branch.redundant.is_redundant(self.subs) {
+                    let body = branch.value.region;
+
+                    self.sites
+                        .push((CoverageSite::branch(self.home, body), body));
+                }
+            }
+        }
+
+        walk_expr(self, expr, var);
+    }
+}
+
+/// Puts a call to [COVERAGE_HIT] with the id of `site` in front of `stmt`.
+pub fn count_site<'a>(env: &mut Env<'a, '_>, site: CoverageSite, stmt: Stmt<'a>) -> Stmt<'a> {
+    let id_symbol = env.unique_symbol();
+    let unit_symbol = env.unique_symbol();
+
+    let call = Call {
+        call_type: CallType::Foreign {
+            foreign_symbol: ForeignSymbol::from(COVERAGE_HIT),
+            ret_layout: Layout::UNIT,
+        },
+        arguments: env.arena.alloc([id_symbol]),
+    };
+
+    let hit = Stmt::Let(
+        unit_symbol,
+        Expr::Call(call),
+        Layout::UNIT,
+        env.arena.alloc(stmt),
+    );
+
+    Stmt::Let(
+        id_symbol,
+        Expr::Literal(Literal::Int((site.id() as i128).to_ne_bytes())),
+        Layout::U64,
+        env.arena.alloc(hit),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn branch_ids_differ_from_def_ids() {
+        let module_id = ModuleId::ATTR;
+        let def = Interns::from_index(module_id, 12);
+
+        assert_ne!(
+            CoverageSite::Def(def).id(),
+            CoverageSite::Branch {
+                module_id,
+                offset: 12
+            }
+            .id()
+        );
+        assert_ne!(
+            CoverageSite::branch(module_id, Region::zero()).id(),
+            CoverageSite::Def(Interns::from_index(module_id, 0)).id()
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Where procedures are defined in Roc source, so backends can emit debug info that maps
+//! machine code back to Roc files and lines.
+use roc_collections::all::MutMap;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_region::all::{LineInfo, Region};
+use std::path::{Path, PathBuf};
+
+/// The source location of each top-level def. When this is empty, backends emit no source
+/// locations in their debug info.
+#[derive(Debug, Default)]
+pub struct DebugLocations {
+    /// The source file of each module
+    pub files: MutMap<ModuleId, PathBuf>,
+    /// The (1-based) line that each top-level def starts on
+    pub lines: MutMap<Symbol, u32>,
+}
+
+impl DebugLocations {
+    pub fn new(
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+        def_regions: &MutMap<Symbol, Region>,
+    ) -> Self {
+        let mut locations = Self::default();
+        let mut line_infos = MutMap::default();
+
+        for (symbol, region) in def_regions {
+            let module_id = symbol.module_id();
+
+            let Some((path, src)) = sources.get(&module_id) else {
+                continue;
+            };
+
+            let line_info = line_infos
+                .entry(module_id)
+                .or_insert_with(|| LineInfo::new(src));
+
+            // LineInfo lines start at 0, debug info lines at 1
+            let line = line_info.convert_pos(region.start()).line + 1;
+
+            locations.lines.insert(*symbol, line);
+            locations
+                .files
+                .entry(module_id)
+                .or_insert_with(|| path.clone());
+        }
+
+        locations
+    }
+
+    /// The file and line that the given def starts on, if it's known.
+    pub fn get(&self, symbol: Symbol) -> Option<(&PathBuf, u32)> {
+        let line = *self.lines.get(&symbol)?;
+        let path = self.files.get(&symbol.module_id())?;
+
+        Some((path, line))
+    }
+}
+
+/// The path of a source file as a reproducible build embeds it in the program. Files in
+/// `root_dir` (the root module's directory) get paths relative to it, and other relative paths
+/// are kept as they are. Other files, like the ones of a downloaded package, get paths relative
+/// to their package: the nearest directory above them with a main.roc.
+pub fn reproducible_path(path: &Path, root_dir: &Path) -> PathBuf {
+    if !root_dir.as_os_str().is_empty() {
+        if let Ok(relative) = path.strip_prefix(root_dir) {
+            return relative.to_path_buf();
+        }
+    }
+
+    if path.is_relative() {
+        return path.to_path_buf();
+    }
+
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.join("main.roc").is_file())
+        .and_then(|package_dir| path.strip_prefix(package_dir).ok())
+        .or_else(|| path.file_name().map(Path::new))
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reproducible_path_in_root_dir() {
+        let root_dir = Path::new("/home/me/app");
+
+        assert_eq!(
+            reproducible_path(Path::new("/home/me/app/src/Parser.roc"), root_dir),
+            Path::new("src/Parser.roc")
+        );
+    }
+
+    #[test]
+    fn reproducible_path_already_relative() {
+        let root_dir = Path::new("/home/me/app");
+
+        assert_eq!(
+            reproducible_path(Path::new("platform/Task.roc"), root_dir),
+            Path::new("platform/Task.roc")
+        );
+    }
+
+    #[test]
+    fn reproducible_path_in_package() {
+        let cache = tempfile::tempdir().unwrap();
+        let package_dir = cache.path().join("packages/abc123");
+        std::fs::create_dir_all(package_dir.join("Json")).unwrap();
+        std::fs::write(package_dir.join("main.roc"), "").unwrap();
+
+        let module = package_dir.join("Json/Decode.roc");
+        std::fs::write(&module, "").unwrap();
+
+        assert_eq!(
+            reproducible_path(&module, Path::new("/home/me/app")),
+            Path::new("Json/Decode.roc")
+        );
+    }
+
+    #[test]
+    fn reproducible_path_outside_any_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("Loose.roc");
+        std::fs::write(&file, "").unwrap();
+
+        assert_eq!(
+            reproducible_path(&file, Path::new("/home/me/app")),
+            Path::new("Loose.roc")
+        );
+    }
+}
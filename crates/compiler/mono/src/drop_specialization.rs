@@ -1536,7 +1536,7 @@ fn low_level_no_rc(lowlevel: &LowLevel) -> RC {
         ListLenU64 | ListLenUsize | StrIsEmpty | StrCountUtf8Bytes | ListGetCapacity
         | ListWithCapacity | StrWithCapacity => RC::NoRc,
         ListReplaceUnsafe => RC::Rc,
-        StrGetUnsafe | ListGetUnsafe => RC::NoRc,
+        StrGetUnsafe | StrGraphemeEnd | ListGetUnsafe => RC::NoRc,
         ListConcat => RC::Rc,
         StrConcat => RC::Rc,
         StrSubstringUnsafe => RC::Rc,
@@ -1567,6 +1567,8 @@ fn low_level_no_rc(lowlevel: &LowLevel) -> RC {
         | NumPow | NumPowInt | NumBitwiseAnd | NumBitwiseXor | NumBitwiseOr | NumShiftLeftBy
         | NumShiftRightBy | NumShiftRightZfBy => RC::NoRc,
 
+        NumToStrWith => RC::NoRc,
+
         NumToStr
         | NumAbs
         | NumNeg
@@ -1608,6 +1610,8 @@ fn low_level_no_rc(lowlevel: &LowLevel) -> RC {
 
         ListIsUnique => RC::Rc,
         ListClone => RC::Rc,
+        JsonSkipWhitespace | JsonValueEnd => RC::NoRc,
+        InspectMatchesSnapshot => RC::NoRc,
 
         BoxExpr | UnboxExpr => {
             unreachable!("These lowlevel operations are turned into mono Expr's")
@@ -0,0 +1,65 @@
+//! Finds which of the platform's effects a program can actually perform.
+//!
+//! Every effect a platform provides is a hosted function, which mono turns into a foreign call
+//! to `roc_fx_{name}`. Mono only specializes the procs that are reachable from the entry point
+//! (including the closures the host calls back into), so the foreign calls left in the
+//! specialized procs are exactly the effects the program can reach. Hosts can use that to skip
+//! setting up subsystems (like networking) that the app never uses.
+use crate::inline_hints::visit_stmts;
+use crate::ir::{Call, CallType, Expr, Proc};
+use std::collections::BTreeSet;
+
+/// The data symbol that lists the effects an app uses, for hosts that want to check.
+///
+/// It's a sequence of NUL-terminated `roc_fx_*` names, followed by an empty name (so it ends in
+/// two NUL bytes). An app that uses no effects has a single NUL byte.
+pub const USED_EFFECTS_SYMBOL: &str = "roc__used_effects";
+
+/// The names of the foreign functions (e.g. `roc_fx_putLine`) called from `procs`, sorted.
+pub fn used_effects<'p, 'a: 'p>(procs: impl IntoIterator<Item = &'p Proc<'a>>) -> Vec<String> {
+    let mut effects = BTreeSet::new();
+
+    for proc in procs {
+        visit_stmts(&proc.body, |expr| {
+            if let Expr::Call(Call {
+                call_type: CallType::Foreign { foreign_symbol, .. },
+                ..
+            }) = expr
+            {
+                effects.insert(foreign_symbol.as_str().to_string());
+            }
+        });
+    }
+
+    effects.into_iter().collect()
+}
+
+/// The bytes of [USED_EFFECTS_SYMBOL], for the given effects.
+pub fn used_effects_bytes(effects: &[String]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for effect in effects {
+        bytes.extend_from_slice(effect.as_bytes());
+        bytes.push(0);
+    }
+
+    bytes.push(0);
+
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn used_effects_bytes_are_nul_separated() {
+        let effects = ["roc_fx_getLine".to_string(), "roc_fx_putLine".to_string()];
+
+        assert_eq!(
+            used_effects_bytes(&effects),
+            b"roc_fx_getLine\0roc_fx_putLine\0\0"
+        );
+        assert_eq!(used_effects_bytes(&[]), b"\0");
+    }
+}
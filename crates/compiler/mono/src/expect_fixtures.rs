@@ -0,0 +1,78 @@
+//! Finds the top-level constants that each top-level expect can evaluate.
+//!
+//! A top-level constant is a proc without arguments (a thunk), which is evaluated again every
+//! time it's used. When `roc test` runs a module's expects, it memoizes these thunks and
+//! evaluates the ones an expect can reach before running that expect, so an expensive constant
+//! that many expects share is only computed once.
+use crate::inline_hints::visit_stmts;
+use crate::ir::{Call, CallType, Expr, Proc, ProcLayout};
+use roc_collections::{MutMap, MutSet};
+use roc_module::symbol::Symbol;
+
+/// The exported function that forgets every memoized constant, so they are evaluated again the
+/// next time they're used. The test runner calls it when evaluating a constant up front crashed
+/// or failed an inline `expect`, so that each expect using it reports that on its own.
+pub const RESET_EXPECT_FIXTURES_SYMBOL: &str = "roc__reset_expect_fixtures";
+
+/// For each of the `expects`, the specializations of the top-level constants it can evaluate,
+/// either directly or through the functions it calls.
+pub fn expect_fixtures<'a>(
+    procs: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+    expects: &[Symbol],
+) -> MutMap<Symbol, Vec<(Symbol, ProcLayout<'a>)>> {
+    let mut callees: MutMap<Symbol, MutSet<Symbol>> = MutMap::default();
+    let mut constants: MutMap<Symbol, Vec<ProcLayout<'a>>> = MutMap::default();
+
+    for ((symbol, layout), proc) in procs {
+        if proc.args.is_empty() && !proc.is_erased && !expects.contains(symbol) {
+            constants.entry(*symbol).or_default().push(*layout);
+        }
+
+        let callees = callees.entry(*symbol).or_default();
+
+        visit_stmts(&proc.body, |expr| match expr {
+            Expr::Call(Call {
+                call_type: CallType::ByName { name, .. },
+                ..
+            }) => {
+                callees.insert(name.name());
+            }
+            Expr::Call(Call {
+                call_type: CallType::HigherOrder(higher_order),
+                ..
+            }) => {
+                callees.insert(higher_order.passed_function.name.name());
+            }
+            Expr::FunctionPointer { lambda_name } => {
+                callees.insert(lambda_name.name());
+            }
+            _ => {}
+        });
+    }
+
+    let mut fixtures_by_expect = MutMap::default();
+
+    for expect in expects {
+        let mut fixtures = Vec::new();
+        let mut seen = MutSet::default();
+        let mut stack = vec![*expect];
+
+        while let Some(symbol) = stack.pop() {
+            for callee in callees.get(&symbol).into_iter().flatten() {
+                if !seen.insert(*callee) {
+                    continue;
+                }
+
+                if let Some(layouts) = constants.get(callee) {
+                    fixtures.extend(layouts.iter().map(|layout| (*callee, *layout)));
+                }
+
+                stack.push(*callee);
+            }
+        }
+
+        fixtures_by_expect.insert(*expect, fixtures);
+    }
+
+    fixtures_by_expect
+}
@@ -1285,7 +1285,7 @@ pub(crate) fn lowlevel_borrow_signature(op: LowLevel) -> &'static [Ownership] {
         ListLenU64 | ListLenUsize | StrIsEmpty | StrCountUtf8Bytes | ListGetCapacity => &[BORROWED],
         ListWithCapacity | StrWithCapacity => &[IRRELEVANT],
         ListReplaceUnsafe => &[OWNED, IRRELEVANT, IRRELEVANT],
-        StrGetUnsafe | ListGetUnsafe => &[BORROWED, IRRELEVANT],
+        StrGetUnsafe | StrGraphemeEnd | ListGetUnsafe => &[BORROWED, IRRELEVANT],
         ListConcat => &[OWNED, OWNED],
         StrConcat => &[OWNED, BORROWED],
         StrSubstringUnsafe => &[OWNED, IRRELEVANT, IRRELEVANT],
@@ -1319,6 +1319,8 @@ pub(crate) fn lowlevel_borrow_signature(op: LowLevel) -> &'static [Ownership] {
         | NumPow | NumPowInt | NumBitwiseAnd | NumBitwiseXor | NumBitwiseOr | NumShiftLeftBy
         | NumShiftRightBy | NumShiftRightZfBy => &[IRRELEVANT, IRRELEVANT],
 
+        NumToStrWith => &[BORROWED, IRRELEVANT, IRRELEVANT, IRRELEVANT],
+
         NumToStr
         | NumAbs
         | NumNeg
@@ -1360,6 +1362,8 @@ pub(crate) fn lowlevel_borrow_signature(op: LowLevel) -> &'static [Ownership] {
 
         ListIsUnique => &[BORROWED],
         ListClone => &[OWNED],
+        JsonSkipWhitespace | JsonValueEnd => &[BORROWED, IRRELEVANT],
+        InspectMatchesSnapshot => &[BORROWED, BORROWED],
 
         BoxExpr | UnboxExpr => {
             unreachable!("These lowlevel operations are turned into mono Expr's")
@@ -0,0 +1,141 @@
+//! Decides which procs are worth inlining, so backends can pass that on to their optimizer.
+//!
+//! LLVM's own heuristics often leave small helpers out-of-line, especially the closures passed
+//! to higher-order builtins like `List.map`, because it only sees them behind a function
+//! pointer. Here we still know every call site, so we can tell it which procs are cheap.
+use crate::ir::{Call, CallType, Expr, Proc, ProcLayout, SelfRecursive, Stmt};
+use roc_collections::MutMap;
+use roc_module::symbol::Symbol;
+
+/// The largest proc (in statements) that gets an inline hint, unless configured otherwise.
+pub const DEFAULT_INLINE_THRESHOLD: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineHint {
+    /// The proc is so small that a call costs about as much as its body
+    Always,
+    /// The proc is small and called from several places
+    Hint,
+}
+
+/// Procs with at most `threshold` statements that are called from at least two places get
+/// [InlineHint::Hint], and procs with at most a quarter of that get [InlineHint::Always].
+/// Self-recursive procs never get a hint. A threshold of 0 turns hints off.
+pub fn inline_hints<'a>(
+    procs: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+    threshold: u32,
+) -> MutMap<(Symbol, ProcLayout<'a>), InlineHint> {
+    let mut hints = MutMap::default();
+
+    if threshold == 0 {
+        return hints;
+    }
+
+    let mut call_sites: MutMap<(Symbol, ProcLayout<'a>), u32> = MutMap::default();
+    let mut sizes = MutMap::default();
+
+    for (key, proc) in procs.iter() {
+        let size = visit_stmts(&proc.body, |expr| {
+            if let Some(callee) = called_proc(expr) {
+                *call_sites.entry(callee).or_default() += 1;
+            }
+        });
+
+        sizes.insert(*key, size);
+    }
+
+    for (key, proc) in procs.iter() {
+        if let SelfRecursive::SelfRecursive(_) = proc.is_self_recursive {
+            continue;
+        }
+
+        let size = sizes[key];
+        let calls = call_sites.get(key).copied().unwrap_or(0);
+
+        if size <= (threshold / 4).max(1) {
+            hints.insert(*key, InlineHint::Always);
+        } else if size <= threshold && calls >= 2 {
+            hints.insert(*key, InlineHint::Hint);
+        }
+    }
+
+    hints
+}
+
+/// The proc that an expression calls, including the function passed to a higher-order
+/// builtin, which ends up being called once per element.
+pub(crate) fn called_proc<'a>(expr: &Expr<'a>) -> Option<(Symbol, ProcLayout<'a>)> {
+    match expr {
+        Expr::Call(Call {
+            call_type:
+                CallType::ByName {
+                    name,
+                    ret_layout,
+                    arg_layouts,
+                    ..
+                },
+            ..
+        }) => Some((
+            name.name(),
+            ProcLayout {
+                arguments: arg_layouts,
+                result: *ret_layout,
+                niche: name.niche(),
+            },
+        )),
+        Expr::Call(Call {
+            call_type: CallType::HigherOrder(higher_order),
+            ..
+        }) => {
+            let passed_function = &higher_order.passed_function;
+
+            Some((
+                passed_function.name.name(),
+                ProcLayout {
+                    arguments: passed_function.argument_layouts,
+                    result: passed_function.return_layout,
+                    niche: passed_function.name.niche(),
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Calls `visit_expr` on every expression in the body, and returns the number of statements.
+pub fn visit_stmts<'a>(body: &Stmt<'a>, mut visit_expr: impl FnMut(&Expr<'a>)) -> u32 {
+    let mut size = 0;
+    let mut stack = vec![body];
+
+    while let Some(stmt) = stack.pop() {
+        size += 1;
+
+        match stmt {
+            Stmt::Let(_, expr, _, remainder) => {
+                visit_expr(expr);
+                stack.push(remainder);
+            }
+            Stmt::Switch {
+                branches,
+                default_branch,
+                ..
+            } => {
+                stack.extend(branches.iter().map(|(_, _, branch)| branch));
+                stack.push(default_branch.1);
+            }
+            Stmt::Refcounting(_, remainder)
+            | Stmt::Expect { remainder, .. }
+            | Stmt::ExpectFx { remainder, .. }
+            | Stmt::Dbg { remainder, .. } => stack.push(remainder),
+            Stmt::Join {
+                body, remainder, ..
+            } => {
+                stack.push(body);
+                stack.push(remainder);
+            }
+            Stmt::Ret(_) | Stmt::Jump(..) | Stmt::Crash(..) => {}
+        }
+    }
+
+    size
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::manual_map)]
 
+use crate::coverage::{self, CoverageSite};
 use crate::ir::erased::{build_erased_function, ResolvedErasedLambda};
 use crate::ir::literal::{make_num_literal, IntOrFloatValue};
 use crate::layout::{
@@ -8,6 +9,7 @@ use crate::layout::{
     LayoutRepr, Niche, RawFunctionLayout, TLLayoutInterner, TagIdIntType, UnionLayout,
     WrappedVariant,
 };
+use crate::spec_cache::SpecializationCache;
 use bumpalo::collections::{CollectIn, Vec};
 use bumpalo::Bump;
 use roc_can::abilities::SpecializationId;
@@ -42,12 +44,18 @@ use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder};
 use pattern::{from_can_pattern, store_pattern, Pattern};
 
 pub use literal::{ListLiteralElement, Literal};
+pub(crate) use text::{parse_anonymous_mono_text, proc_layout_to_mono_text};
+pub use text::{
+    parse_mono_procedures, parse_mono_text, proc_to_anonymous_mono_text, procs_to_mono_text,
+    MonoTextError,
+};
 
 mod boxed;
 mod decision_tree;
 mod erased;
 mod literal;
 mod pattern;
+mod text;
 
 #[inline(always)]
 pub fn pretty_print_ir_symbols() -> bool {
@@ -139,7 +147,14 @@ pub struct SingleEntryPoint<'a> {
 #[derive(Debug, Clone, Copy)]
 pub enum EntryPoint<'a> {
     Single(SingleEntryPoint<'a>),
-    Expects { symbols: &'a [Symbol] },
+    Expects {
+        symbols: &'a [Symbol],
+        /// The properties that `roc test --fuzz` calls with generated arguments
+        properties: &'a [(Symbol, ProcLayout<'a>)],
+        /// The top-level constants that the test runner memoizes, so their values are shared
+        /// (see [crate::expect_fixtures])
+        fixtures: &'a [(Symbol, ProcLayout<'a>)],
+    },
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1362,6 +1377,10 @@ pub struct Env<'a, 'i> {
     pub exposed_by_module: &'i ExposedByModule,
     pub derived_module: &'i SharedDerivedModule,
     pub struct_indexing: UsageTrackingMap<(Symbol, u64), Symbol>,
+    /// Where specializations of builtins are cached between builds, if anywhere
+    pub specialization_cache: Option<&'i SpecializationCache>,
+    /// Whether to count the top-level defs and `when` branches that run (see [crate::coverage])
+    pub coverage: bool,
 }
 
 impl<'a, 'i> Env<'a, 'i> {
@@ -3540,6 +3559,12 @@ fn specialize_proc_help<'a>(
 
     let mut specialized_body = from_can(env, body_var, body, procs, layout_cache);
 
+    if env.coverage {
+        let site = CoverageSite::Def(lambda_name.name());
+
+        specialized_body = coverage::count_site(env, site, specialized_body);
+    }
+
     let specialized_proc = match specialized {
         SpecializedLayout::FunctionPointerBody {
             ret_layout,
@@ -4038,6 +4063,16 @@ fn specialize_variable<'a>(
         raw
     };
 
+    let proc_layout = ProcLayout::from_raw_named(env.arena, proc_name, raw);
+
+    if let Some(cache) = env.specialization_cache {
+        if let Some(proc) = cache.get(env, &mut layout_cache.interner, proc_name, proc_layout) {
+            rollback_typestate(env.subs, procs, layout_cache, snapshot);
+
+            return Ok((proc, raw));
+        }
+    }
+
     // make sure rigid variables in the annotation are converted to flex variables
     let annotation_var = procs.partial_procs.get_id(partial_proc_id).annotation;
     instantiate_rigids(env.subs, annotation_var);
@@ -4057,6 +4092,10 @@ fn specialize_variable<'a>(
 
     let result = match specialized {
         Ok(proc) => {
+            if let Some(cache) = env.specialization_cache {
+                cache.insert(env.target, &layout_cache.interner, &proc, &proc_layout);
+            }
+
             // when successful, the layout after unification should be the layout before unification
             //            debug_assert_eq!(
             //                attempted_layout,
@@ -7220,6 +7259,7 @@ fn to_opt_branches<'a>(
     Pattern<'a>,
     Option<Loc<roc_can::expr::Expr>>,
     roc_can::expr::Expr,
+    Option<CoverageSite>,
 )> {
     debug_assert!(!branches.is_empty());
 
@@ -7231,6 +7271,8 @@ fn to_opt_branches<'a>(
             continue;
         }
 
+        let site = CoverageSite::branch(env.home, when_branch.value.region);
+
         for loc_pattern in when_branch.patterns {
             match from_can_pattern(env, procs, layout_cache, &loc_pattern.pattern.value) {
                 Ok((mono_pattern, assignments)) => {
@@ -7264,7 +7306,12 @@ fn to_opt_branches<'a>(
                     };
 
                     // TODO remove clone?
-                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr.value));
+                    opt_branches.push((
+                        mono_pattern,
+                        when_branch.guard.clone(),
+                        loc_expr.value,
+                        Some(site),
+                    ));
                 }
                 Err(runtime_error) => {
                     // TODO remove clone?
@@ -7272,6 +7319,7 @@ fn to_opt_branches<'a>(
                         Pattern::Underscore,
                         when_branch.guard.clone(),
                         roc_can::expr::Expr::RuntimeError(runtime_error),
+                        Some(site),
                     ));
                 }
             }
@@ -7285,6 +7333,7 @@ fn to_opt_branches<'a>(
             Pattern::Underscore,
             None,
             roc_can::expr::Expr::RuntimeError(roc_problem::can::RuntimeError::NonExhaustivePattern),
+            None,
         ));
     }
 
@@ -7325,7 +7374,7 @@ fn from_can_when<'a>(
     let arena = env.arena;
     let it = opt_branches
         .into_iter()
-        .filter_map(|(pattern, opt_guard, can_expr)| {
+        .filter_map(|(pattern, opt_guard, can_expr, opt_site)| {
             // If the pattern has a void layout we can drop it; however, we must still perform the
             // work of building the body, because that may contain specializations we must
             // discover for use elsewhere. See
@@ -7353,6 +7402,11 @@ fn from_can_when<'a>(
                 }
             };
 
+            let branch_stmt = match opt_site {
+                Some(site) if env.coverage => coverage::count_site(env, site, branch_stmt),
+                _ => branch_stmt,
+            };
+
             use decision_tree::Guard;
             let result = if let Some(loc_expr) = opt_guard {
                 let guard_spec = GuardStmtSpec {
@@ -0,0 +1,2124 @@
+//! A stable textual format for mono IR, and a parser for it.
+//!
+//! The pretty printer behind `ROC_PRINT_IR_AFTER_SPECIALIZATION` is meant for humans, and drops
+//! details like layouts. This format keeps everything that backends look at, so that a `.mono`
+//! file can be parsed back and handed to a backend directly, without running the frontend.
+//!
+//! ```text
+//! proc `Test.main`(`Test.x`: I64) -> I64 {
+//!     let `Test.1`: I64 = int 1;
+//!     let `Test.2`: I64 = lowlevel NumAdd(`Test.x`, `Test.1`);
+//!     ret `Test.2`;
+//! }
+//! ```
+//!
+//! Symbols are written `` `Module.ident` ``, with `` \` `` and `\\` escaping a backtick or
+//! backslash in the name, and are resolved against the [Interns] when parsing. Names that
+//! don't start with a known module are added to the home module.
+//!
+//! Layouts are written as `I8`..`U128`, `F32`, `F64`, `Dec`, `Bool`, `Str`, `List(L)`,
+//! `Ptr(L)` and `{L, L}` for structs. Tag unions are written as their [UnionLayout] variant
+//! with a list of tags, e.g. `Union[[I64], [Str, U8]]`, `Rec[[*, I64], []]`,
+//! `RecUnwrapped[I64, *]`, `RecNullable(0)[[I64, *]]` and `RecNullableUnwrapped(1)[I64, *]`,
+//! where `*` is the recursion pointer of the innermost recursive union.
+//!
+//! Lambda sets are written as ``Lambda((L) -> L, [`f`[L], `g`[]], R)``, with the captures of
+//! each lambda and the runtime representation `R`. Inside the captures, `*` points back at the
+//! lambda set. Function pointers are `FnPtr((L) -> L)`, and erased values are `Erased`. A
+//! lambda with a captures niche is written as `` `f` niche[L] `` wherever it is named, and a
+//! `*` in a niche points back at the lambda set too.
+//!
+//! `expect` and `dbg` are not supported, since backends render their values using type
+//! variables that the text doesn't keep. The printer writes them as `!unsupported(...)`, which
+//! the parser rejects. Semantic layout information (like record field names) is not kept
+//! either.
+//!
+//! [parse_mono_procedures] gives parsed procs in the shape backends take, so a `.mono` file
+//! can be compiled without running the frontend.
+use std::fmt::{self, Write};
+
+use bumpalo::collections::Vec;
+use bumpalo::Bump;
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
+use roc_collections::all::{MutMap, MutSet};
+use roc_module::ident::{ForeignSymbol, ModuleName};
+use roc_module::low_level::LowLevel;
+use roc_module::symbol::{IdentIds, Interns, ModuleId, Symbol};
+
+use crate::layout::{
+    Builtin, FunctionPointer, InLayout, LambdaName, Layout, LayoutInterner, LayoutRepr,
+    NeedsRecursionPointerFixup, Niche, UnionLayout,
+};
+use crate::low_level::HigherOrder;
+
+use super::{
+    BranchInfo, Call, CallSpecId, CallType, CrashTag, Env, ErasedField, Expr, HigherOrderLowLevel,
+    JoinPointId, ListLiteralElement, Literal, ModifyRc, Param, PassedFunction, Proc, ProcLayout,
+    ReuseToken, SelfRecursive, Stmt, UpdateModeIds,
+};
+
+const INDENT: usize = 4;
+
+/// Writes the procs in the mono text format, in the given order.
+pub fn procs_to_mono_text<'a, 'p, I>(
+    interner: &I,
+    interns: &Interns,
+    procs: impl IntoIterator<Item = &'p Proc<'a>>,
+) -> String
+where
+    'a: 'p,
+    I: LayoutInterner<'a>,
+{
+    let mut printer = Printer::new(interner, Naming::Interns(interns));
+
+    for (i, proc) in procs.into_iter().enumerate() {
+        if i > 0 {
+            printer.buf.push('\n');
+        }
+
+        printer.proc(proc);
+    }
+
+    printer.buf
+}
+
+/// Writes a single proc without using the names of its symbols: the proc itself is called
+/// `self`, and every other symbol gets a number. Returns None if the proc uses anything the
+/// format doesn't support.
+pub fn proc_to_anonymous_mono_text<'a, I>(interner: &I, proc: &Proc<'a>) -> Option<String>
+where
+    I: LayoutInterner<'a>,
+{
+    let mut printer = Printer::new(
+        interner,
+        Naming::Anonymous {
+            own: Some(proc.name.name()),
+        },
+    );
+
+    printer.proc(proc);
+
+    supported(printer.buf)
+}
+
+/// Writes the layout of a proc as `(L, L) -> L`, or None if the format doesn't support it.
+pub(crate) fn proc_layout_to_mono_text<'a, I>(
+    interner: &I,
+    proc_layout: &ProcLayout<'a>,
+) -> Option<String>
+where
+    I: LayoutInterner<'a>,
+{
+    if proc_layout.niche != Niche::NONE {
+        return None;
+    }
+
+    let mut printer = Printer::new(interner, Naming::Anonymous { own: None });
+
+    printer.function_layout(proc_layout.arguments, proc_layout.result);
+
+    supported(printer.buf)
+}
+
+fn supported(text: String) -> Option<String> {
+    if text.contains("!unsupported") {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+enum Naming<'r> {
+    /// Symbols are written as `Module.ident`
+    Interns(&'r Interns),
+    /// The proc being written is `self`, and all other symbols are numbered
+    Anonymous { own: Option<Symbol> },
+}
+
+struct Printer<'r, I> {
+    interner: &'r I,
+    naming: Naming<'r>,
+    names: MutMap<Symbol, String>,
+    taken: MutSet<String>,
+    buf: String,
+    /// How many recursive unions, lambda set captures and niches we are inside of, which is
+    /// what a `*` can point back at
+    recursive_depth: u32,
+}
+
+impl<'r, 'a, I: LayoutInterner<'a>> Printer<'r, I> {
+    fn new(interner: &'r I, naming: Naming<'r>) -> Self {
+        Self {
+            interner,
+            naming,
+            names: MutMap::default(),
+            taken: MutSet::default(),
+            buf: String::new(),
+            recursive_depth: 0,
+        }
+    }
+
+    fn proc(&mut self, proc: &Proc<'a>) {
+        self.buf.push_str("proc ");
+        self.symbol(proc.name.name());
+        self.buf.push('(');
+
+        for (i, (layout, symbol)) in proc.args.iter().enumerate() {
+            if i > 0 {
+                self.buf.push_str(", ");
+            }
+
+            self.symbol(*symbol);
+            self.buf.push_str(": ");
+            self.layout(*layout);
+        }
+
+        self.buf.push_str(") -> ");
+        self.layout(proc.ret_layout);
+
+        self.niche(proc.name.niche());
+
+        if let Some(closure_data_layout) = proc.closure_data_layout {
+            self.buf.push_str(" closure(");
+            self.layout(closure_data_layout);
+            self.buf.push(')');
+        }
+
+        if let SelfRecursive::SelfRecursive(id) = proc.is_self_recursive {
+            self.buf.push_str(" selfrec(");
+            self.symbol(id.0);
+            self.buf.push(')');
+        }
+
+        if proc.is_erased {
+            self.buf.push_str(" erased");
+        }
+
+        self.buf.push_str(" {\n");
+        self.stmt(&proc.body, INDENT);
+        self.buf.push_str("}\n");
+    }
+
+    fn stmt(&mut self, mut stmt: &Stmt<'a>, indent: usize) {
+        loop {
+            self.indent(indent);
+
+            match stmt {
+                Stmt::Let(symbol, expr, layout, remainder) => {
+                    self.buf.push_str("let ");
+                    self.symbol(*symbol);
+                    self.buf.push_str(": ");
+                    self.layout(*layout);
+                    self.buf.push_str(" = ");
+                    self.expr(expr);
+                    self.buf.push_str(";\n");
+
+                    stmt = remainder;
+                }
+                Stmt::Refcounting(modify, remainder) => {
+                    match modify {
+                        ModifyRc::Inc(symbol, amount) => {
+                            self.buf.push_str("inc ");
+                            self.symbol(*symbol);
+                            let _ = write!(self.buf, " {amount}");
+                        }
+                        ModifyRc::Dec(symbol) => {
+                            self.buf.push_str("dec ");
+                            self.symbol(*symbol);
+                        }
+                        ModifyRc::DecRef(symbol) => {
+                            self.buf.push_str("decref ");
+                            self.symbol(*symbol);
+                        }
+                        ModifyRc::Free(symbol) => {
+                            self.buf.push_str("free ");
+                            self.symbol(*symbol);
+                        }
+                    }
+                    self.buf.push_str(";\n");
+
+                    stmt = remainder;
+                }
+                Stmt::Expect { remainder, .. } => {
+                    self.buf.push_str("!unsupported(expect);\n");
+                    stmt = remainder;
+                }
+                Stmt::ExpectFx { remainder, .. } => {
+                    self.buf.push_str("!unsupported(expect-fx);\n");
+                    stmt = remainder;
+                }
+                Stmt::Dbg { remainder, .. } => {
+                    self.buf.push_str("!unsupported(dbg);\n");
+                    stmt = remainder;
+                }
+                Stmt::Join {
+                    id,
+                    parameters,
+                    body,
+                    remainder,
+                } => {
+                    self.buf.push_str("join ");
+                    self.symbol(id.0);
+                    self.buf.push('(');
+
+                    for (i, param) in parameters.iter().enumerate() {
+                        if i > 0 {
+                            self.buf.push_str(", ");
+                        }
+
+                        self.symbol(param.symbol);
+                        self.buf.push_str(": ");
+                        self.layout(param.layout);
+                    }
+
+                    self.buf.push_str(") {\n");
+                    self.stmt(body, indent + INDENT);
+                    self.indent(indent);
+                    self.buf.push_str("}\n");
+
+                    stmt = remainder;
+                }
+                Stmt::Switch {
+                    cond_symbol,
+                    cond_layout,
+                    branches,
+                    default_branch,
+                    ret_layout,
+                } => {
+                    self.buf.push_str("switch ");
+                    self.symbol(*cond_symbol);
+                    self.buf.push_str(": ");
+                    self.layout(*cond_layout);
+                    self.buf.push_str(" -> ");
+                    self.layout(*ret_layout);
+                    self.buf.push_str(" {\n");
+
+                    for (value, info, branch) in branches.iter() {
+                        self.indent(indent + INDENT);
+                        let _ = write!(self.buf, "case {value}");
+                        self.branch_info(info);
+                        self.buf.push_str(" {\n");
+                        self.stmt(branch, indent + 2 * INDENT);
+                        self.indent(indent + INDENT);
+                        self.buf.push_str("}\n");
+                    }
+
+                    self.indent(indent + INDENT);
+                    self.buf.push_str("default");
+                    self.branch_info(&default_branch.0);
+                    self.buf.push_str(" {\n");
+                    self.stmt(default_branch.1, indent + 2 * INDENT);
+                    self.indent(indent + INDENT);
+                    self.buf.push_str("}\n");
+
+                    self.indent(indent);
+                    self.buf.push_str("}\n");
+
+                    return;
+                }
+                Stmt::Ret(symbol) => {
+                    self.buf.push_str("ret ");
+                    self.symbol(*symbol);
+                    self.buf.push_str(";\n");
+
+                    return;
+                }
+                Stmt::Jump(id, arguments) => {
+                    self.buf.push_str("jump ");
+                    self.symbol(id.0);
+                    self.arguments(arguments);
+                    self.buf.push_str(";\n");
+
+                    return;
+                }
+                Stmt::Crash(symbol, tag) => {
+                    self.buf.push_str("crash ");
+                    self.symbol(*symbol);
+                    self.buf.push_str(match tag {
+                        CrashTag::Roc => " roc;\n",
+                        CrashTag::User => " user;\n",
+                    });
+
+                    return;
+                }
+            }
+        }
+    }
+
+    fn branch_info(&mut self, info: &BranchInfo<'a>) {
+        match info {
+            BranchInfo::None => {}
+            BranchInfo::Constructor {
+                scrutinee,
+                layout,
+                tag_id,
+            } => {
+                self.buf.push_str(" ctor(");
+                self.symbol(*scrutinee);
+                self.buf.push_str(": ");
+                self.layout(*layout);
+                let _ = write!(self.buf, ", {tag_id})");
+            }
+            BranchInfo::List { scrutinee, len } => {
+                self.buf.push_str(" list(");
+                self.symbol(*scrutinee);
+                let _ = write!(self.buf, ", {len})");
+            }
+            BranchInfo::Unique { scrutinee, unique } => {
+                self.buf.push_str(" unique(");
+                self.symbol(*scrutinee);
+                let _ = write!(self.buf, ", {unique})");
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr<'a>) {
+        match expr {
+            Expr::Literal(literal) => self.literal(literal),
+            Expr::Call(Call {
+                call_type,
+                arguments,
+            }) => match call_type {
+                CallType::ByName {
+                    name,
+                    ret_layout,
+                    arg_layouts,
+                    ..
+                } => {
+                    self.buf.push_str("call ");
+                    self.lambda_name(*name);
+                    self.arguments(arguments);
+                    self.buf.push(' ');
+                    self.function_layout(arg_layouts, *ret_layout);
+                }
+                CallType::ByPointer {
+                    pointer,
+                    ret_layout,
+                    arg_layouts,
+                } => {
+                    self.buf.push_str("call_ptr ");
+                    self.symbol(*pointer);
+                    self.arguments(arguments);
+                    self.buf.push(' ');
+                    self.function_layout(arg_layouts, *ret_layout);
+                }
+                CallType::Foreign {
+                    foreign_symbol,
+                    ret_layout,
+                } => {
+                    let _ = write!(self.buf, "foreign {:?}", foreign_symbol.as_str());
+                    self.arguments(arguments);
+                    self.buf.push_str(" -> ");
+                    self.layout(*ret_layout);
+                }
+                CallType::LowLevel { op, .. } => {
+                    let _ = write!(self.buf, "lowlevel {op:?}");
+                    self.arguments(arguments);
+                }
+                CallType::HigherOrder(HigherOrderLowLevel {
+                    op,
+                    closure_env_layout,
+                    passed_function,
+                    ..
+                }) => {
+                    let (name, lists) = higher_order_parts(op);
+                    let _ = write!(self.buf, "higher_order {name}");
+                    self.arguments(&lists);
+                    self.buf.push_str(" pass ");
+                    self.lambda_name(passed_function.name);
+                    self.buf.push(' ');
+                    self.function_layout(
+                        passed_function.argument_layouts,
+                        passed_function.return_layout,
+                    );
+                    self.buf.push_str(" env ");
+                    self.symbol(passed_function.captured_environment);
+                    self.buf
+                        .push_str(if passed_function.owns_captured_environment {
+                            " owned"
+                        } else {
+                            " borrowed"
+                        });
+
+                    if let Some(layout) = closure_env_layout {
+                        self.buf.push_str(" closure(");
+                        self.layout(*layout);
+                        self.buf.push(')');
+                    }
+
+                    self.buf.push(' ');
+                    self.arguments(arguments);
+                }
+            },
+            Expr::Tag {
+                tag_layout,
+                tag_id,
+                arguments,
+                reuse,
+            } => {
+                self.buf.push_str("tag ");
+
+                if let Some(reuse) = reuse {
+                    self.buf.push_str("reuse(");
+                    self.symbol(reuse.symbol);
+                    let _ = write!(self.buf, ", {}) ", reuse.update_tag_id);
+                }
+
+                self.union_layout(*tag_layout);
+                let _ = write!(self.buf, " {tag_id}");
+                self.arguments(arguments);
+            }
+            Expr::Struct(fields) => {
+                self.buf.push_str("struct");
+                self.arguments(fields);
+            }
+            Expr::NullPointer => self.buf.push_str("null"),
+            Expr::StructAtIndex {
+                index,
+                field_layouts,
+                structure,
+            } => {
+                self.buf.push_str("struct_at_index ");
+                self.symbol(*structure);
+                let _ = write!(self.buf, " {index} ");
+                self.layouts('{', field_layouts, '}');
+            }
+            Expr::GetTagId {
+                structure,
+                union_layout,
+            } => {
+                self.buf.push_str("get_tag_id ");
+                self.symbol(*structure);
+                self.buf.push(' ');
+                self.union_layout(*union_layout);
+            }
+            Expr::UnionAtIndex {
+                structure,
+                tag_id,
+                union_layout,
+                index,
+            } => {
+                self.buf.push_str("union_at_index ");
+                self.symbol(*structure);
+                let _ = write!(self.buf, " {tag_id} {index} ");
+                self.union_layout(*union_layout);
+            }
+            Expr::GetElementPointer {
+                structure,
+                union_layout,
+                indices,
+            } => {
+                self.buf.push_str("get_element_pointer ");
+                self.symbol(*structure);
+                let _ = write!(self.buf, " {indices:?} ");
+                self.union_layout(*union_layout);
+            }
+            Expr::Array { elem_layout, elems } => {
+                self.buf.push_str("array ");
+                self.layout(*elem_layout);
+                self.buf.push_str(" [");
+
+                for (i, elem) in elems.iter().enumerate() {
+                    if i > 0 {
+                        self.buf.push_str(", ");
+                    }
+
+                    match elem {
+                        ListLiteralElement::Literal(literal) => self.literal(literal),
+                        ListLiteralElement::Symbol(symbol) => self.symbol(*symbol),
+                    }
+                }
+
+                self.buf.push(']');
+            }
+            Expr::EmptyArray => self.buf.push_str("empty_array"),
+            Expr::ErasedMake { value, callee } => {
+                self.buf.push_str("erased_make ");
+                self.symbol(*callee);
+
+                if let Some(value) = value {
+                    self.buf.push(' ');
+                    self.symbol(*value);
+                }
+            }
+            Expr::ErasedLoad { symbol, field } => {
+                self.buf.push_str("erased_load ");
+                self.symbol(*symbol);
+                self.buf.push_str(match field {
+                    ErasedField::Value => " value",
+                    ErasedField::ValuePtr => " value_ptr",
+                    ErasedField::Callee => " callee",
+                });
+            }
+            Expr::FunctionPointer { lambda_name } => {
+                self.buf.push_str("function_pointer ");
+                self.lambda_name(*lambda_name);
+            }
+            Expr::Alloca {
+                element_layout,
+                initializer,
+            } => {
+                self.buf.push_str("alloca ");
+                self.layout(*element_layout);
+
+                if let Some(initializer) = initializer {
+                    self.buf.push(' ');
+                    self.symbol(*initializer);
+                }
+            }
+            Expr::Reset { symbol, .. } => {
+                self.buf.push_str("reset ");
+                self.symbol(*symbol);
+            }
+            Expr::ResetRef { symbol, .. } => {
+                self.buf.push_str("reset_ref ");
+                self.symbol(*symbol);
+            }
+            Expr::RuntimeErrorFunction(message) => {
+                let _ = write!(self.buf, "runtime_error {message:?}");
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal<'a>) {
+        let _ = match literal {
+            Literal::Int(bytes) => write!(self.buf, "int {}", i128::from_ne_bytes(*bytes)),
+            Literal::U128(bytes) => write!(self.buf, "u128 {}", u128::from_ne_bytes(*bytes)),
+            Literal::Float(float) => write!(self.buf, "float {float:?}"),
+            // The raw value, i.e. the decimal times 10^18, so that it round-trips exactly
+            Literal::Decimal(bytes) => write!(self.buf, "dec {}", i128::from_ne_bytes(*bytes)),
+            Literal::Str(string) => write!(self.buf, "str {string:?}"),
+            Literal::Bool(bool) => write!(self.buf, "bool {bool}"),
+            Literal::Byte(byte) => write!(self.buf, "byte {byte}"),
+        };
+    }
+
+    fn arguments(&mut self, arguments: &[Symbol]) {
+        self.buf.push('(');
+
+        for (i, symbol) in arguments.iter().enumerate() {
+            if i > 0 {
+                self.buf.push_str(", ");
+            }
+
+            self.symbol(*symbol);
+        }
+
+        self.buf.push(')');
+    }
+
+    fn function_layout(&mut self, arg_layouts: &[InLayout<'a>], ret_layout: InLayout<'a>) {
+        self.layouts('(', arg_layouts, ')');
+        self.buf.push_str(" -> ");
+        self.layout(ret_layout);
+    }
+
+    fn layouts(&mut self, open: char, layouts: &[InLayout<'a>], close: char) {
+        self.buf.push(open);
+
+        for (i, layout) in layouts.iter().enumerate() {
+            if i > 0 {
+                self.buf.push_str(", ");
+            }
+
+            self.layout(*layout);
+        }
+
+        self.buf.push(close);
+    }
+
+    fn tags(&mut self, tags: &[&[InLayout<'a>]]) {
+        self.buf.push('[');
+
+        for (i, fields) in tags.iter().enumerate() {
+            if i > 0 {
+                self.buf.push_str(", ");
+            }
+
+            self.layouts('[', fields, ']');
+        }
+
+        self.buf.push(']');
+    }
+
+    fn layout(&mut self, layout: InLayout<'a>) {
+        match self.interner.get_repr(layout) {
+            LayoutRepr::Builtin(builtin) => match builtin {
+                Builtin::Int(width) => self.buf.push_str(int_width_name(width)),
+                Builtin::Float(FloatWidth::F32) => self.buf.push_str("F32"),
+                Builtin::Float(FloatWidth::F64) => self.buf.push_str("F64"),
+                Builtin::Bool => self.buf.push_str("Bool"),
+                Builtin::Decimal => self.buf.push_str("Dec"),
+                Builtin::Str => self.buf.push_str("Str"),
+                Builtin::List(element) => {
+                    self.buf.push_str("List(");
+                    self.layout(element);
+                    self.buf.push(')');
+                }
+            },
+            LayoutRepr::Struct(fields) => self.layouts('{', fields, '}'),
+            LayoutRepr::Ptr(inner) => {
+                self.buf.push_str("Ptr(");
+                self.layout(inner);
+                self.buf.push(')');
+            }
+            LayoutRepr::Union(union_layout) => self.union_layout(union_layout),
+            LayoutRepr::RecursivePointer(_) if self.recursive_depth == 0 => {
+                // Nothing around it is what it points at, so the parser couldn't resolve a `*`
+                self.buf.push_str("!unsupported(recursion-pointer)");
+            }
+            LayoutRepr::RecursivePointer(_) => self.buf.push('*'),
+            LayoutRepr::LambdaSet(_) if matches!(self.naming, Naming::Anonymous { .. }) => {
+                // The lambdas in the set are other procs, which anonymous names can't refer to
+                self.buf.push_str("!unsupported(lambda-set)");
+            }
+            LayoutRepr::LambdaSet(lambda_set) => {
+                self.buf.push_str("Lambda(");
+                self.function_layout(lambda_set.args, lambda_set.ret);
+                self.buf.push_str(", [");
+                self.recursive_depth += 1;
+
+                for (i, (symbol, captures)) in lambda_set.set.iter().enumerate() {
+                    if i > 0 {
+                        self.buf.push_str(", ");
+                    }
+
+                    self.symbol(*symbol);
+                    self.layouts('[', captures, ']');
+                }
+
+                self.recursive_depth -= 1;
+                self.buf.push_str("], ");
+                self.layout(lambda_set.representation);
+                self.buf.push(')');
+            }
+            LayoutRepr::FunctionPointer(FunctionPointer { args, ret }) => {
+                self.buf.push_str("FnPtr(");
+                self.function_layout(args, ret);
+                self.buf.push(')');
+            }
+            LayoutRepr::Erased(_) => self.buf.push_str("Erased"),
+        }
+    }
+
+    fn union_layout(&mut self, union_layout: UnionLayout<'a>) {
+        let is_recursive = !matches!(union_layout, UnionLayout::NonRecursive(_));
+
+        if is_recursive {
+            self.recursive_depth += 1;
+        }
+
+        match union_layout {
+            UnionLayout::NonRecursive(tags) => {
+                self.buf.push_str("Union");
+                self.tags(tags);
+            }
+            UnionLayout::Recursive(tags) => {
+                self.buf.push_str("Rec");
+                self.tags(tags);
+            }
+            UnionLayout::NonNullableUnwrapped(fields) => {
+                self.buf.push_str("RecUnwrapped");
+                self.layouts('[', fields, ']');
+            }
+            UnionLayout::NullableWrapped {
+                nullable_id,
+                other_tags,
+            } => {
+                let _ = write!(self.buf, "RecNullable({nullable_id})");
+                self.tags(other_tags);
+            }
+            UnionLayout::NullableUnwrapped {
+                nullable_id,
+                other_fields,
+            } => {
+                let _ = write!(self.buf, "RecNullableUnwrapped({})", nullable_id as u8);
+                self.layouts('[', other_fields, ']');
+            }
+        }
+
+        if is_recursive {
+            self.recursive_depth -= 1;
+        }
+    }
+
+    fn lambda_name(&mut self, lambda_name: LambdaName<'a>) {
+        self.symbol(lambda_name.name());
+        self.niche(lambda_name.niche());
+    }
+
+    fn niche(&mut self, niche: Niche<'a>) {
+        if niche != Niche::NONE {
+            self.buf.push_str(" niche");
+            self.recursive_depth += 1;
+            self.layouts('[', niche.captures(), ']');
+            self.recursive_depth -= 1;
+        }
+    }
+
+    fn symbol(&mut self, symbol: Symbol) {
+        if let Some(name) = self.names.get(&symbol) {
+            write_symbol_name(&mut self.buf, name);
+            return;
+        }
+
+        let base = match self.naming {
+            Naming::Interns(interns) => qualified_name(interns, symbol),
+            Naming::Anonymous { own } if own == Some(symbol) => "self".to_string(),
+            Naming::Anonymous { .. } => format!("%{}", self.names.len()),
+        };
+
+        // Idents are not unique within a module (shadowing, generated helpers), but names in
+        // the text must be
+        let mut name = base.clone();
+        let mut suffix = 1;
+
+        while self.taken.contains(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+
+        write_symbol_name(&mut self.buf, &name);
+
+        self.taken.insert(name.clone());
+        self.names.insert(symbol, name);
+    }
+
+    fn indent(&mut self, indent: usize) {
+        let _ = write!(self.buf, "{:indent$}", "");
+    }
+}
+
+/// Writes a symbol's name between backticks. Generated idents can contain backticks themselves,
+/// so those (and backslashes) are escaped with a backslash.
+fn write_symbol_name(buf: &mut String, name: &str) {
+    buf.push('`');
+
+    for c in name.chars() {
+        if matches!(c, '`' | '\\') {
+            buf.push('\\');
+        }
+
+        buf.push(c);
+    }
+
+    buf.push('`');
+}
+
+fn qualified_name(interns: &Interns, symbol: Symbol) -> String {
+    let module_id = symbol.module_id();
+    let ident_id = symbol.ident_id();
+
+    let module_name = match interns.module_ids.get_name(module_id) {
+        Some(name) => name.as_str().to_string(),
+        None => format!("{module_id:?}"),
+    };
+
+    match interns
+        .all_ident_ids
+        .get(&module_id)
+        .and_then(|ident_ids| ident_ids.get_name(ident_id))
+    {
+        Some(ident) => format!("{module_name}.{ident}"),
+        None => format!("{module_name}.{}", ident_id.index()),
+    }
+}
+
+/// The name of a higher-order lowlevel, and the lists it works on
+fn higher_order_parts(op: &HigherOrder) -> (&'static str, std::vec::Vec<Symbol>) {
+    match *op {
+        HigherOrder::ListMap { xs } => ("ListMap", vec![xs]),
+        HigherOrder::ListMap2 { xs, ys } => ("ListMap2", vec![xs, ys]),
+        HigherOrder::ListMap3 { xs, ys, zs } => ("ListMap3", vec![xs, ys, zs]),
+        HigherOrder::ListMap4 { xs, ys, zs, ws } => ("ListMap4", vec![xs, ys, zs, ws]),
+        HigherOrder::ListSortWith { xs } => ("ListSortWith", vec![xs]),
+    }
+}
+
+fn higher_order_from_parts(name: &str, lists: &[Symbol]) -> Option<HigherOrder> {
+    let op = match (name, lists) {
+        ("ListMap", &[xs]) => HigherOrder::ListMap { xs },
+        ("ListMap2", &[xs, ys]) => HigherOrder::ListMap2 { xs, ys },
+        ("ListMap3", &[xs, ys, zs]) => HigherOrder::ListMap3 { xs, ys, zs },
+        ("ListMap4", &[xs, ys, zs, ws]) => HigherOrder::ListMap4 { xs, ys, zs, ws },
+        ("ListSortWith", &[xs]) => HigherOrder::ListSortWith { xs },
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn int_width_name(width: IntWidth) -> &'static str {
+    match width {
+        IntWidth::U8 => "U8",
+        IntWidth::U16 => "U16",
+        IntWidth::U32 => "U32",
+        IntWidth::U64 => "U64",
+        IntWidth::U128 => "U128",
+        IntWidth::I8 => "I8",
+        IntWidth::I16 => "I16",
+        IntWidth::I32 => "I32",
+        IntWidth::I64 => "I64",
+        IntWidth::I128 => "I128",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonoTextError {
+    /// The line the error was found on, starting at 1
+    pub line: u32,
+    pub message: String,
+}
+
+impl fmt::Display for MonoTextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for MonoTextError {}
+
+/// Parses procs written by [procs_to_mono_text]. Symbols of known modules are looked up (or
+/// added) in those modules' ident ids, and all other names become symbols in `home`.
+///
+/// Calls get fresh specialization ids, and resets and lowlevels get fresh update mode ids, so
+/// alias analysis has to run again on the result.
+pub fn parse_mono_text<'a, I>(
+    arena: &'a Bump,
+    interner: &mut I,
+    interns: &mut Interns,
+    home: ModuleId,
+    text: &str,
+) -> Result<std::vec::Vec<Proc<'a>>, MonoTextError>
+where
+    I: LayoutInterner<'a>,
+{
+    let procs = parse_procs_with_lines(arena, interner, interns, home, text)?;
+
+    Ok(procs.into_iter().map(|(_, proc)| proc).collect())
+}
+
+/// Parses procs like [parse_mono_text], keyed by name and layout the way backends take them.
+/// Two procs with the same name and layout are an error.
+pub fn parse_mono_procedures<'a, I>(
+    arena: &'a Bump,
+    interner: &mut I,
+    interns: &mut Interns,
+    home: ModuleId,
+    text: &str,
+) -> Result<MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>, MonoTextError>
+where
+    I: LayoutInterner<'a>,
+{
+    let procs = parse_procs_with_lines(arena, interner, interns, home, text)?;
+    let mut procedures = MutMap::default();
+
+    for (line, proc) in procs {
+        let proc_layout = ProcLayout {
+            arguments: Vec::from_iter_in(proc.args.iter().map(|(layout, _)| *layout), arena)
+                .into_bump_slice(),
+            result: proc.ret_layout,
+            niche: proc.name.niche(),
+        };
+        let name = proc.name.name();
+
+        if procedures.insert((name, proc_layout), proc).is_some() {
+            return Err(MonoTextError {
+                line,
+                message: format!(
+                    "`{}` is already defined with the same layout",
+                    qualified_name(interns, name)
+                ),
+            });
+        }
+    }
+
+    Ok(procedures)
+}
+
+/// Parses procs, along with the line each one starts on
+fn parse_procs_with_lines<'a, I>(
+    arena: &'a Bump,
+    interner: &mut I,
+    interns: &mut Interns,
+    home: ModuleId,
+    text: &str,
+) -> Result<std::vec::Vec<(u32, Proc<'a>)>, MonoTextError>
+where
+    I: LayoutInterner<'a>,
+{
+    // call_specialization_counter=0 is reserved
+    let mut call_specialization_counter = 1;
+    let mut update_mode_ids = UpdateModeIds::new();
+
+    let mut parser = Parser {
+        arena,
+        interner,
+        symbols: Symbols::Interns { interns, home },
+        tokens: tokenize(text)?,
+        pos: 0,
+        call_specialization_counter: &mut call_specialization_counter,
+        update_mode_ids: &mut update_mode_ids,
+        recursive_depth: 0,
+        naked_recursion_pointer: false,
+    };
+
+    let mut procs = std::vec::Vec::new();
+
+    while !parser.at_end() {
+        let line = parser.line();
+        procs.push((line, parser.proc()?));
+    }
+
+    Ok(procs)
+}
+
+/// Parses a single proc written by [proc_to_anonymous_mono_text]. The proc gets the name
+/// `own`, and all other symbols are fresh symbols in the home module of `env`.
+pub(crate) fn parse_anonymous_mono_text<'a, I>(
+    env: &mut Env<'a, '_>,
+    interner: &mut I,
+    own: Symbol,
+    text: &str,
+) -> Result<Proc<'a>, MonoTextError>
+where
+    I: LayoutInterner<'a>,
+{
+    let mut parser = Parser {
+        arena: env.arena,
+        interner,
+        symbols: Symbols::Fresh {
+            own,
+            home: env.home,
+            ident_ids: &mut *env.ident_ids,
+            seen: MutMap::default(),
+        },
+        tokens: tokenize(text)?,
+        pos: 0,
+        call_specialization_counter: &mut env.call_specialization_counter,
+        update_mode_ids: &mut *env.update_mode_ids,
+        recursive_depth: 0,
+        naked_recursion_pointer: false,
+    };
+
+    let proc = parser.proc()?;
+
+    if !parser.at_end() {
+        return parser.error("expected a single proc".into());
+    }
+
+    Ok(proc)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Keywords, layout names and numbers
+    Word(String),
+    /// A symbol written as `Module.ident`
+    Symbol(String),
+    Str(String),
+    Punct(char),
+    Arrow,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{word}`"),
+            Token::Symbol(name) => write!(f, "symbol `{name}`"),
+            Token::Str(string) => write!(f, "string {string:?}"),
+            Token::Punct(c) => write!(f, "`{c}`"),
+            Token::Arrow => write!(f, "`->`"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<std::vec::Vec<(Token, u32)>, MonoTextError> {
+    let mut tokens = std::vec::Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+
+    let error = |line, message: String| Err(MonoTextError { line, message });
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' => {
+                // Comments run until the end of the line
+                while chars.next_if(|c| *c != '\n').is_some() {}
+            }
+            '`' => {
+                let mut name = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('`') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('\\' | '`')) => name.push(c),
+                            other => {
+                                return error(line, format!("invalid escape in symbol: {other:?}"))
+                            }
+                        },
+                        Some('\n') | None => {
+                            return error(line, "this symbol is missing its closing `".into())
+                        }
+                        Some(c) => name.push(c),
+                    }
+                }
+
+                tokens.push((Token::Symbol(name), line));
+            }
+            '"' => {
+                let mut string = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => string.push('\n'),
+                            Some('r') => string.push('\r'),
+                            Some('t') => string.push('\t'),
+                            Some('0') => string.push('\0'),
+                            Some(c @ ('\\' | '"' | '\'')) => string.push(c),
+                            Some('u') => {
+                                let mut hex = String::new();
+
+                                if chars.next() != Some('{') {
+                                    return error(line, "expected `{` after `\\u`".into());
+                                }
+
+                                while let Some(c) = chars.next_if(|c| *c != '}') {
+                                    hex.push(c);
+                                }
+                                chars.next();
+
+                                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                                    Some(c) => string.push(c),
+                                    None => {
+                                        return error(line, format!("invalid escape \\u{{{hex}}}"))
+                                    }
+                                }
+                            }
+                            other => {
+                                return error(line, format!("invalid escape in string: {other:?}"))
+                            }
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+
+                            string.push(c)
+                        }
+                        None => return error(line, "this string is missing its closing \"".into()),
+                    }
+                }
+
+                tokens.push((Token::Str(string), line));
+            }
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push((Token::Arrow, line));
+            }
+            '(' | ')' | '{' | '}' | '[' | ']' | ',' | ':' | ';' | '=' | '*' | '!' => {
+                tokens.push((Token::Punct(c), line));
+            }
+            c if is_word_char(c) => {
+                let mut word = String::from(c);
+
+                while let Some(c) = chars.next_if(|c| is_word_char(*c)) {
+                    word.push(c);
+                }
+
+                tokens.push((Token::Word(word), line));
+            }
+            c => return error(line, format!("unexpected character {c:?}")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    // Numbers like -1.5e-3 and NaN are words too
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '+')
+}
+
+enum Symbols<'r> {
+    /// `Module.ident` names are looked up in the interns
+    Interns {
+        interns: &'r mut Interns,
+        home: ModuleId,
+    },
+    /// `self` is the proc being parsed, and every other name gets a fresh symbol
+    Fresh {
+        own: Symbol,
+        home: ModuleId,
+        ident_ids: &'r mut IdentIds,
+        seen: MutMap<String, Symbol>,
+    },
+}
+
+struct Parser<'a, 'r, I> {
+    arena: &'a Bump,
+    interner: &'r mut I,
+    symbols: Symbols<'r>,
+    tokens: std::vec::Vec<(Token, u32)>,
+    pos: usize,
+    call_specialization_counter: &'r mut u32,
+    update_mode_ids: &'r mut UpdateModeIds,
+    /// How many recursive unions and lambda sets we are inside of, which is where `*` is allowed
+    recursive_depth: u32,
+    /// Whether a `*` was parsed since entering the innermost recursive union or lambda set
+    naked_recursion_pointer: bool,
+}
+
+type ParseResult<T> = Result<T, MonoTextError>;
+
+impl<'a, 'r, I: LayoutInterner<'a>> Parser<'a, 'r, I> {
+    fn proc(&mut self) -> ParseResult<Proc<'a>> {
+        self.keyword("proc")?;
+        let name = self.symbol()?;
+
+        let mut args = Vec::new_in(self.arena);
+        self.punct('(')?;
+        while !self.eat_punct(')') {
+            if !args.is_empty() {
+                self.punct(',')?;
+            }
+
+            let symbol = self.symbol()?;
+            self.punct(':')?;
+            let layout = self.layout()?;
+            args.push((layout, symbol));
+        }
+
+        self.arrow()?;
+        let ret_layout = self.layout()?;
+
+        let mut niche: &[InLayout] = &[];
+        let mut closure_data_layout = None;
+        let mut is_self_recursive = SelfRecursive::NotSelfRecursive;
+        let mut is_erased = false;
+
+        loop {
+            if self.eat_keyword("niche") {
+                niche = self.niche_captures()?;
+            } else if self.eat_keyword("closure") {
+                self.punct('(')?;
+                closure_data_layout = Some(self.layout()?);
+                self.punct(')')?;
+            } else if self.eat_keyword("selfrec") {
+                self.punct('(')?;
+                is_self_recursive = SelfRecursive::SelfRecursive(JoinPointId(self.symbol()?));
+                self.punct(')')?;
+            } else if self.eat_keyword("erased") {
+                is_erased = true;
+            } else {
+                break;
+            }
+        }
+
+        self.punct('{')?;
+        let body = self.block()?;
+
+        Ok(Proc {
+            name: LambdaName::from_captures(name, niche),
+            args: args.into_bump_slice(),
+            body,
+            closure_data_layout,
+            ret_layout,
+            is_self_recursive,
+            is_erased,
+        })
+    }
+
+    /// Parses statements up to and including the closing `}`. Lets, refcounting and join
+    /// points continue with the statements after them, and every block ends with a `ret`,
+    /// `jump`, `crash` or `switch`.
+    fn block(&mut self) -> ParseResult<Stmt<'a>> {
+        let keyword = self.word()?;
+
+        let stmt = match keyword.as_str() {
+            "let" => {
+                let symbol = self.symbol()?;
+                self.punct(':')?;
+                let layout = self.layout()?;
+                self.punct('=')?;
+                let expr = self.expr()?;
+                self.punct(';')?;
+                let remainder = self.block()?;
+
+                return Ok(Stmt::Let(symbol, expr, layout, self.arena.alloc(remainder)));
+            }
+            "inc" | "dec" | "decref" | "free" => {
+                let symbol = self.symbol()?;
+                let modify = match keyword.as_str() {
+                    "inc" => ModifyRc::Inc(symbol, self.number()?),
+                    "dec" => ModifyRc::Dec(symbol),
+                    "decref" => ModifyRc::DecRef(symbol),
+                    _ => ModifyRc::Free(symbol),
+                };
+                self.punct(';')?;
+                let remainder = self.block()?;
+
+                return Ok(Stmt::Refcounting(modify, self.arena.alloc(remainder)));
+            }
+            "join" => {
+                let id = JoinPointId(self.symbol()?);
+
+                let mut parameters = Vec::new_in(self.arena);
+                self.punct('(')?;
+                while !self.eat_punct(')') {
+                    if !parameters.is_empty() {
+                        self.punct(',')?;
+                    }
+
+                    let symbol = self.symbol()?;
+                    self.punct(':')?;
+                    let layout = self.layout()?;
+                    parameters.push(Param { symbol, layout });
+                }
+
+                self.punct('{')?;
+                let body = self.block()?;
+                let remainder = self.block()?;
+
+                return Ok(Stmt::Join {
+                    id,
+                    parameters: parameters.into_bump_slice(),
+                    body: self.arena.alloc(body),
+                    remainder: self.arena.alloc(remainder),
+                });
+            }
+            "switch" => {
+                let cond_symbol = self.symbol()?;
+                self.punct(':')?;
+                let cond_layout = self.layout()?;
+                self.arrow()?;
+                let ret_layout = self.layout()?;
+                self.punct('{')?;
+
+                let mut branches = Vec::new_in(self.arena);
+                while self.eat_keyword("case") {
+                    let value = self.number()?;
+                    let info = self.branch_info()?;
+                    self.punct('{')?;
+                    let branch = self.block()?;
+                    branches.push((value, info, branch));
+                }
+
+                self.keyword("default")?;
+                let default_info = self.branch_info()?;
+                self.punct('{')?;
+                let default_branch = self.block()?;
+                self.punct('}')?;
+
+                Stmt::Switch {
+                    cond_symbol,
+                    cond_layout,
+                    branches: branches.into_bump_slice(),
+                    default_branch: (default_info, self.arena.alloc(default_branch)),
+                    ret_layout,
+                }
+            }
+            "ret" => {
+                let symbol = self.symbol()?;
+                self.punct(';')?;
+
+                Stmt::Ret(symbol)
+            }
+            "jump" => {
+                let id = JoinPointId(self.symbol()?);
+                let arguments = self.arguments()?;
+                self.punct(';')?;
+
+                Stmt::Jump(id, arguments)
+            }
+            "crash" => {
+                let symbol = self.symbol()?;
+                let tag = match self.word()?.as_str() {
+                    "roc" => CrashTag::Roc,
+                    "user" => CrashTag::User,
+                    other => return self.error(format!("unknown crash tag `{other}`")),
+                };
+                self.punct(';')?;
+
+                Stmt::Crash(symbol, tag)
+            }
+            other => return self.error(format!("expected a statement, found `{other}`")),
+        };
+
+        self.punct('}')?;
+
+        Ok(stmt)
+    }
+
+    fn branch_info(&mut self) -> ParseResult<BranchInfo<'a>> {
+        let info = if self.eat_keyword("ctor") {
+            self.punct('(')?;
+            let scrutinee = self.symbol()?;
+            self.punct(':')?;
+            let layout = self.layout()?;
+            self.punct(',')?;
+            let tag_id = self.number()?;
+            self.punct(')')?;
+
+            BranchInfo::Constructor {
+                scrutinee,
+                layout,
+                tag_id,
+            }
+        } else if self.eat_keyword("list") {
+            self.punct('(')?;
+            let scrutinee = self.symbol()?;
+            self.punct(',')?;
+            let len = self.number()?;
+            self.punct(')')?;
+
+            BranchInfo::List { scrutinee, len }
+        } else if self.eat_keyword("unique") {
+            self.punct('(')?;
+            let scrutinee = self.symbol()?;
+            self.punct(',')?;
+            let unique = self.bool()?;
+            self.punct(')')?;
+
+            BranchInfo::Unique { scrutinee, unique }
+        } else {
+            BranchInfo::None
+        };
+
+        Ok(info)
+    }
+
+    fn expr(&mut self) -> ParseResult<Expr<'a>> {
+        let keyword = self.word()?;
+
+        let expr = match keyword.as_str() {
+            "int" | "u128" | "float" | "dec" | "str" | "bool" | "byte" => {
+                Expr::Literal(self.literal(&keyword)?)
+            }
+            "call" => {
+                let name = self.lambda_name()?;
+                let arguments = self.arguments()?;
+                let (arg_layouts, ret_layout) = self.function_layout()?;
+                let specialization_id = CallSpecId {
+                    id: *self.call_specialization_counter,
+                };
+                *self.call_specialization_counter += 1;
+
+                Expr::Call(Call {
+                    call_type: CallType::ByName {
+                        name,
+                        ret_layout,
+                        arg_layouts,
+                        specialization_id,
+                    },
+                    arguments,
+                })
+            }
+            "call_ptr" => {
+                let pointer = self.symbol()?;
+                let arguments = self.arguments()?;
+                let (arg_layouts, ret_layout) = self.function_layout()?;
+
+                Expr::Call(Call {
+                    call_type: CallType::ByPointer {
+                        pointer,
+                        ret_layout,
+                        arg_layouts,
+                    },
+                    arguments,
+                })
+            }
+            "foreign" => {
+                let foreign_symbol = ForeignSymbol::from(self.string()?);
+                let arguments = self.arguments()?;
+                self.arrow()?;
+                let ret_layout = self.layout()?;
+
+                Expr::Call(Call {
+                    call_type: CallType::Foreign {
+                        foreign_symbol,
+                        ret_layout,
+                    },
+                    arguments,
+                })
+            }
+            "lowlevel" => {
+                let name = self.word()?;
+                let op = match LowLevel::from_name(&name) {
+                    Some(op) if !op.is_higher_order() => op,
+                    Some(_) => {
+                        return self.error(format!(
+                            "higher-order lowlevel `{name}` must be written with `higher_order`"
+                        ))
+                    }
+                    None => return self.error(format!("unknown lowlevel `{name}`")),
+                };
+                let arguments = self.arguments()?;
+
+                Expr::Call(Call {
+                    call_type: CallType::LowLevel {
+                        op,
+                        update_mode: self.update_mode_ids.next_id(),
+                    },
+                    arguments,
+                })
+            }
+            "higher_order" => {
+                let name = self.word()?;
+                let lists = self.arguments()?;
+                let op = match higher_order_from_parts(&name, lists) {
+                    Some(op) => op,
+                    None => return self.error(format!("unknown higher-order lowlevel `{name}`")),
+                };
+
+                self.keyword("pass")?;
+                let lambda_name = self.lambda_name()?;
+                let (argument_layouts, return_layout) = self.function_layout()?;
+
+                self.keyword("env")?;
+                let captured_environment = self.symbol()?;
+                let owns_captured_environment = match self.word()?.as_str() {
+                    "owned" => true,
+                    "borrowed" => false,
+                    other => {
+                        return self
+                            .error(format!("expected `owned` or `borrowed`, found `{other}`"))
+                    }
+                };
+
+                let closure_env_layout = if self.eat_keyword("closure") {
+                    self.punct('(')?;
+                    let layout = self.layout()?;
+                    self.punct(')')?;
+
+                    Some(layout)
+                } else {
+                    None
+                };
+
+                let arguments = self.arguments()?;
+
+                let specialization_id = CallSpecId {
+                    id: *self.call_specialization_counter,
+                };
+                *self.call_specialization_counter += 1;
+
+                let passed_function = PassedFunction {
+                    name: lambda_name,
+                    argument_layouts,
+                    return_layout,
+                    specialization_id,
+                    captured_environment,
+                    owns_captured_environment,
+                };
+
+                Expr::Call(Call {
+                    call_type: CallType::HigherOrder(self.arena.alloc(HigherOrderLowLevel {
+                        op,
+                        closure_env_layout,
+                        update_mode: self.update_mode_ids.next_id(),
+                        passed_function,
+                    })),
+                    arguments,
+                })
+            }
+            "tag" => {
+                let reuse = if self.eat_keyword("reuse") {
+                    self.punct('(')?;
+                    let symbol = self.symbol()?;
+                    self.punct(',')?;
+                    let update_tag_id = self.bool()?;
+                    self.punct(')')?;
+
+                    Some(ReuseToken {
+                        symbol,
+                        update_tag_id,
+                        update_mode: self.update_mode_ids.next_id(),
+                    })
+                } else {
+                    None
+                };
+
+                let tag_layout = self.union_layout()?;
+                let tag_id = self.number()?;
+                let arguments = self.arguments()?;
+
+                Expr::Tag {
+                    tag_layout,
+                    tag_id,
+                    arguments,
+                    reuse,
+                }
+            }
+            "struct" => Expr::Struct(self.arguments()?),
+            "null" => Expr::NullPointer,
+            "struct_at_index" => {
+                let structure = self.symbol()?;
+                let index = self.number()?;
+                let field_layouts = self.layouts('{', '}')?;
+
+                Expr::StructAtIndex {
+                    index,
+                    field_layouts,
+                    structure,
+                }
+            }
+            "get_tag_id" => {
+                let structure = self.symbol()?;
+                let union_layout = self.union_layout()?;
+
+                Expr::GetTagId {
+                    structure,
+                    union_layout,
+                }
+            }
+            "union_at_index" => {
+                let structure = self.symbol()?;
+                let tag_id = self.number()?;
+                let index = self.number()?;
+                let union_layout = self.union_layout()?;
+
+                Expr::UnionAtIndex {
+                    structure,
+                    tag_id,
+                    union_layout,
+                    index,
+                }
+            }
+            "get_element_pointer" => {
+                let structure = self.symbol()?;
+
+                let mut indices = Vec::new_in(self.arena);
+                self.punct('[')?;
+                while !self.eat_punct(']') {
+                    if !indices.is_empty() {
+                        self.punct(',')?;
+                    }
+
+                    indices.push(self.number()?);
+                }
+
+                let union_layout = self.union_layout()?;
+
+                Expr::GetElementPointer {
+                    structure,
+                    union_layout,
+                    indices: indices.into_bump_slice(),
+                }
+            }
+            "array" => {
+                let elem_layout = self.layout()?;
+
+                let mut elems = Vec::new_in(self.arena);
+                self.punct('[')?;
+                while !self.eat_punct(']') {
+                    if !elems.is_empty() {
+                        self.punct(',')?;
+                    }
+
+                    let elem = match self.peek() {
+                        Some(Token::Symbol(_)) => ListLiteralElement::Symbol(self.symbol()?),
+                        _ => {
+                            let keyword = self.word()?;
+                            ListLiteralElement::Literal(self.literal(&keyword)?)
+                        }
+                    };
+                    elems.push(elem);
+                }
+
+                Expr::Array {
+                    elem_layout,
+                    elems: elems.into_bump_slice(),
+                }
+            }
+            "empty_array" => Expr::EmptyArray,
+            "erased_make" => {
+                let callee = self.symbol()?;
+                let value = match self.peek() {
+                    Some(Token::Symbol(_)) => Some(self.symbol()?),
+                    _ => None,
+                };
+
+                Expr::ErasedMake { value, callee }
+            }
+            "erased_load" => {
+                let symbol = self.symbol()?;
+                let field = match self.word()?.as_str() {
+                    "value" => ErasedField::Value,
+                    "value_ptr" => ErasedField::ValuePtr,
+                    "callee" => ErasedField::Callee,
+                    other => return self.error(format!("unknown erased field `{other}`")),
+                };
+
+                Expr::ErasedLoad { symbol, field }
+            }
+            "function_pointer" => Expr::FunctionPointer {
+                lambda_name: self.lambda_name()?,
+            },
+            "alloca" => {
+                let element_layout = self.layout()?;
+                let initializer = match self.peek() {
+                    Some(Token::Symbol(_)) => Some(self.symbol()?),
+                    _ => None,
+                };
+
+                Expr::Alloca {
+                    element_layout,
+                    initializer,
+                }
+            }
+            "reset" => Expr::Reset {
+                symbol: self.symbol()?,
+                update_mode: self.update_mode_ids.next_id(),
+            },
+            "reset_ref" => Expr::ResetRef {
+                symbol: self.symbol()?,
+                update_mode: self.update_mode_ids.next_id(),
+            },
+            "runtime_error" => {
+                let message = self.string()?;
+                Expr::RuntimeErrorFunction(self.arena.alloc_str(&message))
+            }
+            other => return self.error(format!("expected an expression, found `{other}`")),
+        };
+
+        Ok(expr)
+    }
+
+    fn literal(&mut self, keyword: &str) -> ParseResult<Literal<'a>> {
+        let literal = match keyword {
+            "int" => Literal::Int(self.number::<i128>()?.to_ne_bytes()),
+            "u128" => Literal::U128(self.number::<u128>()?.to_ne_bytes()),
+            "float" => Literal::Float(self.number()?),
+            "dec" => Literal::Decimal(self.number::<i128>()?.to_ne_bytes()),
+            "str" => {
+                let string = self.string()?;
+                Literal::Str(self.arena.alloc_str(&string))
+            }
+            "bool" => Literal::Bool(self.bool()?),
+            "byte" => Literal::Byte(self.number()?),
+            other => return self.error(format!("expected a literal, found `{other}`")),
+        };
+
+        Ok(literal)
+    }
+
+    fn arguments(&mut self) -> ParseResult<&'a [Symbol]> {
+        let mut arguments = Vec::new_in(self.arena);
+
+        self.punct('(')?;
+        while !self.eat_punct(')') {
+            if !arguments.is_empty() {
+                self.punct(',')?;
+            }
+
+            arguments.push(self.symbol()?);
+        }
+
+        Ok(arguments.into_bump_slice())
+    }
+
+    fn function_layout(&mut self) -> ParseResult<(&'a [InLayout<'a>], InLayout<'a>)> {
+        let arg_layouts = self.layouts('(', ')')?;
+        self.arrow()?;
+        let ret_layout = self.layout()?;
+
+        Ok((arg_layouts, ret_layout))
+    }
+
+    fn layouts(&mut self, open: char, close: char) -> ParseResult<&'a [InLayout<'a>]> {
+        let mut layouts = Vec::new_in(self.arena);
+
+        self.punct(open)?;
+        while !self.eat_punct(close) {
+            if !layouts.is_empty() {
+                self.punct(',')?;
+            }
+
+            layouts.push(self.layout()?);
+        }
+
+        Ok(layouts.into_bump_slice())
+    }
+
+    fn tags(&mut self) -> ParseResult<&'a [&'a [InLayout<'a>]]> {
+        let mut tags = Vec::new_in(self.arena);
+
+        self.punct('[')?;
+        while !self.eat_punct(']') {
+            if !tags.is_empty() {
+                self.punct(',')?;
+            }
+
+            tags.push(self.layouts('[', ']')?);
+        }
+
+        Ok(tags.into_bump_slice())
+    }
+
+    fn layout(&mut self) -> ParseResult<InLayout<'a>> {
+        if self.eat_punct('*') {
+            if self.recursive_depth == 0 {
+                return self
+                    .error("`*` can only be used inside a recursive union or lambda set".into());
+            }
+
+            self.naked_recursion_pointer = true;
+
+            // The interner points this at the enclosing union in `insert_recursive`, or the
+            // enclosing lambda set in `insert_lambda_set`
+            let naked = LayoutRepr::RecursivePointer(Layout::VOID);
+            return Ok(self.interner.insert_direct_no_semantic(naked));
+        }
+
+        if let Some(Token::Punct('{')) = self.peek() {
+            let fields = self.layouts('{', '}')?;
+            let repr = LayoutRepr::struct_(fields);
+            return Ok(self.interner.insert_direct_no_semantic(repr));
+        }
+
+        if let Some(Token::Punct('!')) = self.peek() {
+            return self.unsupported();
+        }
+
+        let name = self.word()?;
+
+        let repr = match name.as_str() {
+            "U8" => LayoutRepr::Builtin(Builtin::Int(IntWidth::U8)),
+            "U16" => LayoutRepr::Builtin(Builtin::Int(IntWidth::U16)),
+            "U32" => LayoutRepr::Builtin(Builtin::Int(IntWidth::U32)),
+            "U64" => LayoutRepr::Builtin(Builtin::Int(IntWidth::U64)),
+            "U128" => LayoutRepr::Builtin(Builtin::Int(IntWidth::U128)),
+            "I8" => LayoutRepr::Builtin(Builtin::Int(IntWidth::I8)),
+            "I16" => LayoutRepr::Builtin(Builtin::Int(IntWidth::I16)),
+            "I32" => LayoutRepr::Builtin(Builtin::Int(IntWidth::I32)),
+            "I64" => LayoutRepr::Builtin(Builtin::Int(IntWidth::I64)),
+            "I128" => LayoutRepr::Builtin(Builtin::Int(IntWidth::I128)),
+            "F32" => LayoutRepr::Builtin(Builtin::Float(FloatWidth::F32)),
+            "F64" => LayoutRepr::Builtin(Builtin::Float(FloatWidth::F64)),
+            "Dec" => LayoutRepr::Builtin(Builtin::Decimal),
+            "Bool" => LayoutRepr::Builtin(Builtin::Bool),
+            "Str" => LayoutRepr::Builtin(Builtin::Str),
+            "Erased" => LayoutRepr::ERASED,
+            "List" => {
+                self.punct('(')?;
+                let element = self.layout()?;
+                self.punct(')')?;
+
+                LayoutRepr::Builtin(Builtin::List(element))
+            }
+            "Ptr" => {
+                self.punct('(')?;
+                let inner = self.layout()?;
+                self.punct(')')?;
+
+                LayoutRepr::Ptr(inner)
+            }
+            "FnPtr" => {
+                self.punct('(')?;
+                let (args, ret) = self.function_layout()?;
+                self.punct(')')?;
+
+                LayoutRepr::FunctionPointer(FunctionPointer { args, ret })
+            }
+            "Lambda" => return self.lambda_set_layout(),
+            "Union" => LayoutRepr::Union(UnionLayout::NonRecursive(self.tags()?)),
+            "Rec" | "RecUnwrapped" | "RecNullable" | "RecNullableUnwrapped" => {
+                return self.recursive_union_layout(&name);
+            }
+            other => return self.error(format!("unknown layout `{other}`")),
+        };
+
+        Ok(self.interner.insert_direct_no_semantic(repr))
+    }
+
+    fn lambda_set_layout(&mut self) -> ParseResult<InLayout<'a>> {
+        self.punct('(')?;
+        let (args, ret) = self.function_layout()?;
+        self.punct(',')?;
+
+        // A `*` in the captures points back at this lambda set
+        let outer_naked_recursion_pointer = self.naked_recursion_pointer;
+        self.naked_recursion_pointer = false;
+        self.recursive_depth += 1;
+
+        let mut set = Vec::new_in(self.arena);
+        self.punct('[')?;
+        while !self.eat_punct(']') {
+            if !set.is_empty() {
+                self.punct(',')?;
+            }
+
+            let symbol = self.symbol()?;
+            let captures = self.layouts('[', ']')?;
+            set.push((symbol, captures));
+        }
+
+        self.recursive_depth -= 1;
+        let needs_recursive_fixup = NeedsRecursionPointerFixup(self.naked_recursion_pointer);
+        self.naked_recursion_pointer = outer_naked_recursion_pointer;
+
+        self.punct(',')?;
+        let representation = self.layout()?;
+        self.punct(')')?;
+
+        let lambda_set = self.interner.insert_lambda_set(
+            self.arena,
+            self.arena.alloc(args),
+            ret,
+            self.arena.alloc(set.into_bump_slice()),
+            needs_recursive_fixup,
+            representation,
+        );
+
+        Ok(lambda_set.full_layout)
+    }
+
+    fn recursive_union_layout(&mut self, name: &str) -> ParseResult<InLayout<'a>> {
+        let outer_naked_recursion_pointer = self.naked_recursion_pointer;
+        self.recursive_depth += 1;
+
+        let union_layout = match name {
+            "Rec" => UnionLayout::Recursive(self.tags()?),
+            "RecUnwrapped" => UnionLayout::NonNullableUnwrapped(self.layouts('[', ']')?),
+            "RecNullable" => {
+                self.punct('(')?;
+                let nullable_id = self.number()?;
+                self.punct(')')?;
+
+                UnionLayout::NullableWrapped {
+                    nullable_id,
+                    other_tags: self.tags()?,
+                }
+            }
+            _ => {
+                self.punct('(')?;
+                let nullable_id = self.number::<u8>()? != 0;
+                self.punct(')')?;
+
+                UnionLayout::NullableUnwrapped {
+                    nullable_id,
+                    other_fields: self.layouts('[', ']')?,
+                }
+            }
+        };
+
+        self.recursive_depth -= 1;
+        self.naked_recursion_pointer = outer_naked_recursion_pointer;
+
+        let normalized = Layout::no_semantic(LayoutRepr::Union(union_layout).direct());
+
+        Ok(self.interner.insert_recursive(self.arena, normalized))
+    }
+
+    fn union_layout(&mut self) -> ParseResult<UnionLayout<'a>> {
+        let layout = self.layout()?;
+
+        match self.interner.get_repr(layout) {
+            LayoutRepr::Union(union_layout) => Ok(union_layout),
+            _ => self.error("expected a union layout".into()),
+        }
+    }
+
+    fn lambda_name(&mut self) -> ParseResult<LambdaName<'a>> {
+        let symbol = self.symbol()?;
+
+        let captures = if self.eat_keyword("niche") {
+            self.niche_captures()?
+        } else {
+            &[]
+        };
+
+        Ok(LambdaName::from_captures(symbol, captures))
+    }
+
+    /// The captures of a niche are those of a lambda in some lambda set, so a `*` in them points
+    /// back at that lambda set even when the niche is written outside of it.
+    fn niche_captures(&mut self) -> ParseResult<&'a [InLayout<'a>]> {
+        let outer_naked_recursion_pointer = self.naked_recursion_pointer;
+        self.recursive_depth += 1;
+
+        let captures = self.layouts('[', ']');
+
+        self.recursive_depth -= 1;
+        self.naked_recursion_pointer = outer_naked_recursion_pointer;
+
+        captures
+    }
+
+    fn symbol(&mut self) -> ParseResult<Symbol> {
+        match self.next() {
+            Some(Token::Symbol(name)) => Ok(self.intern_symbol(&name)),
+            Some(Token::Punct('!')) => {
+                self.pos -= 1;
+                self.unsupported()
+            }
+            other => self.expected("a symbol", other),
+        }
+    }
+
+    fn intern_symbol(&mut self, name: &str) -> Symbol {
+        match &mut self.symbols {
+            Symbols::Interns { interns, home } => {
+                // Generated idents can contain dots too, so use the first prefix that names a
+                // module
+                let known_module = name.match_indices('.').find_map(|(index, _)| {
+                    let module_name = ModuleName::from(&name[..index]);
+                    let module_id = interns.module_ids.get_id(&module_name)?;
+
+                    Some((module_id, &name[index + 1..]))
+                });
+
+                let (module_id, ident) = known_module.unwrap_or((*home, name));
+
+                let ident_ids = interns.all_ident_ids.get_or_insert(module_id);
+
+                Symbol::new(module_id, ident_ids.get_or_insert(ident))
+            }
+            Symbols::Fresh { own, .. } if name == "self" => *own,
+            Symbols::Fresh {
+                home,
+                ident_ids,
+                seen,
+                ..
+            } => *seen
+                .entry(name.to_string())
+                .or_insert_with(|| Symbol::new(*home, ident_ids.gen_unique())),
+        }
+    }
+
+    fn number<T: std::str::FromStr>(&mut self) -> ParseResult<T> {
+        let word = self.word()?;
+
+        match word.parse() {
+            Ok(number) => Ok(number),
+            Err(_) => self.error(format!("expected a number, found `{word}`")),
+        }
+    }
+
+    fn bool(&mut self) -> ParseResult<bool> {
+        match self.word()?.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            other => self.error(format!("expected `true` or `false`, found `{other}`")),
+        }
+    }
+
+    fn string(&mut self) -> ParseResult<String> {
+        match self.next() {
+            Some(Token::Str(string)) => Ok(string),
+            other => self.expected("a string", other),
+        }
+    }
+
+    fn word(&mut self) -> ParseResult<String> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word),
+            Some(Token::Punct('!')) => {
+                self.pos -= 1;
+                self.unsupported()
+            }
+            other => self.expected("a word", other),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        match self.next() {
+            Some(Token::Word(word)) if word == keyword => Ok(()),
+            other => self.expected(&format!("`{keyword}`"), other),
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn punct(&mut self, c: char) -> ParseResult<()> {
+        match self.next() {
+            Some(Token::Punct(p)) if p == c => Ok(()),
+            Some(Token::Punct('!')) if c != '!' => {
+                self.pos -= 1;
+                self.unsupported()
+            }
+            other => self.expected(&format!("`{c}`"), other),
+        }
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        match self.peek() {
+            Some(Token::Punct(p)) if *p == c => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn arrow(&mut self) -> ParseResult<()> {
+        match self.next() {
+            Some(Token::Arrow) => Ok(()),
+            other => self.expected("`->`", other),
+        }
+    }
+
+    /// Reports an `!unsupported(...)` written by the printer
+    fn unsupported<T>(&mut self) -> ParseResult<T> {
+        self.pos += 1;
+
+        let what = match (self.next(), self.next()) {
+            (Some(Token::Punct('(')), Some(Token::Word(what))) => what,
+            _ => "this construct".into(),
+        };
+
+        self.pos -= 1;
+        self.error(format!("{what} is not supported by the mono text format"))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(token, _)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn expected<T>(&mut self, expected: &str, found: Option<Token>) -> ParseResult<T> {
+        self.pos -= 1;
+
+        match found {
+            Some(token) => self.error(format!("expected {expected}, found {token}")),
+            None => self.error(format!("expected {expected}, found the end of the file")),
+        }
+    }
+
+    fn line(&self) -> u32 {
+        match self.tokens.get(self.pos).or_else(|| self.tokens.last()) {
+            Some((_, line)) => *line,
+            None => 1,
+        }
+    }
+
+    fn error<T>(&self, message: String) -> ParseResult<T> {
+        Err(MonoTextError {
+            line: self.line(),
+            message,
+        })
+    }
+}
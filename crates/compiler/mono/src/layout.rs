@@ -1,5 +1,5 @@
 use crate::ir::Parens;
-use crate::layout::intern::NeedsRecursionPointerFixup;
+pub(crate) use crate::layout::intern::NeedsRecursionPointerFixup;
 use bitvec::vec::BitVec;
 use bumpalo::collections::Vec;
 use bumpalo::Bump;
@@ -1351,6 +1351,11 @@ impl<'a> Niche<'a> {
         let NichePriv::Captures(caps) = &self.0;
         interner.dbg_stable_iter(caps)
     }
+
+    pub(crate) fn captures(&self) -> &'a [InLayout<'a>] {
+        let NichePriv::Captures(caps) = self.0;
+        caps
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -961,6 +961,16 @@ impl<'a> STLayoutInterner<'a> {
     pub fn is_empty(&self) -> bool {
         self.vec.is_empty()
     }
+
+    /// The number of layouts interned so far, including the reserved ones.
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Every layout interned so far, in the order they were interned.
+    pub fn iter(&self) -> impl Iterator<Item = InLayout<'a>> + '_ {
+        (0..self.vec.len()).map(|index| unsafe { InLayout::from_index(index) })
+    }
 }
 
 macro_rules! st_impl {
@@ -0,0 +1,101 @@
+//! A summary of the layouts interned during mono, for `roc build --verbose-layouts`.
+//!
+//! Every value of a large layout is copied around on the stack, and every layout needs its own
+//! specializations of the procs that use it, so a handful of big unions or records can account
+//! for much of an app's code size. This shows platform authors which ones those are.
+use std::fmt::Write;
+
+use crate::layout::{InLayout, LayoutInterner, STLayoutInterner};
+
+/// How many of the largest layouts to list.
+const LARGEST_COUNT: usize = 10;
+
+/// The upper bounds (in bytes, inclusive) of the stack-size buckets.
+const BUCKETS: &[u32] = &[0, 8, 16, 32, 64, 128, 256];
+
+/// Descriptions of layouts longer than this are cut off.
+const MAX_DESCRIPTION_WIDTH: usize = 100;
+
+#[derive(Debug)]
+pub struct LayoutStats<'a> {
+    pub interned: usize,
+    /// The largest layouts with their stack size and alignment, largest first
+    pub largest: Vec<(InLayout<'a>, u32, u32)>,
+    /// How many layouts fall into each of [BUCKETS], plus one more for everything larger
+    pub stack_sizes: Vec<usize>,
+}
+
+impl<'a> LayoutStats<'a> {
+    pub fn new(interner: &STLayoutInterner<'a>) -> Self {
+        let mut stack_sizes = vec![0; BUCKETS.len() + 1];
+        let mut all = Vec::with_capacity(interner.len());
+
+        for layout in interner.iter() {
+            let (size, alignment) = interner.stack_size_and_alignment(layout);
+
+            let bucket = BUCKETS
+                .iter()
+                .position(|max| size <= *max)
+                .unwrap_or(BUCKETS.len());
+            stack_sizes[bucket] += 1;
+
+            all.push((layout, size, alignment));
+        }
+
+        // Stable, so ties keep the order they were interned in
+        all.sort_by(|(_, a, _), (_, b, _)| b.cmp(a));
+        all.truncate(LARGEST_COUNT);
+
+        Self {
+            interned: interner.len(),
+            largest: all,
+            stack_sizes,
+        }
+    }
+
+    pub fn report(&self, interner: &STLayoutInterner<'a>) -> String {
+        let mut buf = String::new();
+
+        writeln!(buf, "Interned layouts: {}", self.interned).unwrap();
+
+        writeln!(buf, "\nLargest layouts (size, alignment):").unwrap();
+        for (layout, size, alignment) in self.largest.iter() {
+            writeln!(
+                buf,
+                "  {size:>6} B  {alignment:>2}  {}",
+                describe(interner, *layout)
+            )
+            .unwrap();
+        }
+
+        writeln!(buf, "\nStack sizes:").unwrap();
+        let mut lower = 0;
+        for (i, count) in self.stack_sizes.iter().enumerate() {
+            let range = match BUCKETS.get(i) {
+                Some(0) => "0 B".to_string(),
+                Some(upper) => format!("{lower}-{upper} B"),
+                None => format!("{lower}+ B"),
+            };
+            writeln!(buf, "  {range:>10}  {count}").unwrap();
+
+            if let Some(upper) = BUCKETS.get(i) {
+                lower = upper + 1;
+            }
+        }
+
+        buf
+    }
+}
+
+/// The layout on one line, cut off if it's very long.
+fn describe<'a>(interner: &STLayoutInterner<'a>, layout: InLayout<'a>) -> String {
+    let pretty = interner.dbg(layout);
+    let line = pretty.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if line.chars().count() > MAX_DESCRIPTION_WIDTH {
+        let cut: String = line.chars().take(MAX_DESCRIPTION_WIDTH).collect();
+        format!("{cut}...")
+    } else {
+        line
+    }
+}
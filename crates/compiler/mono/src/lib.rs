@@ -9,14 +9,24 @@
 // Not a useful lint for us
 #![allow(clippy::too_many_arguments)]
 
+pub mod app_name;
 pub mod borrow;
+pub mod build_info;
 pub mod code_gen_help;
+pub mod coverage;
+pub mod debug_info;
 pub mod drop_specialization;
+pub mod effects;
+pub mod expect_fixtures;
 pub mod inc_dec;
+pub mod inline_hints;
 pub mod ir;
 pub mod layout;
+pub mod layout_stats;
 pub mod low_level;
 pub mod reset_reuse;
+pub mod spec_cache;
+pub mod stack_checks;
 pub mod tail_recursion;
 
 pub mod debug;
@@ -0,0 +1,189 @@
+//! An on-disk cache of specialized builtin procs, shared between builds.
+//!
+//! Specializing a builtin (like `Str.concat`, or `List.len` at `List U8`) gives the same proc
+//! every time for a given compiler, so once a build has made it, later builds can read it from
+//! `~/.cache/roc/specializations` instead of making it again. Entries are keyed by the proc's
+//! symbol, its layout, the target and the compiler.
+//!
+//! Only builtins are cached: an app's own functions can change between builds while keeping
+//! their name and layout. A proc also has to be self-contained, since specializing a proc that
+//! calls other procs is what requests their specializations, and a cache hit would skip that.
+//! That rules out the builtins that take a function (like `List.map`), whose specialization
+//! depends on the closure they're given as much as on their layout. Entries are stored in the
+//! [mono text format](crate::ir::procs_to_mono_text), so procs that use something the format
+//! doesn't support are not cached either.
+//!
+//! Since so few procs qualify, the cache is off unless [CACHE_SPECIALIZATIONS_ENV] is set.
+use std::path::PathBuf;
+
+use roc_target::Target;
+
+use crate::inline_hints::visit_stmts;
+use crate::ir::{
+    parse_anonymous_mono_text, proc_layout_to_mono_text, proc_to_anonymous_mono_text, CallType,
+    Env, Expr, Proc, ProcLayout,
+};
+use crate::layout::{LambdaName, LayoutInterner, Niche};
+
+/// Set this environment variable to read and write the cache.
+pub const CACHE_SPECIALIZATIONS_ENV: &str = "ROC_CACHE_SPECIALIZATIONS";
+
+/// Bump this whenever the mono text format changes.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone)]
+pub struct SpecializationCache {
+    dir: PathBuf,
+    compiler: String,
+}
+
+impl SpecializationCache {
+    /// `compiler` identifies the compiler that makes the specializations; entries written by
+    /// any other compiler are never read.
+    pub fn new(dir: PathBuf, compiler: String) -> Self {
+        Self { dir, compiler }
+    }
+
+    /// Reads a previously cached specialization of `name` at `proc_layout`, if there is one.
+    pub(crate) fn get<'a, I>(
+        &self,
+        env: &mut Env<'a, '_>,
+        interner: &mut I,
+        name: LambdaName<'a>,
+        proc_layout: ProcLayout<'a>,
+    ) -> Option<Proc<'a>>
+    where
+        I: LayoutInterner<'a>,
+    {
+        if !is_cacheable(name) {
+            return None;
+        }
+
+        let path = self.path(env.target, interner, name, &proc_layout)?;
+        let text = std::fs::read_to_string(path).ok()?;
+
+        // A corrupt entry, or one that doesn't have the layout we asked for, is just a miss
+        let proc = parse_anonymous_mono_text(env, interner, name.name(), &text).ok()?;
+
+        let arguments_match = proc.args.len() == proc_layout.arguments.len()
+            && proc
+                .args
+                .iter()
+                .zip(proc_layout.arguments)
+                .all(|((layout, _), expected)| layout == expected);
+
+        if arguments_match && proc.ret_layout == proc_layout.result {
+            Some(proc)
+        } else {
+            None
+        }
+    }
+
+    /// Writes a specialization to the cache, if it can be cached. Failing to write is not a
+    /// problem worth reporting; we'll just specialize the proc again next time.
+    pub(crate) fn insert<'a, I>(
+        &self,
+        target: Target,
+        interner: &I,
+        proc: &Proc<'a>,
+        proc_layout: &ProcLayout<'a>,
+    ) where
+        I: LayoutInterner<'a>,
+    {
+        if !is_cacheable(proc.name) || !is_self_contained(proc, proc_layout) {
+            return;
+        }
+
+        let Some(path) = self.path(target, interner, proc.name, proc_layout) else {
+            return;
+        };
+
+        if path.exists() {
+            return;
+        }
+
+        if let Some(text) = proc_to_anonymous_mono_text(interner, proc) {
+            let _ = write_atomically(&path, &text);
+        }
+    }
+
+    fn path<'a, I>(
+        &self,
+        target: Target,
+        interner: &I,
+        name: LambdaName<'a>,
+        proc_layout: &ProcLayout<'a>,
+    ) -> Option<PathBuf>
+    where
+        I: LayoutInterner<'a>,
+    {
+        let layout = proc_layout_to_mono_text(interner, proc_layout)?;
+
+        let mut hasher = blake3::Hasher::new();
+
+        hasher.update(&FORMAT_VERSION.to_le_bytes());
+        hasher.update(self.compiler.as_bytes());
+        hasher.update(target.to_string().as_bytes());
+        // Builtin symbols are the same in every build
+        hasher.update(&name.name().as_u64().to_le_bytes());
+        hasher.update(layout.as_bytes());
+
+        let file_name = format!("{}.mono", hasher.finalize().to_hex());
+
+        Some(self.dir.join(file_name))
+    }
+}
+
+fn is_cacheable(name: LambdaName) -> bool {
+    name.name().module_id().is_builtin() && name.niche() == Niche::NONE
+}
+
+/// Whether the proc calls nothing but lowlevels and itself (at the same layout). Expects and
+/// `dbg`s don't need checking here, since the mono text format can't hold them.
+fn is_self_contained<'a>(proc: &Proc<'a>, proc_layout: &ProcLayout<'a>) -> bool {
+    let mut self_contained = true;
+
+    visit_stmts(&proc.body, |expr| {
+        let ok = match expr {
+            Expr::Call(call) => match &call.call_type {
+                CallType::ByName {
+                    name,
+                    ret_layout,
+                    arg_layouts,
+                    ..
+                } => {
+                    *name == proc.name
+                        && *ret_layout == proc_layout.result
+                        && *arg_layouts == proc_layout.arguments
+                }
+                CallType::LowLevel { .. } => true,
+                CallType::ByPointer { .. }
+                | CallType::Foreign { .. }
+                | CallType::HigherOrder(_) => false,
+            },
+            Expr::FunctionPointer { .. } | Expr::ErasedMake { .. } | Expr::ErasedLoad { .. } => {
+                false
+            }
+            _ => true,
+        };
+
+        self_contained &= ok;
+    });
+
+    self_contained
+}
+
+/// Writes to a temporary file and then moves it into place, so that concurrent builds never
+/// read a half-written entry.
+fn write_atomically(path: &std::path::Path, text: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let dir = path.parent().expect("cache entries are always in a dir");
+    std::fs::create_dir_all(dir)?;
+
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(text.as_bytes())?;
+    file.persist(path).map_err(|err| err.error)?;
+
+    Ok(())
+}
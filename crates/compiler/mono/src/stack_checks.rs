@@ -0,0 +1,46 @@
+//! Finds the procs that can make the stack overflow, so backends can check for that before it
+//! happens, and report it instead of crashing with a segfault.
+//!
+//! A chain of calls can only get deep enough to overflow the stack if it goes around a cycle,
+//! so only procs in a cycle of the call graph get a check. Calls in tail position are already
+//! loops by now, so they don't count.
+use crate::inline_hints::{called_proc, visit_stmts};
+use crate::ir::{Proc, ProcLayout};
+use roc_collections::{MutMap, MutSet, ReferenceMatrix};
+use roc_module::symbol::Symbol;
+
+/// The procs that are part of a cycle in the call graph, including the ones that call
+/// themselves outside of tail position.
+pub fn recursive_procs<'a>(
+    procs: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+) -> MutSet<(Symbol, ProcLayout<'a>)> {
+    let keys: Vec<_> = procs.keys().copied().collect();
+    let index_of: MutMap<_, _> = keys.iter().enumerate().map(|(i, key)| (*key, i)).collect();
+
+    let mut calls = ReferenceMatrix::new(keys.len());
+
+    for (caller, key) in keys.iter().enumerate() {
+        visit_stmts(&procs[key].body, |expr| {
+            if let Some(callee) = called_proc(expr).and_then(|callee| index_of.get(&callee)) {
+                calls.set_row_col(caller, *callee, true);
+            }
+        });
+    }
+
+    let mut recursive = MutSet::default();
+
+    for (group, _) in calls.strongly_connected_components_all().groups() {
+        let mut members = group.iter_ones();
+
+        let in_cycle = match (members.next(), members.next()) {
+            (Some(only), None) => calls.get_row_col(only, only),
+            _ => true,
+        };
+
+        if in_cycle {
+            recursive.extend(group.iter_ones().map(|i| keys[i]));
+        }
+    }
+
+    recursive
+}
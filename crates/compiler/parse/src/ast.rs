@@ -782,6 +782,10 @@ pub enum ValueDef<'a> {
     IngestedFileImport(IngestedFileImport<'a>),
 
     Stmt(&'a Loc<Expr<'a>>),
+
+    /// A top-level def that doesn't parse, as it was written.
+    /// See [`parse_module_defs_recovering`](crate::module::parse_module_defs_recovering).
+    Malformed(&'a str),
 }
 
 impl<'a> ValueDef<'a> {
@@ -1023,7 +1027,9 @@ impl<'a, 'b> Iterator for RecursiveValueDefIter<'a, 'b> {
                             }
                         }
                         ValueDef::Stmt(loc_expr) => self.push_pending_from_expr(&loc_expr.value),
-                        ValueDef::Annotation(_, _) | ValueDef::IngestedFileImport(_) => {}
+                        ValueDef::Annotation(_, _)
+                        | ValueDef::IngestedFileImport(_)
+                        | ValueDef::Malformed(_) => {}
                     }
 
                     self.index += 1;
@@ -2670,6 +2676,7 @@ impl<'a> Malformed for ValueDef<'a> {
                 annotation,
             }) => path.is_malformed() || annotation.is_malformed(),
             ValueDef::Stmt(loc_expr) => loc_expr.is_malformed(),
+            ValueDef::Malformed(_) => true,
         }
     }
 }
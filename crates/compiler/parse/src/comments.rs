@@ -0,0 +1,764 @@
+//! A side table of the comments in a module: where each one is, and which node of the AST it's
+//! attached to.
+//!
+//! The AST keeps comments in the spaces before and after its nodes, which is all the formatter
+//! needs to put them back, but it doesn't keep their positions. Tools that move code around,
+//! like refactorings, need both, so they can build a [`CommentTable`] after parsing. It isn't
+//! built while parsing, because the parser backtracks, and most of its callers don't need it.
+use roc_region::all::{Loc, Position, Region};
+
+use crate::ast::{
+    AbilityImpls, AssignedField, Collection, CommentOrNewline, Defs, Expr, Implements,
+    ImplementsAbilities, ImplementsAbility, Pattern, RecordBuilderField, Spaced, StrLiteral,
+    StrSegment, Tag, TypeAnnotation, TypeDef, TypeHeader, ValueDef,
+};
+use crate::header::KeywordItem;
+use crate::highlight::{highlight, Token};
+
+/// Where a comment is, relative to the node it's attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// In the spaces before the node, like a comment on the line above a def
+    Before,
+    /// In the spaces after the node, like a comment at the end of its line
+    After,
+    /// Inside the node, but not around any of its children, like a comment after the last
+    /// element of a list
+    Inside,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attachment {
+    /// The region of the node, without the spaces around it
+    pub node: Region,
+    pub placement: Placement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comment<'a> {
+    /// The text after the `#` (or after the `## ` of a doc comment)
+    pub text: &'a str,
+    pub is_doc: bool,
+    /// From the `#` to the end of the line
+    pub region: Region,
+    /// `None` for comments outside of the module's defs, like the ones in its header
+    pub attached_to: Option<Attachment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommentTable<'a> {
+    comments: Vec<Comment<'a>>,
+}
+
+impl<'a> CommentTable<'a> {
+    /// Finds every comment in `src`, and which node in `defs` each one is attached to.
+    /// `defs` must have been parsed from `src` itself, not from a copy of it.
+    pub fn new(src: &'a str, defs: &Defs<'a>) -> Self {
+        let mut collector = Collector {
+            src,
+            comments: Vec::new(),
+        };
+
+        collector.defs(defs);
+
+        let mut comments = collector.comments;
+
+        // The same spaces can be reachable from more than one node, like the comment of an
+        // annotated body; the first node the comment was found at is the one it's attached to.
+        comments.sort_by_key(|comment| comment.region.start());
+        comments.dedup_by_key(|comment| comment.region.start());
+
+        let attached_count = comments.len();
+
+        for token in highlight(src) {
+            let is_doc = match token.value {
+                Token::LineComment => false,
+                Token::DocComment => true,
+                _ => continue,
+            };
+
+            let start = token.region.start();
+            let attached = comments[..attached_count]
+                .binary_search_by_key(&start, |comment| comment.region.start())
+                .is_ok();
+
+            if !attached {
+                comments.push(Comment {
+                    text: unattached_text(src, token.region, is_doc),
+                    is_doc,
+                    region: token.region,
+                    attached_to: None,
+                });
+            }
+        }
+
+        comments.sort_by_key(|comment| comment.region.start());
+
+        Self { comments }
+    }
+
+    /// Every comment, in the order they're in the source
+    pub fn comments(&self) -> &[Comment<'a>] {
+        &self.comments
+    }
+
+    /// The comments attached to the node at `node`, in the order they're in the source
+    pub fn attached_to(&self, node: Region) -> impl Iterator<Item = &Comment<'a>> + '_ {
+        self.comments.iter().filter(move |comment| {
+            comment
+                .attached_to
+                .is_some_and(|attachment| attachment.node == node)
+        })
+    }
+}
+
+fn unattached_text(src: &str, region: Region, is_doc: bool) -> &str {
+    let text = &src[region.start().offset as usize..region.end().offset as usize];
+
+    if is_doc {
+        let text = text.strip_prefix("##").unwrap_or(text);
+
+        text.strip_prefix(' ').unwrap_or(text)
+    } else {
+        text.strip_prefix('#').unwrap_or(text)
+    }
+}
+
+struct Collector<'a> {
+    src: &'a str,
+    comments: Vec<Comment<'a>>,
+}
+
+impl<'a> Collector<'a> {
+    fn comment(&mut self, text: &'a str, attachment: Attachment) {
+        // The text of a comment is a slice of the source, so that's where its position comes from
+        let Some(offset) = (text.as_ptr() as usize).checked_sub(self.src.as_ptr() as usize) else {
+            return;
+        };
+
+        if offset + text.len() > self.src.len() {
+            return;
+        }
+
+        // A line comment's text comes right after its `#`, and a doc comment's after `## ` (or
+        // `##`, when there's no space); a line comment's text can't come after a space.
+        let before = &self.src.as_bytes()[..offset];
+        let (start, is_doc) = if before.ends_with(b"## ") {
+            (offset - 3, true)
+        } else if before.ends_with(b"##") {
+            (offset - 2, true)
+        } else {
+            (offset - 1, false)
+        };
+
+        self.comments.push(Comment {
+            text,
+            is_doc,
+            region: Region::new(
+                Position::new(start as u32),
+                Position::new((offset + text.len()) as u32),
+            ),
+            attached_to: Some(attachment),
+        });
+    }
+
+    fn spaces(&mut self, spaces: &[CommentOrNewline<'a>], node: Region, placement: Placement) {
+        for space in spaces {
+            if let CommentOrNewline::LineComment(text) | CommentOrNewline::DocComment(text) = space
+            {
+                self.comment(text, Attachment { node, placement });
+            }
+        }
+    }
+
+    fn collection<T>(
+        &mut self,
+        collection: &Collection<'a, T>,
+        region: Region,
+        mut item: impl FnMut(&mut Self, &T),
+    ) {
+        for element in collection.iter() {
+            item(self, element);
+        }
+
+        self.spaces(collection.final_comments(), region, Placement::Inside);
+    }
+
+    /// The spaces around a keyword, like the `as` of an import, are attached to its item
+    fn keyword_item<K, V>(&mut self, keyword_item: &KeywordItem<'a, K, V>, region: Region) {
+        self.spaces(keyword_item.keyword.before, region, Placement::Before);
+        self.spaces(keyword_item.keyword.after, region, Placement::Before);
+    }
+
+    fn spaced<T>(&mut self, spaced: &Spaced<'a, T>, region: Region) {
+        match spaced {
+            Spaced::Item(_) => {}
+            Spaced::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.spaced(inner, region);
+            }
+            Spaced::SpaceAfter(inner, spaces) => {
+                self.spaced(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+        }
+    }
+
+    fn defs(&mut self, defs: &Defs<'a>) {
+        for (index, def) in defs.defs().enumerate() {
+            let region = defs.regions[index];
+
+            self.spaces(
+                &defs.spaces[defs.space_before[index].indices()],
+                region,
+                Placement::Before,
+            );
+
+            match def {
+                Ok(type_def) => self.type_def(type_def),
+                Err(value_def) => self.value_def(value_def),
+            }
+
+            self.spaces(
+                &defs.spaces[defs.space_after[index].indices()],
+                region,
+                Placement::After,
+            );
+        }
+    }
+
+    fn type_header(&mut self, header: &TypeHeader<'a>) {
+        for var in header.vars {
+            self.loc_pattern(var);
+        }
+    }
+
+    fn type_def(&mut self, type_def: &TypeDef<'a>) {
+        match type_def {
+            TypeDef::Alias { header, ann } => {
+                self.type_header(header);
+                self.loc_type(ann);
+            }
+            TypeDef::Opaque {
+                header,
+                typ,
+                derived,
+            } => {
+                self.type_header(header);
+                self.loc_type(typ);
+
+                if let Some(derived) = derived {
+                    self.implements_abilities(&derived.value, derived.region);
+                }
+            }
+            TypeDef::Ability {
+                header,
+                loc_implements,
+                members,
+            } => {
+                self.type_header(header);
+                self.implements(&loc_implements.value, loc_implements.region);
+
+                for member in members.iter() {
+                    self.spaced(&member.name.value, member.name.region);
+                    self.loc_type(&member.typ);
+                }
+            }
+        }
+    }
+
+    fn value_def(&mut self, value_def: &ValueDef<'a>) {
+        match value_def {
+            ValueDef::Annotation(pattern, ann) => {
+                self.loc_pattern(pattern);
+                self.loc_type(ann);
+            }
+            ValueDef::Body(pattern, expr) => {
+                self.loc_pattern(pattern);
+                self.loc_expr(expr);
+            }
+            ValueDef::AnnotatedBody {
+                ann_pattern,
+                ann_type,
+                comment,
+                body_pattern,
+                body_expr,
+            } => {
+                self.loc_pattern(ann_pattern);
+                self.loc_type(ann_type);
+
+                if let Some(text) = comment {
+                    let attachment = Attachment {
+                        node: ann_type.region,
+                        placement: Placement::After,
+                    };
+
+                    self.comment(text, attachment);
+                }
+
+                self.loc_pattern(body_pattern);
+                self.loc_expr(body_expr);
+            }
+            ValueDef::Dbg { condition, .. }
+            | ValueDef::Expect { condition, .. }
+            | ValueDef::ExpectFx { condition, .. } => self.loc_expr(condition),
+            ValueDef::ModuleImport(import) => {
+                self.spaces(import.before_name, import.name.region, Placement::Before);
+
+                if let Some(params) = import.params {
+                    let region = params
+                        .params
+                        .iter()
+                        .fold(import.name.region, |region, field| {
+                            Region::span_across(&region, &field.region)
+                        });
+
+                    self.spaces(params.before, region, Placement::Inside);
+                    self.collection(&params.params, region, |collector, field| {
+                        collector.loc_assigned_field(field, Self::loc_expr)
+                    });
+                }
+
+                if let Some(alias) = &import.alias {
+                    self.keyword_item(alias, alias.item.region);
+                }
+
+                if let Some(exposed) = &import.exposed {
+                    let region = exposed
+                        .item
+                        .iter()
+                        .fold(import.name.region, |region, name| {
+                            Region::span_across(&region, &name.region)
+                        });
+
+                    self.keyword_item(exposed, region);
+                    self.collection(&exposed.item, region, |collector, name| {
+                        collector.spaced(&name.value, name.region)
+                    });
+                }
+            }
+            ValueDef::IngestedFileImport(import) => {
+                self.spaces(import.before_path, import.path.region, Placement::Before);
+                self.str_literal(&import.path.value);
+                self.keyword_item(&import.name, import.name.item.region);
+
+                if let Some(annotation) = &import.annotation {
+                    self.spaces(
+                        annotation.before_colon,
+                        annotation.annotation.region,
+                        Placement::Before,
+                    );
+                    self.loc_type(&annotation.annotation);
+                }
+            }
+            ValueDef::Stmt(expr) => self.loc_expr(expr),
+            ValueDef::Malformed(_) => {}
+        }
+    }
+
+    fn loc_expr(&mut self, loc_expr: &Loc<Expr<'a>>) {
+        self.expr(&loc_expr.value, loc_expr.region)
+    }
+
+    /// `region` is the region of the closest `Loc` around `expr`, since not every expression
+    /// has one of its own
+    fn expr(&mut self, expr: &Expr<'a>, region: Region) {
+        match expr {
+            Expr::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.expr(inner, region);
+            }
+            Expr::SpaceAfter(inner, spaces) => {
+                self.expr(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+            Expr::Str(literal) => self.str_literal(literal),
+            Expr::RecordAccess(inner, _)
+            | Expr::TupleAccess(inner, _)
+            | Expr::TaskAwaitBang(inner)
+            | Expr::ParensAround(inner) => self.expr(inner, region),
+            Expr::List(elems) | Expr::Tuple(elems) => {
+                self.collection(elems, region, |collector, elem| collector.loc_expr(elem))
+            }
+            Expr::RecordUpdate { update, fields } => {
+                self.loc_expr(update);
+                self.collection(fields, region, |collector, field| {
+                    collector.loc_assigned_field(field, Self::loc_expr)
+                });
+            }
+            Expr::Record(fields) => self.collection(fields, region, |collector, field| {
+                collector.loc_assigned_field(field, Self::loc_expr)
+            }),
+            Expr::RecordBuilder(fields) => self.collection(fields, region, |collector, field| {
+                collector.record_builder_field(&field.value, field.region)
+            }),
+            Expr::Closure(args, body) => {
+                for arg in args.iter() {
+                    self.loc_pattern(arg);
+                }
+
+                self.loc_expr(body);
+            }
+            Expr::Defs(defs, final_expr) => {
+                self.defs(defs);
+                self.loc_expr(final_expr);
+            }
+            Expr::Backpassing(patterns, body, rest) => {
+                for pattern in patterns.iter() {
+                    self.loc_pattern(pattern);
+                }
+
+                self.loc_expr(body);
+                self.loc_expr(rest);
+            }
+            Expr::Expect(condition, continuation)
+            | Expr::Dbg(condition, continuation)
+            | Expr::LowLevelDbg(_, condition, continuation) => {
+                self.loc_expr(condition);
+                self.loc_expr(continuation);
+            }
+            Expr::Apply(function, args, _) => {
+                self.loc_expr(function);
+
+                for arg in args.iter() {
+                    self.loc_expr(arg);
+                }
+            }
+            Expr::BinOps(operands, last) => {
+                for (operand, _) in operands.iter() {
+                    self.loc_expr(operand);
+                }
+
+                self.loc_expr(last);
+            }
+            Expr::UnaryOp(operand, _) => self.loc_expr(operand),
+            Expr::If(branches, final_else) => {
+                for (condition, then) in branches.iter() {
+                    self.loc_expr(condition);
+                    self.loc_expr(then);
+                }
+
+                self.loc_expr(final_else);
+            }
+            Expr::When(condition, branches) => {
+                self.loc_expr(condition);
+
+                for branch in branches.iter() {
+                    for pattern in branch.patterns {
+                        self.loc_pattern(pattern);
+                    }
+
+                    if let Some(guard) = &branch.guard {
+                        self.loc_expr(guard);
+                    }
+
+                    self.loc_expr(&branch.value);
+                }
+            }
+            Expr::MalformedSuffixed(inner)
+            | Expr::MultipleRecordBuilders(inner)
+            | Expr::UnappliedRecordBuilder(inner) => self.loc_expr(inner),
+            Expr::PrecedenceConflict(conflict) => self.loc_expr(conflict.expr),
+            Expr::Float(_)
+            | Expr::Num(_)
+            | Expr::NonBase10Int { .. }
+            | Expr::SingleQuote(_)
+            | Expr::AccessorFunction(_)
+            | Expr::Var { .. }
+            | Expr::Underscore(_)
+            | Expr::Crash
+            | Expr::Tag(_)
+            | Expr::OpaqueRef(_)
+            | Expr::EmptyDefsFinal
+            | Expr::MalformedIdent(_, _)
+            | Expr::MalformedClosure => {}
+        }
+    }
+
+    fn str_literal(&mut self, literal: &StrLiteral<'a>) {
+        let lines = match literal {
+            StrLiteral::PlainLine(_) => return,
+            StrLiteral::Line(segments) => std::slice::from_ref(segments),
+            StrLiteral::Block(lines) => *lines,
+        };
+
+        for segment in lines.iter().flat_map(|segments| segments.iter()) {
+            match segment {
+                StrSegment::Interpolated(loc_expr)
+                | StrSegment::DeprecatedInterpolated(loc_expr) => {
+                    self.expr(loc_expr.value, loc_expr.region)
+                }
+                StrSegment::Plaintext(_) | StrSegment::Unicode(_) | StrSegment::EscapedChar(_) => {}
+            }
+        }
+    }
+
+    fn loc_assigned_field<Val>(
+        &mut self,
+        field: &Loc<AssignedField<'a, Val>>,
+        value: fn(&mut Self, &Loc<Val>),
+    ) {
+        self.assigned_field(&field.value, field.region, value)
+    }
+
+    fn assigned_field<Val>(
+        &mut self,
+        field: &AssignedField<'a, Val>,
+        region: Region,
+        value: fn(&mut Self, &Loc<Val>),
+    ) {
+        match field {
+            AssignedField::RequiredValue(_, spaces, val)
+            | AssignedField::OptionalValue(_, spaces, val) => {
+                self.spaces(spaces, region, Placement::Inside);
+                value(self, val);
+            }
+            AssignedField::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.assigned_field(inner, region, value);
+            }
+            AssignedField::SpaceAfter(inner, spaces) => {
+                self.assigned_field(inner, region, value);
+                self.spaces(spaces, region, Placement::After);
+            }
+            AssignedField::LabelOnly(_) | AssignedField::Malformed(_) => {}
+        }
+    }
+
+    fn record_builder_field(&mut self, field: &RecordBuilderField<'a>, region: Region) {
+        match field {
+            RecordBuilderField::Value(_, spaces, value) => {
+                self.spaces(spaces, region, Placement::Inside);
+                self.loc_expr(value);
+            }
+            RecordBuilderField::ApplyValue(_, before_colon, before_arrow, value) => {
+                self.spaces(before_colon, region, Placement::Inside);
+                self.spaces(before_arrow, region, Placement::Inside);
+                self.loc_expr(value);
+            }
+            RecordBuilderField::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.record_builder_field(inner, region);
+            }
+            RecordBuilderField::SpaceAfter(inner, spaces) => {
+                self.record_builder_field(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+            RecordBuilderField::LabelOnly(_) | RecordBuilderField::Malformed(_) => {}
+        }
+    }
+
+    fn loc_pattern(&mut self, loc_pattern: &Loc<Pattern<'a>>) {
+        self.pattern(&loc_pattern.value, loc_pattern.region)
+    }
+
+    fn pattern(&mut self, pattern: &Pattern<'a>, region: Region) {
+        match pattern {
+            Pattern::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.pattern(inner, region);
+            }
+            Pattern::SpaceAfter(inner, spaces) => {
+                self.pattern(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+            Pattern::Apply(tag, args) => {
+                self.loc_pattern(tag);
+
+                for arg in args.iter() {
+                    self.loc_pattern(arg);
+                }
+            }
+            Pattern::RecordDestructure(patterns)
+            | Pattern::Tuple(patterns)
+            | Pattern::List(patterns) => self.collection(patterns, region, Self::loc_pattern),
+            Pattern::RequiredField(_, inner) => self.loc_pattern(inner),
+            Pattern::OptionalField(_, default) => self.loc_expr(default),
+            Pattern::StrLiteral(literal) => self.str_literal(literal),
+            Pattern::ListRest(rest) => {
+                if let Some((spaces, pattern_as)) = rest {
+                    self.spaces(spaces, region, Placement::Inside);
+                    self.spaces(pattern_as.spaces_before, region, Placement::Inside);
+                }
+            }
+            Pattern::As(inner, pattern_as) => {
+                self.loc_pattern(inner);
+                self.spaces(pattern_as.spaces_before, region, Placement::Inside);
+            }
+            Pattern::Identifier { .. }
+            | Pattern::QualifiedIdentifier { .. }
+            | Pattern::Tag(_)
+            | Pattern::OpaqueRef(_)
+            | Pattern::NumLiteral(_)
+            | Pattern::NonBase10Literal { .. }
+            | Pattern::FloatLiteral(_)
+            | Pattern::Underscore(_)
+            | Pattern::SingleQuote(_)
+            | Pattern::Malformed(_)
+            | Pattern::MalformedIdent(_, _) => {}
+        }
+    }
+
+    fn loc_type(&mut self, loc_type: &Loc<TypeAnnotation<'a>>) {
+        self.type_annotation(&loc_type.value, loc_type.region)
+    }
+
+    fn type_annotation(&mut self, ann: &TypeAnnotation<'a>, region: Region) {
+        match ann {
+            TypeAnnotation::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.type_annotation(inner, region);
+            }
+            TypeAnnotation::SpaceAfter(inner, spaces) => {
+                self.type_annotation(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+            TypeAnnotation::Function(args, ret) => {
+                for arg in args.iter() {
+                    self.loc_type(arg);
+                }
+
+                self.loc_type(ret);
+            }
+            TypeAnnotation::Apply(_, _, args) => {
+                for arg in args.iter() {
+                    self.loc_type(arg);
+                }
+            }
+            TypeAnnotation::As(inner, spaces, header) => {
+                self.loc_type(inner);
+                self.spaces(spaces, region, Placement::Inside);
+                self.type_header(header);
+            }
+            TypeAnnotation::Record { fields, ext } => {
+                self.collection(fields, region, |collector, field| {
+                    collector.loc_assigned_field(field, Self::loc_type)
+                });
+
+                if let Some(ext) = ext {
+                    self.loc_type(ext);
+                }
+            }
+            TypeAnnotation::Tuple { elems, ext } => {
+                self.collection(elems, region, Self::loc_type);
+
+                if let Some(ext) = ext {
+                    self.loc_type(ext);
+                }
+            }
+            TypeAnnotation::TagUnion { ext, tags } => {
+                self.collection(tags, region, |collector, tag| {
+                    collector.tag(&tag.value, tag.region)
+                });
+
+                if let Some(ext) = ext {
+                    self.loc_type(ext);
+                }
+            }
+            TypeAnnotation::Where(inner, clauses) => {
+                self.loc_type(inner);
+
+                for clause in clauses.iter() {
+                    self.spaced(&clause.value.var.value, clause.value.var.region);
+
+                    for ability in clause.value.abilities {
+                        self.loc_type(ability);
+                    }
+                }
+            }
+            TypeAnnotation::BoundVariable(_)
+            | TypeAnnotation::Inferred
+            | TypeAnnotation::Wildcard
+            | TypeAnnotation::Malformed(_) => {}
+        }
+    }
+
+    fn tag(&mut self, tag: &Tag<'a>, region: Region) {
+        match tag {
+            Tag::Apply { args, .. } => {
+                for arg in args.iter() {
+                    self.loc_type(arg);
+                }
+            }
+            Tag::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.tag(inner, region);
+            }
+            Tag::SpaceAfter(inner, spaces) => {
+                self.tag(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+            Tag::Malformed(_) => {}
+        }
+    }
+
+    fn implements(&mut self, implements: &Implements<'a>, region: Region) {
+        match implements {
+            Implements::Implements => {}
+            Implements::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.implements(inner, region);
+            }
+            Implements::SpaceAfter(inner, spaces) => {
+                self.implements(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+        }
+    }
+
+    fn implements_abilities(&mut self, abilities: &ImplementsAbilities<'a>, region: Region) {
+        match abilities {
+            ImplementsAbilities::Implements(abilities) => {
+                self.collection(abilities, region, |collector, ability| {
+                    collector.implements_ability(&ability.value, ability.region)
+                })
+            }
+            ImplementsAbilities::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.implements_abilities(inner, region);
+            }
+            ImplementsAbilities::SpaceAfter(inner, spaces) => {
+                self.implements_abilities(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+        }
+    }
+
+    fn implements_ability(&mut self, ability: &ImplementsAbility<'a>, region: Region) {
+        match ability {
+            ImplementsAbility::ImplementsAbility { ability, impls } => {
+                self.loc_type(ability);
+
+                if let Some(impls) = impls {
+                    self.ability_impls(&impls.value, impls.region);
+                }
+            }
+            ImplementsAbility::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.implements_ability(inner, region);
+            }
+            ImplementsAbility::SpaceAfter(inner, spaces) => {
+                self.implements_ability(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+        }
+    }
+
+    fn ability_impls(&mut self, impls: &AbilityImpls<'a>, region: Region) {
+        match impls {
+            AbilityImpls::AbilityImpls(fields) => {
+                self.collection(fields, region, |collector, field| {
+                    collector.loc_assigned_field(field, Self::loc_expr)
+                })
+            }
+            AbilityImpls::SpaceBefore(inner, spaces) => {
+                self.spaces(spaces, region, Placement::Before);
+                self.ability_impls(inner, region);
+            }
+            AbilityImpls::SpaceAfter(inner, spaces) => {
+                self.ability_impls(inner, region);
+                self.spaces(spaces, region, Placement::After);
+            }
+        }
+    }
+}
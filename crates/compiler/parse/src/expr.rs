@@ -19,7 +19,7 @@ use crate::parser::{
     two_bytes, EClosure, EExpect, EExpr, EIf, EImport, EImportParams, EInParens, EList, ENumber,
     EPattern, ERecord, EString, EType, EWhen, Either, ParseResult, Parser,
 };
-use crate::pattern::{closure_param, loc_implements_parser};
+use crate::pattern::{closure_param, loc_implements_parser, loc_tag_pattern_args_help};
 use crate::state::State;
 use crate::string_literal::{self, StrLikeLiteral};
 use crate::{header, keyword};
@@ -454,6 +454,7 @@ impl<'a> ExprState<'a> {
             Err(fail)
         } else if !self.expr.value.is_tag()
             && !self.expr.value.is_opaque()
+            && !self.is_function_def_sugar(loc_op.value)
             && !self.arguments.is_empty()
             && !is_expr_suffixed(&self.expr.value)
         {
@@ -466,6 +467,23 @@ impl<'a> ExprState<'a> {
         }
     }
 
+    /// `f x y = body`, which is sugar for `f = \x, y -> body`. The name and its
+    /// arguments have to be on one line.
+    fn is_function_def_sugar(&self, op: BinOp) -> bool {
+        op == BinOp::Assignment
+            && matches!(
+                self.expr.value,
+                Expr::Var {
+                    module_name: "",
+                    ..
+                }
+            )
+            && !self
+                .arguments
+                .iter()
+                .any(|arg| matches!(arg.value, Expr::SpaceBefore(..)))
+    }
+
     fn validate_is_type_def(
         mut self,
         arena: &'a Bump,
@@ -929,6 +947,41 @@ pub fn parse_single_def<'a>(
                 }
             };
 
+            // `f x y = body`, which is sugar for `f = \x, y -> body`
+            if let Pattern::Identifier { .. } = loc_pattern.value {
+                if let Ok((_, args, args_state)) =
+                    loc_tag_pattern_args_help().parse(arena, state.clone(), min_indent)
+                {
+                    let operator_after_args = space0_e(EExpr::IndentEnd)
+                        .parse(arena, args_state, min_indent)
+                        .ok()
+                        .filter(|(_, spaces, _)| spaces.is_empty())
+                        .and_then(|(_, _, state)| operator().parse(arena, state, min_indent).ok());
+
+                    let on_one_line = !args
+                        .iter()
+                        .any(|arg| matches!(arg.value, Pattern::SpaceBefore(..)));
+
+                    if let (true, Some(last_arg), Some((_, BinOp::Assignment, operator_state))) =
+                        (on_one_line, args.last(), operator_after_args)
+                    {
+                        let region = Region::span_across(&loc_pattern.region, &last_arg.region);
+                        let head = arena.alloc(loc_pattern);
+                        let def_loc_pattern =
+                            Loc::at(region, Pattern::Apply(head, args.into_bump_slice()));
+
+                        return parse_single_def_assignment(
+                            options,
+                            &operator_state.line_indent() + 1,
+                            arena,
+                            operator_state,
+                            def_loc_pattern,
+                            spaces_before_current,
+                        );
+                    }
+                }
+            }
+
             // Otherwise try to re-parse as a Statement
             match parse_statement_inside_def(
                 arena,
@@ -8,6 +8,7 @@
 pub mod parser;
 pub mod ast;
 pub mod blankspace;
+pub mod comments;
 pub mod expr;
 pub mod header;
 pub mod highlight;
@@ -1,4 +1,4 @@
-use crate::ast::{Collection, CommentOrNewline, Defs, Header, Module, Spaced, Spaces};
+use crate::ast::{Collection, CommentOrNewline, Defs, Header, Module, Spaced, Spaces, ValueDef};
 use crate::blankspace::{space0_around_ee, space0_before_e, space0_e};
 use crate::expr::merge_spaces;
 use crate::header::{
@@ -46,6 +46,131 @@ pub fn parse_module_defs<'a>(
     }
 }
 
+/// How many more lines that look like the start of a top-level def a def that doesn't parse
+/// may run into, before [`parse_module_defs_recovering`] gives up on it. Those lines can still
+/// be part of it, like the lines of a multiline string.
+const MAX_RECOVERY_LOOKAHEAD: usize = 8;
+
+/// Like [`parse_module_defs`], but a top-level def that doesn't parse doesn't stop it. That def
+/// becomes a [`ValueDef::Malformed`] holding its source, and parsing picks up again at the next
+/// line that starts a top-level def, so one typo doesn't hide every def (and problem) after it.
+///
+/// Returns the defs, along with the syntax error of each malformed one.
+pub fn parse_module_defs_recovering<'a>(
+    arena: &'a bumpalo::Bump,
+    state: State<'a>,
+    defs: Defs<'a>,
+) -> (Defs<'a>, Vec<SyntaxError<'a>>) {
+    let first_problem = match parse_module_defs(arena, state.clone(), defs.clone()) {
+        Ok(parsed) => return (parsed, Vec::new()),
+        Err(fail) => fail,
+    };
+
+    let boundaries = top_level_def_boundaries(&state);
+    let start_offset = state.pos().offset as usize;
+    let chunk_state = |start: usize, end: usize| {
+        if start == start_offset {
+            state.clone().truncated(end)
+        } else {
+            state.lines_between(start, end)
+        }
+    };
+
+    // First, find the runs of lines that parse, and the defs in between that don't
+    let mut runs: Vec<(usize, usize, Option<SyntaxError<'a>>)> = Vec::new();
+    let last = boundaries.len() - 1;
+    let mut chunk = 0;
+
+    while chunk < last {
+        let start = boundaries[chunk];
+        let mut problem = None;
+        let mut parsed_until = None;
+
+        for end in (chunk + 1..=last).take(MAX_RECOVERY_LOOKAHEAD + 1) {
+            match parse_module_defs(arena, chunk_state(start, boundaries[end]), Defs::default()) {
+                Ok(_) => {
+                    parsed_until = Some(end);
+                    break;
+                }
+                Err(fail) => {
+                    problem.get_or_insert(fail);
+                }
+            }
+        }
+
+        match parsed_until {
+            Some(end) => {
+                match runs.last_mut() {
+                    Some((_, run_end, None)) if *run_end == start => *run_end = boundaries[end],
+                    _ => runs.push((start, boundaries[end], None)),
+                }
+
+                chunk = end;
+            }
+            None => {
+                runs.push((start, boundaries[chunk + 1], problem));
+
+                chunk += 1;
+            }
+        }
+    }
+
+    // Then parse each run as a whole, so that e.g. an annotation still goes with its body
+    let mut defs = defs;
+    let mut problems = Vec::new();
+
+    for (start, end, problem) in runs {
+        let problem = match problem {
+            Some(problem) => problem,
+            None => match parse_module_defs(arena, chunk_state(start, end), defs.clone()) {
+                Ok(parsed) => {
+                    defs = parsed;
+                    continue;
+                }
+                Err(fail) => fail,
+            },
+        };
+
+        let source = std::str::from_utf8(&state.original_bytes()[start..end]).unwrap_or("");
+        let text_start = start + source.len() - source.trim_start().len();
+        let text = source.trim();
+        let region = Region::new(
+            Position::new(text_start as u32),
+            Position::new((text_start + text.len()) as u32),
+        );
+
+        defs.push_value_def(ValueDef::Malformed(text), region, &[], &[]);
+        problems.push(problem);
+    }
+
+    // Parsing the whole input found the first problem with all of the input to go on, so it's
+    // the one to report about the first malformed def
+    match problems.first_mut() {
+        Some(problem) => *problem = first_problem,
+        None => problems.push(first_problem),
+    }
+
+    (defs, problems)
+}
+
+/// Where parsing can pick up again after a top-level def that doesn't parse: the start of each
+/// line that begins with an identifier or keyword, like every top-level def does. The first
+/// boundary is the current position, and the last is the end of the input.
+fn top_level_def_boundaries(state: &State) -> Vec<usize> {
+    let bytes = state.original_bytes();
+    let start = state.pos().offset as usize;
+    let mut boundaries = vec![start];
+
+    for (offset, pair) in bytes.windows(2).enumerate().skip(start) {
+        if pair[0] == b'\n' && (pair[1].is_ascii_alphabetic() || pair[1] == b'_') {
+            boundaries.push(offset + 1);
+        }
+    }
+
+    boundaries.push(bytes.len());
+    boundaries
+}
+
 pub fn parse_header<'a>(
     arena: &'a bumpalo::Bump,
     state: State<'a>,
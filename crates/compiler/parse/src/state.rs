@@ -36,6 +36,37 @@ impl<'a> State<'a> {
         }
     }
 
+    /// The same position in a rewritten copy of the original bytes, which must be just as long
+    pub fn with_original_bytes(self, original_bytes: &'a [u8]) -> State<'a> {
+        debug_assert_eq!(original_bytes.len(), self.original_bytes.len());
+
+        State {
+            original_bytes,
+            ..self
+        }
+    }
+
+    /// The input from the start of the line at `line_start` up to `end`, so that those lines
+    /// can be parsed on their own. Positions in it are still from the start of the file.
+    pub(crate) fn lines_between(&self, line_start: usize, end: usize) -> State<'a> {
+        let line_start_pos = Position::new(line_start as u32);
+
+        State {
+            original_bytes: &self.original_bytes[..end],
+            offset: line_start,
+            line_start: line_start_pos,
+            line_start_after_whitespace: line_start_pos,
+        }
+    }
+
+    /// The same input, cut off at `end`
+    pub(crate) fn truncated(self, end: usize) -> State<'a> {
+        State {
+            original_bytes: &self.original_bytes[..end],
+            ..self
+        }
+    }
+
     pub fn original_bytes(&self) -> &'a [u8] {
         self.original_bytes
     }
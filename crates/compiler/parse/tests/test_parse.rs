@@ -22,7 +22,8 @@ mod test_parse {
     use roc_parse::ast::StrSegment::*;
     use roc_parse::ast::{self, EscapedChar};
     use roc_parse::ast::{CommentOrNewline, StrLiteral::*};
-    use roc_parse::module::parse_module_defs;
+    use roc_parse::comments::{CommentTable, Placement};
+    use roc_parse::module::{parse_module_defs, parse_module_defs_recovering};
     use roc_parse::parser::SyntaxError;
     use roc_parse::state::State;
     use roc_parse::test_helpers::parse_expr_with;
@@ -381,6 +382,136 @@ mod test_parse {
         }
     }
 
+    #[test]
+    fn recovers_after_malformed_top_level_defs() {
+        let arena = &Bump::new();
+        let src = indoc!(
+            r#"
+            first = 1
+
+            broken = (2 +
+
+            second : Str
+            second = "two"
+
+            alsoBroken = [3,
+
+            third = 3
+            "#
+        );
+
+        let state = State::new(src.as_bytes());
+        let (defs, problems) = parse_module_defs_recovering(arena, state, ast::Defs::default());
+
+        let kinds: std::vec::Vec<&str> = defs
+            .defs()
+            .filter_map(Result::err)
+            .map(|def| match def {
+                ast::ValueDef::Body(..) => "body",
+                ast::ValueDef::AnnotatedBody { .. } => "annotated body",
+                ast::ValueDef::Malformed(source) => *source,
+                _ => "other",
+            })
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                "body",
+                "broken = (2 +",
+                "annotated body",
+                "alsoBroken = [3,",
+                "body"
+            ]
+        );
+        assert_eq!(problems.len(), 2);
+    }
+
+    #[test]
+    fn recovering_parse_of_valid_defs_is_unchanged() {
+        let arena = &Bump::new();
+        let src = indoc!(
+            r#"
+            first : Str
+            first = "one"
+
+            second = \x ->
+                x + 1
+            "#
+        );
+
+        let expected = parse_module_defs(arena, State::new(src.as_bytes()), ast::Defs::default());
+        let (defs, problems) =
+            parse_module_defs_recovering(arena, State::new(src.as_bytes()), ast::Defs::default());
+
+        assert_eq!(expected, Ok(defs));
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn comment_table_has_positions_and_attachments() {
+        let arena = &Bump::new();
+        let src = indoc!(
+            r"
+            ## Doubles a number
+            double = \n ->
+                # multiply
+                n * 2
+
+            list = [
+                1,
+                # nothing else
+            ]
+            "
+        );
+
+        let defs =
+            parse_module_defs(arena, State::new(src.as_bytes()), ast::Defs::default()).unwrap();
+        let table = CommentTable::new(src, &defs);
+
+        let comments: std::vec::Vec<_> = table
+            .comments()
+            .iter()
+            .map(|comment| {
+                let region = comment.region;
+                let written = &src[region.start().offset as usize..region.end().offset as usize];
+
+                (
+                    comment.text,
+                    written,
+                    comment.is_doc,
+                    comment.attached_to.map(|attachment| attachment.placement),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            comments,
+            [
+                (
+                    "Doubles a number",
+                    "## Doubles a number",
+                    true,
+                    Some(Placement::Before)
+                ),
+                (" multiply", "# multiply", false, Some(Placement::Before)),
+                (
+                    " nothing else",
+                    "# nothing else",
+                    false,
+                    Some(Placement::Inside)
+                ),
+            ]
+        );
+
+        let doc_comments: std::vec::Vec<_> = table
+            .attached_to(defs.regions[0])
+            .map(|comment| comment.text)
+            .collect();
+
+        assert_eq!(doc_comments, ["Doubles a number"]);
+    }
+
     #[test]
     fn parse_expr_size() {
         assert_eq!(std::mem::size_of::<roc_parse::ast::Expr>(), 40);
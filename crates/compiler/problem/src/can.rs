@@ -39,6 +39,14 @@ pub enum Problem {
     UnusedImport(Symbol, Region),
     UnusedModuleImport(ModuleId, Region),
     ExposedButNotDefined(Symbol),
+    /// A platform lists this in `provides`, but doesn't define it.
+    ProvidedButNotDefined(Symbol),
+    /// A hosted module's signature doesn't return the module's effect type.
+    HostedSignatureNotEffect {
+        symbol: Symbol,
+        effect: Symbol,
+        region: Region,
+    },
     UnknownGeneratesWith(Loc<Ident>),
     ImportNameConflict {
         name: ModuleName,
@@ -186,11 +194,6 @@ pub enum Problem {
         ability: Symbol,
         not_required: Vec<Symbol>,
     },
-    DoesNotImplementAbility {
-        region: Region,
-        ability: Symbol,
-        not_implemented: Vec<Symbol>,
-    },
     NotBoundInAllPatterns {
         unbound_symbol: Symbol,
         region: Region,
@@ -251,6 +254,8 @@ impl Problem {
             Problem::ExplicitBuiltinTypeImport(_, _) => Warning,
             Problem::ImportShadowsSymbol { .. } => RuntimeError,
             Problem::ExposedButNotDefined(_) => RuntimeError,
+            Problem::ProvidedButNotDefined(_) => RuntimeError,
+            Problem::HostedSignatureNotEffect { .. } => RuntimeError,
             Problem::UnknownGeneratesWith(_) => RuntimeError,
             Problem::UnusedArgument(_, _, _, _) => Warning,
             Problem::UnusedBranchDef(_, _) => Warning,
@@ -293,7 +298,6 @@ impl Problem {
             Problem::DuplicateImpl { .. } => Warning, // First impl is used at runtime
             Problem::NotAnAbility(_) => Warning,
             Problem::ImplementsNonRequired { .. } => Warning,
-            Problem::DoesNotImplementAbility { .. } => RuntimeError,
             Problem::NotBoundInAllPatterns { .. } => RuntimeError,
             Problem::NoIdentifiersIntroduced(_) => Warning,
             Problem::OverloadedSpecialization { .. } => Warning, // Ideally, will compile
@@ -333,6 +337,7 @@ impl Problem {
             | Problem::ExplicitBuiltinImport(_, region)
             | Problem::ExplicitBuiltinTypeImport(_, region)
             | Problem::ImportShadowsSymbol { region, .. }
+            | Problem::HostedSignatureNotEffect { region, .. }
             | Problem::UnknownGeneratesWith(Loc { region, .. })
             | Problem::UnusedArgument(_, _, _, region)
             | Problem::UnusedBranchDef(_, region)
@@ -449,7 +454,6 @@ impl Problem {
             }
             | Problem::NotAnAbility(region)
             | Problem::ImplementsNonRequired { region, .. }
-            | Problem::DoesNotImplementAbility { region, .. }
             | Problem::NoIdentifiersIntroduced(region)
             | Problem::OverloadedSpecialization {
                 overload: region, ..
@@ -477,7 +481,8 @@ impl Problem {
             | Problem::RuntimeError(RuntimeError::ExposedButNotDefined(_))
             | Problem::RuntimeError(RuntimeError::NoImplementationNamed { .. })
             | Problem::FileProblem { .. }
-            | Problem::ExposedButNotDefined(_) => None,
+            | Problem::ExposedButNotDefined(_)
+            | Problem::ProvidedButNotDefined(_) => None,
         }
     }
 }
@@ -545,6 +550,18 @@ pub enum FloatErrorKind {
     IntSuffix,
 }
 
+/// A value or type exposed by another module, used to suggest a qualified name when the
+/// one that was written refers to a module that isn't imported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExposedName {
+    pub module_name: ModuleName,
+    /// How the module would be written in an `import`, e.g. `pf.Stdout`
+    pub import_name: Box<str>,
+    pub ident: Ident,
+    /// Whether the module is already in scope where the name was referenced
+    pub imported: bool,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RuntimeError {
     Shadowing {
@@ -622,6 +639,13 @@ pub enum RuntimeError {
         ///
         /// If unsure, this should be set to `false`
         module_exists: bool,
+        /// How the module would be written in an `import`, e.g. `pf.Stdout`
+        import_name: Box<str>,
+        /// The name that was looked up in the module, e.g. `map` in `Lst.map`
+        ident: Ident,
+        /// Everything exposed by the modules we know about, so that we can suggest the
+        /// closest qualified name, e.g. `List.map` for `Lst.map`
+        exposed_names: Vec<ExposedName>,
     },
     ReadIngestedFileError {
         filename: PathBuf,
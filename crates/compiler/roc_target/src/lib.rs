@@ -72,6 +72,9 @@ pub enum Target {
     WinX64,
     WinArm64,
     Wasm32,
+    /// A standalone module that gets its stdio, environment and clock from WASI,
+    /// so it can run under a WASI runtime like wasmtime without a custom JS host.
+    Wasm32Wasi,
 }
 
 impl Target {
@@ -81,7 +84,7 @@ impl Target {
             LinuxX32 | WinX32 => Architecture::X86_32,
             LinuxX64 | WinX64 | MacX64 => Architecture::X86_64,
             LinuxArm64 | WinArm64 | MacArm64 => Architecture::Aarch64,
-            Wasm32 => Architecture::Wasm32,
+            Wasm32 | Wasm32Wasi => Architecture::Wasm32,
         }
     }
 
@@ -91,7 +94,8 @@ impl Target {
             LinuxX32 | LinuxX64 | LinuxArm64 => OperatingSystem::Linux,
             MacX64 | MacArm64 => OperatingSystem::Mac,
             WinX32 | WinX64 | WinArm64 => OperatingSystem::Windows,
-            Wasm32 => OperatingSystem::Freestanding,
+            // Code gen is the same for both; WASI only changes what the host links against.
+            Wasm32 | Wasm32Wasi => OperatingSystem::Freestanding,
         }
     }
 
@@ -127,7 +131,7 @@ impl Target {
         match self {
             LinuxX32 | LinuxX64 | LinuxArm64 | MacX64 | MacArm64 => "o",
             WinX32 | WinX64 | WinArm64 => "obj",
-            Wasm32 => "wasm",
+            Wasm32 | Wasm32Wasi => "wasm",
         }
     }
 
@@ -136,7 +140,7 @@ impl Target {
         match self {
             LinuxX32 | LinuxX64 | LinuxArm64 | MacX64 | MacArm64 => "a",
             WinX32 | WinX64 | WinArm64 => "lib",
-            Wasm32 => "wasm",
+            Wasm32 | Wasm32Wasi => "wasm",
         }
     }
 
@@ -145,7 +149,7 @@ impl Target {
         match self {
             LinuxX32 | LinuxX64 | LinuxArm64 | MacX64 | MacArm64 => None,
             WinX32 | WinX64 | WinArm64 => Some("exe"),
-            Wasm32 => Some("wasm"),
+            Wasm32 | Wasm32Wasi => Some("wasm"),
         }
     }
 }
@@ -172,6 +176,7 @@ impl FromStr for Target {
             "windows-x64" => Ok(WinX64),
             "windows-arm64" => Ok(WinArm64),
             "wasm32" => Ok(Wasm32),
+            "wasm32-wasi" => Ok(Wasm32Wasi),
             _ => Err(ParseError::InvalidTargetString),
         }
     }
@@ -198,6 +203,7 @@ impl From<&Target> for &'static str {
             WinX64 => "windows-x64",
             WinArm64 => "windows-arm64",
             Wasm32 => "wasm32",
+            Wasm32Wasi => "wasm32-wasi",
         }
     }
 }
@@ -252,6 +258,11 @@ impl From<&Triple> for Target {
                 operating_system: OperatingSystem::MacOSX { .. } | OperatingSystem::Darwin,
                 ..
             } => Target::MacArm64,
+            Triple {
+                architecture: Architecture::Wasm32,
+                operating_system: OperatingSystem::Wasi,
+                ..
+            } => Target::Wasm32Wasi,
             Triple {
                 architecture: Architecture::Wasm32,
                 ..
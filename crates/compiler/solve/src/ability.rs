@@ -1,16 +1,17 @@
 use roc_can::abilities::AbilitiesStore;
-use roc_can::expr::PendingDerives;
+use roc_can::expr::{IncompleteImpl, IncompleteImpls, PendingDerives};
 use roc_checkmate::with_checkmate;
-use roc_collections::{VecMap, VecSet};
+use roc_collections::{SendMap, VecMap, VecSet};
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::ROC_PRINT_UNDERIVABLE;
 use roc_derive_key::{DeriveError, Derived};
 use roc_error_macros::internal_error;
+use roc_module::ident::TagName;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::{Loc, Region};
 use roc_solve_problem::{
-    NotDerivableContext, NotDerivableEq, TypeError, UnderivableReason, Unfulfilled,
+    NotDerivableContext, NotDerivableEq, TypeError, TypePathStep, UnderivableReason, Unfulfilled,
 };
 use roc_solve_schema::UnificationMode;
 use roc_types::num::NumericRange;
@@ -18,7 +19,9 @@ use roc_types::subs::{
     instantiate_rigids, Content, FlatType, GetSubsSlice, Rank, RecordFields, Subs, SubsSlice,
     TupleElems, Variable,
 };
-use roc_types::types::{AliasKind, Category, MemberImpl, PatternCategory, Polarity, Types};
+use roc_types::types::{
+    AliasKind, Category, ErrorType, MemberImpl, PatternCategory, Polarity, Types,
+};
 use roc_unify::unify::MustImplementConstraints;
 use roc_unify::unify::{MustImplementAbility, Obligated};
 use roc_unify::Env as UEnv;
@@ -106,6 +109,106 @@ impl PendingDerivesTable {
     }
 }
 
+/// Reports the abilities opaque types claimed to implement without implementing all of their
+/// members. Each missing member is reported with its signature specialized to the opaque type,
+/// which is only known once the module has been solved.
+pub fn report_incomplete_impls(
+    subs: &mut Subs,
+    abilities_store: &AbilitiesStore,
+    incomplete_impls: IncompleteImpls,
+    problems: &mut Vec<TypeError>,
+) {
+    for IncompleteImpl {
+        region,
+        opaque,
+        opaque_vars,
+        ability,
+        not_implemented,
+    } in incomplete_impls
+    {
+        let opaque_type = ErrorType::Alias(
+            opaque,
+            opaque_vars.into_iter().map(ErrorType::FlexVar).collect(),
+            Box::new(ErrorType::Error),
+            AliasKind::Opaque,
+        );
+
+        let not_implemented = not_implemented
+            .into_iter()
+            .map(|member| {
+                let signature = match abilities_store.member_def(member) {
+                    Some(member_data) => {
+                        let signature =
+                            subs.var_to_error_type(member_data.signature_var(), Polarity::OF_VALUE);
+
+                        substitute_implementer(signature, ability, &opaque_type)
+                    }
+                    None => ErrorType::Error,
+                };
+
+                (member, signature)
+            })
+            .collect();
+
+        problems.push(TypeError::IncompleteAbilityImplementation {
+            region,
+            ability,
+            not_implemented,
+        });
+    }
+}
+
+/// Replaces the type variable bound to `ability` in an ability member's signature with the type
+/// implementing the ability.
+fn substitute_implementer(typ: ErrorType, ability: Symbol, implementer: &ErrorType) -> ErrorType {
+    use ErrorType::*;
+
+    let go = |typ| substitute_implementer(typ, ability, implementer);
+    let go_tags = |tags: SendMap<TagName, Vec<ErrorType>>| {
+        tags.into_iter()
+            .map(|(tag, args)| (tag, args.into_iter().map(go).collect()))
+            .collect()
+    };
+
+    match typ {
+        FlexAbleVar(_, abilities) | RigidAbleVar(_, abilities) if abilities.contains(&ability) => {
+            implementer.clone()
+        }
+        Type(symbol, args) => Type(symbol, args.into_iter().map(go).collect()),
+        Record(fields, ext) => Record(
+            fields
+                .into_iter()
+                .map(|(label, field)| (label, field.map_owned(go)))
+                .collect(),
+            ext,
+        ),
+        Tuple(elems, ext) => Tuple(
+            elems
+                .into_iter()
+                .map(|(index, elem)| (index, go(elem)))
+                .collect(),
+            ext,
+        ),
+        TagUnion(tags, ext, pol) => TagUnion(go_tags(tags), ext, pol),
+        RecursiveTagUnion(rec, tags, ext, pol) => {
+            RecursiveTagUnion(Box::new(go(*rec)), go_tags(tags), ext, pol)
+        }
+        Function(args, closure, ret) => Function(
+            args.into_iter().map(go).collect(),
+            Box::new(go(*closure)),
+            Box::new(go(*ret)),
+        ),
+        Alias(symbol, args, real, kind) => Alias(
+            symbol,
+            args.into_iter().map(go).collect(),
+            Box::new(go(*real)),
+            kind,
+        ),
+        Range(types) => Range(types.into_iter().map(go).collect()),
+        Infinite | FlexVar(_) | RigidVar(_) | FlexAbleVar(..) | RigidAbleVar(..) | Error => typ,
+    }
+}
+
 type ObligationResult = Result<(), Unfulfilled>;
 
 #[derive(Default)]
@@ -282,12 +385,14 @@ impl ObligationCache {
         // Not worth caching ad-hoc checks because variables are unlikely to be the same between
         // independent queries.
 
+        let mut path = DerivePath::default();
         let opt_can_derive_builtin = match ability {
             Symbol::ENCODE_ENCODING => Some(DeriveEncoding::is_derivable(
                 self,
                 abilities_store,
                 subs,
                 var,
+                &mut path,
             )),
 
             Symbol::DECODE_DECODING => Some(DeriveDecoding::is_derivable(
@@ -295,19 +400,31 @@ impl ObligationCache {
                 abilities_store,
                 subs,
                 var,
+                &mut path,
             )),
 
-            Symbol::HASH_HASH_ABILITY => {
-                Some(DeriveHash::is_derivable(self, abilities_store, subs, var))
-            }
+            Symbol::HASH_HASH_ABILITY => Some(DeriveHash::is_derivable(
+                self,
+                abilities_store,
+                subs,
+                var,
+                &mut path,
+            )),
 
-            Symbol::BOOL_EQ => Some(DeriveEq::is_derivable(self, abilities_store, subs, var)),
+            Symbol::BOOL_EQ => Some(DeriveEq::is_derivable(
+                self,
+                abilities_store,
+                subs,
+                var,
+                &mut path,
+            )),
 
             Symbol::INSPECT_INSPECT_ABILITY => Some(DeriveInspect::is_derivable(
                 self,
                 abilities_store,
                 subs,
                 var,
+                &mut path,
             )),
 
             _ => None,
@@ -330,7 +447,7 @@ impl ObligationCache {
                     UnderivableReason::SurfaceNotDerivable(context)
                 } else {
                     let error_type = subs.var_to_error_type(failure_var, Polarity::OF_VALUE);
-                    UnderivableReason::NestedNotDerivable(error_type, context)
+                    UnderivableReason::NestedNotDerivable(error_type, context, path.current())
                 })
             }
             None => Some(UnderivableReason::NotABuiltin),
@@ -523,6 +640,39 @@ struct NotDerivable {
 
 struct Descend(bool);
 
+/// Where each type visited while checking derivability sits inside the type we're deriving
+/// for, so that a failure can point at the field or tag it came from.
+#[derive(Default)]
+struct DerivePath {
+    /// Each step, and the index of the step it was taken from
+    steps: Vec<(TypePathStep, Option<usize>)>,
+    /// The last step taken to reach the type being visited
+    at: Option<usize>,
+}
+
+impl DerivePath {
+    fn step(&mut self, step: TypePathStep) -> Option<usize> {
+        self.steps.push((step, self.at));
+
+        Some(self.steps.len() - 1)
+    }
+
+    /// The steps to the type being visited, outermost first
+    fn current(&self) -> Vec<TypePathStep> {
+        let mut path = Vec::new();
+        let mut at = self.at;
+
+        while let Some(index) = at {
+            let (step, parent) = &self.steps[index];
+            path.push(step.clone());
+            at = *parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
 trait DerivableVisitor {
     const ABILITY: Symbol;
     const ABILITY_SLICE: SubsSlice<Symbol>;
@@ -662,21 +812,28 @@ trait DerivableVisitor {
         abilities_store: &AbilitiesStore,
         subs: &mut Subs,
         var: Variable,
+        path: &mut DerivePath,
     ) -> Result<(), NotDerivable> {
-        let mut stack = vec![var];
+        let mut stack = vec![(var, None)];
         let mut seen_recursion_vars = vec![];
 
         macro_rules! push_var_slice {
-            ($slice:expr) => {
-                stack.extend(subs.get_subs_slice($slice))
+            ($slice:expr, $step:expr) => {
+                stack.extend(
+                    subs.get_subs_slice($slice)
+                        .iter()
+                        .map(|var| (*var, path.step($step))),
+                )
             };
         }
 
-        while let Some(var) = stack.pop() {
+        while let Some((var, at)) = stack.pop() {
             if seen_recursion_vars.contains(&var) {
                 continue;
             }
 
+            path.at = at;
+
             let content = subs.get_content_without_compacting(var);
 
             use Content::*;
@@ -716,27 +873,30 @@ trait DerivableVisitor {
                     let descend = Self::visit_recursion(var)?;
                     if descend.0 {
                         seen_recursion_vars.push(var);
-                        stack.push(structure);
+                        stack.push((structure, at));
                     }
                 }
                 Structure(flat_type) => match flat_type {
                     Apply(symbol, vars) => {
                         let descend = Self::visit_apply(var, symbol)?;
                         if descend.0 {
-                            push_var_slice!(vars)
+                            push_var_slice!(vars, TypePathStep::TypeArg(symbol))
                         }
                     }
                     Func(args, _clos, ret) => {
                         let descend = Self::visit_func(var)?;
                         if descend.0 {
-                            push_var_slice!(args);
-                            stack.push(ret);
+                            push_var_slice!(args, TypePathStep::FunctionArg);
+                            stack.push((ret, path.step(TypePathStep::FunctionReturn)));
                         }
                     }
                     Record(fields, ext) => {
                         let descend = Self::visit_record(subs, var, fields)?;
                         if descend.0 {
-                            push_var_slice!(fields.variables());
+                            for (name_index, var_index, _) in fields.iter_all() {
+                                let step = TypePathStep::RecordField(subs[name_index].clone());
+                                stack.push((subs[var_index], path.step(step)));
+                            }
                             if !matches!(
                                 subs.get_content_without_compacting(ext),
                                 Content::FlexVar(_) | Content::RigidVar(_)
@@ -744,45 +904,56 @@ trait DerivableVisitor {
                                 // TODO: currently, just we suppose the presence of a flex var may
                                 // include more or less things which we can derive. But, we should
                                 // instead recurse here, and add a `t ~ u where u implements Decode` constraint as needed.
-                                stack.push(ext);
+                                stack.push((ext, at));
                             }
                         }
                     }
                     Tuple(elems, ext) => {
                         let descend = Self::visit_tuple(subs, var, elems)?;
                         if descend.0 {
-                            push_var_slice!(elems.variables());
+                            for (index_index, var_index) in elems.iter_all() {
+                                let step = TypePathStep::TupleElem(subs[index_index]);
+                                stack.push((subs[var_index], path.step(step)));
+                            }
                             if !matches!(
                                 subs.get_content_without_compacting(ext),
                                 Content::FlexVar(_) | Content::RigidVar(_)
                             ) {
-                                stack.push(ext);
+                                stack.push((ext, at));
                             }
                         }
                     }
                     TagUnion(tags, ext) => {
                         let descend = Self::visit_tag_union(var)?;
                         if descend.0 {
-                            for i in tags.variables() {
-                                push_var_slice!(subs[i]);
+                            for (name_index, payload_index) in tags.iter_all() {
+                                let tag_name = subs[name_index].clone();
+                                push_var_slice!(
+                                    subs[payload_index],
+                                    TypePathStep::TagPayload(tag_name.clone())
+                                );
                             }
-                            stack.push(ext.var());
+                            stack.push((ext.var(), at));
                         }
                     }
                     FunctionOrTagUnion(_tag_name, _fn_name, ext) => {
                         let descend = Self::visit_function_or_tag_union(var)?;
                         if descend.0 {
-                            stack.push(ext.var());
+                            stack.push((ext.var(), at));
                         }
                     }
                     RecursiveTagUnion(rec, tags, ext) => {
                         let descend = Self::visit_recursive_tag_union(var)?;
                         if descend.0 {
                             seen_recursion_vars.push(rec);
-                            for i in tags.variables() {
-                                push_var_slice!(subs[i]);
+                            for (name_index, payload_index) in tags.iter_all() {
+                                let tag_name = subs[name_index].clone();
+                                push_var_slice!(
+                                    subs[payload_index],
+                                    TypePathStep::TagPayload(tag_name.clone())
+                                );
                             }
-                            stack.push(ext.var());
+                            stack.push((ext.var(), at));
                         }
                     }
                     EmptyRecord => Self::visit_empty_record(var)?,
@@ -797,13 +968,13 @@ trait DerivableVisitor {
                 ) => {
                     // Unbound numbers and integers: always decay until a ground is hit,
                     // since all of our builtin abilities currently support integers.
-                    stack.push(real_var);
+                    stack.push((real_var, at));
                 }
                 Alias(Symbol::NUM_FLOATINGPOINT, _alias_variables, real_var, AliasKind::Opaque) => {
                     let descend = Self::visit_floating_point_content(var, subs, real_var)?;
                     if descend.0 {
                         // Decay to a ground
-                        stack.push(real_var)
+                        stack.push((real_var, at))
                     }
                 }
                 Alias(opaque, _alias_variables, _real_var, AliasKind::Opaque) => {
@@ -821,7 +992,7 @@ trait DerivableVisitor {
                 Alias(symbol, _alias_variables, real_var, AliasKind::Structural) => {
                     let descend = Self::visit_alias(var, symbol)?;
                     if descend.0 {
-                        stack.push(real_var);
+                        stack.push((real_var, at));
                     }
                 }
                 RangedNumber(range) => Self::visit_ranged_number(var, range)?,
@@ -1,7 +1,11 @@
 use bumpalo::Bump;
-use roc_can::{constraint::Constraints, module::ExposedByModule};
+use roc_can::{
+    constraint::{Constraints, Provenance, TypeOrVar},
+    module::ExposedByModule,
+};
 use roc_checkmate::with_checkmate;
 use roc_derive::SharedDerivedModule;
+use roc_region::all::Region;
 use roc_types::subs::{Content, Descriptor, Mark, OptVariable, Rank, Subs, Variable};
 use roc_unify::Env as UEnv;
 
@@ -31,6 +35,11 @@ pub struct InferenceEnv<'a> {
     pub derived_env: &'a DerivedEnv<'a>,
     pub subs: &'a mut Subs,
     pub pools: &'a mut Pools,
+    /// Breadcrumbs from the `Eq` constraints solved so far, used to explain type errors.
+    pub provenance: Vec<Provenance>,
+    /// The regions of the `Eq` constraints that introduced structured types, used to point at
+    /// the code responsible for each part of a circular type.
+    pub structure_origins: Vec<(Variable, Region)>,
     #[cfg(debug_assertions)]
     pub checkmate: Option<roc_checkmate::Collector>,
 }
@@ -99,6 +108,30 @@ impl<'a> InferenceEnv<'a> {
         var
     }
 
+    /// Remembers that the constraint at `region` introduced `var`, if `type_index` is a type
+    /// rather than a bare variable.
+    pub fn record_structure_origin(
+        &mut self,
+        type_index: TypeOrVar,
+        var: Variable,
+        region: Region,
+    ) {
+        if type_index.split().is_ok() {
+            self.structure_origins.push((var, region));
+        }
+    }
+
+    /// Finds the region of the earliest constraint that introduced a structured type now
+    /// unified with `var`.
+    pub fn structure_origin(&self, var: Variable) -> Option<Region> {
+        let root = self.subs.get_root_key_without_compacting(var);
+
+        self.structure_origins
+            .iter()
+            .find(|(origin, _)| self.subs.get_root_key_without_compacting(*origin) == root)
+            .map(|(_, region)| *region)
+    }
+
     /// Retrieves an environment for unification.
     pub fn uenv(&mut self) -> UEnv {
         with_checkmate!({
@@ -3,7 +3,7 @@ use crate::FunctionKind;
 use crate::{aliases::Aliases, solve};
 use roc_can::abilities::{AbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint, Constraints};
-use roc_can::expr::PendingDerives;
+use roc_can::expr::{IncompleteImpls, PendingDerives};
 use roc_can::module::{ExposedByModule, ResolvedImplementations, RigidVariables};
 use roc_collections::all::MutMap;
 use roc_collections::VecMap;
@@ -68,6 +68,9 @@ pub struct SolveConfig<'a> {
     /// Table of types introduced in this module that claim to derive an ability implementation.
     /// Due for checking and instantiation after the solver runs over the module.
     pub pending_derives: PendingDerives,
+    /// Abilities that types introduced in this module claim to implement, but leave members
+    /// out of. Reported after the solver runs over the module, with the missing members' types.
+    pub incomplete_impls: IncompleteImpls,
     /// Types exposed by other modules.
     /// Available for late instantiation of imports, lambda sets, or ability types.
     pub exposed_by_module: &'a ExposedByModule,
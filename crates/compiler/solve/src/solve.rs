@@ -1,6 +1,6 @@
 use crate::ability::{
-    resolve_ability_specialization, type_implementing_specialization, AbilityImplError,
-    CheckedDerives, ObligationCache, PendingDerivesTable, Resolved,
+    report_incomplete_impls, resolve_ability_specialization, type_implementing_specialization,
+    AbilityImplError, CheckedDerives, ObligationCache, PendingDerivesTable, Resolved,
 };
 use crate::deep_copy::deep_copy_var_in;
 use crate::env::{DerivedEnv, InferenceEnv};
@@ -14,22 +14,26 @@ use crate::Aliases;
 use bumpalo::Bump;
 use roc_can::abilities::{AbilitiesStore, MemberSpecializationInfo};
 use roc_can::constraint::Constraint::{self, *};
-use roc_can::constraint::{Cycle, LetConstraint, OpportunisticResolve};
+use roc_can::constraint::{Cycle, LetConstraint, OpportunisticResolve, Provenance};
 use roc_can::expected::{Expected, PExpected};
+use roc_collections::MutSet;
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::ROC_VERIFY_RIGID_LET_GENERALIZED;
 use roc_error_macros::internal_error;
+use roc_module::ident::Lowercase;
 use roc_module::symbol::Symbol;
 use roc_problem::can::CycleEntry;
 use roc_region::all::Loc;
-use roc_solve_problem::TypeError;
+use roc_solve_problem::{CycleLink, TypeCycle, TypeError};
 use roc_solve_schema::UnificationMode;
 use roc_types::subs::{
     self, Content, FlatType, GetSubsSlice, Mark, OptVariable, Rank, Subs, TagExt, UlsOfVar,
     Variable,
 };
-use roc_types::types::{Category, Polarity, Reason, RecordField, Type, TypeExtension, Types, Uls};
+use roc_types::types::{
+    Category, ErrorType, Polarity, Reason, RecordField, Type, TypeExtension, Types, Uls,
+};
 use roc_unify::unify::{
     unify, unify_introduced_ability_specialization, Obligated, SpecializationLsetCollector,
     Unified::*,
@@ -126,6 +130,7 @@ fn run_help(
         root_constraint,
         mut types,
         pending_derives,
+        incomplete_impls,
         exposed_by_module,
         derived_module,
         function_kind,
@@ -156,6 +161,8 @@ fn run_help(
         derived_env: &derived_env,
         subs,
         pools: &mut pools,
+        provenance: Vec::new(),
+        structure_origins: Vec::new(),
         #[cfg(debug_assertions)]
         checkmate: config.checkmate,
     };
@@ -188,6 +195,8 @@ fn run_help(
         &mut awaiting_specializations,
     );
 
+    report_incomplete_impls(env.subs, abilities_store, incomplete_impls, problems);
+
     RunSolveOutput {
         scope: state.scope,
         #[cfg(debug_assertions)]
@@ -476,6 +485,11 @@ fn solve(
             Eq(roc_can::constraint::Eq(type_index, expectation_index, category_index, region)) => {
                 let category = &env.constraints.categories[category_index.index()];
 
+                let provenance = env.constraints.provenance(*expectation_index);
+                if provenance != Provenance::None {
+                    env.provenance.push(provenance);
+                }
+
                 let actual = either_type_index_to_var(
                     env,
                     rank,
@@ -499,6 +513,9 @@ fn solve(
                     *expectation.get_type_ref(),
                 );
 
+                env.record_structure_origin(*type_index, actual, *region);
+                env.record_structure_origin(*expectation.get_type_ref(), expected, *region);
+
                 match unify(
                     &mut env.uenv(),
                     actual,
@@ -537,11 +554,18 @@ fn solve(
                     Failure(vars, actual_type, expected_type, _bad_impls) => {
                         env.introduce(rank, &vars);
 
+                        let provenance = provenance_chain(
+                            &env.provenance,
+                            category,
+                            expectation,
+                            &expected_type,
+                        );
                         let problem = TypeError::BadExpr(
                             *region,
                             category.clone(),
                             actual_type,
                             expectation.replace_ref(expected_type),
+                            provenance,
                         );
 
                         problems.push(problem);
@@ -658,6 +682,7 @@ fn solve(
                                     Category::Lookup(*symbol),
                                     actual_type,
                                     expectation.replace_ref(expected_type),
+                                    Vec::new(),
                                 );
 
                                 problems.push(problem);
@@ -712,6 +737,9 @@ fn solve(
                     *expectation.get_type_ref(),
                 );
 
+                env.record_structure_origin(*type_index, actual, *region);
+                env.record_structure_origin(*expectation.get_type_ref(), expected, *region);
+
                 let mode = match constraint {
                     PatternPresence(..) => UnificationMode::PRESENT,
                     _ => UnificationMode::EQ,
@@ -1153,6 +1181,7 @@ fn solve(
                                                 real_category,
                                                 actual_type,
                                                 expected.replace_ref(expected_type),
+                                                Vec::new(),
                                             )
                                         }
 
@@ -1398,6 +1427,46 @@ fn solve(
     state
 }
 
+/// When a number literal doesn't fit the type it's checked against, finds the breadcrumbs that
+/// explain where that type came from; e.g. the annotation on the function it's passed to.
+fn provenance_chain<T>(
+    breadcrumbs: &[Provenance],
+    category: &Category,
+    expectation: &Expected<T>,
+    expected_type: &ErrorType,
+) -> Vec<Provenance> {
+    let is_number_literal = matches!(category, Category::Int | Category::Num | Category::Frac);
+
+    // A type variable wasn't pinned down by anything, so there's no chain to show.
+    let is_variable = matches!(
+        expected_type,
+        ErrorType::FlexVar(_)
+            | ErrorType::RigidVar(_)
+            | ErrorType::FlexAbleVar(..)
+            | ErrorType::RigidAbleVar(..)
+    );
+
+    if !is_number_literal || is_variable {
+        return Vec::new();
+    }
+
+    match expectation {
+        Expected::ForReason(
+            Reason::FnArg {
+                name: Some(name), ..
+            },
+            _,
+            _,
+        ) => breadcrumbs
+            .iter()
+            .find(|breadcrumb| matches!(breadcrumb, Provenance::Annotation(symbol, _) if symbol == name))
+            .into_iter()
+            .copied()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 fn chase_alias_content(subs: &Subs, mut var: Variable) -> (Variable, &Content) {
     loop {
         match subs.get_content_without_compacting(var) {
@@ -1717,6 +1786,7 @@ fn check_ability_specialization(
                             Category::AbilityMemberSpecialization(ability_member),
                             actual_type,
                             Expected::ForReason(reason, expected_type, symbol_loc_var.region),
+                            Vec::new(),
                         );
 
                         problems.push(problem);
@@ -1741,6 +1811,7 @@ fn check_ability_specialization(
                     Category::AbilityMemberSpecialization(ability_member),
                     actual_type,
                     Expected::ForReason(reason, expected_type, symbol_loc_var.region),
+                    Vec::new(),
                 );
 
                 problems.push(problem);
@@ -1846,7 +1917,7 @@ fn check_for_infinite_type(
 ) {
     let var = loc_var.value;
 
-    'next_occurs_check: while let Err((_, chain)) = env.subs.occurs(var) {
+    'next_occurs_check: while let Err((recurring, chain)) = env.subs.occurs(var) {
         // walk the chain till we find a tag union or lambda set, starting from the variable that
         // occurred recursively, which is always at the end of the chain.
         for &var in chain.iter().rev() {
@@ -1877,25 +1948,132 @@ fn check_for_infinite_type(
             }
         }
 
-        circular_error(env.subs, problems, symbol, &loc_var);
+        circular_error(env, problems, symbol, &loc_var, recurring, &chain);
     }
 }
 
 fn circular_error(
-    subs: &mut Subs,
+    env: &mut InferenceEnv,
     problems: &mut Vec<TypeError>,
     symbol: Symbol,
     loc_var: &Loc<Variable>,
+    recurring: Variable,
+    chain: &[Variable],
 ) {
     let var = loc_var.value;
-    let error_type = subs.var_to_error_type(var, Polarity::OF_VALUE);
-    let problem = TypeError::CircularType(loc_var.region, symbol, error_type);
+    let error_type = env.subs.var_to_error_type(var, Polarity::OF_VALUE);
+    let cycle = type_cycle(env, recurring, chain);
+    let problem = TypeError::CircularType(loc_var.region, symbol, error_type, cycle);
 
-    subs.set_content(var, Content::Error);
+    env.subs.set_content(var, Content::Error);
 
     problems.push(problem);
 }
 
+/// Describes the cycle through `recurring` found by an occurs check.
+///
+/// The chain of an occurs check runs from the innermost variable out to the one that was
+/// checked, so the cycle is the part of the chain up to where `recurring` itself appears.
+fn type_cycle(env: &mut InferenceEnv, recurring: Variable, chain: &[Variable]) -> TypeCycle {
+    let cycle_len = chain
+        .iter()
+        .position(|&var| var == recurring)
+        .map_or(chain.len(), |index| index + 1);
+
+    let links = chain[..cycle_len]
+        .iter()
+        .rev()
+        .filter_map(|&var| {
+            let link = match env.subs.get_content_without_compacting(var) {
+                Content::Structure(FlatType::Apply(symbol, _)) | Content::Alias(symbol, ..) => {
+                    CycleLink::Type(*symbol)
+                }
+                Content::Structure(FlatType::Func(..)) => CycleLink::Function,
+                Content::Structure(FlatType::Record(..)) => CycleLink::Record,
+                Content::Structure(FlatType::Tuple(..)) => CycleLink::Tuple,
+                Content::Structure(
+                    FlatType::TagUnion(..)
+                    | FlatType::FunctionOrTagUnion(..)
+                    | FlatType::RecursiveTagUnion(..),
+                ) => CycleLink::TagUnion,
+                _ => return None,
+            };
+
+            Some((link, env.structure_origin(var)))
+        })
+        .collect();
+
+    let mut typ = env.subs.var_to_error_type(recurring, Polarity::OF_VALUE);
+
+    // Generated names like `#a` may be displayed as `a`, so they claim that name too.
+    let mut taken = MutSet::default();
+    visit_shown_types(&mut typ, &mut |typ| match typ {
+        ErrorType::Record(_, ext)
+        | ErrorType::Tuple(_, ext)
+        | ErrorType::TagUnion(_, ext, _)
+        | ErrorType::RecursiveTagUnion(_, _, ext, _) => ext.add_names(&mut taken),
+        ErrorType::FlexVar(_)
+        | ErrorType::RigidVar(_)
+        | ErrorType::FlexAbleVar(..)
+        | ErrorType::RigidAbleVar(..) => typ.add_names(&mut taken),
+        _ => {}
+    });
+    let displayed: MutSet<&str> = taken
+        .iter()
+        .map(|name| name.as_str().trim_start_matches('#'))
+        .collect();
+    let name: Lowercase = ('a'..='z')
+        .map(|letter| letter.to_string())
+        .chain((1..).map(|n| format!("a{n}")))
+        .find(|name| !displayed.contains(name.as_str()))
+        .unwrap()
+        .into();
+
+    // Write each place where the type refers back to itself, otherwise printed as `∞`, as `name`.
+    visit_shown_types(&mut typ, &mut |typ| {
+        if let ErrorType::Infinite = typ {
+            *typ = ErrorType::FlexVar(name.clone());
+        }
+    });
+
+    TypeCycle { name, typ, links }
+}
+
+/// Calls `f` on a type and on each type nested in it that error messages print, which leaves
+/// out closures and the definitions behind aliases.
+fn visit_shown_types(typ: &mut ErrorType, f: &mut impl FnMut(&mut ErrorType)) {
+    use ErrorType::*;
+
+    f(typ);
+
+    match typ {
+        Type(_, args) | Range(args) | Alias(_, args, _, _) => {
+            args.iter_mut().for_each(|arg| visit_shown_types(arg, f))
+        }
+        Record(fields, _) => fields
+            .iter_mut()
+            .for_each(|(_, field)| visit_shown_types(field.as_inner_mut(), f)),
+        Tuple(elems, _) => elems
+            .iter_mut()
+            .for_each(|(_, elem)| visit_shown_types(elem, f)),
+        TagUnion(tags, _, _) => tags
+            .iter_mut()
+            .flat_map(|(_, payload)| payload.iter_mut())
+            .for_each(|arg| visit_shown_types(arg, f)),
+        RecursiveTagUnion(rec, tags, _, _) => {
+            visit_shown_types(rec, f);
+            tags.iter_mut()
+                .flat_map(|(_, payload)| payload.iter_mut())
+                .for_each(|arg| visit_shown_types(arg, f));
+        }
+        Function(args, _closure, ret) => {
+            args.iter_mut().for_each(|arg| visit_shown_types(arg, f));
+            visit_shown_types(ret, f);
+        }
+        Infinite | FlexVar(_) | RigidVar(_) | FlexAbleVar(..) | RigidAbleVar(..) | Error => {}
+    }
+}
+
 /// Generalizes variables at the `young_rank`, which did not escape a let-binding
 /// into a lower scope.
 ///
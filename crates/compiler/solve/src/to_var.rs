@@ -822,6 +822,7 @@ pub(crate) fn type_to_var_help(
                             category,
                             actual_type,
                             Expected::NoExpectation(expected_type),
+                            std::vec::Vec::new(),
                         );
 
                         problems.push(problem);
@@ -385,7 +385,7 @@ mod solve_expr {
                 [[1], [[]]]
             "
             ),
-            "List <type mismatch>",
+            "List (List <type mismatch>)",
         );
     }
 
@@ -2621,7 +2621,7 @@ mod solve_expr {
                     _ -> r
                 "#
             ),
-            "<type mismatch>",
+            "Foo <type mismatch>",
         );
     }
 
@@ -2641,7 +2641,7 @@ mod solve_expr {
                     0 -> r
                    "
             ),
-            "<type mismatch>",
+            "{ x : Num <type mismatch> }",
         );
     }
 
@@ -2661,7 +2661,7 @@ mod solve_expr {
                     0 -> r
                    "
             ),
-            "<type mismatch>",
+            "[Ok <type mismatch>]#b",
         );
     }
 
@@ -3879,6 +3879,31 @@ mod solve_expr {
             "List elem, elem -> List elem",
         );
     }
+
+    #[test]
+    fn list_windows() {
+        infer_eq_without_problem(
+            indoc!(
+                r"
+                List.windows
+                "
+            ),
+            "List a, U64 -> List (List a)",
+        );
+    }
+
+    #[test]
+    fn list_group_by() {
+        infer_eq_without_problem(
+            indoc!(
+                r"
+                List.groupBy
+                "
+            ),
+            "List a, (a, a -> Bool) -> List (List a)",
+        );
+    }
+
     #[test]
     fn function_that_captures_nothing_is_not_captured() {
         // we should make sure that a function that doesn't capture anything it not itself captured
@@ -1,8 +1,12 @@
 //! Provides types to describe problems that can occur during solving.
 use std::{path::PathBuf, str::Utf8Error};
 
+use roc_can::constraint::Provenance;
 use roc_can::expected::{Expected, PExpected};
-use roc_module::{ident::Lowercase, symbol::Symbol};
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
 use roc_problem::{can::CycleEntry, Severity};
 use roc_region::all::Region;
 
@@ -10,9 +14,17 @@ use roc_types::types::{Category, ErrorType, PatternCategory};
 
 #[derive(Debug, Clone)]
 pub enum TypeError {
-    BadExpr(Region, Category, ErrorType, Expected<ErrorType>),
+    /// The last field is the chain of requirements that pinned down the expected type, if known.
+    BadExpr(
+        Region,
+        Category,
+        ErrorType,
+        Expected<ErrorType>,
+        Vec<Provenance>,
+    ),
     BadPattern(Region, PatternCategory, ErrorType, PExpected<ErrorType>),
-    CircularType(Region, Symbol, ErrorType),
+    /// The last field is the part of the type that refers back to itself.
+    CircularType(Region, Symbol, ErrorType, TypeCycle),
     CircularDef(Vec<CycleEntry>),
     UnexposedLookup(Region, Symbol),
     UnfulfilledAbility(Unfulfilled),
@@ -31,6 +43,13 @@ pub enum TypeError {
         expected_opaque: Symbol,
         found_opaque: Symbol,
     },
+    /// An opaque type claims to implement an ability, but leaves out some of its members.
+    /// Each missing member is paired with the type its implementation must have.
+    IncompleteAbilityImplementation {
+        region: Region,
+        ability: Symbol,
+        not_implemented: Vec<(Symbol, ErrorType)>,
+    },
     IngestedFileBadUtf8(Box<PathBuf>, Utf8Error),
     IngestedFileUnsupportedType(Box<PathBuf>, ErrorType),
 }
@@ -52,6 +71,7 @@ impl TypeError {
             TypeError::Exhaustive(exhtv) => exhtv.severity(),
             TypeError::StructuralSpecialization { .. } => RuntimeError,
             TypeError::WrongSpecialization { .. } => RuntimeError,
+            TypeError::IncompleteAbilityImplementation { .. } => RuntimeError,
             TypeError::IngestedFileBadUtf8(..) => Fatal,
             TypeError::IngestedFileUnsupportedType(..) => Fatal,
         }
@@ -66,6 +86,7 @@ impl TypeError {
             | TypeError::BadExprMissingAbility(region, ..)
             | TypeError::StructuralSpecialization { region, .. }
             | TypeError::WrongSpecialization { region, .. }
+            | TypeError::IncompleteAbilityImplementation { region, .. }
             | TypeError::BadPatternMissingAbility(region, ..) => Some(*region),
             TypeError::UnfulfilledAbility(ab, ..) => ab.region(),
             TypeError::Exhaustive(e) => Some(e.region()),
@@ -111,8 +132,41 @@ pub enum UnderivableReason {
     NotABuiltin,
     /// The surface type is not derivable
     SurfaceNotDerivable(NotDerivableContext),
-    /// A nested type is not derivable
-    NestedNotDerivable(ErrorType, NotDerivableContext),
+    /// A nested type is not derivable. The steps lead from the surface type down to it,
+    /// outermost first.
+    NestedNotDerivable(ErrorType, NotDerivableContext, Vec<TypePathStep>),
+}
+
+/// One step from a type into a type inside of it
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum TypePathStep {
+    RecordField(Lowercase),
+    TupleElem(usize),
+    TagPayload(TagName),
+    TypeArg(Symbol),
+    FunctionArg,
+    FunctionReturn,
+}
+
+/// The part of a circular type that refers back to itself, e.g. `a = List a`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct TypeCycle {
+    /// The name standing for the type wherever it refers back to itself
+    pub name: Lowercase,
+    pub typ: ErrorType,
+    /// The type constructors that form the cycle, outermost first, each paired with the
+    /// region of the code that introduced it, where known.
+    pub links: Vec<(CycleLink, Option<Region>)>,
+}
+
+/// One type constructor on the path around a circular type
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CycleLink {
+    Type(Symbol),
+    Function,
+    Record,
+    Tuple,
+    TagUnion,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
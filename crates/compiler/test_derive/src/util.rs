@@ -436,6 +436,7 @@ fn check_derived_typechecks_and_golden(
         types,
         function_kind: FunctionKind::LambdaSet,
         pending_derives: Default::default(),
+        incomplete_impls: Default::default(),
         exposed_by_module: &exposed_for_module.exposed_by_module,
         derived_module: Default::default(),
 
@@ -1045,6 +1045,80 @@ fn decode_use_stdlib_json_list() {
     )
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn json_format_encode() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [JsonFormat]
+                provides [main] to "./platform"
+
+            main =
+                value = { name: "a \"b\"\n", shapes: [Circle 1.5, Point], size: (2u8, Bool.true) }
+
+                Encode.toBytes value JsonFormat.utf8
+                |> Str.fromUtf8
+                |> Result.withDefault "<bad>"
+            "#
+        ),
+        RocStr::from(r#"{"name":"a \"b\"\n","shapes":[{"Circle":[1.5]},"Point"],"size":[2,true]}"#),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn json_format_decode_record() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [JsonFormat]
+                provides [main] to "./platform"
+
+            main =
+                input = Str.toUtf8 """ { "skipped": [1, {"x": null}], "b": [ 2, 3 ] , "a" : "\\u00e9\\ud83d\\ude00" } """
+
+                decoded : Result { a : Str, b : List U8 } _
+                decoded = Decode.fromBytesPartial input JsonFormat.utf8 |> .result
+
+                when decoded is
+                    Ok { a, b } -> Str.concat a (Num.toStr (List.sum b))
+                    Err _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("é😀5"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn json_format_round_trip() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [JsonFormat]
+                provides [main] to "./platform"
+
+            main =
+                original = { id: -7i64, tags: ["x\ty", ""], pair: (0.25dec, Bool.false) }
+
+                decoded =
+                    Encode.toBytes original JsonFormat.utf8
+                    |> Decode.fromBytes JsonFormat.utf8
+
+                decoded == Ok original
+            "#
+        ),
+        true,
+        bool
+    )
+}
+
 mod decode_immediate {
     #[cfg(feature = "gen-llvm")]
     use crate::helpers::llvm::assert_evals_to;
@@ -2322,4 +2396,55 @@ mod inspect {
             RocStr
         );
     }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn to_json_scalars() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+            app "test" provides [main] to "./platform"
+
+            main = [
+                Inspect.toJson Bool.true,
+                Inspect.toJson 42u8,
+                Inspect.toJson -7i64,
+                Inspect.toJson 1.5f64,
+                Inspect.toJson (Num.sqrt -1f64),
+                Inspect.toJson 3.3dec,
+                Inspect.toJson "say \"hi\"\n",
+            ] |> Str.joinWith " "
+            "#
+            ),
+            RocStr::from(r#"true 42 -7 1.5 null 3.3 "say \"hi\"\n""#),
+            RocStr
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn to_json_structures() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+            app "test" provides [main] to "./platform"
+
+            Op := {}
+
+            main = [
+                Inspect.toJson { name: "Roc", tags: [Fast, Friendly] },
+                Inspect.toJson (Ok 1u8),
+                Inspect.toJson ("a", 2u8),
+                Inspect.toJson (Dict.fromList [(1u8, "one")]),
+                Inspect.toJson (Set.fromList [1u8]),
+                Inspect.toJson (@Op {}),
+            ] |> Str.joinWith " "
+            "#
+            ),
+            RocStr::from(
+                r#"{"name":"Roc","tags":[{"tag":"Fast","payload":[]},{"tag":"Friendly","payload":[]}]} {"tag":"Ok","payload":[1]} ["a",2] [[1,"one"]] [1] "<opaque>""#
+            ),
+            RocStr
+        );
+    }
 }
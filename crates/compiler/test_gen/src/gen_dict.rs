@@ -550,3 +550,82 @@ fn walk_sum_keys() {
         i64
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn map_keeps_keys() {
+    assert_evals_to!(
+        indoc!(
+            r"
+            dict1 : Dict.Dict I64 I64
+            dict1 =
+                Dict.empty {}
+                    |> Dict.insert 1 10
+                    |> Dict.insert 2 20
+                    |> Dict.insert 3 30
+
+            mapped = Dict.map dict1 \k, v -> Num.toStr (k + v)
+
+            when Dict.get mapped 3 is
+                Ok value -> List.append (Dict.values mapped) value
+                Err KeyNotFound -> []
+            "
+        ),
+        RocList::from_slice(&[
+            RocStr::from("11"),
+            RocStr::from("22"),
+            RocStr::from("33"),
+            RocStr::from("33"),
+        ]),
+        RocList<RocStr>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn join_map() {
+    assert_evals_to!(
+        indoc!(
+            r"
+            dict1 : Dict.Dict I64 I64
+            dict1 =
+                Dict.empty {}
+                    |> Dict.insert 1 1
+                    |> Dict.insert 2 2
+
+            Dict.joinMap dict1 (\k, v -> Dict.empty {} |> Dict.insert k v |> Dict.insert (k * 10) v)
+                |> Dict.keys
+            "
+        ),
+        RocList::from_slice(&[1, 10, 2, 20]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn keep_if_then_get() {
+    assert_evals_to!(
+        indoc!(
+            r"
+            dict1 : Dict.Dict I64 I64
+            dict1 =
+                Dict.empty {}
+                    |> Dict.insert 1 1
+                    |> Dict.insert 2 2
+                    |> Dict.insert 3 3
+                    |> Dict.insert 4 4
+
+            kept = Dict.keepIf dict1 \(k, _) -> Num.isEven k
+
+            [
+                Dict.len kept,
+                Dict.get kept 4 |> Result.withDefault 0 |> Num.toU64,
+                Dict.get kept 3 |> Result.withDefault 0 |> Num.toU64,
+            ]
+            "
+        ),
+        RocList::from_slice(&[2, 4, 0]),
+        RocList<u64>
+    );
+}
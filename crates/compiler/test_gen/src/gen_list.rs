@@ -519,6 +519,67 @@ fn list_chunks_of() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn list_windows() {
+    assert_evals_to!(
+        "List.windows [1, 2, 3, 4] 2",
+        RocList::<RocList<i64>>::from_slice(&[
+            RocList::from_slice(&[1, 2]),
+            RocList::from_slice(&[2, 3]),
+            RocList::from_slice(&[3, 4]),
+        ]),
+        RocList<RocList<i64>>
+    );
+
+    assert_evals_to!(
+        "List.windows [1, 2, 3] 3",
+        RocList::<RocList<i64>>::from_slice(&[RocList::from_slice(&[1, 2, 3])]),
+        RocList<RocList<i64>>
+    );
+
+    assert_evals_to!(
+        "List.windows [1, 2, 3] 4",
+        RocList::<RocList<i64>>::from_slice(&[]),
+        RocList<RocList<i64>>
+    );
+
+    assert_evals_to!(
+        "List.windows [1, 2, 3] 0",
+        RocList::<RocList<i64>>::from_slice(&[]),
+        RocList<RocList<i64>>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn list_group_by() {
+    assert_evals_to!(
+        r"List.groupBy [1, 1, 2, 3, 3, 3] (\a, b -> a == b)",
+        RocList::<RocList<i64>>::from_slice(&[
+            RocList::from_slice(&[1, 1]),
+            RocList::from_slice(&[2]),
+            RocList::from_slice(&[3, 3, 3]),
+        ]),
+        RocList<RocList<i64>>
+    );
+
+    assert_evals_to!(
+        r"List.groupBy [1, 2, 3, 1, 2] (\a, b -> a < b)",
+        RocList::<RocList<i64>>::from_slice(&[
+            RocList::from_slice(&[1, 2, 3]),
+            RocList::from_slice(&[1, 2]),
+        ]),
+        RocList<RocList<i64>>
+    );
+
+    assert_evals_to!(
+        r"List.groupBy [] (\a, b -> a == b)",
+        RocList::<RocList<i64>>::from_slice(&[]),
+        RocList<RocList<i64>>
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn list_drop_first() {
@@ -0,0 +1,76 @@
+//! Tests that feed procs written in the mono text format (see `roc_mono::ir::text`) straight
+//! into a backend, without running the frontend.
+#![cfg(feature = "gen-dev")]
+
+use crate::helpers::dev::assert_mono_text_evals_to;
+
+use indoc::indoc;
+
+#[test]
+fn lowlevel_add() {
+    assert_mono_text_evals_to!(
+        indoc!(
+            r"
+            proc `Test.main`() -> I64 {
+                let `Test.1`: I64 = int 40;
+                let `Test.2`: I64 = int 2;
+                let `Test.3`: I64 = lowlevel NumAdd(`Test.1`, `Test.2`);
+                ret `Test.3`;
+            }
+            "
+        ),
+        42,
+        i64
+    );
+}
+
+#[test]
+fn switch_and_join() {
+    assert_mono_text_evals_to!(
+        indoc!(
+            r"
+            proc `Test.main`() -> I64 {
+                let `Test.b`: Bool = bool false;
+                join `Test.j`(`Test.r`: I64) {
+                    ret `Test.r`;
+                }
+                switch `Test.b`: Bool -> I64 {
+                    case 1 {
+                        let `Test.1`: I64 = int 1;
+                        jump `Test.j`(`Test.1`);
+                    }
+                    default {
+                        let `Test.2`: I64 = int 2;
+                        jump `Test.j`(`Test.2`);
+                    }
+                }
+            }
+            "
+        ),
+        2,
+        i64
+    );
+}
+
+#[test]
+fn call_closure() {
+    assert_mono_text_evals_to!(
+        indoc!(
+            r"
+            proc `Test.add`(`Test.x`: I64, `Test.n`: Lambda((I64) -> I64, [`Test.add`[I64]], I64)) -> I64 niche[I64] {
+                let `Test.r`: I64 = lowlevel NumAdd(`Test.x`, `Test.n`);
+                ret `Test.r`;
+            }
+
+            proc `Test.main`() -> I64 {
+                let `Test.n`: I64 = int 40;
+                let `Test.x`: I64 = int 2;
+                let `Test.r`: I64 = call `Test.add` niche[I64](`Test.x`, `Test.n`) (I64, Lambda((I64) -> I64, [`Test.add`[I64]], I64)) -> I64;
+                ret `Test.r`;
+            }
+            "
+        ),
+        42,
+        i64
+    );
+}
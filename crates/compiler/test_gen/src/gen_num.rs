@@ -467,6 +467,31 @@ fn f32_float_alias() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn f32_kept_across_a_call() {
+    // `a` is still needed after the call, so it's saved on the stack while the call happens
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            addHalf : F32 -> F32
+            addHalf = \x -> x + 0.5f32
+
+            main : F32
+            main =
+                a = 1.25f32
+                b = addHalf a
+
+                a + b
+            "#
+        ),
+        3.0,
+        f32
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
 fn f64_sqrt_100() {
@@ -567,16 +592,12 @@ fn various_sized_abs() {
     assert_evals_to!("Num.abs -6i16", 6, i16);
     assert_evals_to!("Num.abs -6i32", 6, i32);
     assert_evals_to!("Num.abs -6i64", 6, i64);
-    if !cfg!(feature = "gen-wasm") {
-        assert_evals_to!("Num.abs -6i128", I128::from(6), I128);
-    }
+    assert_evals_to!("Num.abs -6i128", I128::from(6), I128);
     assert_evals_to!("Num.abs 6u8", 6, u8);
     assert_evals_to!("Num.abs 6u16", 6, u16);
     assert_evals_to!("Num.abs 6u32", 6, u32);
     assert_evals_to!("Num.abs 6u64", 6, u64);
-    if !cfg!(feature = "gen-wasm") {
-        assert_evals_to!("Num.abs 6u128", U128::from(6), U128);
-    }
+    assert_evals_to!("Num.abs 6u128", U128::from(6), U128);
 }
 
 #[test]
@@ -596,6 +617,15 @@ fn abs_min_int_overflow() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[should_panic(
+    expected = r#"Roc failed with message: "Integer absolute overflowed because its argument is the minimum value"#
+)]
+fn abs_min_i128_overflow() {
+    assert_evals_to!("Num.abs Num.minI128", I128::from(0), I128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
 fn gen_if_fn() {
@@ -1194,6 +1224,22 @@ fn gen_rem_div_by_zero_i64() {
     assert_evals_to!("Num.rem 42 0", 100, i64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn gen_div_rem_128() {
+    assert_evals_to!(
+        "Num.divTrunc Num.maxU128 (Num.shiftLeftBy 1u128 64)",
+        U128::from(u64::MAX as u128),
+        U128
+    );
+    assert_evals_to!(
+        "Num.rem (Num.shiftLeftBy 1i128 100 + 7) -10",
+        I128::from(((1i128 << 100) + 7) % -10),
+        I128
+    );
+    assert_evals_to!("Num.rem 4_000_000_000u32 7", 4_000_000_000u32 % 7, u32);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn gen_rem_checked_i64() {
@@ -1345,6 +1391,26 @@ fn bitwise_or() {
     assert_evals_to!("Num.bitwiseOr 1 2", 3, i64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn bitwise_128() {
+    assert_evals_to!(
+        "Num.bitwiseAnd (Num.shiftLeftBy 3i128 70) (Num.shiftLeftBy 1i128 71)",
+        I128::from(1i128 << 71),
+        I128
+    );
+    assert_evals_to!(
+        "Num.bitwiseOr (Num.shiftLeftBy 1u128 100) 1",
+        U128::from((1u128 << 100) | 1),
+        U128
+    );
+    assert_evals_to!(
+        "Num.bitwiseXor Num.maxI128 Num.minI128",
+        I128::from(-1),
+        I128
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn lt_u8() {
@@ -1641,6 +1707,23 @@ fn neg_min_int_overflow() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_negate() {
+    assert_evals_to!("Num.neg 123i128", I128::from(-123), I128);
+    assert_evals_to!("Num.neg -123i128", I128::from(123), I128);
+    assert_evals_to!("Num.neg Num.maxI128", I128::from(-i128::MAX), I128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[should_panic(
+    expected = r#"Roc failed with message: "Integer negation overflowed because its argument is the minimum value"#
+)]
+fn neg_min_i128_overflow() {
+    assert_evals_to!("Num.neg Num.minI128", I128::from(0), I128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn gen_wrap_int_neg() {
@@ -1782,6 +1865,45 @@ fn float_to_float() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn dec_to_float() {
+    assert_evals_to!("Num.toF64 1.5dec", 1.5, f64);
+    assert_evals_to!("Num.toF64 -0.25dec", -0.25, f64);
+    assert_evals_to!("Num.toF32 1.5dec", 1.5, f32);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn to_float_checked() {
+    assert_evals_to!("Num.toF32Checked 1.5f64", RocResult::ok(1.5), RocResult<f32, ()>);
+    assert_evals_to!("Num.toF32Checked 15i128", RocResult::ok(15.0), RocResult<f32, ()>);
+    assert_evals_to!("Num.toF64Checked 1.5dec", RocResult::ok(1.5), RocResult<f64, ()>);
+    assert_evals_to!(
+        "Num.toF32Checked Num.maxF64",
+        RocResult::err(()),
+        RocResult<f32, ()>
+    );
+    assert_evals_to!(
+        "Num.toF32Checked Num.maxU128",
+        RocResult::err(()),
+        RocResult<f32, ()>
+    );
+    assert_evals_to!(
+        "Num.toF32Checked Num.infinityF64",
+        RocResult::ok(f32::INFINITY),
+        RocResult<f32, ()>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn int_cast_sign_extends_to_i128() {
+    assert_evals_to!("Num.toI128 -1i64", I128::from(-1), I128);
+    assert_evals_to!("Num.toI128 -15i32", I128::from(-15), I128);
+    assert_evals_to!("Num.toI128 Num.maxU64", I128::from(u64::MAX as i128), I128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn frac_is_nan() {
@@ -1895,6 +2017,12 @@ fn pow_int() {
     assert_evals_to!("Num.powInt 2 3", 8, i64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn pow_int_i128() {
+    assert_evals_to!("Num.powInt 2i128 100", I128::from(1i128 << 100), I128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn atan() {
@@ -1928,6 +2056,54 @@ fn int_add_checked_err() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn u64_add_checked() {
+    assert_evals_to!(
+        "Num.addChecked 18_446_744_073_709_551_614u64 1",
+        RocResult::ok(u64::MAX),
+        RocResult<u64, ()>
+    );
+
+    assert_evals_to!(
+        "Num.addChecked 18_446_744_073_709_551_615u64 1",
+        RocResult::err(()),
+        RocResult<u64, ()>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn u64_sub_checked() {
+    assert_evals_to!(
+        "Num.subChecked 5u64 5",
+        RocResult::ok(0),
+        RocResult<u64, ()>
+    );
+
+    assert_evals_to!(
+        "Num.subChecked 5u64 6",
+        RocResult::err(()),
+        RocResult<u64, ()>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn int_mul_checked_negative() {
+    assert_evals_to!(
+        "Num.mulChecked Num.minI64 1",
+        RocResult::ok(i64::MIN),
+        RocResult<i64, ()>
+    );
+
+    assert_evals_to!(
+        "Num.mulChecked Num.minI64 (-1)",
+        RocResult::err(()),
+        RocResult<i64, ()>
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn int_add_wrap() {
@@ -2148,6 +2324,13 @@ fn shift_right_by() {
     }
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn shift_by_i128() {
+    assert_evals_to!("Num.shiftLeftBy 1i128 100", I128::from(1i128 << 100), I128);
+    assert_evals_to!("Num.shiftRightBy Num.minI128 126", I128::from(-2), I128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn shift_right_zf_by() {
@@ -2362,12 +2545,12 @@ num_conversion_tests! {
         to_i64_sign_extend_i8, "-15i8", -15, ["gen-wasm", "gen-dev"]
         to_i64_sign_extend_i16, "-15i16", -15, ["gen-wasm", "gen-dev"]
         to_i64_sign_extend_i32, "-15i32", -15, ["gen-wasm", "gen-dev"]
-        to_i64_truncate, "115i128", 115
-        to_i64_truncate_wraps, "10_000_000_000_000_000_000i128", -8446744073709551616
+        to_i64_truncate, "115i128", 115, ["gen-wasm"]
+        to_i64_truncate_wraps, "10_000_000_000_000_000_000i128", -8446744073709551616, ["gen-wasm"]
     )
     "Num.toI128", i128, (
-        to_i128_same_width, "15u128", 15, ["gen-dev"]
-        to_i128_extend, "15i8", 15
+        to_i128_same_width, "15u128", 15, ["gen-wasm", "gen-dev"]
+        to_i128_extend, "15i8", 15, ["gen-wasm"]
     )
     "Num.toU8", u8, (
         to_u8_same_width, "15i8", 15, ["gen-wasm", "gen-dev"]
@@ -2389,25 +2572,25 @@ num_conversion_tests! {
     "Num.toU64", u64, (
         to_u64_same_width, "15i64", 15, ["gen-wasm", "gen-dev"]
         to_u64_extend, "15i8", 15, ["gen-wasm", "gen-dev"]
-        to_u64_truncate, "115i128", 115
-        to_u64_truncate_wraps, "10_000_000_000_000_000_000_000i128", 1864712049423024128
+        to_u64_truncate, "115i128", 115, ["gen-wasm"]
+        to_u64_truncate_wraps, "10_000_000_000_000_000_000_000i128", 1864712049423024128, ["gen-wasm"]
     )
     "Num.toU128", u128, (
-        to_u128_same_width, "15i128", 15, ["gen-dev"]
-        to_u128_extend, "15i8", 15
-        to_u128_big, "11562537357600483583u64", 11562537357600483583, ["gen-dev"]
+        to_u128_same_width, "15i128", 15, ["gen-wasm", "gen-dev"]
+        to_u128_extend, "15i8", 15, ["gen-wasm"]
+        to_u128_big, "11562537357600483583u64", 11562537357600483583, ["gen-wasm", "gen-dev"]
     )
     "Num.toF32", f32, (
         to_f32_from_i8, "15i8", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_i16, "15i16", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_i32, "15i32", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_i64, "15i64", 15.0, ["gen-wasm", "gen-dev"]
-        to_f32_from_i128, "15i128", 15.0, ["gen-dev"]
+        to_f32_from_i128, "15i128", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_u8, "15u8", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_u16, "15u16", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_u32, "15u32", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_u64, "15u64", 15.0, ["gen-wasm", "gen-dev"]
-        to_f32_from_u128, "15u128", 15.0, ["gen-dev"]
+        to_f32_from_u128, "15u128", 15.0, ["gen-wasm", "gen-dev"]
         to_f32_from_f32, "1.5f32", 1.5, ["gen-wasm", "gen-dev"]
         to_f32_from_f64, "1.5f64", 1.5, ["gen-wasm", "gen-dev"]
     )
@@ -2416,13 +2599,13 @@ num_conversion_tests! {
         to_f64_from_i16, "15i16", 15.0, ["gen-wasm", "gen-dev"]
         to_f64_from_i32, "15i32", 15.0, ["gen-wasm", "gen-dev"]
         to_f64_from_i64, "15i64", 15.0, ["gen-wasm", "gen-dev"]
-        to_f64_from_i128, "15i128", 15.0, ["gen-dev"]
+        to_f64_from_i128, "15i128", 15.0, ["gen-wasm", "gen-dev"]
         to_f64_from_u8, "15u8", 15.0, ["gen-wasm", "gen-dev"]
         to_f64_from_u16, "15u16", 15.0, ["gen-wasm", "gen-dev"]
         to_f64_from_u32, "15u32", 15.0, ["gen-wasm", "gen-dev"]
         to_f64_from_u64, "15u64", 15.0, ["gen-wasm", "gen-dev"]
-        to_f64_from_u128, "15u128", 15.0, ["gen-dev"]
-        to_f64_from_f32, "1.5f32", 1.5, ["gen-dev"]
+        to_f64_from_u128, "15u128", 15.0, ["gen-wasm", "gen-dev"]
+        to_f64_from_f32, "1.5f32", 1.5, ["gen-wasm", "gen-dev"]
         to_f64_from_f64, "1.5f64", 1.5, ["gen-wasm", "gen-dev"]
     )
 }
@@ -2914,6 +3097,80 @@ fn num_to_str_dec() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn num_to_str_with_int() {
+    use roc_std::RocStr;
+
+    assert_evals_to!(r"Num.toStrWith 1234i64 {}", RocStr::from("1234"), RocStr);
+    assert_evals_to!(
+        r#"Num.toStrWith -1234567i64 { separator: "," }"#,
+        RocStr::from("-1,234,567"),
+        RocStr
+    );
+    assert_evals_to!(
+        r"Num.toStrWith 255u8 { base: 16 }",
+        RocStr::from("ff"),
+        RocStr
+    );
+    assert_evals_to!(
+        r#"Num.toStrWith 0xdeadbeefu32 { base: 16, separator: "_" }"#,
+        RocStr::from("dead_beef"),
+        RocStr
+    );
+    assert_evals_to!(
+        r"Num.toStrWith 5u8 { base: 2 }",
+        RocStr::from("101"),
+        RocStr
+    );
+    assert_evals_to!(
+        r"Num.toStrWith 42i64 { decimals: Exactly 2 }",
+        RocStr::from("42.00"),
+        RocStr
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn num_to_str_with_f64() {
+    use roc_std::RocStr;
+
+    assert_evals_to!(r"Num.toStrWith 0.1f64 {}", RocStr::from("0.1"), RocStr);
+    assert_evals_to!(
+        r"Num.toStrWith 3.14159f64 { decimals: Exactly 2 }",
+        RocStr::from("3.14"),
+        RocStr
+    );
+    assert_evals_to!(
+        r#"Num.toStrWith 1234567.6f64 { decimals: Exactly 0, separator: "," }"#,
+        RocStr::from("1,234,568"),
+        RocStr
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn num_to_str_with_dec() {
+    use roc_std::RocStr;
+
+    assert_evals_to!(r"Num.toStrWith 10.75dec {}", RocStr::from("10.75"), RocStr);
+    assert_evals_to!(
+        r#"Num.toStrWith -1234.565dec { decimals: Exactly 2, separator: "," }"#,
+        RocStr::from("-1,234.57"),
+        RocStr
+    );
+    assert_evals_to!(
+        r"Num.toStrWith 1.5dec { decimals: Exactly 0 }",
+        RocStr::from("2"),
+        RocStr
+    );
+    assert_evals_to!(
+        r"Num.toStrWith 0.25dec { decimals: Exactly 4 }",
+        RocStr::from("0.2500"),
+        RocStr
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn u8_addition_greater_than_i8() {
@@ -3391,6 +3648,44 @@ fn round_to_u32() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn round_ceiling_floor_to_i128() {
+    assert_evals_to!(
+        indoc!(
+            r"
+            n : I128
+            n = Num.ceiling 124.5f64
+            n
+            "
+        ),
+        I128::from(125),
+        I128
+    );
+    assert_evals_to!(
+        indoc!(
+            r"
+            n : I128
+            n = Num.floor -124.5f32
+            n
+            "
+        ),
+        I128::from(-125),
+        I128
+    );
+    assert_evals_to!(
+        indoc!(
+            r"
+            n : U128
+            n = Num.round 124.5f64
+            n
+            "
+        ),
+        U128::from(125),
+        U128
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn promote_u64_number_layout() {
@@ -4664,3 +4664,35 @@ fn multiple_uses_of_bool_true_tag_union() {
         bool
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn stack_frame_bigger_than_immediate_offsets() {
+    // All of these stay alive until the end, so the stack frame gets too big for the offsets
+    // that fit in the immediate of a load or store, which the dev backends have to handle.
+    const VALUES: u64 = 5000;
+
+    let defs: String = (0..VALUES)
+        .map(|i| format!("    x{i} = n + {i}\n"))
+        .collect();
+    let names: Vec<String> = (0..VALUES).map(|i| format!("x{i}")).collect();
+
+    let source = format!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            sumMany : U64 -> U64
+            sumMany = \n ->
+            {}    List.sum [{}]
+
+            main : U64
+            main = sumMany 1
+            "#
+        ),
+        defs,
+        names.join(", ")
+    );
+
+    assert_evals_to!(&source, VALUES + VALUES * (VALUES - 1) / 2, u64);
+}
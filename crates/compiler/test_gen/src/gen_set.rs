@@ -1,5 +1,5 @@
 #![cfg(all(
-    feature = "gen-llvm",
+    any(feature = "gen-llvm", feature = "gen-wasm"),
     not(debug_assertions) // https://github.com/roc-lang/roc/issues/3898
 ))]
 
@@ -9,14 +9,14 @@ use crate::helpers::llvm::assert_evals_to;
 // #[cfg(feature = "gen-dev")]
 // use crate::helpers::dev::assert_evals_to;
 
-// #[cfg(feature = "gen-wasm")]
-// use crate::helpers::wasm::assert_evals_to;
+#[cfg(feature = "gen-wasm")]
+use crate::helpers::wasm::assert_evals_to;
 
 use indoc::indoc;
 use roc_std::RocList;
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn empty_len() {
     assert_evals_to!(
         indoc!(
@@ -30,7 +30,7 @@ fn empty_len() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn single_len() {
     assert_evals_to!(
         indoc!(
@@ -44,7 +44,7 @@ fn single_len() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn single_to_list() {
     assert_evals_to!(
         indoc!(
@@ -68,7 +68,7 @@ fn single_to_list() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn insert() {
     assert_evals_to!(
         indoc!(
@@ -86,7 +86,7 @@ fn insert() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn remove() {
     assert_evals_to!(
         indoc!(
@@ -105,7 +105,7 @@ fn remove() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn union() {
     assert_evals_to!(
         indoc!(
@@ -126,7 +126,7 @@ fn union() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn difference() {
     assert_evals_to!(
         indoc!(
@@ -147,7 +147,7 @@ fn difference() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn intersection() {
     assert_evals_to!(
         indoc!(
@@ -168,7 +168,7 @@ fn intersection() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn walk_sum() {
     assert_evals_to!(
         indoc!(
@@ -182,7 +182,7 @@ fn walk_sum() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn contains() {
     assert_evals_to!(
         indoc!(
@@ -206,7 +206,7 @@ fn contains() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn from_list() {
     assert_evals_to!(
         indoc!(
@@ -254,7 +254,7 @@ fn from_list_void() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn to_list_empty() {
     assert_evals_to!(
         indoc!(
@@ -268,7 +268,7 @@ fn to_list_empty() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn from_list_result() {
     assert_evals_to!(
         indoc!(
@@ -289,7 +289,7 @@ fn from_list_result() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn resolve_set_eq_issue_4671() {
     assert_evals_to!(
         indoc!(
@@ -1721,6 +1721,54 @@ fn str_walk_utf8_with_index() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn str_graphemes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Str.graphemes "e\u(301)👩‍👩‍👦‍👦\r\n🇺🇸🇫🇷"
+            "#
+        ),
+        RocList::from_slice(&[
+            RocStr::from("e\u{301}"),
+            RocStr::from("👩‍👩‍👦‍👦"),
+            RocStr::from("\r\n"),
+            RocStr::from("🇺🇸"),
+            RocStr::from("🇫🇷"),
+        ]),
+        RocList<RocStr>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn str_count_graphemes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Str.countGraphemes "한국어 👍🏽"
+            "#
+        ),
+        5,
+        u64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn str_walk_graphemes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Str.walkGraphemes "ab👍🏽" [] (\list, grapheme -> List.prepend list (Str.countUtf8Bytes grapheme))
+            "#
+        ),
+        RocList::from_slice(&[8, 1, 1]),
+        RocList<u64>
+    );
+}
+
 #[test]
 #[cfg(feature = "gen-llvm-wasm")]
 fn llvm_wasm_str_layout() {
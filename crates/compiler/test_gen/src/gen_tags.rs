@@ -2327,3 +2327,42 @@ fn recursive_tag_id_in_allocation_eq() {
         bool
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn tag_union_and_strings_passed_on_the_stack() {
+    // `describe` takes more arguments than there are argument registers, and a tag union too
+    // big to pass in registers; the closure given to List.map captures `scale`.
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            Shape : [Circle I64, Rect I64 I64, Label Str]
+
+            area : Shape -> I64
+            area = \shape ->
+                when shape is
+                    Circle r -> 3 * r * r
+                    Rect w h -> w * h
+                    Label _ -> 0
+
+            describe : Str, Str, Str, Str, Str, Str, Str, Str, Str, Shape -> Str
+            describe = \a, b, c, d, e, f, g, h, i, shape ->
+                Str.joinWith [a, b, c, d, e, f, g, h, i, Num.toStr (area shape)] ","
+
+            main : Str
+            main =
+                scale = 2
+                shapes = [Circle 1, Rect 2 3, Label "long enough not to be a small string"]
+                areas = List.map shapes (\shape -> area shape * scale)
+
+                describe "a" "b" "c" "d" "e" "f" "g" "h" "i" (Rect 4 5)
+                |> Str.concat ";"
+                |> Str.concat (Num.toStr (List.sum areas))
+            "#
+        ),
+        RocStr::from("a,b,c,d,e,f,g,h,i,20;18"),
+        RocStr
+    );
+}
@@ -1,8 +1,12 @@
 use libloading::Library;
 use roc_build::link::{link, LinkType};
+use roc_collections::all::{MutMap, MutSet};
 use roc_load::{EntryPoint, ExecutionMode, LoadConfig, Threading};
+use roc_module::ident::ModuleName;
+use roc_module::symbol::{IdentIds, Interns, ModuleId, ModuleIds, Symbol};
 use roc_mono::ir::CrashTag;
-use roc_mono::ir::SingleEntryPoint;
+use roc_mono::ir::{Proc, ProcLayout, SingleEntryPoint};
+use roc_mono::layout::STLayoutInterner;
 use roc_packaging::cache::RocCacheDir;
 use roc_region::all::LineInfo;
 use roc_solve::FunctionKind;
@@ -10,9 +14,6 @@ use roc_std::RocStr;
 use std::mem::MaybeUninit;
 use tempfile::tempdir;
 
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
-use roc_collections::all::MutMap;
-
 #[allow(unused_imports)]
 use roc_mono::ir::pretty_print_ir_symbols;
 
@@ -39,10 +40,8 @@ pub fn helper(
 ) -> (String, Vec<roc_problem::can::Problem>, Library) {
     use std::path::PathBuf;
 
-    let dir = tempdir().unwrap();
     let filename = PathBuf::from("Test.roc");
     let src_dir = PathBuf::from("fake/test/path");
-    let app_o_file = dir.path().join("app.o");
 
     let module_src;
     let temp;
@@ -62,6 +61,8 @@ pub fn helper(
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
         function_kind: FunctionKind::LambdaSet,
+        reproducible: false,
+        coverage: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -112,6 +113,7 @@ pub fn helper(
         EntryPoint::Executable {
             exposed_to_host,
             platform_path: _,
+            app_name: _,
         } => {
             // TODO support multiple of these!
             debug_assert_eq!(exposed_to_host.len(), 1);
@@ -189,17 +191,88 @@ pub fn helper(
         assert_eq!(0, 1, "Mistakes were made");
     }
 
+    let lib = build_and_link(
+        arena,
+        module_id,
+        &mut interns,
+        &mut layout_interner,
+        exposed_to_host.top_level_values.keys().copied().collect(),
+        procedures,
+        lazy_literals,
+    );
+
+    (main_fn_name, delayed_errors, lib)
+}
+
+/// Compiles procs that are written in the mono text format, without running the frontend.
+/// The proc named `Test.main` is the one that gets exposed to the test.
+#[allow(dead_code)]
+pub fn mono_text_helper(arena: &bumpalo::Bump, text: &str, lazy_literals: bool) -> Library {
+    let mut interns = Interns {
+        module_ids: ModuleIds::default(),
+        all_ident_ids: IdentIds::exposed_builtins(1),
+    };
+    let module_id = interns.module_id(&ModuleName::from("Test"));
+    let main_ident = interns
+        .all_ident_ids
+        .get_or_insert(module_id)
+        .get_or_insert("main");
+    let main_fn_symbol = Symbol::new(module_id, main_ident);
+
+    let target: roc_target::Target = target_lexicon::Triple::host().into();
+    let mut layout_interner = STLayoutInterner::with_capacity(64, target);
+
+    let procedures = roc_mono::ir::parse_mono_procedures(
+        arena,
+        &mut layout_interner,
+        &mut interns,
+        module_id,
+        text,
+    )
+    .unwrap_or_else(|error| panic!("failed to parse mono text at {error}"));
+
+    assert!(
+        procedures
+            .keys()
+            .any(|(symbol, _)| *symbol == main_fn_symbol),
+        "the mono text has no `Test.main` proc"
+    );
+
+    build_and_link(
+        arena,
+        module_id,
+        &mut interns,
+        &mut layout_interner,
+        [main_fn_symbol].into_iter().collect(),
+        procedures,
+        lazy_literals,
+    )
+}
+
+fn build_and_link<'a>(
+    arena: &'a bumpalo::Bump,
+    module_id: ModuleId,
+    interns: &mut Interns,
+    layout_interner: &mut STLayoutInterner<'a>,
+    exposed_to_host: MutSet<Symbol>,
+    procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+    lazy_literals: bool,
+) -> Library {
+    let dir = tempdir().unwrap();
+    let app_o_file = dir.path().join("app.o");
+
     let env = roc_gen_dev::Env {
         arena,
         module_id,
-        exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
+        exposed_to_host,
         lazy_literals,
         mode: roc_gen_dev::AssemblyBackendMode::Test,
+        debug_locations: Default::default(),
     };
 
     let target = target_lexicon::Triple::host().into();
     let module_object =
-        roc_gen_dev::build_module(&env, &mut interns, &mut layout_interner, target, procedures);
+        roc_gen_dev::build_module(&env, interns, layout_interner, target, procedures);
 
     let module_out = module_object
         .write()
@@ -233,9 +306,7 @@ pub fn helper(
 
     // std::fs::copy(&path, "/tmp/libapp.so").unwrap();
 
-    let lib = unsafe { Library::new(path) }.expect("failed to load shared library");
-
-    (main_fn_name, delayed_errors, lib)
+    unsafe { Library::new(path) }.expect("failed to load shared library")
 }
 
 #[derive(Debug)]
@@ -362,6 +433,29 @@ pub(crate) fn asm_evals_to<T, U, F>(
     }
 }
 
+// only used in tests
+pub(crate) fn mono_text_evals_to<T, U, F>(text: &str, expected: U, transform: F)
+where
+    U: PartialEq + std::fmt::Debug,
+    F: FnOnce(T) -> U,
+{
+    use bumpalo::Bump;
+
+    let arena = Bump::new();
+    let lib = crate::helpers::dev::mono_text_helper(&arena, text, false);
+
+    match crate::helpers::dev::run_test_main::<T>(&lib) {
+        Ok(value) => {
+            let given = transform(value);
+            assert_eq!(&given, &expected, "output is different");
+        }
+        Err((msg, tag)) => match tag {
+            CrashTag::Roc => panic!(r#"Roc failed with message: "{msg}""#),
+            CrashTag::User => panic!(r#"User crash with message: "{msg}""#),
+        },
+    }
+}
+
 pub(crate) fn identity<T>(x: T) -> T {
     x
 }
@@ -399,3 +493,17 @@ macro_rules! assert_evals_to {
 
 #[allow(unused_imports)]
 pub(crate) use assert_evals_to;
+
+#[allow(unused_macros)]
+macro_rules! assert_mono_text_evals_to {
+    ($text:expr, $expected:expr, $ty:ty) => {
+        $crate::helpers::dev::mono_text_evals_to::<$ty, _, _>(
+            $text,
+            $expected,
+            $crate::helpers::dev::identity,
+        );
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use assert_mono_text_evals_to;
@@ -76,6 +76,8 @@ fn create_llvm_module<'a>(
         palette: DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        reproducible: false,
+        coverage: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -227,6 +229,10 @@ fn create_llvm_module<'a>(
         module,
         target,
         mode: config.mode,
+        debug_locations: Default::default(),
+        inline_threshold: 0,
+        alias_analysis: Default::default(),
+        stack_checks: true,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
     };
@@ -239,6 +245,7 @@ fn create_llvm_module<'a>(
         EntryPoint::Executable {
             exposed_to_host,
             platform_path: _,
+            app_name: _,
         } => {
             // TODO support multiple of these!
             debug_assert_eq!(exposed_to_host.len(), 1);
@@ -10,6 +10,7 @@ pub mod gen_definitions;
 pub mod gen_dict;
 pub mod gen_erased;
 pub mod gen_list;
+pub mod gen_mono_text;
 pub mod gen_num;
 pub mod gen_panic;
 pub mod gen_primitives;
@@ -21,6 +21,7 @@ use roc_load::LoadConfig;
 use roc_load::LoadMonomorphizedError;
 use roc_load::Threading;
 use roc_module::symbol::Interns;
+use roc_module::symbol::ModuleId;
 use roc_module::symbol::Symbol;
 use roc_mono::ir::Proc;
 use roc_mono::ir::ProcLayout;
@@ -111,6 +112,8 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, allow_type_errors: boo
         render: roc_reporting::report::RenderTarget::Generic,
         palette: roc_reporting::report::DEFAULT_PALETTE,
         exec_mode,
+        reproducible: false,
+        coverage: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -138,7 +141,7 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, allow_type_errors: boo
         procedures,
         exposed_to_host,
         mut layout_interner,
-        interns,
+        mut interns,
         ..
     } = loaded;
 
@@ -159,9 +162,96 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, allow_type_errors: boo
         check_procedures(arena, &interns, &mut layout_interner, &procedures);
     }
 
+    check_mono_text_round_trip(arena, home, &mut interns, &mut layout_interner, &procedures);
+
     verify_procedures(test_name, layout_interner, procedures, main_fn_symbol);
 }
 
+/// Specializations read from a warm cache must be the same as the ones made without it.
+#[test]
+fn warm_specialization_cache_gives_the_same_procs() {
+    use roc_mono::spec_cache::CACHE_SPECIALIZATIONS_ENV;
+    use roc_packaging::cache::RocCacheDir;
+
+    let src = indoc!(
+        r#"
+        if List.isEmpty [1u8] || Num.isPositive 3i64 || Num.isNegative 1.5f64 then
+            List.map [1u8, 2] (\x -> x + 1)
+        else
+            []
+        "#
+    );
+
+    let cache_dir = tempfile::tempdir().unwrap();
+    let packages_dir = cache_dir.path().join("packages");
+    let specializations_dir = cache_dir.path().join("specializations");
+
+    // The other tests don't use a cache dir, so this doesn't change what they do
+    std::env::set_var(CACHE_SPECIALIZATIONS_ENV, "1");
+
+    let cold = anonymous_procs(src, RocCacheDir::Persistent(&packages_dir));
+
+    let cached = std::fs::read_dir(&specializations_dir)
+        .map(|entries| entries.count())
+        .unwrap_or(0);
+    assert!(
+        cached > 0,
+        "nothing was written to the specialization cache"
+    );
+
+    let warm = anonymous_procs(src, RocCacheDir::Persistent(&packages_dir));
+    let uncached = anonymous_procs(src, RocCacheDir::Disallowed);
+
+    assert_eq!(cold, warm);
+    assert_eq!(cold, uncached);
+}
+
+/// The procs of a program as text that doesn't depend on which symbols their locals got, so
+/// procs that were read from the cache can be compared with ones that weren't.
+fn anonymous_procs(src: &str, roc_cache_dir: roc_packaging::cache::RocCacheDir) -> Vec<String> {
+    use roc_mono::ir::proc_to_anonymous_mono_text;
+    use std::path::PathBuf;
+
+    let arena = &Bump::new();
+
+    let load_config = LoadConfig {
+        target: TARGET,
+        function_kind: FunctionKind::LambdaSet,
+        threading: Threading::Single,
+        render: roc_reporting::report::RenderTarget::Generic,
+        palette: roc_reporting::report::DEFAULT_PALETTE,
+        exec_mode: ExecutionMode::Executable,
+        reproducible: false,
+        coverage: false,
+    };
+    let module_src = promote_expr_to_module(src);
+    let loaded = roc_load::load_and_monomorphize_from_str(
+        arena,
+        PathBuf::from("Test.roc"),
+        &module_src,
+        PathBuf::from("fake/test/path"),
+        roc_cache_dir,
+        load_config,
+    )
+    .unwrap_or_else(|e| panic!("{e:?}"));
+
+    let interns = &loaded.interns;
+    let mut procs: Vec<String> = loaded
+        .procedures
+        .iter()
+        .map(|((symbol, _), proc)| {
+            let text = proc_to_anonymous_mono_text(&loaded.layout_interner, proc)
+                .unwrap_or_else(|| proc.to_pretty(&loaded.layout_interner, 200, false));
+
+            format!("{}\n{text}", symbol.as_str(interns))
+        })
+        .collect();
+
+    procs.sort();
+
+    procs
+}
+
 fn check_procedures<'a>(
     arena: &'a Bump,
     interns: &Interns,
@@ -177,6 +267,39 @@ fn check_procedures<'a>(
     panic!("IR problems found:\n{formatted}");
 }
 
+/// Printing the procs as mono text, parsing that, and printing the result again must give
+/// the same text. Procs that use constructs the format doesn't support are skipped.
+fn check_mono_text_round_trip<'a>(
+    arena: &'a Bump,
+    home: ModuleId,
+    interns: &mut Interns,
+    interner: &mut STLayoutInterner<'a>,
+    procedures: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+) {
+    use roc_mono::ir::{parse_mono_text, procs_to_mono_text};
+
+    let mut procs = procedures
+        .values()
+        .map(|proc| (proc.to_pretty(interner, 200, false), proc))
+        .collect::<Vec<_>>();
+    procs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let text = procs_to_mono_text(interner, interns, procs.iter().map(|(_, proc)| *proc));
+
+    if text.contains("!unsupported") {
+        return;
+    }
+
+    let parsed = match parse_mono_text(arena, interner, interns, home, &text) {
+        Ok(parsed) => parsed,
+        Err(error) => panic!("failed to parse mono text at {error}:\n\n{text}"),
+    };
+
+    let reprinted = procs_to_mono_text(interner, interns, parsed.iter());
+
+    assert_eq!(text, reprinted, "mono text did not round-trip");
+}
+
 fn verify_procedures<'a>(
     test_name: &str,
     interner: STLayoutInterner<'a>,
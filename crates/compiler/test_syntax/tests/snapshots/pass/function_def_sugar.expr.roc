@@ -1 +1,3 @@
 f x y = x
+
+f 1 2
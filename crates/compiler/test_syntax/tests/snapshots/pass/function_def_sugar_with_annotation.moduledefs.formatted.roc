@@ -0,0 +1,4 @@
+add : I64, I64 -> I64
+add x y = x + y
+
+first (Pair x _) = x
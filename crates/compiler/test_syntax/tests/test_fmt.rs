@@ -124,6 +124,60 @@ mod test_fmt {
         module_formats_to(input, input);
     }
 
+    #[test]
+    fn verify_formatted_module() {
+        let arena = Bump::new();
+        let src = "module [a]\n\na =  1\n";
+
+        let formatted = roc_fmt::verify::format_and_verify(&arena, src).unwrap();
+        assert_eq!(formatted, "module [a]\n\na = 1\n");
+
+        assert!(roc_fmt::verify::minimize_counterexample(src).is_none());
+    }
+
+    #[test]
+    fn minimize_keeps_only_failing_lines() {
+        let src = "a\nb\nbad\nc\nd\nworse\ne\n";
+
+        let minimized = roc_fmt::verify::minimize(src, |candidate| {
+            candidate.contains("bad") && candidate.contains("worse")
+        });
+
+        assert_eq!(minimized, "bad\nworse\n");
+    }
+
+    #[test]
+    fn fmt_range_only_formats_enclosing_defs() {
+        let arena = Bump::new();
+        let src = indoc!(
+            r"
+            module [a, b, c]
+
+            a =  1
+
+            # comment about b
+            b   =  2
+            c =  3
+            "
+        );
+        let b_start = src.find("b   =").unwrap();
+        let b_end = src.find("2\n").unwrap() + 1;
+
+        // A cursor inside of b
+        let formatted = roc_fmt::fmt_range(&arena, src, b_start + 1..b_start + 1).unwrap();
+        assert_eq!(formatted.range, b_start..b_end);
+        assert_eq!(formatted.text, "b = 2");
+
+        // A selection from a to b keeps the comment in between
+        let a_start = src.find("a =").unwrap();
+        let formatted = roc_fmt::fmt_range(&arena, src, a_start..b_start + 1).unwrap();
+        assert_eq!(formatted.range, a_start..b_end);
+        assert_eq!(formatted.text, "a = 1\n\n# comment about b\nb = 2");
+
+        // The header is not in any def
+        assert_eq!(roc_fmt::fmt_range(&arena, src, 0..6), None);
+    }
+
     // STRING LITERALS
 
     #[test]
@@ -1262,6 +1316,34 @@ mod test_fmt {
         );
     }
 
+    #[test]
+    fn function_def_sugar() {
+        expr_formats_same(indoc!(
+            r"
+            f x (Pair a b) = x + a + b
+
+            f 1 (Pair 2 3)
+            "
+        ));
+
+        expr_formats_to(
+            indoc!(
+                r"
+                f   x  y  =  x
+
+                f 1 2
+                "
+            ),
+            indoc!(
+                r"
+                f x y = x
+
+                f 1 2
+                "
+            ),
+        );
+    }
+
     #[test]
     fn excess_parens() {
         expr_formats_to(
@@ -2122,8 +2204,7 @@ mod test_fmt {
             {
                 y: 41,
                 # comment 1
-                x: 42,
-                # comment 2
+                x: 42, # comment 2
             }"
             ),
         );
@@ -2144,8 +2225,7 @@ mod test_fmt {
                 r"
             {
                 y: 41,
-                x: 42,
-                # comment 1
+                x: 42, # comment 1
                 # comment 2
             }"
             ),
@@ -2173,10 +2253,8 @@ mod test_fmt {
             indoc!(
                 r"
             {
-                z: 44,
-                # comment 0
-                y: 41,
-                # comment 1
+                z: 44, # comment 0
+                y: 41, # comment 1
                 # comment 2
                 x: 42,
                 # comment 3
@@ -2188,27 +2266,86 @@ mod test_fmt {
 
     #[test]
     fn multiple_final_comments_with_comma_in_records() {
-        expr_formats_to(
-            indoc!(
-                r"
+        expr_formats_same(indoc!(
+            r"
             {
                 y: 41,
                 x: 42, # comment 1
                 # comment 2
             }"
+        ));
+    }
+
+    #[test]
+    fn trailing_comments_line_up_in_records() {
+        expr_formats_to(
+            indoc!(
+                r"
+            {
+                a: 1, # one
+                bbb: 22, # two
+                c: 3,
+                # not trailing
+                d: 4, # four
+            }"
             ),
             indoc!(
                 r"
             {
-                y: 41,
-                x: 42,
-                # comment 1
-                # comment 2
+                a: 1,    # one
+                bbb: 22, # two
+                c: 3,
+                # not trailing
+                d: 4, # four
             }"
             ),
         );
     }
 
+    #[test]
+    fn trailing_comments_line_up_in_lists() {
+        expr_formats_to(
+            indoc!(
+                r"
+            [
+                1, # one
+                22, # two
+                333,
+            ]"
+            ),
+            indoc!(
+                r"
+            [
+                1,  # one
+                22, # two
+                333,
+            ]"
+            ),
+        );
+    }
+
+    #[test]
+    fn trailing_comments_line_up_in_when() {
+        expr_formats_to(
+            indoc!(
+                r"
+            when x is
+                A -> 1 # one
+                BB -> 2 # two
+                _ -> 3
+            "
+            ),
+            indoc!(
+                r"
+            when x is
+                A -> 1  # one
+                BB -> 2 # two
+                _ -> 3
+            "
+            ),
+        );
+    }
+
     #[test]
     fn trailing_comma_in_record_annotation() {
         expr_formats_to(
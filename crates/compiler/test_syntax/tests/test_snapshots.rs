@@ -190,7 +190,6 @@ mod test_snapshots {
         fail/comment_with_tab.expr,
         fail/def_missing_final_expression.expr,
         fail/double_plus.expr,
-        fail/elm_function_syntax.expr,
         fail/empty_or_pattern.expr,
         fail/error_inline_alias_argument_uppercase.expr,
         fail/error_inline_alias_not_an_alias.expr,
@@ -321,6 +320,8 @@ mod test_snapshots {
         pass/fn_with_record_arg.expr,
         pass/full_app_header.header,
         pass/full_app_header_trailing_commas.header,
+        pass/function_def_sugar.expr,
+        pass/function_def_sugar_with_annotation.moduledefs,
         pass/function_effect_types.header,
         pass/function_with_tuple_ext_type.expr,
         pass/function_with_tuple_type.expr,
@@ -76,10 +76,48 @@ impl DebugPrint {
     };
 }
 
+/// How to write the names of types from other modules, e.g. `Config` or `Http.Config`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Qualification {
+    /// Qualify names from other modules, except builtins.
+    #[default]
+    Foreign,
+    /// Qualify every name, even those from the home module.
+    Full,
+    /// Write names without their module, unless another module has a name that reads the same.
+    Short,
+}
+
+impl Qualification {
+    /// Whether `symbol` should be written with its module when printing a type in `home`.
+    pub fn qualifies(self, symbol: Symbol, home: ModuleId, interns: &Interns) -> bool {
+        let module_id = symbol.module_id();
+
+        match self {
+            Qualification::Full => true,
+            Qualification::Foreign => module_id != home && !module_id.is_builtin(),
+            Qualification::Short if module_id == home => false,
+            Qualification::Short => {
+                let name = symbol.as_str(interns);
+
+                // Builtin modules have idents for the names they import from each other, but
+                // never define two types with the same name, so only other modules can clash
+                interns
+                    .all_ident_ids
+                    .keys()
+                    .filter(|other| **other != module_id && !other.is_builtin())
+                    .filter_map(|other| interns.all_ident_ids.get(other))
+                    .any(|ident_ids| ident_ids.get_id(name).is_some())
+            }
+        }
+    }
+}
+
 struct Env<'a> {
     home: ModuleId,
     interns: &'a Interns,
     debug: DebugPrint,
+    qualification: Qualification,
 }
 
 /// How many times a root variable appeared in Subs.
@@ -580,6 +618,7 @@ fn variable_to_string(
     interns: &Interns,
     named_result: NamedResult,
     debug_print: DebugPrint,
+    qualification: Qualification,
     pol: Polarity,
 ) -> String {
     let mut buf = String::new();
@@ -587,6 +626,7 @@ fn variable_to_string(
         home,
         interns,
         debug: debug_print,
+        qualification,
     };
     let mut ctx = Context {
         able_variables: vec![],
@@ -634,6 +674,24 @@ pub fn name_and_print_var(
     home: ModuleId,
     interns: &Interns,
     debug_print: DebugPrint,
+) -> String {
+    name_and_print_var_qualified(
+        var,
+        subs,
+        home,
+        interns,
+        debug_print,
+        Qualification::default(),
+    )
+}
+
+pub fn name_and_print_var_qualified(
+    var: Variable,
+    subs: &mut Subs,
+    home: ModuleId,
+    interns: &Interns,
+    debug_print: DebugPrint,
+    qualification: Qualification,
 ) -> String {
     let named_result = name_all_type_vars(var, subs, debug_print);
     variable_to_string(
@@ -643,6 +701,7 @@ pub fn name_and_print_var(
         interns,
         named_result,
         debug_print,
+        qualification,
         Polarity::Pos,
     )
 }
@@ -1502,9 +1561,7 @@ fn write_symbol(env: &Env, symbol: Symbol, buf: &mut String) {
     let ident_str = symbol.as_str(interns);
     let module_id = symbol.module_id();
 
-    // Don't qualify the symbol if it's in our home module,
-    // or if it's a builtin (since all their types are always in scope)
-    if module_id != env.home && !module_id.is_builtin() {
+    if env.qualification.qualifies(symbol, env.home, interns) {
         buf.push_str(module_id.to_ident_str(interns).as_str());
         buf.push('.');
     }
@@ -9,6 +9,12 @@ pub struct Env<'a> {
     cm: Option<&'a mut roc_checkmate::Collector>,
     seen_recursion: VecSet<(Variable, Variable)>,
     fixed_variables: VecSet<Variable>,
+    /// The innermost pairs of variables that failed to unify. These are what we poison with
+    /// `Error` when unification fails, so that the rest of the types stay usable.
+    mismatched_pairs: Vec<(Variable, Variable)>,
+    /// Whether we've unified a recursive type. Those can't be partially poisoned, because
+    /// unifying what's left of them again may never finish.
+    saw_recursive_type: bool,
 }
 
 impl std::ops::Deref for Env<'_> {
@@ -33,6 +39,8 @@ impl<'a> Env<'a> {
             cm,
             seen_recursion: Default::default(),
             fixed_variables: Default::default(),
+            mismatched_pairs: Default::default(),
+            saw_recursive_type: false,
         }
     }
 
@@ -42,6 +50,8 @@ impl<'a> Env<'a> {
             subs,
             seen_recursion: Default::default(),
             fixed_variables: Default::default(),
+            mismatched_pairs: Default::default(),
+            saw_recursive_type: false,
         }
     }
 
@@ -87,6 +97,30 @@ impl<'a> Env<'a> {
             .any(|fixed_var| self.subs.equivalent_without_compacting(*fixed_var, var))
     }
 
+    pub(crate) fn mismatched_pairs_len(&self) -> usize {
+        self.mismatched_pairs.len()
+    }
+
+    pub(crate) fn push_mismatched_pair(&mut self, var1: Variable, var2: Variable) {
+        self.mismatched_pairs.push((var1, var2));
+    }
+
+    pub(crate) fn truncate_mismatched_pairs(&mut self, len: usize) {
+        self.mismatched_pairs.truncate(len);
+    }
+
+    pub(crate) fn take_mismatched_pairs(&mut self) -> Vec<(Variable, Variable)> {
+        std::mem::take(&mut self.mismatched_pairs)
+    }
+
+    pub(crate) fn note_recursive_type(&mut self) {
+        self.saw_recursive_type = true;
+    }
+
+    pub(crate) fn saw_recursive_type(&self) -> bool {
+        self.saw_recursive_type
+    }
+
     pub(crate) fn extend_fixed_variables(&mut self, vars: impl IntoIterator<Item = Variable>) {
         self.fixed_variables.extend(vars);
     }
@@ -363,8 +363,6 @@ fn unify_help<M: MetaCollector>(
         let type1 = env.var_to_error_type_contextual(var1, error_context, observed_pol);
         let type2 = env.var_to_error_type_contextual(var2, error_context, observed_pol);
 
-        env.union(var1, var2, Content::Error.into());
-
         let do_not_implement_ability = mismatches
             .into_iter()
             .filter_map(|mismatch| match mismatch {
@@ -377,6 +375,8 @@ fn unify_help<M: MetaCollector>(
             })
             .collect();
 
+        recover_from_mismatch(env, &mut vars, var1, var2, mode);
+
         Unified::Failure(vars, type1, type2, do_not_implement_ability)
     }
 }
@@ -476,6 +476,14 @@ fn unify_context<M: MetaCollector>(env: &mut Env, pool: &mut Pool, ctx: Context)
     #[cfg(debug_assertions)]
     debug_print_unified_types::<M>(env, &ctx, None);
 
+    let mismatched_pairs_before = env.mismatched_pairs_len();
+
+    if is_recursive_content(&ctx.first_desc.content)
+        || is_recursive_content(&ctx.second_desc.content)
+    {
+        env.note_recursive_type();
+    }
+
     // This #[allow] is needed in release builds, where `result` is no longer used.
     #[allow(clippy::let_and_return)]
     let mut result: Outcome<M> = match &ctx.first_desc.content {
@@ -525,12 +533,74 @@ fn unify_context<M: MetaCollector>(env: &mut Env, pool: &mut Pool, ctx: Context)
             .record_changed_variable(env, ctx.second);
     }
 
+    // If nothing nested inside these two failed, this is where the mismatch really is.
+    if !result.mismatches.is_empty() && env.mismatched_pairs_len() == mismatched_pairs_before {
+        env.push_mismatched_pair(ctx.first, ctx.second);
+    }
+
     #[cfg(debug_assertions)]
     debug_print_unified_types(env, &ctx, Some(&result));
 
     result
 }
 
+/// After `var1` and `var2` failed to unify, poison only the innermost pairs of variables that
+/// didn't match, and then unify the rest of the two types as usual.
+///
+/// That way a mistake in one field of a record doesn't turn the whole record into an error,
+/// and a later, unrelated mistake involving another field still gets reported. The poisoned
+/// variables themselves unify with anything, so the same mistake is never reported twice.
+///
+/// If that doesn't work out cleanly, we fall back to poisoning both types in their entirety.
+fn recover_from_mismatch(
+    env: &mut Env,
+    pool: &mut Pool,
+    var1: Variable,
+    var2: Variable,
+    mode: UnificationMode,
+) {
+    let mismatched_pairs = env.take_mismatched_pairs();
+
+    // The failed unification may have left a recursive type half-unified, and unifying that
+    // again might never terminate.
+    if env.saw_recursive_type() {
+        env.union(var1, var2, Content::Error.into());
+        return;
+    }
+
+    let snapshot = env.snapshot();
+    let pool_len = pool.len();
+
+    for (left, right) in mismatched_pairs {
+        env.union(left, right, Content::Error.into());
+    }
+
+    let outcome: Outcome<NoCollector> = unify_pool(env, pool, var1, var2, mode);
+
+    // Anything left over for the solver to do would be dropped, because the caller only sees
+    // a failure. So only keep the recovered types if there's nothing left over.
+    let recovered = outcome.mismatches.is_empty()
+        && outcome.must_implement_ability.is_empty()
+        && outcome.lambda_sets_to_specialize.is_empty();
+
+    if recovered {
+        env.commit_snapshot(snapshot);
+    } else {
+        env.rollback_to(snapshot);
+        pool.truncate(pool_len);
+        env.take_mismatched_pairs();
+
+        env.union(var1, var2, Content::Error.into());
+    }
+}
+
+fn is_recursive_content(content: &Content) -> bool {
+    matches!(
+        content,
+        RecursionVar { .. } | Structure(FlatType::RecursiveTagUnion(..))
+    )
+}
+
 fn not_in_range_mismatch<M: MetaCollector>() -> Outcome<M> {
     Outcome {
         mismatches: vec![Mismatch::TypeNotInRange],
@@ -1437,6 +1507,7 @@ fn separate_union_lambdas<M: MetaCollector>(
                             //          foo ({} -[ bar U64 ]-> {}) ] -> {}
                             let subs_snapshot = env.snapshot();
                             let pool_snapshot = pool.len();
+                            let mismatched_pairs_snapshot = env.mismatched_pairs_len();
                             let outcome: Outcome<M> = unify_pool(env, pool, var1, var2, mode);
 
                             if !outcome.mismatches.is_empty() {
@@ -1444,6 +1515,7 @@ fn separate_union_lambdas<M: MetaCollector>(
                                 // were joined into the same set.
                                 env.rollback_to(subs_snapshot);
                                 pool.truncate(pool_snapshot);
+                                env.truncate_mismatched_pairs(mismatched_pairs_snapshot);
                                 continue 'try_next_right;
                             } else {
                                 let outcome = unify_pool(env, pool, var1, var2, mode);
@@ -2850,10 +2922,13 @@ fn unify_tag_unions<M: MetaCollector>(
 
         let mut total_outcome = Outcome::default();
         let snapshot = env.snapshot();
+        // Once the ext vars are rolled back, the tag unions themselves are what didn't match
+        let mismatched_pairs_snapshot = env.mismatched_pairs_len();
 
         let ext1_outcome = unify_tag_ext(env, pool, UnifySides::Left(ext1, sub2), ctx.mode);
         if !ext1_outcome.mismatches.is_empty() {
             env.rollback_to(snapshot);
+            env.truncate_mismatched_pairs(mismatched_pairs_snapshot);
             return ext1_outcome;
         }
         total_outcome.union(ext1_outcome);
@@ -2862,6 +2937,7 @@ fn unify_tag_unions<M: MetaCollector>(
             let ext2_outcome = unify_tag_ext(env, pool, UnifySides::Right(sub1, ext2), ctx.mode);
             if !ext2_outcome.mismatches.is_empty() {
                 env.rollback_to(snapshot);
+                env.truncate_mismatched_pairs(mismatched_pairs_snapshot);
                 return ext2_outcome;
             }
             total_outcome.union(ext2_outcome);
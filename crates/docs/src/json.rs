@@ -0,0 +1,284 @@
+//! The documentation model as JSON, so other site generators and package registries can
+//! render docs themselves. Doc comments are included as a markdown syntax tree.
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, LinkType, Tag};
+use roc_collections::{VecMap, VecSet};
+use roc_load::docs::{DocEntry, ModuleDocumentation, RecordField, TypeAnnotation};
+use roc_module::symbol::{ModuleId, Symbol};
+use serde_json::{json, Map, Value};
+
+pub(crate) fn docs_to_json(
+    package_name: &str,
+    docs_by_module: &[(ModuleId, ModuleDocumentation)],
+    all_exposed_symbols: &VecSet<Symbol>,
+    inferred_types: &VecMap<Symbol, String>,
+) -> Value {
+    let modules = docs_by_module
+        .iter()
+        .map(|(_, module)| {
+            let entries = module
+                .entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    DocEntry::DocDef(doc_def) => {
+                        // Only exposed entries are documented
+                        if !all_exposed_symbols.contains(&doc_def.symbol) {
+                            return None;
+                        }
+
+                        Some(json!({
+                            "kind": "def",
+                            "name": doc_def.name,
+                            "typeVars": doc_def.type_vars,
+                            "type": type_annotation_to_json(&doc_def.type_annotation),
+                            "inferredType": inferred_types.get(&doc_def.symbol),
+                            "docs": doc_def.docs.as_deref().map(markdown_to_json),
+                        }))
+                    }
+                    DocEntry::ModuleDoc(docs) => Some(json!({
+                        "kind": "moduleDoc",
+                        "docs": markdown_to_json(docs),
+                    })),
+                    DocEntry::DetachedDoc(docs) => Some(json!({
+                        "kind": "detachedDoc",
+                        "docs": markdown_to_json(docs),
+                    })),
+                })
+                .collect::<Vec<_>>();
+
+            json!({
+                "name": module.name,
+                "entries": entries,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "package": package_name,
+        "modules": modules,
+    })
+}
+
+fn type_annotation_to_json(type_ann: &TypeAnnotation) -> Value {
+    let all_to_json = |type_anns: &[TypeAnnotation]| {
+        type_anns
+            .iter()
+            .map(type_annotation_to_json)
+            .collect::<Vec<_>>()
+    };
+
+    match type_ann {
+        TypeAnnotation::TagUnion { tags, extension } => json!({
+            "kind": "tagUnion",
+            "tags": tags
+                .iter()
+                .map(|tag| json!({ "name": tag.name, "values": all_to_json(&tag.values) }))
+                .collect::<Vec<_>>(),
+            "extension": type_annotation_to_json(extension),
+        }),
+        TypeAnnotation::Function { args, output } => json!({
+            "kind": "function",
+            "args": all_to_json(args),
+            "output": type_annotation_to_json(output),
+        }),
+        TypeAnnotation::ObscuredTagUnion => json!({ "kind": "obscuredTagUnion" }),
+        TypeAnnotation::ObscuredRecord => json!({ "kind": "obscuredRecord" }),
+        TypeAnnotation::BoundVariable(name) => json!({ "kind": "boundVariable", "name": name }),
+        TypeAnnotation::Apply { name, parts } => json!({
+            "kind": "apply",
+            "name": name,
+            "parts": all_to_json(parts),
+        }),
+        TypeAnnotation::Record { fields, extension } => json!({
+            "kind": "record",
+            "fields": fields.iter().map(record_field_to_json).collect::<Vec<_>>(),
+            "extension": type_annotation_to_json(extension),
+        }),
+        TypeAnnotation::Tuple { elems, extension } => json!({
+            "kind": "tuple",
+            "elems": all_to_json(elems),
+            "extension": type_annotation_to_json(extension),
+        }),
+        TypeAnnotation::Ability { members } => json!({
+            "kind": "ability",
+            "members": members
+                .iter()
+                .map(|member| json!({
+                    "name": member.name,
+                    "type": type_annotation_to_json(&member.type_annotation),
+                    "ableVariables": member
+                        .able_variables
+                        .iter()
+                        .map(|(name, abilities)| json!({
+                            "name": name,
+                            "abilities": all_to_json(abilities),
+                        }))
+                        .collect::<Vec<_>>(),
+                    "docs": member.docs.as_deref().map(markdown_to_json),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        TypeAnnotation::Wildcard => json!({ "kind": "wildcard" }),
+        TypeAnnotation::NoTypeAnn => Value::Null,
+        TypeAnnotation::Where { ann, implements } => json!({
+            "kind": "where",
+            "ann": type_annotation_to_json(ann),
+            "implements": implements
+                .iter()
+                .map(|clause| json!({
+                    "name": clause.name,
+                    "abilities": all_to_json(&clause.abilities),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        TypeAnnotation::As { ann, name, vars } => json!({
+            "kind": "as",
+            "ann": type_annotation_to_json(ann),
+            "name": name,
+            "vars": vars,
+        }),
+    }
+}
+
+fn record_field_to_json(field: &RecordField) -> Value {
+    match field {
+        RecordField::RecordField {
+            name,
+            type_annotation,
+        } => json!({
+            "kind": "required",
+            "name": name,
+            "type": type_annotation_to_json(type_annotation),
+        }),
+        RecordField::OptionalField {
+            name,
+            type_annotation,
+        } => json!({
+            "kind": "optional",
+            "name": name,
+            "type": type_annotation_to_json(type_annotation),
+        }),
+        RecordField::LabelOnly { name } => json!({ "kind": "labelOnly", "name": name }),
+    }
+}
+
+/// A markdown syntax tree, where every node is an object with a "type", and the nodes that can
+/// contain others have "children". Shortcut links that don't have a URL, like `[Str.join]`, are
+/// "reference" nodes with the name they refer to, for the renderer to resolve.
+fn markdown_to_json(markdown: &str) -> Value {
+    let mut broken_link_callback = |link: pulldown_cmark::BrokenLink| match link.link_type {
+        LinkType::Shortcut => Some((link.reference.to_string().into(), "".into())),
+        _ => None,
+    };
+
+    let markdown_options =
+        pulldown_cmark::Options::ENABLE_TABLES | pulldown_cmark::Options::ENABLE_HEADING_ATTRIBUTES;
+
+    let parser = pulldown_cmark::Parser::new_with_broken_link_callback(
+        markdown,
+        markdown_options,
+        Some(&mut broken_link_callback),
+    );
+
+    // The nodes that have started but not ended yet, with the outermost first
+    let mut open = vec![markdown_node("document")];
+
+    for event in parser {
+        match event {
+            Event::Start(tag) => open.push(tag_to_node(tag)),
+            Event::End(_) => {
+                let node = open.pop().expect("every end event follows its start event");
+                push_child(&mut open, node);
+            }
+            Event::Text(text) => push_child(&mut open, text_node("text", &text)),
+            Event::Code(code) => push_child(&mut open, text_node("code", &code)),
+            Event::Html(html) => push_child(&mut open, text_node("html", &html)),
+            Event::FootnoteReference(label) => push_child(
+                &mut open,
+                json!({ "type": "footnoteReference", "label": &*label }),
+            ),
+            Event::SoftBreak => push_child(&mut open, json!({ "type": "softBreak" })),
+            Event::HardBreak => push_child(&mut open, json!({ "type": "hardBreak" })),
+            Event::Rule => push_child(&mut open, json!({ "type": "rule" })),
+            Event::TaskListMarker(checked) => push_child(
+                &mut open,
+                json!({ "type": "taskListMarker", "checked": checked }),
+            ),
+        }
+    }
+
+    Value::Object(open.swap_remove(0))
+}
+
+fn markdown_node(node_type: &str) -> Map<String, Value> {
+    let mut node = Map::new();
+
+    node.insert("type".to_string(), node_type.into());
+    node.insert("children".to_string(), Value::Array(Vec::new()));
+
+    node
+}
+
+fn text_node(node_type: &str, text: &str) -> Value {
+    json!({ "type": node_type, "text": text })
+}
+
+fn push_child(open: &mut [Map<String, Value>], child: impl Into<Value>) {
+    if let Some(Value::Array(children)) = open
+        .last_mut()
+        .and_then(|parent| parent.get_mut("children"))
+    {
+        children.push(child.into());
+    }
+}
+
+fn tag_to_node(tag: Tag<'_>) -> Map<String, Value> {
+    let (node_type, attrs) = match tag {
+        Tag::Paragraph => ("paragraph", json!({})),
+        Tag::Heading(level, id, classes) => (
+            "heading",
+            json!({ "level": level as u8, "id": id, "classes": classes }),
+        ),
+        Tag::BlockQuote => ("blockQuote", json!({})),
+        Tag::CodeBlock(CodeBlockKind::Fenced(lang)) => (
+            "codeBlock",
+            json!({ "lang": Some(&*lang).filter(|lang| !lang.is_empty()) }),
+        ),
+        Tag::CodeBlock(CodeBlockKind::Indented) => ("codeBlock", json!({ "lang": null })),
+        Tag::List(start) => ("list", json!({ "start": start })),
+        Tag::Item => ("item", json!({})),
+        Tag::FootnoteDefinition(label) => ("footnoteDefinition", json!({ "label": &*label })),
+        Tag::Table(alignments) => (
+            "table",
+            json!({
+                "alignments": alignments
+                    .iter()
+                    .map(|alignment| match alignment {
+                        Alignment::None => None,
+                        Alignment::Left => Some("left"),
+                        Alignment::Center => Some("center"),
+                        Alignment::Right => Some("right"),
+                    })
+                    .collect::<Vec<_>>(),
+            }),
+        ),
+        Tag::TableHead => ("tableHead", json!({})),
+        Tag::TableRow => ("tableRow", json!({})),
+        Tag::TableCell => ("tableCell", json!({})),
+        Tag::Emphasis => ("emphasis", json!({})),
+        Tag::Strong => ("strong", json!({})),
+        Tag::Strikethrough => ("strikethrough", json!({})),
+        Tag::Link(LinkType::ShortcutUnknown, reference, _) => {
+            ("reference", json!({ "name": &*reference }))
+        }
+        Tag::Link(_, url, title) => ("link", json!({ "url": &*url, "title": &*title })),
+        Tag::Image(_, url, title) => ("image", json!({ "url": &*url, "title": &*title })),
+    };
+
+    let mut node = markdown_node(node_type);
+
+    if let Value::Object(attrs) = attrs {
+        node.extend(attrs);
+    }
+
+    node
+}
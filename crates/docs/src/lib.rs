@@ -4,7 +4,7 @@ extern crate pulldown_cmark;
 extern crate roc_load;
 use bumpalo::Bump;
 use roc_can::scope::Scope;
-use roc_collections::VecSet;
+use roc_collections::{VecMap, VecSet};
 use roc_load::docs::{DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
@@ -15,9 +15,15 @@ use roc_parse::keyword;
 use roc_parse::state::State;
 use roc_problem::Severity;
 use roc_region::all::Region;
+use roc_reporting::error::r#type::error_type_to_doc;
+use roc_reporting::report::{Annotation, CiWrite, RocDocAllocator};
+use roc_types::pretty_print::Qualification;
+use roc_types::types::Polarity;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod json;
+
 const LINK_SVG: &str = include_str!("./static/link.svg");
 
 pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path) {
@@ -114,16 +120,34 @@ pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path) {
             render_sidebar(exposed_module_docs.iter().map(|(_, docs)| docs)).as_str(),
         );
 
-    let all_exposed_symbols = {
+    let all_exposed_symbols = all_exposed_symbols(&exposed_module_docs);
+
+    let inferred_types = infer_unannotated_types(
+        &mut loaded_module,
+        &exposed_module_docs,
+        &all_exposed_symbols,
+    );
+
+    let documented_modules = {
         let mut set = VecSet::default();
 
-        for (_, docs) in exposed_module_docs.iter() {
-            set.insert_all(docs.exposed_symbols.iter().copied());
+        for (module_id, _) in exposed_module_docs.iter() {
+            set.insert(*module_id);
         }
 
         set
     };
 
+    // Write the index that search.js uses to find entries across all the modules
+    {
+        let search_index =
+            render_search_index(&exposed_module_docs, &all_exposed_symbols, &inferred_types);
+
+        fs::write(build_dir.join("search-index.json"), search_index).unwrap_or_else(|error| {
+            panic!("Attempted to write search-index.json but failed with this error: {error}")
+        });
+    }
+
     // TODO fix: as is, this overrides an existing index.html
     // Write index.html for package (/index.html)
     {
@@ -170,6 +194,8 @@ pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path) {
                     module_docs,
                     &loaded_module,
                     &all_exposed_symbols,
+                    &documented_modules,
+                    &inferred_types,
                 )
                 .as_str(),
             );
@@ -181,6 +207,55 @@ pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path) {
     println!("🎉 Docs generated in {}", build_dir.display());
 }
 
+/// Writes the documentation model, rather than html, to `docs.json` in `build_dir`. Doc comments
+/// are included as markdown syntax trees.
+pub fn generate_docs_json(root_file: PathBuf, build_dir: &Path) {
+    let mut loaded_module = load_module_for_docs(root_file);
+    let exposed_module_docs = get_exposed_module_docs(&mut loaded_module);
+
+    // TODO get these from the platform's source file rather than hardcoding them!
+    // github.com/roc-lang/roc/issues/5712
+    let package_name = "Documentation".to_string();
+
+    let all_exposed_symbols = all_exposed_symbols(&exposed_module_docs);
+    let inferred_types = infer_unannotated_types(
+        &mut loaded_module,
+        &exposed_module_docs,
+        &all_exposed_symbols,
+    );
+
+    let docs = json::docs_to_json(
+        &package_name,
+        &exposed_module_docs,
+        &all_exposed_symbols,
+        &inferred_types,
+    );
+
+    fs::create_dir_all(build_dir).expect("TODO gracefully handle being unable to create build dir");
+
+    let path = build_dir.join("docs.json");
+
+    fs::write(&path, docs.to_string()).unwrap_or_else(|error| {
+        panic!(
+            "Attempted to write {} but failed with this error: {}",
+            path.display(),
+            error
+        )
+    });
+
+    println!("🎉 Docs generated in {}", path.display());
+}
+
+fn all_exposed_symbols(exposed_module_docs: &[(ModuleId, ModuleDocumentation)]) -> VecSet<Symbol> {
+    let mut set = VecSet::default();
+
+    for (_, docs) in exposed_module_docs.iter() {
+        set.insert_all(docs.exposed_symbols.iter().copied());
+    }
+
+    set
+}
+
 /// Gives only the module docs for modules that are exposed by the platform or package.
 fn get_exposed_module_docs(
     loaded_module: &mut LoadedModule,
@@ -240,14 +315,138 @@ fn render_package_index(docs_by_module: &[(ModuleId, ModuleDocumentation)]) -> S
     index_buf
 }
 
+/// The types the compiler inferred for exposed values that don't have an annotation, printed
+/// the same way as in error messages.
+fn infer_unannotated_types(
+    loaded_module: &mut LoadedModule,
+    docs_by_module: &[(ModuleId, ModuleDocumentation)],
+    all_exposed_symbols: &VecSet<Symbol>,
+) -> VecMap<Symbol, String> {
+    let mut inferred_types = VecMap::default();
+
+    for (module_id, module) in docs_by_module.iter() {
+        // The root module's types end up in `solved`, while every other module keeps its own
+        let (subs, decls) = if *module_id == loaded_module.module_id {
+            match loaded_module.declarations_by_id.get(module_id) {
+                Some(decls) => (loaded_module.solved.inner_mut(), decls),
+                None => continue,
+            }
+        } else {
+            match loaded_module.typechecked.get_mut(module_id) {
+                Some(checked) => (checked.solved_subs.inner_mut(), &checked.decls),
+                None => continue,
+            }
+        };
+
+        let alloc = RocDocAllocator::new(&[], *module_id, &loaded_module.interns)
+            .with_qualification(Qualification::Short);
+
+        for entry in &module.entries {
+            let DocEntry::DocDef(doc_def) = entry else {
+                continue;
+            };
+
+            if !matches!(doc_def.type_annotation, TypeAnnotation::NoTypeAnn)
+                || !all_exposed_symbols.contains(&doc_def.symbol)
+            {
+                continue;
+            }
+
+            let Some(index) = decls
+                .symbols
+                .iter()
+                .position(|loc_symbol| loc_symbol.value == doc_def.symbol)
+            else {
+                continue;
+            };
+
+            let error_type = subs.var_to_error_type(decls.variables[index], Polarity::OF_VALUE);
+            let mut type_str = String::new();
+
+            // As a type block, so the names aren't wrapped in backticks
+            if error_type_to_doc(&alloc, error_type)
+                .annotate(Annotation::TypeBlock)
+                .1
+                .render_raw(70, &mut CiWrite::new(&mut type_str))
+                .is_ok()
+            {
+                inferred_types.insert(doc_def.symbol, type_str);
+            }
+        }
+    }
+
+    inferred_types
+}
+
+/// A JSON array with an entry for each exposed def, giving its name, module, URL (relative to
+/// the base URL), type, and the first paragraph of its docs.
+fn render_search_index(
+    docs_by_module: &[(ModuleId, ModuleDocumentation)],
+    all_exposed_symbols: &VecSet<Symbol>,
+    inferred_types: &VecMap<Symbol, String>,
+) -> String {
+    let mut entries = Vec::new();
+
+    for (_, module) in docs_by_module.iter() {
+        let module_name = module.name.as_str();
+
+        for entry in &module.entries {
+            match entry {
+                DocEntry::DocDef(doc_def) if all_exposed_symbols.contains(&doc_def.symbol) => {
+                    let def_name = doc_def.name.as_str();
+                    let mut type_str = String::new();
+
+                    if !matches!(doc_def.type_annotation, TypeAnnotation::NoTypeAnn) {
+                        type_annotation_to_html(
+                            0,
+                            &mut type_str,
+                            &doc_def.type_annotation,
+                            false,
+                            None,
+                        );
+                    } else if let Some(inferred) = inferred_types.get(&doc_def.symbol) {
+                        type_str.push_str(inferred);
+                    }
+
+                    let summary = doc_def
+                        .docs
+                        .as_deref()
+                        .and_then(|docs| docs.split("\n\n").next())
+                        .unwrap_or_default()
+                        .trim();
+
+                    entries.push(serde_json::json!({
+                        "name": def_name,
+                        "module": module_name,
+                        "url": format!("{module_name}#{def_name}"),
+                        "type": type_str.split_whitespace().collect::<Vec<_>>().join(" "),
+                        "docs": summary,
+                    }));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    serde_json::Value::Array(entries).to_string()
+}
+
 fn render_module_documentation(
     module_id: ModuleId,
     module: &ModuleDocumentation,
     root_module: &LoadedModule,
     all_exposed_symbols: &VecSet<Symbol>,
+    documented_modules: &VecSet<ModuleId>,
+    inferred_types: &VecMap<Symbol, String>,
 ) -> String {
     let mut buf = String::new();
     let module_name = module.name.as_str();
+    let links = Links {
+        all_exposed_symbols,
+        documented_modules,
+        scope: &module.scope,
+        interns: &root_module.interns,
+    };
 
     push_html(&mut buf, "h2", vec![("class", "module-name")], {
         let mut link_buf = String::new();
@@ -286,7 +485,11 @@ fn render_module_documentation(
 
                         content.push(' ');
 
-                        type_annotation_to_html(0, &mut content, type_ann, false);
+                        type_annotation_to_html(0, &mut content, type_ann, false, Some(&links));
+                    } else if let Some(inferred) = inferred_types.get(&doc_def.symbol) {
+                        // There's no annotation, so show the type the compiler inferred instead
+                        content.push_str(" : ");
+                        content.push_str(inferred);
                     }
 
                     push_html(
@@ -300,8 +503,7 @@ fn render_module_documentation(
                         markdown_to_html(
                             &mut buf,
                             &root_module.filename(module_id),
-                            all_exposed_symbols,
-                            &module.scope,
+                            &links,
                             docs,
                             root_module,
                         );
@@ -314,21 +516,13 @@ fn render_module_documentation(
                 markdown_to_html(
                     &mut buf,
                     &root_module.filename(module_id),
-                    all_exposed_symbols,
-                    &module.scope,
+                    &links,
                     docs,
                     root_module,
                 );
             }
             DocEntry::DetachedDoc(docs) => {
-                markdown_to_html(
-                    &mut buf,
-                    &root_module.filename,
-                    all_exposed_symbols,
-                    &module.scope,
-                    docs,
-                    root_module,
-                );
+                markdown_to_html(&mut buf, &root_module.filename, &links, docs, root_module);
             }
         };
     }
@@ -478,6 +672,8 @@ pub fn load_module_for_docs(filename: PathBuf) -> LoadedModule {
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading: Threading::AllAvailable,
         exec_mode: ExecutionMode::Check,
+        reproducible: false,
+        coverage: false,
     };
     match roc_load::load_and_typecheck(
         &arena,
@@ -512,6 +708,7 @@ fn type_annotation_to_html(
     buf: &mut String,
     type_ann: &TypeAnnotation,
     needs_parens: bool,
+    links: Option<&Links>,
 ) {
     let is_multiline = should_be_multiline(type_ann);
     match type_ann {
@@ -546,7 +743,7 @@ fn type_annotation_to_html(
 
                     for type_value in &tag.values {
                         buf.push(' ');
-                        type_annotation_to_html(next_indent_level, buf, type_value, true);
+                        type_annotation_to_html(next_indent_level, buf, type_value, true, links);
                     }
 
                     if is_multiline {
@@ -565,23 +762,23 @@ fn type_annotation_to_html(
                 buf.push(']');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, links);
         }
         TypeAnnotation::BoundVariable(var_name) => {
             buf.push_str(var_name);
         }
         TypeAnnotation::Apply { name, parts } => {
             if parts.is_empty() {
-                buf.push_str(name);
+                push_type_name(buf, name, links);
             } else {
                 if needs_parens {
                     buf.push('(');
                 }
 
-                buf.push_str(name);
+                push_type_name(buf, name, links);
                 for part in parts {
                     buf.push(' ');
-                    type_annotation_to_html(indent_level, buf, part, true);
+                    type_annotation_to_html(indent_level, buf, part, true, links);
                 }
 
                 if needs_parens {
@@ -629,13 +826,25 @@ fn type_annotation_to_html(
                             type_annotation, ..
                         } => {
                             buf.push_str(" : ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                links,
+                            );
                         }
                         RecordField::OptionalField {
                             type_annotation, ..
                         } => {
                             buf.push_str(" ? ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                links,
+                            );
                         }
                         RecordField::LabelOnly { .. } => {}
                     }
@@ -658,7 +867,7 @@ fn type_annotation_to_html(
                 buf.push('}');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, links);
         }
         TypeAnnotation::Function { args, output } => {
             let mut paren_is_open = false;
@@ -677,7 +886,7 @@ fn type_annotation_to_html(
                 }
 
                 let child_needs_parens = matches!(arg, TypeAnnotation::Function { .. });
-                type_annotation_to_html(indent_level, buf, arg, child_needs_parens);
+                type_annotation_to_html(indent_level, buf, arg, child_needs_parens, links);
 
                 if peekable_args.peek().is_some() {
                     buf.push_str(", ");
@@ -699,7 +908,7 @@ fn type_annotation_to_html(
                 next_indent_level += 1;
             }
 
-            type_annotation_to_html(next_indent_level, buf, output, false);
+            type_annotation_to_html(next_indent_level, buf, output, false, links);
             if needs_parens && paren_is_open {
                 buf.push(')');
             }
@@ -723,7 +932,13 @@ fn type_annotation_to_html(
                 buf.push_str(&member.name);
                 buf.push_str(" : ");
 
-                type_annotation_to_html(indent_level + 1, buf, &member.type_annotation, false);
+                type_annotation_to_html(
+                    indent_level + 1,
+                    buf,
+                    &member.type_annotation,
+                    false,
+                    links,
+                );
 
                 if !member.able_variables.is_empty() {
                     new_line(buf);
@@ -747,7 +962,7 @@ fn type_annotation_to_html(
 
                             buf.push(' ');
 
-                            type_annotation_to_html(indent_level + 2, buf, ann, false);
+                            type_annotation_to_html(indent_level + 2, buf, ann, false, links);
                         }
                     }
                 }
@@ -783,7 +998,7 @@ fn type_annotation_to_html(
                     indent(buf, next_indent_level);
                 }
 
-                type_annotation_to_html(next_indent_level, buf, elem, false);
+                type_annotation_to_html(next_indent_level, buf, elem, false, links);
 
                 if is_multiline {
                     if index < (elems_len - 1) {
@@ -800,10 +1015,10 @@ fn type_annotation_to_html(
 
             buf.push(')');
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, links);
         }
         TypeAnnotation::Where { ann, implements } => {
-            type_annotation_to_html(indent_level, buf, ann, false);
+            type_annotation_to_html(indent_level, buf, ann, false, links);
 
             new_line(buf);
             indent(buf, indent_level + 1);
@@ -836,12 +1051,12 @@ fn type_annotation_to_html(
                         buf.push_str(" & ");
                     }
 
-                    type_annotation_to_html(indent_level, buf, ability, false);
+                    type_annotation_to_html(indent_level, buf, ability, false, links);
                 }
             }
         }
         TypeAnnotation::As { ann, name, vars } => {
-            type_annotation_to_html(indent_level, buf, ann, true);
+            type_annotation_to_html(indent_level, buf, ann, true, links);
             buf.push(' ');
             buf.push_str(name);
 
@@ -853,6 +1068,23 @@ fn type_annotation_to_html(
     }
 }
 
+/// Links the name of a type to its docs, if it has any
+fn push_type_name(buf: &mut String, name: &str, links: Option<&Links>) {
+    let (module_name, ident) = name.rsplit_once('.').unwrap_or(("", name));
+
+    match links.map(|links| doc_url(links, module_name, ident)) {
+        Some(Ok(DocUrl { url, title })) => {
+            push_html(
+                buf,
+                "a",
+                vec![("href", url.as_str()), ("title", title.as_str())],
+                name,
+            );
+        }
+        Some(Err(_)) | None => buf.push_str(name),
+    }
+}
+
 fn should_be_multiline(type_ann: &TypeAnnotation) -> bool {
     match type_ann {
         TypeAnnotation::TagUnion { tags, extension } => {
@@ -909,6 +1141,19 @@ struct DocUrl {
     title: String,
 }
 
+/// What we need to know to turn a name into a link to its docs
+struct Links<'a> {
+    all_exposed_symbols: &'a VecSet<Symbol>,
+    /// The modules we're generating docs for. Builtins that aren't among them get linked
+    /// to the builtins docs on roc-lang.org.
+    documented_modules: &'a VecSet<ModuleId>,
+    /// The scope of the module whose docs the name is in
+    scope: &'a Scope,
+    interns: &'a Interns,
+}
+
+const BUILTINS_DOCS_URL: &str = "https://www.roc-lang.org/builtins/";
+
 enum LinkProblem {
     MalformedAutoLink,
     AutoLinkIdentNotInScope,
@@ -917,55 +1162,31 @@ enum LinkProblem {
 }
 
 fn doc_url<'a>(
-    all_exposed_symbols: &VecSet<Symbol>,
-    scope: &Scope,
-    interns: &'a Interns,
-    mut module_name: &'a str,
+    links: &Links<'a>,
+    module_name: &str,
     ident: &str,
 ) -> Result<DocUrl, (String, LinkProblem)> {
-    if module_name.is_empty() {
+    let interns = links.interns;
+
+    let (symbol, link_markdown) = if module_name.is_empty() {
         // This is an unqualified lookup, so look for the ident
-        // in scope!
-        match scope.lookup_str(ident, Region::zero()) {
-            Ok(symbol) => {
-                // Get the exact module_name from scope. It could be the
-                // current module's name, but it also could be a different
-                // module - for example, if this is in scope from an
-                // unqualified import.
-                module_name = symbol.module_string(interns);
-            }
+        // in scope! It could be from the current module, but it also
+        // could be from a different one - for example, if this is in
+        // scope from an unqualified import.
+        match links.scope.lookup_str(ident, Region::zero()) {
+            Ok(symbol) => (symbol, format!("[{ident}]")),
             Err(_) => {
                 return Err((format!("[{ident}]"), LinkProblem::AutoLinkIdentNotInScope));
             }
         }
     } else {
+        // Note: You can do qualified lookups on your own module, e.g.
+        // if I'm in the Foo module, I can do a `Foo.bar` lookup.
         match interns.module_ids.get_id(&module_name.into()) {
-            Some(module_id) => {
-                let symbol = interns.symbol(module_id, ident.into());
-
-                if symbol.is_builtin() {
-                    // We can always generate links for builtin modules.
-                    // TODO add a `--include-builtins` CLI flag for generating offline docs locally
-                    // which include builtins; if that flag is omitted, have this code path generate
-                    // a link directly to the builtin docs on roc-lang.org instead of to a localhost
-                    // URL that will 404.
-                    module_name = symbol.module_string(interns);
-                }
-                // Note: You can do qualified lookups on your own module, e.g.
-                // if I'm in the Foo module, I can do a `Foo.bar` lookup.
-                else if !all_exposed_symbols.contains(&symbol) {
-                    return Err((
-                        format!("[{module_name}.{ident}]"),
-                        LinkProblem::AutoLinkNotExposed,
-                    ));
-                }
-
-                // This is a valid symbol for this dependency,
-                // so proceed using the current module's name.
-                //
-                // TODO: In the future, this is where we'll
-                // incorporate the package name into the link.
-            }
+            Some(module_id) => (
+                interns.symbol(module_id, ident.into()),
+                format!("[{module_name}.{ident}]"),
+            ),
             None => {
                 return Err((
                     format!("[{module_name}.{ident}]"),
@@ -973,9 +1194,25 @@ fn doc_url<'a>(
                 ));
             }
         }
-    }
+    };
+
+    // We can always generate links for builtins, since they're documented either
+    // alongside this package, or on roc-lang.org.
+    let mut url = if !symbol.is_builtin() {
+        if !links.all_exposed_symbols.contains(&symbol) {
+            return Err((link_markdown, LinkProblem::AutoLinkNotExposed));
+        }
+
+        // TODO: In the future, this is where we'll
+        // incorporate the package name into the link.
+        base_url()
+    } else if links.documented_modules.contains(&symbol.module_id()) {
+        base_url()
+    } else {
+        BUILTINS_DOCS_URL.to_string()
+    };
 
-    let mut url = base_url();
+    let module_name = symbol.module_string(interns);
 
     // Example:
     //
@@ -993,8 +1230,7 @@ fn doc_url<'a>(
 fn markdown_to_html(
     buf: &mut String,
     filename: &Path,
-    all_exposed_symbols: &VecSet<Symbol>,
-    scope: &Scope,
+    links: &Links,
     markdown: &str,
     loaded_module: &LoadedModule,
 ) {
@@ -1028,13 +1264,7 @@ fn markdown_to_html(
 
                         match iter.next() {
                             Some(Accessor::RecordField(symbol_name)) if iter.next().is_none() => {
-                                match doc_url(
-                                    all_exposed_symbols,
-                                    scope,
-                                    &loaded_module.interns,
-                                    module_name,
-                                    symbol_name,
-                                ) {
+                                match doc_url(links, module_name, symbol_name) {
                                     Ok(DocUrl { url, title }) => Some((url.into(), title.into())),
                                     Err((link_markdown, problem)) => {
                                         report_markdown_link_problem(
@@ -1062,13 +1292,7 @@ fn markdown_to_html(
                     Ok((_, Ident::Tag(type_name), _)) => {
                         // This looks like a tag name, but it could
                         // be a type alias that's in scope, e.g. [I64]
-                        match doc_url(
-                            all_exposed_symbols,
-                            scope,
-                            &loaded_module.interns,
-                            "",
-                            type_name,
-                        ) {
+                        match doc_url(links, "", type_name) {
                             Ok(DocUrl { url, title }) => Some((url.into(), title.into())),
                             Err((link_markdown, problem)) => {
                                 report_markdown_link_problem(
@@ -1246,3 +1470,357 @@ fn report_markdown_link_problem(
 
     report.render_color_terminal(&mut buf, &alloc, &palette);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use insta::assert_snapshot;
+
+    type FixturePackage = (
+        LoadedModule,
+        Vec<(ModuleId, ModuleDocumentation)>,
+        VecSet<Symbol>,
+        VecMap<Symbol, String>,
+    );
+
+    /// Loads the package in tests/fixtures/package, and gives back what the generators need.
+    fn load_fixture_package() -> FixturePackage {
+        let root_file = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("package")
+            .join("main.roc");
+
+        let mut loaded_module = load_module_for_docs(root_file);
+        let exposed_module_docs = get_exposed_module_docs(&mut loaded_module);
+        let all_exposed_symbols = all_exposed_symbols(&exposed_module_docs);
+        let inferred_types = infer_unannotated_types(
+            &mut loaded_module,
+            &exposed_module_docs,
+            &all_exposed_symbols,
+        );
+
+        (
+            loaded_module,
+            exposed_module_docs,
+            all_exposed_symbols,
+            inferred_types,
+        )
+    }
+
+    fn pretty_json(json: &str) -> String {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+
+        serde_json::to_string_pretty(&value).unwrap()
+    }
+
+    #[test]
+    fn inferred_types_of_unannotated_values() {
+        let (loaded_module, _, _, inferred_types) = load_fixture_package();
+
+        let mut lines = inferred_types
+            .iter()
+            .map(|(symbol, type_str)| {
+                format!(
+                    "{}.{} : {type_str}",
+                    symbol.module_string(&loaded_module.interns),
+                    symbol.as_str(&loaded_module.interns)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        lines.sort();
+
+        assert_snapshot!(lines.join("\n"), @r###"
+        Greet.describe : Shape -> Str
+        Shape.unit : [Square (Num *)]
+        "###);
+    }
+
+    #[test]
+    fn search_index() {
+        let (_, exposed_module_docs, all_exposed_symbols, inferred_types) = load_fixture_package();
+
+        let search_index =
+            render_search_index(&exposed_module_docs, &all_exposed_symbols, &inferred_types);
+
+        assert_snapshot!(pretty_json(&search_index), @r###"
+        [
+          {
+            "docs": "A shape that can be drawn.",
+            "module": "Shape",
+            "name": "Shape",
+            "type": "",
+            "url": "Shape#Shape"
+          },
+          {
+            "docs": "The area of a [Shape].",
+            "module": "Shape",
+            "name": "area",
+            "type": "Shape -> F64",
+            "url": "Shape#area"
+          },
+          {
+            "docs": "A square whose sides are 1 long.",
+            "module": "Shape",
+            "name": "unit",
+            "type": "[Square (Num *)]",
+            "url": "Shape#unit"
+          },
+          {
+            "docs": "Says hello to `name`.",
+            "module": "Greet",
+            "name": "greet",
+            "type": "Str -> Str",
+            "url": "Greet#greet"
+          },
+          {
+            "docs": "Describes a [Shape.Shape] by its area.",
+            "module": "Greet",
+            "name": "describe",
+            "type": "Shape -> Str",
+            "url": "Greet#describe"
+          }
+        ]
+        "###);
+    }
+
+    #[test]
+    fn docs_json() {
+        let (_, exposed_module_docs, all_exposed_symbols, inferred_types) = load_fixture_package();
+
+        let docs = json::docs_to_json(
+            "Documentation",
+            &exposed_module_docs,
+            &all_exposed_symbols,
+            &inferred_types,
+        );
+
+        assert_snapshot!(serde_json::to_string_pretty(&docs).unwrap(), @r###"
+        {
+          "modules": [
+            {
+              "entries": [
+                {
+                  "docs": {
+                    "children": [
+                      {
+                        "children": [
+                          {
+                            "text": "Shapes, and how much room they take up.",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      }
+                    ],
+                    "type": "document"
+                  },
+                  "kind": "moduleDoc"
+                },
+                {
+                  "docs": {
+                    "children": [
+                      {
+                        "children": [
+                          {
+                            "text": "A shape that can be drawn.",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      }
+                    ],
+                    "type": "document"
+                  },
+                  "inferredType": null,
+                  "kind": "def",
+                  "name": "Shape",
+                  "type": null,
+                  "typeVars": []
+                },
+                {
+                  "docs": {
+                    "children": [
+                      {
+                        "children": [
+                          {
+                            "text": "The area of a ",
+                            "type": "text"
+                          },
+                          {
+                            "children": [
+                              {
+                                "text": "Shape",
+                                "type": "text"
+                              }
+                            ],
+                            "name": "Shape",
+                            "type": "reference"
+                          },
+                          {
+                            "text": ".",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      },
+                      {
+                        "children": [
+                          {
+                            "text": "Circles use ",
+                            "type": "text"
+                          },
+                          {
+                            "children": [
+                              {
+                                "text": "Num.pi",
+                                "type": "text"
+                              }
+                            ],
+                            "name": "Num.pi",
+                            "type": "reference"
+                          },
+                          {
+                            "text": ".",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      }
+                    ],
+                    "type": "document"
+                  },
+                  "inferredType": null,
+                  "kind": "def",
+                  "name": "area",
+                  "type": {
+                    "args": [
+                      {
+                        "kind": "apply",
+                        "name": "Shape",
+                        "parts": []
+                      }
+                    ],
+                    "kind": "function",
+                    "output": {
+                      "kind": "apply",
+                      "name": "F64",
+                      "parts": []
+                    }
+                  },
+                  "typeVars": []
+                },
+                {
+                  "docs": {
+                    "children": [
+                      {
+                        "children": [
+                          {
+                            "text": "A square whose sides are 1 long.",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      }
+                    ],
+                    "type": "document"
+                  },
+                  "inferredType": "[Square (Num *)]",
+                  "kind": "def",
+                  "name": "unit",
+                  "type": null,
+                  "typeVars": []
+                }
+              ],
+              "name": "Shape"
+            },
+            {
+              "entries": [
+                {
+                  "docs": {
+                    "children": [
+                      {
+                        "children": [
+                          {
+                            "text": "Says hello to ",
+                            "type": "text"
+                          },
+                          {
+                            "text": "name",
+                            "type": "code"
+                          },
+                          {
+                            "text": ".",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      }
+                    ],
+                    "type": "document"
+                  },
+                  "inferredType": null,
+                  "kind": "def",
+                  "name": "greet",
+                  "type": {
+                    "args": [
+                      {
+                        "kind": "apply",
+                        "name": "Str",
+                        "parts": []
+                      }
+                    ],
+                    "kind": "function",
+                    "output": {
+                      "kind": "apply",
+                      "name": "Str",
+                      "parts": []
+                    }
+                  },
+                  "typeVars": []
+                },
+                {
+                  "docs": {
+                    "children": [
+                      {
+                        "children": [
+                          {
+                            "text": "Describes a ",
+                            "type": "text"
+                          },
+                          {
+                            "children": [
+                              {
+                                "text": "Shape.Shape",
+                                "type": "text"
+                              }
+                            ],
+                            "name": "Shape.Shape",
+                            "type": "reference"
+                          },
+                          {
+                            "text": " by its area.",
+                            "type": "text"
+                          }
+                        ],
+                        "type": "paragraph"
+                      }
+                    ],
+                    "type": "document"
+                  },
+                  "inferredType": "Shape -> Str",
+                  "kind": "def",
+                  "name": "describe",
+                  "type": null,
+                  "typeVars": []
+                }
+              ],
+              "name": "Greet"
+            }
+          ],
+          "package": "Documentation"
+        }
+        "###);
+    }
+}
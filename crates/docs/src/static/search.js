@@ -1,65 +1,135 @@
 (() => {
     let sidebar = document.getElementById("sidebar-nav");
     let searchBox = document.getElementById("module-search");
+    let searchResults = document.getElementById("search-results");
 
-    if (searchBox != null) {
-        function search() {
-            let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
-
-            if (text === "") {
-                // Un-hide everything
-                sidebar
-                    .querySelectorAll(".sidebar-entry a")
-                    .forEach((entry) => entry.classList.remove("hidden"));
-
-                // Re-hide all the sub-entries except for those of the current module
-                let currentModuleName =
-                    document.querySelector(".module-name").textContent;
-
-                sidebar.querySelectorAll(".sidebar-entry").forEach((entry) => {
-                    let entryName = entry.querySelector(
-                        ".sidebar-module-link"
-                    ).textContent;
-                    if (currentModuleName === entryName) {
-                        entry.firstChild.classList.add("active");
-                        return;
+    // Every exposed entry in every module, written by `roc docs`
+    let searchIndex = [];
+
+    fetch("search-index.json")
+        .then((response) => response.json())
+        .then((entries) => {
+            searchIndex = entries;
+
+            if (searchBox != null && searchBox.value !== "") {
+                search();
+            }
+        })
+        .catch(() => {
+            // Without the index, search still filters the sidebar
+        });
+
+    function showSearchResults(text) {
+        if (searchResults == null) {
+            return;
+        }
+
+        searchResults.replaceChildren();
+
+        if (text === "") {
+            searchResults.classList.add("hidden");
+            return;
+        }
+
+        searchIndex
+            .filter(
+                (entry) =>
+                    entry.name.toLowerCase().includes(text) ||
+                    `${entry.module}.${entry.name}`.toLowerCase().includes(text) ||
+                    entry.type.toLowerCase().includes(text)
+            )
+            .slice(0, 50)
+            .forEach((entry) => {
+                let item = document.createElement("li");
+                let link = document.createElement("a");
+                link.href = entry.url;
+
+                let name = document.createElement("strong");
+                name.textContent = `${entry.module}.${entry.name}`;
+                link.appendChild(name);
+
+                if (entry.type !== "") {
+                    let type = document.createElement("code");
+                    type.textContent = ` : ${entry.type}`;
+                    link.appendChild(type);
+                }
+
+                if (entry.docs !== "") {
+                    let docs = document.createElement("p");
+                    docs.textContent = entry.docs;
+                    link.appendChild(docs);
+                }
+
+                item.appendChild(link);
+                searchResults.appendChild(item);
+            });
+
+        searchResults.classList.toggle(
+            "hidden",
+            searchResults.children.length === 0
+        );
+    }
+
+    function search() {
+        let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
+
+        showSearchResults(text);
+
+        if (text === "") {
+            // Un-hide everything
+            sidebar
+                .querySelectorAll(".sidebar-entry a")
+                .forEach((entry) => entry.classList.remove("hidden"));
+
+            // Re-hide all the sub-entries except for those of the current module
+            let currentModuleName =
+                document.querySelector(".module-name").textContent;
+
+            sidebar.querySelectorAll(".sidebar-entry").forEach((entry) => {
+                let entryName = entry.querySelector(
+                    ".sidebar-module-link"
+                ).textContent;
+                if (currentModuleName === entryName) {
+                    entry.firstChild.classList.add("active");
+                    return;
+                }
+                entry
+                    .querySelectorAll(".sidebar-sub-entries a")
+                    .forEach((subEntry) =>
+                        subEntry.classList.add("hidden")
+                    );
+            });
+        } else {
+            // First, show/hide all the sub-entries within each module (top-level functions etc.)
+            sidebar
+                .querySelectorAll(".sidebar-sub-entries a")
+                .forEach((entry) => {
+                    if (entry.textContent.toLowerCase().includes(text)) {
+                        entry.classList.remove("hidden");
+                    } else {
+                        entry.classList.add("hidden");
+                    }
+                });
+
+            // Then, show/hide modules based on whether they match, or any of their sub-entries matched
+            sidebar
+                .querySelectorAll(".sidebar-module-link")
+                .forEach((entry) => {
+                    if (
+                        entry.textContent.toLowerCase().includes(text) ||
+                        entry.parentNode.querySelectorAll(
+                            ".sidebar-sub-entries a:not(.hidden)"
+                        ).length > 0
+                    ) {
+                        entry.classList.remove("hidden");
+                    } else {
+                        entry.classList.add("hidden");
                     }
-                    entry
-                        .querySelectorAll(".sidebar-sub-entries a")
-                        .forEach((subEntry) =>
-                            subEntry.classList.add("hidden")
-                        );
                 });
-            } else {
-                // First, show/hide all the sub-entries within each module (top-level functions etc.)
-                sidebar
-                    .querySelectorAll(".sidebar-sub-entries a")
-                    .forEach((entry) => {
-                        if (entry.textContent.toLowerCase().includes(text)) {
-                            entry.classList.remove("hidden");
-                        } else {
-                            entry.classList.add("hidden");
-                        }
-                    });
-
-                // Then, show/hide modules based on whether they match, or any of their sub-entries matched
-                sidebar
-                    .querySelectorAll(".sidebar-module-link")
-                    .forEach((entry) => {
-                        if (
-                            entry.textContent.toLowerCase().includes(text) ||
-                            entry.parentNode.querySelectorAll(
-                                ".sidebar-sub-entries a:not(.hidden)"
-                            ).length > 0
-                        ) {
-                            entry.classList.remove("hidden");
-                        } else {
-                            entry.classList.add("hidden");
-                        }
-                    });
-            }
         }
+    }
 
+    if (searchBox != null) {
         searchBox.addEventListener("input", search);
 
         search();
@@ -0,0 +1,11 @@
+module [greet, describe]
+
+import Shape
+
+## Says hello to `name`.
+greet : Str -> Str
+greet = \name -> Str.concat "Hello, " name
+
+## Describes a [Shape.Shape] by its area.
+describe = \shape ->
+    Str.concat "Something with an area of " (Num.toStr (Shape.area shape))
@@ -0,0 +1,17 @@
+## Shapes, and how much room they take up.
+module [Shape, area, unit]
+
+## A shape that can be drawn.
+Shape : [Circle F64, Square F64]
+
+## The area of a [Shape].
+##
+## Circles use [Num.pi].
+area : Shape -> F64
+area = \shape ->
+    when shape is
+        Circle radius -> Num.pi * radius * radius
+        Square side -> side * side
+
+## A square whose sides are 1 long.
+unit = Square 1
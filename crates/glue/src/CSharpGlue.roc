@@ -0,0 +1,781 @@
+app "csharp-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [
+        pf.Types.{ Types },
+        pf.Shape.{ Shape },
+        pf.File.{ File },
+        pf.TypeId.{ TypeId },
+        "../static/RocStd.cs" as rocStd : Str,
+        "../static/RocHost.cs" as rocHost : Str,
+    ]
+    provides [makeGlue] to pf
+
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    # .NET hosts are 64-bit, and the 64-bit architectures all lay out Roc values the same way,
+    # so one file covers all of them.
+    when List.findFirst typesByArch is64Bit is
+        Ok types ->
+            convertTypesToFile types
+            |> Result.map \file -> List.prepend staticFiles file
+
+        Err NotFound ->
+            Err "The C# glue only supports 64-bit targets."
+
+## These are always included, and don't depend on the specifics of the app.
+staticFiles : List File
+staticFiles = [
+    { name: "RocApp/RocStd.cs", content: rocStd },
+    { name: "RocApp/RocHost.cs", content: rocHost },
+]
+
+is64Bit : Types -> Bool
+is64Bit = \types ->
+    when (Types.target types).architecture is
+        Aarch64 | X86x64 -> Bool.true
+        Aarch32 | Wasm32 | X86x32 -> Bool.false
+
+convertTypesToFile : Types -> Result File Str
+convertTypesToFile = \types ->
+    # Every instance of Result gets its own type, so this tracks which ones are declared already.
+    init = Ok { buf: fileHeader, results: Set.empty {} }
+
+    declarations =
+        Types.walkShapes types init \state, shape, id ->
+            Result.try state \declared -> generateDeclaration declared types shape id
+
+    Result.try declarations \{ buf } ->
+        generateEntryPoints buf types
+        |> Result.map \content -> { name: "RocApp/RocApp.cs", content }
+
+generateDeclaration = \state, types, shape, id ->
+    when shape is
+        Struct { name, fields } ->
+            Ok { state & buf: generateStruct state.buf types id name (structFields fields) }
+
+        TagUnionPayload { name, fields } ->
+            # Tag union payloads have numbered fields, so we prefix them
+            # with an "F" because C# doesn't allow fields to be numbers.
+            payloadFields =
+                structFields fields
+                |> List.map \field -> { field & name: "F$(field.name)" }
+
+            Ok { state & buf: generateStruct state.buf types id name payloadFields }
+
+        TagUnion (SingleTagStruct { name, payload: HasNoClosure fields }) ->
+            payloadFields =
+                List.mapWithIndex fields \{ id: fieldId }, index ->
+                    indexStr = Num.toStr index
+
+                    { name: "F$(indexStr)", id: fieldId }
+
+            Ok { state & buf: generateStruct state.buf types id name payloadFields }
+
+        TagUnion (SingleTagStruct { name, payload: HasClosure _ }) ->
+            Err "The C# glue doesn't support closures yet, which $(name) has."
+
+        TagUnion (Enumeration { name, tags, size }) ->
+            Ok { state & buf: generateEnumeration state.buf name tags size }
+
+        TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
+            if List.isEmpty tags then
+                Ok state
+            else
+                Ok { state & buf: generateTagUnion state.buf types id (escapeKW name) tags discriminantSize discriminantOffset }
+
+        TagUnion (Recursive { name }) ->
+            Ok { state & buf: generateRecursiveTagUnion state.buf name }
+
+        TagUnion (NullableWrapped { name }) ->
+            Ok { state & buf: generateRecursiveTagUnion state.buf name }
+
+        TagUnion (NullableUnwrapped { name }) ->
+            Ok { state & buf: generateRecursiveTagUnion state.buf name }
+
+        TagUnion (NonNullableUnwrapped { name }) ->
+            Ok { state & buf: generateRecursiveTagUnion state.buf name }
+
+        RocResult okId errId ->
+            name = typeName types id
+
+            if Set.contains state.results name then
+                Ok state
+            else
+                # Result is a tag union like any other, so its tags are in alphabetical order.
+                tags = [
+                    { name: "Err", payload: Some errId },
+                    { name: "Ok", payload: Some okId },
+                ]
+                discriminantOffset =
+                    Num.max (Types.size types okId) (Types.size types errId)
+                    |> roundUpToAlignment (Num.max (Types.alignment types okId) (Types.alignment types errId))
+
+                Ok {
+                    buf: generateTagUnion state.buf types id name tags 1 discriminantOffset,
+                    results: Set.insert state.results name,
+                }
+
+        RocDict _ _ | RocSet _ ->
+            Err "The C# glue doesn't support Dict or Set yet."
+
+        Function rocFn ->
+            if rocFn.isToplevel then
+                Ok state
+            else
+                Err "The C# glue doesn't support closures yet, like $(rocFn.functionName)."
+
+        RecursivePointer _
+        | Unit
+        | Unsized
+        | EmptyTagUnion
+        | Num _
+        | Bool
+        | RocStr
+        | RocList _
+        | RocBox _ ->
+            # These are in RocStd.cs, or are built into C#.
+            Ok state
+
+structFields = \fields ->
+    when fields is
+        HasNoClosure list -> List.map list \{ name, id } -> { name, id }
+        HasClosure list -> List.map list \{ name, id } -> { name, id }
+
+generateStruct : Str, Types, TypeId, Str, List { name : Str, id : TypeId } -> Str
+generateStruct = \buf, types, id, name, fields ->
+    escapedName = escapeKW name
+    size = Num.toStr (Types.size types id)
+
+    # Fields of `{}` don't take up any room in Roc, but they would in C#.
+    sizedFields = List.dropIf fields \{ id: fieldId } -> isUnit (Types.shape types fieldId)
+
+    declarations =
+        sizedFields
+        |> List.map \{ name: fieldName, id: fieldId } ->
+            type = typeName types fieldId
+            escapedFieldName = escapeKW fieldName
+
+            "$(indent)public $(type) $(escapedFieldName);\n"
+        |> Str.joinWith ""
+
+    refcountedFields =
+        sizedFields
+        |> List.keepIf \{ id: fieldId } -> isRefcounted types (Types.shape types fieldId)
+        |> List.map \{ name: fieldName } -> escapeKW fieldName
+
+    debugFields =
+        sizedFields
+        |> List.map \{ name: fieldName } ->
+            escapedFieldName = escapeKW fieldName
+
+            "$(fieldName): {$(escapedFieldName)}"
+        |> Str.joinWith ", "
+
+    interface = if List.isEmpty refcountedFields then "" else " : IRocRefcounted"
+    refcounting = generateStructRefcounting refcountedFields
+
+    """
+    $(buf)
+    [StructLayout(LayoutKind.Sequential, Size = $(size))]
+    public struct $(escapedName)$(interface)
+    {
+    $(declarations)$(refcounting)
+        public override string ToString() => $"$(escapedName) {{ $(debugFields) }}";
+    }
+
+    """
+
+generateStructRefcounting : List Str -> Str
+generateStructRefcounting = \fields ->
+    if List.isEmpty fields then
+        ""
+    else
+        calls = \method ->
+            fields
+            |> List.map \field -> "$(indent)$(indent)$(field).$(method)();\n"
+            |> Str.joinWith ""
+        increments = calls "IncrementRefcounts"
+        decrements = calls "DecrementRefcounts"
+
+        """
+
+            public void IncrementRefcounts()
+            {
+        $(increments)    }
+
+            public void DecrementRefcounts()
+            {
+        $(decrements)    }
+
+        """
+
+generateEnumeration : Str, Str, List Str, U32 -> Str
+generateEnumeration = \buf, name, tags, size ->
+    escapedName = escapeKW name
+    baseType = discriminantType size
+    variants =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            indexStr = Num.toStr index
+
+            "$(indent)$(tag) = $(indexStr),\n"
+        |> Str.joinWith ""
+
+    """
+    $(buf)
+    public enum $(escapedName) : $(baseType)
+    {
+    $(variants)}
+
+    """
+
+generateTagUnion : Str, Types, TypeId, Str, List { name : Str, payload : [Some TypeId, None] }, U32, U32 -> Str
+generateTagUnion = \buf, types, id, name, tags, discriminantSize, discriminantOffset ->
+    discriminantName = "Discriminant$(name)"
+    size = Num.toStr (Types.size types id)
+    discriminantOffsetStr = Num.toStr discriminantOffset
+
+    # Payloads of `{}` don't take up any room in Roc, but they would in C#.
+    sizedTags =
+        List.map tags \tag ->
+            when tag.payload is
+                Some payloadId if isUnit (Types.shape types payloadId) -> { tag & payload: None }
+                _ -> tag
+
+    payloadFields =
+        sizedTags
+        |> List.map \{ name: tagName, payload } ->
+            when payload is
+                Some payloadId ->
+                    type = typeName types payloadId
+
+                    "$(indent)[FieldOffset(0)]\n$(indent)private $(type) payload$(tagName);\n"
+
+                None ->
+                    ""
+        |> Str.joinWith ""
+
+    constructors =
+        sizedTags
+        |> List.map \{ name: tagName, payload } ->
+            when payload is
+                Some payloadId ->
+                    type = typeName types payloadId
+
+                    """
+                        public static $(name) $(tagName)($(type) payload) =>
+                            new() { discriminant = $(discriminantName).$(tagName), payload$(tagName) = payload };
+
+                    """
+
+                None ->
+                    """
+                        public static $(name) $(tagName)() => new() { discriminant = $(discriminantName).$(tagName) };
+
+                    """
+        |> Str.joinWith "\n"
+
+    accessors =
+        sizedTags
+        |> List.map \{ name: tagName, payload } ->
+            isTag =
+                """
+                    public bool Is$(tagName) => discriminant == $(discriminantName).$(tagName);
+
+                """
+
+            when payload is
+                Some payloadId ->
+                    type = typeName types payloadId
+
+                    """
+                    $(isTag)
+                        /// <summary>The payload of `$(tagName)`. Throws if this is a different tag.</summary>
+                        public $(type) Unwrap$(tagName)() =>
+                            Is$(tagName) ? payload$(tagName) : throw new InvalidOperationException($"Expected $(name).$(tagName), but it was {discriminant}");
+
+                    """
+
+                None ->
+                    isTag
+        |> Str.joinWith "\n"
+
+    refcountedTags =
+        sizedTags
+        |> List.keepIf \{ payload } ->
+            when payload is
+                Some payloadId -> isRefcounted types (Types.shape types payloadId)
+                None -> Bool.false
+        |> List.map .name
+
+    debugCases =
+        sizedTags
+        |> List.map \{ name: tagName, payload } ->
+            when payload is
+                Some _ -> "$(indent)$(indent)$(discriminantName).$(tagName) => $\"$(name).$(tagName)({payload$(tagName)})\",\n"
+                None -> "$(indent)$(indent)$(discriminantName).$(tagName) => \"$(name).$(tagName)\",\n"
+        |> Str.joinWith ""
+
+    interface = if List.isEmpty refcountedTags then "" else " : IRocRefcounted"
+    discriminantEnum = generateEnumeration "" discriminantName (List.map tags .name) discriminantSize
+    refcounting = generateTagUnionRefcounting discriminantName refcountedTags
+
+    """
+    $(buf)
+    $(discriminantEnum)
+    [StructLayout(LayoutKind.Explicit, Size = $(size))]
+    public struct $(name)$(interface)
+    {
+    $(payloadFields)    [FieldOffset($(discriminantOffsetStr))]
+        private $(discriminantName) discriminant;
+
+        /// <summary>Which tag this is. Note that this never includes a payload!</summary>
+        public $(discriminantName) Discriminant => discriminant;
+
+    $(constructors)
+    $(accessors)$(refcounting)
+        public override string ToString() => discriminant switch
+        {
+    $(debugCases)        _ => $"$(name)({discriminant})",
+        };
+    }
+
+    """
+
+generateTagUnionRefcounting : Str, List Str -> Str
+generateTagUnionRefcounting = \discriminantName, tagNames ->
+    if List.isEmpty tagNames then
+        ""
+    else
+        cases = \method ->
+            tagNames
+            |> List.map \tagName ->
+                """
+                            case $(discriminantName).$(tagName):
+                                payload$(tagName).$(method)();
+                                break;
+
+                """
+            |> Str.joinWith ""
+        increments = cases "IncrementRefcounts"
+        decrements = cases "DecrementRefcounts"
+
+        """
+
+            public void IncrementRefcounts()
+            {
+                switch (discriminant)
+                {
+        $(increments)        }
+            }
+
+            public void DecrementRefcounts()
+            {
+                switch (discriminant)
+                {
+        $(decrements)        }
+            }
+
+        """
+
+generateRecursiveTagUnion : Str, Str -> Str
+generateRecursiveTagUnion = \buf, name ->
+    escapedName = escapeKW name
+
+    """
+    $(buf)
+    /// <summary>
+    /// A recursive tag union. The C# glue doesn't generate accessors for these yet,
+    /// so hosts can only pass them along to Roc.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    public struct $(escapedName)
+    {
+        private IntPtr pointer;
+    }
+
+    """
+
+generateEntryPoints : Str, Types -> Result Str Str
+generateEntryPoints = \buf, types ->
+    methods =
+        List.walk (Types.entryPoints types) (Ok "") \state, T name id ->
+            Result.try state \accum -> generateEntryPoint accum types name id
+
+    Result.map methods \methodsStr ->
+        """
+        $(buf)
+        public static unsafe class RocApp
+        {
+            /// <summary>
+            /// The library the app is built into. The runtime looks for it as `app.so`, `app.dylib`, or `app.dll`,
+            /// and Native AOT hosts can link `app.o` in statically with `<DirectPInvoke Include="app" />`.
+            /// </summary>
+            public const string LibraryName = "app";
+        $(methodsStr)}
+
+        """
+
+generateEntryPoint : Str, Types, Str, TypeId -> Result Str Str
+generateEntryPoint = \buf, types, name, id ->
+    (args, ret) =
+        when Types.shape types id is
+            Function rocFn -> (rocFn.args, rocFn.ret)
+            _ -> ([], id)
+
+    when Types.shape types ret is
+        Function _ ->
+            Err "The C# glue doesn't support entry points that return functions yet, like $(name)."
+
+        retShape ->
+            methodName = toPascalCase name
+
+            # Drop `{}` args; nothing gets passed for them anyway.
+            params =
+                args
+                |> List.mapWithIndex \argId, index ->
+                    indexStr = Num.toStr index
+
+                    { name: "arg$(indexStr)", id: argId }
+                |> List.dropIf \{ id: argId } -> isUnit (Types.shape types argId)
+
+            publicParams =
+                params
+                |> List.map \{ name: argName, id: argId } ->
+                    type = typeName types argId
+
+                    "$(type) $(argName)"
+                |> Str.joinWith ", "
+
+            # Roc takes arguments that have memory to manage by pointer.
+            externParams =
+                params
+                |> List.map \{ name: argName, id: argId } ->
+                    type = typeName types argId
+
+                    if isPassedByValue types (Types.shape types argId) then
+                        ", $(type) $(argName)"
+                    else
+                        ", $(type)* $(argName)"
+                |> Str.joinWith ""
+
+            externArgs =
+                params
+                |> List.map \{ name: argName, id: argId } ->
+                    if isPassedByValue types (Types.shape types argId) then
+                        ", $(argName)"
+                    else
+                        ", &$(argName)"
+                |> Str.joinWith ""
+
+            body =
+                if isUnit retShape then
+                    """
+                            $(methodName)Generic(null$(externArgs));
+                    """
+                else
+                    retType = typeName types ret
+
+                    """
+                            $(retType) ret;
+                            $(methodName)Generic(&ret$(externArgs));
+
+                            return ret;
+                    """
+
+            (publicRet, retParam) =
+                if isUnit retShape then
+                    ("void", "void*")
+                else
+                    retType = typeName types ret
+
+                    (retType, "$(retType)*")
+
+            Ok
+                """
+                $(buf)
+                    [DllImport(LibraryName, EntryPoint = "roc__$(name)_1_exposed_generic")]
+                    private static extern void $(methodName)Generic($(retParam) ret$(externParams));
+
+                    /// <summary>
+                    /// Calls `$(name)`. Roc takes over the references the arguments hold, and the caller owns the ones in the result.
+                    /// </summary>
+                    public static $(publicRet) $(methodName)($(publicParams))
+                    {
+                $(body)
+                    }
+
+                """
+
+typeName : Types, TypeId -> Str
+typeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "RocUnit"
+        Unsized -> "RocList<byte>"
+        EmptyTagUnion -> "RocUnit"
+        RocStr -> "RocStr"
+        Bool -> "bool"
+        Num U8 -> "byte"
+        Num U16 -> "ushort"
+        Num U32 -> "uint"
+        Num U64 -> "ulong"
+        Num U128 -> "UInt128"
+        Num I8 -> "sbyte"
+        Num I16 -> "short"
+        Num I32 -> "int"
+        Num I64 -> "long"
+        Num I128 -> "Int128"
+        Num F32 -> "float"
+        Num F64 -> "double"
+        Num Dec -> "RocDec"
+        RocDict key value ->
+            keyName = typeName types key
+            valueName = typeName types value
+
+            "RocDict<$(keyName), $(valueName)>"
+
+        RocSet elem ->
+            elemName = typeName types elem
+
+            "RocSet<$(elemName)>"
+
+        RocList elem ->
+            elemName = typeName types elem
+
+            "RocList<$(elemName)>"
+
+        RocBox elem ->
+            elemName = typeName types elem
+
+            "RocBox<$(elemName)>"
+
+        RocResult ok err ->
+            # C# can't overlap the fields of a generic struct, so every instance of Result is its own type.
+            okName = identifierName types ok
+            errName = identifierName types err
+
+            "RocResult_$(okName)_$(errName)"
+
+        RecursivePointer content ->
+            typeName types content
+
+        Struct { name } -> escapeKW name
+        TagUnionPayload { name } -> escapeKW name
+        TagUnion (NonRecursive { name }) -> escapeKW name
+        TagUnion (Recursive { name }) -> escapeKW name
+        TagUnion (Enumeration { name }) -> escapeKW name
+        TagUnion (NullableWrapped { name }) -> escapeKW name
+        TagUnion (NullableUnwrapped { name }) -> escapeKW name
+        TagUnion (NonNullableUnwrapped { name }) -> escapeKW name
+        TagUnion (SingleTagStruct { name }) -> escapeKW name
+        Function { functionName } -> escapeKW functionName
+
+## The name of a type, in a form that can be part of another type's name, like `RocListOfRocStr`
+identifierName : Types, TypeId -> Str
+identifierName = \types, id ->
+    typeName types id
+    |> Str.replaceEach "<" "Of"
+    |> Str.replaceEach ", " "And"
+    |> Str.replaceEach ">" ""
+    |> Str.replaceEach "@" ""
+
+discriminantType : U32 -> Str
+discriminantType = \size ->
+    when size is
+        1 -> "byte"
+        2 -> "ushort"
+        4 -> "uint"
+        _ -> "ulong"
+
+## Whether the type implements IRocRefcounted, because it holds references to memory on the Roc heap.
+isRefcounted : Types, Shape -> Bool
+isRefcounted = \types, shape ->
+    when shape is
+        RocStr | RocList _ | RocBox _ -> Bool.true
+        # Recursive tag unions are opaque, so the host can't release them.
+        TagUnion (Recursive _) | TagUnion (NullableWrapped _) | TagUnion (NullableUnwrapped _) | TagUnion (NonNullableUnwrapped _) | RecursivePointer _ -> Bool.false
+        Unit | Unsized | EmptyTagUnion | Bool | Num _ | TagUnion (Enumeration _) | RocDict _ _ | RocSet _ | Function _ -> Bool.false
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.any fields \{ id } -> isRefcounted types (Types.shape types id)
+
+        TagUnion (SingleTagStruct { payload: HasClosure fields }) ->
+            List.any fields \{ id } -> isRefcounted types (Types.shape types id)
+
+        TagUnion (NonRecursive { tags }) ->
+            List.any tags \{ payload } ->
+                when payload is
+                    Some id -> isRefcounted types (Types.shape types id)
+                    None -> Bool.false
+
+        RocResult okId errId ->
+            isRefcounted types (Types.shape types okId)
+            || isRefcounted types (Types.shape types errId)
+
+        Struct { fields } | TagUnionPayload { fields } ->
+            List.any (structFields fields) \{ id } -> isRefcounted types (Types.shape types id)
+
+## Whether Roc takes arguments of this type by value, rather than by pointer.
+isPassedByValue : Types, Shape -> Bool
+isPassedByValue = \types, shape ->
+    when shape is
+        Function rocFn ->
+            isPassedByValue types (Types.shape types rocFn.lambdaSet)
+
+        # unsized values are heap-allocated
+        Unsized -> Bool.false
+        Unit | EmptyTagUnion | Bool | Num _ | TagUnion (Enumeration _) -> Bool.true
+        RocStr | RocList _ | RocDict _ _ | RocSet _ | RocBox _ | TagUnion (NullableUnwrapped _) | TagUnion (NullableWrapped _) | TagUnion (Recursive _) | TagUnion (NonNullableUnwrapped _) | RecursivePointer _ -> Bool.false
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.all fields \{ id } -> isPassedByValue types (Types.shape types id)
+
+        TagUnion (SingleTagStruct { payload: HasClosure fields }) ->
+            List.all fields \{ id } -> isPassedByValue types (Types.shape types id)
+
+        TagUnion (NonRecursive { tags }) ->
+            List.all tags \{ payload } ->
+                when payload is
+                    Some id -> isPassedByValue types (Types.shape types id)
+                    None -> Bool.true
+
+        RocResult okId errId ->
+            isPassedByValue types (Types.shape types okId)
+            && isPassedByValue types (Types.shape types errId)
+
+        Struct { fields } | TagUnionPayload { fields } ->
+            List.all (structFields fields) \{ id } -> isPassedByValue types (Types.shape types id)
+
+isUnit : Shape -> Bool
+isUnit = \shape ->
+    when shape is
+        Unit -> Bool.true
+        _ -> Bool.false
+
+roundUpToAlignment = \width, alignment ->
+    when alignment is
+        0 -> width
+        1 -> width
+        _ ->
+            if width % alignment > 0 then
+                width + alignment - (width % alignment)
+            else
+                width
+
+toPascalCase : Str -> Str
+toPascalCase = \name ->
+    bytes = Str.toUtf8 name
+
+    when List.first bytes is
+        Ok first if first >= 'a' && first <= 'z' ->
+            List.set bytes 0 (first - 'a' + 'A')
+            |> Str.fromUtf8
+            |> Result.withDefault name
+
+        _ ->
+            name
+
+fileHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    #nullable enable
+
+    using System;
+    using System.Runtime.CompilerServices;
+    using System.Runtime.InteropServices;
+    using Roc;
+
+    // Roc's Bool is one byte, and Roc expects structs exactly as they're laid out here.
+    [assembly: DisableRuntimeMarshalling]
+
+    namespace Roc.App;
+
+    """
+
+indent = "    "
+
+reservedKeywords = Set.fromList [
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+]
+
+escapeKW = \input ->
+    # use a verbatim identifier for this, to prevent a syntax error due to using a reserved keyword.
+    if Set.contains reservedKeywords input then
+        "@$(input)"
+    else
+        input
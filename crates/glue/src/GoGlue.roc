@@ -0,0 +1,724 @@
+app "go-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [
+        pf.Types.{ Types },
+        pf.Shape.{ Shape },
+        pf.File.{ File },
+        pf.TypeId.{ TypeId },
+        "../static/RocStd.h" as rocStdH : Str,
+        "../static/rocstd.go" as rocStdGo : Str,
+        "../static/rochost.go" as rocHostGo : Str,
+    ]
+    provides [makeGlue] to pf
+
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    # The 64-bit architectures all lay out Roc values the same way, so one set of files covers all of them.
+    when List.findFirst typesByArch is64Bit is
+        Ok types ->
+            convertTypesToFiles types
+            |> Result.map \files -> List.concat files staticFiles
+
+        Err NotFound ->
+            Err "The Go glue only supports 64-bit targets."
+
+## These are always included, and don't depend on the specifics of the app.
+staticFiles : List File
+staticFiles = [
+    { name: "go.mod", content: goMod },
+    { name: "RocStd.h", content: rocStdH },
+    { name: "rocstd.go", content: rocStdGo },
+    { name: "rochost.go", content: rocHostGo },
+]
+
+goMod =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    module rocapp
+
+    go 1.21
+
+    """
+
+is64Bit : Types -> Bool
+is64Bit = \types ->
+    when (Types.target types).architecture is
+        Aarch64 | X86x64 -> Bool.true
+        Aarch32 | Wasm32 | X86x32 -> Bool.false
+
+## The app's types go in two files: C declarations in roc_app.h, which say how Roc lays them out,
+## and Go types in roc_app.go, which convert to and from those.
+convertTypesToFiles : Types -> Result (List File) Str
+convertTypesToFiles = \types ->
+    # C needs every type declared before the types that contain it, and forward declarations
+    # let the recursive ones point to each other.
+    init = Ok { cForward: "", cDefinitions: "", go: goHeader }
+
+    declarations =
+        Types.walkShapes types init \state, shape, id ->
+            Result.try state \declared -> generateDeclaration declared types shape id
+
+    Result.try declarations \{ cForward, cDefinitions, go } ->
+        Result.map (generateEntryPoints types) \entryPoints ->
+            header = Str.joinWith [cHeader, cForward, cDefinitions, entryPoints.c] "\n"
+
+            [
+                { name: "roc_app.h", content: header },
+                { name: "roc_app.go", content: Str.concat go entryPoints.go },
+            ]
+
+generateDeclaration = \state, types, shape, id ->
+    when shape is
+        Struct { name, fields } ->
+            Ok (generateStruct state types id name (structFields fields))
+
+        TagUnionPayload { name, fields } ->
+            # Tag union payloads have numbered fields, so we prefix them
+            # with an "f" because neither C nor Go allow fields to be numbers.
+            payloadFields =
+                structFields fields
+                |> List.map \field -> { field & name: "f$(field.name)" }
+
+            Ok (generateStruct state types id name payloadFields)
+
+        TagUnion (SingleTagStruct { name, payload: HasNoClosure fields }) ->
+            payloadFields =
+                List.mapWithIndex fields \{ id: fieldId }, index ->
+                    indexStr = Num.toStr index
+
+                    { name: "f$(indexStr)", id: fieldId }
+
+            Ok (generateStruct state types id name payloadFields)
+
+        TagUnion (Enumeration { name, tags, size }) ->
+            Ok (generateEnumeration state name tags size)
+
+        TagUnion (NullableUnwrapped { name, nullTag, nonNullTag, nonNullPayload }) ->
+            Ok (generateNullableUnwrapped state types name nullTag nonNullTag nonNullPayload)
+
+        TagUnion (SingleTagStruct { name, payload: HasClosure _ }) ->
+            Err "The Go glue doesn't support closures yet, which $(name) has."
+
+        TagUnion (NonRecursive { name }) ->
+            Err "The Go glue doesn't support tag unions with payloads yet, like $(name)."
+
+        TagUnion (Recursive { name }) | TagUnion (NullableWrapped { name }) | TagUnion (NonNullableUnwrapped { name }) ->
+            Err "The Go glue only supports recursive tag unions with one tag that has a payload and one that doesn't, unlike $(name)."
+
+        Num Dec ->
+            Err "The Go glue doesn't support Dec yet."
+
+        RocResult _ _ ->
+            Err "The Go glue doesn't support Result yet."
+
+        RocDict _ _ | RocSet _ | RocBox _ ->
+            Err "The Go glue doesn't support Dict, Set, or Box yet."
+
+        Unsized | EmptyTagUnion ->
+            Err "The Go glue doesn't support types that have no values or no size."
+
+        Function rocFn ->
+            if rocFn.isToplevel then
+                Ok state
+            else
+                Err "The Go glue doesn't support closures yet, like $(rocFn.functionName)."
+
+        RecursivePointer _ | Unit | Num _ | Bool | RocStr | RocList _ ->
+            # These are in RocStd.h and rocstd.go, or are built into Go.
+            Ok state
+
+structFields = \fields ->
+    when fields is
+        HasNoClosure list -> List.map list \{ name, id } -> { name, id }
+        HasClosure list -> List.map list \{ name, id } -> { name, id }
+
+generateStruct = \state, types, id, name, fields ->
+    cName = cTypeName types id
+    goName = toPascalCase name
+    size = Num.toStr (Types.size types id)
+    align = Num.toStr (Types.alignment types id)
+
+    # Fields of `{}` don't take up any room in Roc, but they would in C.
+    sizedFields =
+        fields
+        |> List.dropIf \{ id: fieldId } -> isUnit (Types.shape types fieldId)
+        |> List.map \{ name: fieldName, id: fieldId } ->
+            cFieldName = escapeC fieldName
+
+            {
+                name: fieldName,
+                cName: cFieldName,
+                # cgo adds an underscore to C names that are Go keywords.
+                cgoName: escapeGo cFieldName,
+                goName: toPascalCase fieldName,
+                cType: cTypeName types fieldId,
+                goType: goTypeName types fieldId,
+            }
+
+    cFields =
+        sizedFields
+        |> List.map \field -> "$(indent)$(field.cType) $(field.cName);\n"
+        |> Str.joinWith ""
+
+    goFields =
+        sizedFields
+        |> List.map \field -> "\t$(field.goName) $(field.goType)\n"
+        |> Str.joinWith ""
+
+    loads =
+        sizedFields
+        |> List.map \field -> "\t\t\t\t$(field.goName): codecOf[$(field.goType)]().load(unsafe.Pointer(&raw.$(field.cgoName))),\n"
+        |> Str.joinWith ""
+
+    stores =
+        sizedFields
+        |> List.map \field -> "\t\t\tcodecOf[$(field.goType)]().store(unsafe.Pointer(&raw.$(field.cgoName)), value.$(field.goName))\n"
+        |> Str.joinWith ""
+
+    drops =
+        sizedFields
+        |> List.map \field -> "\t\t\tcodecOf[$(field.goType)]().drop(unsafe.Pointer(&raw.$(field.cgoName)))\n"
+        |> Str.joinWith ""
+
+    debugFormat =
+        sizedFields
+        |> List.map \field -> "$(field.name): %v"
+        |> Str.joinWith ", "
+
+    debugArgs =
+        sizedFields
+        |> List.map \field -> ", value.$(field.goName)"
+        |> Str.joinWith ""
+
+    cForward = "typedef struct $(cName) $(cName);\n"
+
+    cDefinition =
+        """
+        struct $(cName) {
+        $(cFields)};
+
+        _Static_assert(sizeof($(cName)) == $(size), "$(cName) should be $(size) bytes");
+
+        """
+
+    go =
+        """
+
+        type $(goName) struct {
+        $(goFields)}
+
+        func (value $(goName)) String() string {
+        \treturn fmt.Sprintf("$(name) { $(debugFormat) }"$(debugArgs))
+        }
+
+        func ($(goName)) rocCodec() codec[$(goName)] {
+        \treturn codec[$(goName)]{
+        \t\tsize:  $(size),
+        \t\talign: $(align),
+        \t\tload: func(pointer unsafe.Pointer) $(goName) {
+        \t\t\traw := (*C.$(cName))(pointer)
+
+        \t\t\treturn $(goName){
+        $(loads)\t\t\t}
+        \t\t},
+        \t\tstore: func(pointer unsafe.Pointer, value $(goName)) {
+        \t\t\traw := (*C.$(cName))(pointer)
+
+        $(stores)\t\t},
+        \t\tdrop: func(pointer unsafe.Pointer) {
+        \t\t\traw := (*C.$(cName))(pointer)
+
+        $(drops)\t\t},
+        \t}
+        }
+
+        """
+
+    {
+        cForward: Str.concat state.cForward cForward,
+        cDefinitions: Str.concat state.cDefinitions cDefinition,
+        go: Str.concat state.go go,
+    }
+
+generateEnumeration = \state, name, tags, size ->
+    cName = "roc_$(name)"
+    goName = toPascalCase name
+    intType = goIntType size
+
+    constants =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            if index == 0 then
+                "\t$(goName)$(tag) $(goName) = iota\n"
+            else
+                "\t$(goName)$(tag)\n"
+        |> Str.joinWith ""
+
+    debugCases =
+        tags
+        |> List.map \tag -> "\tcase $(goName)$(tag):\n\t\treturn \"$(name).$(tag)\"\n"
+        |> Str.joinWith ""
+
+    cForward = "typedef $(cIntType size) $(cName);\n"
+
+    go =
+        """
+
+        type $(goName) $(intType)
+
+        const (
+        $(constants))
+
+        func (value $(goName)) String() string {
+        \tswitch value {
+        $(debugCases)\t}
+
+        \treturn fmt.Sprintf("$(name)(%d)", $(intType)(value))
+        }
+
+        func ($(goName)) rocCodec() codec[$(goName)] {
+        \treturn trivialCodec[$(goName)]()
+        }
+
+        """
+
+    { state &
+        cForward: Str.concat state.cForward cForward,
+        go: Str.concat state.go go,
+    }
+
+## A recursive tag union like `ConsList a : [Nil, Cons a (ConsList a)]`, where the tag without a
+## payload is a null pointer, and the other one points to its payload.
+generateNullableUnwrapped = \state, types, name, nullTag, nonNullTag, nonNullPayload ->
+    cName = "roc_$(name)"
+    goName = toPascalCase name
+    payloadCName = cTypeName types nonNullPayload
+    payloadGoName = goTypeName types nonNullPayload
+
+    payloadFields =
+        when Types.shape types nonNullPayload is
+            TagUnionPayload { fields } ->
+                structFields fields
+                |> List.dropIf \{ id } -> isUnit (Types.shape types id)
+                |> List.map \{ name: fieldName, id } -> { name: "f$(fieldName)", type: goTypeName types id }
+
+            _ ->
+                []
+
+    constructorParams =
+        payloadFields
+        |> List.map \field -> "$(field.name) $(field.type)"
+        |> Str.joinWith ", "
+
+    constructorArgs =
+        payloadFields
+        |> List.map \field ->
+            fieldName = toPascalCase field.name
+
+            "$(fieldName): $(field.name)"
+        |> Str.joinWith ", "
+
+    debugFormat =
+        payloadFields
+        |> List.map \_ -> "%v"
+        |> Str.joinWith ", "
+
+    debugArgs =
+        payloadFields
+        |> List.map \field ->
+            fieldName = toPascalCase field.name
+
+            ", payload.$(fieldName)"
+        |> Str.joinWith ""
+
+    cForward = "typedef struct $(payloadCName) *$(cName);\n"
+
+    go =
+        """
+
+        // $(goName) is nil for `$(nullTag)`, and points to the node holding its payload for `$(nonNullTag)`.
+        type $(goName) struct {
+        \tnode *nodeRef[$(payloadGoName)]
+        }
+
+        func $(goName)$(nullTag)() $(goName) {
+        \treturn $(goName){}
+        }
+
+        func $(goName)$(nonNullTag)($(constructorParams)) $(goName) {
+        \treturn $(goName){newNode($(payloadGoName){$(constructorArgs)})}
+        }
+
+        func (value $(goName)) Is$(nullTag)() bool {
+        \treturn value.node == nil
+        }
+
+        // As$(nonNullTag) is the payload of `$(nonNullTag)`, or false if this is `$(nullTag)`.
+        func (value $(goName)) As$(nonNullTag)() ($(payloadGoName), bool) {
+        \tif value.node == nil {
+        \t\treturn $(payloadGoName){}, false
+        \t}
+
+        \treturn value.node.payload(), true
+        }
+
+        func (value $(goName)) String() string {
+        \tpayload, ok := value.As$(nonNullTag)()
+
+        \tif !ok {
+        \t\treturn "$(name).$(nullTag)"
+        \t}
+
+        \treturn fmt.Sprintf("$(name).$(nonNullTag)($(debugFormat))"$(debugArgs))
+        }
+
+        func ($(goName)) rocCodec() codec[$(goName)] {
+        \treturn nodeCodec(
+        \t\tfunc(node *nodeRef[$(payloadGoName)]) $(goName) { return $(goName){node} },
+        \t\tfunc(value $(goName)) *nodeRef[$(payloadGoName)] { return value.node },
+        \t)
+        }
+
+        """
+
+    { state &
+        cForward: Str.concat state.cForward cForward,
+        go: Str.concat state.go go,
+    }
+
+generateEntryPoints : Types -> Result { c : Str, go : Str } Str
+generateEntryPoints = \types ->
+    List.walk (Types.entryPoints types) (Ok { c: "", go: "" }) \state, T name id ->
+        Result.try state \accum ->
+            Result.map (generateEntryPoint types name id) \entryPoint -> {
+                c: Str.concat accum.c entryPoint.c,
+                go: Str.concat accum.go entryPoint.go,
+            }
+
+generateEntryPoint : Types, Str, TypeId -> Result { c : Str, go : Str } Str
+generateEntryPoint = \types, name, id ->
+    (args, ret) =
+        when Types.shape types id is
+            Function rocFn -> (rocFn.args, rocFn.ret)
+            _ -> ([], id)
+
+    when Types.shape types ret is
+        Function _ ->
+            Err "The Go glue doesn't support entry points that return functions yet, like $(name)."
+
+        retShape ->
+            externName = "roc__$(name)_1_exposed_generic"
+            funcName = toPascalCase name
+
+            # Drop `{}` args; nothing gets passed for them anyway.
+            params =
+                args
+                |> List.mapWithIndex \argId, index ->
+                    indexStr = Num.toStr index
+
+                    {
+                        name: "arg$(indexStr)",
+                        cType: cTypeName types argId,
+                        goType: goTypeName types argId,
+                        size: Num.toStr (Types.size types argId),
+                        byValue: isPassedByValue types (Types.shape types argId),
+                    }
+                |> List.dropIf \{ cType } -> Str.isEmpty cType
+
+            # Roc takes arguments that have memory to manage by pointer.
+            cParams =
+                params
+                |> List.map \param ->
+                    if param.byValue then
+                        ", $(param.cType) $(param.name)"
+                    else
+                        ", $(param.cType) *$(param.name)"
+                |> Str.joinWith ""
+
+            goParams =
+                params
+                |> List.map \param -> "$(param.name) $(param.goType)"
+                |> Str.joinWith ", "
+
+            # The C values go in C memory, which C aligns the way Roc expects, and Go doesn't always.
+            rawArgs =
+                params
+                |> List.map \param ->
+                    malloc = "\t$(param.name)Raw := C.malloc($(param.size))\n\tdefer C.free($(param.name)Raw)\n"
+
+                    "$(malloc)\tcodecOf[$(param.goType)]().store($(param.name)Raw, $(param.name))\n\n"
+                |> Str.joinWith ""
+
+            externArgs =
+                params
+                |> List.map \param ->
+                    if param.byValue then
+                        ", *(*C.$(param.cType))($(param.name)Raw)"
+                    else
+                        ", (*C.$(param.cType))($(param.name)Raw)"
+                |> Str.joinWith ""
+
+            if isUnit retShape then
+                go =
+                    """
+
+                    // $(funcName) calls the app's `$(name)`. Roc gets references of its own to what the arguments point to.
+                    func $(funcName)($(goParams)) {
+                    \tCollect()
+
+                    $(rawArgs)\tC.$(externName)(nil$(externArgs))
+                    }
+
+                    """
+
+                Ok { c: "void $(externName)(void *ret$(cParams));\n", go }
+            else
+                cRet = cTypeName types ret
+                goRet = goTypeName types ret
+                retSize = Num.toStr (Types.size types ret)
+
+                go =
+                    """
+
+                    // $(funcName) calls the app's `$(name)`. Roc gets references of its own to what the arguments point to.
+                    func $(funcName)($(goParams)) $(goRet) {
+                    \tCollect()
+
+                    \tret := C.malloc($(retSize))
+                    \tdefer C.free(ret)
+
+                    $(rawArgs)\tC.$(externName)((*C.$(cRet))(ret)$(externArgs))
+
+                    \treturn consume[$(goRet)](ret)
+                    }
+
+                    """
+
+                Ok { c: "void $(externName)($(cRet) *ret$(cParams));\n", go }
+
+## The C type of a Roc value, or an empty string for `{}`, which doesn't have a C representation
+cTypeName : Types, TypeId -> Str
+cTypeName = \types, id ->
+    when Types.shape types id is
+        Unit -> ""
+        RocStr -> "roc_str"
+        RocList _ -> "roc_list"
+        Bool -> "bool"
+        Num U8 -> "uint8_t"
+        Num U16 -> "uint16_t"
+        Num U32 -> "uint32_t"
+        Num U64 -> "uint64_t"
+        Num U128 -> "roc_u128"
+        Num I8 -> "int8_t"
+        Num I16 -> "int16_t"
+        Num I32 -> "int32_t"
+        Num I64 -> "int64_t"
+        Num I128 -> "roc_i128"
+        Num F32 -> "float"
+        Num F64 -> "double"
+        RecursivePointer content -> cTypeName types content
+        Struct { name } -> "roc_$(name)"
+        TagUnionPayload { name } -> "roc_$(name)"
+        TagUnion (SingleTagStruct { name }) -> "roc_$(name)"
+        TagUnion (Enumeration { name }) -> "roc_$(name)"
+        TagUnion (NullableUnwrapped { name }) -> "roc_$(name)"
+        # These are reported as unsupported when they're declared.
+        _ -> "void"
+
+goTypeName : Types, TypeId -> Str
+goTypeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "struct{}"
+        RocStr -> "RocStr"
+        RocList elem ->
+            elemName = goTypeName types elem
+
+            "RocList[$(elemName)]"
+
+        Bool -> "bool"
+        Num U8 -> "uint8"
+        Num U16 -> "uint16"
+        Num U32 -> "uint32"
+        Num U64 -> "uint64"
+        Num U128 -> "U128"
+        Num I8 -> "int8"
+        Num I16 -> "int16"
+        Num I32 -> "int32"
+        Num I64 -> "int64"
+        Num I128 -> "I128"
+        Num F32 -> "float32"
+        Num F64 -> "float64"
+        RecursivePointer content -> goTypeName types content
+        Struct { name } -> toPascalCase name
+        TagUnionPayload { name } -> toPascalCase name
+        TagUnion (SingleTagStruct { name }) -> toPascalCase name
+        TagUnion (Enumeration { name }) -> toPascalCase name
+        TagUnion (NullableUnwrapped { name }) -> toPascalCase name
+        # These are reported as unsupported when they're declared.
+        _ -> "any"
+
+cIntType : U32 -> Str
+cIntType = \size ->
+    when size is
+        1 -> "uint8_t"
+        2 -> "uint16_t"
+        4 -> "uint32_t"
+        _ -> "uint64_t"
+
+goIntType : U32 -> Str
+goIntType = \size ->
+    when size is
+        1 -> "uint8"
+        2 -> "uint16"
+        4 -> "uint32"
+        _ -> "uint64"
+
+## Whether Roc takes arguments of this type by value, rather than by pointer.
+isPassedByValue : Types, Shape -> Bool
+isPassedByValue = \types, shape ->
+    when shape is
+        Unit | Bool | Num _ | TagUnion (Enumeration _) -> Bool.true
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.all fields \{ id } -> isPassedByValue types (Types.shape types id)
+
+        Struct { fields } | TagUnionPayload { fields } ->
+            List.all (structFields fields) \{ id } -> isPassedByValue types (Types.shape types id)
+
+        _ -> Bool.false
+
+isUnit : Shape -> Bool
+isUnit = \shape ->
+    when shape is
+        Unit -> Bool.true
+        _ -> Bool.false
+
+## Go only exports names that start with a capital letter.
+toPascalCase : Str -> Str
+toPascalCase = \name ->
+    bytes = Str.toUtf8 name
+
+    when List.first bytes is
+        Ok first if first >= 'a' && first <= 'z' ->
+            List.set bytes 0 (first - 'a' + 'A')
+            |> Str.fromUtf8
+            |> Result.withDefault name
+
+        _ ->
+            name
+
+cHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    #pragma once
+
+    #include "RocStd.h"
+
+    """
+
+goHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    package rocapp
+
+    /*
+    #include <stdlib.h>
+    #include "roc_app.h"
+    */
+    import "C"
+
+    import (
+    \t"fmt"
+    \t"unsafe"
+    )
+
+    // These keep the imports used, whichever types the app has.
+    var (
+    \t_ = fmt.Sprint
+    \t_ unsafe.Pointer
+    )
+
+    """
+
+indent = "    "
+
+cKeywords = Set.fromList [
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+]
+
+goKeywords = Set.fromList [
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+]
+
+escapeC = \input ->
+    # C has no way to use a keyword as a name, so this adds an underscore to it.
+    if Set.contains cKeywords input then
+        "$(input)_"
+    else
+        input
+
+escapeGo = \input ->
+    # This is how cgo names C fields that are Go keywords.
+    if Set.contains goKeywords input then
+        "_$(input)"
+    else
+        input
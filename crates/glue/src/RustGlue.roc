@@ -9,8 +9,10 @@ app "rust-glue"
         "../../roc_std/Cargo.toml" as rocStdCargoToml : Str,
         "../../roc_std/src/lib.rs" as rocStdLib : Str,
         "../../roc_std/src/roc_box.rs" as rocStdBox : Str,
+        "../../roc_std/src/roc_dec.rs" as rocStdDec : Str,
         "../../roc_std/src/roc_list.rs" as rocStdList : Str,
         "../../roc_std/src/roc_dict.rs" as rocStdDict : Str,
+        "../../roc_std/src/roc_host_closure.rs" as rocStdHostClosure : Str,
         "../../roc_std/src/roc_set.rs" as rocStdSet : Str,
         "../../roc_std/src/roc_str.rs" as rocStdStr : Str,
         "../../roc_std/src/storage.rs" as rocStdStorage : Str,
@@ -19,8 +21,10 @@ app "rust-glue"
 
 makeGlue : List Types -> Result (List File) Str
 makeGlue = \typesByArch ->
+    libHeader = Str.concat fileHeader noStdHeader
+
     modFileContent =
-        List.walk typesByArch fileHeader \content, types ->
+        List.walk typesByArch libHeader \content, types ->
             arch = (Types.target types).architecture
             archStr = archName arch
 
@@ -47,8 +51,10 @@ staticFiles = [
     { name: "roc_std/Cargo.toml", content: rocStdCargoToml },
     { name: "roc_std/src/lib.rs", content: rocStdLib },
     { name: "roc_std/src/roc_box.rs", content: rocStdBox },
+    { name: "roc_std/src/roc_dec.rs", content: rocStdDec },
     { name: "roc_std/src/roc_list.rs", content: rocStdList },
     { name: "roc_std/src/roc_dict.rs", content: rocStdDict },
+    { name: "roc_std/src/roc_host_closure.rs", content: rocStdHostClosure },
     { name: "roc_std/src/roc_set.rs", content: rocStdSet },
     { name: "roc_std/src/roc_str.rs", content: rocStdStr },
     { name: "roc_std/src/storage.rs", content: rocStdStorage },
@@ -60,7 +66,10 @@ convertTypesToFile = \types ->
         Types.walkShapes types fileHeader \buf, type, id ->
             when type is
                 Struct { name, fields } ->
-                    generateStruct buf types id name fields Public
+                    if isHostClosure types fields then
+                        Str.concat buf "pub type $(escapeKW name) = roc_std::RocHostClosure;\n\n"
+                    else
+                        generateStruct buf types id name fields Public
 
                 TagUnionPayload { name, fields } ->
                     generateStruct buf types id name (nameTagUnionPayloadFields fields) Public
@@ -188,11 +197,35 @@ generateEntryPoint = \buf, types, name, id ->
             _ ->
                 ""
 
+    callName = "call_$(toSnakeCase name)"
+
+    callArguments =
+        when Types.shape types id is
+            Function rocFn ->
+                toArgStr rocFn.args types \_argId, _shape, index ->
+                    indexStr = Num.toStr index
+
+                    "arg$(indexStr)"
+
+            _ ->
+                ""
+
+    # Hosts that call the entry point themselves can still use the generated wrapper by name.
+    alias =
+        """
+
+        /// Same as [`$(callName)`].
+        pub fn $(name)$(publicSignature) {
+            $(callName)($(callArguments))
+        }
+        """
+
     if returnsFn then
         """
         $(buf)
 
-        pub fn $(name)$(publicSignature) {
+        /// Calls the app's `$(name)`, which returns a closure whose size is only known at runtime.
+        pub fn $(callName)$(publicSignature) {
             extern "C" {
                 fn roc__$(name)_1_exposed_generic$(externSignature);
                 fn roc__$(name)_1_exposed_size() -> i64;
@@ -202,7 +235,7 @@ generateEntryPoint = \buf, types, name, id ->
                 let capacity = roc__$(name)_1_exposed_size() as usize;
 
                 let mut ret = $(returnTypeName) {
-                    closure_data: Vec::with_capacity(capacity),
+                    closure_data: alloc::vec::Vec::with_capacity(capacity),
                 };
                 ret.closure_data.resize(capacity, 0);
 
@@ -211,12 +244,30 @@ generateEntryPoint = \buf, types, name, id ->
                 ret
             }
         }
+        $(alias)
         """
     else
+        constName = toScreamingSnakeCase name
+        retId =
+            when Types.shape types id is
+                Function rocFn -> rocFn.ret
+                _ -> id
+        retSize = Num.toStr (Types.size types retId)
+        retAlignment = Num.toStr (Types.alignment types retId)
+
         """
         $(buf)
 
-        pub fn $(name)$(publicSignature) {
+        /// The size of what `$(name)` returns, in bytes. Roc doesn't return it in registers; it writes it
+        /// to memory the caller passes a pointer to, which needs this much room.
+        pub const $(constName)_RET_SIZE: usize = $(retSize);
+
+        /// The alignment of the memory `$(name)` writes what it returns to.
+        pub const $(constName)_RET_ALIGNMENT: usize = $(retAlignment);
+
+        /// Calls the app's `$(name)`. Arguments that need to be dropped are passed by pointer, and
+        /// Roc takes ownership of them.
+        pub fn $(callName)$(publicSignature) {
             extern "C" {
                 fn roc__$(name)_1_exposed_generic$(externSignature);
             }
@@ -229,6 +280,7 @@ generateEntryPoint = \buf, types, name, id ->
                 ret.assume_init()
             }
         }
+        $(alias)
         """
 
 generateFunction : Str, Types, RocFn -> Str
@@ -280,7 +332,7 @@ generateFunction = \buf, types, rocFn ->
     #[repr(C)]
     #[derive(Debug)]
     pub struct $(name) {
-        closure_data: Vec<u8>,
+        closure_data: alloc::vec::Vec<u8>,
     }
 
     impl $(name) {
@@ -326,6 +378,20 @@ generateStruct = \buf, types, id, name, structFields, visibility ->
     |> generateStructFields types Public structFields
     |> Str.concat "}\n\n"
 
+## A record of exactly `{ call : U64, data : U64 }` is a host closure: a function the host
+## passes to Roc as a value. See roc_std/src/roc_host_closure.rs for the calling convention.
+isHostClosure = \types, structFields ->
+    when structFields is
+        HasNoClosure [call, data] ->
+            isU64 = \field ->
+                when Types.shape types field.id is
+                    Num U64 -> Bool.true
+                    _ -> Bool.false
+
+            call.name == "call" && data.name == "data" && isU64 call && isU64 data
+
+        _ -> Bool.false
+
 generateStructFields = \buf, types, visibility, structFields ->
     when structFields is
         HasNoClosure fields ->
@@ -502,7 +568,7 @@ deriveOrdTagUnion = \buf, types, shape, tagUnionType ->
         $(buf)
 
         impl Ord for $(tagUnionType) {
-            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
                 self.partial_cmp(other).unwrap()
             }
         }
@@ -524,10 +590,10 @@ derivePartialOrdTagUnion = \buf, types, shape, tagUnionType, tags ->
         $(buf)
 
         impl PartialOrd for $(tagUnionType) {
-            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
                 use discriminant_$(tagUnionType)::*;
 
-                use std::cmp::Ordering::*;
+                use core::cmp::Ordering::*;
 
                 match self.discriminant.cmp(&other.discriminant) {
                     Less => Option::Some(Less),
@@ -929,7 +995,7 @@ generateRecursiveTagUnion = \buf, types, id, tagUnionName, tags, discriminantSiz
                 }
 
                 pub fn $(tagName)($(constructorArguments)) -> Self {
-                    Self(std::ptr::null_mut())
+                    Self(core::ptr::null_mut())
                 }
             """
         else
@@ -1115,7 +1181,7 @@ generateRecursiveTagUnion = \buf, types, id, tagUnionName, tags, discriminantSiz
     partialOrdCase = \{ name: tagName }, index ->
         if Some (Num.intCast index) == nullTagIndex then
             """
-                        $(tagName) => std::cmp::Ordering::Equal,
+                        $(tagName) => core::cmp::Ordering::Equal,
             """
         else
             """
@@ -1138,16 +1204,16 @@ generateRecursiveTagUnion = \buf, types, id, tagUnionName, tags, discriminantSiz
         if canSupportPartialEqOrd types (Types.shape types id) then
             """
             impl PartialOrd for $(escapedName) {
-                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
                     Some(<Self as Ord>::cmp(self, other))
                 }
             }
 
             impl Ord for $(escapedName) {
-                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                fn cmp(&self, other: &Self) -> core::cmp::Ordering {
                     use discriminant_$(escapedName)::*;
 
-                    use std::cmp::Ordering::*;
+                    use core::cmp::Ordering::*;
 
                     match self.discriminant().cmp(&other.discriminant()) {
                         Less => Less,
@@ -1167,6 +1233,10 @@ generateRecursiveTagUnion = \buf, types, id, tagUnionName, tags, discriminantSiz
     sizeOfSelf = Num.toStr (Types.size types id)
     alignOfSelf = Num.toStr (Types.alignment types id)
 
+    # The tag id is stored in the low bits of the pointer, which are free because of its alignment.
+    # The union is a pointer, so its size tells us how many bits that is on this target.
+    tagIdMask = if Types.size types id == 4 then "0b011" else "0b111"
+
     buf
     |> generateDiscriminant types discriminantName tagNames discriminantSize
     |> Str.concat
@@ -1188,22 +1258,14 @@ generateRecursiveTagUnion = \buf, types, id, tagUnionName, tags, discriminantSiz
                 if self.0.is_null() {
                     $(nullTagId)
                 } else  {
-                    match std::mem::size_of::<usize>() {
-                        4 => discriminants[self.0 as usize & 0b011],
-                        8 => discriminants[self.0 as usize & 0b111],
-                        _ => unreachable!(),
-                    }
+                    discriminants[self.0 as usize & $(tagIdMask)]
                 }
             }
 
             fn unmasked_pointer(&self) -> *mut union_$(escapedName) {
                 debug_assert!(!self.0.is_null());
 
-                let mask = match std::mem::size_of::<usize>() {
-                    4 => !0b011usize,
-                    8 => !0b111usize,
-                    _ => unreachable!(),
-                };
+                let mask = !$(tagIdMask)usize;
 
                 ((self.0 as usize) & mask) as *mut union_$(escapedName)
             }
@@ -1211,7 +1273,7 @@ generateRecursiveTagUnion = \buf, types, id, tagUnionName, tags, discriminantSiz
             unsafe fn ptr_read_union(&self) -> core::mem::ManuallyDrop<union_$(escapedName)> {
                 let ptr = self.unmasked_pointer();
 
-                core::mem::ManuallyDrop::new(unsafe { std::ptr::read(ptr) })
+                core::mem::ManuallyDrop::new(unsafe { core::ptr::read(ptr) })
             }
 
             $(constructors)
@@ -1474,7 +1536,7 @@ generateNullableUnwrapped = \buf, types, tagUnionid, name, nullTag, nonNullTag,
             if self.is_$(nullTag)() {
                 f.debug_tuple("$(name)::$(nullTag)").finish()
             } else {
-                let node = core::mem::ManuallyDrop::new(unsafe { std::ptr::read(self.0) });
+                let node = core::mem::ManuallyDrop::new(unsafe { core::ptr::read(self.0) });
                 f.debug_tuple("$(name)::$(nonNullTag)")$(debugFields).finish()
             }
         }
@@ -1485,9 +1547,9 @@ generateNullableUnwrapped = \buf, types, tagUnionid, name, nullTag, nonNullTag,
             if self.is_$(nullTag)() {
                 Self::$(nullTag)()
             } else {
-                use std::ops::Deref;
+                use core::ops::Deref;
 
-                let node_ref = core::mem::ManuallyDrop::new(unsafe { std::ptr::read(self.0) });
+                let node_ref = core::mem::ManuallyDrop::new(unsafe { core::ptr::read(self.0) });
                 let payload : $(name)_$(nonNullTag) = (node_ref.deref()).clone();
 
                 let ptr = unsafe { roc_std::RocBox::leak(roc_std::RocBox::new(payload)) };
@@ -1507,8 +1569,8 @@ generateNullableUnwrapped = \buf, types, tagUnionid, name, nullTag, nonNullTag,
                 return true;
             }
 
-            let payload1 = core::mem::ManuallyDrop::new(unsafe { std::ptr::read(self.0) });
-            let payload2 = core::mem::ManuallyDrop::new(unsafe { std::ptr::read(other.0) });
+            let payload1 = core::mem::ManuallyDrop::new(unsafe { core::ptr::read(self.0) });
+            let payload2 = core::mem::ManuallyDrop::new(unsafe { core::ptr::read(other.0) });
 
             payload1 == payload2
         }
@@ -1521,7 +1583,7 @@ generateNullableUnwrapped = \buf, types, tagUnionid, name, nullTag, nonNullTag,
             self.discriminant().hash(state);
 
             if self.is_$(nonNullTag)() {
-                let payload = core::mem::ManuallyDrop::new(unsafe { std::ptr::read(self.0) });
+                let payload = core::mem::ManuallyDrop::new(unsafe { core::ptr::read(self.0) });
                 payload.hash(state);
             }
         }
@@ -1957,7 +2019,7 @@ typeName = \types, id ->
     when Types.shape types id is
         Unit -> "()"
         Unsized -> "roc_std::RocList<u8>"
-        EmptyTagUnion -> "std::convert::Infallible"
+        EmptyTagUnion -> "core::convert::Infallible"
         RocStr -> "roc_std::RocStr"
         Bool -> "bool"
         Num U8 -> "u8"
@@ -2069,6 +2131,17 @@ fileHeader =
 
 
 
+    """
+
+## The glue only needs `core` and `alloc`, so hosts without `std` can turn off
+## the `std` feature (which is on by default) to build it with `#![no_std]`.
+noStdHeader =
+    """
+    #![cfg_attr(not(feature = "std"), no_std)]
+
+    extern crate alloc;
+
+
     """
 
 indent = "    "
@@ -2148,6 +2221,30 @@ isUnit = \shape ->
         Unit -> Bool.true
         _ -> Bool.false
 
+## Turns a camelCase Roc name like `mainForHost` into `main_for_host`.
+toSnakeCase : Str -> Str
+toSnakeCase = \name ->
+    Str.toUtf8 name
+    |> List.walk [] \bytes, byte ->
+        if byte >= 'A' && byte <= 'Z' then
+            lower = byte - 'A' + 'a'
+
+            if List.isEmpty bytes then
+                List.append bytes lower
+            else
+                List.concat bytes ['_', lower]
+        else
+            List.append bytes byte
+    |> Str.fromUtf8
+    |> Result.withDefault name
+
+toScreamingSnakeCase : Str -> Str
+toScreamingSnakeCase = \name ->
+    Str.toUtf8 (toSnakeCase name)
+    |> List.map \byte -> if byte >= 'a' && byte <= 'z' then byte - 'a' + 'A' else byte
+    |> Str.fromUtf8
+    |> Result.withDefault name
+
 toArgStr : List TypeId, Types, (TypeId, Shape, U64 -> Str) -> Str
 toArgStr = \args, types, fmt ->
     List.walkWithIndex args "" \state, argId, index ->
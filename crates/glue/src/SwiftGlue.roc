@@ -0,0 +1,683 @@
+app "swift-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [
+        pf.Types.{ Types },
+        pf.Shape.{ Shape },
+        pf.File.{ File },
+        pf.TypeId.{ TypeId },
+        "../static/Package.swift" as packageSwift : Str,
+        "../static/module.modulemap" as moduleMap : Str,
+        "../static/RocStd.h" as rocStdH : Str,
+        "../static/RocStd.swift" as rocStdSwift : Str,
+        "../static/RocHost.swift" as rocHostSwift : Str,
+    ]
+    provides [makeGlue] to pf
+
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    # Apple platforms are 64-bit, and the 64-bit architectures all lay out Roc values the same way,
+    # so one set of files covers all of them.
+    when List.findFirst typesByArch is64Bit is
+        Ok types ->
+            convertTypesToFiles types
+            |> Result.map \files -> List.concat files staticFiles
+
+        Err NotFound ->
+            Err "The Swift glue only supports 64-bit targets."
+
+## These are always included, and don't depend on the specifics of the app.
+staticFiles : List File
+staticFiles = [
+    { name: "Package.swift", content: packageSwift },
+    { name: "Sources/CRocApp/module.modulemap", content: moduleMap },
+    { name: "Sources/CRocApp/RocStd.h", content: rocStdH },
+    { name: "Sources/RocApp/RocStd.swift", content: rocStdSwift },
+    { name: "Sources/RocApp/RocHost.swift", content: rocHostSwift },
+]
+
+is64Bit : Types -> Bool
+is64Bit = \types ->
+    when (Types.target types).architecture is
+        Aarch64 | X86x64 -> Bool.true
+        Aarch32 | Wasm32 | X86x32 -> Bool.false
+
+## The app's types go in two files: C declarations in RocApp.h, which say how Roc lays them out,
+## and Swift types in RocApp.swift, which wrap those.
+convertTypesToFiles : Types -> Result (List File) Str
+convertTypesToFiles = \types ->
+    # C needs every type declared before the types that contain it, and forward declarations
+    # let the recursive ones point to each other.
+    init = Ok { cForward: "", cDefinitions: "", swift: swiftHeader }
+
+    declarations =
+        Types.walkShapes types init \state, shape, id ->
+            Result.try state \declared -> generateDeclaration declared types shape id
+
+    Result.try declarations \{ cForward, cDefinitions, swift } ->
+        Result.map (generateEntryPoints types) \entryPoints ->
+            header = Str.joinWith [cHeader, cForward, cDefinitions, entryPoints.c] "\n"
+
+            [
+                { name: "Sources/CRocApp/RocApp.h", content: header },
+                { name: "Sources/RocApp/RocApp.swift", content: Str.concat swift entryPoints.swift },
+            ]
+
+generateDeclaration = \state, types, shape, id ->
+    when shape is
+        Struct { name, fields } ->
+            Ok (generateStruct state types id name (structFields fields))
+
+        TagUnionPayload { name, fields } ->
+            # Tag union payloads have numbered fields, so we prefix them
+            # with an "f" because neither C nor Swift allow fields to be numbers.
+            payloadFields =
+                structFields fields
+                |> List.map \field -> { field & name: "f$(field.name)" }
+
+            Ok (generateStruct state types id name payloadFields)
+
+        TagUnion (SingleTagStruct { name, payload: HasNoClosure fields }) ->
+            payloadFields =
+                List.mapWithIndex fields \{ id: fieldId }, index ->
+                    indexStr = Num.toStr index
+
+                    { name: "f$(indexStr)", id: fieldId }
+
+            Ok (generateStruct state types id name payloadFields)
+
+        TagUnion (Enumeration { name, tags, size }) ->
+            Ok (generateEnumeration state name tags size)
+
+        TagUnion (NullableUnwrapped { name, nullTag, nonNullTag, nonNullPayload }) ->
+            Ok (generateNullableUnwrapped state types name nullTag nonNullTag nonNullPayload)
+
+        TagUnion (SingleTagStruct { name, payload: HasClosure _ }) ->
+            Err "The Swift glue doesn't support closures yet, which $(name) has."
+
+        TagUnion (NonRecursive { name }) ->
+            Err "The Swift glue doesn't support tag unions with payloads yet, like $(name)."
+
+        TagUnion (Recursive { name }) | TagUnion (NullableWrapped { name }) | TagUnion (NonNullableUnwrapped { name }) ->
+            Err "The Swift glue only supports recursive tag unions with one tag that has a payload and one that doesn't, unlike $(name)."
+
+        Num Dec ->
+            Err "The Swift glue doesn't support Dec yet."
+
+        RocResult _ _ ->
+            Err "The Swift glue doesn't support Result yet."
+
+        RocDict _ _ | RocSet _ | RocBox _ ->
+            Err "The Swift glue doesn't support Dict, Set, or Box yet."
+
+        Unsized | EmptyTagUnion ->
+            Err "The Swift glue doesn't support types that have no values or no size."
+
+        Function rocFn ->
+            if rocFn.isToplevel then
+                Ok state
+            else
+                Err "The Swift glue doesn't support closures yet, like $(rocFn.functionName)."
+
+        RecursivePointer _ | Unit | Num _ | Bool | RocStr | RocList _ ->
+            # These are in RocStd.h and RocStd.swift, or are built into Swift.
+            Ok state
+
+structFields = \fields ->
+    when fields is
+        HasNoClosure list -> List.map list \{ name, id } -> { name, id }
+        HasClosure list -> List.map list \{ name, id } -> { name, id }
+
+generateStruct = \state, types, id, name, fields ->
+    cName = cTypeName types id
+    swiftName = escapeSwift name
+    size = Num.toStr (Types.size types id)
+
+    # Fields of `{}` don't take up any room in Roc, but they would in C.
+    sizedFields =
+        fields
+        |> List.dropIf \{ id: fieldId } -> isUnit (Types.shape types fieldId)
+        |> List.map \{ name: fieldName, id: fieldId } ->
+            {
+                name: fieldName,
+                cName: escapeC fieldName,
+                swiftName: escapeSwift fieldName,
+                cType: cTypeName types fieldId,
+                swiftType: swiftTypeName types fieldId,
+            }
+
+    cFields =
+        sizedFields
+        |> List.map \field -> "$(indent)$(field.cType) $(field.cName);\n"
+        |> Str.joinWith ""
+
+    swiftFields =
+        sizedFields
+        |> List.map \field -> "$(indent)public var $(field.swiftName): $(field.swiftType)\n"
+        |> Str.joinWith ""
+
+    initParams =
+        sizedFields
+        |> List.map \field -> "$(field.swiftName): $(field.swiftType)"
+        |> Str.joinWith ", "
+
+    initAssignments =
+        sizedFields
+        |> List.map \field -> "$(indent)$(indent)self.$(field.swiftName) = $(field.swiftName)\n"
+        |> Str.joinWith ""
+
+    fromRaw = \ownership ->
+        sizedFields
+        |> List.map \field -> "$(indent)$(indent)$(field.swiftName) = $(field.swiftType)($(ownership): raw.$(field.cName))\n"
+        |> Str.joinWith ""
+
+    consumingAssignments = fromRaw "consuming"
+    borrowingAssignments = fromRaw "borrowing"
+
+    toRawArgs =
+        sizedFields
+        |> List.map \field -> "$(field.cName): $(field.swiftName).toRaw()"
+        |> Str.joinWith ", "
+
+    debugFields =
+        sizedFields
+        |> List.map \field -> "$(field.name): \\($(field.swiftName))"
+        |> Str.joinWith ", "
+
+    cForward = "typedef struct $(cName) $(cName);\n"
+
+    cDefinition =
+        """
+        struct $(cName) {
+        $(cFields)};
+
+        _Static_assert(sizeof($(cName)) == $(size), "$(cName) should be $(size) bytes");
+
+        """
+
+    swift =
+        """
+
+        public struct $(swiftName): RocRepresentable, CustomStringConvertible {
+        $(swiftFields)
+            public init($(initParams)) {
+        $(initAssignments)    }
+
+            public init(consuming raw: $(cName)) {
+        $(consumingAssignments)    }
+
+            public init(borrowing raw: $(cName)) {
+        $(borrowingAssignments)    }
+
+            public func toRaw() -> $(cName) {
+                $(cName)($(toRawArgs))
+            }
+
+            public var description: String { "$(name) { $(debugFields) }" }
+        }
+
+        """
+
+    {
+        cForward: Str.concat state.cForward cForward,
+        cDefinitions: Str.concat state.cDefinitions cDefinition,
+        swift: Str.concat state.swift swift,
+    }
+
+generateEnumeration = \state, name, tags, size ->
+    cName = "roc_$(name)"
+    swiftName = escapeSwift name
+    intType = swiftIntType size
+
+    cases =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            indexStr = Num.toStr index
+
+            "$(indent)case $(tag) = $(indexStr)\n"
+        |> Str.joinWith ""
+
+    debugCases =
+        tags
+        |> List.map \tag -> "$(indent)$(indent)case .$(tag): return \"$(name).$(tag)\"\n"
+        |> Str.joinWith ""
+
+    cForward = "typedef $(cIntType size) $(cName);\n"
+
+    swift =
+        """
+
+        public enum $(swiftName): $(intType), RocRepresentable, CustomStringConvertible {
+        $(cases)
+            public init(consuming raw: $(intType)) {
+                self = $(swiftName)(rawValue: raw)!
+            }
+
+            public init(borrowing raw: $(intType)) {
+                self = $(swiftName)(rawValue: raw)!
+            }
+
+            public func toRaw() -> $(intType) { rawValue }
+
+            public var description: String {
+                switch self {
+        $(debugCases)        }
+            }
+        }
+
+        """
+
+    { state &
+        cForward: Str.concat state.cForward cForward,
+        swift: Str.concat state.swift swift,
+    }
+
+## A recursive tag union like `ConsList a : [Nil, Cons a (ConsList a)]`, where the tag without a
+## payload is a null pointer, and the other one points to its payload.
+generateNullableUnwrapped = \state, types, name, nullTag, nonNullTag, nonNullPayload ->
+    cName = "roc_$(name)"
+    swiftName = escapeSwift name
+    payloadCName = cTypeName types nonNullPayload
+    payloadSwiftName = swiftTypeName types nonNullPayload
+
+    payloadFields =
+        when Types.shape types nonNullPayload is
+            TagUnionPayload { fields } ->
+                structFields fields
+                |> List.dropIf \{ id } -> isUnit (Types.shape types id)
+                |> List.map \{ name: fieldName, id } -> { name: "f$(fieldName)", type: swiftTypeName types id }
+
+            _ ->
+                []
+
+    constructorParams =
+        payloadFields
+        |> List.map \field -> "_ $(field.name): $(field.type)"
+        |> Str.joinWith ", "
+
+    constructorArgs =
+        payloadFields
+        |> List.map \field -> "$(field.name): $(field.name)"
+        |> Str.joinWith ", "
+
+    debugFields =
+        payloadFields
+        |> List.map \field -> "\\(payload.$(field.name))"
+        |> Str.joinWith ", "
+
+    cForward = "typedef struct $(payloadCName) *$(cName);\n"
+
+    swift =
+        """
+
+        public struct $(swiftName): RocRepresentable, CustomStringConvertible {
+            // `$(nullTag)` is nil, and `$(nonNullTag)` is the node its payload is in.
+            private let node: RocNode<$(payloadSwiftName)>?
+
+            private init(node: RocNode<$(payloadSwiftName)>?) {
+                self.node = node
+            }
+
+            public static func $(nullTag)() -> $(swiftName) {
+                $(swiftName)(node: nil)
+            }
+
+            public static func $(nonNullTag)($(constructorParams)) -> $(swiftName) {
+                $(swiftName)(node: RocNode($(payloadSwiftName)($(constructorArgs))))
+            }
+
+            public var is$(nullTag): Bool { node == nil }
+
+            /// The payload of `$(nonNullTag)`, or nil if this is `$(nullTag)`.
+            public var as$(nonNullTag): $(payloadSwiftName)? { node?.payload }
+
+            public init(consuming raw: $(cName)) {
+                node = raw.map { RocNode(consuming: $0) }
+            }
+
+            public init(borrowing raw: $(cName)) {
+                node = raw.map { RocNode(borrowing: $0) }
+            }
+
+            public func toRaw() -> $(cName) {
+                node?.toRaw()
+            }
+
+            public var description: String {
+                guard let payload = as$(nonNullTag) else {
+                    return "$(name).$(nullTag)"
+                }
+
+                return "$(name).$(nonNullTag)($(debugFields))"
+            }
+        }
+
+        """
+
+    { state &
+        cForward: Str.concat state.cForward cForward,
+        swift: Str.concat state.swift swift,
+    }
+
+generateEntryPoints : Types -> Result { c : Str, swift : Str } Str
+generateEntryPoints = \types ->
+    List.walk (Types.entryPoints types) (Ok { c: "", swift: "" }) \state, T name id ->
+        Result.try state \accum ->
+            Result.map (generateEntryPoint types name id) \entryPoint -> {
+                c: Str.concat accum.c entryPoint.c,
+                swift: Str.concat accum.swift entryPoint.swift,
+            }
+
+generateEntryPoint : Types, Str, TypeId -> Result { c : Str, swift : Str } Str
+generateEntryPoint = \types, name, id ->
+    (args, ret) =
+        when Types.shape types id is
+            Function rocFn -> (rocFn.args, rocFn.ret)
+            _ -> ([], id)
+
+    when Types.shape types ret is
+        Function _ ->
+            Err "The Swift glue doesn't support entry points that return functions yet, like $(name)."
+
+        retShape ->
+            externName = "roc__$(name)_1_exposed_generic"
+
+            # Drop `{}` args; nothing gets passed for them anyway.
+            params =
+                args
+                |> List.mapWithIndex \argId, index ->
+                    indexStr = Num.toStr index
+
+                    {
+                        name: "arg$(indexStr)",
+                        cType: cTypeName types argId,
+                        swiftType: swiftTypeName types argId,
+                        byValue: isPassedByValue types (Types.shape types argId),
+                    }
+                |> List.dropIf \{ cType } -> Str.isEmpty cType
+
+            # Roc takes arguments that have memory to manage by pointer.
+            cParams =
+                params
+                |> List.map \param ->
+                    if param.byValue then
+                        ", $(param.cType) $(param.name)"
+                    else
+                        ", $(param.cType) *$(param.name)"
+                |> Str.joinWith ""
+
+            swiftParams =
+                params
+                |> List.map \param -> "_ $(param.name): $(param.swiftType)"
+                |> Str.joinWith ", "
+
+            rawArgs =
+                params
+                |> List.map \param ->
+                    if param.byValue then
+                        "$(indent)let $(param.name)Raw = $(param.name).toRaw()\n"
+                    else
+                        "$(indent)var $(param.name)Raw = $(param.name).toRaw()\n"
+                |> Str.joinWith ""
+
+            externArgs =
+                params
+                |> List.map \param ->
+                    if param.byValue then
+                        ", $(param.name)Raw"
+                    else
+                        ", &$(param.name)Raw"
+                |> Str.joinWith ""
+
+            if isUnit retShape then
+                swift =
+                    """
+
+                    /// Calls `$(name)`. Roc gets references of its own to what the arguments point to.
+                    public func $(name)($(swiftParams)) {
+                    $(rawArgs)    $(externName)(nil$(externArgs))
+                    }
+
+                    """
+
+                Ok { c: "void $(externName)(void *ret$(cParams));\n", swift }
+            else
+                cRet = cTypeName types ret
+                swiftRet = swiftTypeName types ret
+
+                swift =
+                    """
+
+                    /// Calls `$(name)`. Roc gets references of its own to what the arguments point to.
+                    public func $(name)($(swiftParams)) -> $(swiftRet) {
+                        let ret = UnsafeMutablePointer<$(cRet)>.allocate(capacity: 1)
+                        defer { ret.deallocate() }
+
+                    $(rawArgs)    $(externName)(ret$(externArgs))
+
+                        return $(swiftRet)(consuming: ret.move())
+                    }
+
+                    """
+
+                Ok { c: "void $(externName)($(cRet) *ret$(cParams));\n", swift }
+
+## The C type of a Roc value, or an empty string for `{}`, which doesn't have a C representation
+cTypeName : Types, TypeId -> Str
+cTypeName = \types, id ->
+    when Types.shape types id is
+        Unit -> ""
+        RocStr -> "roc_str"
+        RocList _ -> "roc_list"
+        Bool -> "bool"
+        Num U8 -> "uint8_t"
+        Num U16 -> "uint16_t"
+        Num U32 -> "uint32_t"
+        Num U64 -> "uint64_t"
+        Num U128 -> "roc_u128"
+        Num I8 -> "int8_t"
+        Num I16 -> "int16_t"
+        Num I32 -> "int32_t"
+        Num I64 -> "int64_t"
+        Num I128 -> "roc_i128"
+        Num F32 -> "float"
+        Num F64 -> "double"
+        RecursivePointer content -> cTypeName types content
+        Struct { name } -> "roc_$(name)"
+        TagUnionPayload { name } -> "roc_$(name)"
+        TagUnion (SingleTagStruct { name }) -> "roc_$(name)"
+        TagUnion (Enumeration { name }) -> "roc_$(name)"
+        TagUnion (NullableUnwrapped { name }) -> "roc_$(name)"
+        # These are reported as unsupported when they're declared.
+        _ -> "void"
+
+swiftTypeName : Types, TypeId -> Str
+swiftTypeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "Void"
+        RocStr -> "RocString"
+        RocList elem ->
+            elemName = swiftTypeName types elem
+
+            "RocList<$(elemName)>"
+
+        Bool -> "Bool"
+        Num U8 -> "UInt8"
+        Num U16 -> "UInt16"
+        Num U32 -> "UInt32"
+        Num U64 -> "UInt64"
+        Num U128 -> "roc_u128"
+        Num I8 -> "Int8"
+        Num I16 -> "Int16"
+        Num I32 -> "Int32"
+        Num I64 -> "Int64"
+        Num I128 -> "roc_i128"
+        Num F32 -> "Float"
+        Num F64 -> "Double"
+        RecursivePointer content -> swiftTypeName types content
+        Struct { name } -> escapeSwift name
+        TagUnionPayload { name } -> escapeSwift name
+        TagUnion (SingleTagStruct { name }) -> escapeSwift name
+        TagUnion (Enumeration { name }) -> escapeSwift name
+        TagUnion (NullableUnwrapped { name }) -> escapeSwift name
+        # These are reported as unsupported when they're declared.
+        _ -> "Never"
+
+cIntType : U32 -> Str
+cIntType = \size ->
+    when size is
+        1 -> "uint8_t"
+        2 -> "uint16_t"
+        4 -> "uint32_t"
+        _ -> "uint64_t"
+
+swiftIntType : U32 -> Str
+swiftIntType = \size ->
+    when size is
+        1 -> "UInt8"
+        2 -> "UInt16"
+        4 -> "UInt32"
+        _ -> "UInt64"
+
+## Whether Roc takes arguments of this type by value, rather than by pointer.
+isPassedByValue : Types, Shape -> Bool
+isPassedByValue = \types, shape ->
+    when shape is
+        Unit | Bool | Num _ | TagUnion (Enumeration _) -> Bool.true
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.all fields \{ id } -> isPassedByValue types (Types.shape types id)
+
+        Struct { fields } | TagUnionPayload { fields } ->
+            List.all (structFields fields) \{ id } -> isPassedByValue types (Types.shape types id)
+
+        _ -> Bool.false
+
+isUnit : Shape -> Bool
+isUnit = \shape ->
+    when shape is
+        Unit -> Bool.true
+        _ -> Bool.false
+
+cHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    #pragma once
+
+    #include "RocStd.h"
+
+    """
+
+swiftHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    import CRocApp
+
+    """
+
+indent = "    "
+
+cKeywords = Set.fromList [
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+]
+
+swiftKeywords = Set.fromList [
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+]
+
+escapeC = \input ->
+    # C has no way to use a keyword as a name, so this adds an underscore to it.
+    if Set.contains cKeywords input then
+        "$(input)_"
+    else
+        input
+
+escapeSwift = \input ->
+    # use backticks for this, to prevent a syntax error due to using a reserved keyword.
+    if Set.contains swiftKeywords input then
+        "`$(input)`"
+    else
+        input
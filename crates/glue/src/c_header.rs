@@ -0,0 +1,408 @@
+//! Generates the C header for a Roc library built with `roc build --lib`.
+//!
+//! The library exposes each of the platform's entry points as a C function, so the header only
+//! has to declare those functions and the types they take and return. Types the header can't
+//! describe field by field (tag unions, closures, and so on) are declared as opaque structs
+//! of the right size and alignment, so they can still be passed around by C code.
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use roc_load::Threading;
+use roc_target::Target;
+
+use crate::load::{load_types, IgnoreErrors};
+use crate::types::{
+    RocNum, RocSingleTagPayload, RocStructFields, RocTagUnion, RocType, TypeId, Types,
+};
+
+const PRELUDE: &str = r#"// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by `roc build --lib`
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The program that loads this library has to provide these functions:
+//
+//     void* roc_alloc(size_t size, unsigned int alignment);
+//     void* roc_realloc(void* ptr, size_t new_size, size_t old_size, unsigned int alignment);
+//     void roc_dealloc(void* ptr, unsigned int alignment);
+//     void roc_panic(const struct RocStr* msg, unsigned int tag_id);
+//     void roc_dbg(const struct RocStr* loc, const struct RocStr* msg, const struct RocStr* src);
+//     void* roc_memset(void* str, int c, size_t n);
+
+// `roc dev --hot` starts the platform's hot-reload host with this environment variable set to
+// the path of a file that holds the path of the newest build of this library. Each time the
+// app is rebuilt, the file is replaced and (on Unix) the host is sent SIGUSR1, so it can load
+// the new build into a fresh RocEntryPoints table and carry on with its own state.
+#define ROC_HOT_RELOAD_MANIFEST_ENV "ROC_HOT_RELOAD_MANIFEST"
+
+// The effects this library can perform, as NUL-terminated `roc_fx_*` names followed by an
+// empty name. A host can check it to skip setting up the effects the app never uses.
+extern const char roc__used_effects[];
+
+// The app's name: its main .roc file's name, without the extension. Unlike argv[0], it doesn't
+// depend on where the binary is, or how it was started.
+extern const char roc__app_name[];
+
+// Which compiler built the app, for which target, and with which flags, as lines of text
+// (e.g. "compiler: 0.0.1"). It can be printed as it is; `roc inspect-binary` shows the same.
+extern const char roc__build_info[];
+
+// A string shorter than a RocStr is stored in the RocStr itself; in that case the high bit of
+// `capacity` is set, and the length is the last byte with its high bit cleared.
+struct RocStr {
+    char* bytes;
+    size_t len;
+    size_t capacity;
+};
+
+struct RocList {
+    void* elements;
+    size_t len;
+    size_t capacity;
+};
+"#;
+
+const LOAD_ENTRY_POINTS: &str = r#"
+// Fills in `table` with `lookup(ctx, symbol_name)` for each entry point, and returns whether
+// all of them were found.
+static inline bool roc_load_entry_points(
+    void* (*lookup)(void* ctx, const char* name),
+    void* ctx,
+    struct RocEntryPoints* table
+) {
+    bool found = true;
+"#;
+
+const POSTLUDE: &str = r#"
+#ifdef __cplusplus
+}
+#endif
+"#;
+
+/// Loads the platform at `platform_path`, and writes the C header for a library built with it
+/// for `target` to `header_path`.
+pub fn write_c_header(platform_path: &Path, header_path: &Path, target: Target) -> io::Result<()> {
+    let types = load_types(
+        platform_path.to_path_buf(),
+        Threading::AllAvailable,
+        IgnoreErrors { can: false },
+        target,
+    )?;
+
+    let types = types
+        .iter()
+        .find(|types| types.target() == target)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "unsupported target"))?;
+
+    std::fs::write(header_path, c_header(types))
+}
+
+pub fn c_header(types: &Types) -> String {
+    let mut buf = String::from(PRELUDE);
+    let mut declared = HashSet::new();
+
+    for id in types.sorted_ids() {
+        // Equivalent anonymous types (like two `Result Str U8`s) get the same C name
+        if declared.insert(type_name(types, id)) {
+            declare_type(&mut buf, types, id);
+        }
+    }
+
+    buf.push('\n');
+
+    for (name, id) in types.entry_points() {
+        declare_entry_point(&mut buf, types, name, *id);
+    }
+
+    declare_entry_point_table(&mut buf, types);
+
+    buf.push_str(POSTLUDE);
+
+    buf
+}
+
+fn declare_type(buf: &mut String, types: &Types, id: TypeId) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } | RocType::TagUnionPayload { name, fields } => {
+            match fields {
+                RocStructFields::HasNoClosure { fields } if !fields.is_empty() => {
+                    let _ = writeln!(buf, "\nstruct {name} {{");
+
+                    for (field_name, field_id) in fields {
+                        let _ = writeln!(
+                            buf,
+                            "    {} {};",
+                            type_name(types, *field_id),
+                            field_ident(field_name)
+                        );
+                    }
+
+                    buf.push_str("};\n");
+                }
+                _ => declare_opaque(buf, types, name, id),
+            }
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            let _ = writeln!(buf, "\ntypedef uint{}_t {name};", size * 8);
+
+            buf.push_str("enum {\n");
+
+            for (index, tag) in tags.iter().enumerate() {
+                let _ = writeln!(buf, "    {name}_{tag} = {index},");
+            }
+
+            buf.push_str("};\n");
+        }
+        RocType::TagUnion(tag_union) => {
+            declare_opaque(buf, types, tag_union_name(tag_union), id);
+        }
+        RocType::RocResult(_, _) | RocType::RocDict(_, _) | RocType::RocSet(_) => {
+            let name = type_name(types, id);
+
+            declare_opaque(buf, types, name.trim_start_matches("struct "), id);
+        }
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_)
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Function(_)
+        | RocType::Unit
+        | RocType::Unsized => {
+            // These are either builtin C types, or declared in the prelude
+        }
+    }
+}
+
+fn declare_opaque(buf: &mut String, types: &Types, name: &str, id: TypeId) {
+    let _ = writeln!(
+        buf,
+        "\n// Opaque: only Roc code can look inside.\nstruct {name} {{\n    _Alignas({}) uint8_t bytes[{}];\n}};",
+        types.align(id),
+        types.size_rounded_to_alignment(id).max(1),
+    );
+}
+
+fn declare_entry_point(buf: &mut String, types: &Types, name: &str, id: TypeId) {
+    if returns_closure(types, id) {
+        // The closure's size depends on the app, so the caller allocates
+        // roc__{name}_1_exposed_size() bytes for it.
+        let _ = writeln!(buf, "int64_t roc__{name}_1_exposed_size(void);");
+    }
+
+    let _ = writeln!(
+        buf,
+        "void roc__{name}_1_exposed_generic({});",
+        entry_point_params(types, id).join(", ")
+    );
+}
+
+/// A table of pointers to the entry points, so a host can look them up at runtime (e.g. with
+/// `dlsym`) instead of linking against them. Its fields follow the order of the platform's
+/// entry points, so its layout only changes when they do.
+fn declare_entry_point_table(buf: &mut String, types: &Types) {
+    buf.push_str("\nstruct RocEntryPoints {\n");
+
+    for (name, id) in types.entry_points() {
+        if returns_closure(types, *id) {
+            let _ = writeln!(buf, "    int64_t (*{name}_size)(void);");
+        }
+
+        let _ = writeln!(
+            buf,
+            "    void (*{name})({});",
+            entry_point_params(types, *id).join(", ")
+        );
+    }
+
+    buf.push_str("};\n");
+    buf.push_str(LOAD_ENTRY_POINTS);
+
+    for (name, id) in types.entry_points() {
+        if returns_closure(types, *id) {
+            let _ = writeln!(
+                buf,
+                "    found &= (*(void**)&table->{name}_size = lookup(ctx, \"roc__{name}_1_exposed_size\")) != NULL;"
+            );
+        }
+
+        let _ = writeln!(
+            buf,
+            "    found &= (*(void**)&table->{name} = lookup(ctx, \"roc__{name}_1_exposed_generic\")) != NULL;"
+        );
+    }
+
+    buf.push_str("    return found;\n}\n");
+}
+
+fn returns_closure(types: &Types, id: TypeId) -> bool {
+    match types.get_type(id) {
+        RocType::Function(roc_fn) => matches!(types.get_type(roc_fn.ret), RocType::Function(_)),
+        _ => false,
+    }
+}
+
+/// The parameters of an entry point's C function: a pointer to write the return value to,
+/// followed by the arguments.
+fn entry_point_params(types: &Types, id: TypeId) -> Vec<String> {
+    let (args, ret) = match types.get_type(id) {
+        RocType::Function(roc_fn) => (roc_fn.args.as_slice(), roc_fn.ret),
+        _ => (&[][..], id),
+    };
+
+    let mut params = Vec::with_capacity(args.len() + 1);
+
+    match types.get_type(ret) {
+        RocType::Function(_) | RocType::Unit | RocType::EmptyTagUnion => {
+            params.push("void* ret".to_string())
+        }
+        _ => params.push(format!("{}* ret", type_name(types, ret))),
+    }
+
+    for (index, arg) in args.iter().enumerate() {
+        let arg_type = type_name(types, *arg);
+
+        if is_passed_by_value(types, *arg) {
+            params.push(format!("{arg_type} arg{index}"));
+        } else {
+            params.push(format!("{arg_type}* arg{index}"));
+        }
+    }
+
+    params
+}
+
+fn type_name(types: &Types, id: TypeId) -> String {
+    match types.get_type(id) {
+        RocType::RocStr => "struct RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(num) => num_name(*num).to_string(),
+        RocType::RocList(_) => "struct RocList".to_string(),
+        RocType::RocBox(_) | RocType::RecursivePointer(_) | RocType::Unsized => "void*".to_string(),
+        RocType::Function(_) => "void*".to_string(),
+        RocType::Unit | RocType::EmptyTagUnion => "uint8_t".to_string(),
+        RocType::Struct { name, .. } | RocType::TagUnionPayload { name, .. } => {
+            format!("struct {name}")
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, .. }) => name.clone(),
+        RocType::TagUnion(tag_union) => format!("struct {}", tag_union_name(tag_union)),
+        RocType::RocResult(ok, err) => format!(
+            "struct RocResult_{}_{}",
+            mangle(types, *ok),
+            mangle(types, *err)
+        ),
+        RocType::RocDict(key, value) => format!(
+            "struct RocDict_{}_{}",
+            mangle(types, *key),
+            mangle(types, *value)
+        ),
+        RocType::RocSet(elem) => format!("struct RocSet_{}", mangle(types, *elem)),
+    }
+}
+
+/// A type's name, made usable as part of another C identifier
+fn mangle(types: &Types, id: TypeId) -> String {
+    type_name(types, id)
+        .trim_start_matches("struct ")
+        .replace('*', "Ptr")
+        .replace(' ', "_")
+}
+
+fn num_name(num: RocNum) -> &'static str {
+    match num {
+        RocNum::I8 => "int8_t",
+        RocNum::U8 => "uint8_t",
+        RocNum::I16 => "int16_t",
+        RocNum::U16 => "uint16_t",
+        RocNum::I32 => "int32_t",
+        RocNum::U32 => "uint32_t",
+        RocNum::I64 => "int64_t",
+        RocNum::U64 => "uint64_t",
+        RocNum::I128 => "__int128",
+        RocNum::U128 => "unsigned __int128",
+        RocNum::F32 => "float",
+        RocNum::F64 => "double",
+        // A Dec is an i128 scaled by 10^18
+        RocNum::Dec => "__int128",
+    }
+}
+
+fn tag_union_name(tag_union: &RocTagUnion) -> &str {
+    match tag_union {
+        RocTagUnion::Enumeration { name, .. }
+        | RocTagUnion::NonRecursive { name, .. }
+        | RocTagUnion::Recursive { name, .. }
+        | RocTagUnion::NonNullableUnwrapped { name, .. }
+        | RocTagUnion::SingleTagStruct { name, .. }
+        | RocTagUnion::NullableWrapped { name, .. }
+        | RocTagUnion::NullableUnwrapped { name, .. } => name,
+    }
+}
+
+/// Tuple fields are named by their index, which isn't a valid C identifier
+fn field_ident(name: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("f{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Roc passes values that need refcounting to its entry points by pointer, and everything else
+/// by value.
+fn is_passed_by_value(types: &Types, id: TypeId) -> bool {
+    match types.get_type(id) {
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. }) => true,
+        RocType::Unsized
+        | RocType::RocStr
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::RecursivePointer(_)
+        | RocType::TagUnion(
+            RocTagUnion::Recursive { .. }
+            | RocTagUnion::NullableWrapped { .. }
+            | RocTagUnion::NullableUnwrapped { .. }
+            | RocTagUnion::NonNullableUnwrapped { .. },
+        ) => false,
+        RocType::Function(roc_fn) => is_passed_by_value(types, roc_fn.lambda_set),
+        RocType::RocResult(ok, err) => {
+            is_passed_by_value(types, *ok) && is_passed_by_value(types, *err)
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) => tags
+            .iter()
+            .all(|(_, payload)| payload.map_or(true, |id| is_passed_by_value(types, id))),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload, .. }) => match payload {
+            RocSingleTagPayload::HasNoClosure { payload_fields } => payload_fields
+                .iter()
+                .all(|id| is_passed_by_value(types, *id)),
+            RocSingleTagPayload::HasClosure { payload_getters } => payload_getters
+                .iter()
+                .all(|(id, _)| is_passed_by_value(types, *id)),
+        },
+        RocType::Struct { fields, .. } | RocType::TagUnionPayload { fields, .. } => match fields {
+            RocStructFields::HasNoClosure { fields } => {
+                fields.iter().all(|(_, id)| is_passed_by_value(types, *id))
+            }
+            RocStructFields::HasClosure { fields } => fields
+                .iter()
+                .all(|(_, id, _)| is_passed_by_value(types, *id)),
+        },
+    }
+}
@@ -0,0 +1,49 @@
+//! Writes the functions every host has to provide to a Roc app, like `roc_alloc` and `roc_panic`,
+//! so a new platform can start from these instead of writing its own.
+//!
+//! The allocator is pluggable: the Rust version has a `RocAllocator` trait and the C version has
+//! a `RocAllocator` struct of function pointers, and both default to the C library's `malloc`.
+//!
+//! `roc_externs.h` also has the C side of `roc_std::RocHostClosure`, for hosts that pass their
+//! own functions to Roc as values.
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+const RUST_EXTERNS: &str = include_str!("../static/roc_externs.rs");
+const C_EXTERNS_HEADER: &str = include_str!("../static/roc_externs.h");
+const C_EXTERNS: &str = include_str!("../static/roc_externs.c");
+
+/// The language of the host to write the externs for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostLanguage {
+    /// Writes `roc_externs.rs`, a module for the host crate.
+    Rust,
+    /// Writes `roc_externs.c`, and `roc_externs.h` for setting the allocator.
+    C,
+}
+
+impl FromStr for HostLanguage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(HostLanguage::Rust),
+            "c" => Ok(HostLanguage::C),
+            _ => Err(()),
+        }
+    }
+}
+
+pub fn write_externs(glue_dir: &Path, language: HostLanguage) -> io::Result<()> {
+    fs::create_dir_all(glue_dir)?;
+
+    match language {
+        HostLanguage::Rust => fs::write(glue_dir.join("roc_externs.rs"), RUST_EXTERNS),
+        HostLanguage::C => {
+            fs::write(glue_dir.join("roc_externs.h"), C_EXTERNS_HEADER)?;
+            fs::write(glue_dir.join("roc_externs.c"), C_EXTERNS)
+        }
+    }
+}
@@ -1,9 +1,12 @@
 //! Generates code needed for platform hosts to communicate with Roc apps.
 //! This tool is not necessary for writing a platform in another language,
-//! however, it's a great convenience! Currently supports Rust platforms, and
+//! however, it's a great convenience! Currently supports Rust, C#, Swift, and Go platforms, and
 //! the plan is to support any language via a plugin model.
+pub mod c_header;
 pub mod enums;
+pub mod externs;
 pub mod load;
+pub mod no_std;
 pub mod roc_type;
 pub mod structs;
 pub mod types;
@@ -16,6 +16,7 @@ use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadedModule, LoadingPro
 use roc_mono::ir::{generate_glue_procs, CrashTag, GlueProc, OptLevel};
 use roc_mono::layout::{GlobalLayoutInterner, LayoutCache, LayoutInterner};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::cli::OutputFormat;
 use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
 use roc_target::{Architecture, Target, TargetFromTripleError::TripleUnsupported};
 use roc_types::subs::{Subs, Variable};
@@ -57,8 +58,14 @@ pub fn generate(
                 backend,
                 opt_level: OptLevel::Development,
                 emit_debug_info: false,
-                emit_llvm_ir: false,
+                emit_debug_locations: false,
+                emit: Default::default(),
                 fuzz: false,
+                frame_pointers: false,
+                inline_threshold: 0,
+                alias_analysis: Default::default(),
+                verbose_layouts: false,
+                reproducible: false,
             };
 
             let load_config = standard_load_config(
@@ -90,6 +97,7 @@ pub fn generate(
                     None,
                     RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
                     load_config,
+                    OutputFormat::Human,
                     Some(dylib_dir.path()),
                 ),
                 Err(_) => {
@@ -101,6 +109,8 @@ pub fn generate(
             let answer = match res_binary_path {
                 Ok(BuiltFile {
                     binary_path,
+                    platform_path: _,
+                    package_urls: _,
                     problems,
                     total_time,
                     expect_metadata: _,
@@ -186,10 +196,16 @@ pub fn generate(
 
                     Ok(0)
                 }
-                Err(BuildFileError::ErrorModule { module, total_time }) => {
-                    handle_error_module(module, total_time, spec_path.as_os_str(), true)
+                Err(BuildFileError::ErrorModule { module, total_time }) => handle_error_module(
+                    module,
+                    total_time,
+                    spec_path.as_os_str(),
+                    true,
+                    OutputFormat::Human,
+                ),
+                Err(BuildFileError::LoadingProblem(problem)) => {
+                    handle_loading_problem(problem, OutputFormat::Human)
                 }
-                Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
             };
 
             // Extend the lifetime of the tempdir to after we're done with everything,
@@ -422,6 +438,8 @@ pub fn load_types(
             palette: DEFAULT_PALETTE,
             threading,
             exec_mode: ExecutionMode::Check,
+            reproducible: false,
+            coverage: false,
         },
     )
     .unwrap_or_else(|problem| match problem {
@@ -0,0 +1,40 @@
+//! Makes the glue from the Rust glue spec build without `std` by default, for embedded hosts.
+//!
+//! The generated `roc_app` crate only needs `core` and `alloc`, and only uses `std` when its
+//! `std` feature is on. That feature is on by default, so this turns it off in the generated
+//! `roc_app/Cargo.toml`, which means the host doesn't need `default-features = false`.
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const DEFAULT_FEATURES: &str = "default = [\"std\"]";
+const NO_STD_DEFAULT_FEATURES: &str = "default = []";
+
+pub fn disable_std(glue_dir: &Path) -> io::Result<()> {
+    let cargo_toml_path = glue_dir.join("roc_app").join("Cargo.toml");
+
+    let cargo_toml = fs::read_to_string(&cargo_toml_path).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!(
+                "`--no-std` only works with the Rust glue spec, but I couldn't read {}: {err}",
+                cargo_toml_path.display()
+            ),
+        )
+    })?;
+
+    if !cargo_toml.contains(DEFAULT_FEATURES) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "`--no-std` expected {} to turn on the `std` feature by default, but it doesn't.",
+                cargo_toml_path.display()
+            ),
+        ));
+    }
+
+    fs::write(
+        cargo_toml_path,
+        cargo_toml.replace(DEFAULT_FEATURES, NO_STD_DEFAULT_FEATURES),
+    )
+}
@@ -0,0 +1,17 @@
+// swift-tools-version:5.9
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+import PackageDescription
+
+let package = Package(
+    name: "RocApp",
+    products: [
+        .library(name: "RocApp", targets: ["RocApp"]),
+    ],
+    targets: [
+        // The C types Roc values are laid out as, and the entry points of the app
+        .systemLibrary(name: "CRocApp"),
+        // Swift types that wrap those, and manage their references with ARC
+        .target(name: "RocApp", dependencies: ["CRocApp"]),
+    ]
+)
@@ -0,0 +1,52 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#nullable enable
+
+namespace Roc;
+
+using System;
+using System.Runtime.InteropServices;
+
+/// <summary>
+/// The functions every Roc app calls on its host.
+/// </summary>
+/// <remarks>
+/// These are exported under the names Roc expects when the host is compiled with Native AOT,
+/// which links the app into the host. Hosts that need something different, like their own
+/// allocator, can leave this file out and export their own.
+/// </remarks>
+public static unsafe class RocHost
+{
+    [UnmanagedCallersOnly(EntryPoint = "roc_alloc")]
+    public static void* Alloc(nuint size, uint alignment) => RocAllocator.Alloc(size, alignment);
+
+    [UnmanagedCallersOnly(EntryPoint = "roc_realloc")]
+    public static void* Realloc(void* ptr, nuint newSize, nuint oldSize, uint alignment) =>
+        RocAllocator.Realloc(ptr, newSize, alignment);
+
+    [UnmanagedCallersOnly(EntryPoint = "roc_dealloc")]
+    public static void Dealloc(void* ptr, uint alignment) => RocAllocator.Dealloc(ptr, alignment);
+
+    [UnmanagedCallersOnly(EntryPoint = "roc_memset")]
+    public static void* Memset(void* dst, int value, nuint count)
+    {
+        NativeMemory.Fill(dst, count, (byte)value);
+
+        return dst;
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "roc_panic")]
+    public static void Panic(RocStr* message, uint tagId)
+    {
+        var source = tagId == 0 ? "Roc standard library" : "Application";
+
+        // Exceptions can't unwind through Roc code, so this is as far as we can go.
+        Environment.FailFast($"{source} hit a panic: {*message}");
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "roc_dbg")]
+    public static void Dbg(RocStr* location, RocStr* message, RocStr* source)
+    {
+        Console.Error.WriteLine($"[{*location}] {*source} = {*message}");
+    }
+}
@@ -0,0 +1,68 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+import CRocApp
+
+#if canImport(Darwin)
+import Darwin
+#else
+import Glibc
+#endif
+
+// The functions every Roc app calls on its host. Hosts that need something different,
+// like their own allocator, can leave this file out and export their own.
+
+@_cdecl("roc_alloc")
+public func rocAlloc(_ size: Int, _ alignment: UInt32) -> UnsafeMutableRawPointer? {
+    var pointer: UnsafeMutableRawPointer?
+
+    // posix_memalign needs at least the alignment of a pointer.
+    let alignment = max(Int(alignment), MemoryLayout<UnsafeRawPointer>.alignment)
+
+    return posix_memalign(&pointer, alignment, size) == 0 ? pointer : nil
+}
+
+@_cdecl("roc_realloc")
+public func rocRealloc(
+    _ pointer: UnsafeMutableRawPointer?,
+    _ newSize: Int,
+    _ oldSize: Int,
+    _ alignment: UInt32
+) -> UnsafeMutableRawPointer? {
+    guard let newPointer = rocAlloc(newSize, alignment) else {
+        return nil
+    }
+
+    if let pointer {
+        newPointer.copyMemory(from: pointer, byteCount: min(oldSize, newSize))
+        free(pointer)
+    }
+
+    return newPointer
+}
+
+@_cdecl("roc_dealloc")
+public func rocDealloc(_ pointer: UnsafeMutableRawPointer?, _ alignment: UInt32) {
+    free(pointer)
+}
+
+@_cdecl("roc_memset")
+public func rocMemset(_ destination: UnsafeMutableRawPointer?, _ value: Int32, _ count: Int) -> UnsafeMutableRawPointer? {
+    memset(destination, value, count)
+}
+
+@_cdecl("roc_panic")
+public func rocPanic(_ message: UnsafePointer<roc_str>, _ tagId: UInt32) {
+    let source = tagId == 0 ? "Roc standard library" : "Application"
+
+    fputs("\(source) hit a panic: \(RocString(borrowing: message.pointee))\n", stderr)
+    exit(1)
+}
+
+@_cdecl("roc_dbg")
+public func rocDbg(_ location: UnsafePointer<roc_str>, _ message: UnsafePointer<roc_str>, _ source: UnsafePointer<roc_str>) {
+    let location = RocString(borrowing: location.pointee)
+    let message = RocString(borrowing: message.pointee)
+    let source = RocString(borrowing: source.pointee)
+
+    fputs("[\(location)] \(source) = \(message)\n", stderr)
+}
@@ -0,0 +1,372 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#nullable enable
+
+namespace Roc;
+
+using System;
+using System.Runtime.InteropServices;
+using System.Text;
+
+/// <summary>
+/// A Roc value that holds references to memory on the Roc heap, like a Str or a List.
+/// </summary>
+public interface IRocRefcounted
+{
+    /// <summary>Adds a reference to everything this value points to, like before passing a copy of it to Roc.</summary>
+    void IncrementRefcounts();
+
+    /// <summary>Drops this value's references, freeing whatever it was the last reference to.</summary>
+    void DecrementRefcounts();
+}
+
+/// <summary>
+/// Owns one reference to a Roc value, and drops it when disposed or finalized.
+/// </summary>
+/// <remarks>
+/// The values Roc returns are owned by the caller. Wrapping them in this makes sure their
+/// references get dropped even if the host forgets to, instead of leaking the memory.
+/// </remarks>
+public sealed class RocOwned<T> : SafeHandle where T : unmanaged, IRocRefcounted
+{
+    public RocOwned(T value) : base(IntPtr.Zero, ownsHandle: true)
+    {
+        Value = value;
+
+        // The handle only records whether the value still needs to be released;
+        // the references themselves are inside the value.
+        SetHandle(new IntPtr(1));
+    }
+
+    public T Value { get; }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        Value.DecrementRefcounts();
+
+        return true;
+    }
+}
+
+/// <summary>
+/// The allocator for the Roc heap. It must be the same one that `roc_alloc` and `roc_dealloc` use,
+/// because Roc frees the values the host creates, and the host frees the values Roc creates.
+/// </summary>
+public static unsafe class RocAllocator
+{
+    public static void* Alloc(nuint size, uint alignment) =>
+        NativeMemory.AlignedAlloc(size, AtLeastPointerAligned(alignment));
+
+    public static void* Realloc(void* ptr, nuint newSize, uint alignment) =>
+        NativeMemory.AlignedRealloc(ptr, newSize, AtLeastPointerAligned(alignment));
+
+    public static void Dealloc(void* ptr, uint alignment) => NativeMemory.AlignedFree(ptr);
+
+    /// <summary>
+    /// Allocates room for `size` bytes of elements behind a reference count of 1, and returns a pointer to the elements.
+    /// </summary>
+    internal static byte* AllocRefcounted(nuint size, uint alignment)
+    {
+        alignment = AtLeastPointerAligned(alignment);
+
+        var allocation = (byte*)Alloc(alignment + size, alignment);
+        var elements = allocation + alignment;
+
+        *((nint*)elements - 1) = RocRefcount.One;
+
+        return elements;
+    }
+
+    private static uint AtLeastPointerAligned(uint alignment) => Math.Max(alignment, (uint)sizeof(nint));
+}
+
+internal static unsafe class RocRefcount
+{
+    /// <summary>The reference count of a value with only one reference. Readonly values, like string literals, have 0.</summary>
+    public static readonly nint One = nint.MinValue;
+
+    /// <summary>The top bit of a Str or List length, which is set for slices of another allocation.</summary>
+    public static readonly nuint SeamlessSliceBit = unchecked((nuint)nint.MinValue);
+
+    public static void Increment(nint* refcount)
+    {
+        if (*refcount != 0)
+        {
+            *refcount += 1;
+        }
+    }
+
+    /// <summary>Returns true once the last reference is gone, and the memory should be freed.</summary>
+    public static bool Decrement(nint* refcount)
+    {
+        if (*refcount == 0)
+        {
+            return false;
+        }
+
+        if (*refcount == One)
+        {
+            return true;
+        }
+
+        *refcount -= 1;
+
+        return false;
+    }
+
+    /// <summary>The alignment of the allocations that hold `T`s, which always has room for the reference count.</summary>
+    public static uint AllocAlignment<T>() where T : unmanaged =>
+        (uint)Math.Max(sizeof(AlignmentOf<T>) - sizeof(T), sizeof(nint));
+
+    [StructLayout(LayoutKind.Sequential)]
+    private struct AlignmentOf<T> where T : unmanaged
+    {
+        private byte padding;
+        private T value;
+    }
+}
+
+/// <summary>
+/// The Roc `{}` type, and the payload of tags that don't have one.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct RocUnit
+{
+    public override string ToString() => "{}";
+}
+
+/// <summary>
+/// A Roc `Str`. Strings shorter than this struct are stored inside it rather than on the heap.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public unsafe struct RocStr : IRocRefcounted
+{
+    private byte* bytes;
+    private nuint length;
+    private nuint capacityOrAllocPtr;
+
+    public static RocStr FromString(string value)
+    {
+        var length = Encoding.UTF8.GetByteCount(value);
+        var str = default(RocStr);
+
+        if (length < sizeof(RocStr))
+        {
+            var small = (byte*)&str;
+
+            Encoding.UTF8.GetBytes(value, new Span<byte>(small, length));
+            small[sizeof(RocStr) - 1] = (byte)(length | 0x80);
+
+            return str;
+        }
+
+        str.bytes = RocAllocator.AllocRefcounted((nuint)length, 1);
+        str.length = (nuint)length;
+        str.capacityOrAllocPtr = (nuint)length;
+
+        Encoding.UTF8.GetBytes(value, new Span<byte>(str.bytes, length));
+
+        return str;
+    }
+
+    private bool IsSmall => (nint)capacityOrAllocPtr < 0;
+
+    private bool IsSeamlessSlice => (length & RocRefcount.SeamlessSliceBit) != 0;
+
+    private nint* Refcount =>
+        IsSeamlessSlice ? (nint*)(capacityOrAllocPtr << 1) - 1 : (nint*)bytes - 1;
+
+    public void IncrementRefcounts()
+    {
+        if (!IsSmall && bytes != null)
+        {
+            RocRefcount.Increment(Refcount);
+        }
+    }
+
+    public void DecrementRefcounts()
+    {
+        if (!IsSmall && bytes != null && RocRefcount.Decrement(Refcount))
+        {
+            // Strings are allocated pointer-aligned, so the reference count is at the start of the allocation.
+            RocAllocator.Dealloc(Refcount, 1);
+        }
+    }
+
+    public override string ToString()
+    {
+        if (IsSmall)
+        {
+            fixed (RocStr* self = &this)
+            {
+                var small = (byte*)self;
+
+                return Encoding.UTF8.GetString(small, small[sizeof(RocStr) - 1] & 0x7F);
+            }
+        }
+
+        return Encoding.UTF8.GetString(bytes, (int)(length & ~RocRefcount.SeamlessSliceBit));
+    }
+}
+
+/// <summary>
+/// A Roc `List`. Its elements are on the heap, behind a reference count.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public unsafe struct RocList<T> : IRocRefcounted where T : unmanaged
+{
+    private T* elements;
+    private nuint length;
+    private nuint capacityOrAllocPtr;
+
+    /// <summary>
+    /// Copies `items` into a new list, which takes over the references they hold.
+    /// </summary>
+    public static RocList<T> FromSpan(ReadOnlySpan<T> items)
+    {
+        var list = default(RocList<T>);
+
+        if (items.IsEmpty)
+        {
+            return list;
+        }
+
+        list.elements = (T*)RocAllocator.AllocRefcounted(
+            (nuint)(items.Length * sizeof(T)),
+            RocRefcount.AllocAlignment<T>()
+        );
+        list.length = (nuint)items.Length;
+        list.capacityOrAllocPtr = (nuint)items.Length;
+
+        items.CopyTo(new Span<T>(list.elements, items.Length));
+
+        return list;
+    }
+
+    public int Length => (int)(length & ~RocRefcount.SeamlessSliceBit);
+
+    public T this[int index]
+    {
+        get
+        {
+            if ((uint)index >= (uint)Length)
+            {
+                throw new IndexOutOfRangeException();
+            }
+
+            return elements[index];
+        }
+    }
+
+    public ReadOnlySpan<T> AsSpan() => new(elements, Length);
+
+    private bool IsSeamlessSlice => (length & RocRefcount.SeamlessSliceBit) != 0;
+
+    /// <summary>The elements of the whole allocation, which a slice only has part of.</summary>
+    private byte* AllocatedElements =>
+        IsSeamlessSlice ? (byte*)(capacityOrAllocPtr << 1) : (byte*)elements;
+
+    public void IncrementRefcounts()
+    {
+        if (elements != null)
+        {
+            RocRefcount.Increment((nint*)AllocatedElements - 1);
+        }
+    }
+
+    public void DecrementRefcounts()
+    {
+        if (elements == null || !RocRefcount.Decrement((nint*)AllocatedElements - 1))
+        {
+            return;
+        }
+
+        if (default(T) is IRocRefcounted)
+        {
+            for (var i = 0; i < Length; i++)
+            {
+                ((IRocRefcounted)elements[i]).DecrementRefcounts();
+            }
+        }
+
+        RocAllocator.Dealloc(
+            AllocatedElements - RocRefcount.AllocAlignment<T>(),
+            RocRefcount.AllocAlignment<T>()
+        );
+    }
+
+    public override string ToString() => $"[{string.Join(", ", AsSpan().ToArray())}]";
+}
+
+/// <summary>
+/// A Roc `Box`. Its value is on the heap, behind a reference count.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public unsafe struct RocBox<T> : IRocRefcounted where T : unmanaged
+{
+    private T* value;
+
+    /// <summary>
+    /// Copies `value` to the heap. The box takes over the references it holds.
+    /// </summary>
+    public static RocBox<T> FromValue(T value)
+    {
+        var box = default(RocBox<T>);
+
+        box.value = (T*)RocAllocator.AllocRefcounted((nuint)sizeof(T), RocRefcount.AllocAlignment<T>());
+        *box.value = value;
+
+        return box;
+    }
+
+    public T Value => *value;
+
+    public void IncrementRefcounts() => RocRefcount.Increment((nint*)value - 1);
+
+    public void DecrementRefcounts()
+    {
+        if (!RocRefcount.Decrement((nint*)value - 1))
+        {
+            return;
+        }
+
+        if (*value is IRocRefcounted inner)
+        {
+            inner.DecrementRefcounts();
+        }
+
+        RocAllocator.Dealloc(
+            (byte*)value - RocRefcount.AllocAlignment<T>(),
+            RocRefcount.AllocAlignment<T>()
+        );
+    }
+
+    public override string ToString() => $"Box({Value})";
+}
+
+/// <summary>
+/// A Roc `Dec`: a fixed-point decimal with 18 digits after the point.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct RocDec
+{
+    private const long OneQuintillion = 1_000_000_000_000_000_000;
+
+    private Int128 value;
+
+    public static RocDec FromRaw(Int128 raw) => new() { value = raw };
+
+    /// <summary>The value times 10^18, which is how Roc stores it.</summary>
+    public Int128 Raw => value;
+
+    public override string ToString()
+    {
+        var whole = value / OneQuintillion;
+        var fraction = Int128.Abs(value % OneQuintillion).ToString().PadLeft(18, '0').TrimEnd('0');
+        var sign = value < 0 && whole == 0 ? "-" : "";
+
+        return fraction.Length == 0 ? $"{sign}{whole}.0" : $"{sign}{whole}.{fraction}";
+    }
+}
@@ -0,0 +1,155 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+// The host provides these, and the app calls them too.
+void *roc_alloc(size_t size, uint32_t alignment);
+void roc_dealloc(void *ptr, uint32_t alignment);
+
+typedef struct roc_str {
+    char *bytes;
+    size_t len;
+    size_t capacity_or_alloc_ptr;
+} roc_str;
+
+typedef struct roc_list {
+    void *elements;
+    size_t len;
+    size_t capacity_or_alloc_ptr;
+} roc_list;
+
+typedef struct __attribute__((aligned(16))) roc_u128 {
+    uint64_t lo;
+    uint64_t hi;
+} roc_u128;
+
+typedef struct __attribute__((aligned(16))) roc_i128 {
+    uint64_t lo;
+    int64_t hi;
+} roc_i128;
+
+// The reference count of a value with only one reference. Readonly values, like string literals, have 0.
+static inline intptr_t roc_refcount_one(void) { return INTPTR_MIN; }
+
+// The top bit of a Str or List length, which is set for slices of another allocation.
+static inline size_t roc_seamless_slice_bit(void) { return (size_t)INTPTR_MIN; }
+
+static inline uint32_t roc_alloc_alignment(uint32_t alignment) {
+    return alignment > sizeof(intptr_t) ? alignment : sizeof(intptr_t);
+}
+
+// Allocates `size` bytes behind a reference count of 1, and returns a pointer to those bytes.
+static inline void *roc_alloc_refcounted(size_t size, uint32_t alignment) {
+    alignment = roc_alloc_alignment(alignment);
+
+    char *allocation = roc_alloc(alignment + size, alignment);
+    char *data = allocation + alignment;
+
+    ((intptr_t *)data)[-1] = roc_refcount_one();
+
+    return data;
+}
+
+static inline void roc_free_refcounted(void *data, uint32_t alignment) {
+    alignment = roc_alloc_alignment(alignment);
+
+    roc_dealloc((char *)data - alignment, alignment);
+}
+
+static inline void roc_incref(void *data) {
+    intptr_t *refcount = (intptr_t *)data - 1;
+
+    if (*refcount != 0) {
+        *refcount += 1;
+    }
+}
+
+// Returns true once the last reference is gone, and the memory should be freed.
+static inline bool roc_decref(void *data) {
+    intptr_t *refcount = (intptr_t *)data - 1;
+
+    if (*refcount == 0) {
+        return false;
+    }
+
+    if (*refcount == roc_refcount_one()) {
+        return true;
+    }
+
+    *refcount -= 1;
+
+    return false;
+}
+
+// Strings shorter than a roc_str are stored inside it, with their length in the last byte.
+static inline bool roc_str_is_small(roc_str str) { return (intptr_t)str.capacity_or_alloc_ptr < 0; }
+
+static inline size_t roc_str_len(roc_str str) {
+    if (roc_str_is_small(str)) {
+        return ((unsigned char *)&str)[sizeof(roc_str) - 1] & 0x7F;
+    }
+
+    return str.len & ~roc_seamless_slice_bit();
+}
+
+static inline const char *roc_str_bytes(const roc_str *str) {
+    return roc_str_is_small(*str) ? (const char *)str : str->bytes;
+}
+
+static inline roc_str roc_str_from_bytes(const char *bytes, size_t len) {
+    roc_str str = {0};
+
+    if (len < sizeof(roc_str)) {
+        memcpy(&str, bytes, len);
+        ((unsigned char *)&str)[sizeof(roc_str) - 1] = (unsigned char)(len | 0x80);
+    } else {
+        str.bytes = roc_alloc_refcounted(len, 1);
+        str.len = len;
+        str.capacity_or_alloc_ptr = len;
+
+        memcpy(str.bytes, bytes, len);
+    }
+
+    return str;
+}
+
+// The start of the string's allocation, or NULL if it doesn't have one.
+static inline void *roc_str_allocation(roc_str str) {
+    if (roc_str_is_small(str) || str.bytes == NULL) {
+        return NULL;
+    }
+
+    return (str.len & roc_seamless_slice_bit()) ? (void *)(str.capacity_or_alloc_ptr << 1) : str.bytes;
+}
+
+static inline void roc_str_incref(roc_str str) {
+    void *allocation = roc_str_allocation(str);
+
+    if (allocation != NULL) {
+        roc_incref(allocation);
+    }
+}
+
+static inline void roc_str_decref(roc_str str) {
+    void *allocation = roc_str_allocation(str);
+
+    if (allocation != NULL && roc_decref(allocation)) {
+        roc_free_refcounted(allocation, 1);
+    }
+}
+
+static inline size_t roc_list_len(roc_list list) { return list.len & ~roc_seamless_slice_bit(); }
+
+// The elements of the list's whole allocation, which a slice only has part of, or NULL if it doesn't have one.
+static inline void *roc_list_allocation(roc_list list) {
+    if (list.elements == NULL) {
+        return NULL;
+    }
+
+    return (list.len & roc_seamless_slice_bit()) ? (void *)(list.capacity_or_alloc_ptr << 1) : list.elements;
+}
@@ -0,0 +1,217 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+@_exported import CRocApp
+
+/// A Swift value that Roc has a C representation for, in RocApp.h.
+///
+/// Swift values hold their Roc references in classes like `RocString`, so ARC decides when
+/// they get dropped: copying a Swift value shares its references, and the Roc reference is
+/// dropped when the last Swift reference to it goes away.
+public protocol RocRepresentable {
+    associatedtype Raw
+
+    /// Takes over the references to Roc memory that `raw` holds.
+    init(consuming raw: Raw)
+
+    /// Adds references of its own to the Roc memory that `raw` points to.
+    init(borrowing raw: Raw)
+
+    /// A raw value with new references, for handing over to Roc.
+    func toRaw() -> Raw
+}
+
+/// Values that don't point to any Roc memory are their own representation.
+public protocol RocTrivial: RocRepresentable where Raw == Self {}
+
+extension RocTrivial {
+    public init(consuming raw: Self) { self = raw }
+    public init(borrowing raw: Self) { self = raw }
+    public func toRaw() -> Self { self }
+}
+
+extension Bool: RocTrivial {}
+extension Int8: RocTrivial {}
+extension Int16: RocTrivial {}
+extension Int32: RocTrivial {}
+extension Int64: RocTrivial {}
+extension UInt8: RocTrivial {}
+extension UInt16: RocTrivial {}
+extension UInt32: RocTrivial {}
+extension UInt64: RocTrivial {}
+extension Float: RocTrivial {}
+extension Double: RocTrivial {}
+
+extension roc_u128: RocTrivial, CustomStringConvertible {
+    public var description: String {
+        hi == 0 ? "\(lo)" : "roc_u128(hi: \(hi), lo: \(lo))"
+    }
+}
+
+extension roc_i128: RocTrivial, CustomStringConvertible {
+    public var description: String {
+        if hi == 0 {
+            return "\(lo)"
+        } else if hi == -1 && lo >= 1 << 63 {
+            return "\(Int64(bitPattern: lo))"
+        } else {
+            return "roc_i128(hi: \(hi), lo: \(lo))"
+        }
+    }
+}
+
+/// A Roc `Str`, which holds one reference to Roc's copy of the string.
+public final class RocString: RocRepresentable, CustomStringConvertible {
+    private let raw: roc_str
+
+    public init(consuming raw: roc_str) {
+        self.raw = raw
+    }
+
+    public convenience init(borrowing raw: roc_str) {
+        roc_str_incref(raw)
+        self.init(consuming: raw)
+    }
+
+    public convenience init(_ string: String) {
+        var string = string
+        let raw = string.withUTF8 { utf8 in
+            utf8.withMemoryRebound(to: CChar.self) { roc_str_from_bytes($0.baseAddress, $0.count) }
+        }
+
+        self.init(consuming: raw)
+    }
+
+    deinit {
+        roc_str_decref(raw)
+    }
+
+    public func toRaw() -> roc_str {
+        roc_str_incref(raw)
+
+        return raw
+    }
+
+    public var string: String {
+        withUnsafePointer(to: raw) { pointer in
+            let bytes = UnsafeRawPointer(roc_str_bytes(pointer)!).assumingMemoryBound(to: UInt8.self)
+
+            return String(decoding: UnsafeBufferPointer(start: bytes, count: roc_str_len(raw)), as: UTF8.self)
+        }
+    }
+
+    public var description: String { string }
+}
+
+/// A Roc `List`, which holds one reference to Roc's copy of the elements.
+public final class RocList<Element: RocRepresentable>: RocRepresentable, CustomStringConvertible {
+    private let raw: roc_list
+
+    public init(consuming raw: roc_list) {
+        self.raw = raw
+    }
+
+    public convenience init(borrowing raw: roc_list) {
+        if let allocation = roc_list_allocation(raw) {
+            roc_incref(allocation)
+        }
+
+        self.init(consuming: raw)
+    }
+
+    /// Copies `elements` into a new Roc list.
+    public convenience init(_ elements: [Element]) {
+        var raw = roc_list()
+
+        if !elements.isEmpty {
+            let data = roc_alloc_refcounted(MemoryLayout<Element.Raw>.stride * elements.count, Self.alignment)!
+            let typed = data.bindMemory(to: Element.Raw.self, capacity: elements.count)
+
+            for (index, element) in elements.enumerated() {
+                (typed + index).initialize(to: element.toRaw())
+            }
+
+            raw.elements = data
+            raw.len = elements.count
+            raw.capacity_or_alloc_ptr = elements.count
+        }
+
+        self.init(consuming: raw)
+    }
+
+    deinit {
+        guard let allocation = roc_list_allocation(raw), roc_decref(allocation) else {
+            return
+        }
+
+        // Taking over the elements' references drops them along with the Swift values.
+        let elements = raw.elements.bindMemory(to: Element.Raw.self, capacity: count)
+
+        for index in 0..<count {
+            _ = Element(consuming: (elements + index).move())
+        }
+
+        roc_free_refcounted(allocation, Self.alignment)
+    }
+
+    private static var alignment: UInt32 { UInt32(MemoryLayout<Element.Raw>.alignment) }
+
+    public func toRaw() -> roc_list {
+        if let allocation = roc_list_allocation(raw) {
+            roc_incref(allocation)
+        }
+
+        return raw
+    }
+
+    public var count: Int { roc_list_len(raw) }
+
+    public subscript(index: Int) -> Element {
+        precondition(index >= 0 && index < count, "Index out of range")
+
+        return Element(borrowing: raw.elements.assumingMemoryBound(to: Element.Raw.self)[index])
+    }
+
+    public var elements: [Element] { (0..<count).map { self[$0] } }
+
+    public var description: String { "[\(elements.map { "\($0)" }.joined(separator: ", "))]" }
+}
+
+/// A node of a recursive tag union, which holds one reference to Roc's copy of its payload.
+public final class RocNode<Payload: RocRepresentable> {
+    private let pointer: UnsafeMutablePointer<Payload.Raw>
+
+    public init(consuming pointer: UnsafeMutablePointer<Payload.Raw>) {
+        self.pointer = pointer
+    }
+
+    public convenience init(borrowing pointer: UnsafeMutablePointer<Payload.Raw>) {
+        roc_incref(pointer)
+        self.init(consuming: pointer)
+    }
+
+    /// Copies `payload` into a new node.
+    public convenience init(_ payload: Payload) {
+        let data = roc_alloc_refcounted(MemoryLayout<Payload.Raw>.size, Self.alignment)!
+        let pointer = data.bindMemory(to: Payload.Raw.self, capacity: 1)
+
+        pointer.initialize(to: payload.toRaw())
+        self.init(consuming: pointer)
+    }
+
+    deinit {
+        if roc_decref(pointer) {
+            _ = Payload(consuming: pointer.move())
+            roc_free_refcounted(pointer, Self.alignment)
+        }
+    }
+
+    private static var alignment: UInt32 { UInt32(MemoryLayout<Payload.Raw>.alignment) }
+
+    public func toRaw() -> UnsafeMutablePointer<Payload.Raw> {
+        roc_incref(pointer)
+
+        return pointer
+    }
+
+    public var payload: Payload { Payload(borrowing: pointer.pointee) }
+}
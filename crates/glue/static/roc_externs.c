@@ -0,0 +1,93 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+// The functions every Roc app calls on its host. Compile this along with the host, and call
+// roc_set_allocator (from roc_externs.h) before calling into Roc if Roc's memory should come
+// from somewhere other than the C library's malloc.
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "roc_externs.h"
+
+// A string shorter than a RocStr is stored in the RocStr itself; in that case the high bit of
+// `capacity` is set, and the length is the last byte with its high bit cleared.
+struct RocStr {
+    char* bytes;
+    size_t len;
+    size_t capacity;
+};
+
+static void* system_alloc(size_t size, uint32_t alignment) {
+    // malloc aligns to 16 bytes on 64-bit targets, which covers everything Roc allocates.
+    (void)alignment;
+    return malloc(size);
+}
+
+static void* system_realloc(void* ptr, size_t new_size, size_t old_size, uint32_t alignment) {
+    (void)old_size;
+    (void)alignment;
+    return realloc(ptr, new_size);
+}
+
+static void system_dealloc(void* ptr, uint32_t alignment) {
+    (void)alignment;
+    free(ptr);
+}
+
+static const RocAllocator system_allocator = {system_alloc, system_realloc, system_dealloc};
+
+static const RocAllocator* allocator = &system_allocator;
+static int has_allocated = 0;
+
+int roc_set_allocator(const RocAllocator* new_allocator) {
+    if (has_allocated) {
+        return 0;
+    }
+
+    allocator = new_allocator;
+    return 1;
+}
+
+void* roc_alloc(size_t size, unsigned int alignment) {
+    has_allocated = 1;
+    return allocator->alloc(size, alignment);
+}
+
+void* roc_realloc(void* ptr, size_t new_size, size_t old_size, unsigned int alignment) {
+    return allocator->realloc(ptr, new_size, old_size, alignment);
+}
+
+void roc_dealloc(void* ptr, unsigned int alignment) {
+    allocator->dealloc(ptr, alignment);
+}
+
+void* roc_memset(void* str, int c, size_t n) {
+    return memset(str, c, n);
+}
+
+static void print_roc_str(FILE* stream, const struct RocStr* str) {
+    if ((intptr_t)str->capacity < 0) {
+        size_t len = ((const unsigned char*)str)[sizeof(struct RocStr) - 1] & 0x7F;
+        fwrite((const char*)str, 1, len, stream);
+    } else {
+        fwrite(str->bytes, 1, str->len & (SIZE_MAX >> 1), stream);
+    }
+}
+
+void roc_panic(const struct RocStr* msg, unsigned int tag_id) {
+    fputs(tag_id == 0 ? "Roc standard library hit a panic: " : "Application hit a panic: ", stderr);
+    print_roc_str(stderr, msg);
+    fputc('\n', stderr);
+    exit(1);
+}
+
+void roc_dbg(const struct RocStr* loc, const struct RocStr* msg, const struct RocStr* src) {
+    fputc('[', stderr);
+    print_roc_str(stderr, loc);
+    fputs("] ", stderr);
+    print_roc_str(stderr, src);
+    fputs(" = ", stderr);
+    print_roc_str(stderr, msg);
+    fputc('\n', stderr);
+}
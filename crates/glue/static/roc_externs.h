@@ -0,0 +1,48 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Where Roc gets its memory from. These have to behave like malloc, realloc, and free,
+// and the memory they hand out has to be aligned to at least `alignment` bytes.
+typedef struct RocAllocator {
+    void* (*alloc)(size_t size, uint32_t alignment);
+    void* (*realloc)(void* ptr, size_t new_size, size_t old_size, uint32_t alignment);
+    void (*dealloc)(void* ptr, uint32_t alignment);
+} RocAllocator;
+
+// Makes Roc get its memory from `allocator`, which has to outlive every call into Roc.
+// Memory has to go back to the allocator it came from, so this only works before Roc has
+// allocated anything; after that, it returns 0 and changes nothing.
+int roc_set_allocator(const RocAllocator* allocator);
+
+// A host function passed to Roc as a value, which Roc sees as a `{ call : U64, data : U64 }`
+// record. Roc can only hand it back to the host, through a hosted function that calls it
+// with roc_host_closure_call. `data` has to stay valid for as long as Roc can call it.
+typedef void (*RocHostFn)(void* data, const void* arg, void* ret);
+
+typedef struct RocHostClosure {
+    uint64_t call;
+    uint64_t data;
+} RocHostClosure;
+
+static inline RocHostClosure roc_host_closure(RocHostFn call, void* data) {
+    RocHostClosure closure = {(uint64_t)(uintptr_t)call, (uint64_t)(uintptr_t)data};
+    return closure;
+}
+
+// Calls the host function with `arg`, and writes its answer to `ret`.
+static inline void roc_host_closure_call(const RocHostClosure* closure, const void* arg, void* ret) {
+    RocHostFn call = (RocHostFn)(uintptr_t)closure->call;
+    call((void*)(uintptr_t)closure->data, arg, ret);
+}
+
+#ifdef __cplusplus
+}
+#endif
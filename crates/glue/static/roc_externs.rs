@@ -0,0 +1,129 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+//! The functions every Roc app calls on its host. Add this file to the host as a module,
+//! and call [`set_allocator`] before calling into Roc if Roc's memory should come from
+//! somewhere other than the C library's `malloc`.
+
+#![allow(clippy::missing_safety_doc)]
+
+use core::ffi::c_void;
+use std::sync::OnceLock;
+
+use roc_std::RocStr;
+
+/// Where Roc gets its memory from.
+///
+/// # Safety
+///
+/// This has to behave like `malloc`, `realloc`, and `free`, and the memory it hands out
+/// has to be aligned to at least `alignment` bytes.
+pub unsafe trait RocAllocator: Sync {
+    fn alloc(&self, size: usize, alignment: u32) -> *mut c_void;
+
+    /// # Safety
+    ///
+    /// `ptr` has to have come from this allocator, and be `old_size` bytes long.
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        old_size: usize,
+        alignment: u32,
+    ) -> *mut c_void;
+
+    /// # Safety
+    ///
+    /// `ptr` has to have come from this allocator, and not be used again.
+    unsafe fn dealloc(&self, ptr: *mut c_void, alignment: u32);
+}
+
+/// The C library's allocator, which Roc uses unless [`set_allocator`] says otherwise.
+/// `malloc` aligns to 16 bytes on 64-bit targets, which covers everything Roc allocates.
+pub struct SystemAllocator;
+
+extern "C" {
+    fn malloc(size: usize) -> *mut c_void;
+    fn realloc(ptr: *mut c_void, size: usize) -> *mut c_void;
+    fn free(ptr: *mut c_void);
+    fn memset(dst: *mut c_void, c: i32, n: usize) -> *mut c_void;
+}
+
+unsafe impl RocAllocator for SystemAllocator {
+    fn alloc(&self, size: usize, _alignment: u32) -> *mut c_void {
+        unsafe { malloc(size) }
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut c_void,
+        new_size: usize,
+        _old_size: usize,
+        _alignment: u32,
+    ) -> *mut c_void {
+        realloc(ptr, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut c_void, _alignment: u32) {
+        free(ptr)
+    }
+}
+
+static ALLOCATOR: OnceLock<&'static dyn RocAllocator> = OnceLock::new();
+
+/// Makes Roc get its memory from `allocator`.
+///
+/// Memory has to go back to the allocator it came from, so this only works before Roc has
+/// allocated anything. After that, it gives `allocator` back as an error.
+pub fn set_allocator(
+    allocator: &'static dyn RocAllocator,
+) -> Result<(), &'static dyn RocAllocator> {
+    ALLOCATOR.set(allocator)
+}
+
+fn allocator() -> &'static dyn RocAllocator {
+    *ALLOCATOR.get_or_init(|| &SystemAllocator)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_alloc(size: usize, alignment: u32) -> *mut c_void {
+    allocator().alloc(size, alignment)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_realloc(
+    c_ptr: *mut c_void,
+    new_size: usize,
+    old_size: usize,
+    alignment: u32,
+) -> *mut c_void {
+    allocator().realloc(c_ptr, new_size, old_size, alignment)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, alignment: u32) {
+    allocator().dealloc(c_ptr, alignment)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_panic(msg: *mut RocStr, tag_id: u32) {
+    match tag_id {
+        0 => {
+            eprintln!("Roc standard library hit a panic: {}", &*msg);
+        }
+        1 => {
+            eprintln!("Application hit a panic: {}", &*msg);
+        }
+        _ => unreachable!(),
+    }
+    std::process::exit(1);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_dbg(loc: *mut RocStr, msg: *mut RocStr, src: *mut RocStr) {
+    eprintln!("[{}] {} = {}", &*loc, &*src, &*msg);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut c_void {
+    memset(dst, c, n)
+}
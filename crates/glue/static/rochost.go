@@ -0,0 +1,72 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+package rocapp
+
+/*
+#include <stdlib.h>
+#include <string.h>
+#include "RocStd.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"os"
+	"unsafe"
+)
+
+// The functions every Roc app calls on its host. Hosts that need something different,
+// like their own allocator, can leave this file out and export their own.
+
+//export roc_alloc
+func roc_alloc(size C.size_t, alignment C.uint32_t) unsafe.Pointer {
+	var pointer unsafe.Pointer
+
+	// posix_memalign needs at least the alignment of a pointer.
+	align := max(C.size_t(alignment), C.size_t(unsafe.Sizeof(uintptr(0))))
+
+	if C.posix_memalign(&pointer, align, size) != 0 {
+		return nil
+	}
+
+	return pointer
+}
+
+//export roc_realloc
+func roc_realloc(pointer unsafe.Pointer, newSize C.size_t, oldSize C.size_t, alignment C.uint32_t) unsafe.Pointer {
+	newPointer := roc_alloc(newSize, alignment)
+
+	if newPointer != nil && pointer != nil {
+		C.memcpy(newPointer, pointer, min(oldSize, newSize))
+		C.free(pointer)
+	}
+
+	return newPointer
+}
+
+//export roc_dealloc
+func roc_dealloc(pointer unsafe.Pointer, alignment C.uint32_t) {
+	C.free(pointer)
+}
+
+//export roc_memset
+func roc_memset(destination unsafe.Pointer, value C.int, count C.size_t) unsafe.Pointer {
+	return C.memset(destination, value, count)
+}
+
+//export roc_panic
+func roc_panic(message *C.roc_str, tagID C.uint32_t) {
+	source := "Application"
+
+	if tagID == 0 {
+		source = "Roc standard library"
+	}
+
+	fmt.Fprintf(os.Stderr, "%s hit a panic: %s\n", source, goString(*message))
+	os.Exit(1)
+}
+
+//export roc_dbg
+func roc_dbg(location *C.roc_str, message *C.roc_str, source *C.roc_str) {
+	fmt.Fprintf(os.Stderr, "[%s] %s = %s\n", goString(*location), goString(*source), goString(*message))
+}
@@ -0,0 +1,429 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+package rocapp
+
+// #include "RocStd.h"
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"strings"
+	"sync"
+	"unsafe"
+)
+
+// Go values hold their Roc references in pointers like the one in RocStr, so the garbage collector
+// decides when they get dropped: copying a Go value shares its references, and a finalizer drops
+// the Roc reference once nothing points to it anymore.
+//
+// Roc's reference counts aren't atomic, and finalizers run on a goroutine of their own, so they
+// only hand their references over to be dropped the next time Go calls into Roc, or calls Collect.
+// Roc values shouldn't be used on more than one goroutine at a time either.
+
+var unreachable struct {
+	sync.Mutex
+	drops []func()
+}
+
+func deferDrop(drop func()) {
+	unreachable.Lock()
+	unreachable.drops = append(unreachable.drops, drop)
+	unreachable.Unlock()
+}
+
+// Collect drops the Roc references of values the garbage collector has found unreachable.
+// Calling into Roc does this too, so hosts only need it between calls that are far apart.
+func Collect() {
+	unreachable.Lock()
+	drops := unreachable.drops
+	unreachable.drops = nil
+	unreachable.Unlock()
+
+	for _, drop := range drops {
+		drop()
+	}
+}
+
+// codec says how a Go type converts to and from the C value Roc lays it out as.
+type codec[T any] struct {
+	size  uintptr
+	align uintptr
+
+	// load converts the C value at the pointer, with new references of its own.
+	load func(unsafe.Pointer) T
+	// store writes a C value with new references to the pointer.
+	store func(unsafe.Pointer, T)
+	// drop drops the references the C value at the pointer holds.
+	drop func(unsafe.Pointer)
+}
+
+// rocValue is implemented by the Go types that have a C representation in roc_app.h.
+type rocValue[T any] interface {
+	rocCodec() codec[T]
+}
+
+func codecOf[T any]() codec[T] {
+	var zero T
+
+	switch any(zero).(type) {
+	case bool, int8, int16, int32, int64, uint8, uint16, uint32, uint64, float32, float64:
+		return trivialCodec[T]()
+	case U128, I128:
+		// C aligns these to 16 bytes, but Go only aligns them to 8.
+		wide := trivialCodec[T]()
+		wide.align = 16
+
+		return wide
+	}
+
+	if value, ok := any(zero).(rocValue[T]); ok {
+		return value.rocCodec()
+	}
+
+	panic(fmt.Sprintf("%T has no Roc representation", zero))
+}
+
+// consume converts the C value at the pointer, taking over the references it holds.
+func consume[T any](pointer unsafe.Pointer) T {
+	valueCodec := codecOf[T]()
+	value := valueCodec.load(pointer)
+
+	valueCodec.drop(pointer)
+
+	return value
+}
+
+// trivialCodec is for values that don't point to any Roc memory, and that Go lays out like C does.
+func trivialCodec[T any]() codec[T] {
+	var zero T
+
+	return codec[T]{
+		size:  unsafe.Sizeof(zero),
+		align: unsafe.Alignof(zero),
+		load:  func(pointer unsafe.Pointer) T { return *(*T)(pointer) },
+		store: func(pointer unsafe.Pointer, value T) { *(*T)(pointer) = value },
+		drop:  func(unsafe.Pointer) {},
+	}
+}
+
+// U128 is a Roc U128.
+type U128 struct {
+	Lo uint64
+	Hi uint64
+}
+
+func (n U128) String() string {
+	if n.Hi == 0 {
+		return fmt.Sprint(n.Lo)
+	}
+
+	return fmt.Sprintf("U128{Hi: %d, Lo: %d}", n.Hi, n.Lo)
+}
+
+// I128 is a Roc I128.
+type I128 struct {
+	Lo uint64
+	Hi int64
+}
+
+func (n I128) String() string {
+	if n.Hi == 0 {
+		return fmt.Sprint(n.Lo)
+	} else if n.Hi == -1 && n.Lo >= 1<<63 {
+		return fmt.Sprint(int64(n.Lo))
+	}
+
+	return fmt.Sprintf("I128{Hi: %d, Lo: %d}", n.Hi, n.Lo)
+}
+
+// RocStr is a Roc Str, which holds one reference to Roc's copy of the string.
+// The zero value is the empty string.
+type RocStr struct {
+	ref *strRef
+}
+
+type strRef struct {
+	raw C.roc_str
+}
+
+// adoptStr takes over the reference that raw holds.
+func adoptStr(raw C.roc_str) RocStr {
+	ref := &strRef{raw}
+
+	runtime.SetFinalizer(ref, func(ref *strRef) {
+		raw := ref.raw
+
+		deferDrop(func() { C.roc_str_decref(raw) })
+	})
+
+	return RocStr{ref}
+}
+
+// NewRocStr copies s into a new Roc string.
+func NewRocStr(s string) RocStr {
+	if len(s) == 0 {
+		return RocStr{}
+	}
+
+	bytes := (*C.char)(unsafe.Pointer(unsafe.StringData(s)))
+
+	return adoptStr(C.roc_str_from_bytes(bytes, C.size_t(len(s))))
+}
+
+// Len is the length of the string in bytes.
+func (s RocStr) Len() int {
+	if s.ref == nil {
+		return 0
+	}
+
+	return int(C.roc_str_len(s.ref.raw))
+}
+
+// String copies the string out of Roc's memory.
+func (s RocStr) String() string {
+	if s.ref == nil {
+		return ""
+	}
+
+	return goString(s.ref.raw)
+}
+
+func goString(raw C.roc_str) string {
+	length := int(C.roc_str_len(raw))
+
+	if C.roc_str_is_small(raw) {
+		return string(unsafe.Slice((*byte)(unsafe.Pointer(&raw)), length))
+	}
+
+	return C.GoStringN(raw.bytes, C.int(length))
+}
+
+func (RocStr) rocCodec() codec[RocStr] {
+	return codec[RocStr]{
+		size:  unsafe.Sizeof(C.roc_str{}),
+		align: unsafe.Alignof(C.roc_str{}),
+		load: func(pointer unsafe.Pointer) RocStr {
+			raw := *(*C.roc_str)(pointer)
+
+			C.roc_str_incref(raw)
+
+			return adoptStr(raw)
+		},
+		store: func(pointer unsafe.Pointer, s RocStr) {
+			if s.ref == nil {
+				*(*C.roc_str)(pointer) = C.roc_str{}
+				return
+			}
+
+			C.roc_str_incref(s.ref.raw)
+			*(*C.roc_str)(pointer) = s.ref.raw
+		},
+		drop: func(pointer unsafe.Pointer) { C.roc_str_decref(*(*C.roc_str)(pointer)) },
+	}
+}
+
+// RocList is a Roc List, which holds one reference to Roc's copy of the elements.
+// The zero value is the empty list.
+type RocList[T any] struct {
+	ref *listRef
+}
+
+type listRef struct {
+	raw C.roc_list
+}
+
+// adoptList takes over the reference that raw holds.
+func adoptList[T any](raw C.roc_list) RocList[T] {
+	ref := &listRef{raw}
+
+	runtime.SetFinalizer(ref, func(ref *listRef) {
+		raw := ref.raw
+
+		deferDrop(func() { dropList[T](raw) })
+	})
+
+	return RocList[T]{ref}
+}
+
+func dropList[T any](raw C.roc_list) {
+	allocation := C.roc_list_allocation(raw)
+
+	if allocation == nil || !C.roc_decref(allocation) {
+		return
+	}
+
+	elem := codecOf[T]()
+
+	for index := uintptr(0); index < uintptr(C.roc_list_len(raw)); index++ {
+		elem.drop(unsafe.Add(raw.elements, index*elem.size))
+	}
+
+	C.roc_free_refcounted(allocation, C.uint32_t(elem.align))
+}
+
+// NewRocList copies items into a new Roc list.
+func NewRocList[T any](items []T) RocList[T] {
+	if len(items) == 0 {
+		return RocList[T]{}
+	}
+
+	elem := codecOf[T]()
+	data := C.roc_alloc_refcounted(C.size_t(uintptr(len(items))*elem.size), C.uint32_t(elem.align))
+
+	for index, item := range items {
+		elem.store(unsafe.Add(data, uintptr(index)*elem.size), item)
+	}
+
+	return adoptList[T](C.roc_list{
+		elements:              data,
+		len:                   C.size_t(len(items)),
+		capacity_or_alloc_ptr: C.size_t(len(items)),
+	})
+}
+
+// Len is the number of elements in the list.
+func (l RocList[T]) Len() int {
+	if l.ref == nil {
+		return 0
+	}
+
+	return int(C.roc_list_len(l.ref.raw))
+}
+
+// Get converts the element at index, which shares Roc's references with the list.
+func (l RocList[T]) Get(index int) T {
+	if index < 0 || index >= l.Len() {
+		panic(fmt.Sprintf("index %d out of range for a RocList of length %d", index, l.Len()))
+	}
+
+	elem := codecOf[T]()
+
+	return elem.load(unsafe.Add(l.ref.raw.elements, uintptr(index)*elem.size))
+}
+
+// Slice copies the elements out of Roc's memory.
+func (l RocList[T]) Slice() []T {
+	items := make([]T, l.Len())
+
+	for index := range items {
+		items[index] = l.Get(index)
+	}
+
+	return items
+}
+
+func (l RocList[T]) String() string {
+	items := make([]string, l.Len())
+
+	for index := range items {
+		items[index] = fmt.Sprint(l.Get(index))
+	}
+
+	return "[" + strings.Join(items, ", ") + "]"
+}
+
+func (RocList[T]) rocCodec() codec[RocList[T]] {
+	return codec[RocList[T]]{
+		size:  unsafe.Sizeof(C.roc_list{}),
+		align: unsafe.Alignof(C.roc_list{}),
+		load: func(pointer unsafe.Pointer) RocList[T] {
+			raw := *(*C.roc_list)(pointer)
+
+			if allocation := C.roc_list_allocation(raw); allocation != nil {
+				C.roc_incref(allocation)
+			}
+
+			return adoptList[T](raw)
+		},
+		store: func(pointer unsafe.Pointer, l RocList[T]) {
+			if l.ref == nil {
+				*(*C.roc_list)(pointer) = C.roc_list{}
+				return
+			}
+
+			if allocation := C.roc_list_allocation(l.ref.raw); allocation != nil {
+				C.roc_incref(allocation)
+			}
+
+			*(*C.roc_list)(pointer) = l.ref.raw
+		},
+		drop: func(pointer unsafe.Pointer) { dropList[T](*(*C.roc_list)(pointer)) },
+	}
+}
+
+// nodeRef holds one reference to a node of a recursive tag union, which holds the payload of one of its tags.
+type nodeRef[P any] struct {
+	data unsafe.Pointer
+}
+
+// adoptNode takes over the reference to data.
+func adoptNode[P any](data unsafe.Pointer) *nodeRef[P] {
+	ref := &nodeRef[P]{data}
+
+	runtime.SetFinalizer(ref, func(ref *nodeRef[P]) {
+		data := ref.data
+
+		deferDrop(func() { dropNode[P](data) })
+	})
+
+	return ref
+}
+
+// newNode copies payload into a new node.
+func newNode[P any](payload P) *nodeRef[P] {
+	payloadCodec := codecOf[P]()
+	data := C.roc_alloc_refcounted(C.size_t(payloadCodec.size), C.uint32_t(payloadCodec.align))
+
+	payloadCodec.store(data, payload)
+
+	return adoptNode[P](data)
+}
+
+func dropNode[P any](data unsafe.Pointer) {
+	if C.roc_decref(data) {
+		payloadCodec := codecOf[P]()
+
+		payloadCodec.drop(data)
+		C.roc_free_refcounted(data, C.uint32_t(payloadCodec.align))
+	}
+}
+
+func (ref *nodeRef[P]) payload() P {
+	return codecOf[P]().load(ref.data)
+}
+
+// nodeCodec is for recursive tag unions where one tag is a null pointer, and the other points to a node.
+func nodeCodec[P any, T any](wrap func(*nodeRef[P]) T, unwrap func(T) *nodeRef[P]) codec[T] {
+	return codec[T]{
+		size:  unsafe.Sizeof(uintptr(0)),
+		align: unsafe.Alignof(uintptr(0)),
+		load: func(pointer unsafe.Pointer) T {
+			data := *(*unsafe.Pointer)(pointer)
+
+			if data == nil {
+				return wrap(nil)
+			}
+
+			C.roc_incref(data)
+
+			return wrap(adoptNode[P](data))
+		},
+		store: func(pointer unsafe.Pointer, value T) {
+			node := unwrap(value)
+
+			if node == nil {
+				*(*unsafe.Pointer)(pointer) = nil
+				return
+			}
+
+			C.roc_incref(node.data)
+			*(*unsafe.Pointer)(pointer) = node.data
+		},
+		drop: func(pointer unsafe.Pointer) {
+			if data := *(*unsafe.Pointer)(pointer); data != nil {
+				dropNode[P](data)
+			}
+		},
+	}
+}
@@ -0,0 +1,33 @@
+// swift-tools-version:5.9
+
+// ⚠️ READ THIS BEFORE MODIFYING THIS FILE! ⚠️
+//
+// This file is a fixture template. If the file you're looking at is
+// in the fixture-templates/ directory, then you're all set - go ahead
+// and modify it, and it will modify all the Swift fixture tests.
+//
+// If this file is in the fixtures/ directory, on the other hand, then
+// it is gitignored and will be overwritten the next time tests run.
+// So you probably don't want to modify it by hand! Instead, modify the
+// file with the same name in the fixture-templates/ directory.
+
+import PackageDescription
+
+let package = Package(
+    name: "Host",
+    dependencies: [
+        // The package `roc glue` generates for the fixture's platform
+        .package(path: "test_glue"),
+    ],
+    targets: [
+        .executableTarget(
+            name: "Host",
+            dependencies: [.product(name: "RocApp", package: "test_glue")],
+            path: ".",
+            exclude: ["test_glue", "app.o"],
+            sources: ["main.swift"],
+            // The tests build the app into this object file before building the host.
+            linkerSettings: [.unsafeFlags(["\(Context.packageDirectory)/app.o"])]
+        ),
+    ]
+)
@@ -0,0 +1,35 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+// Generated next to the library by `roc build --lib`
+#include "libapp.h"
+
+void* roc_alloc(size_t size, unsigned int alignment) { return malloc(size); }
+
+void* roc_realloc(void* ptr, size_t new_size, size_t old_size, unsigned int alignment) {
+    return realloc(ptr, new_size);
+}
+
+void roc_dealloc(void* ptr, unsigned int alignment) { free(ptr); }
+
+void roc_panic(const struct RocStr* msg, unsigned int tag_id) {
+    fprintf(stderr, "Roc crashed\n");
+    exit(1);
+}
+
+void roc_dbg(const struct RocStr* loc, const struct RocStr* msg, const struct RocStr* src) {}
+
+void* roc_memset(void* str, int c, size_t n) { return memset(str, c, n); }
+
+int main(void) {
+    struct MyRcd record;
+
+    roc__mainForHost_1_exposed_generic(&record);
+
+    // printf has no format for 128-bit integers, but these values are small
+    printf("Record was: MyRcd { b: %llu, a: %llu }\n", (unsigned long long)record.b,
+           (unsigned long long)record.a);
+
+    return 0;
+}
@@ -0,0 +1,5 @@
+using Roc.App;
+
+var record = RocApp.MainForHost();
+
+Console.WriteLine($"Record was: {record}");
@@ -0,0 +1,13 @@
+package main
+
+import (
+	"fmt"
+
+	"rocapp"
+)
+
+func main() {
+	tagUnion := rocapp.MainForHost()
+
+	fmt.Printf("tag_union was: %v, Bar is: %v, Baz is: %v\n", tagUnion, rocapp.MyEnumBar, rocapp.MyEnumBaz)
+}
@@ -0,0 +1,5 @@
+import RocApp
+
+let tagUnion = mainForHost()
+
+print("tag_union was: \(tagUnion), Bar is: \(MyEnum.Bar), Baz is: \(MyEnum.Baz)")
@@ -0,0 +1,14 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+# A function the host passes in, which Roc hands back to the host to call
+Logger : { call : U64, data : U64 }
+
+Greeting : { logger : Logger, message : Str }
+
+mainForHost : Logger -> Greeting
+mainForHost = \logger -> main logger
@@ -0,0 +1,26 @@
+use roc_app;
+use roc_std::{RocHostClosure, RocStr};
+
+#[no_mangle]
+pub extern "C" fn rust_main() {
+    let mut messages = 0;
+    let mut log = |msg: &RocStr| {
+        messages += 1;
+        println!("Roc said: {}", msg);
+    };
+
+    RocHostClosure::with(&mut log, |logger: roc_app::Logger| {
+        let greeting = roc_app::mainForHost(logger);
+
+        // Roc gives back the same closure it was given, which the host can call.
+        assert_eq!(greeting.logger, logger);
+
+        unsafe { greeting.logger.call::<RocStr, ()>(&greeting.message) };
+    });
+
+    assert_eq!(messages, 1);
+}
+
+// Externs required by roc_std and by the Roc app, which `roc glue --externs rust` generates
+#[path = "../test_glue/roc_externs.rs"]
+mod roc_externs;
@@ -0,0 +1,6 @@
+using Roc;
+using Roc.App;
+
+using var record = new RocOwned<Outer>(RocApp.MainForHost());
+
+Console.WriteLine($"Record was: {record.Value}");
@@ -0,0 +1,13 @@
+package main
+
+import (
+	"fmt"
+
+	"rocapp"
+)
+
+func main() {
+	record := rocapp.MainForHost()
+
+	fmt.Printf("Record was: %v\n", record)
+}
@@ -0,0 +1,5 @@
+import RocApp
+
+let record = mainForHost()
+
+print("Record was: \(record)")
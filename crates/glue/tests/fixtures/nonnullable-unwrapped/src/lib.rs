@@ -4,23 +4,12 @@ use indoc::indoc;
 use roc_app::StrRoseTree;
 use roc_std::{RocList, RocStr};
 
-extern "C" {
-    #[link_name = "roc__mainForHost_1_exposed_generic"]
-    fn roc_main(_: *mut StrRoseTree);
-}
-
 #[no_mangle]
 pub extern "C" fn rust_main() {
     use std::cmp::Ordering;
     use std::collections::hash_set::HashSet;
 
-    let tag_union = unsafe {
-        let mut ret: core::mem::MaybeUninit<StrRoseTree> = core::mem::MaybeUninit::uninit();
-
-        roc_main(ret.as_mut_ptr());
-
-        ret.assume_init()
-    };
+    let tag_union = roc_app::call_main_for_host();
 
     // Verify that it has all the expected traits.
 
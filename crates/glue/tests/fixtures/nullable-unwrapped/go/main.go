@@ -0,0 +1,15 @@
+package main
+
+import (
+	"fmt"
+
+	"rocapp"
+)
+
+func main() {
+	tagUnion := rocapp.MainForHost()
+
+	fmt.Printf("tag_union was: %v\n", tagUnion)
+	fmt.Printf("`Cons \"small str\" Nil` is: %v\n", rocapp.StrConsListCons(rocapp.NewRocStr("small str"), rocapp.StrConsListNil()))
+	fmt.Printf("`Nil` is: %v\n", rocapp.StrConsListNil())
+}
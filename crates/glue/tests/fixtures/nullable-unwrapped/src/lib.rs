@@ -2,7 +2,6 @@ use roc_app;
 
 use indoc::indoc;
 use roc_app::StrConsList;
-use roc_std::RocStr;
 
 #[no_mangle]
 pub extern "C" fn rust_main() {
@@ -41,52 +40,6 @@ pub extern "C" fn rust_main() {
     assert_eq!(set.len(), 1);
 }
 
-// Externs required by roc_std and by the Roc app
-
-use core::ffi::c_void;
-use std::ffi::CStr;
-use std::os::raw::c_char;
-
-#[no_mangle]
-pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
-    return libc::malloc(size);
-}
-
-#[no_mangle]
-pub unsafe extern "C" fn roc_realloc(
-    c_ptr: *mut c_void,
-    new_size: usize,
-    _old_size: usize,
-    _alignment: u32,
-) -> *mut c_void {
-    return libc::realloc(c_ptr, new_size);
-}
-
-#[no_mangle]
-pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
-    return libc::free(c_ptr);
-}
-
-#[no_mangle]
-pub unsafe extern "C" fn roc_panic(msg: *mut RocStr, tag_id: u32) {
-    match tag_id {
-        0 => {
-            eprintln!("Roc standard library hit a panic: {}", &*msg);
-        }
-        1 => {
-            eprintln!("Application hit a panic: {}", &*msg);
-        }
-        _ => unreachable!(),
-    }
-    std::process::exit(1);
-}
-
-#[no_mangle]
-pub unsafe extern "C" fn roc_dbg(loc: *mut RocStr, msg: *mut RocStr, src: *mut RocStr) {
-    eprintln!("[{}] {} = {}", &*loc, &*src, &*msg);
-}
-
-#[no_mangle]
-pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut c_void {
-    libc::memset(dst, c, n)
-}
+// Externs required by roc_std and by the Roc app, which `roc glue --externs rust` generates
+#[path = "../test_glue/roc_externs.rs"]
+mod roc_externs;
@@ -0,0 +1,7 @@
+import RocApp
+
+let tagUnion = mainForHost()
+
+print("tag_union was: \(tagUnion)")
+print("`Cons \"small str\" Nil` is: \(StrConsList.Cons(RocString("small str"), StrConsList.Nil()))")
+print("`Nil` is: \(StrConsList.Nil())")
@@ -4,11 +4,6 @@ use indoc::indoc;
 use roc_app::StrFingerTree;
 use roc_std::RocStr;
 
-extern "C" {
-    #[link_name = "roc__mainForHost_1_exposed_generic"]
-    fn roc_main(_: *mut StrFingerTree);
-}
-
 #[no_mangle]
 pub extern "C" fn rust_main() {
     use std::cmp::Ordering;
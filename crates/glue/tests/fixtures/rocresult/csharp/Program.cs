@@ -0,0 +1,8 @@
+using Roc;
+using Roc.App;
+
+using var str = new RocOwned<RocResult_RocStr_int>(RocApp.MainForHost(true));
+using var integer = new RocOwned<RocResult_RocStr_int>(RocApp.MainForHost(false));
+
+Console.WriteLine($"Answer was: {str.Value}");
+Console.WriteLine($"Answer was: {integer.Value}");
@@ -0,0 +1,15 @@
+using Roc;
+using Roc.App;
+
+using var tagUnion = new RocOwned<NonRecursive>(RocApp.MainForHost());
+using var smallStr = new RocOwned<NonRecursive>(NonRecursive.Foo(RocStr.FromString("small str")));
+using var bigStr = new RocOwned<NonRecursive>(
+    NonRecursive.Foo(RocStr.FromString("A long enough string to not be small"))
+);
+
+Console.WriteLine($"tag_union was: {tagUnion.Value}");
+Console.WriteLine($"`Foo \"small str\"` is: {smallStr.Value}");
+Console.WriteLine($"`Foo \"A long enough string to not be small\"` is: {bigStr.Value}");
+Console.WriteLine($"`Bar 123` is: {NonRecursive.Bar(123)}");
+Console.WriteLine($"`Baz` is: {NonRecursive.Baz()}");
+Console.WriteLine($"`Blah 456` is: {NonRecursive.Blah(456)}");
@@ -3,11 +3,6 @@ use roc_app;
 use roc_app::NonRecursive;
 use roc_std::RocStr;
 
-extern "C" {
-    #[link_name = "roc__mainForHost_1_exposed_generic"]
-    fn roc_main(_: *mut NonRecursive);
-}
-
 #[no_mangle]
 pub extern "C" fn rust_main() {
     use std::cmp::Ordering;
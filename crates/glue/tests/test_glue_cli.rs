@@ -12,7 +12,7 @@ mod helpers;
 #[cfg(test)]
 mod glue_cli_run {
     use crate::helpers::fixtures_dir;
-    use cli_utils::helpers::{has_error, run_glue, run_roc, Out};
+    use cli_utils::helpers::{has_error, run_cmd, run_glue, run_roc, Out};
     use std::fs;
     use std::path::{Path, PathBuf};
 
@@ -46,7 +46,8 @@ mod glue_cli_run {
                 fn $test_name() {
                     let dir = fixtures_dir($fixture_dir);
 
-                    generate_glue_for(&dir, std::iter::empty());
+                    // Hosts can use the generated roc_alloc and friends instead of writing their own.
+                    generate_glue_for(&dir, ["--externs", "rust"]);
 
                     fn validate<'a, I: IntoIterator<Item = &'a str>>(dir: PathBuf, args: I) {
                         let out = run_app(&dir.join("app.roc"), args);
@@ -166,6 +167,205 @@ mod glue_cli_run {
             Answer was: "Hello World!"
             Answer was: discriminant_U1::None
         "#),
+        host_closure:"host-closure" => indoc!(r#"
+            Roc said: Hello from Roc!
+        "#),
+    }
+
+    /// Like `fixtures!`, but for glue that targets a non-Rust host. Each fixture has the host
+    /// for `$template` in a subdirectory of that name; the glue is generated there from
+    /// `$glue_spec`, then `$run_host` builds and runs that host.
+    ///
+    /// These need the target language's toolchain, which CI does not install, so they only run
+    /// with `--ignored` and are not exercised in CI.
+    macro_rules! host_fixtures {
+        ($template:expr, $glue_spec:expr, $run_host:ident; $($test_name:ident:$fixture_dir:expr => $ends_with:expr,)+) => {
+            $(
+                #[test]
+                #[ignore]
+                #[allow(non_snake_case)]
+                fn $test_name() {
+                    let dir = fixtures_dir($fixture_dir);
+                    let host_dir = dir.join($template);
+
+                    generate_glue_with(&dir, &host_dir, $template, $glue_spec, std::iter::empty());
+
+                    let out = $run_host(&dir, &host_dir);
+
+                    assert!(out.status.success(), "bad status {out:?}");
+                    assert_eq!(out.stderr, "");
+                    assert!(
+                        out.stdout.ends_with($ends_with),
+                        "Unexpected stdout ending\n\n  expected:\n\n    {}\n\n  but stdout was:\n\n    {}",
+                        $ends_with,
+                        out.stdout
+                    );
+                }
+            )*
+        }
+    }
+
+    /// .NET hosts, which need the .NET SDK including what Native AOT needs to link.
+    mod csharp {
+        use super::*;
+
+        host_fixtures! {
+            "csharp", "CSharpGlue.roc", run_csharp_host;
+            basic_record:"basic-record" => "Record was: MyRcd { b: 42, a: 1995 }\n",
+            nested_record:"nested-record" => "Record was: Outer { y: foo, z: [1, 2], x: Inner { b: 24, a: 5 } }\n",
+            union_with_padding:"union-with-padding" => indoc!(r#"
+                tag_union was: NonRecursive.Foo(This is a test)
+                `Foo "small str"` is: NonRecursive.Foo(small str)
+                `Foo "A long enough string to not be small"` is: NonRecursive.Foo(A long enough string to not be small)
+                `Bar 123` is: NonRecursive.Bar(123)
+                `Baz` is: NonRecursive.Baz
+                `Blah 456` is: NonRecursive.Blah(456)
+            "#),
+            rocresult:"rocresult" => indoc!(r#"
+                Answer was: RocResult_RocStr_int.Ok(Hello World!)
+                Answer was: RocResult_RocStr_int.Err(42)
+            "#),
+        }
+    }
+
+    /// SwiftPM hosts, which need a Swift toolchain.
+    mod swift {
+        use super::*;
+
+        host_fixtures! {
+            "swift", "SwiftGlue.roc", run_swift_host;
+            basic_record:"basic-record" => "Record was: MyRcd { b: 42, a: 1995 }\n",
+            nested_record:"nested-record" => "Record was: Outer { y: foo, z: [1, 2], x: Inner { b: 24.0, a: 5 } }\n",
+            enumeration:"enumeration" => "tag_union was: MyEnum.Foo, Bar is: MyEnum.Bar, Baz is: MyEnum.Baz\n",
+            nullable_unwrapped:"nullable-unwrapped" => indoc!(r#"
+                tag_union was: StrConsList.Cons(World!, StrConsList.Cons(Hello , StrConsList.Nil))
+                `Cons "small str" Nil` is: StrConsList.Cons(small str, StrConsList.Nil)
+                `Nil` is: StrConsList.Nil
+            "#),
+        }
+    }
+
+    /// cgo hosts, which need a Go toolchain and a C compiler.
+    mod go {
+        use super::*;
+
+        host_fixtures! {
+            "go", "GoGlue.roc", run_go_host;
+            basic_record:"basic-record" => "Record was: MyRcd { b: 42, a: 1995 }\n",
+            nested_record:"nested-record" => "Record was: Outer { y: foo, z: [1, 2], x: Inner { b: 24, a: 5 } }\n",
+            enumeration:"enumeration" => "tag_union was: MyEnum.Foo, Bar is: MyEnum.Bar, Baz is: MyEnum.Baz\n",
+            nullable_unwrapped:"nullable-unwrapped" => indoc!(r#"
+                tag_union was: StrConsList.Cons(World!, StrConsList.Cons(Hello , StrConsList.Nil))
+                `Cons "small str" Nil` is: StrConsList.Cons(small str, StrConsList.Nil)
+                `Nil` is: StrConsList.Nil
+            "#),
+        }
+    }
+
+    #[test]
+    fn externs_for_c_hosts() {
+        let dir = fixtures_dir("basic-record");
+        let glue_dir = dir.join("test_glue");
+
+        generate_glue_for(&dir, ["--externs", "c"]);
+
+        let header = fs::read_to_string(glue_dir.join("roc_externs.h")).unwrap();
+        let source = fs::read_to_string(glue_dir.join("roc_externs.c")).unwrap();
+
+        assert!(header.contains("int roc_set_allocator(const RocAllocator* allocator);"));
+
+        for symbol in [
+            "roc_alloc",
+            "roc_realloc",
+            "roc_dealloc",
+            "roc_memset",
+            "roc_panic",
+            "roc_dbg",
+        ] {
+            assert!(
+                source.contains(&format!(" {symbol}(")),
+                "roc_externs.c should define {symbol}"
+            );
+        }
+    }
+
+    /// `roc build --lib` writes a C header next to the library; this compiles the C host in
+    /// the fixture's c/ directory against that header and the library, and runs it.
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn c_host_with_library_header() {
+        let dir = fixtures_dir("basic-record");
+        let host_dir = dir.join("c");
+        let lib_file = host_dir.join(if cfg!(target_os = "macos") {
+            "libapp.dylib"
+        } else {
+            "libapp.so"
+        });
+
+        let build_out = run_roc(
+            [
+                "build",
+                "--lib",
+                "--output",
+                lib_file.to_str().unwrap(),
+                dir.join("app.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(build_out.status.success(), "bad status {build_out:?}");
+        assert!(host_dir.join("libapp.h").exists());
+
+        let host_file = host_dir.join("host");
+        let cc_out = run_cmd(
+            "cc",
+            std::iter::empty(),
+            &[
+                "-Wall".to_string(),
+                "-Werror".to_string(),
+                host_dir.join("host.c").to_str().unwrap().to_string(),
+                lib_file.to_str().unwrap().to_string(),
+                "-o".to_string(),
+                host_file.to_str().unwrap().to_string(),
+            ],
+            std::iter::empty(),
+        );
+
+        assert!(cc_out.status.success(), "bad status {cc_out:?}");
+
+        let out = run_cmd(
+            host_file.to_str().unwrap(),
+            std::iter::empty(),
+            &[],
+            std::iter::empty(),
+        );
+
+        assert!(out.status.success(), "bad status {out:?}");
+        assert_eq!(out.stdout, "Record was: MyRcd { b: 42, a: 1995 }\n");
+    }
+
+    #[test]
+    fn no_std_rust_glue() {
+        let dir = fixtures_dir("basic-record");
+        let glue_dir = dir.join("test_glue");
+
+        generate_glue_for(&dir, ["--no-std"]);
+
+        let cargo_toml = fs::read_to_string(glue_dir.join("roc_app/Cargo.toml")).unwrap();
+        let lib_rs = fs::read_to_string(glue_dir.join("roc_app/src/lib.rs")).unwrap();
+
+        assert!(cargo_toml.contains("default = []"));
+        assert!(lib_rs.contains("#![cfg_attr(not(feature = \"std\"), no_std)]"));
+
+        for entry in fs::read_dir(glue_dir.join("roc_app/src")).unwrap() {
+            let source = fs::read_to_string(entry.unwrap().path()).unwrap();
+
+            assert!(
+                !source.replace("roc_std::", "").contains("std::"),
+                "the glue should only use core and alloc"
+            );
+        }
     }
 
     fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
@@ -201,9 +401,21 @@ mod glue_cli_run {
     fn generate_glue_for<'a, I: IntoIterator<Item = &'a str>>(
         platform_dir: &'a Path,
         args: I,
+    ) -> Out {
+        generate_glue_with(platform_dir, platform_dir, "rust", "RustGlue.roc", args)
+    }
+
+    /// Copies the host in fixture-templates/{template} into `host_dir`, and generates glue for
+    /// the platform in `platform_dir` into `host_dir`/test_glue using the given glue spec.
+    fn generate_glue_with<'a, I: IntoIterator<Item = &'a str>>(
+        platform_dir: &'a Path,
+        host_dir: &Path,
+        template: &str,
+        glue_spec: &str,
+        args: I,
     ) -> Out {
         let platform_module_path = platform_dir.join("platform.roc");
-        let glue_dir = platform_dir.join("test_glue");
+        let glue_dir = host_dir.join("test_glue");
         let fixture_templates_dir = platform_dir
             .parent()
             .unwrap()
@@ -211,8 +423,8 @@ mod glue_cli_run {
             .unwrap()
             .join("fixture-templates");
 
-        // Copy the rust template from the templates directory into the fixture dir.
-        dircpy::CopyBuilder::new(fixture_templates_dir.join("rust"), platform_dir)
+        // Copy the template from the templates directory into the fixture dir.
+        dircpy::CopyBuilder::new(fixture_templates_dir.join(template), host_dir)
             .overwrite(true) // overwrite any files that were already present
             .run()
             .unwrap();
@@ -223,20 +435,20 @@ mod glue_cli_run {
                 .expect("Unable to remove test_glue dir in order to regenerate it in the test");
         }
 
-        let rust_glue_spec = fixture_templates_dir
+        let glue_spec = fixture_templates_dir
             .parent()
             .unwrap()
             .parent()
             .unwrap()
             .join("src")
-            .join("RustGlue.roc");
+            .join(glue_spec);
 
         // Generate a fresh test_glue for this platform
         let glue_out = run_glue(
             // converting these all to String avoids lifetime issues
             std::iter::once("glue".to_string()).chain(
                 args.into_iter().map(|arg| arg.to_string()).chain([
-                    rust_glue_spec.to_str().unwrap().to_string(),
+                    glue_spec.to_str().unwrap().to_string(),
                     glue_dir.to_str().unwrap().to_string(),
                     platform_module_path.to_str().unwrap().to_string(),
                 ]),
@@ -255,6 +467,131 @@ mod glue_cli_run {
         glue_out
     }
 
+    /// Builds the app into an object file, links that into the .NET host with Native AOT, and runs it.
+    fn run_csharp_host(platform_dir: &Path, host_dir: &Path) -> Out {
+        let app_o_file = host_dir.join("app.o");
+        let build_out = run_roc(
+            [
+                "build",
+                "--no-link",
+                "--output",
+                app_o_file.to_str().unwrap(),
+                platform_dir.join("app.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(build_out.status.success(), "bad status {build_out:?}");
+
+        let publish_dir = host_dir.join("publish");
+        let publish_out = run_cmd(
+            "dotnet",
+            std::iter::empty(),
+            &[
+                "publish".to_string(),
+                host_dir.join("Host.csproj").to_str().unwrap().to_string(),
+                "--output".to_string(),
+                publish_dir.to_str().unwrap().to_string(),
+            ],
+            std::iter::empty(),
+        );
+
+        assert!(publish_out.status.success(), "bad status {publish_out:?}");
+
+        run_cmd(
+            publish_dir.join("Host").to_str().unwrap(),
+            std::iter::empty(),
+            &[],
+            std::iter::empty(),
+        )
+    }
+
+    /// Builds the app into an object file, then builds the SwiftPM host that links it and runs that.
+    fn run_swift_host(platform_dir: &Path, host_dir: &Path) -> Out {
+        let app_o_file = host_dir.join("app.o");
+        let build_out = run_roc(
+            [
+                "build",
+                "--no-link",
+                "--output",
+                app_o_file.to_str().unwrap(),
+                platform_dir.join("app.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(build_out.status.success(), "bad status {build_out:?}");
+
+        let swift_build_out = run_cmd(
+            "swift",
+            std::iter::empty(),
+            &[
+                "build".to_string(),
+                "--package-path".to_string(),
+                host_dir.to_str().unwrap().to_string(),
+                "-c".to_string(),
+                "release".to_string(),
+            ],
+            std::iter::empty(),
+        );
+
+        assert!(
+            swift_build_out.status.success(),
+            "bad status {swift_build_out:?}"
+        );
+
+        run_cmd(
+            host_dir.join(".build/release/Host").to_str().unwrap(),
+            std::iter::empty(),
+            &[],
+            std::iter::empty(),
+        )
+    }
+
+    /// Builds the app into an object file, then builds the cgo host with that linked in and runs it.
+    fn run_go_host(platform_dir: &Path, host_dir: &Path) -> Out {
+        let app_o_file = host_dir.join("app.o");
+        let build_out = run_roc(
+            [
+                "build",
+                "--no-link",
+                "--output",
+                app_o_file.to_str().unwrap(),
+                platform_dir.join("app.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(build_out.status.success(), "bad status {build_out:?}");
+
+        let host_file = host_dir.join("host");
+        let go_build_out = run_cmd(
+            "go",
+            std::iter::empty(),
+            &[
+                "-C".to_string(),
+                host_dir.to_str().unwrap().to_string(),
+                "build".to_string(),
+                "-o".to_string(),
+                host_file.to_str().unwrap().to_string(),
+                ".".to_string(),
+            ],
+            [("CGO_LDFLAGS", app_o_file.to_str().unwrap())],
+        );
+
+        assert!(go_build_out.status.success(), "bad status {go_build_out:?}");
+
+        run_cmd(
+            host_file.to_str().unwrap(),
+            std::iter::empty(),
+            &[],
+            std::iter::empty(),
+        )
+    }
+
     fn run_app<'a, 'b, I: IntoIterator<Item = &'a str>>(app_file: &'b Path, args: I) -> Out {
         // Generate test_glue for this platform
         let compile_out = run_roc(
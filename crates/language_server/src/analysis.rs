@@ -17,10 +17,13 @@ use roc_reporting::report::RocDocAllocator;
 use roc_solve_problem::TypeError;
 use roc_types::subs::{Subs, Variable};
 
-use tower_lsp::lsp_types::{Diagnostic, SemanticTokenType, Url};
+use tower_lsp::lsp_types::{Diagnostic, SemanticTokenModifier, SemanticTokenType, Url};
 
 mod analysed_doc;
 mod completion;
+mod incremental;
+mod inlay_hints;
+mod missing_branches;
 mod parse_ast;
 mod semantic_tokens;
 mod tokens;
@@ -29,9 +32,11 @@ mod utils;
 use crate::convert::diag::{IntoLspDiagnostic, ProblemFmt};
 
 pub(crate) use self::analysed_doc::{AnalyzedDocument, DocInfo};
-use self::{analysed_doc::ModuleIdToUrl, tokens::Token};
+pub(crate) use self::incremental::{analyze_edited_def, apply_content_changes};
+use self::{analysed_doc::ModuleIdToUrl, missing_branches::MissingBranches, tokens::Token};
 
 pub const HIGHLIGHT_TOKENS_LEGEND: &[SemanticTokenType] = Token::LEGEND;
+pub const HIGHLIGHT_TOKEN_MODIFIERS_LEGEND: &[SemanticTokenModifier] = Token::MODIFIERS;
 
 #[derive(Debug)]
 struct ModulesInfo {
@@ -98,6 +103,7 @@ pub(super) struct AnalyzedModule {
 pub struct AnalysisResult {
     module: Option<AnalyzedModule>,
     diagnostics: Vec<Diagnostic>,
+    missing_branches: Vec<MissingBranches>,
 }
 
 pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
@@ -130,6 +136,7 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
                 analysis_result: AnalysisResult {
                     module: None,
                     diagnostics: all_problems,
+                    missing_branches: Vec::new(),
                 },
             };
 
@@ -313,7 +320,8 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
         };
 
         let line_info = LineInfo::new(&source);
-        let diagnostics = self.build_diagnostics(&path, &source, &line_info, module_id);
+        let (diagnostics, missing_branches) =
+            self.build_diagnostics(&path, &source, &line_info, module_id);
 
         AnalyzedDocument {
             doc_info: DocInfo {
@@ -325,6 +333,7 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             analysis_result: AnalysisResult {
                 module: Some(analyzed_module),
                 diagnostics,
+                missing_branches,
             },
         }
     }
@@ -355,7 +364,7 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
         source: &str,
         line_info: &LineInfo,
         module_id: ModuleId,
-    ) -> Vec<Diagnostic> {
+    ) -> (Vec<Diagnostic>, Vec<MissingBranches>) {
         let lines: Vec<_> = source.lines().collect();
 
         let alloc = RocDocAllocator::new(&lines, module_id, self.interns);
@@ -377,12 +386,16 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             }
         }
 
+        let mut missing_branches = Vec::new();
+
         for type_problem in type_problems {
+            missing_branches.extend(MissingBranches::from_type_problem(&type_problem, &alloc));
+
             if let Some(diag) = type_problem.into_lsp_diagnostic(&fmt) {
                 all_problems.push(diag);
             }
         }
 
-        all_problems
+        (all_problems, missing_branches)
     }
 }
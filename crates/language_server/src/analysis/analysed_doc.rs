@@ -3,23 +3,27 @@ use std::collections::HashMap;
 
 use bumpalo::Bump;
 
+use roc_can::traverse::Visitor;
 use roc_module::symbol::{ModuleId, Symbol};
 
-use roc_region::all::LineInfo;
+use roc_region::all::{LineInfo, Region};
 
 use tower_lsp::lsp_types::{
-    CompletionItem, Diagnostic, GotoDefinitionResponse, Hover, HoverContents, LanguageString,
-    Location, MarkedString, Position, Range, SemanticTokens, SemanticTokensResult, TextEdit, Url,
+    CodeAction, CodeActionKind, CodeActionOrCommand, CompletionItem, Diagnostic,
+    GotoDefinitionResponse, Hover, HoverContents, InlayHint, InlayHintKind, InlayHintLabel,
+    LanguageString, Location, MarkedString, Position, Range, SemanticTokens, SemanticTokensResult,
+    TextEdit, Url, WorkspaceEdit,
 };
 
 use crate::{
     analysis::completion::{field_completion, get_completion_items, get_module_completion_items},
-    convert::{ToRange, ToRocPosition},
+    convert::{ToRange, ToRegion, ToRocPosition},
 };
 
 use super::{
+    inlay_hints::{compact_type, InlayHintVisitor},
     parse_ast::Ast,
-    semantic_tokens::arrange_semantic_tokens,
+    semantic_tokens::{arrange_semantic_tokens, refine_tokens},
     utils::{format_var_type, is_roc_identifier_char},
     AnalysisResult, AnalyzedModule,
 };
@@ -100,12 +104,41 @@ impl DocInfo {
         }
     }
 
-    pub fn semantic_tokens(&self) -> Option<SemanticTokensResult> {
+    pub fn format_range(&self, range: Range) -> Option<Vec<TextEdit>> {
         let source = &self.source;
         let arena = &Bump::new();
 
-        let ast = Ast::parse(arena, source).ok()?;
-        let tokens = ast.semantic_tokens();
+        let start = range.start.to_roc_position(&self.line_info).offset as usize;
+        let end = range.end.to_roc_position(&self.line_info).offset as usize;
+        let formatted = roc_fmt::fmt_range(arena, source, start..end)?;
+
+        if source[formatted.range.clone()] == formatted.text {
+            None
+        } else {
+            let region = Region::new(
+                roc_region::all::Position::new(formatted.range.start as u32),
+                roc_region::all::Position::new(formatted.range.end as u32),
+            );
+            let text_edit = TextEdit::new(region.to_range(&self.line_info), formatted.text);
+            Some(vec![text_edit])
+        }
+    }
+
+    /// Uses what type checking found out as well, when `checked` is for the same source.
+    pub fn semantic_tokens(
+        &self,
+        checked: Option<&AnalyzedDocument>,
+    ) -> Option<SemanticTokensResult> {
+        let source = &self.source;
+        let arena = &Bump::new();
+
+        let ast = Ast::parse_recovering(arena, source).ok()?;
+
+        let checked = checked
+            .filter(|document| document.doc_info.source == self.source)
+            .and_then(|document| document.module())
+            .map(|module| (&module.declarations, &module.subs));
+        let tokens = refine_tokens(ast.semantic_tokens(), source, checked);
 
         let data = arrange_semantic_tokens(tokens, &self.line_info);
 
@@ -144,6 +177,38 @@ impl AnalyzedDocument {
         self.analysis_result.diagnostics.clone()
     }
 
+    /// Quick fixes for the problems in a range: adding the branches a `when` is missing
+    pub fn code_actions(&self, range: Range) -> Vec<CodeActionOrCommand> {
+        let region = range.to_region(self.line_info());
+
+        self.analysis_result
+            .missing_branches
+            .iter()
+            .filter(|missing| {
+                missing.region.start() <= region.end() && region.start() <= missing.region.end()
+            })
+            .filter_map(|missing| {
+                let (replaced, new_when) = missing.add_to(&self.doc_info.source)?;
+                let replaced = Region::new(
+                    roc_region::all::Position::new(replaced.start as u32),
+                    roc_region::all::Position::new(replaced.end as u32),
+                );
+                let edit = TextEdit::new(replaced.to_range(self.line_info()), new_when);
+
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: "Add missing branches".to_string(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(self.url().clone(), vec![edit])])),
+                        ..WorkspaceEdit::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..CodeAction::default()
+                }))
+            })
+            .collect()
+    }
+
     pub fn symbol_at(&self, position: Position) -> Option<Symbol> {
         let line_info = self.line_info();
 
@@ -204,6 +269,54 @@ impl AnalyzedDocument {
         })
     }
 
+    /// The types inferred for the unannotated definitions and lambda parameters in a range
+    pub fn inlay_hints(&self, range: Range) -> Option<Vec<InlayHint>> {
+        let AnalyzedModule {
+            subs,
+            declarations,
+            module_id,
+            interns,
+            ..
+        } = self.module()?;
+
+        let mut visitor = InlayHintVisitor::new(range.to_region(self.line_info()));
+        visitor.visit_decls(declarations);
+
+        let mut subs = subs.clone();
+        let lines: Vec<&str> = self.doc_info.source.lines().collect();
+
+        let mut hints = visitor
+            .found
+            .into_iter()
+            .map(|(region, var)| {
+                let position = region.to_range(self.line_info()).end;
+                let line_width = lines
+                    .get(position.line as usize)
+                    .map_or(0, |line| line.chars().count());
+
+                let type_str = format_var_type(var, &mut subs, module_id, interns);
+
+                InlayHint {
+                    position,
+                    label: InlayHintLabel::String(format!(
+                        ": {}",
+                        compact_type(&type_str, line_width)
+                    )),
+                    kind: Some(InlayHintKind::TYPE),
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: Some(true),
+                    padding_right: None,
+                    data: None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        hints.sort_by_key(|hint| (hint.position.line, hint.position.character));
+
+        Some(hints)
+    }
+
     pub fn definition(&self, symbol: Symbol) -> Option<GotoDefinitionResponse> {
         let AnalyzedModule { declarations, .. } = self.module()?;
 
@@ -0,0 +1,124 @@
+use std::ops::Range;
+
+use bumpalo::Bump;
+use roc_parse::{ast::Defs, module::parse_module_defs, state::State};
+use roc_region::all::{Position, Region};
+use tower_lsp::lsp_types::{self, Diagnostic, DiagnosticSeverity, TextDocumentContentChangeEvent};
+
+use crate::convert::ToRange;
+
+use super::{parse_ast::Ast, AnalysisResult, AnalyzedDocument, DocInfo};
+
+/// A change to a document's source, in bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SourceEdit {
+    /// What was replaced, in the source before the change
+    pub(crate) replaced: Range<usize>,
+    /// How long the text that replaced it is
+    pub(crate) inserted_len: usize,
+}
+
+/// Applies the changes from a `textDocument/didChange` notification to a document's source, in
+/// order. Also returns the edit, when the changes amount to a single edit of part of the document.
+pub(crate) fn apply_content_changes(
+    source: &str,
+    changes: Vec<TextDocumentContentChangeEvent>,
+) -> (String, Option<SourceEdit>) {
+    let single_edit = changes.len() == 1;
+    let mut source = source.to_string();
+    let mut last_edit = None;
+
+    for change in changes {
+        match change.range {
+            Some(range) => {
+                let start = byte_offset(&source, range.start);
+                let end = byte_offset(&source, range.end).max(start);
+
+                source.replace_range(start..end, &change.text);
+
+                last_edit = Some(SourceEdit {
+                    replaced: start..end,
+                    inserted_len: change.text.len(),
+                });
+            }
+            None => {
+                // A change without a range replaces the whole document
+                source = change.text;
+                last_edit = None;
+            }
+        }
+    }
+
+    (source, last_edit.filter(|_| single_edit))
+}
+
+/// Clamped to the end of the position's line, and to the end of the document
+fn byte_offset(source: &str, position: lsp_types::Position) -> usize {
+    let line_start: usize = source
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum();
+    let line = source[line_start..].split('\n').next().unwrap_or_default();
+
+    let mut column = (position.character as usize).min(line.len());
+
+    while !line.is_char_boundary(column) {
+        column -= 1;
+    }
+
+    line_start + column
+}
+
+/// Re-parses only the top-level def that an edit was made in, for when it no longer parses.
+///
+/// That's what typing usually looks like, and there's no point in loading and checking the whole
+/// module for it. Returns `None` when the whole module has to be analysed again: when the edit
+/// isn't within a single def, or when the def still parses, since canonicalizing and solving a
+/// def needs the rest of the module. Re-checking just the edited def in that case isn't
+/// supported yet (see TODO.md).
+pub(crate) fn analyze_edited_def(
+    old: &DocInfo,
+    new: &DocInfo,
+    edit: &SourceEdit,
+) -> Option<AnalyzedDocument> {
+    let arena = Bump::new();
+    let old_ast = Ast::parse(&arena, &old.source).ok()?;
+
+    let def_region = old_ast.def_regions().iter().find(|region| {
+        region.start().offset as usize <= edit.replaced.start
+            && edit.replaced.end <= region.end().offset as usize
+    })?;
+
+    let start = def_region.start().offset as usize;
+    let end =
+        (def_region.end().offset as usize + edit.inserted_len).checked_sub(edit.replaced.len())?;
+    let def_src = new.source.get(start..end)?;
+
+    let problem = match parse_module_defs(&arena, State::new(def_src.as_bytes()), Defs::default()) {
+        Ok(_) => return None,
+        Err(problem) => problem,
+    };
+
+    let region = Region::new(Position::new(start as u32), Position::new(end as u32));
+    let diagnostic = Diagnostic {
+        range: region.to_range(&new.line_info),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: None,
+        code_description: None,
+        source: Some("parse".to_owned()),
+        message: format!("Failed to parse Roc source file: {problem:?}"),
+        related_information: None,
+        tags: None,
+        data: None,
+    };
+
+    Some(AnalyzedDocument {
+        doc_info: new.clone(),
+        analysis_result: AnalysisResult {
+            module: None,
+            diagnostics: vec![diagnostic],
+            missing_branches: Vec::new(),
+        },
+    })
+}
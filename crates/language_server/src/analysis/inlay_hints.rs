@@ -0,0 +1,140 @@
+use roc_can::{
+    def::Def,
+    expr::{AnnotatedMark, ClosureData, Expr},
+    pattern::Pattern,
+    traverse::{walk_decl, walk_def, walk_expr, DeclarationInfo, Visitor},
+};
+use roc_region::all::{Loc, Region};
+use roc_types::subs::Variable;
+
+/// Hints that would make a line longer than this get shortened, down to `MIN_HINT_WIDTH`.
+const MAX_LINE_WIDTH: usize = 100;
+const MIN_HINT_WIDTH: usize = 16;
+
+/// Finds the bindings in a region that have no type annotation, so we can show the type
+/// the compiler inferred for them: top-level and `let` definitions, and lambda parameters.
+pub(super) struct InlayHintVisitor {
+    pub(super) region: Region,
+    /// The region of the identifier the hint belongs to, and its type
+    pub(super) found: Vec<(Region, Variable)>,
+}
+
+impl InlayHintVisitor {
+    pub(super) fn new(region: Region) -> Self {
+        Self {
+            region,
+            found: Vec::new(),
+        }
+    }
+
+    fn add_identifier(&mut self, pattern: &Pattern, region: Region, var: Variable) {
+        if let Pattern::Identifier(_) = pattern {
+            if self.should_visit(region) {
+                self.found.push((region, var));
+            }
+        }
+    }
+
+    fn add_arguments(&mut self, arguments: &[(Variable, AnnotatedMark, Loc<Pattern>)]) {
+        for (var, _, loc_pattern) in arguments {
+            self.add_identifier(&loc_pattern.value, loc_pattern.region, *var);
+        }
+    }
+}
+
+impl Visitor for InlayHintVisitor {
+    fn should_visit(&mut self, region: Region) -> bool {
+        region.start() <= self.region.end() && self.region.start() <= region.end()
+    }
+
+    fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+        if !self.should_visit(decl.region()) {
+            return;
+        }
+
+        match &decl {
+            DeclarationInfo::Value {
+                loc_symbol,
+                expr_var,
+                pattern,
+                annotation: None,
+                ..
+            } => self.add_identifier(pattern, loc_symbol.region, *expr_var),
+            DeclarationInfo::Function {
+                loc_symbol,
+                expr_var,
+                pattern,
+                function,
+                annotation: None,
+                ..
+            } => {
+                self.add_identifier(pattern, loc_symbol.region, *expr_var);
+                self.add_arguments(&function.value.arguments);
+            }
+            DeclarationInfo::Destructure {
+                loc_pattern,
+                expr_var,
+                annotation: None,
+                ..
+            } => self.add_identifier(&loc_pattern.value, loc_pattern.region, *expr_var),
+            _ => {}
+        }
+
+        walk_decl(self, decl);
+    }
+
+    fn visit_def(&mut self, def: &Def) {
+        if !self.should_visit(def.region()) {
+            return;
+        }
+
+        if def.annotation.is_some() {
+            // The annotation already says what the lambda's parameters are,
+            // so skip straight to its body.
+            if let Expr::Closure(ClosureData { loc_body, .. }) = &def.loc_expr.value {
+                self.visit_expr(&loc_body.value, loc_body.region, def.expr_var);
+                return;
+            }
+        } else {
+            self.add_identifier(&def.loc_pattern.value, def.loc_pattern.region, def.expr_var);
+        }
+
+        walk_def(self, def);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if !self.should_visit(region) {
+            return;
+        }
+
+        if let Expr::Closure(ClosureData { arguments, .. }) = expr {
+            self.add_arguments(arguments);
+        }
+
+        walk_expr(self, expr, var);
+    }
+}
+
+/// Puts a printed type on one line, and shortens it with "…" so that the line it's shown on
+/// stays within `MAX_LINE_WIDTH` where possible.
+pub(super) fn compact_type(type_str: &str, line_width: usize) -> String {
+    let mut compact = type_str.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    // Multi-line records, tag unions and tuples end with a trailing comma
+    for (with_comma, without) in [(", }", " }"), (", ]", " ]"), (", )", " )")] {
+        compact = compact.replace(with_comma, without);
+    }
+
+    let max_width = MAX_LINE_WIDTH
+        .saturating_sub(line_width)
+        .max(MIN_HINT_WIDTH);
+
+    if compact.chars().count() <= max_width {
+        compact
+    } else {
+        let mut shortened: String = compact.chars().take(max_width - 1).collect();
+        shortened.truncate(shortened.trim_end().len());
+        shortened.push('…');
+        shortened
+    }
+}
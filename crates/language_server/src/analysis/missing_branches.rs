@@ -0,0 +1,119 @@
+use std::ops::Range;
+
+use bumpalo::Bump;
+use roc_exhaustive::{Context, Error, Pattern, RenderAs};
+use roc_fmt::{annotation::Formattable, Buf};
+use roc_parse::{ast::Expr, expr::loc_expr, parser::Parser, state::State};
+use roc_region::all::{Loc, Region};
+use roc_reporting::{
+    error::r#type::exhaustive_pattern_to_doc,
+    report::{CiWrite, RocDocAllocator},
+};
+use roc_solve_problem::TypeError;
+
+/// The patterns a `when` doesn't handle, for the quick fix that adds branches for them
+#[derive(Debug, Clone)]
+pub(super) struct MissingBranches {
+    /// From the start of the `when`'s condition to the end of its last branch
+    pub(super) region: Region,
+    pub(super) patterns: Vec<String>,
+}
+
+impl MissingBranches {
+    pub(super) fn from_type_problem(
+        problem: &TypeError,
+        alloc: &RocDocAllocator<'_>,
+    ) -> Option<Self> {
+        let TypeError::Exhaustive(Error::Incomplete(region, Context::BadCase, patterns)) = problem
+        else {
+            return None;
+        };
+
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                // A branch that's only missing because of its `if` guard is rendered with a
+                // note about the guard, but we only want the pattern itself
+                let pattern = match pattern {
+                    Pattern::Ctor(union, _, args) if union.render_as == RenderAs::Guard => {
+                        args.get(1)?.clone()
+                    }
+                    _ => pattern.clone(),
+                };
+
+                let mut text = String::new();
+                exhaustive_pattern_to_doc(alloc, pattern)
+                    .1
+                    .render_raw(80, &mut CiWrite::new(&mut text))
+                    .ok()?;
+
+                Some(text)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            region: *region,
+            patterns,
+        })
+    }
+
+    /// Adds a branch for each missing pattern to the end of the `when`, which crashes with a
+    /// TODO, and formats the result. Returns the range of the `when` in `source`, and what to
+    /// replace it with.
+    pub(super) fn add_to(&self, source: &str) -> Option<(Range<usize>, String)> {
+        let condition_start = self.region.start().offset as usize;
+        let end = self.region.end().offset as usize;
+
+        let start = source
+            .get(..condition_start)?
+            .trim_end()
+            .strip_suffix("when")?
+            .len();
+        let when_src = source.get(start..end)?;
+
+        let arena = Bump::new();
+
+        // The branches are on the lines after the `when`, so the columns in `when_src` are the
+        // same as in `source`
+        let Expr::When(_, branches) = parse_expr(&arena, when_src)?.value else {
+            return None;
+        };
+        let first_pattern_start = branches.first()?.patterns.first()?.region.start().offset;
+        let first_pattern_start = first_pattern_start as usize;
+        let branch_column = first_pattern_start
+            - when_src[..first_pattern_start]
+                .rfind('\n')
+                .map_or(0, |newline| newline + 1);
+
+        let mut new_src = when_src.to_string();
+        for pattern in &self.patterns {
+            new_src.push('\n');
+            new_src.push_str(&" ".repeat(branch_column));
+            new_src.push_str(pattern);
+            new_src.push_str(" -> crash \"TODO\"");
+        }
+
+        let new_src = arena.alloc_str(&new_src);
+        let new_when = parse_expr(&arena, new_src)?;
+
+        let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+        let indent = source[line_start..start]
+            .chars()
+            .take_while(|c| *c == ' ')
+            .count();
+
+        let mut buf = Buf::new_in(&arena);
+        new_when.format(&mut buf, indent as u16);
+
+        Some((start..end, buf.as_str().trim().to_string()))
+    }
+}
+
+/// Parses `src` the way the REPL parses an expression, if all of it is one expression.
+fn parse_expr<'a>(arena: &'a Bump, src: &'a str) -> Option<Loc<Expr<'a>>> {
+    let (_, loc_expr, state) = loc_expr(true)
+        .parse(arena, State::new(src.as_bytes()), 0)
+        .ok()?;
+
+    state.has_reached_end().then_some(loc_expr)
+}
@@ -2,10 +2,10 @@ use bumpalo::Bump;
 use roc_fmt::Buf;
 use roc_parse::{
     ast::{Defs, Module},
-    module::parse_module_defs,
+    module::{parse_module_defs, parse_module_defs_recovering},
     parser::SyntaxError,
 };
-use roc_region::all::Loc;
+use roc_region::all::{Loc, Region};
 
 use self::format::FormattedAst;
 
@@ -37,6 +37,30 @@ impl<'a> Ast<'a> {
         })
     }
 
+    /// Like [`Ast::parse`], but a top-level def that doesn't parse only makes that def
+    /// malformed, so the rest of a document that's being typed in can still be highlighted.
+    pub fn parse_recovering(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+        use roc_parse::{module::parse_header, state::State};
+
+        let (module, state) = parse_header(arena, State::new(src.as_bytes()))
+            .map_err(|e| SyntaxError::Header(e.problem))?;
+
+        let (module, defs) = module.upgrade_header_imports(arena);
+
+        let (defs, _problems) = parse_module_defs_recovering(arena, state, defs);
+
+        Ok(Ast {
+            module,
+            defs,
+            arena,
+        })
+    }
+
+    /// The regions of the module's top-level defs, in order
+    pub fn def_regions(&self) -> &[Region] {
+        &self.defs.regions
+    }
+
     pub fn fmt(&self) -> FormattedAst<'a> {
         let mut buf = Buf::new_in(self.arena);
 
@@ -1,12 +1,94 @@
-use roc_region::all::{LineColumn, LineInfo, Loc};
+use std::collections::HashSet;
+
+use roc_can::{
+    expr::{Declarations, Expr},
+    pattern::Pattern,
+    traverse::{walk_expr, walk_pattern, Visitor},
+};
+use roc_region::all::{LineColumn, LineInfo, Loc, Region};
+use roc_types::subs::{Subs, Variable};
 use tower_lsp::lsp_types::SemanticToken;
 
-use super::tokens::Token;
+use super::tokens::{Token, EFFECTFUL};
+
+/// Improves on the tokens that parsing found with what type checking found: variables that hold
+/// functions are highlighted as functions. Also marks suffixed calls like `Stdout.line!` as
+/// effectful.
+pub fn refine_tokens<'a>(
+    tokens: impl IntoIterator<Item = Loc<Token>> + 'a,
+    source: &'a str,
+    checked: Option<(&'a Declarations, &'a Subs)>,
+) -> impl Iterator<Item = (Loc<Token>, u32)> + 'a {
+    let function_ends = checked
+        .map(|(declarations, subs)| {
+            let mut visitor = FunctionVisitor {
+                subs,
+                function_ends: HashSet::new(),
+            };
+            visitor.visit_decls(declarations);
+            visitor.function_ends
+        })
+        .unwrap_or_default();
+
+    tokens.into_iter().map(move |Loc { region, value }| {
+        let text = source
+            .get(region.start().offset as usize..region.end().offset as usize)
+            .unwrap_or_default();
+        let is_effectful = text.ends_with('!');
+
+        let mut token = value;
+        if let Token::Variable = token {
+            // The canonical region of `Str.concat` covers the module name too, and the one for
+            // `Stdout.line!` may or may not cover the `!`, so we go by where they end
+            let end = region.end().offset;
+            if function_ends.contains(&end) || (is_effectful && function_ends.contains(&(end - 1)))
+            {
+                token = Token::Function;
+            }
+        }
+
+        let modifiers = if is_effectful { EFFECTFUL } else { 0 };
+
+        (Loc::at(region, token), modifiers)
+    })
+}
+
+/// Finds the variables and bindings whose type is a function
+struct FunctionVisitor<'a> {
+    subs: &'a Subs,
+    function_ends: HashSet<u32>,
+}
+
+impl FunctionVisitor<'_> {
+    fn add_if_function(&mut self, region: Region, var: Variable) {
+        if self.subs.is_function(var) {
+            self.function_ends.insert(region.end().offset);
+        }
+    }
+}
+
+impl Visitor for FunctionVisitor<'_> {
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if let Expr::Var(_, var) = expr {
+            self.add_if_function(region, *var);
+        }
+
+        walk_expr(self, expr, var);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern, region: Region, opt_var: Option<Variable>) {
+        if let (Pattern::Identifier(_), Some(var)) = (pattern, opt_var) {
+            self.add_if_function(region, var);
+        }
+
+        walk_pattern(self, pattern);
+    }
+}
 
 /// Encodes semantic tokens as described in the LSP specification.
 /// See [the sample documentation](https://github.com/microsoft/vscode-extension-samples/blob/5ae1f7787122812dcc84e37427ca90af5ee09f14/semantic-tokens-sample/vscode.proposed.d.ts#L71-L128).
 pub fn arrange_semantic_tokens(
-    tokens: impl IntoIterator<Item = Loc<Token>>,
+    tokens: impl IntoIterator<Item = (Loc<Token>, u32)>,
     line_info: &LineInfo,
 ) -> Vec<SemanticToken> {
     let tokens = tokens.into_iter();
@@ -17,10 +99,13 @@ pub fn arrange_semantic_tokens(
     let mut last_line = 0;
     let mut last_start = 0;
 
-    for Loc {
-        region,
-        value: token,
-    } in tokens
+    for (
+        Loc {
+            region,
+            value: token,
+        },
+        modifiers,
+    ) in tokens
     {
         let length = region.len();
 
@@ -38,7 +123,7 @@ pub fn arrange_semantic_tokens(
             delta_start,
             length,
             token_type: token as u32,
-            token_modifiers_bitset: 0,
+            token_modifiers_bitset: modifiers,
         });
 
         last_line = line;
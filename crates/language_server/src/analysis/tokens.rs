@@ -18,7 +18,7 @@ use roc_parse::{
     ident::{Accessor, UppercaseIdent},
 };
 use roc_region::all::{Loc, Region};
-use tower_lsp::lsp_types::SemanticTokenType;
+use tower_lsp::lsp_types::{SemanticTokenModifier, SemanticTokenType};
 
 macro_rules! tokens {
     ($($(#[$meta:meta])* $token:ident => $lsp_token:literal),* $(,)?) => {
@@ -57,6 +57,7 @@ tokens! {
     Variable => "variable",
     Field => "property",
     Tag => "enumMember",
+    Opaque => "struct",
     Function => "function",
     Keyword => "keyword",
     String => "string",
@@ -66,6 +67,15 @@ tokens! {
     Import => "import",
 }
 
+/// Set in `SemanticToken::token_modifiers_bitset` for suffixed calls like `Stdout.line!`,
+/// which run an effect.
+pub const EFFECTFUL: u32 = 1 << 0;
+
+impl Token {
+    /// The modifiers, in the order of their bits
+    pub const MODIFIERS: &'static [SemanticTokenModifier] = &[SemanticTokenModifier::ASYNC];
+}
+
 fn onetoken(token: Token, region: Region, arena: &Bump) -> BumpVec<Loc<Token>> {
     bumpvec![in arena; Loc::at(region, token)]
 }
@@ -520,10 +530,11 @@ impl IterTokens for TypeDef<'_> {
                 .chain(ann.iter_tokens(arena))
                 .collect_in(arena),
             TypeDef::Opaque {
-                header,
+                header: TypeHeader { name, vars },
                 typ,
                 derived,
-            } => (header.iter_tokens(arena).into_iter())
+            } => (onetoken(Token::Opaque, name.region, arena).into_iter())
+                .chain(vars.iter().map(|v| v.with_value(Token::Type)))
                 .chain(typ.iter_tokens(arena))
                 .chain(derived.iter().flat_map(|t| t.iter_tokens(arena)))
                 .collect_in(arena),
@@ -654,6 +665,7 @@ impl IterTokens for ValueDef<'_> {
                 onetoken(Token::Import, import.name.item.region, arena)
             }
             ValueDef::Stmt(loc_expr) => loc_expr.iter_tokens(arena),
+            ValueDef::Malformed(_) => bumpvec![in arena;],
         }
     }
 }
@@ -684,11 +696,21 @@ impl IterTokens for Loc<Expr<'_>> {
             Expr::Record(rcd) => rcd.iter_tokens(arena),
             Expr::Tuple(tup) => tup.iter_tokens(arena),
             Expr::RecordBuilder(rb) => rb.iter_tokens(arena),
+            Expr::Var { module_name, .. } if !module_name.is_empty() => {
+                // `Str.concat` is the `Str` module, then the `concat` in it
+                let module_end = region.start().bump_column(module_name.len() as u32);
+                let ident_start = module_end.bump_column(1);
+
+                bumpvec![in arena;
+                    Loc::at(Region::new(region.start(), module_end), Token::Module),
+                    Loc::at(Region::new(ident_start, region.end()), Token::Variable),
+                ]
+            }
             Expr::Var { .. } => onetoken(Token::Variable, region, arena),
             Expr::Underscore(_) => onetoken(Token::Variable, region, arena),
             Expr::Crash => onetoken(Token::Keyword, region, arena),
             Expr::Tag(_) => onetoken(Token::Tag, region, arena),
-            Expr::OpaqueRef(_) => onetoken(Token::Type, region, arena),
+            Expr::OpaqueRef(_) => onetoken(Token::Opaque, region, arena),
             Expr::Closure(patterns, body) => (patterns.iter_tokens(arena).into_iter())
                 .chain(body.iter_tokens(arena))
                 .collect_in(arena),
@@ -787,7 +809,7 @@ impl IterTokens for Loc<Pattern<'_>> {
         match self.value {
             Pattern::Identifier { .. } => onetoken(Token::Variable, region, arena),
             Pattern::Tag(_) => onetoken(Token::Tag, region, arena),
-            Pattern::OpaqueRef(_) => onetoken(Token::Type, region, arena),
+            Pattern::OpaqueRef(_) => onetoken(Token::Opaque, region, arena),
             Pattern::Apply(p1, p2) => (p1.iter_tokens(arena).into_iter())
                 .chain(p2.iter_tokens(arena))
                 .collect_in(arena),
@@ -42,7 +42,7 @@ impl ToRegion for Range {
             },
             end: LineColumn {
                 line: self.end.line,
-                column: self.end.line,
+                column: self.end.character,
             },
         };
 
@@ -136,9 +136,9 @@ pub(crate) mod diag {
                         filename.display()
                     )
                 }
-                LoadingProblem::ParsingFailed(fe) => {
-                    let problem = &fe.problem.problem;
-                    format!("Failed to parse Roc source file: {problem:?}")
+                LoadingProblem::ParsingFailed(problems) => {
+                    let problems: Vec<_> = problems.iter().map(|fe| &fe.problem.problem).collect();
+                    format!("Failed to parse Roc source file: {problems:?}")
                 }
                 LoadingProblem::UnexpectedHeader(header) => {
                     format!("Unexpected header: {}", header)
@@ -9,8 +9,8 @@ use std::{
 use tokio::sync::{Mutex, MutexGuard};
 
 use tower_lsp::lsp_types::{
-    CompletionResponse, Diagnostic, GotoDefinitionResponse, Hover, Position, SemanticTokensResult,
-    TextEdit, Url,
+    CodeActionResponse, CompletionResponse, Diagnostic, GotoDefinitionResponse, Hover, InlayHint,
+    Position, Range, SemanticTokensResult, TextEdit, Url,
 };
 
 use crate::analysis::{AnalyzedDocument, DocInfo};
@@ -51,6 +51,8 @@ impl Default for RegistryConfig {
 #[derive(Debug, Default)]
 pub(crate) struct Registry {
     documents: Mutex<HashMap<Url, DocumentPair>>,
+    /// The latest source of each open document, including ones that haven't been analysed yet
+    sources: Mutex<HashMap<Url, DocInfo>>,
     config: RegistryConfig,
 }
 
@@ -58,6 +60,7 @@ impl Registry {
     pub(crate) fn new(config: RegistryConfig) -> Self {
         Self {
             documents: Default::default(),
+            sources: Default::default(),
             config,
         }
     }
@@ -121,6 +124,8 @@ impl Registry {
     }
 
     pub async fn apply_doc_info_changes(&self, url: Url, info: DocInfo) {
+        self.sources.lock().await.insert(url.clone(), info.clone());
+
         let mut documents_lock = self.documents.lock().await;
         let doc = documents_lock.get_mut(&url);
         match doc {
@@ -140,6 +145,15 @@ impl Registry {
         }
     }
 
+    /// The latest source of a document, which incremental changes apply to
+    pub async fn latest_source(&self, url: &Url) -> Option<DocInfo> {
+        self.sources.lock().await.get(url).cloned()
+    }
+
+    pub async fn close(&self, url: &Url) {
+        self.sources.lock().await.remove(url);
+    }
+
     async fn document_info_by_url(&self, url: &Url) -> Option<DocInfo> {
         self.documents.lock().await.get(url).map(|a| a.info.clone())
     }
@@ -175,6 +189,14 @@ impl Registry {
         self.latest_document_by_url(url).await?.hover(position)
     }
 
+    pub async fn code_actions(&self, url: &Url, range: Range) -> Option<CodeActionResponse> {
+        Some(self.latest_document_by_url(url).await?.code_actions(range))
+    }
+
+    pub async fn inlay_hints(&self, url: &Url, range: Range) -> Option<Vec<InlayHint>> {
+        self.latest_document_by_url(url).await?.inlay_hints(range)
+    }
+
     pub async fn goto_definition(
         &self,
         url: &Url,
@@ -192,9 +214,17 @@ impl Registry {
         document.format()
     }
 
-    pub async fn semantic_tokens(&self, url: &Url) -> Option<SemanticTokensResult> {
+    pub async fn range_formatting(&self, url: &Url, range: Range) -> Option<Vec<TextEdit>> {
         let document = self.document_info_by_url(url).await?;
-        document.semantic_tokens()
+        document.format_range(range)
+    }
+
+    pub async fn semantic_tokens(&self, url: &Url) -> Option<SemanticTokensResult> {
+        let lock = self.documents.lock().await;
+        let pair = lock.get(url)?;
+
+        pair.info
+            .semantic_tokens(Some(pair.last_good_document.as_ref()))
     }
     pub async fn completion_items(
         &self,
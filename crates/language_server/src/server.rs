@@ -1,4 +1,4 @@
-use analysis::HIGHLIGHT_TOKENS_LEGEND;
+use analysis::{HIGHLIGHT_TOKENS_LEGEND, HIGHLIGHT_TOKEN_MODIFIERS_LEGEND};
 
 use log::{debug, trace};
 use registry::{Registry, RegistryConfig};
@@ -10,7 +10,7 @@ use tower_lsp::jsonrpc::{self, Result};
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
-use crate::analysis::{global_analysis, DocInfo};
+use crate::analysis::{analyze_edited_def, apply_content_changes, global_analysis, DocInfo};
 
 mod analysis;
 mod convert;
@@ -64,14 +64,11 @@ impl RocServer {
     }
 
     pub fn capabilities() -> ServerCapabilities {
-        let text_document_sync = TextDocumentSyncCapability::Options(
-            // TODO: later on make this incremental
-            TextDocumentSyncOptions {
-                open_close: Some(true),
-                change: Some(TextDocumentSyncKind::FULL),
-                ..TextDocumentSyncOptions::default()
-            },
-        );
+        let text_document_sync = TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::INCREMENTAL),
+            ..TextDocumentSyncOptions::default()
+        });
         let hover_provider = HoverProviderCapability::Simple(true);
         let definition_provider = DefinitionOptions {
             work_done_progress_options: WorkDoneProgressOptions {
@@ -90,7 +87,7 @@ impl RocServer {
                 },
                 legend: SemanticTokensLegend {
                     token_types: HIGHLIGHT_TOKENS_LEGEND.into(),
-                    token_modifiers: vec![],
+                    token_modifiers: HIGHLIGHT_TOKEN_MODIFIERS_LEGEND.into(),
                 },
                 range: None,
                 full: Some(SemanticTokensFullOptions::Bool(true)),
@@ -108,8 +105,11 @@ impl RocServer {
             hover_provider: Some(hover_provider),
             definition_provider: Some(OneOf::Right(definition_provider)),
             document_formatting_provider: Some(OneOf::Right(document_formatting_provider)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
             semantic_tokens_provider: Some(semantic_tokens_provider),
             completion_provider: Some(completion_provider),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             ..ServerCapabilities::default()
         }
     }
@@ -117,7 +117,26 @@ impl RocServer {
     /// Records a document content change.
     async fn change(&self, fi: Url, text: String, version: i32) {
         let updating_result = self.state.change(&fi, text, version).await;
+        self.publish_diagnostics(fi, updating_result, version).await;
+    }
+
+    /// Records a change to part of a document's content.
+    async fn change_incremental(
+        &self,
+        fi: Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) {
+        let updating_result = self.state.change_incremental(&fi, changes, version).await;
+        self.publish_diagnostics(fi, updating_result, version).await;
+    }
 
+    async fn publish_diagnostics(
+        &self,
+        fi: Url,
+        updating_result: std::result::Result<(), String>,
+        version: i32,
+    ) {
         //The analysis task can be cancelled by another change coming in which will update the watched variable
         if let Err(e) = updating_result {
             debug!("Cancelled change. Reason:{:?}", e);
@@ -139,7 +158,43 @@ impl RocServerState {
         Self { config, registry }
     }
 
-    async fn close(&self, _fi: Url) {}
+    async fn close(&self, fi: Url) {
+        self.registry.close(&fi).await;
+    }
+
+    /// Applies a change to part of a document. When it's within a single top-level def that no
+    /// longer parses, only that def gets parsed again; otherwise the whole module is analysed.
+    pub async fn change_incremental(
+        &self,
+        fi: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> std::result::Result<(), String> {
+        let Some(old_info) = self.registry.latest_source(fi).await else {
+            return Err(format!("Got changes to {fi} before it was opened"));
+        };
+
+        let (text, edit) = apply_content_changes(&old_info.source, changes);
+
+        if let Some(edit) = edit {
+            let doc_info = DocInfo::new(fi.clone(), text.clone(), version);
+
+            if let Some(document) = analyze_edited_def(&old_info, &doc_info, &edit) {
+                debug!("V{:?}:edited def doesn't parse, skipping analysis", version);
+
+                self.registry
+                    .apply_doc_info_changes(fi.clone(), doc_info)
+                    .await;
+                self.registry
+                    .apply_changes(vec![document], fi.clone())
+                    .await;
+
+                return Ok(());
+            }
+        }
+
+        self.change(fi, text, version).await
+    }
 
     pub async fn change(
         &self,
@@ -243,15 +298,8 @@ impl LanguageServer for RocServer {
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let VersionedTextDocumentIdentifier { uri, version, .. } = params.text_document;
 
-        // NOTE: We specify that we expect full-content syncs in the server capabilities,
-        // so here we assume the only change passed is a change of the entire document's content.
-        let TextDocumentContentChangeEvent { text, .. } = params
-            .content_changes
-            .into_iter()
-            .last()
-            .expect("textDocument change event had no changes ");
-
-        self.change(uri, text, version).await;
+        self.change_incremental(uri, params.content_changes, version)
+            .await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
@@ -276,6 +324,28 @@ impl LanguageServer for RocServer {
         unwind_async(self.state.registry.hover(&text_document.uri, position)).await
     }
 
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let CodeActionParams {
+            text_document,
+            range,
+            context: _,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.code_actions(&text_document.uri, range)).await
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let InlayHintParams {
+            text_document,
+            range,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.inlay_hints(&text_document.uri, range)).await
+    }
+
     async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
@@ -308,6 +378,25 @@ impl LanguageServer for RocServer {
         unwind_async(self.state.registry.formatting(&text_document.uri)).await
     }
 
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let DocumentRangeFormattingParams {
+            text_document,
+            range,
+            options: _,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .range_formatting(&text_document.uri, range),
+        )
+        .await
+    }
+
     async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
@@ -581,6 +670,222 @@ mod tests {
         .assert_debug_eq(&actual);
     }
 
+    /// Tests that incremental changes apply to the latest source, and that an edit that breaks
+    /// a def only gets that def parsed again.
+    #[tokio::test]
+    async fn test_incremental_change_in_def() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+            main = "Hello"
+            "#};
+
+        let (inner, url) = test_setup(doc.clone()).await;
+
+        // Removing the closing quote means `main` no longer parses
+        let remove_quote = TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(3, 13), Position::new(3, 14))),
+            range_length: None,
+            text: String::new(),
+        };
+        inner
+            .change_incremental(&url, vec![remove_quote], 1)
+            .await
+            .unwrap();
+        let broken = inner.registry.diagnostics(&url).await;
+
+        let add_quote = TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(3, 13), Position::new(3, 13))),
+            range_length: None,
+            text: "\"".to_string(),
+        };
+        inner
+            .change_incremental(&url, vec![add_quote], 2)
+            .await
+            .unwrap();
+        let fixed = inner.registry.diagnostics(&url).await;
+
+        let parse_errors = |diagnostics: Vec<Diagnostic>| {
+            diagnostics
+                .into_iter()
+                .filter(|diagnostic| diagnostic.source.as_deref() == Some("parse"))
+                .map(|diagnostic| diagnostic.range)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            parse_errors(broken),
+            vec![Range::new(Position::new(3, 0), Position::new(3, 13))]
+        );
+        assert_eq!(parse_errors(fixed), vec![]);
+        assert_eq!(
+            inner
+                .registry
+                .latest_source(&url)
+                .await
+                .map(|info| info.source),
+            Some(doc)
+        );
+    }
+
+    /// Tests that variables holding functions get highlighted as functions, using what
+    /// type checking found out.
+    #[tokio::test]
+    async fn test_semantic_tokens() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+            main =
+              shout = \s -> Str.concat s "!"
+              shout "Hi"
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+
+        let Some(SemanticTokensResult::Tokens(tokens)) = inner.registry.semantic_tokens(&url).await
+        else {
+            panic!("Expected semantic tokens");
+        };
+
+        let mut line = 0;
+        let mut start = 0;
+        let actual = tokens
+            .data
+            .into_iter()
+            .map(|token| {
+                if token.delta_line > 0 {
+                    start = 0;
+                }
+                line += token.delta_line;
+                start += token.delta_start;
+
+                let token_type = HIGHLIGHT_TOKENS_LEGEND[token.token_type as usize].as_str();
+                (line, start, token.length, token_type)
+            })
+            .filter(|(line, ..)| *line >= 3)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                (3, 0, 4, "variable"),
+                (4, 2, 5, "function"),
+                (4, 11, 1, "variable"),
+                (4, 16, 3, "namespace"),
+                (4, 20, 6, "function"),
+                (4, 27, 1, "variable"),
+                (4, 29, 3, "string"),
+                (5, 2, 5, "function"),
+                (5, 8, 4, "string"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_missing_branches() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+            color : [Red, Green, Blue] -> Str
+            color = \c ->
+                when c is
+                    Red -> "red"
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+        let range = Range::new(Position::new(5, 9), Position::new(5, 9));
+
+        let actual = inner
+            .registry
+            .code_actions(&url, range)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|action| match action {
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    edit:
+                        Some(WorkspaceEdit {
+                            changes: Some(changes),
+                            ..
+                        }),
+                    ..
+                }) => changes.into_values().flatten().collect(),
+                _ => vec![],
+            })
+            .map(|edit| (edit.range, edit.new_text))
+            .collect::<Vec<_>>();
+
+        expect![[r#"
+            [
+                (
+                    Range {
+                        start: Position {
+                            line: 5,
+                            character: 4,
+                        },
+                        end: Position {
+                            line: 6,
+                            character: 20,
+                        },
+                    },
+                    "when c is\n        Red -> \"red\"\n        Blue -> crash \"TODO\"\n        Green -> crash \"TODO\"",
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_inlay_hints() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+            main =
+              greeting = "Hello"
+              shout = \s -> Str.concat s "!"
+              shout greeting
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+        let range = Range::new(Position::new(0, 0), Position::new(7, 0));
+
+        let actual = inner.registry.inlay_hints(&url, range).await.map(|hints| {
+            hints
+                .into_iter()
+                .map(|hint| match hint.label {
+                    InlayHintLabel::String(label) => {
+                        (hint.position.line, hint.position.character, label)
+                    }
+                    InlayHintLabel::LabelParts(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        });
+
+        expect![[r#"
+            Some(
+                [
+                    (
+                        3,
+                        4,
+                        ": Str",
+                    ),
+                    (
+                        4,
+                        10,
+                        ": Str",
+                    ),
+                    (
+                        5,
+                        7,
+                        ": Str -> Str",
+                    ),
+                    (
+                        5,
+                        12,
+                        ": Str",
+                    ),
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
     #[tokio::test]
     async fn test_completion_with_docs() {
         let actual = completion_test(
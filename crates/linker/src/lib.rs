@@ -8,6 +8,9 @@ use object::Object;
 use roc_error_macros::internal_error;
 use roc_load::{EntryPoint, ExecutionMode, ExposedToHost, LoadConfig, Threading};
 use roc_module::symbol::Interns;
+use roc_mono::app_name::APP_NAME_SYMBOL;
+use roc_mono::build_info::BUILD_INFO_SYMBOL;
+use roc_mono::effects::USED_EFFECTS_SYMBOL;
 use roc_packaging::cache::RocCacheDir;
 use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
 use roc_solve::FunctionKind;
@@ -28,6 +31,8 @@ pub enum LinkType {
     Executable = 0,
     Dylib = 1,
     None = 2,
+    /// An executable with no dynamic dependencies, linked against musl (see --static)
+    StaticExecutable = 3,
 }
 
 pub fn supported(link_type: LinkType, target: Target) -> bool {
@@ -87,6 +92,8 @@ pub fn generate_stub_lib(
             palette: DEFAULT_PALETTE,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Executable,
+            reproducible: false,
+            coverage: false,
         },
     )
     .unwrap_or_else(|problem| todo!("{:?}", problem));
@@ -197,6 +204,10 @@ impl ExposedSymbols {
             custom_names.extend([format!("roc__{sym}_{id}_caller")]);
         }
 
+        custom_names.push(USED_EFFECTS_SYMBOL.to_string());
+        custom_names.push(APP_NAME_SYMBOL.to_string());
+        custom_names.push(BUILD_INFO_SYMBOL.to_string());
+
         // on windows (PE) binary search is used on the symbols,
         // so they must be in alphabetical order
         custom_names.sort_unstable();
@@ -223,6 +234,10 @@ impl ExposedSymbols {
             }
         }
 
+        custom_names.push(USED_EFFECTS_SYMBOL.to_string());
+        custom_names.push(APP_NAME_SYMBOL.to_string());
+        custom_names.push(BUILD_INFO_SYMBOL.to_string());
+
         // on windows (PE) binary search is used on the symbols,
         // so they must be in alphabetical order
         custom_names.sort_unstable();
@@ -0,0 +1,199 @@
+//! `roc.lock` records every package an app loads over HTTPS, so that CI can check (with
+//! `--locked`) that the packages it builds with are the ones that were committed.
+//!
+//! Package URLs already contain the hash of the package's contents, so the lockfile doesn't pin
+//! anything a URL doesn't; what it adds is one place that lists the whole set of packages,
+//! including the ones that are only depended on by other packages.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::https::{PackageMetadata, UrlProblem};
+
+pub const LOCKFILE_NAME: &str = "roc.lock";
+
+const HEADER: &str = "# This file is generated by roc; commit it, but don't edit it by hand.\n# Each line is a package's content hash, followed by the URL it was loaded from.\n";
+
+/// The lockfile for the app or package whose root module is at `root_module`.
+pub fn lockfile_path(root_module: &Path) -> PathBuf {
+    root_module.with_file_name(LOCKFILE_NAME)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Lockfile {
+    /// Package URL to content hash, sorted so the lockfile diffs nicely
+    packages: BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LockfileProblem {
+    /// The line didn't have a hash followed by a URL
+    MalformedLine(usize),
+    InvalidUrl(usize, UrlProblem),
+    /// The hash on the line isn't the one in the URL
+    HashMismatch(usize),
+}
+
+impl fmt::Display for LockfileProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockfileProblem::MalformedLine(line) => {
+                write!(f, "line {line} should be a hash followed by a URL")
+            }
+            LockfileProblem::InvalidUrl(line, problem) => {
+                write!(f, "line {line} has an invalid package URL: {problem:?}")
+            }
+            LockfileProblem::HashMismatch(line) => {
+                write!(f, "line {line} has a hash that doesn't match its URL")
+            }
+        }
+    }
+}
+
+/// How the packages an app loaded differ from its lockfile
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LockfileDiff {
+    /// Loaded, but not in the lockfile
+    pub added: Vec<String>,
+    /// In the lockfile, but not loaded
+    pub removed: Vec<String>,
+}
+
+impl LockfileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Lockfile {
+    pub fn from_urls<'a>(urls: impl IntoIterator<Item = &'a str>) -> Result<Self, UrlProblem> {
+        let mut packages = BTreeMap::new();
+
+        for url in urls {
+            let metadata = PackageMetadata::try_from(url)?;
+
+            packages.insert(url.to_string(), metadata.content_hash.to_string());
+        }
+
+        Ok(Self { packages })
+    }
+
+    pub fn parse(src: &str) -> Result<Self, LockfileProblem> {
+        let mut packages = BTreeMap::new();
+
+        for (index, line) in src.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((hash, url)) = line.split_once(' ') else {
+                return Err(LockfileProblem::MalformedLine(line_number));
+            };
+
+            let url = url.trim();
+            let metadata = PackageMetadata::try_from(url)
+                .map_err(|problem| LockfileProblem::InvalidUrl(line_number, problem))?;
+
+            if metadata.content_hash != hash {
+                return Err(LockfileProblem::HashMismatch(line_number));
+            }
+
+            packages.insert(url.to_string(), hash.to_string());
+        }
+
+        Ok(Self { packages })
+    }
+
+    /// Reads the lockfile at `path`, returning `None` if there isn't one.
+    pub fn read(path: &Path) -> io::Result<Option<Result<Self, LockfileProblem>>> {
+        match std::fs::read_to_string(path) {
+            Ok(src) => Ok(Some(Self::parse(&src))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.packages.keys().map(String::as_str)
+    }
+
+    /// What changed from this lockfile to `loaded`
+    pub fn diff(&self, loaded: &Lockfile) -> LockfileDiff {
+        LockfileDiff {
+            added: loaded
+                .urls()
+                .filter(|url| !self.packages.contains_key(*url))
+                .map(str::to_string)
+                .collect(),
+            removed: self
+                .urls()
+                .filter(|url| !loaded.packages.contains_key(*url))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Lockfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(HEADER)?;
+
+        for (url, hash) in self.packages.iter() {
+            writeln!(f, "{hash} {url}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+const BASIC_CLI: &str = "https://github.com/roc-lang/basic-cli/releases/download/0.10.0/vNe6s9hWzoTZtFmNkvEICPErI9ptji_ySjicO6CkucY.tar.br";
+
+#[cfg(test)]
+const PARSER: &str =
+    "https://example.com/parser/2GqGvsKg6Jd8wpCtN0mbgCCSESeQvDyxZWe1GRoDDy8.tar.br#Parser.roc";
+
+#[test]
+fn lockfile_round_trip() {
+    let lockfile = Lockfile::from_urls([PARSER, BASIC_CLI]).unwrap();
+    let rendered = lockfile.to_string();
+
+    assert_eq!(Lockfile::parse(&rendered), Ok(lockfile));
+    assert!(rendered.ends_with(&format!(
+        "2GqGvsKg6Jd8wpCtN0mbgCCSESeQvDyxZWe1GRoDDy8 {PARSER}\nvNe6s9hWzoTZtFmNkvEICPErI9ptji_ySjicO6CkucY {BASIC_CLI}\n"
+    )));
+}
+
+#[test]
+fn lockfile_hash_mismatch() {
+    let src = format!("{HEADER}notTheHash {BASIC_CLI}\n");
+
+    assert_eq!(Lockfile::parse(&src), Err(LockfileProblem::HashMismatch(3)));
+}
+
+#[test]
+fn lockfile_diff() {
+    let locked = Lockfile::from_urls([BASIC_CLI]).unwrap();
+    let loaded = Lockfile::from_urls([PARSER]).unwrap();
+
+    assert_eq!(
+        locked.diff(&loaded),
+        LockfileDiff {
+            added: vec![PARSER.to_string()],
+            removed: vec![BASIC_CLI.to_string()],
+        }
+    );
+    assert!(locked.diff(&locked).is_empty());
+}
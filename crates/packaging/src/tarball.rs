@@ -4,7 +4,6 @@ use flate2::write::GzEncoder;
 use roc_parse::ast::{
     Header, IngestedFileImport, Module, RecursiveValueDefIter, StrLiteral, ValueDef,
 };
-use roc_parse::header::PlatformHeader;
 use roc_parse::module::{parse_header, parse_module_defs};
 use roc_parse::state::State;
 use std::ffi::OsStr;
@@ -56,6 +55,36 @@ impl<'a> TryFrom<&'a str> for Compression {
     }
 }
 
+/// What kind of module a bundle's root module is. Only packages and platforms can be bundled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleKind {
+    Package,
+    Platform,
+}
+
+/// Reads the header of the .roc file at `path`, and returns an `InvalidInput` error if it's not
+/// something that can be bundled.
+pub fn bundle_kind(path: &Path) -> io::Result<BundleKind> {
+    let arena = Bump::new();
+    let mut buf = Vec::new();
+
+    let kind = match read_header(&arena, &mut buf, path)?.0.header {
+        Header::Package(_) => return Ok(BundleKind::Package),
+        Header::Platform(_) => return Ok(BundleKind::Platform),
+        Header::App(_) => "an app",
+        Header::Module(_) => "a module",
+        Header::Hosted(_) => "a hosted module",
+    };
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "{} is {kind}, but only packages and platforms can be bundled. Bundle the package or platform's main.roc instead.",
+            path.display()
+        ),
+    ))
+}
+
 /// Given a path to a .roc file, write a .tar file to disk.
 ///
 /// The .tar file will be in the same directory, and its filename
@@ -125,27 +154,13 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
     };
     let mut builder = tar::Builder::new(writer);
     let arena = Bump::new();
-    let mut buf = Vec::new();
 
-    // TODO use this when finding .roc files by discovering them from the root module.
-    // let other_modules: &[Module<'_>] =
-    match read_header(&arena, &mut buf, path)?.0.header {
-        Header::Module(_) => {
-            todo!();
-            // TODO report error
-        }
-        Header::App(_) => {
-            todo!();
-            // TODO report error
-        }
-        Header::Hosted(_) => {
-            todo!();
-            // TODO report error
-        }
-        Header::Package(_) => {
+    // TODO use the header when finding .roc files by discovering them from the root module.
+    match bundle_kind(path)? {
+        BundleKind::Package => {
             add_source_files(&arena, root_dir, &mut builder)?;
         }
-        Header::Platform(PlatformHeader { imports: _, .. }) => {
+        BundleKind::Platform => {
             // Add all the prebuilt host files to the archive.
             // These should all be in the same directory as the platform module.
             for entry in std::fs::read_dir(root_dir)? {
@@ -274,8 +289,11 @@ fn read_header<'a>(
     // (We can't use that for the parser state and still return Module<'a> unfortunately.)
     let arena_buf = bumpalo::collections::Vec::from_iter_in(buf.iter().copied(), arena);
     let parse_state = State::new(arena_buf.into_bump_slice());
-    parse_header(arena, parse_state).map_err(|_err| {
-        todo!(); // TODO report a nice error and exit 1 - or maybe just return Err, for better testability?
+    parse_header(arena, parse_state).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} has a malformed header: {:?}", path.display(), err),
+        )
     })
 }
 
@@ -16,7 +16,7 @@ use roc_repl_eval::gen::{format_answer, ReplOutput};
 use roc_repl_eval::{ReplApp, ReplAppMemory};
 use roc_std::RocStr;
 use roc_target::Target;
-use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::pretty_print::{name_and_print_var_qualified, DebugPrint, Qualification};
 use roc_types::subs::Subs;
 
 pub fn eval_llvm(
@@ -37,12 +37,13 @@ pub fn eval_llvm(
     let main_fn_var = *main_fn_var;
 
     // pretty-print the expr type string for later.
-    let expr_type_str = name_and_print_var(
+    let expr_type_str = name_and_print_var_qualified(
         main_fn_var,
         &mut loaded.subs,
         loaded.module_id,
         &loaded.interns,
         DebugPrint::NOTHING,
+        Qualification::Short,
     );
 
     let (_, main_fn_layout) = *loaded
@@ -215,6 +216,10 @@ fn mono_module_to_dylib_llvm<'a>(
         module,
         target,
         mode: LlvmBackendMode::GenTest, // so roc_panic is generated
+        debug_locations: Default::default(),
+        inline_threshold: 0,
+        alias_analysis: Default::default(),
+        stack_checks: true,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
     };
@@ -227,6 +232,7 @@ fn mono_module_to_dylib_llvm<'a>(
         EntryPoint::Executable {
             exposed_to_host,
             platform_path: _,
+            app_name: _,
         } => {
             // TODO support multiple of these!
             debug_assert_eq!(exposed_to_host.len(), 1);
@@ -309,6 +315,7 @@ fn mono_module_to_dylib_asm<'a>(
         exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
         lazy_literals,
         mode: roc_gen_dev::AssemblyBackendMode::Repl,
+        debug_locations: Default::default(),
     };
 
     let module_object =
@@ -8,12 +8,16 @@ use roc_mono::ir::OptLevel;
 use roc_repl_eval::gen::Problems;
 use roc_repl_ui::colors::{CYAN, END_COL};
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
-use roc_repl_ui::{format_output, is_incomplete, CONT_PROMPT, PROMPT, SHORT_INSTRUCTIONS, TIPS};
+use roc_repl_ui::{
+    format_docs, format_output, format_unknown_package, is_incomplete, CONT_PROMPT, PROMPT,
+    RESET_MESSAGE, SHORT_INSTRUCTIONS, TIPS,
+};
 use roc_reporting::report::{to_file_problem_report_string, ANSI_STYLE_CODES, DEFAULT_PALETTE};
 use roc_target::Target;
+use rustyline::completion::Completer;
 use rustyline::highlight::{Highlighter, PromptInfo};
 use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
-use rustyline_derive::{Completer, Helper, Hinter};
+use rustyline_derive::{Helper, Hinter};
 use std::borrow::Cow;
 use target_lexicon::Triple;
 
@@ -28,7 +32,7 @@ pub const WELCOME_MESSAGE: &str = concatcp!(
     "\n\n"
 );
 
-#[derive(Completer, Helper, Hinter, Default)]
+#[derive(Helper, Hinter, Default)]
 pub struct ReplHelper {
     validator: InputValidator,
     state: ReplState,
@@ -80,6 +84,24 @@ pub fn main() -> i32 {
                     ReplAction::Help => {
                         println!("{TIPS}");
                     }
+                    ReplAction::Type {
+                        opt_output,
+                        problems,
+                    } => {
+                        println!("{}", format_output(ANSI_STYLE_CODES, opt_output, problems));
+                    }
+                    ReplAction::Doc { name, docs } => {
+                        println!("{}", format_docs(&name, docs));
+                    }
+                    ReplAction::UnknownPackage { shorthand } => {
+                        println!("{}", format_unknown_package(&shorthand));
+                    }
+                    ReplAction::Reset => {
+                        println!("{RESET_MESSAGE}");
+                    }
+                    ReplAction::CommandProblem(problem) => {
+                        println!("{problem}");
+                    }
                     ReplAction::Nothing => {}
                 }
             }
@@ -125,6 +147,19 @@ impl Validator for InputValidator {
     }
 }
 
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.state.complete(line, pos, Triple::host().into()))
+    }
+}
+
 impl Highlighter for ReplHelper {
     fn has_continuation_prompt(&self) -> bool {
         true
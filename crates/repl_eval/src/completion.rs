@@ -0,0 +1,224 @@
+//! What the REPL can complete on Tab: the names that are in scope after the defs entered
+//! so far, the modules that names can be qualified with, and the fields of records.
+use bumpalo::Bump;
+use roc_collections::VecMap;
+use roc_load::LoadedModule;
+use roc_module::symbol::ModuleId;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_parse::ast::Header;
+use roc_parse::state::State;
+use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+use roc_solve::FunctionKind;
+use roc_target::Target;
+use roc_types::subs::{Content, FlatType, Subs, Variable};
+use std::path::PathBuf;
+
+use crate::gen::promote_expr_to_module;
+
+/// The builtin modules that every module can use without importing them
+const DEFAULT_MODULES: [(ModuleId, &str); 12] = [
+    (ModuleId::BOOL, "Bool"),
+    (ModuleId::BOX, "Box"),
+    (ModuleId::DECODE, "Decode"),
+    (ModuleId::DICT, "Dict"),
+    (ModuleId::ENCODE, "Encode"),
+    (ModuleId::HASH, "Hash"),
+    (ModuleId::INSPECT, "Inspect"),
+    (ModuleId::LIST, "List"),
+    (ModuleId::NUM, "Num"),
+    (ModuleId::RESULT, "Result"),
+    (ModuleId::SET, "Set"),
+    (ModuleId::STR, "Str"),
+];
+
+/// How deep to look into records of records, e.g. `config.server.port` is 2 deep
+const MAX_FIELD_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplScope {
+    /// Values that can be used without qualifying them, e.g. the defs entered so far
+    pub unqualified: Vec<String>,
+    /// What each module exposes, e.g. `List` exposes `map`
+    pub modules: VecMap<String, Vec<String>>,
+    /// The fields of records, by the path to the record, e.g. `user` or `user.address`
+    pub record_fields: VecMap<String, Vec<String>>,
+}
+
+impl ReplScope {
+    /// Completions of the word that ends at `pos` in `line`, and where in `line` the part
+    /// that they would replace starts.
+    pub fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '.')
+            .last()
+            .map_or(pos, |(index, _)| index);
+        let word = &before[start..];
+
+        let (replace_from, partial, names): (_, _, Box<dyn Iterator<Item = &String>>) =
+            match word.rsplit_once('.') {
+                Some((qualifier, partial)) => {
+                    let names = self
+                        .modules
+                        .get(&qualifier.to_string())
+                        .or_else(|| self.record_fields.get(&qualifier.to_string()));
+
+                    match names {
+                        Some(names) => (pos - partial.len(), partial, Box::new(names.iter())),
+                        None => return (pos, Vec::new()),
+                    }
+                }
+                None => (
+                    start,
+                    word,
+                    Box::new(self.unqualified.iter().chain(self.modules.keys())),
+                ),
+            };
+
+        let mut completions: Vec<String> = names
+            .filter(|name| name.starts_with(partial))
+            .cloned()
+            .collect();
+
+        completions.sort();
+        completions.dedup();
+
+        (replace_from, completions)
+    }
+}
+
+/// Type-checks the given defs to find out what is in scope after them.
+pub fn load_scope<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    packages: &[(String, String)],
+    defs: I,
+    target: Target,
+) -> ReplScope {
+    let mut scope = ReplScope::default();
+
+    for (module_id, module_name) in DEFAULT_MODULES {
+        let source = roc_builtins::roc::module_source(module_id);
+
+        if let Ok((module, _)) =
+            roc_parse::module::parse_header(arena, State::new(source.as_bytes()))
+        {
+            if let Header::Module(header) = module.header {
+                let exposed = header
+                    .exposes
+                    .items
+                    .iter()
+                    .map(|name| name.value.item().as_str().to_string())
+                    .collect();
+
+                scope.modules.insert(module_name.to_string(), exposed);
+            }
+        }
+    }
+
+    let filename = PathBuf::from("replfile.roc");
+    let src_dir = PathBuf::from(".");
+    let (_, module_src) = promote_expr_to_module(arena, packages, defs, "{}");
+    let loaded = roc_load::load_and_typecheck_str(
+        arena,
+        filename,
+        module_src,
+        src_dir,
+        target,
+        FunctionKind::LambdaSet,
+        RenderTarget::ColorTerminal,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        DEFAULT_PALETTE,
+    );
+
+    // With a parse error in the defs, the builtins are all we can offer
+    if let Ok(loaded) = loaded {
+        add_loaded(&mut scope, loaded);
+    }
+
+    scope
+}
+
+fn add_loaded(scope: &mut ReplScope, loaded: LoadedModule) {
+    let home = loaded.module_id;
+
+    // modules that the defs imported
+    for (module_id, exposed) in loaded.exposes.iter() {
+        if *module_id == home || module_id.is_builtin() {
+            continue;
+        }
+
+        let names = exposed
+            .iter()
+            .map(|(symbol, _)| symbol.as_str(&loaded.interns).to_string())
+            .collect();
+
+        scope.modules.insert(
+            loaded.interns.module_name(*module_id).as_str().to_string(),
+            names,
+        );
+    }
+
+    let Some(declarations) = loaded.declarations_by_id.get(&home) else {
+        return;
+    };
+
+    let subs = loaded.solved.inner();
+
+    for (symbol, var) in declarations
+        .symbols
+        .iter()
+        .zip(declarations.variables.iter())
+    {
+        let name = symbol.value.as_str(&loaded.interns).to_string();
+
+        // the def that the REPL adds to hold the expression being evaluated, and
+        // generated symbols, e.g. for expects
+        if name == "replOutput" || name.starts_with('#') {
+            continue;
+        }
+
+        add_record_fields(scope, subs, name.clone(), *var, 1);
+        scope.unqualified.push(name);
+    }
+}
+
+fn add_record_fields(
+    scope: &mut ReplScope,
+    subs: &Subs,
+    path: String,
+    var: Variable,
+    depth: usize,
+) {
+    let fields: Vec<(String, Variable)> = match subs.get_content_without_compacting(var) {
+        Content::Structure(FlatType::Record(fields, ext)) => {
+            match fields.unsorted_iterator(subs, *ext) {
+                Ok(fields) => fields
+                    .map(|(label, field)| (label.as_str().to_string(), field.into_inner()))
+                    .collect(),
+                Err(_) => return,
+            }
+        }
+        Content::Alias(_, _, real_var, _) => {
+            return add_record_fields(scope, subs, path, *real_var, depth);
+        }
+        _ => return,
+    };
+
+    if depth < MAX_FIELD_DEPTH {
+        for (label, field_var) in fields.iter() {
+            add_record_fields(
+                scope,
+                subs,
+                format!("{path}.{label}"),
+                *field_var,
+                depth + 1,
+            );
+        }
+    }
+
+    let labels = fields.into_iter().map(|(label, _)| label).collect();
+
+    scope.record_fields.insert(path, labels);
+}
@@ -13,6 +13,7 @@ use roc_region::all::LineInfo;
 use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
 use roc_solve::FunctionKind;
 use roc_target::Target;
+use roc_types::pretty_print::{name_and_print_var_qualified, DebugPrint, Qualification};
 
 #[derive(Debug)]
 pub struct ReplOutput {
@@ -45,8 +46,10 @@ impl Problems {
     }
 }
 
+/// `packages` are the shorthands that imports can use, with the URL or path of each.
 pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
+    packages: &[(String, String)],
     defs: I,
     expr: &str,
     target: Target,
@@ -54,7 +57,7 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
 ) -> (Option<MonomorphizedModule<'a>>, Problems) {
     let filename = PathBuf::from("replfile.roc");
     let src_dir = PathBuf::from(".");
-    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, expr);
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, packages, defs, expr);
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
         filename,
@@ -68,6 +71,8 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
             palette,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Executable,
+            reproducible: false,
+            coverage: false,
         },
     );
 
@@ -167,16 +172,124 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
     (Some(loaded), problems)
 }
 
-fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
+/// The type of the expression compiled by [`compile_to_mono`], e.g. `Num *`.
+pub fn expr_type(loaded: &mut MonomorphizedModule<'_>) -> Option<String> {
+    let (_, var) = loaded.exposed_to_host.top_level_values.iter().next()?;
+    let var = *var;
+
+    Some(name_and_print_var_qualified(
+        var,
+        &mut loaded.subs,
+        loaded.module_id,
+        &loaded.interns,
+        DebugPrint::NOTHING,
+        Qualification::Short,
+    ))
+}
+
+/// The doc comment of a value or type that is in scope after the given defs, e.g.
+/// `List.map`, or of a module, e.g. `List`.
+pub fn find_docs<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    packages: &[(String, String)],
+    defs: I,
+    name: &str,
+    target: Target,
+    palette: Palette,
+) -> Option<String> {
+    use roc_load::docs::{DocDef, DocEntry};
+
+    let filename = PathBuf::from("replfile.roc");
+    let src_dir = PathBuf::from(".");
+    let (_, module_src) = promote_expr_to_module(arena, packages, defs, "{}");
+    let loaded = roc_load::load_and_typecheck_str(
+        arena,
+        filename,
+        module_src,
+        src_dir,
+        target,
+        FunctionKind::LambdaSet,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        palette,
+    )
+    .ok()?;
+
+    let (module_name, def_name) = match name.rsplit_once('.') {
+        Some((module_name, def_name)) => (Some(module_name), def_name),
+        None => (None, name),
+    };
+
+    let mut docs = Vec::new();
+
+    for (_, module_docs) in loaded.docs_by_module.iter() {
+        if module_name.is_none() && module_docs.name == def_name {
+            // the docs of a module are all of its module doc comments
+            docs.extend(module_docs.entries.iter().filter_map(|entry| match entry {
+                DocEntry::ModuleDoc(doc) => Some(doc.trim()),
+                _ => None,
+            }));
+
+            continue;
+        }
+
+        if module_name.is_some_and(|module_name| module_docs.name != module_name) {
+            continue;
+        }
+
+        for entry in module_docs.entries.iter() {
+            if let DocEntry::DocDef(DocDef {
+                name,
+                docs: Some(doc),
+                ..
+            }) = entry
+            {
+                if name == def_name {
+                    docs.push(doc.trim());
+                }
+            }
+        }
+    }
+
+    if docs.is_empty() {
+        None
+    } else {
+        Some(docs.join("\n\n"))
+    }
+}
+
+pub(crate) fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
+    packages: &[(String, String)],
     defs: I,
     expr: &str,
 ) -> (usize, &'a str) {
-    const REPL_MODULE_HEADER: &str = "app \"app\" provides [replOutput] to \"./platform\"\n\n";
+    const REPL_MODULE_NAME: &str = "app \"app\" ";
+    const REPL_MODULE_PROVIDES: &str = "provides [replOutput] to \"./platform\"\n\n";
     const REPL_MODULE_MAIN_DEF: &str = "replOutput =\n";
     const INDENT: &str = "    ";
 
-    let mut buffer = bumpalo::collections::string::String::from_str_in(REPL_MODULE_HEADER, arena);
+    let mut buffer = bumpalo::collections::string::String::from_str_in(REPL_MODULE_NAME, arena);
+
+    if !packages.is_empty() {
+        buffer.push_str("packages { ");
+
+        for (index, (shorthand, location)) in packages.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(", ");
+            }
+
+            // `:package` only accepts locations that fit in a plain string literal
+            buffer.push_str(shorthand);
+            buffer.push_str(": \"");
+            buffer.push_str(location);
+            buffer.push('"');
+        }
+
+        buffer.push_str(" } ");
+    }
+
+    buffer.push_str(REPL_MODULE_PROVIDES);
 
     for line in defs {
         // don't indent the defs
@@ -3,8 +3,10 @@ use roc_parse::ast::Expr;
 use roc_std::RocDec;
 use roc_target::Target;
 
+pub mod completion;
 pub mod eval;
 pub mod gen;
+pub mod transport;
 
 pub trait ReplApp<'a> {
     type Memory: 'a + ReplAppMemory;
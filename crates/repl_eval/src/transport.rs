@@ -0,0 +1,72 @@
+//! Where the output of `expect` goes.
+//!
+//! The CLI just writes the rendered reports to the terminal, but an embedding host or
+//! the wasm REPL may want to show them somewhere else, e.g. next to the test in a
+//! browser. Anything that implements [`std::io::Write`] is a transport that writes the
+//! rendered reports, exactly like the CLI does; [`CollectedMessages`] keeps them instead.
+use std::io;
+use std::path::PathBuf;
+
+use roc_region::all::Region;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectMessageKind {
+    /// An `expect` whose condition was false
+    ExpectFailed,
+    /// An `expect` that crashed while it was being evaluated
+    ExpectPanicked,
+    /// An `expect` marked `# roc-test: xfail` that passed
+    UnexpectedPass,
+    /// An `expect` that passed, but didn't free everything it allocated. Only sent by
+    /// `roc test --leak-check`.
+    Leaked,
+    /// A property that failed on some generated arguments, or that takes arguments which
+    /// can't be generated. Only sent by `roc test --fuzz`.
+    PropertyFailed,
+    /// An `Inspect.expectSnapshot` whose value didn't match its snapshot, or whose snapshot
+    /// couldn't be read or written. Sent after the failure of the `expect` that checked it.
+    SnapshotFailed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectMessage {
+    pub kind: ExpectMessageKind,
+    /// The module the message is about
+    pub filename: PathBuf,
+    pub region: Region,
+    /// The report, rendered for the render target the runner was given
+    pub rendered: String,
+}
+
+pub trait ExpectTransport {
+    fn send(&mut self, message: ExpectMessage) -> io::Result<()>;
+
+    /// All messages about the current top-level expect have been sent.
+    fn end_of_expect(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ExpectTransport for W {
+    fn send(&mut self, message: ExpectMessage) -> io::Result<()> {
+        self.write_all(message.rendered.as_bytes())
+    }
+
+    fn end_of_expect(&mut self) -> io::Result<()> {
+        writeln!(self)
+    }
+}
+
+/// Keeps all messages, in the order they were sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectedMessages {
+    pub messages: Vec<ExpectMessage>,
+}
+
+impl ExpectTransport for CollectedMessages {
+    fn send(&mut self, message: ExpectMessage) -> io::Result<()> {
+        self.messages.push(message);
+
+        Ok(())
+    }
+}
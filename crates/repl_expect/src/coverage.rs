@@ -0,0 +1,181 @@
+//! `roc test --coverage` builds the expects with a call at the start of every top-level def and
+//! `when` branch in the root package (see [roc_mono::coverage]), which this module counts.
+//!
+//! Expects can run in forked processes, so the counts live in memory that is shared with every
+//! child, and the parent can read them once all the expects are done.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{RwLock, RwLockReadGuard};
+
+use roc_error_macros::internal_error;
+use roc_gen_llvm::llvm::externs::ROC_COVERAGE_HOOK;
+use roc_module::symbol::ModuleId;
+use roc_mono::coverage::CoverageSite;
+use roc_region::all::Region;
+
+static COUNTERS: RwLock<Option<Counters>> = RwLock::new(None);
+
+struct Counters {
+    /// Sorted by the site's id, which is what the instrumented code passes to the hook
+    sites: Vec<(u64, ModuleId, CoverageSite, Region)>,
+    /// One count per site, in a shared mapping
+    counts: *mut AtomicU64,
+}
+
+// The counts are only ever accessed atomically
+unsafe impl Send for Counters {}
+unsafe impl Sync for Counters {}
+
+impl Counters {
+    fn count(&self, index: usize) -> &AtomicU64 {
+        debug_assert!(index < self.sites.len());
+
+        unsafe { &*self.counts.add(index) }
+    }
+}
+
+impl Drop for Counters {
+    fn drop(&mut self) {
+        let size = self.sites.len() * std::mem::size_of::<AtomicU64>();
+
+        unsafe { libc::munmap(self.counts.cast(), size) };
+    }
+}
+
+fn counters() -> RwLockReadGuard<'static, Option<Counters>> {
+    // A panic in the hook can't leave the counts in a state worth refusing to report.
+    COUNTERS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// How much of one module the expects reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleCoverage {
+    pub module_id: ModuleId,
+    pub reached: usize,
+    pub total: usize,
+    /// The defs and branches that never ran, in source order
+    pub unreached: Vec<(CoverageSite, Region)>,
+}
+
+impl ModuleCoverage {
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.reached as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// Starts counting `sites`, with counts that start at zero, and stores the hook in `lib` if
+/// it was built with coverage. Must be called before any expect runs, and before forking.
+pub(crate) fn install(
+    lib: &libloading::Library,
+    sites_by_module: Vec<(ModuleId, Vec<(CoverageSite, Region)>)>,
+) -> Result<(), libloading::Error> {
+    let mut sites: Vec<_> = sites_by_module
+        .into_iter()
+        .flat_map(|(module_id, sites)| {
+            sites
+                .into_iter()
+                .map(move |(site, region)| (site.id(), module_id, site, region))
+        })
+        .collect();
+
+    sites.sort_by_key(|(id, ..)| *id);
+    sites.dedup_by_key(|(id, ..)| *id);
+
+    if sites.is_empty() {
+        return Ok(());
+    }
+
+    let size = sites.len() * std::mem::size_of::<AtomicU64>();
+
+    // Anonymous memory is zeroed, and a shared mapping stays shared with forked children
+    let counts = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            size,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+
+    if counts == libc::MAP_FAILED {
+        internal_error!("failed to mmap the coverage counts");
+    }
+
+    *COUNTERS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Counters {
+        sites,
+        counts: counts.cast(),
+    });
+
+    // Nothing calls the hook if none of the instrumented code made it into the program
+    if let Ok(hook) = unsafe { lib.get::<*mut extern "C" fn(u64)>(ROC_COVERAGE_HOOK.as_bytes()) } {
+        unsafe { **hook = roc_coverage_hook };
+    }
+
+    Ok(())
+}
+
+extern "C" fn roc_coverage_hook(id: u64) {
+    if let Some(counters) = counters().as_ref() {
+        // Sites that aren't in the table (like lifted closures) aren't reported on
+        if let Ok(index) = counters.sites.binary_search_by_key(&id, |(id, ..)| *id) {
+            counters.count(index).fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Stops counting, and says how much of each module the expects that ran so far reached.
+/// Empty if nothing was built with coverage.
+pub fn take_coverage() -> Vec<ModuleCoverage> {
+    let counters = COUNTERS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take();
+
+    let counters = match counters {
+        Some(counters) => counters,
+        None => return Vec::new(),
+    };
+
+    let mut by_module: Vec<ModuleCoverage> = Vec::new();
+
+    for (index, (_, module_id, site, region)) in counters.sites.iter().enumerate() {
+        let position = match by_module.iter().position(|m| m.module_id == *module_id) {
+            Some(position) => position,
+            None => {
+                by_module.push(ModuleCoverage {
+                    module_id: *module_id,
+                    reached: 0,
+                    total: 0,
+                    unreached: Vec::new(),
+                });
+
+                by_module.len() - 1
+            }
+        };
+
+        let module = &mut by_module[position];
+
+        module.total += 1;
+
+        if counters.count(index).load(Ordering::Relaxed) > 0 {
+            module.reached += 1;
+        } else {
+            module.unreached.push((*site, *region));
+        }
+    }
+
+    for module in by_module.iter_mut() {
+        module.unreached.sort_by_key(|(_, region)| region.start());
+    }
+
+    by_module
+}
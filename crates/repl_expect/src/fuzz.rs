@@ -0,0 +1,542 @@
+//! `roc test --fuzz` runs properties: top-level functions whose name starts with `fuzz`, and
+//! which return a `Bool`.
+//!
+//! ```roc
+//! fuzzAddCommutes : I64, I64 -> Bool
+//! fuzzAddCommutes = \a, b -> Num.addWrap a b == Num.addWrap b a
+//! ```
+//!
+//! Each property is called with many generated arguments. Once it returns `Bool.false`, crashes,
+//! or fails an inline `expect`, its arguments are shrunk towards zero for as long as it keeps
+//! failing, and the smallest failing call is reported. The arguments are generated from a seed
+//! based on the name of the property, so every run of `roc test --fuzz` tries the same ones.
+use std::mem::MaybeUninit;
+
+use bumpalo::Bump;
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
+use roc_collections::VecMap;
+use roc_error_macros::internal_error;
+use roc_gen_llvm::run_roc::RocCallResult;
+use roc_load::Expectations;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_mono::{
+    ir::CrashTag,
+    layout::{Builtin, GlobalLayoutInterner, InLayout, LayoutInterner, LayoutRepr},
+};
+use roc_repl_eval::transport::{ExpectMessageKind, ExpectTransport};
+use roc_reporting::{
+    error::expect::{PropertyFailure, Renderer},
+    report::RenderTarget,
+};
+
+use crate::run::{
+    render_expect_failure, send_report, ExpectMarker, ExpectMemory, ExpectResults, ExpectSequence,
+    FuzzProperty,
+};
+
+/// How many generated calls a property has to pass
+pub const DEFAULT_FUZZ_RUNS: usize = 100;
+
+/// Shrinking gives up after this many calls, so a property that fails on a big argument still
+/// gets reported in reasonable time.
+const MAX_SHRINK_CALLS: usize = 10_000;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_properties<'a, T: ExpectTransport>(
+    transport: &mut T,
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &GlobalLayoutInterner<'a>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    properties: &[FuzzProperty<'_>],
+    runs: usize,
+) -> std::io::Result<ExpectResults> {
+    // for the inline expects that the properties run
+    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
+    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+    memory.set_shared_buffer(lib);
+
+    let results = run_properties_with_memory(
+        transport,
+        render_target,
+        arena,
+        interns,
+        layout_interner,
+        lib,
+        expectations,
+        properties,
+        runs,
+        &mut memory,
+    );
+
+    if let Some(shm_name) = memory.shm_name.as_ref() {
+        unsafe { libc::shm_unlink(shm_name.as_ptr()) };
+    }
+
+    results
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_properties_with_memory<'a, T: ExpectTransport>(
+    transport: &mut T,
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &GlobalLayoutInterner<'a>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    properties: &[FuzzProperty<'_>],
+    runs: usize,
+    memory: &mut ExpectMemory,
+) -> std::io::Result<ExpectResults> {
+    let mut results = ExpectResults::default();
+
+    let interner = layout_interner.fork();
+
+    for property in properties {
+        if property.marker == ExpectMarker::Skip {
+            results.skipped += 1;
+            continue;
+        }
+
+        let module_id = property.symbol.module_id();
+        let data = expectations.get_mut(&module_id).unwrap();
+        let filename = data.path.to_owned();
+        let source = std::fs::read_to_string(&data.path).unwrap();
+
+        let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
+
+        let kinds: Option<Vec<InputKind>> = property
+            .arguments
+            .iter()
+            .map(|layout| InputKind::from_layout(&interner, *layout))
+            .collect();
+
+        let Some(kinds) = kinds else {
+            send_report(
+                transport,
+                &renderer,
+                ExpectMessageKind::PropertyFailed,
+                property.region,
+                |buf| renderer.render_unsupported_property(buf, property.region),
+            )?;
+            transport.end_of_expect()?;
+
+            results.failed += 1;
+            continue;
+        };
+
+        let function = PropertyFunction::load(lib, property.name);
+        let failure = find_failure(&function, memory, &kinds, runs, seed(property.name));
+
+        match (property.marker, failure) {
+            (ExpectMarker::ExpectedFailure, Some(_)) => results.xfailed += 1,
+            (ExpectMarker::ExpectedFailure, None) => {
+                send_report(
+                    transport,
+                    &renderer,
+                    ExpectMessageKind::UnexpectedPass,
+                    property.region,
+                    |buf| renderer.render_unexpected_pass(buf, property.region),
+                )?;
+                transport.end_of_expect()?;
+
+                results.failed += 1;
+            }
+            (_, None) => results.passed += 1,
+            (_, Some(failure)) => {
+                let mut call = property.symbol.as_str(interns).to_string();
+                for input in &failure.inputs {
+                    call.push(' ');
+                    call.push_str(&input.to_roc_string());
+                }
+
+                let property_failure = match &failure.outcome {
+                    Outcome::Passed => unreachable!(),
+                    Outcome::ReturnedFalse => PropertyFailure::ReturnedFalse,
+                    Outcome::Crashed(message) => PropertyFailure::Crashed(message),
+                    Outcome::ExpectFailed => PropertyFailure::ExpectFailed,
+                };
+
+                send_report(
+                    transport,
+                    &renderer,
+                    ExpectMessageKind::PropertyFailed,
+                    property.region,
+                    |buf| {
+                        renderer.render_property_failure(
+                            buf,
+                            property.region,
+                            &call,
+                            failure.runs,
+                            property_failure,
+                        )
+                    },
+                )?;
+
+                if failure.outcome == Outcome::ExpectFailed {
+                    // run the smallest failing call once more, to report what the expects saw
+                    let sequence = ExpectSequence::new(memory.ptr);
+                    let _ = function.call(&failure.inputs);
+
+                    let mut offset = ExpectSequence::START_OFFSET;
+
+                    for _ in 0..sequence.count_failures() {
+                        offset = render_expect_failure(
+                            transport,
+                            &renderer,
+                            arena,
+                            None,
+                            expectations,
+                            interns,
+                            layout_interner,
+                            memory.ptr,
+                            offset,
+                        )?;
+                    }
+                }
+
+                transport.end_of_expect()?;
+
+                results.failed += 1;
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// The exposed C function that calls a property with the arguments in an array of slots
+struct PropertyFunction<'lib> {
+    function: libloading::Symbol<'lib, unsafe extern "C" fn(*const u64, *mut RocCallResult<bool>)>,
+}
+
+impl<'lib> PropertyFunction<'lib> {
+    fn load(lib: &'lib libloading::Library, name: &str) -> Self {
+        let function = unsafe { lib.get(name.as_bytes()) }
+            .unwrap_or_else(|_| internal_error!("no {name} in the dylib"));
+
+        Self { function }
+    }
+
+    fn call(&self, inputs: &[Input]) -> Result<bool, (String, CrashTag)> {
+        let slots: Vec<u64> = inputs.iter().map(|input| input.to_slot()).collect();
+
+        let mut result = MaybeUninit::uninit();
+
+        unsafe {
+            (self.function)(slots.as_ptr(), result.as_mut_ptr());
+
+            result.assume_init().into()
+        }
+    }
+
+    fn outcome(&self, memory: &ExpectMemory, inputs: &[Input]) -> Outcome {
+        let sequence = ExpectSequence::new(memory.ptr);
+
+        match self.call(inputs) {
+            Err((message, _)) => Outcome::Crashed(message),
+            Ok(_) if sequence.count_failures() > 0 => Outcome::ExpectFailed,
+            Ok(true) => Outcome::Passed,
+            Ok(false) => Outcome::ReturnedFalse,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Passed,
+    ReturnedFalse,
+    Crashed(String),
+    ExpectFailed,
+}
+
+#[derive(Debug)]
+struct Failure {
+    /// The smallest arguments found that make the property fail
+    inputs: Vec<Input>,
+    outcome: Outcome,
+    /// How many generated calls it took to find the first failure
+    runs: usize,
+}
+
+/// Calls the property with generated arguments until it fails, and then shrinks those arguments
+/// for as long as it keeps failing.
+fn find_failure(
+    function: &PropertyFunction,
+    memory: &ExpectMemory,
+    kinds: &[InputKind],
+    runs: usize,
+    seed: u64,
+) -> Option<Failure> {
+    let mut rng = Rng(seed);
+
+    for run in 0..runs {
+        // start out small, and work up to the full range of each argument's type
+        let bits = 1 + (63 * run / runs.max(1)) as u32;
+
+        let inputs: Vec<Input> = kinds
+            .iter()
+            .map(|kind| kind.generate(&mut rng, bits))
+            .collect();
+        let outcome = function.outcome(memory, &inputs);
+
+        if outcome != Outcome::Passed {
+            let (inputs, outcome) = shrink(function, memory, inputs, outcome);
+
+            return Some(Failure {
+                inputs,
+                outcome,
+                runs: run + 1,
+            });
+        }
+    }
+
+    None
+}
+
+/// Repeatedly replaces an argument with a smaller one, as long as the property still fails.
+fn shrink(
+    function: &PropertyFunction,
+    memory: &ExpectMemory,
+    mut inputs: Vec<Input>,
+    mut outcome: Outcome,
+) -> (Vec<Input>, Outcome) {
+    let mut calls = 0;
+
+    'shrink: while calls < MAX_SHRINK_CALLS {
+        for index in 0..inputs.len() {
+            for candidate in inputs[index].smaller() {
+                let mut smaller = inputs.clone();
+                smaller[index] = candidate;
+
+                calls += 1;
+
+                let smaller_outcome = function.outcome(memory, &smaller);
+
+                if smaller_outcome != Outcome::Passed {
+                    inputs = smaller;
+                    outcome = smaller_outcome;
+
+                    continue 'shrink;
+                }
+            }
+        }
+
+        // no argument could be made any smaller
+        break;
+    }
+
+    (inputs, outcome)
+}
+
+/// The kinds of arguments a property can take
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputKind {
+    Int(IntWidth),
+    Float(FloatWidth),
+    Bool,
+}
+
+impl InputKind {
+    fn from_layout<'a>(
+        layout_interner: &impl LayoutInterner<'a>,
+        layout: InLayout<'a>,
+    ) -> Option<Self> {
+        match layout_interner.get_repr(layout) {
+            LayoutRepr::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128)) => None,
+            LayoutRepr::Builtin(Builtin::Int(width)) => Some(InputKind::Int(width)),
+            LayoutRepr::Builtin(Builtin::Float(width)) => Some(InputKind::Float(width)),
+            LayoutRepr::Builtin(Builtin::Bool) => Some(InputKind::Bool),
+            _ => None,
+        }
+    }
+
+    /// Generates an argument, which (apart from the occasional edge case) takes up to `bits`
+    /// bits.
+    fn generate(self, rng: &mut Rng, bits: u32) -> Input {
+        let edge_case = rng.below(8) == 0;
+
+        match self {
+            InputKind::Int(width) => {
+                let (min, max) = int_range(width);
+
+                let value = if edge_case {
+                    let edge_cases = [0, 1, -1, min, max];
+                    edge_cases[rng.below(edge_cases.len() as u64) as usize]
+                } else {
+                    let magnitude = (rng.next() & (u64::MAX >> (64 - bits))) as i128;
+
+                    if width.is_signed() && rng.below(2) == 0 {
+                        -magnitude
+                    } else {
+                        magnitude
+                    }
+                };
+
+                Input::Int(value.clamp(min, max), width)
+            }
+            InputKind::Float(width) => {
+                let value = if edge_case {
+                    let edge_cases = match width {
+                        FloatWidth::F32 => [0.0, 1.0, -1.0, f32::MIN as f64, f32::MAX as f64],
+                        FloatWidth::F64 => [0.0, 1.0, -1.0, f64::MIN, f64::MAX],
+                    };
+                    edge_cases[rng.below(edge_cases.len() as u64) as usize]
+                } else {
+                    let unit = rng.next() as f64 / u64::MAX as f64 * 2.0 - 1.0;
+
+                    unit * 2f64.powi(bits as i32)
+                };
+
+                Input::Float(value, width).normalized()
+            }
+            InputKind::Bool => Input::Bool(rng.below(2) == 0),
+        }
+    }
+}
+
+fn int_range(width: IntWidth) -> (i128, i128) {
+    let bits = width.stack_size() * 8;
+
+    if width.is_signed() {
+        (-(1 << (bits - 1)), (1 << (bits - 1)) - 1)
+    } else {
+        (0, (1 << bits) - 1)
+    }
+}
+
+/// An argument to pass to a property
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Input {
+    Int(i128, IntWidth),
+    Float(f64, FloatWidth),
+    Bool(bool),
+}
+
+impl Input {
+    /// The slot that the exposed property loads this argument from
+    fn to_slot(self) -> u64 {
+        match self {
+            // the low bytes of the two's complement, which is all that a narrow int loads
+            Input::Int(value, _) => value as u64,
+            Input::Float(value, FloatWidth::F32) => (value as f32).to_bits() as u64,
+            Input::Float(value, FloatWidth::F64) => value.to_bits(),
+            Input::Bool(value) => value as u64,
+        }
+    }
+
+    /// Rounds an `F32` to the nearest value it can actually hold
+    fn normalized(self) -> Self {
+        match self {
+            Input::Float(value, FloatWidth::F32) => {
+                Input::Float(value as f32 as f64, FloatWidth::F32)
+            }
+            _ => self,
+        }
+    }
+
+    /// Arguments closer to zero (or `Bool.false`) to try instead of this one, most promising
+    /// first. Each one is either closer to zero, or just as close but not negative, so
+    /// shrinking always ends.
+    fn smaller(&self) -> Vec<Input> {
+        let candidates = match *self {
+            Input::Int(value, width) => {
+                let (_, max) = int_range(width);
+
+                let mut values = vec![0, value / 2, value - value.signum()];
+
+                if value < 0 && -value <= max {
+                    values.insert(1, -value);
+                }
+
+                values
+                    .into_iter()
+                    .map(|value| Input::Int(value, width))
+                    .collect()
+            }
+            Input::Float(value, width) => {
+                let mut values = vec![0.0];
+
+                if value < 0.0 {
+                    values.push(-value);
+                }
+
+                if value.fract() != 0.0 {
+                    values.push(value.trunc());
+                }
+
+                if value.abs() >= 2.0 {
+                    values.push((value / 2.0).trunc());
+                }
+
+                if value.abs() >= 1.0 {
+                    values.push(value.trunc() - value.signum());
+                }
+
+                values
+                    .into_iter()
+                    .map(|value| Input::Float(value, width).normalized())
+                    .collect()
+            }
+            Input::Bool(_) => vec![Input::Bool(false)],
+        };
+
+        let mut smaller: Vec<Input> = Vec::new();
+
+        for candidate in candidates {
+            if candidate != *self && !smaller.contains(&candidate) {
+                smaller.push(candidate);
+            }
+        }
+
+        smaller
+    }
+
+    /// How this argument is written in a call, e.g. `(-1)`
+    fn to_roc_string(self) -> String {
+        let string = match self {
+            Input::Int(value, _) => value.to_string(),
+            Input::Float(value, FloatWidth::F32) => format!("{:?}", value as f32),
+            Input::Float(value, FloatWidth::F64) => format!("{value:?}"),
+            Input::Bool(true) => return "Bool.true".to_string(),
+            Input::Bool(false) => return "Bool.false".to_string(),
+        };
+
+        if string.starts_with('-') {
+            format!("({string})")
+        } else {
+            string
+        }
+    }
+}
+
+/// A fixed seed per property, so that its arguments are the same from one run to the next
+fn seed(name: &str) -> u64 {
+    // FNV-1a
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    // xorshift gets stuck on 0
+    hash | 1
+}
+
+/// xorshift64*, which is plenty for generating arguments
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
@@ -0,0 +1,140 @@
+//! `roc test --leak-check` builds the expects with roc_alloc, roc_realloc, and roc_dealloc
+//! routed through hooks (see `route_roc_allocators_through_hooks`), and stores the functions
+//! in this module in them. They allocate with libc, just like the default allocators do, but
+//! also remember every allocation that is still live, along with where it was made.
+//!
+//! A pure expect can't keep anything alive after it returns, so whatever is still live when
+//! it finishes has leaked: its reference count never reached zero.
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::sync::{Mutex, MutexGuard};
+
+use roc_gen_llvm::llvm::externs::{ROC_ALLOC_HOOK, ROC_DEALLOC_HOOK, ROC_REALLOC_HOOK};
+use roc_reporting::error::expect::Leak;
+
+/// The most frames of an allocation's backtrace that a leak report shows
+const MAX_FRAMES: usize = 16;
+
+static LIVE: Mutex<BTreeMap<usize, LiveAllocation>> = Mutex::new(BTreeMap::new());
+
+struct LiveAllocation {
+    size: usize,
+    alignment: u32,
+    backtrace: Backtrace,
+}
+
+fn live() -> MutexGuard<'static, BTreeMap<usize, LiveAllocation>> {
+    // A panic in a hook can't leave the map in a state worth refusing to report.
+    LIVE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Stores the hooks in a dylib built with `leak_check` enabled.
+pub(crate) fn install(lib: &libloading::Library) -> Result<(), libloading::Error> {
+    type AllocFn = unsafe extern "C" fn(usize, u32) -> *mut c_void;
+    type ReallocFn = unsafe extern "C" fn(*mut c_void, usize, usize, u32) -> *mut c_void;
+    type DeallocFn = unsafe extern "C" fn(*mut c_void, u32);
+
+    unsafe {
+        let alloc_hook = lib.get::<*mut AllocFn>(ROC_ALLOC_HOOK.as_bytes())?;
+        let realloc_hook = lib.get::<*mut ReallocFn>(ROC_REALLOC_HOOK.as_bytes())?;
+        let dealloc_hook = lib.get::<*mut DeallocFn>(ROC_DEALLOC_HOOK.as_bytes())?;
+
+        **alloc_hook = roc_alloc_hook;
+        **realloc_hook = roc_realloc_hook;
+        **dealloc_hook = roc_dealloc_hook;
+    }
+
+    Ok(())
+}
+
+/// Whether the expects in `lib` allocate through the hooks, so they can be checked for leaks
+pub(crate) fn is_enabled(lib: &libloading::Library) -> bool {
+    unsafe { lib.get::<*mut c_void>(ROC_ALLOC_HOOK.as_bytes()) }.is_ok()
+}
+
+/// Forgets every allocation made so far, so the next expect starts from nothing.
+pub(crate) fn reset() {
+    live().clear();
+}
+
+/// The allocations made since the last [`reset`] that are still live, oldest first.
+pub(crate) fn take_leaks() -> Vec<Leak> {
+    std::mem::take(&mut *live())
+        .into_values()
+        .map(|allocation| Leak {
+            size: allocation.size,
+            alignment: allocation.alignment,
+            frames: roc_frames(&allocation.backtrace),
+        })
+        .collect()
+}
+
+fn track(ptr: *mut c_void, size: usize, alignment: u32) {
+    if ptr.is_null() {
+        return;
+    }
+
+    let allocation = LiveAllocation {
+        size,
+        alignment,
+        backtrace: Backtrace::force_capture(),
+    };
+
+    live().insert(ptr as usize, allocation);
+}
+
+unsafe extern "C" fn roc_alloc_hook(size: usize, alignment: u32) -> *mut c_void {
+    let ptr = libc::malloc(size);
+
+    track(ptr, size, alignment);
+
+    ptr
+}
+
+unsafe extern "C" fn roc_realloc_hook(
+    ptr: *mut c_void,
+    new_size: usize,
+    _old_size: usize,
+    alignment: u32,
+) -> *mut c_void {
+    live().remove(&(ptr as usize));
+
+    let new_ptr = libc::realloc(ptr, new_size);
+
+    track(new_ptr, new_size, alignment);
+
+    new_ptr
+}
+
+unsafe extern "C" fn roc_dealloc_hook(ptr: *mut c_void, _alignment: u32) {
+    live().remove(&(ptr as usize));
+
+    libc::free(ptr)
+}
+
+/// The names of the frames between the allocator and the test runner, innermost first.
+/// Those are the Roc procs (and builtins) that made the allocation.
+fn roc_frames(backtrace: &Backtrace) -> Vec<String> {
+    // The frames of a backtrace are only exposed through its `Display` impl, which puts each
+    // one on a line like `  3: List_concat_5c1a`, followed by `at file:line` lines.
+    let rendered = backtrace.to_string();
+    let names = rendered.lines().filter_map(|line| {
+        let (index, name) = line.trim().split_once(": ")?;
+
+        index
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+            .then_some(name)
+    });
+
+    names
+        .skip_while(|name| !name.contains("leak_check::roc_"))
+        .skip_while(|name| {
+            name.contains("leak_check::roc_") || matches!(*name, "roc_alloc" | "roc_realloc")
+        })
+        .take_while(|name| !name.starts_with("roc_repl_expect::"))
+        .take(MAX_FRAMES)
+        .map(str::to_string)
+        .collect()
+}
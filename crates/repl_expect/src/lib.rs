@@ -15,7 +15,15 @@ use {
 #[cfg(not(windows))]
 mod app;
 #[cfg(not(windows))]
+pub mod coverage;
+#[cfg(not(windows))]
+pub mod fuzz;
+#[cfg(not(windows))]
+mod leak_check;
+#[cfg(not(windows))]
 pub mod run;
+#[cfg(not(windows))]
+pub mod snapshot;
 
 #[cfg(not(windows))]
 use app::{ExpectMemory, ExpectReplApp};
@@ -97,12 +105,18 @@ mod test {
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, Threading};
     use roc_packaging::cache::RocCacheDir;
+    use roc_repl_eval::transport::{CollectedMessages, ExpectMessageKind, ExpectTransport};
     use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
     use target_lexicon::Triple;
 
     use crate::run::expect_mono_module_to_dylib;
 
-    fn run_expect_test(source: &str, expected: &str) {
+    fn run_expects<T: ExpectTransport>(
+        source: &str,
+        leak_check: bool,
+        coverage: bool,
+        transport: &mut T,
+    ) -> crate::run::ExpectResults {
         let arena = bumpalo::Bump::new();
         let arena = &arena;
 
@@ -124,6 +138,8 @@ mod test {
             palette: DEFAULT_PALETTE,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Test,
+            reproducible: false,
+            coverage,
         };
         let loaded = match roc_load::load_and_monomorphize_from_str(
             arena,
@@ -146,9 +162,15 @@ mod test {
 
         let interns = loaded.interns.clone();
 
-        let (dy_lib, expects_by_module, layout_interner) =
-            expect_mono_module_to_dylib(arena, target, loaded, opt_level, LlvmBackendMode::CliTest)
-                .unwrap();
+        let (dy_lib, expects_by_module, layout_interner) = expect_mono_module_to_dylib(
+            arena,
+            target,
+            loaded,
+            opt_level,
+            LlvmBackendMode::CliTest,
+            leak_check,
+        )
+        .unwrap();
 
         let arena = &bumpalo::Bump::new();
         let interns = arena.alloc(interns);
@@ -163,12 +185,13 @@ mod test {
         let mut result = RocCallResult::default();
         unsafe { set_shared_buffer((shared_buffer.as_mut_ptr(), BUFFER_SIZE), &mut result) };
 
-        let mut writer = Vec::with_capacity(1024);
-
         let global_layout_interner = layout_interner.into_global();
+        let mut results = crate::run::ExpectResults::default();
         for (_, expect_funcs) in expects_by_module {
-            let (_failed, _passed) = crate::run::run_expects_with_memory(
-                &mut writer,
+            let properties = expect_funcs.properties.to_vec();
+
+            let module_results = crate::run::run_expects_with_memory(
+                transport,
                 RenderTarget::ColorTerminal,
                 arena,
                 interns,
@@ -179,8 +202,39 @@ mod test {
                 &mut memory,
             )
             .unwrap();
+
+            let _results = crate::fuzz::run_properties_with_memory(
+                transport,
+                RenderTarget::ColorTerminal,
+                arena,
+                interns,
+                &global_layout_interner,
+                &dy_lib,
+                &mut expectations,
+                &properties,
+                crate::fuzz::DEFAULT_FUZZ_RUNS,
+                &mut memory,
+            )
+            .unwrap();
+
+            results.failed += module_results.failed;
+            results.passed += module_results.passed;
+            results.skipped += module_results.skipped;
+            results.xfailed += module_results.xfailed;
         }
 
+        results
+    }
+
+    fn run_expect_test(source: &str, expected: &str) {
+        run_expect_test_with_results(source, expected);
+    }
+
+    /// Like `run_expect_test`, but also returns how many top-level expects passed, failed, etc.
+    fn run_expect_test_with_results(source: &str, expected: &str) -> crate::run::ExpectResults {
+        let mut writer = Vec::with_capacity(1024);
+        let results = run_expects(source, false, false, &mut writer);
+
         // Remove ANSI escape codes from the answer - for example:
         //
         //     Before: "42 \u{1b}[35m:\u{1b}[0m Num *"
@@ -204,11 +258,13 @@ mod test {
         } else {
             assert_eq!(expected, actual);
         }
+
+        results
     }
 
     #[test]
     fn equals_pass() {
-        run_expect_test(
+        let results = run_expect_test_with_results(
             r#"
             app "test" provides [main] to "./platform"
 
@@ -218,11 +274,19 @@ mod test {
             "#,
             "",
         );
+
+        assert_eq!(
+            results,
+            crate::run::ExpectResults {
+                passed: 1,
+                ..Default::default()
+            }
+        );
     }
 
     #[test]
     fn equals_fail() {
-        run_expect_test(
+        let results = run_expect_test_with_results(
             indoc!(
                 r#"
                 app "test" provides [main] to "./platform"
@@ -241,6 +305,308 @@ mod test {
                 "
             ),
         );
+
+        assert_eq!(
+            results,
+            crate::run::ExpectResults {
+                failed: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn skipped_fail() {
+        let results = run_expect_test_with_results(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                # roc-test: skip
+                expect 1 == 2
+                "#
+            ),
+            "",
+        );
+
+        assert_eq!(
+            results,
+            crate::run::ExpectResults {
+                skipped: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn expected_fail() {
+        let results = run_expect_test_with_results(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                # roc-test: xfail
+                expect 1 == 2
+                "#
+            ),
+            "",
+        );
+
+        assert_eq!(
+            results,
+            crate::run::ExpectResults {
+                xfailed: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn collected_messages() {
+        let mut collected = CollectedMessages::default();
+
+        run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect 1 == 2
+
+                # roc-test: xfail
+                expect 1 == 1
+                "#
+            ),
+            false,
+            false,
+            &mut collected,
+        );
+
+        let kinds: Vec<_> = collected.messages.iter().map(|m| m.kind).collect();
+
+        assert_eq!(
+            kinds,
+            [
+                ExpectMessageKind::ExpectFailed,
+                ExpectMessageKind::UnexpectedPass
+            ]
+        );
+        assert!(collected.messages[0]
+            .rendered
+            .contains("This expectation failed"));
+    }
+
+    #[test]
+    fn leak_check_passes_expects_that_free_everything() {
+        let mut collected = CollectedMessages::default();
+
+        run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    list = List.concat [1, 2] [3]
+                    str = Str.concat "a string that is too long to be stored inline" "!"
+
+                    List.len list == 3 && Str.countUtf8Bytes str == 46
+
+                expect
+                    bytes = List.repeat "x" 100 |> List.map Str.toUtf8 |> List.join
+
+                    List.len bytes == 100
+                "#
+            ),
+            true,
+            false,
+            &mut collected,
+        );
+
+        assert_eq!(collected.messages, []);
+    }
+
+    #[test]
+    fn expects_share_top_level_constants() {
+        let mut collected = CollectedMessages::default();
+
+        // the constant is evaluated once, before the first expect runs, so it is not a leak;
+        // and since it is shared, the second expect must not update it in place
+        run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                words = List.repeat "a string that is too long to be stored inline" 100
+
+                expect List.len words == 100
+
+                expect
+                    updated = List.set words 0 "short"
+
+                    List.first updated == Ok "short" && List.first words != Ok "short"
+
+                expect List.first words != Ok "short"
+                "#
+            ),
+            true,
+            false,
+            &mut collected,
+        );
+
+        assert_eq!(collected.messages, []);
+    }
+
+    #[test]
+    fn properties_report_smallest_failing_call() {
+        let mut collected = CollectedMessages::default();
+
+        run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                fuzzAddCommutes : I64, I64 -> Bool
+                fuzzAddCommutes = \a, b -> Num.addWrap a b == Num.addWrap b a
+
+                fuzzSmall : U8 -> Bool
+                fuzzSmall = \n -> n < 10
+
+                fuzzNonNegative : I32, Bool -> Bool
+                fuzzNonNegative = \n, _ -> n >= 0
+                "#
+            ),
+            false,
+            false,
+            &mut collected,
+        );
+
+        let kinds: Vec<_> = collected.messages.iter().map(|m| m.kind).collect();
+
+        assert_eq!(
+            kinds,
+            [
+                ExpectMessageKind::PropertyFailed,
+                ExpectMessageKind::PropertyFailed
+            ]
+        );
+
+        let rendered = |call: &str| {
+            collected
+                .messages
+                .iter()
+                .any(|message| message.rendered.contains(call))
+        };
+
+        assert!(rendered("fuzzSmall 10"));
+        assert!(rendered("fuzzNonNegative (-1) Bool.false"));
+    }
+
+    #[test]
+    fn coverage_finds_unreached_defs_and_branches() {
+        use roc_mono::coverage::CoverageSite;
+
+        let source = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            describe : [Red, Green, Blue] -> Str
+            describe = \color ->
+                when color is
+                    Red -> "red"
+                    Green -> "green"
+                    Blue -> "blue"
+
+            increment = \n -> n + 1
+
+            expect describe Red == "red"
+            "#
+        );
+
+        let mut collected = CollectedMessages::default();
+
+        run_expects(source, false, true, &mut collected);
+
+        assert_eq!(collected.messages, []);
+
+        let coverage = crate::coverage::take_coverage();
+
+        assert_eq!(coverage.len(), 1);
+
+        let module = &coverage[0];
+
+        // main, describe, increment, and the three branches; the expect reaches describe and Red
+        assert_eq!((module.reached, module.total), (2, 6));
+
+        let unreached_branches: Vec<_> = module
+            .unreached
+            .iter()
+            .filter(|(site, _)| matches!(site, CoverageSite::Branch { .. }))
+            .map(|(_, region)| {
+                &source[region.start().offset as usize..region.end().offset as usize]
+            })
+            .collect();
+
+        assert_eq!(unreached_branches, [r#""green""#, r#""blue""#]);
+    }
+
+    #[test]
+    fn snapshots_are_created_checked_and_updated() {
+        let source = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            expect Inspect.expectSnapshot (List.map [1, 2, 3] \n -> n * 2) "doubled"
+            "#
+        );
+
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        let snapshot = snapshot_dir.path().join("doubled.txt");
+
+        let kinds = |update: bool| {
+            crate::snapshot::configure(snapshot_dir.path().to_path_buf(), update);
+
+            let mut collected = CollectedMessages::default();
+
+            run_expects(source, false, false, &mut collected);
+
+            collected
+                .messages
+                .iter()
+                .map(|message| message.kind)
+                .collect::<Vec<_>>()
+        };
+
+        // the first run creates the snapshot
+        assert_eq!(kinds(false), []);
+        assert_eq!(std::fs::read_to_string(&snapshot).unwrap(), "[2, 4, 6]\n");
+
+        std::fs::write(&snapshot, "[2, 4, 5]\n").unwrap();
+
+        assert_eq!(
+            kinds(false),
+            [
+                ExpectMessageKind::ExpectFailed,
+                ExpectMessageKind::SnapshotFailed
+            ]
+        );
+
+        assert_eq!(kinds(true), []);
+        assert_eq!(std::fs::read_to_string(&snapshot).unwrap(), "[2, 4, 6]\n");
     }
 
     #[test]
@@ -1099,6 +1465,106 @@ mod test {
                 expected : Request
                 expected = { fieldA: Get, fieldB: "/things?id=1" }
 
+                This is where actual and expected differ:
+
+                    .fieldB: "/things?id=2" ≠ "/things?id=1"
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn nested_differences() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = { name: "Alice", tags: ["admin", "dev"] }
+                    b = { name: "Alice", tags: ["admin", "ops", "qa"] }
+
+                    a == b
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      a = { name: "Alice", tags: ["admin", "dev"] }
+                7│>      b = { name: "Alice", tags: ["admin", "ops", "qa"] }
+                8│>
+                9│>      a == b
+
+                When it failed, these variables had these values:
+
+                a : {
+                    name : Str,
+                    tags : List Str,
+                }
+                a = { name: "Alice", tags: ["admin", "dev"] }
+
+                b : {
+                    name : Str,
+                    tags : List Str,
+                }
+                b = { name: "Alice", tags: ["admin", "ops", "qa"] }
+
+                This is where a and b differ:
+
+                    .tags[1]: "dev" ≠ "ops"
+                    .tags[2]: only in b: "qa"
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn differences_in_parenthesized_comparison() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = { name: "Alice", role: "dev" }
+                    b = { name: "Alice", role: "ops" }
+
+                    (a == b)
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      a = { name: "Alice", role: "dev" }
+                7│>      b = { name: "Alice", role: "ops" }
+                8│>
+                9│>      (a == b)
+
+                When it failed, these variables had these values:
+
+                a : {
+                    name : Str,
+                    role : Str,
+                }
+                a = { name: "Alice", role: "dev" }
+
+                b : {
+                    name : Str,
+                    role : Str,
+                }
+                b = { name: "Alice", role: "ops" }
+
+                This is where a and b differ:
+
+                    .role: "dev" ≠ "ops"
                 "#
             ),
         );
@@ -1,5 +1,6 @@
 use std::{
     os::unix::process::parent_id,
+    path::Path,
     sync::{
         atomic::{AtomicBool, AtomicU32},
         Arc,
@@ -8,31 +9,44 @@ use std::{
 
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
+use inkwell::attributes::{Attribute, AttributeLoc};
 use inkwell::context::Context;
 use roc_build::link::llvm_module_to_dylib;
 use roc_can::expr::ExpectLookup;
 use roc_collections::{MutMap, MutSet, VecMap};
 use roc_error_macros::internal_error;
 use roc_gen_llvm::{
-    llvm::{build::LlvmBackendMode, externs::add_default_roc_externs},
+    llvm::{
+        build::LlvmBackendMode,
+        externs::{
+            add_default_roc_externs, route_coverage_hits_through_hook,
+            route_roc_allocators_through_hooks, route_snapshots_through_hook,
+        },
+    },
     run_roc::RocCallResult,
     run_roc_dylib,
 };
 use roc_load::{Expectations, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::{
+    expect_fixtures::RESET_EXPECT_FIXTURES_SYMBOL,
     ir::OptLevel,
-    layout::{GlobalLayoutInterner, STLayoutInterner},
+    layout::{GlobalLayoutInterner, InLayout, STLayoutInterner},
 };
-use roc_region::all::Region;
+use roc_region::all::{LineInfo, Position, Region};
+use roc_repl_eval::transport::{ExpectMessage, ExpectMessageKind, ExpectTransport};
 use roc_reporting::{error::expect::Renderer, report::RenderTarget};
 use roc_target::Target;
 use roc_types::subs::Subs;
 
+use crate::coverage;
+use crate::leak_check;
+use crate::snapshot;
+
 pub struct ExpectMemory<'a> {
-    ptr: *mut u8,
+    pub(crate) ptr: *mut u8,
     length: usize,
-    shm_name: Option<std::ffi::CString>,
+    pub(crate) shm_name: Option<std::ffi::CString>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 
@@ -107,7 +121,7 @@ impl<'a> ExpectMemory<'a> {
         }
     }
 
-    fn set_shared_buffer(&mut self, lib: &libloading::Library) {
+    pub(crate) fn set_shared_buffer(&mut self, lib: &libloading::Library) {
         let set_shared_buffer = run_roc_dylib!(lib, "set_shared_buffer", (*mut u8, usize), ());
         let mut result = RocCallResult::default();
         unsafe { set_shared_buffer((self.ptr, self.length), &mut result) };
@@ -125,8 +139,8 @@ impl<'a> ExpectMemory<'a> {
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn run_inline_expects<'a, W: std::io::Write>(
-    writer: &mut W,
+pub fn run_inline_expects<'a, T: ExpectTransport>(
+    transport: &mut T,
     render_target: RenderTarget,
     arena: &'a Bump,
     interns: &'a Interns,
@@ -134,12 +148,12 @@ pub fn run_inline_expects<'a, W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
+) -> std::io::Result<ExpectResults> {
     let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
     let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
 
     run_expects_with_memory(
-        writer,
+        transport,
         render_target,
         arena,
         interns,
@@ -152,8 +166,8 @@ pub fn run_inline_expects<'a, W: std::io::Write>(
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn run_toplevel_expects<'a, W: std::io::Write>(
-    writer: &mut W,
+pub fn run_toplevel_expects<'a, T: ExpectTransport>(
+    transport: &mut T,
     render_target: RenderTarget,
     arena: &'a Bump,
     interns: &'a Interns,
@@ -161,12 +175,12 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
+) -> std::io::Result<ExpectResults> {
     let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
     let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
 
     run_expects_with_memory(
-        writer,
+        transport,
         render_target,
         arena,
         interns,
@@ -178,9 +192,265 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
     )
 }
 
+/// Like [`run_toplevel_expects`], but runs every expect in its own forked process, with
+/// at most `jobs` of them running at once.
+///
+/// The compiled expects all report to one global buffer in the dylib (see
+/// `set_shared_buffer`), so they cannot share a process across threads; a process per
+/// expect also means each one gets its own copy of the arena, and that an expect which
+/// crashes (e.g. with a segfault) only takes down its own process. The messages of each
+/// expect are buffered and sent in source order, so they do not depend on scheduling.
+///
+/// Before forking the process for an expect, this process evaluates the top-level constants
+/// that the expect uses (see [`ToplevelExpect::setup_name`]), so each of them is computed once
+/// and then shared by all the expects. A constant that crashes with a roc panic is left for each
+/// expect to evaluate (and report) on its own, but one that segfaults takes this process down.
+#[allow(clippy::too_many_arguments)]
+pub fn run_toplevel_expects_parallel<'a, T: ExpectTransport>(
+    transport: &mut T,
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &GlobalLayoutInterner<'a>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    expects: ExpectFunctions<'_>,
+    jobs: usize,
+) -> std::io::Result<ExpectResults> {
+    use std::io::{Seek, Write};
+
+    // otherwise every child would write out its copy of whatever is still buffered
+    std::io::stdout().flush()?;
+
+    let mut results = ExpectResults::default();
+
+    // Same order as run_expects_with_memory: effectful expects first
+    let all: Vec<(bool, ToplevelExpect)> = expects
+        .fx
+        .iter()
+        .map(|expect| (true, *expect))
+        .chain(expects.pure.iter().map(|expect| (false, *expect)))
+        .filter(|(_, expect)| {
+            let skip = expect.marker == ExpectMarker::Skip;
+
+            if skip {
+                results.skipped += 1;
+            }
+
+            !skip
+        })
+        .collect();
+
+    let mut outputs: Vec<IsolatedEvents> = all.iter().map(|_| Default::default()).collect();
+    let mut running: MutMap<libc::pid_t, (usize, std::fs::File)> = MutMap::default();
+    let mut next = 0;
+
+    // for the inline expects that run while evaluating the top-level constants
+    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
+    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+    memory.set_shared_buffer(lib);
+
+    while next < all.len() || !running.is_empty() {
+        while running.len() < jobs.max(1) && next < all.len() {
+            let (is_fx, expect) = all[next];
+            let output = tempfile::tempfile()?;
+
+            set_up_fixtures(lib, &mut memory, expect);
+
+            match unsafe { libc::fork() } {
+                0 => {
+                    // we are the child
+                    let code = run_isolated_expect(
+                        render_target,
+                        arena,
+                        interns,
+                        layout_interner,
+                        lib,
+                        expectations,
+                        is_fx,
+                        expect,
+                        output,
+                    );
+
+                    std::process::exit(code)
+                }
+                -1 => {
+                    let error = std::io::Error::last_os_error();
+
+                    // don't leave the workers we already started behind as zombies
+                    reap_all(&running);
+
+                    if let Some(shm_name) = memory.shm_name.as_ref() {
+                        unsafe { libc::shm_unlink(shm_name.as_ptr()) };
+                    }
+
+                    return Err(error);
+                }
+                pid => {
+                    running.insert(pid, (next, output));
+                }
+            }
+
+            next += 1;
+        }
+
+        let (pid, status) = wait_for_any_of(&running)?;
+        let (index, mut output) = running.remove(&pid).unwrap();
+
+        // whatever the expect itself sent comes before any crash report
+        output.rewind()?;
+        outputs[index] = IsolatedEvents::read_from(&mut std::io::BufReader::new(output))?;
+
+        let exit_code = libc::WIFEXITED(status).then(|| libc::WEXITSTATUS(status));
+
+        match exit_code {
+            Some(ISOLATED_PASSED) => results.passed += 1,
+            Some(ISOLATED_FAILED) => results.failed += 1,
+            Some(ISOLATED_XFAILED) => results.xfailed += 1,
+            _ => {
+                results.failed += 1;
+
+                let message = if libc::WIFSIGNALED(status) {
+                    format!(
+                        "The process running it was killed by signal {}.",
+                        libc::WTERMSIG(status)
+                    )
+                } else {
+                    format!(
+                        "The process running it exited with code {}.",
+                        libc::WEXITSTATUS(status)
+                    )
+                };
+
+                let (_, expect) = all[index];
+                let module_id = expect.symbol.module_id();
+                let data = expectations.get_mut(&module_id).unwrap();
+                let filename = data.path.to_owned();
+                let source = std::fs::read_to_string(&data.path).unwrap();
+
+                let renderer =
+                    Renderer::new(arena, interns, render_target, module_id, filename, &source);
+
+                let crash = &mut outputs[index];
+                send_report(
+                    crash,
+                    &renderer,
+                    ExpectMessageKind::ExpectPanicked,
+                    expect.region,
+                    |buf| renderer.render_panic(buf, &message, expect.region),
+                )?;
+                crash.end_of_expect()?;
+            }
+        }
+    }
+
+    if let Some(shm_name) = memory.shm_name.as_ref() {
+        unsafe { libc::shm_unlink(shm_name.as_ptr()) };
+    }
+
+    for output in outputs {
+        output.replay(transport)?;
+    }
+
+    Ok(results)
+}
+
+/// Waits until one of the given child processes exits, and returns its pid and status.
+/// Unlike `waitpid(-1, ..)`, this never reaps a child that isn't ours, like one the host
+/// spawned.
+fn wait_for_any_of<V>(
+    children: &MutMap<libc::pid_t, V>,
+) -> std::io::Result<(libc::pid_t, libc::c_int)> {
+    loop {
+        for &pid in children.keys() {
+            let mut status = 0;
+
+            match unsafe { libc::waitpid(pid, &mut status, libc::WNOHANG) } {
+                0 => {}
+                -1 => return Err(std::io::Error::last_os_error()),
+                _ => return Ok((pid, status)),
+            }
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+}
+
+/// Blocks until every one of these children has exited.
+fn reap_all<V>(children: &MutMap<libc::pid_t, V>) {
+    for &pid in children.keys() {
+        let mut status = 0;
+
+        unsafe { libc::waitpid(pid, &mut status, 0) };
+    }
+}
+
+const ISOLATED_PASSED: i32 = 0;
+const ISOLATED_FAILED: i32 = 1;
+const ISOLATED_XFAILED: i32 = 3;
+
+/// Runs in a forked child: runs a single expect, writes the messages it sent to `output`,
+/// and returns the exit code that tells the parent how it went.
+#[allow(clippy::too_many_arguments)]
+fn run_isolated_expect<'a>(
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &GlobalLayoutInterner<'a>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    is_fx: bool,
+    expect: ToplevelExpect<'_>,
+    output: std::fs::File,
+) -> i32 {
+    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
+    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+
+    let single = BumpVec::from_iter_in([expect], arena);
+    let expects = if is_fx {
+        ExpectFunctions {
+            pure: BumpVec::new_in(arena),
+            fx: single,
+            properties: BumpVec::new_in(arena),
+        }
+    } else {
+        ExpectFunctions {
+            pure: single,
+            fx: BumpVec::new_in(arena),
+            properties: BumpVec::new_in(arena),
+        }
+    };
+
+    let mut events = IsolatedEvents::default();
+    let result = run_expects_with_memory(
+        &mut events,
+        render_target,
+        arena,
+        interns,
+        layout_interner,
+        lib,
+        expectations,
+        expects,
+        &mut memory,
+    );
+
+    if let Some(shm_name) = memory.shm_name.as_ref() {
+        unsafe { libc::shm_unlink(shm_name.as_ptr()) };
+    }
+
+    let written = events.write_to(&mut std::io::BufWriter::new(output));
+
+    match result.and_then(|results| written.map(|()| results)) {
+        Ok(results) if results.xfailed > 0 => ISOLATED_XFAILED,
+        Ok(results) if results.failed > 0 => ISOLATED_FAILED,
+        Ok(_) => ISOLATED_PASSED,
+        Err(_) => ISOLATED_FAILED,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
-    writer: &mut W,
+pub(crate) fn run_expects_with_memory<'a, T: ExpectTransport>(
+    transport: &mut T,
     render_target: RenderTarget,
     arena: &'a Bump,
     interns: &'a Interns,
@@ -189,13 +459,24 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
     memory: &mut ExpectMemory,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+) -> std::io::Result<ExpectResults> {
+    let mut results = ExpectResults::default();
 
     for expect in expects.fx {
+        if expect.marker == ExpectMarker::Skip {
+            results.skipped += 1;
+            continue;
+        }
+
+        // The failure of an expected failure is not worth showing
+        let mut sink = std::io::sink();
+        let failure_transport: &mut dyn ExpectTransport = match expect.marker {
+            ExpectMarker::ExpectedFailure => &mut sink,
+            _ => &mut *transport,
+        };
+
         let result = run_expect_fx(
-            writer,
+            failure_transport,
             render_target,
             arena,
             interns,
@@ -206,17 +487,36 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
             expect,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        results.record(
+            transport,
+            render_target,
+            arena,
+            interns,
+            expectations,
+            expect,
+            result,
+        )?;
     }
 
     memory.set_shared_buffer(lib);
 
     for expect in expects.pure {
+        if expect.marker == ExpectMarker::Skip {
+            results.skipped += 1;
+            continue;
+        }
+
+        // outside of run_expect_pure, so the memoized constants don't count as leaks
+        set_up_fixtures(lib, memory, expect);
+
+        let mut sink = std::io::sink();
+        let failure_transport: &mut dyn ExpectTransport = match expect.marker {
+            ExpectMarker::ExpectedFailure => &mut sink,
+            _ => &mut *transport,
+        };
+
         let result = run_expect_pure(
-            writer,
+            failure_transport,
             render_target,
             arena,
             interns,
@@ -227,18 +527,103 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
             expect,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
+        results.record(
+            transport,
+            render_target,
+            arena,
+            interns,
+            expectations,
+            expect,
+            result,
+        )?;
+    }
+
+    Ok(results)
+}
+
+/// Evaluates the top-level constants that `expect` uses, unless an earlier expect already did.
+/// If that crashes or fails an inline `expect`, all the constants are forgotten again, so that
+/// each expect that uses them evaluates them itself, and reports what went wrong.
+fn set_up_fixtures(
+    lib: &libloading::Library,
+    memory: &mut ExpectMemory,
+    expect: ToplevelExpect<'_>,
+) {
+    use roc_gen_llvm::try_run_jit_function;
+
+    let sequence = ExpectSequence::new(memory.ptr.cast());
+
+    let result: Result<(), (String, _)> =
+        try_run_jit_function!(lib, expect.setup_name, (), |v: ()| v);
+
+    if result.is_err() || sequence.count_failures() > 0 {
+        let reset: libloading::Symbol<unsafe extern "C" fn()> = unsafe {
+            lib.get(RESET_EXPECT_FIXTURES_SYMBOL.as_bytes())
         }
+        .unwrap_or_else(|_| internal_error!("no {RESET_EXPECT_FIXTURES_SYMBOL} in the dylib"));
+
+        unsafe { reset() };
     }
+}
 
-    Ok((failed, passed))
+/// The outcome of running a module's top-level expects.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExpectResults {
+    pub failed: usize,
+    pub passed: usize,
+    /// Marked `# roc-test: skip`, so never run
+    pub skipped: usize,
+    /// Marked `# roc-test: xfail`, and failed as expected
+    pub xfailed: usize,
+}
+
+impl ExpectResults {
+    /// An expected failure that passes counts as a failure, so that the marker gets
+    /// removed once whatever it was waiting on has been fixed.
+    #[allow(clippy::too_many_arguments)]
+    fn record<'a, T: ExpectTransport>(
+        &mut self,
+        transport: &mut T,
+        render_target: RenderTarget,
+        arena: &'a Bump,
+        interns: &'a Interns,
+        expectations: &mut VecMap<ModuleId, Expectations>,
+        expect: ToplevelExpect<'_>,
+        succeeded: bool,
+    ) -> std::io::Result<()> {
+        match (expect.marker, succeeded) {
+            (ExpectMarker::ExpectedFailure, false) => self.xfailed += 1,
+            (ExpectMarker::ExpectedFailure, true) => {
+                let module_id = expect.symbol.module_id();
+                let data = expectations.get_mut(&module_id).unwrap();
+                let filename = data.path.to_owned();
+                let source = std::fs::read_to_string(&data.path).unwrap();
+
+                let renderer =
+                    Renderer::new(arena, interns, render_target, module_id, filename, &source);
+
+                send_report(
+                    transport,
+                    &renderer,
+                    ExpectMessageKind::UnexpectedPass,
+                    expect.region,
+                    |buf| renderer.render_unexpected_pass(buf, expect.region),
+                )?;
+                transport.end_of_expect()?;
+
+                self.failed += 1;
+            }
+            (_, true) => self.passed += 1,
+            (_, false) => self.failed += 1,
+        }
+
+        Ok(())
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_expect_pure<'a, W: std::io::Write>(
-    writer: &mut W,
+fn run_expect_pure<'a, T: ExpectTransport + ?Sized>(
+    transport: &mut T,
     render_target: RenderTarget,
     arena: &'a Bump,
     interns: &'a Interns,
@@ -252,11 +637,27 @@ fn run_expect_pure<'a, W: std::io::Write>(
 
     let sequence = ExpectSequence::new(shared_memory.ptr.cast());
 
+    let check_leaks = leak_check::is_enabled(lib);
+
+    if check_leaks {
+        leak_check::reset();
+    }
+
+    snapshot::reset();
+
     let result: Result<(), (String, _)> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
 
     let shared_memory_ptr: *const u8 = shared_memory.ptr.cast();
 
-    if result.is_err() || sequence.count_failures() > 0 {
+    let leaks = if check_leaks {
+        leak_check::take_leaks()
+    } else {
+        Vec::new()
+    };
+
+    let snapshot_failures = snapshot::take_failures();
+
+    if result.is_err() || sequence.count_failures() > 0 || !leaks.is_empty() {
         let module_id = expect.symbol.module_id();
         let data = expectations.get_mut(&module_id).unwrap();
 
@@ -267,13 +668,28 @@ fn run_expect_pure<'a, W: std::io::Write>(
         let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
         if let Err((roc_panic_message, _roc_panic_tag)) = result {
-            renderer.render_panic(writer, &roc_panic_message, expect.region)?;
+            send_report(
+                transport,
+                &renderer,
+                ExpectMessageKind::ExpectPanicked,
+                expect.region,
+                |buf| renderer.render_panic(buf, &roc_panic_message, expect.region),
+            )?;
+        } else if sequence.count_failures() == 0 {
+            // only an expect that otherwise passed is reported for what it leaked
+            send_report(
+                transport,
+                &renderer,
+                ExpectMessageKind::Leaked,
+                expect.region,
+                |buf| renderer.render_leaks(buf, &leaks, expect.region),
+            )?;
         } else {
             let mut offset = ExpectSequence::START_OFFSET;
 
             for _ in 0..sequence.count_failures() {
                 offset = render_expect_failure(
-                    writer,
+                    transport,
                     &renderer,
                     arena,
                     Some(expect),
@@ -284,9 +700,19 @@ fn run_expect_pure<'a, W: std::io::Write>(
                     offset,
                 )?;
             }
+
+            for failure in snapshot_failures.iter() {
+                send_report(
+                    transport,
+                    &renderer,
+                    ExpectMessageKind::SnapshotFailed,
+                    expect.region,
+                    |buf| renderer.render_snapshot_failure(buf, failure, expect.region),
+                )?;
+            }
         }
 
-        writeln!(writer)?;
+        transport.end_of_expect()?;
 
         Ok(false)
     } else {
@@ -295,8 +721,8 @@ fn run_expect_pure<'a, W: std::io::Write>(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_expect_fx<'a, W: std::io::Write>(
-    writer: &mut W,
+fn run_expect_fx<'a, T: ExpectTransport + ?Sized>(
+    transport: &mut T,
     render_target: RenderTarget,
     arena: &'a Bump,
     interns: &'a Interns,
@@ -374,7 +800,7 @@ fn run_expect_fx<'a, W: std::io::Write>(
                         );
 
                         render_expect_failure(
-                            writer,
+                            transport,
                             &renderer,
                             arena,
                             None,
@@ -396,7 +822,7 @@ fn run_expect_fx<'a, W: std::io::Write>(
 }
 
 pub fn render_expects_in_memory<'a>(
-    writer: &mut impl std::io::Write,
+    transport: &mut impl ExpectTransport,
     arena: &'a Bump,
     expectations: &mut VecMap<ModuleId, Expectations>,
     interns: &'a Interns,
@@ -422,7 +848,7 @@ pub fn render_expects_in_memory<'a>(
     );
 
     render_expect_failure(
-        writer,
+        transport,
         &renderer,
         arena,
         None,
@@ -456,8 +882,8 @@ fn split_expect_lookups(subs: &Subs, lookups: &[ExpectLookup]) -> Vec<Symbol> {
 }
 
 #[allow(clippy::too_many_arguments)]
-fn render_expect_failure<'a>(
-    writer: &mut impl std::io::Write,
+pub(crate) fn render_expect_failure<'a, T: ExpectTransport + ?Sized>(
+    transport: &mut T,
     renderer: &Renderer,
     arena: &'a Bump,
     expect: Option<ToplevelExpect>,
@@ -484,6 +910,7 @@ fn render_expect_failure<'a>(
     };
 
     let symbols = split_expect_lookups(&data.subs, current);
+    let compared = data.comparisons.get(&failure_region).copied();
 
     let (offset, expressions, variables) = crate::get_values(
         target,
@@ -496,31 +923,194 @@ fn render_expect_failure<'a>(
         symbols.len(),
     );
 
-    renderer.render_failure(
-        writer,
-        &mut data.subs,
-        &symbols,
-        &variables,
-        &expressions,
-        expect_region,
+    send_report(
+        transport,
+        renderer,
+        ExpectMessageKind::ExpectFailed,
         failure_region,
+        |buf| {
+            renderer.render_failure(
+                buf,
+                &mut data.subs,
+                &symbols,
+                &variables,
+                &expressions,
+                expect_region,
+                failure_region,
+                compared,
+            )
+        },
     )?;
 
     Ok(offset)
 }
 
-struct ExpectSequence {
+/// Renders a report into a buffer, and sends that to the transport.
+pub(crate) fn send_report<T: ExpectTransport + ?Sized>(
+    transport: &mut T,
+    renderer: &Renderer,
+    kind: ExpectMessageKind,
+    region: Region,
+    render: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    render(&mut buf)?;
+
+    transport.send(ExpectMessage {
+        kind,
+        filename: renderer.filename().to_path_buf(),
+        region,
+        rendered: String::from_utf8_lossy(&buf).into_owned(),
+    })
+}
+
+/// What an expect running in a forked process sent to its transport. The child writes
+/// these to a file, from which the parent passes them on to the real transport.
+#[derive(Default)]
+struct IsolatedEvents {
+    events: Vec<Option<ExpectMessage>>,
+}
+
+impl ExpectTransport for IsolatedEvents {
+    fn send(&mut self, message: ExpectMessage) -> std::io::Result<()> {
+        self.events.push(Some(message));
+
+        Ok(())
+    }
+
+    fn end_of_expect(&mut self) -> std::io::Result<()> {
+        // `None` marks the end of an expect
+        self.events.push(None);
+
+        Ok(())
+    }
+}
+
+impl IsolatedEvents {
+    fn replay<T: ExpectTransport>(self, transport: &mut T) -> std::io::Result<()> {
+        for event in self.events {
+            match event {
+                Some(message) => transport.send(message)?,
+                None => transport.end_of_expect()?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        use std::os::unix::ffi::OsStrExt;
+
+        fn write_bytes(writer: &mut impl std::io::Write, bytes: &[u8]) -> std::io::Result<()> {
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            writer.write_all(bytes)
+        }
+
+        for event in self.events.iter() {
+            match event {
+                None => writer.write_all(&[0])?,
+                Some(message) => {
+                    let kind = match message.kind {
+                        ExpectMessageKind::ExpectFailed => 1,
+                        ExpectMessageKind::ExpectPanicked => 2,
+                        ExpectMessageKind::UnexpectedPass => 3,
+                        ExpectMessageKind::Leaked => 4,
+                        ExpectMessageKind::PropertyFailed => 5,
+                        ExpectMessageKind::SnapshotFailed => 6,
+                    };
+
+                    writer.write_all(&[kind])?;
+                    writer.write_all(&message.region.start().offset.to_le_bytes())?;
+                    writer.write_all(&message.region.end().offset.to_le_bytes())?;
+                    write_bytes(writer, message.filename.as_os_str().as_bytes())?;
+                    write_bytes(writer, message.rendered.as_bytes())?;
+                }
+            }
+        }
+
+        writer.flush()
+    }
+
+    fn read_from(reader: &mut impl std::io::Read) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        use std::os::unix::ffi::OsStringExt;
+
+        fn read_u32(reader: &mut impl std::io::Read) -> std::io::Result<u32> {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+
+            Ok(u32::from_le_bytes(bytes))
+        }
+
+        fn read_bytes(reader: &mut impl std::io::Read) -> std::io::Result<Vec<u8>> {
+            let mut len = [0; 8];
+            reader.read_exact(&mut len)?;
+
+            let mut bytes = vec![0; u64::from_le_bytes(len) as usize];
+            reader.read_exact(&mut bytes)?;
+
+            Ok(bytes)
+        }
+
+        let mut events = Vec::new();
+
+        loop {
+            let mut tag = [0];
+
+            // a child that crashed may not have written anything
+            if reader.read(&mut tag)? == 0 {
+                break;
+            }
+
+            let kind = match tag[0] {
+                0 => {
+                    events.push(None);
+                    continue;
+                }
+                1 => ExpectMessageKind::ExpectFailed,
+                2 => ExpectMessageKind::ExpectPanicked,
+                3 => ExpectMessageKind::UnexpectedPass,
+                4 => ExpectMessageKind::Leaked,
+                5 => ExpectMessageKind::PropertyFailed,
+                6 => ExpectMessageKind::SnapshotFailed,
+                other => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("unknown expect event {other}"),
+                    ))
+                }
+            };
+
+            let start = Position::new(read_u32(reader)?);
+            let end = Position::new(read_u32(reader)?);
+            let filename = std::ffi::OsString::from_vec(read_bytes(reader)?).into();
+            let rendered = String::from_utf8(read_bytes(reader)?)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+            events.push(Some(ExpectMessage {
+                kind,
+                filename,
+                region: Region::new(start, end),
+                rendered,
+            }));
+        }
+
+        Ok(Self { events })
+    }
+}
+
+pub(crate) struct ExpectSequence {
     ptr: *const u8,
 }
 
 impl ExpectSequence {
-    const START_OFFSET: usize = 8 + 8 + 8;
+    pub(crate) const START_OFFSET: usize = 8 + 8 + 8;
 
     const COUNT_INDEX: usize = 0;
     const OFFSET_INDEX: usize = 1;
     const LOCK_INDEX: usize = 2;
 
-    fn new(ptr: *mut u8) -> Self {
+    pub(crate) fn new(ptr: *mut u8) -> Self {
         unsafe {
             let ptr = ptr as *mut usize;
             std::ptr::write_unaligned(ptr.add(Self::COUNT_INDEX), 0);
@@ -533,7 +1123,7 @@ impl ExpectSequence {
         }
     }
 
-    fn count_failures(&self) -> usize {
+    pub(crate) fn count_failures(&self) -> usize {
         unsafe { *(self.ptr as *const usize).add(Self::COUNT_INDEX) }
     }
 
@@ -600,14 +1190,183 @@ impl ExpectFrame {
 #[derive(Debug, Clone, Copy)]
 pub struct ToplevelExpect<'a> {
     pub name: &'a str,
+    /// Evaluates the top-level constants this expect uses, which are memoized, so that expects
+    /// running after it (or in processes forked after it) can share them
+    pub setup_name: &'a str,
     pub symbol: Symbol,
     pub region: Region,
+    pub marker: ExpectMarker,
+    /// The def this expect follows in the source, which is what `roc test --filter` matches
+    pub def_name: Option<Symbol>,
+}
+
+/// How a top-level expect is marked in its source, using a comment directly above it:
+///
+/// ```roc
+/// # roc-test: skip
+/// expect slowFunction 1000 == 42
+///
+/// # roc-test: xfail
+/// expect Str.trim " a " == "a "
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectMarker {
+    #[default]
+    None,
+    Skip,
+    ExpectedFailure,
+}
+
+impl ExpectMarker {
+    /// The region of a top-level expect may already include the comments that precede
+    /// it, so look both at the comment lines directly above the region and at the ones
+    /// it starts with.
+    pub fn from_source(source: &str, region: Region) -> Self {
+        let start = region.start().offset as usize;
+        let end = region.end().offset as usize;
+
+        // A region that is out of bounds, or not on char boundaries, can't have a marker
+        let (Some(before), Some(within)) = (source.get(..start), source.get(start..end)) else {
+            return ExpectMarker::default();
+        };
+
+        let above = before
+            .lines()
+            .rev()
+            .map(str::trim)
+            .take_while(|line| line.starts_with('#'));
+        let leading = within
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.starts_with('#'));
+
+        above
+            .chain(leading)
+            .find_map(Self::from_comment)
+            .unwrap_or_default()
+    }
+
+    fn from_comment(line: &str) -> Option<Self> {
+        let directive = line
+            .trim_start_matches('#')
+            .trim()
+            .strip_prefix("roc-test:")?;
+
+        match directive.trim() {
+            "skip" => Some(ExpectMarker::Skip),
+            "xfail" => Some(ExpectMarker::ExpectedFailure),
+            _ => None,
+        }
+    }
+}
+
+/// A top-level function like `fuzzAddCommutes : I64, I64 -> Bool`, which `roc test --fuzz` calls
+/// with generated arguments (see [crate::fuzz]).
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzProperty<'a> {
+    pub name: &'a str,
+    pub symbol: Symbol,
+    pub region: Region,
+    pub marker: ExpectMarker,
+    pub arguments: &'a [InLayout<'a>],
 }
 
 #[derive(Debug)]
 pub struct ExpectFunctions<'a> {
     pub pure: BumpVec<'a, ToplevelExpect<'a>>,
     pub fx: BumpVec<'a, ToplevelExpect<'a>>,
+    /// Only run by `roc test --fuzz`
+    pub properties: BumpVec<'a, FuzzProperty<'a>>,
+}
+
+impl<'a> ExpectFunctions<'a> {
+    pub fn len(&self) -> usize {
+        self.pure.len() + self.fx.len() + self.properties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&ToplevelExpect<'a>) -> bool) {
+        self.pure.retain(|expect| f(expect));
+        self.fx.retain(|expect| f(expect));
+    }
+}
+
+/// Selects which top-level expects `roc test` runs. An empty filter selects all of them.
+#[derive(Debug, Default, Clone)]
+pub struct ExpectFilter {
+    /// Only expects that follow a def whose name contains this
+    pub name: Option<String>,
+    /// Only expects in these modules, given by module name (e.g. `Parser`) or file name
+    /// (e.g. `Parser.roc`)
+    pub modules: Vec<String>,
+    /// Only the expect on this (1-based) line
+    pub line: Option<u32>,
+}
+
+impl ExpectFilter {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.modules.is_empty() && self.line.is_none()
+    }
+
+    /// Removes the expects that this filter does not select from one module's expects.
+    pub fn apply(
+        &self,
+        interns: &Interns,
+        module_id: ModuleId,
+        path: &Path,
+        source: &str,
+        expects: &mut ExpectFunctions<'_>,
+    ) {
+        if self.is_empty() {
+            return;
+        }
+
+        if !self.modules.is_empty() {
+            let module_name = interns.module_name(module_id).as_str();
+            let file_name = path.file_name().and_then(|name| name.to_str());
+
+            let selected = self
+                .modules
+                .iter()
+                .any(|module| module == module_name || Some(module.as_str()) == file_name);
+
+            if !selected {
+                expects.pure.clear();
+                expects.fx.clear();
+                expects.properties.clear();
+
+                return;
+            }
+        }
+
+        let line_info = LineInfo::new(source);
+
+        let line_matches = |region| match self.line {
+            None => true,
+            Some(line) => {
+                let region = line_info.convert_region(region);
+
+                // line_info is 0-based
+                (region.start.line + 1..=region.end.line + 1).contains(&line)
+            }
+        };
+
+        let name_matches = |def_name: Option<Symbol>| match (&self.name, def_name) {
+            (None, _) => true,
+            (Some(name), Some(def_name)) => def_name.as_str(interns).contains(name.as_str()),
+            (Some(_), None) => false,
+        };
+
+        expects.retain(|expect| name_matches(expect.def_name) && line_matches(expect.region));
+
+        // a property is its own def
+        expects.properties.retain(|property| {
+            name_matches(Some(property.symbol)) && line_matches(property.region)
+        });
+    }
 }
 
 pub fn expect_mono_module_to_dylib<'a>(
@@ -616,6 +1375,7 @@ pub fn expect_mono_module_to_dylib<'a>(
     loaded: MonomorphizedModule<'a>,
     opt_level: OptLevel,
     mode: LlvmBackendMode,
+    leak_check: bool,
 ) -> Result<
     (
         libloading::Library,
@@ -629,6 +1389,7 @@ pub fn expect_mono_module_to_dylib<'a>(
         procedures,
         interns,
         layout_interner,
+        sources,
         ..
     } = loaded;
 
@@ -655,6 +1416,10 @@ pub fn expect_mono_module_to_dylib<'a>(
         module,
         target,
         mode,
+        debug_locations: Default::default(),
+        inline_threshold: 0,
+        alias_analysis: Default::default(),
+        stack_checks: true,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
     };
@@ -663,6 +1428,10 @@ pub fn expect_mono_module_to_dylib<'a>(
     // platform to provide them.
     add_default_roc_externs(&env);
 
+    if leak_check {
+        route_roc_allocators_through_hooks(&env);
+    }
+
     let expects_symbols = toplevel_expects
         .iter()
         .map(|(module_id, expects)| {
@@ -680,28 +1449,56 @@ pub fn expect_mono_module_to_dylib<'a>(
         })
         .collect();
 
-    let expect_names = roc_gen_llvm::llvm::build::build_procedures_expose_expects(
+    let properties_symbols = toplevel_expects
+        .iter()
+        .map(|(module_id, expects)| {
+            (
+                *module_id,
+                bumpalo::collections::Vec::from_iter_in(
+                    expects.properties.keys().copied(),
+                    env.arena,
+                ),
+            )
+        })
+        .collect();
+
+    let (expect_names, property_names) = roc_gen_llvm::llvm::build::build_procedures_expose_expects(
         &env,
         &layout_interner,
         opt_level,
         expects_symbols,
+        properties_symbols,
         procedures,
     );
 
+    route_coverage_hits_through_hook(&env);
+    route_snapshots_through_hook(&env);
+
     let mut modules_expects: MutMap<ModuleId, ExpectFunctions> = MutMap::default();
+    let mut coverage_sites = Vec::new();
+
+    for (module_id, mut expects) in toplevel_expects.into_iter() {
+        coverage_sites.push((module_id, std::mem::take(&mut expects.coverage_sites)));
 
-    for (module_id, expects) in toplevel_expects.into_iter() {
         let expect_names = expect_names.get(&module_id).unwrap();
+        let def_names = &expects.def_names;
+        let source = sources
+            .get(&module_id)
+            .map(|(_, source)| source.as_ref())
+            .unwrap_or_default();
 
         let expects_fx = bumpalo::collections::Vec::from_iter_in(
             expects
                 .fx
                 .into_iter()
                 .zip(expect_names.iter().skip(expects.pure.len()))
-                .map(|((symbol, region), name)| ToplevelExpect {
+                .map(|((symbol, region), exposed)| ToplevelExpect {
                     symbol,
                     region,
-                    name,
+                    name: exposed.name,
+                    setup_name: exposed.setup_name,
+                    marker: ExpectMarker::from_source(source, region),
+                    def_name: def_names.get(&symbol).copied(),
                 }),
             env.arena,
         );
@@ -709,23 +1506,55 @@ pub fn expect_mono_module_to_dylib<'a>(
         let expects_pure =
             bumpalo::collections::Vec::from_iter_in(
                 expects.pure.into_iter().zip(expect_names.iter()).map(
-                    |((symbol, region), name)| ToplevelExpect {
+                    |((symbol, region), exposed)| ToplevelExpect {
                         symbol,
                         region,
-                        name,
+                        name: exposed.name,
+                        setup_name: exposed.setup_name,
+                        marker: ExpectMarker::from_source(source, region),
+                        def_name: def_names.get(&symbol).copied(),
                     },
                 ),
                 env.arena,
             );
 
+        let properties = bumpalo::collections::Vec::from_iter_in(
+            expects
+                .properties
+                .into_iter()
+                .zip(property_names.get(&module_id).unwrap().iter())
+                .map(|((symbol, region), exposed)| FuzzProperty {
+                    name: exposed.name,
+                    symbol,
+                    region,
+                    marker: ExpectMarker::from_source(source, region),
+                    arguments: exposed.arguments,
+                }),
+            env.arena,
+        );
+
         let expect_funs = ExpectFunctions {
             pure: expects_pure,
             fx: expects_fx,
+            properties,
         };
 
         modules_expects.insert(module_id, expect_funs);
     }
 
+    if leak_check {
+        // So a backtrace taken in an allocator hook can be unwound through every proc and
+        // builtin, even the ones that can't unwind themselves
+        let uwtable_attr =
+            context.create_enum_attribute(Attribute::get_named_enum_kind_id("uwtable"), 2);
+
+        for function in env.module.get_functions() {
+            if function.count_basic_blocks() > 0 {
+                function.add_attribute(AttributeLoc::Function, uwtable_attr);
+            }
+        }
+    }
+
     env.dibuilder.finalize();
 
     // Uncomment this to see the module's un-optimized LLVM instruction output:
@@ -750,6 +1579,14 @@ pub fn expect_mono_module_to_dylib<'a>(
         env.module.print_to_file(path).unwrap();
     }
 
-    llvm_module_to_dylib(env.module, target, opt_level)
-        .map(|dy_lib| (dy_lib, modules_expects, layout_interner))
+    let dy_lib = llvm_module_to_dylib(env.module, target, opt_level)?;
+
+    if leak_check {
+        leak_check::install(&dy_lib)?;
+    }
+
+    coverage::install(&dy_lib, coverage_sites)?;
+    snapshot::install(&dy_lib);
+
+    Ok((dy_lib, modules_expects, layout_interner))
 }
@@ -0,0 +1,139 @@
+//! `Inspect.expectSnapshot value name` compares `Inspect.toStr value` against the snapshot
+//! called `name`: a file in the [SNAPSHOT_DIR] that [configure] chose. The expects call the
+//! function in this module through a hook (see `route_snapshots_through_hook`) to do that.
+//!
+//! A snapshot that doesn't exist yet is created from the value it is checked against, and
+//! with [configure]'s `update` set, every snapshot is replaced by its current value.
+//!
+//! [SNAPSHOT_DIR]: roc_reporting::error::expect::SNAPSHOT_DIR
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use roc_gen_llvm::llvm::externs::ROC_SNAPSHOT_HOOK;
+use roc_reporting::error::expect::SnapshotFailure;
+use roc_std::RocStr;
+
+static CONFIG: Mutex<Option<Config>> = Mutex::new(None);
+
+static FAILURES: Mutex<Vec<SnapshotFailure>> = Mutex::new(Vec::new());
+
+struct Config {
+    dir: PathBuf,
+    update: bool,
+}
+
+fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    // A panic in the hook can't leave either of these in a state worth refusing to report.
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Keep the snapshots in `dir`, which is created when the first one is written. With
+/// `update`, every snapshot that an expect checks is replaced by the value it's checked
+/// against, so none of them fail.
+pub fn configure(dir: PathBuf, update: bool) {
+    *lock(&CONFIG) = Some(Config { dir, update });
+}
+
+/// Stores the hook in `lib`, if any of its expects check a snapshot.
+pub(crate) fn install(lib: &libloading::Library) {
+    type MatchesFn = extern "C" fn(&RocStr, &RocStr) -> u8;
+
+    if let Ok(hook) = unsafe { lib.get::<*mut MatchesFn>(ROC_SNAPSHOT_HOOK.as_bytes()) } {
+        unsafe { **hook = roc_snapshot_hook };
+    }
+}
+
+/// Forgets the failures so far, so the next expect starts from nothing.
+pub(crate) fn reset() {
+    lock(&FAILURES).clear();
+}
+
+/// The snapshots that failed since the last [`reset`], in the order they were checked.
+pub(crate) fn take_failures() -> Vec<SnapshotFailure> {
+    std::mem::take(&mut *lock(&FAILURES))
+}
+
+extern "C" fn roc_snapshot_hook(name: &RocStr, actual: &RocStr) -> u8 {
+    match check(name.as_str(), actual.as_str()) {
+        Ok(()) => 1,
+        Err(failure) => {
+            lock(&FAILURES).push(failure);
+
+            0
+        }
+    }
+}
+
+fn check(name: &str, actual: &str) -> Result<(), SnapshotFailure> {
+    let unusable = |problem: String| SnapshotFailure::Unusable {
+        name: name.to_string(),
+        problem,
+    };
+
+    let config = lock(&CONFIG);
+    let config = match config.as_ref() {
+        Some(config) => config,
+        None => {
+            return Err(unusable(
+                "No snapshot directory was configured.".to_string(),
+            ))
+        }
+    };
+
+    let path = snapshot_path(&config.dir, name).map_err(unusable)?;
+
+    if !config.update {
+        match std::fs::read_to_string(&path) {
+            Ok(stored) => {
+                // Only the newline that `write` adds is ignored
+                let stored = stored.strip_suffix('\n').unwrap_or(&stored);
+
+                return if stored == actual {
+                    Ok(())
+                } else {
+                    Err(SnapshotFailure::Mismatch {
+                        path,
+                        stored: stored.to_string(),
+                        actual: actual.to_string(),
+                    })
+                };
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                return Err(unusable(format!(
+                    "Reading {} failed: {error}",
+                    path.display()
+                )))
+            }
+        }
+    }
+
+    write(&config.dir, &path, actual)
+        .map_err(|error| unusable(format!("Writing {} failed: {error}", path.display())))
+}
+
+/// Where the snapshot called `name` is kept. Names become file names, so they can't be
+/// empty, or be a path.
+fn snapshot_path(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let is_file_name = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(|c| std::path::is_separator(c) || c == '\0');
+
+    if is_file_name {
+        Ok(dir.join(format!("{name}.txt")))
+    } else {
+        Err(format!(
+            "A snapshot's name must be usable as a file name, but {name:?} isn't."
+        ))
+    }
+}
+
+fn write(dir: &Path, path: &Path, actual: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+
+    // Ending with a newline keeps editors and diffs happy
+    std::fs::write(path, format!("{actual}\n"))
+}
@@ -1,9 +1,10 @@
 use bumpalo::Bump;
 use indoc::indoc;
 use roc_repl_cli::{evaluate, ReplHelper};
-use roc_repl_ui::is_incomplete;
+use roc_repl_ui::command::{parse_command, CommandProblem, ReplCommand};
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
-use roc_reporting::report::DEFAULT_PALETTE;
+use roc_repl_ui::{format_output, is_incomplete};
+use roc_reporting::report::{ANSI_STYLE_CODES, DEFAULT_PALETTE};
 use rustyline::Editor;
 use target_lexicon::Triple;
 
@@ -25,6 +26,44 @@ fn persisted_defs() {
     complete("y = 6", &mut state, "6 : Num *");
 }
 
+#[test]
+fn redefined_defs() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    complete("y = x + 1", &mut state, "6 : Num *");
+    complete("x = 10", &mut state, "10 : Num *");
+    complete("y", &mut state, "11 : Num *");
+}
+
+#[test]
+fn redefined_annotated_def() {
+    let mut state = ReplState::new();
+    let mut input = "s : Str".to_string();
+
+    incomplete(&mut input);
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, &input, target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Nothing));
+
+    complete("s = \"a\"", &mut state, "\"a\" : Str");
+    complete("s = \"b\"", &mut state, "\"b\" : Str");
+}
+
+#[test]
+fn multiline_list() {
+    let mut state = ReplState::new();
+    let mut input = "[".to_string();
+
+    incomplete(&mut input);
+    input.push_str("1,");
+    incomplete(&mut input);
+    input.push_str("2]");
+
+    complete(&input, &mut state, "[1, 2] : List (Num *)");
+}
+
 #[test]
 fn annotated_body() {
     let mut input = "t : [A, B, C]".to_string();
@@ -132,6 +171,174 @@ fn tips() {
     assert!(matches!(action, ReplAction::Help));
 }
 
+#[test]
+fn type_command() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, ":type x + 1", target, DEFAULT_PALETTE);
+
+    match action {
+        ReplAction::Type {
+            opt_output,
+            problems,
+        } => {
+            let output = format_output(ANSI_STYLE_CODES, opt_output, problems);
+            let escaped =
+                std::string::String::from_utf8(strip_ansi_escapes::strip(output.trim()).unwrap())
+                    .unwrap();
+
+            assert_eq!(escaped, "x + 1 : Num *");
+        }
+        _ => panic!("Unexpected action: {:?}", action),
+    }
+}
+
+#[test]
+fn reset_command() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, ":reset", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Reset));
+
+    // x is gone, so it can be defined with a different type
+    complete("x = \"five\"", &mut state, "\"five\" : Str");
+}
+
+#[test]
+fn command_problems() {
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let mut state = ReplState::new();
+
+    let action = state.step(&arena, ":frobnicate", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::CommandProblem(_)));
+
+    let action = state.step(&arena, ":type", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::CommandProblem(_)));
+}
+
+#[test]
+fn parse_commands() {
+    assert_eq!(parse_command("1 + 1"), None);
+    assert_eq!(parse_command(":q"), Some(Ok(ReplCommand::Exit)));
+    assert_eq!(parse_command("quit()"), Some(Ok(ReplCommand::Exit)));
+    assert_eq!(
+        parse_command(":type  List.len [] "),
+        Some(Ok(ReplCommand::Type("List.len []")))
+    );
+    assert_eq!(
+        parse_command(":doc List"),
+        Some(Ok(ReplCommand::Doc("List")))
+    );
+    assert_eq!(
+        parse_command(":package pf \"https://example.com/pf.tar.br\""),
+        Some(Ok(ReplCommand::Package {
+            shorthand: "pf",
+            location: "https://example.com/pf.tar.br"
+        }))
+    );
+    assert_eq!(
+        parse_command(":package pf \"https://example.com/\\\"pf.tar.br\""),
+        Some(Err(CommandProblem::InvalidArgument {
+            command: "package",
+            argument: "https://example.com/\\\"pf.tar.br",
+            reason: "a package location can't contain quotes, backslashes, or `$(`"
+        }))
+    );
+    assert_eq!(
+        parse_command(":package pf} https://example.com/pf.tar.br"),
+        Some(Err(CommandProblem::InvalidArgument {
+            command: "package",
+            argument: "pf}",
+            reason: "a package shorthand has to be a lowercase name, like `pf`"
+        }))
+    );
+    assert_eq!(
+        parse_command(":doc"),
+        Some(Err(CommandProblem::MissingArgument {
+            command: "doc",
+            argument: "a name"
+        }))
+    );
+}
+
+#[test]
+fn package_imports() {
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let mut state = ReplState::new();
+
+    let action = state.step(&arena, "import pf.Stdout", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::UnknownPackage { shorthand } if shorthand == "pf"));
+
+    let action = state.step(
+        &arena,
+        ":package pf ./does-not-exist",
+        target,
+        DEFAULT_PALETTE,
+    );
+    assert!(matches!(action, ReplAction::FileProblem { .. }));
+
+    // Tests run in the crate's directory, and local packages are relative to it
+    let action = state.step(
+        &arena,
+        ":package json ../cli/tests/fixtures/packages/json/main.roc",
+        target,
+        DEFAULT_PALETTE,
+    );
+    assert!(matches!(action, ReplAction::Nothing));
+
+    let action = state.step(&arena, "import json.JsonParser", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Nothing));
+
+    complete(
+        "JsonParser.example",
+        &mut state,
+        "\"This text came from a package!\" : Str",
+    );
+}
+
+#[test]
+fn tab_completion() {
+    let mut state = ReplState::new();
+    let target = Triple::host().into();
+
+    let arena = Bump::new();
+    let input = "user = { name: \"Sam\", address: { city: \"Oslo\" } }";
+    let action = state.step(&arena, input, target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Eval { .. }));
+
+    assert_eq!(
+        state.complete("us", 2, target),
+        (0, vec!["user".to_string()])
+    );
+    assert_eq!(
+        state.complete("user.na", 7, target),
+        (5, vec!["name".to_string()])
+    );
+    assert_eq!(
+        state.complete("user.address.c", 14, target),
+        (13, vec!["city".to_string()])
+    );
+
+    let (start, completions) = state.complete("List.ma", 7, target);
+    assert_eq!(start, 5);
+    assert!(completions.contains(&"map".to_string()));
+    assert!(completions.iter().all(|name| name.starts_with("ma")));
+
+    let (start, completions) = state.complete("1 + St", 6, target);
+    assert_eq!(start, 4);
+    assert_eq!(completions, vec!["Str".to_string()]);
+}
+
 #[test]
 fn standalone_annotation() {
     let mut state = ReplState::new();
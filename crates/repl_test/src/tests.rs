@@ -593,6 +593,34 @@ fn multiline_string_non_wasm() {
     assert!(out.status.success());
 }
 
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn doc_of_builtin() {
+    let out = repl_eval(":doc Str.isEmpty");
+
+    assert_multiline_str_eq!("", out.stderr.as_str());
+    assert!(
+        out.stdout
+            .contains("Returns [Bool.true] if the string is empty, and [Bool.false] otherwise."),
+        "unexpected docs: {}",
+        out.stdout
+    );
+    assert!(out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn doc_of_unknown_name() {
+    let out = repl_eval(":doc Str.notAFunction");
+
+    assert_multiline_str_eq!("", out.stderr.as_str());
+    assert_multiline_str_eq!(
+        "I could not find any docs for Str.notAFunction.",
+        out.stdout.trim()
+    );
+    assert!(out.status.success());
+}
+
 #[cfg(feature = "wasm")]
 #[test]
 fn multiline_string_wasm() {
@@ -1115,7 +1143,7 @@ fn print_u8s() {
 #[test]
 fn parse_problem() {
     expect_failure(
-        "add m n = m + n",
+        "Num.add m n = m + n",
         indoc!(
             r#"
                 ── ARGUMENTS BEFORE EQUALS ─────────────────────────────────────────────────────
@@ -1125,11 +1153,12 @@ fn parse_problem() {
                 1│  app "app" provides [replOutput] to "./platform"
                 2│
                 3│  replOutput =
-                4│      add m n = m + n
-                            ^^^
+                4│      Num.add m n = m + n
+                                ^^^
 
-                Looks like you are trying to define a function. In roc, functions are
-                always written as a lambda, like increment = \n -> n + 1.
+                Looks like you are trying to define a function. A function's name has
+                to be a plain lowercase name, with its arguments on the same line,
+                like increment n = n + 1.
                 "#
         ),
     );
@@ -1579,6 +1608,11 @@ fn interpolation_with_nested_interpolation() {
                 Enter an expression to evaluate, or a definition (like x = 1) to use later.
 
                   - ctrl-v + ctrl-j makes a newline
+                  - an empty line ends a multiline input
+                  - :type <expr> shows the type of an expression without running it
+                  - :doc <name> shows the docs of a value, type, or module
+                  - :reset forgets all definitions
+                  - :package <shorthand> <url or path> makes a package available to import
                   - :q quits
                   - :help shows this text again
             "#
@@ -0,0 +1,134 @@
+//! Meta-commands like `:help` and `:type`, which the REPL handles itself instead of
+//! evaluating them as Roc code.
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplCommand<'a> {
+    Help,
+    Exit,
+    /// Forget everything that was defined so far
+    Reset,
+    /// Print the type of an expression, without evaluating it
+    Type(&'a str),
+    /// Print the docs of a value, type, or module
+    Doc(&'a str),
+    /// Make a package available to import, e.g. `:package pf https://…`
+    Package {
+        shorthand: &'a str,
+        /// A URL, or a path relative to the current directory
+        location: &'a str,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandProblem<'a> {
+    Unknown(&'a str),
+    MissingArgument {
+        command: &'a str,
+        argument: &'static str,
+    },
+    InvalidArgument {
+        command: &'a str,
+        argument: &'a str,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for CommandProblem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandProblem::Unknown(command) => write!(
+                f,
+                "I don't know the command :{command}. Enter :help to see the commands I know."
+            ),
+            CommandProblem::MissingArgument { command, argument } => {
+                write!(f, "The :{command} command needs {argument} after it.")
+            }
+            CommandProblem::InvalidArgument {
+                command,
+                argument,
+                reason,
+            } => write!(f, "The :{command} command can't use {argument}: {reason}."),
+        }
+    }
+}
+
+/// Returns `None` if the line is not a command, and should be evaluated as Roc code instead.
+pub fn parse_command(line: &str) -> Option<Result<ReplCommand<'_>, CommandProblem<'_>>> {
+    let line = line.trim();
+
+    match line.to_lowercase().as_str() {
+        // These are all common things beginners try.
+        // Let people exit the repl easily!
+        // If you really need to evaluate `exit` for some reason,
+        // you can do `foo = exit` and then evaluate `foo` instead.
+        "exit" | "quit" | "exit()" | "quit()" => return Some(Ok(ReplCommand::Exit)),
+        _ => {}
+    }
+
+    // No Roc expression or def starts with a colon
+    let rest = line.strip_prefix(':')?;
+
+    let (command, argument) = match rest.split_once(char::is_whitespace) {
+        Some((command, argument)) => (command, argument.trim()),
+        None => (rest, ""),
+    };
+
+    let required = |argument_name| {
+        if argument.is_empty() {
+            Err(CommandProblem::MissingArgument {
+                command,
+                argument: argument_name,
+            })
+        } else {
+            Ok(argument)
+        }
+    };
+
+    let parsed = match command.to_lowercase().as_str() {
+        "help" => Ok(ReplCommand::Help),
+        "exit" | "quit" | "q" => Ok(ReplCommand::Exit),
+        "reset" => Ok(ReplCommand::Reset),
+        "type" | "t" => required("an expression").map(ReplCommand::Type),
+        "doc" | "d" => required("a name").map(ReplCommand::Doc),
+        "package" => match argument.split_once(char::is_whitespace) {
+            Some((shorthand, location)) => {
+                let location = location.trim().trim_matches('"');
+
+                // Both go into the header of the module the REPL compiles, where a package
+                // location has to be a plain string, without escapes or interpolation.
+                if !is_package_shorthand(shorthand) {
+                    Err(CommandProblem::InvalidArgument {
+                        command,
+                        argument: shorthand,
+                        reason: "a package shorthand has to be a lowercase name, like `pf`",
+                    })
+                } else if location.contains(['"', '\\', '\n']) || location.contains("$(") {
+                    Err(CommandProblem::InvalidArgument {
+                        command,
+                        argument: location,
+                        reason: "a package location can't contain quotes, backslashes, or `$(`",
+                    })
+                } else {
+                    Ok(ReplCommand::Package {
+                        shorthand,
+                        location,
+                    })
+                }
+            }
+            None => Err(CommandProblem::MissingArgument {
+                command,
+                argument: "a shorthand and a URL or path",
+            }),
+        },
+        _ => Err(CommandProblem::Unknown(command)),
+    };
+
+    Some(parsed)
+}
+
+fn is_package_shorthand(shorthand: &str) -> bool {
+    let mut chars = shorthand.chars();
+
+    chars.next().is_some_and(|c| c.is_ascii_lowercase()) && chars.all(|c| c.is_ascii_alphanumeric())
+}
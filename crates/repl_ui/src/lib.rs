@@ -1,6 +1,7 @@
 //! UI functionality, shared between CLI and web, for the Read-Evaluate-Print-Loop (REPL).
 // We don't do anything here related to the terminal (doesn't exist on the web) or LLVM (too big for the web).
 pub mod colors;
+pub mod command;
 pub mod repl_state;
 
 use bumpalo::Bump;
@@ -37,6 +38,38 @@ pub const TIPS: &str = concatcp!(
             CYAN,
             "  - ",
             END_COL,
+            "an empty line ends a multiline input\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":type <expr>",
+            END_COL,
+            " shows the type of an expression without running it\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":doc <name>",
+            END_COL,
+            " shows the docs of a value, type, or module\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":reset",
+            END_COL,
+            " forgets all definitions\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":package <shorthand> <url or path>",
+            END_COL,
+            " makes a package available to import\n",
+            CYAN,
+            "  - ",
+            END_COL,
             GREEN,
             ":q",
             END_COL,
@@ -57,6 +90,17 @@ pub const TIPS: &str = concatcp!(
 pub const SHORT_INSTRUCTIONS: &str = "Enter an expression, or :help, or :q to quit.\n\n";
 pub const PROMPT: &str = concatcp!(CYAN, "»", END_COL, " ");
 pub const CONT_PROMPT: &str = concatcp!(CYAN, "…", END_COL, " ");
+pub const RESET_MESSAGE: &str = "All definitions have been forgotten.";
+
+pub fn format_unknown_package(shorthand: &str) -> String {
+    format!(
+        "I don't know where the package {shorthand} is. Add it with :package {shorthand} <url or path>, then import from it again."
+    )
+}
+
+pub fn format_docs(name: &str, docs: Option<String>) -> String {
+    docs.unwrap_or_else(|| format!("I could not find any docs for {name}."))
+}
 
 pub fn is_incomplete(input: &str) -> bool {
     let arena = Bump::new();
@@ -75,8 +119,8 @@ pub fn is_incomplete(input: &str) -> bool {
             !input.ends_with('\n')
         }
         ParseOutcome::Empty
-        | ParseOutcome::Help
-        | ParseOutcome::Exit
+        | ParseOutcome::Command(_)
+        | ParseOutcome::CommandProblem(_)
         | ParseOutcome::ValueDef(_)
         | ParseOutcome::TypeDef(_)
         | ParseOutcome::SyntaxErr
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::path::PathBuf;
 use std::{fs, io};
 
@@ -7,24 +8,57 @@ use roc_load::MonomorphizedModule;
 use roc_parse::ast::{Expr, Pattern, StrLiteral, TypeDef, TypeHeader, ValueDef};
 use roc_parse::expr::{parse_single_def, ExprParseOptions, SingleDef};
 use roc_parse::parser::Parser;
-use roc_parse::parser::{EClosure, EExpr, EPattern};
+use roc_parse::parser::{EClosure, EExpr, EPattern, EString};
 use roc_parse::parser::{EWhen, Either};
 use roc_parse::state::State;
 use roc_parse::{join_alias_to_body, join_ann_to_body};
 use roc_region::all::Loc;
-use roc_repl_eval::gen::{compile_to_mono, Problems};
+use roc_repl_eval::completion::{load_scope, ReplScope};
+use roc_repl_eval::gen::{compile_to_mono, expr_type, find_docs, Problems, ReplOutput};
 use roc_reporting::report::Palette;
 use roc_target::Target;
 
+use crate::command::{parse_command, CommandProblem, ReplCommand};
+
 #[derive(Debug, Clone, PartialEq)]
 enum PastDef {
-    Def { ident: String, src: String },
+    Def {
+        ident: String,
+        src: String,
+    },
+    /// A type annotation that was entered on its own
+    Annotation {
+        ident: String,
+        src: String,
+    },
     Import(String),
 }
 
+impl PastDef {
+    fn ident(&self) -> Option<&str> {
+        match self {
+            PastDef::Def { ident, .. } | PastDef::Annotation { ident, .. } => Some(ident),
+            PastDef::Import(_) => None,
+        }
+    }
+
+    fn src(&self) -> &str {
+        match self {
+            PastDef::Def { src, .. } | PastDef::Annotation { src, .. } | PastDef::Import(src) => {
+                src
+            }
+        }
+    }
+}
+
 pub struct ReplState {
     past_defs: Vec<PastDef>,
     past_def_idents: MutSet<String>,
+    /// The packages that imports can use, added with `:package`, by shorthand
+    packages: Vec<(String, String)>,
+    /// What can be completed on Tab; worked out on the first Tab after the defs change,
+    /// since that takes a type-check
+    completion_scope: RefCell<Option<ReplScope>>,
 }
 
 impl Default for ReplState {
@@ -46,6 +80,23 @@ pub enum ReplAction<'a> {
         filename: PathBuf,
         error: io::ErrorKind,
     },
+    /// The answer to `:type`
+    Type {
+        opt_output: Option<ReplOutput>,
+        problems: Problems,
+    },
+    /// An import from a package that was not added with `:package`
+    UnknownPackage {
+        shorthand: String,
+    },
+    /// The answer to `:doc`
+    Doc {
+        name: String,
+        docs: Option<String>,
+    },
+    /// All defs were forgotten
+    Reset,
+    CommandProblem(String),
     Nothing,
 }
 
@@ -54,9 +105,30 @@ impl ReplState {
         Self {
             past_defs: Default::default(),
             past_def_idents: Default::default(),
+            packages: Default::default(),
+            completion_scope: Default::default(),
         }
     }
 
+    /// Completions of the word that ends at `pos` in `line`, and where in `line` the part
+    /// that they would replace starts.
+    pub fn complete(&self, line: &str, pos: usize, target: Target) -> (usize, Vec<String>) {
+        let mut completion_scope = self.completion_scope.borrow_mut();
+
+        let scope = completion_scope.get_or_insert_with(|| {
+            let arena = Bump::new();
+
+            load_scope(
+                &arena,
+                &self.packages,
+                self.past_defs.iter().map(PastDef::src),
+                target,
+            )
+        });
+
+        scope.complete(line, pos)
+    }
+
     pub fn step<'a>(
         &mut self,
         arena: &'a Bump,
@@ -66,8 +138,13 @@ impl ReplState {
     ) -> ReplAction<'a> {
         let pending_past_def;
         let src: &str = match parse_src(arena, line) {
-            ParseOutcome::Empty | ParseOutcome::Help => return ReplAction::Help,
-            ParseOutcome::Exit => return ReplAction::Exit,
+            ParseOutcome::Empty => return ReplAction::Help,
+            ParseOutcome::Command(command) => {
+                return self.run_command(arena, command, target, palette)
+            }
+            ParseOutcome::CommandProblem(problem) => {
+                return ReplAction::CommandProblem(problem.to_string())
+            }
             ParseOutcome::Expr(_) | ParseOutcome::Incomplete | ParseOutcome::SyntaxErr => {
                 pending_past_def = None;
 
@@ -87,7 +164,10 @@ impl ReplState {
                         _,
                     ) => {
                         // Record the standalone type annotation for future use.
-                        self.add_past_def(ident.trim_end().to_string(), line.to_string());
+                        self.add_past_def(PastDef::Annotation {
+                            ident: ident.trim_end().to_string(),
+                            src: line.to_string(),
+                        });
 
                         // Return early without running eval, since standalone annotations
                         // cannot be evaluated as expressions.
@@ -109,8 +189,45 @@ impl ReplState {
                                 ..
                             },
                         ..
+                    }
+                    // A function defined like `f x y = body`
+                    | ValueDef::Body(
+                        Loc {
+                            value:
+                                Pattern::Apply(
+                                    Loc {
+                                        value: Pattern::Identifier { ident },
+                                        ..
+                                    },
+                                    _,
+                                ),
+                            ..
+                        },
+                        _,
+                    )
+                    | ValueDef::AnnotatedBody {
+                        body_pattern:
+                            Loc {
+                                value:
+                                    Pattern::Apply(
+                                        Loc {
+                                            value: Pattern::Identifier { ident },
+                                            ..
+                                        },
+                                        _,
+                                    ),
+                                ..
+                            },
+                        ..
                     } => {
-                        pending_past_def = Some((ident.to_string(), line.to_string()));
+                        // Entering a def for a name that was defined before replaces the old
+                        // def, so it must not be around when we evaluate the new one.
+                        self.forget_past_def(ident, true);
+
+                        pending_past_def = Some(PastDef::Def {
+                            ident: ident.to_string(),
+                            src: line.to_string(),
+                        });
 
                         // Recreate the body of the def and then evaluate it as a lookup.
                         // We do this so that any errors will get reported as part of this expr;
@@ -144,8 +261,18 @@ impl ReplState {
                         todo!("handle receiving an `expect-fx` - what should the repl do for that?")
                     }
                     ValueDef::ModuleImport(import) => match import.name.value.package {
-                        Some(_) => {
-                            todo!("handle importing a module from a package")
+                        Some(shorthand) => {
+                            // The loader fetches the package when the next expr gets
+                            // compiled, so all we can check here is that we know where it is.
+                            if !self.packages.iter().any(|(known, _)| known == shorthand) {
+                                return ReplAction::UnknownPackage {
+                                    shorthand: shorthand.to_string(),
+                                };
+                            }
+
+                            self.add_past_def(PastDef::Import(line.to_string()));
+
+                            return ReplAction::Nothing;
                         }
                         None => {
                             let mut filename = PathBuf::new();
@@ -166,7 +293,7 @@ impl ReplState {
                                 };
                             }
 
-                            self.past_defs.push(PastDef::Import(line.to_string()));
+                            self.add_past_def(PastDef::Import(line.to_string()));
 
                             return ReplAction::Nothing;
                         }
@@ -182,11 +309,14 @@ impl ReplState {
                             }
                         }
 
-                        self.past_defs.push(PastDef::Import(line.to_string()));
+                        self.add_past_def(PastDef::Import(line.to_string()));
 
                         return ReplAction::Nothing;
                     }
                     ValueDef::Stmt(_) => todo!(),
+                    ValueDef::Malformed(_) => {
+                        unreachable!("only top-level defs in a module recover from parse errors")
+                    }
                 }
             }
             ParseOutcome::TypeDef(TypeDef::Alias {
@@ -214,7 +344,10 @@ impl ReplState {
                 ..
             }) => {
                 // Record the type for future use.
-                self.add_past_def(ident.trim_end().to_string(), line.to_string());
+                self.add_past_def(PastDef::Def {
+                    ident: ident.trim_end().to_string(),
+                    src: line.to_string(),
+                });
 
                 // Return early without running eval, since none of these
                 // can be evaluated as expressions.
@@ -224,28 +357,147 @@ impl ReplState {
 
         let (opt_mono, problems) = compile_to_mono(
             arena,
-            self.past_defs.iter().map(|past_def| match past_def {
-                PastDef::Def { ident: _, src } => src.as_str(),
-                PastDef::Import(src) => src.as_str(),
-            }),
+            &self.packages,
+            self.past_defs.iter().map(PastDef::src),
             src,
             target,
             palette,
         );
 
-        if let Some((ident, src)) = pending_past_def {
-            self.add_past_def(ident, src);
+        if let Some(past_def) = pending_past_def {
+            self.add_past_def(past_def);
         }
 
         ReplAction::Eval { opt_mono, problems }
     }
 
-    fn add_past_def(&mut self, ident: String, src: String) {
-        let existing_idents = &mut self.past_def_idents;
+    fn run_command<'a>(
+        &mut self,
+        arena: &'a Bump,
+        command: ReplCommand<'_>,
+        target: Target,
+        palette: Palette,
+    ) -> ReplAction<'a> {
+        match command {
+            ReplCommand::Help => ReplAction::Help,
+            ReplCommand::Exit => ReplAction::Exit,
+            ReplCommand::Reset => {
+                *self = Self::new();
+
+                ReplAction::Reset
+            }
+            ReplCommand::Type(expr) => {
+                // Compiling the expr is enough to know its type; there's no need to run it
+                let (opt_mono, problems) = compile_to_mono(
+                    arena,
+                    &self.packages,
+                    self.past_defs.iter().map(PastDef::src),
+                    expr,
+                    target,
+                    palette,
+                );
+
+                let opt_output =
+                    opt_mono
+                        .and_then(|mut mono| expr_type(&mut mono))
+                        .map(|expr_type| ReplOutput {
+                            expr: expr.to_string(),
+                            expr_type,
+                        });
+
+                ReplAction::Type {
+                    opt_output,
+                    problems,
+                }
+            }
+            ReplCommand::Package {
+                shorthand,
+                location,
+            } => {
+                let is_url = location.starts_with("https://") || location.starts_with("http://");
+
+                // A local package is relative to where the REPL was started, like imports
+                if !is_url {
+                    if let Err(err) = fs::metadata(location) {
+                        return ReplAction::FileProblem {
+                            filename: PathBuf::from(location),
+                            error: err.kind(),
+                        };
+                    }
+                }
 
-        existing_idents.insert(ident.clone());
+                self.packages.retain(|(known, _)| known != shorthand);
+                self.packages
+                    .push((shorthand.to_string(), location.to_string()));
+                *self.completion_scope.get_mut() = None;
 
-        self.past_defs.push(PastDef::Def { ident, src });
+                ReplAction::Nothing
+            }
+            ReplCommand::Doc(name) => {
+                let docs = find_docs(
+                    arena,
+                    &self.packages,
+                    self.past_defs.iter().map(PastDef::src),
+                    name,
+                    target,
+                    palette,
+                );
+
+                ReplAction::Doc {
+                    name: name.to_string(),
+                    docs,
+                }
+            }
+        }
+    }
+
+    /// Later defs of a name replace the earlier ones, so that a session can be built up by
+    /// redefining things, instead of running into errors about shadowing.
+    fn add_past_def(&mut self, past_def: PastDef) {
+        *self.completion_scope.get_mut() = None;
+
+        match past_def.ident() {
+            Some(ident) => {
+                let is_body = matches!(past_def, PastDef::Def { .. });
+
+                self.forget_past_def(ident, is_body);
+                self.past_def_idents.insert(ident.to_string());
+            }
+            None => {
+                // Importing the same thing twice is not an error, but there's no point in it
+                if self.past_defs.contains(&past_def) {
+                    return;
+                }
+            }
+        }
+
+        self.past_defs.push(past_def);
+    }
+
+    /// Forgets the defs of the given name. A standalone annotation entered just before is
+    /// kept when `for_body` is set, because it annotates the body that is about to be added.
+    fn forget_past_def(&mut self, ident: &str, for_body: bool) {
+        *self.completion_scope.get_mut() = None;
+
+        if !self.past_def_idents.contains(ident) {
+            return;
+        }
+
+        let keep_annotation = for_body
+            && matches!(
+                self.past_defs.last(),
+                Some(PastDef::Annotation { ident: last, .. }) if last == ident
+            );
+
+        let last_index = self.past_defs.len().saturating_sub(1);
+        let mut index = 0;
+
+        self.past_defs.retain(|past_def| {
+            let keep = past_def.ident() != Some(ident) || (keep_annotation && index == last_index);
+            index += 1;
+
+            keep
+        });
     }
 }
 
@@ -257,19 +509,19 @@ pub enum ParseOutcome<'a> {
     Incomplete,
     SyntaxErr,
     Empty,
-    Help,
-    Exit,
+    Command(ReplCommand<'a>),
+    CommandProblem(CommandProblem<'a>),
 }
 
 pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
-    match line.trim().to_lowercase().as_str() {
+    match parse_command(line) {
+        Some(Ok(command)) => return ParseOutcome::Command(command),
+        Some(Err(problem)) => return ParseOutcome::CommandProblem(problem),
+        None => {}
+    }
+
+    match line.trim() {
         "" => ParseOutcome::Empty,
-        ":help" => ParseOutcome::Help,
-        // These are all common things beginners try.
-        // Let people exit the repl easily!
-        // If you really need to evaluate `exit` for some reason,
-        // you can do `foo = exit` and then evaluate `foo` instead.
-        ":exit" | ":quit" | ":q" | "exit" | "quit" | "exit()" | "quit()" => ParseOutcome::Exit,
         _ => {
             let src_bytes = line.as_bytes();
 
@@ -282,6 +534,9 @@ pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
                 Err((_, EExpr::Closure(EClosure::Body(_, _), _)))
                 | Err((_, EExpr::When(EWhen::Pattern(EPattern::Start(_), _), _)))
                 | Err((_, EExpr::Record(_, _)))
+                | Err((_, EExpr::List(_, _)))
+                | Err((_, EExpr::InParens(_, _)))
+                | Err((_, EExpr::Str(EString::EndlessMultiLine(_), _)))
                 | Err((_, EExpr::Start(_)))
                 | Err((_, EExpr::IndentStart(_))) => ParseOutcome::Incomplete,
                 Err((_, EExpr::DefMissingFinalExpr(_)))
@@ -56,6 +56,9 @@ BINDGEN_FILE="roc_repl_wasm.js"
 echo 'var __wbg_star0 = { now: Date.now };' > build/$BINDGEN_FILE
 grep -v '^import' pkg/$BINDGEN_FILE >> build/$BINDGEN_FILE
 
+# The API for embedding the REPL in other pages, see README.md
+cp -v roc_repl.js build
+
 # As of July 2022, the .wasm file is ~4MB, shrinking to ~1MB with Brotli compression (which Netlify does)
 echo "Generated REPL assets for website:"
 ls -l build
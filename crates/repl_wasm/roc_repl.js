@@ -0,0 +1,172 @@
+// A small API for embedding the Roc REPL in a web page, e.g. in a tutorial:
+//
+//   import * as roc from "./roc_repl.js";
+//
+//   await roc.init();
+//   const { output, type, error } = await roc.eval("List.map [1, 2] Num.toStr");
+//   roc.reset();
+//
+// The REPL on the website uses `evalHtml` instead, which formats everything in one piece.
+// `output` and `error` are HTML, using the same CSS classes as the REPL on the website.
+// build-www.sh copies this file next to roc_repl_wasm.js and roc_repl_wasm_bg.wasm.
+
+import initCompiler, * as roc_repl_wasm from "./roc_repl_wasm.js";
+
+const state = {
+  // The compiler's Wasm instance
+  compiler: null,
+  // The Wasm instance of the last input that was compiled
+  app: null,
+  // The address of the last input's value, in the app's memory
+  resultAddr: 0,
+  // The message of the last `crash`, or panic in the compiled code
+  panicMessage: null,
+  textDecoder: new TextDecoder(),
+};
+
+// Inputs are evaluated one at a time, since the compiler keeps the REPL state in a global.
+let queue = Promise.resolve();
+
+/**
+ * Loads the compiler. Call this once, before `eval`.
+ * @param {string | URL} [wasmUrl] where to find roc_repl_wasm_bg.wasm, next to this file by default
+ */
+export async function init(wasmUrl = new URL("roc_repl_wasm_bg.wasm", import.meta.url)) {
+  // The only way we can provide values to wasm_bindgen's generated code is to set globals
+  window.js_create_app = js_create_app;
+  window.js_run_app = js_run_app;
+  window.js_get_result_and_memory = js_get_result_and_memory;
+
+  state.compiler = await initCompiler(wasmUrl);
+}
+
+/**
+ * Evaluates an expression, def, or REPL command like `:type`, as if it was entered in the REPL.
+ * @param {string} src
+ * @returns {Promise<{ output: string, type: string, error: string | null }>}
+ */
+function evaluate(src) {
+  return enqueue(() => roc_repl_wasm.eval_from_js(src)).then(
+    ({ value, panicMessage }) =>
+      panicMessage === null ? value : { output: "", type: "", error: panicMessage }
+  );
+}
+
+/**
+ * Like `eval`, but resolves to everything the REPL would print for the input, as one piece of HTML.
+ * Rejects with the panic message if the compiled code crashed.
+ * @param {string} src
+ * @returns {Promise<string>}
+ */
+export function evalHtml(src) {
+  return enqueue(() => roc_repl_wasm.entrypoint_from_js(src)).then(
+    ({ value, panicMessage }) => {
+      if (panicMessage !== null) {
+        throw new Error(panicMessage);
+      }
+
+      return value;
+    }
+  );
+}
+
+// Runs one call into the compiler, once every input before it is done.
+function enqueue(run) {
+  const result = queue.then(async () => {
+    if (state.compiler === null) {
+      throw new Error("Call init() before eval()");
+    }
+
+    state.panicMessage = null;
+    const value = await run();
+
+    return { value, panicMessage: state.panicMessage };
+  });
+
+  // Keep going with the next input, even if this one failed
+  queue = result.catch(() => {});
+
+  return result;
+}
+
+// `eval` can't be the name of a function in a module, but it can be the name of an export
+export { evaluate as eval };
+
+/** Forgets everything that was defined so far, like the `:reset` command. */
+export function reset() {
+  roc_repl_wasm.reset_from_js();
+}
+
+// ----------------------------------------------------------------------------
+// Callbacks to JS from Rust
+// ----------------------------------------------------------------------------
+
+function send_panic_msg_to_js(rocstr_ptr, panic_tag) {
+  const { memory } = state.app.exports;
+
+  const rocStrBytes = new Int8Array(memory.buffer, rocstr_ptr, 12);
+  const finalByte = rocStrBytes[11];
+
+  let stringBytes;
+  if (finalByte < 0) {
+    // small string: clear the bit that marks it, like `finalByte as u8 ^ 0b1000_0000` in Rust
+    const length = finalByte + 128;
+    stringBytes = new Uint8Array(memory.buffer, rocstr_ptr, length);
+  } else {
+    // big string
+    const [ptr, len] = new Uint32Array(memory.buffer, rocstr_ptr, 3);
+
+    const SEAMLESS_SLICE_BIT = 1 << 31;
+    const length = len & ~SEAMLESS_SLICE_BIT;
+
+    stringBytes = new Uint8Array(memory.buffer, ptr, length);
+  }
+
+  const msg = state.textDecoder.decode(stringBytes);
+
+  switch (panic_tag) {
+    case 0:
+      state.panicMessage = `Roc failed with message: "${msg}"`;
+      break;
+    case 1:
+      state.panicMessage = `User crash with message: "${msg}"`;
+      break;
+    default:
+      state.panicMessage = `Got an invalid panic tag: "${panic_tag}"`;
+  }
+}
+
+// Load the compiled input into the browser's virtual machine, so we can run it later.
+async function js_create_app(wasm_module_bytes) {
+  const { instance } = await WebAssembly.instantiate(wasm_module_bytes, {
+    env: {
+      send_panic_msg_to_js,
+    },
+  });
+
+  state.app = instance;
+}
+
+// Run the compiled input, and tell Rust how much memory it needs to copy its result.
+function js_run_app() {
+  const { wrapper, memory } = state.app.exports;
+
+  try {
+    state.resultAddr = wrapper();
+  } catch (e) {
+    // Without a panic message, this is a crash we can't explain, likely a compiler bug
+    if (state.panicMessage === null) {
+      throw e;
+    }
+  }
+
+  return memory.buffer.byteLength;
+}
+
+// Copy the app's memory into the space Rust allocated for it, and return the result address
+function js_get_result_and_memory(buffer_alloc_addr) {
+  const appMemory = new Uint8Array(state.app.exports.memory.buffer);
+  const compilerMemory = new Uint8Array(state.compiler.memory.buffer);
+  compilerMemory.set(appMemory, buffer_alloc_addr);
+  return state.resultAddr;
+}
@@ -2,6 +2,7 @@
 // https://github.com/rustwasm/wasm-bindgen/issues/2774
 #![allow(clippy::unused_unit)]
 
+use js_sys::{Object, Reflect};
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsValue;
 
@@ -33,3 +34,33 @@ macro_rules! console_log {
 pub async fn entrypoint_from_js(src: String) -> String {
     crate::repl::entrypoint_from_js(src).await
 }
+
+/// Async entrypoint for embedding the REPL, see `repl_wasm/src/roc_repl.js`
+/// Resolves to a plain object `{ output, type, error }`, where `error` is `null` if there was none.
+#[wasm_bindgen]
+pub async fn eval_from_js(src: String) -> JsValue {
+    let result = crate::repl::eval_from_js(src).await;
+    let object = Object::new();
+
+    let error = match result.error {
+        Some(error) => JsValue::from_str(&error),
+        None => JsValue::NULL,
+    };
+
+    for (key, value) in [
+        ("output", JsValue::from_str(&result.output)),
+        ("type", JsValue::from_str(&result.expr_type)),
+        ("error", error),
+    ] {
+        // Setting a property on a fresh object can't fail
+        let _ = Reflect::set(&object, &JsValue::from_str(key), &value);
+    }
+
+    object.into()
+}
+
+/// Forget everything that was defined so far
+#[wasm_bindgen]
+pub fn reset_from_js() {
+    crate::repl::reset()
+}
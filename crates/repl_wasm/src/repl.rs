@@ -8,16 +8,16 @@ use roc_load::MonomorphizedModule;
 use roc_parse::ast::Expr;
 use roc_repl_eval::{
     eval::jit_to_ast,
-    gen::{format_answer, ReplOutput},
+    gen::{format_answer, Problems, ReplOutput},
     ReplApp, ReplAppMemory,
 };
 use roc_repl_ui::{
-    format_output,
+    format_docs, format_output,
     repl_state::{ReplAction, ReplState},
-    TIPS,
+    RESET_MESSAGE, TIPS,
 };
 use roc_target::Target;
-use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::pretty_print::{name_and_print_var_qualified, DebugPrint, Qualification};
 
 use crate::{js_create_app, js_get_result_and_memory, js_run_app};
 
@@ -179,13 +179,36 @@ impl<'a> ReplApp<'a> for WasmReplApp<'a> {
 const PRE_LINKED_BINARY: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/pre_linked_binary.wasm")) as &[_];
 
-pub async fn entrypoint_from_js(src: String) -> String {
+/// The result of one input, split up for JS code that embeds the REPL
+pub struct EvalResult {
+    /// The value of the expression, or the output of a command like `:help`.
+    /// Any warnings come before the value, like in the REPL.
+    pub output: String,
+    /// The type of the value, or empty if the input was not an expression
+    pub expr_type: String,
+    /// Compile errors, or the exception the app threw, if there were any
+    pub error: Option<String>,
+}
+
+/// What happened when the REPL state machine was advanced by one input
+enum StepOutcome {
+    /// Output that is shown as-is, e.g. for `:help`
+    Message(String),
+    /// An expression was compiled, and maybe evaluated
+    Output {
+        opt_output: Option<ReplOutput>,
+        problems: Problems,
+        /// What the app threw when we ran it
+        exception: Option<String>,
+    },
+}
+
+async fn step(src: &str) -> StepOutcome {
     // If our Rust code panics, redirect the error message to JS console.error
     // Also, our JS code overrides console.error to display the error message text (including stack trace) in the REPL output.
     #[cfg(feature = "console_error_panic_hook")]
     console_error_panic_hook::set_once();
 
-    // TODO: make this a global and reset it?
     let arena = &Bump::new();
 
     // Compile the app
@@ -194,11 +217,11 @@ pub async fn entrypoint_from_js(src: String) -> String {
     // Advance the REPL state machine
     let action = REPL_STATE.with(|repl_state_cell| {
         let mut repl_state = repl_state_cell.borrow_mut();
-        repl_state.step(arena, &src, target, DEFAULT_PALETTE_HTML)
+        repl_state.step(arena, src, target, DEFAULT_PALETTE_HTML)
     });
 
-    // Perform the action the state machine asked for, and return the appropriate output string
-    match action {
+    // Perform the action the state machine asked for
+    let message = match action {
         ReplAction::Help => TIPS.to_string(),
         ReplAction::Exit => {
             "To exit the web version of the REPL, just close the browser tab!".to_string()
@@ -206,23 +229,118 @@ pub async fn entrypoint_from_js(src: String) -> String {
         ReplAction::FileProblem { .. } => {
             "The web version of the REPL cannot import files... for now!".to_string()
         }
+        ReplAction::Type {
+            opt_output,
+            problems,
+        } => {
+            return StepOutcome::Output {
+                opt_output,
+                problems,
+                exception: None,
+            }
+        }
+        ReplAction::Doc { name, docs } => format_docs(&name, docs),
+        ReplAction::UnknownPackage { .. } => {
+            "The web version of the REPL cannot import packages... for now!".to_string()
+        }
+        ReplAction::Reset => RESET_MESSAGE.to_string(),
+        ReplAction::CommandProblem(problem) => problem,
         ReplAction::Nothing => String::new(),
         ReplAction::Eval { opt_mono, problems } => {
-            let opt_output = match opt_mono {
-                Some(mono) => eval_wasm(arena, target, mono).await,
-                None => None,
+            let (opt_output, exception) = match opt_mono {
+                Some(mono) => match eval_wasm(arena, target, mono).await {
+                    Ok(opt_output) => (opt_output, None),
+                    Err(exception) => (None, Some(exception)),
+                },
+                None => (None, None),
+            };
+
+            return StepOutcome::Output {
+                opt_output,
+                problems,
+                exception,
+            };
+        }
+    };
+
+    StepOutcome::Message(message)
+}
+
+pub async fn entrypoint_from_js(src: String) -> String {
+    match step(&src).await {
+        StepOutcome::Message(message) => message,
+        StepOutcome::Output {
+            opt_output,
+            problems,
+            exception: Some(exception),
+        } => {
+            let mut output = format_output(HTML_STYLE_CODES, opt_output, problems);
+            output.push_str(&format!("<span class='color-red'>{exception}</span>"));
+            output
+        }
+        StepOutcome::Output {
+            opt_output,
+            problems,
+            exception: None,
+        } => format_output(HTML_STYLE_CODES, opt_output, problems),
+    }
+}
+
+pub async fn eval_from_js(src: String) -> EvalResult {
+    match step(&src).await {
+        StepOutcome::Message(output) => EvalResult {
+            output,
+            expr_type: String::new(),
+            error: None,
+        },
+        StepOutcome::Output {
+            opt_output,
+            problems: Problems { errors, warnings },
+            exception,
+        } => {
+            // Render the warnings the same way the REPL does, but without the value
+            let no_errors = Problems {
+                errors: std::vec::Vec::new(),
+                warnings,
+            };
+            let mut output = format_output(HTML_STYLE_CODES, None, no_errors);
+            let mut expr_type = String::new();
+
+            if let Some(repl_output) = opt_output {
+                if errors.is_empty() {
+                    output.push_str(&repl_output.expr);
+                    expr_type = repl_output.expr_type;
+                }
+            }
+
+            let error = if !errors.is_empty() {
+                Some(errors.join("\n\n"))
+            } else {
+                exception
             };
 
-            format_output(HTML_STYLE_CODES, opt_output, problems)
+            EvalResult {
+                output,
+                expr_type,
+                error,
+            }
         }
     }
 }
 
+/// Forget everything that was defined so far, like the `:reset` command
+pub fn reset() {
+    REPL_STATE.with(|repl_state_cell| {
+        *repl_state_cell.borrow_mut() = ReplState::new();
+    });
+}
+
+/// Returns the JS exception as an error, if the app could not be run
 async fn eval_wasm<'a>(
     arena: &'a Bump,
     target: Target,
     mono: MonomorphizedModule<'a>,
-) -> Option<ReplOutput> {
+) -> Result<Option<ReplOutput>, String> {
     let MonomorphizedModule {
         module_id,
         procedures,
@@ -239,15 +357,22 @@ async fn eval_wasm<'a>(
     let main_fn_var = *main_fn_var;
 
     // pretty-print the expr type string for later.
-    let expr_type = name_and_print_var(
+    let expr_type = name_and_print_var_qualified(
         main_fn_var,
         &mut subs,
         module_id,
         &interns,
         DebugPrint::NOTHING,
+        Qualification::Short,
     );
 
-    let (_, main_fn_layout) = *procedures.keys().find(|(s, _)| *s == main_fn_symbol)?;
+    let Some((_, main_fn_layout)) = procedures
+        .keys()
+        .find(|(s, _)| *s == main_fn_symbol)
+        .copied()
+    else {
+        return Ok(None);
+    };
 
     let app_module_bytes = {
         let env = roc_gen_wasm::Env {
@@ -293,12 +418,7 @@ async fn eval_wasm<'a>(
     // Send the compiled binary out to JS, which will asynchronously create an executable WebAssembly instance
     match js_create_app(&app_module_bytes).await {
         Ok(()) => {}
-        Err(js_exception) => {
-            return Some(ReplOutput {
-                expr: format!("<span class='color-red'>{js_exception:?}</span>"),
-                expr_type: String::new(),
-            })
-        }
+        Err(js_exception) => return Err(format!("{js_exception:?}")),
     }
 
     let mut app = WasmReplApp { arena };
@@ -320,5 +440,5 @@ async fn eval_wasm<'a>(
     // Transform the Expr to a string
     let expr = format_answer(arena, res_answer).to_string();
 
-    Some(ReplOutput { expr, expr_type })
+    Ok(Some(ReplOutput { expr, expr_type }))
 }
@@ -6,7 +6,8 @@ use roc_problem::can::Problem;
 use roc_region::all::LineInfo;
 use roc_solve_problem::TypeError;
 
-use crate::report::ANSI_STYLE_CODES;
+pub use crate::diagnostic::{Diagnostic, OutputFormat};
+use crate::report::{Palette, Report, RocDocAllocator, ANSI_STYLE_CODES};
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
@@ -29,11 +30,16 @@ impl Problems {
 
     // prints e.g. `1 error and 0 warnings found in 63 ms.`
     pub fn print_error_warning_count(&self, total_time: std::time::Duration) {
+        println!("{}", self.error_warning_count(total_time));
+    }
+
+    // e.g. `1 error and 0 warnings found in 63 ms`
+    pub fn error_warning_count(&self, total_time: std::time::Duration) -> String {
         const GREEN: &str = ANSI_STYLE_CODES.green;
         const YELLOW: &str = ANSI_STYLE_CODES.yellow;
         const RESET: &str = ANSI_STYLE_CODES.reset;
 
-        println!(
+        format!(
             "{}{}{} {} and {}{}{} {} found in {} ms",
             match self.errors {
                 0 => GREEN,
@@ -56,17 +62,100 @@ impl Problems {
                 _ => "warnings",
             },
             total_time.as_millis()
-        );
+        )
     }
 }
 
+/// Problems which have been rendered to strings (with terminal colors, or as JSON), but not
+/// printed yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderedProblems {
+    pub format: OutputFormat,
+    pub fatally_errored: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    /// Every problem (errors and warnings alike), in the order they were rendered
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl RenderedProblems {
+    pub fn problems(&self) -> Problems {
+        Problems {
+            fatally_errored: self.fatally_errored,
+            errors: self.errors.len(),
+            warnings: self.warnings.len(),
+        }
+    }
+
+    /// The reports we actually show: warnings are only shown if there are no errors.
+    pub fn shown(&self) -> &[String] {
+        if self.errors.is_empty() {
+            &self.warnings
+        } else {
+            &self.errors
+        }
+    }
+
+    pub fn print(&self) -> Problems {
+        if let OutputFormat::Json = self.format {
+            // Tools want every diagnostic, so don't hide warnings behind errors here.
+            for diagnostic in self.errors.iter().chain(self.warnings.iter()) {
+                println!("{diagnostic}");
+            }
+
+            return self.problems();
+        }
+
+        let shown = self.shown();
+
+        for report in shown.iter() {
+            println!("\n{report}\n");
+        }
+
+        // If we printed any problems, print a horizontal rule at the end,
+        // and then clear any ANSI escape codes (e.g. colors) we've used.
+        //
+        // The horizontal rule is nice when running the program right after
+        // compiling it, as it lets you clearly see where the compiler
+        // errors/warnings end and the program output begins.
+        if !shown.is_empty() {
+            print_horizontal_rule();
+        }
+
+        self.problems()
+    }
+}
+
+pub fn print_horizontal_rule() {
+    use crate::report::{Report, DEFAULT_PALETTE};
+
+    println!("{}\u{001B}[0m\n", Report::horizontal_rule(&DEFAULT_PALETTE));
+}
+
 pub fn report_problems(
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
 ) -> Problems {
-    use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
+    render_problems(
+        OutputFormat::Human,
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+    )
+    .print()
+}
+
+pub fn render_problems(
+    format: OutputFormat,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+) -> RenderedProblems {
+    use crate::report::{can_problem, type_problem, DEFAULT_PALETTE};
     use roc_problem::Severity::*;
 
     let palette = DEFAULT_PALETTE;
@@ -84,6 +173,7 @@ pub fn report_problems(
     // never need to re-allocate either the warnings or the errors vec!
     let mut warnings = Vec::with_capacity(total_problems);
     let mut errors = Vec::with_capacity(total_problems);
+    let mut diagnostics = Vec::with_capacity(total_problems);
     let mut fatally_errored = false;
 
     for (home, (module_path, src)) in sources.iter() {
@@ -99,11 +189,14 @@ pub fn report_problems(
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
+            let region = problem.region();
+
             if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
                 let severity = report.severity;
-                let mut buf = String::new();
+                let diagnostic = Diagnostic::from_report(&report, region, &lines);
+                let buf = render_report(format, report, &diagnostic, &alloc, &palette);
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                diagnostics.push(diagnostic);
 
                 match severity {
                     Warning => {
@@ -137,11 +230,13 @@ pub fn report_problems(
         ordered.extend(shadowing_errs);
 
         for problem in ordered.into_iter() {
+            let region = problem.region();
             let report = can_problem(&alloc, &lines, module_path.clone(), problem);
             let severity = report.severity;
-            let mut buf = String::new();
+            let diagnostic = Diagnostic::from_report(&report, region, &lines);
+            let buf = render_report(format, report, &diagnostic, &alloc, &palette);
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            diagnostics.push(diagnostic);
 
             match severity {
                 Warning => {
@@ -161,36 +256,30 @@ pub fn report_problems(
     debug_assert!(can_problems.is_empty() && type_problems.is_empty(), "After reporting problems, there were {:?} can_problems and {:?} type_problems that could not be reported because they did not have corresponding entries in `sources`.", can_problems.len(), type_problems.len());
     debug_assert_eq!(errors.len() + warnings.len(), total_problems);
 
-    let problems_reported;
+    RenderedProblems {
+        format,
+        fatally_errored,
+        errors,
+        warnings,
+        diagnostics,
+    }
+}
 
-    // Only print warnings if there are no errors
-    if errors.is_empty() {
-        problems_reported = warnings.len();
+fn render_report<'b>(
+    format: OutputFormat,
+    report: Report<'b>,
+    diagnostic: &Diagnostic,
+    alloc: &'b RocDocAllocator<'b>,
+    palette: &'b Palette,
+) -> String {
+    match format {
+        OutputFormat::Human => {
+            let mut buf = String::new();
 
-        for warning in warnings.iter() {
-            println!("\n{warning}\n");
-        }
-    } else {
-        problems_reported = errors.len();
+            report.render_color_terminal(&mut buf, alloc, palette);
 
-        for error in errors.iter() {
-            println!("\n{error}\n");
+            buf
         }
-    }
-
-    // If we printed any problems, print a horizontal rule at the end,
-    // and then clear any ANSI escape codes (e.g. colors) we've used.
-    //
-    // The horizontal rule is nice when running the program right after
-    // compiling it, as it lets you clearly see where the compiler
-    // errors/warnings end and the program output begins.
-    if problems_reported > 0 {
-        println!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
-    }
-
-    Problems {
-        fatally_errored,
-        errors: errors.len(),
-        warnings: warnings.len(),
+        OutputFormat::Json => diagnostic.to_json(),
     }
 }
@@ -0,0 +1,238 @@
+//! Machine-readable diagnostics, for editors and CI systems that would otherwise have to
+//! scrape the colored terminal output.
+use std::fmt;
+use std::path::PathBuf;
+
+use roc_problem::Severity;
+use roc_region::all::{LineInfo, Region};
+use serde_json::{json, Value};
+use ven_pretty::{Render, RenderAnnotated};
+
+use crate::error::code::code_for_title;
+use crate::report::{Annotation, CiWrite, Report};
+
+/// How problems should be written to stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Rendered reports, with terminal colors
+    #[default]
+    Human,
+    /// One JSON object per line (see [`Diagnostic::to_json`])
+    Json,
+}
+
+impl OutputFormat {
+    pub const NAMES: [&'static str; 2] = ["human", "json"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(OutputFormat::Human),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Prints text meant for people rather than tools. With JSON output, stdout must hold
+    /// nothing but diagnostics, so the text goes to stderr instead.
+    pub fn print_human(self, text: impl fmt::Display) {
+        match self {
+            OutputFormat::Human => print!("{text}"),
+            OutputFormat::Json => eprint!("{text}"),
+        }
+    }
+}
+
+/// A single problem, with everything a tool needs to show it somewhere other than a terminal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// A stable identifier for this kind of problem, if it has one
+    pub code: Option<&'static str>,
+    pub filename: Option<PathBuf>,
+    pub region: Option<DiagnosticRegion>,
+    pub title: String,
+    /// The report without its header, rendered without colors
+    pub body: String,
+    /// Replacement text the report suggests, e.g. the names offered for a typo
+    pub suggestions: Vec<String>,
+}
+
+/// Both byte offsets (which are convenient for tools that work on the raw source) and
+/// 1-based line and column numbers (which match the line numbers shown in reports).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiagnosticRegion {
+    pub start_offset: u32,
+    pub end_offset: u32,
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl DiagnosticRegion {
+    pub fn new(region: Region, lines: &LineInfo) -> Self {
+        let lc = lines.convert_region(region);
+
+        DiagnosticRegion {
+            start_offset: region.start().offset,
+            end_offset: region.end().offset,
+            start_line: lc.start.line + 1,
+            start_column: lc.start.column + 1,
+            end_line: lc.end.line + 1,
+            end_column: lc.end.column + 1,
+        }
+    }
+}
+
+impl Diagnostic {
+    pub fn from_report(report: &Report<'_>, region: Option<Region>, lines: &LineInfo) -> Self {
+        let err_msg = "<buffer is not a utf-8 encoded string>";
+
+        let mut body = String::new();
+        report
+            .doc
+            .1
+            .render_raw(70, &mut CiWrite::new(&mut body))
+            .expect(err_msg);
+
+        let mut suggestions = SuggestionWrite::default();
+        report
+            .doc
+            .1
+            .render_raw(70, &mut suggestions)
+            .expect(err_msg);
+
+        Diagnostic {
+            severity: report.severity,
+            code: code_for_title(&report.title),
+            filename: Some(report.filename.clone()),
+            region: region.map(|region| DiagnosticRegion::new(region, lines)),
+            title: report.title.clone(),
+            body: body.trim_end().to_string(),
+            suggestions: suggestions.finish(),
+        }
+    }
+
+    /// A problem that was already rendered to plain text by the loader (e.g. a parse error),
+    /// so all we have is its text.
+    pub fn from_formatted_report(report: &str) -> Self {
+        Diagnostic {
+            severity: Severity::Fatal,
+            code: None,
+            filename: None,
+            region: None,
+            title: String::new(),
+            body: report.trim().to_string(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Renders this diagnostic as a single-line JSON object, e.g.
+    ///
+    /// ```json
+    /// {"severity":"error","code":"E0135","filename":"main.roc","region":{"start":{"offset":10,"line":2,"column":5},"end":{"offset":13,"line":2,"column":8}},"title":"UNRECOGNIZED NAME","body":"...","suggestions":["foo"]}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let severity = match self.severity {
+            Severity::Fatal => "fatal",
+            Severity::RuntimeError => "error",
+            Severity::Warning => "warning",
+        };
+
+        let region = self.region.map(|region| {
+            json!({
+                "start": {
+                    "offset": region.start_offset,
+                    "line": region.start_line,
+                    "column": region.start_column,
+                },
+                "end": {
+                    "offset": region.end_offset,
+                    "line": region.end_line,
+                    "column": region.end_column,
+                },
+            })
+        });
+
+        let value: Value = json!({
+            "severity": severity,
+            "code": self.code,
+            "filename": self.filename.as_ref().map(|path| path.to_string_lossy()),
+            "region": region,
+            "title": self.title,
+            "body": self.body,
+            "suggestions": self.suggestions,
+        });
+
+        value.to_string()
+    }
+}
+
+/// Collects the text of every suggestion in a doc, ignoring everything else.
+#[derive(Default)]
+struct SuggestionWrite {
+    style_stack: Vec<Annotation>,
+    current: Option<String>,
+    suggestions: Vec<String>,
+}
+
+impl SuggestionWrite {
+    fn finish(mut self) -> Vec<String> {
+        self.suggestions.dedup();
+        self.suggestions
+    }
+}
+
+impl Render for SuggestionWrite {
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<usize, fmt::Error> {
+        self.write_str_all(s).map(|_| s.len())
+    }
+
+    fn write_str_all(&mut self, s: &str) -> fmt::Result {
+        if let Some(current) = self.current.as_mut() {
+            current.push_str(s);
+        }
+
+        Ok(())
+    }
+}
+
+impl RenderAnnotated<Annotation> for SuggestionWrite {
+    fn push_annotation(&mut self, annotation: &Annotation) -> Result<(), Self::Error> {
+        use Annotation::*;
+
+        if let TypoSuggestion | ParserSuggestion = annotation {
+            if self.current.is_none() {
+                self.current = Some(String::new());
+            }
+        }
+
+        self.style_stack.push(*annotation);
+        Ok(())
+    }
+
+    fn pop_annotation(&mut self) -> Result<(), Self::Error> {
+        use Annotation::*;
+
+        if let Some(TypoSuggestion | ParserSuggestion) = self.style_stack.pop() {
+            let still_nested = self
+                .style_stack
+                .iter()
+                .any(|annotation| matches!(annotation, TypoSuggestion | ParserSuggestion));
+
+            if !still_nested {
+                if let Some(suggestion) = self.current.take() {
+                    let suggestion = suggestion.trim();
+
+                    if !suggestion.is_empty() {
+                        self.suggestions.push(suggestion.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,8 +3,8 @@ use roc_module::ident::{Ident, Lowercase, ModuleName};
 use roc_module::symbol::{ScopeModuleSource, DERIVABLE_ABILITIES};
 use roc_problem::can::PrecedenceProblem::BothNonAssociative;
 use roc_problem::can::{
-    BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, IntErrorKind, Problem, RuntimeError,
-    ShadowKind,
+    BadPattern, CycleEntry, ExposedName, ExtensionTypeKind, FloatErrorKind, IntErrorKind, Problem,
+    RuntimeError, ShadowKind,
 };
 use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Region};
@@ -29,6 +29,8 @@ const UNDERSCORE_NOT_ALLOWED: &str = "UNDERSCORE NOT ALLOWED HERE";
 const UNUSED_ARG: &str = "UNUSED ARGUMENT";
 const MISSING_DEFINITION: &str = "MISSING DEFINITION";
 const UNKNOWN_GENERATES_WITH: &str = "UNKNOWN GENERATES FUNCTION";
+const MISSING_PROVIDED_DEFINITION: &str = "MISSING PROVIDED DEFINITION";
+const HOSTED_SIGNATURE_NOT_EFFECT: &str = "HOSTED SIGNATURE NOT AN EFFECT";
 const DUPLICATE_FIELD_NAME: &str = "DUPLICATE FIELD NAME";
 const DUPLICATE_TAG_NAME: &str = "DUPLICATE TAG NAME";
 const INVALID_UNICODE: &str = "INVALID UNICODE";
@@ -63,7 +65,6 @@ const QUALIFIED_ABILITY_IMPLEMENTATION: &str = "QUALIFIED ABILITY IMPLEMENTATION
 const ABILITY_IMPLEMENTATION_NOT_IDENTIFIER: &str = "ABILITY IMPLEMENTATION NOT IDENTIFIER";
 const DUPLICATE_IMPLEMENTATION: &str = "DUPLICATE IMPLEMENTATION";
 const UNNECESSARY_IMPLEMENTATIONS: &str = "UNNECESSARY IMPLEMENTATIONS";
-const INCOMPLETE_ABILITY_IMPLEMENTATION: &str = "INCOMPLETE ABILITY IMPLEMENTATION";
 
 pub fn can_problem<'b>(
     alloc: &'b RocDocAllocator<'b>,
@@ -287,6 +288,45 @@ pub fn can_problem<'b>(
 
             title = MISSING_DEFINITION.to_string();
         }
+        Problem::ProvidedButNotDefined(symbol) => {
+            doc = alloc.stack([
+                alloc.symbol_unqualified(symbol).append(alloc.reflow(
+                    " is listed in this platform's provides, but it isn't defined in this module.",
+                )),
+                alloc.reflow(
+                    "The host calls everything a platform provides, so without a definition it would fail to link.",
+                ),
+                alloc
+                    .reflow("You can fix this by adding a definition for ")
+                    .append(alloc.symbol_unqualified(symbol))
+                    .append(alloc.reflow(", or by removing it from "))
+                    .append(alloc.keyword("provides"))
+                    .append(alloc.reflow(".")),
+            ]);
+
+            title = MISSING_PROVIDED_DEFINITION.to_string();
+        }
+        Problem::HostedSignatureNotEffect {
+            symbol,
+            effect,
+            region,
+        } => {
+            doc = alloc.stack([
+                alloc
+                    .reflow("The signature of ")
+                    .append(alloc.symbol_unqualified(symbol))
+                    .append(alloc.reflow(" doesn't return an "))
+                    .append(alloc.symbol_unqualified(effect))
+                    .append(alloc.reflow(":")),
+                alloc.region(lines.convert_region(region)),
+                alloc
+                    .reflow("Signatures in a hosted module are implemented by the host, so they have to return ")
+                    .append(alloc.symbol_unqualified(effect))
+                    .append(alloc.reflow(", or be functions that return it.")),
+            ]);
+
+            title = HOSTED_SIGNATURE_NOT_EFFECT.to_string();
+        }
         Problem::UnknownGeneratesWith(loc_ident) => {
             doc = alloc.stack([
                 alloc
@@ -1136,30 +1176,6 @@ pub fn can_problem<'b>(
             ]);
             title = UNNECESSARY_IMPLEMENTATIONS.to_string();
         }
-        Problem::DoesNotImplementAbility {
-            region,
-            ability,
-            not_implemented,
-        } => {
-            doc = alloc.stack([
-                alloc.concat([
-                    alloc.reflow("This type does not fully implement the "),
-                    alloc.symbol_unqualified(ability),
-                    alloc.reflow(" ability:"),
-                ]),
-                alloc.region(lines.convert_region(region)),
-                alloc.reflow("The following necessary members are missing implementations:"),
-                alloc.type_block(
-                    alloc.intersperse(
-                        not_implemented
-                            .into_iter()
-                            .map(|sym| alloc.symbol_unqualified(sym)),
-                        alloc.string(",".to_string()).append(alloc.space()),
-                    ),
-                ),
-            ]);
-            title = INCOMPLETE_ABILITY_IMPLEMENTATION.to_string();
-        }
         Problem::NotBoundInAllPatterns {
             unbound_symbol,
             region,
@@ -1945,6 +1961,9 @@ fn pretty_runtime_error<'b>(
             imported_modules,
             region,
             module_exists,
+            import_name,
+            ident,
+            exposed_names,
         } => {
             doc = module_not_found(
                 alloc,
@@ -1953,6 +1972,9 @@ fn pretty_runtime_error<'b>(
                 &module_name,
                 imported_modules,
                 module_exists,
+                &import_name,
+                &ident,
+                exposed_names,
             );
 
             title = MODULE_NOT_IMPORTED;
@@ -2551,11 +2573,36 @@ fn module_not_found<'b>(
     name: &ModuleName,
     options: MutSet<Box<str>>,
     module_exists: bool,
+    import_name: &str,
+    ident: &Ident,
+    exposed_names: Vec<ExposedName>,
 ) -> RocDocBuilder<'b> {
-    // If the module exists, sugguest that the user import it
-    let details = if module_exists {
-        // TODO:  Maybe give an example of how to do that
-        alloc.reflow("Did you mean to import it?")
+    let details = if let Some(exposed) = closest_exposed_name(name, ident, exposed_names) {
+        // Another module exposes almost exactly this name, so that's probably what was meant
+        let qualified = format!("{}.{}", exposed.module_name, exposed.ident);
+
+        if exposed.imported {
+            alloc.concat([
+                alloc.reflow("Did you mean `"),
+                alloc.string(qualified),
+                alloc.reflow("`?"),
+            ])
+        } else {
+            alloc.stack([
+                alloc.concat([
+                    alloc.reflow("Did you mean `"),
+                    alloc.string(qualified),
+                    alloc.reflow("`? To use it, add this import up-top:"),
+                ]),
+                import_line(alloc, &exposed.import_name),
+            ])
+        }
+    } else if module_exists {
+        // If the module exists, sugguest that the user import it
+        alloc.stack([
+            alloc.reflow("Did you mean to import it? Add this import up-top:"),
+            import_line(alloc, import_name),
+        ])
     } else {
         // If the module might not exist, sugguest that it's a typo
         let mut suggestions =
@@ -2591,3 +2638,43 @@ fn module_not_found<'b>(
         details,
     ])
 }
+
+/// The name another module exposes that's closest to `module_name.ident`, if any is close
+/// enough to plausibly be what was meant.
+fn closest_exposed_name(
+    module_name: &ModuleName,
+    ident: &Ident,
+    exposed_names: Vec<ExposedName>,
+) -> Option<ExposedName> {
+    // Beyond this many edits, a suggestion is more likely to confuse than to help
+    const MAX_DISTANCE: usize = 2;
+
+    let typo = format!("{}.{}", module_name, ident);
+    let is_type_name = ident.as_str().starts_with(char::is_uppercase);
+
+    exposed_names
+        .into_iter()
+        .filter(|exposed| {
+            exposed.ident.as_str().starts_with(char::is_uppercase) == is_type_name
+                // A module that's imported under an alias can still end up here
+                && !(exposed.imported && &exposed.module_name == module_name)
+        })
+        .map(|exposed| {
+            let qualified = format!("{}.{}", exposed.module_name, exposed.ident);
+
+            (distance::damerau_levenshtein(&typo, &qualified), exposed)
+        })
+        .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, exposed)| exposed)
+}
+
+fn import_line<'b>(alloc: &'b RocDocAllocator<'b>, import_name: &str) -> RocDocBuilder<'b> {
+    alloc
+        .concat([
+            alloc.keyword("import"),
+            alloc.text(" "),
+            alloc.string(import_name.to_string()),
+        ])
+        .indent(4)
+}
@@ -0,0 +1,1189 @@
+//! Stable codes for the problems we report, and the extended explanations printed by
+//! `roc explain`.
+//!
+//! Codes are assigned per report title, since the title is what identifies a kind of
+//! problem to the user. Once published, a code must keep meaning the same thing: when a
+//! new kind of problem is added, append it to [`ERROR_CODES`] with the next free number,
+//! and never reuse the number of a problem that was removed.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    /// e.g. `E0107`
+    pub code: &'static str,
+    /// The title of the reports that have this code, e.g. `TYPE MISMATCH`
+    pub title: &'static str,
+    /// One sentence describing the problem
+    pub summary: &'static str,
+    /// A longer description with examples, for the most common problems
+    pub explanation: Option<&'static str>,
+}
+
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E0001",
+        title: "ABILITY HAS TYPE VARIABLES",
+        summary: "An ability definition introduces type variables, but abilities can only be parameterized by the type implementing them.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0002",
+        title: "ABILITY IMPLEMENTATION NOT IDENTIFIER",
+        summary: "An ability implementation in an `implements` clause is something other than a plain identifier.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0003",
+        title: "ABILITY MEMBER BINDS MULTIPLE VARIABLES",
+        summary: "An ability member's type binds more than one type variable to the ability.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0004",
+        title: "ABILITY MEMBER MISSING IMPLEMENTS CLAUSE",
+        summary: "An ability member's type doesn't say which type variable implements the ability.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0005",
+        title: "ABILITY NOT ON TOP-LEVEL",
+        summary: "An ability is defined somewhere other than the top level of a module.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0006",
+        title: "ABILITY USED AS TYPE",
+        summary: "An ability is used where a type was expected.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0007",
+        title: "ARGUMENTS BEFORE EQUALS",
+        summary: "A definition has arguments before its `=`, but not after a plain name on the same line.",
+        explanation: Some(include_str!("explanations/E0007.md")),
+    },
+    ErrorCode {
+        code: "E0008",
+        title: "ASCII CONTROL CHARACTER",
+        summary: "The source contains an ASCII control character, which is not allowed outside of string escapes.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0009",
+        title: "BAD BACKPASSING ARROW",
+        summary: "A backpassing arrow `<-` is used somewhere it isn't allowed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0010",
+        title: "BAD OPTIONAL VALUE",
+        summary: "A record field uses `?` to give a default value outside of a record destructure.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0011",
+        title: "BAD RECORD BUILDER",
+        summary: "A record builder field is used somewhere a record builder isn't allowed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0012",
+        title: "BAD RECORD UPDATE",
+        summary: "A record update uses something other than a plain variable as the record being updated.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0013",
+        title: "BAD REQUIRES",
+        summary: "The `requires` section of a platform header is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0014",
+        title: "BAD REQUIRES RIGIDS",
+        summary: "The `requires` section of a platform header contains something other than type variable names.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0015",
+        title: "BAD TYPE VARIABLE",
+        summary: "A type variable in a type annotation is not a valid lowercase name.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0016",
+        title: "CIRCULAR DEFINITION",
+        summary: "A value is defined in terms of itself, without going through a function.",
+        explanation: Some(include_str!("explanations/E0016.md")),
+    },
+    ErrorCode {
+        code: "E0017",
+        title: "CIRCULAR TYPE",
+        summary: "Type inference found a type that would have to contain itself, such as a list that contains itself.",
+        explanation: Some(include_str!("explanations/E0017.md")),
+    },
+    ErrorCode {
+        code: "E0018",
+        title: "CONFLICTING NUMBER SUFFIX",
+        summary: "A number literal's suffix (e.g. `u8`) contradicts the kind of number it is.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0019",
+        title: "CYCLIC ALIAS",
+        summary: "A type alias refers to itself; recursive types need to be tag unions or opaque types.",
+        explanation: Some(include_str!("explanations/E0019.md")),
+    },
+    ErrorCode {
+        code: "E0020",
+        title: "DEFINITION ONLY USED IN RECURSION",
+        summary: "A definition is only ever used by itself (or the other definitions in its cycle), so it can be removed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0021",
+        title: "DEGENERATE BRANCH",
+        summary: "A branch of a pattern match binds variables that are not used consistently.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0022",
+        title: "DOUBLE COMMA",
+        summary: "Two commas appear in a row.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0023",
+        title: "DOUBLE DOT",
+        summary: "Two dots appear in a row in a name, e.g. `Foo..bar`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0024",
+        title: "DUPLICATE BOUND ABILITY",
+        summary: "The same ability is listed more than once in an `implements` clause.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0025",
+        title: "DUPLICATE FIELD NAME",
+        summary: "A record has the same field more than once.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0026",
+        title: "DUPLICATE IMPLEMENTATION",
+        summary: "An ability member is implemented more than once for the same type.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0027",
+        title: "DUPLICATE NAME",
+        summary: "A name is defined again in a scope where it is already defined; Roc does not allow shadowing.",
+        explanation: Some(include_str!("explanations/E0027.md")),
+    },
+    ErrorCode {
+        code: "E0028",
+        title: "DUPLICATE TAG NAME",
+        summary: "A tag union type has the same tag more than once.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0029",
+        title: "EMPTY PARENTHESES",
+        summary: "Empty parentheses `()` are used as a value or type; Roc uses `{}` for the empty record instead.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0030",
+        title: "END OF FILE",
+        summary: "The file ended in the middle of something, such as an unfinished expression.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0031",
+        title: "ENDLESS FORMAT",
+        summary: "A string interpolation is missing its closing parenthesis.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0032",
+        title: "ENDLESS SCALAR",
+        summary: "A single-quoted scalar literal is missing its closing quote.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0033",
+        title: "ENDLESS STRING",
+        summary: "A string literal is missing its closing quote.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0034",
+        title: "EXPECT FAILED",
+        summary: "An `expect` evaluated to `Bool.false`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0035",
+        title: "EXPECT PANICKED",
+        summary: "Evaluating an `expect` crashed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0036",
+        title: "EXPECTED STRING",
+        summary: "A string literal was expected, such as the name of an app or a package URL.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0037",
+        title: "EXPLICIT BUILTIN IMPORT",
+        summary: "A builtin module is imported explicitly; builtins are always available without importing them.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0038",
+        title: "IF GUARD NO CONDITION",
+        summary: "An `if` guard in a `when` branch has no condition after the `if`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0039",
+        title: "ILLEGAL DERIVE",
+        summary: "An ability is listed in an opaque type's `implements` clause but cannot be derived automatically.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0040",
+        title: "ILLEGAL IMPLEMENTS CLAUSE",
+        summary: "An `implements` clause appears somewhere it isn't allowed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0041",
+        title: "ILLEGAL SPECIALIZATION",
+        summary: "An ability member is specialized for a type that isn't an opaque type defined in this module.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0042",
+        title: "IMPLEMENTATION NOT FOUND",
+        summary: "An ability implementation refers to a function that doesn't exist.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0043",
+        title: "IMPLEMENTS CLAUSE IS NOT AN ABILITY",
+        summary: "An `implements` clause lists something that isn't an ability.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0044",
+        title: "IMPORT NAME CONFLICT",
+        summary: "Two imports bring the same name into scope.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0045",
+        title: "INCOMPLETE ABILITY IMPLEMENTATION",
+        summary: "A type claims to implement an ability, but doesn't implement all of its members.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0046",
+        title: "INCOMPLETE HEADER",
+        summary: "A module header is missing a required section.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0047",
+        title: "INCORRECT REST PATTERN",
+        summary: "A list rest pattern `..` is written incorrectly.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0048",
+        title: "INDENT ENDS AFTER EXPRESSION",
+        summary: "The indentation ends right after an expression that needs to be followed by something else.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0049",
+        title: "INGESTED FILE ERROR",
+        summary: "A file that was imported with `import \"...\" as` could not be read.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0050",
+        title: "INSUFFICIENT INDENT IN MULTI-LINE STRING",
+        summary: "A line of a multi-line string is indented less than its opening quotes.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0051",
+        title: "INVALID NUMBER LITERAL",
+        summary: "A number literal is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0052",
+        title: "INVALID PACKAGE NAME",
+        summary: "A package name in a header is not valid.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0053",
+        title: "INVALID PLATFORM NAME",
+        summary: "A platform name in a header is not valid.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0054",
+        title: "INVALID SCALAR",
+        summary: "A single-quoted literal does not contain exactly one Unicode scalar value.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0055",
+        title: "INVALID TYPE FOR INGESTED FILE",
+        summary: "A file imported with `import \"...\" as` is given a type it can't be loaded as.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0056",
+        title: "INVALID UNICODE",
+        summary: "A Unicode escape in a string is not a valid code point.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0057",
+        title: "INVALID_EXTENSION_TYPE",
+        summary: "A record or tag union type is extended with something that isn't a record or tag union.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0058",
+        title: "LOWERCASE ALIAS",
+        summary: "A type alias name starts with a lowercase letter.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0059",
+        title: "MISPLACED CARRIAGE RETURN",
+        summary: "A carriage return character appears somewhere other than right before a newline.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0060",
+        title: "MISSING ARROW",
+        summary: "A function type or lambda is missing its `->`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0061",
+        title: "MISSING DEFINITION",
+        summary: "A module exposes a name that it doesn't define.",
+        explanation: Some(include_str!("explanations/E0061.md")),
+    },
+    ErrorCode {
+        code: "E0062",
+        title: "MISSING EXPRESSION",
+        summary: "An expression was expected, but there was none.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0063",
+        title: "MISSING FINAL EXPRESSION",
+        summary: "A block of definitions is not followed by the expression it evaluates to.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0064",
+        title: "MISSING HEADER",
+        summary: "A file that must have a module header doesn't have one.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0065",
+        title: "MISSING PACKAGES",
+        summary: "A header is missing its `packages` section.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0066",
+        title: "MISSING REQUIRES",
+        summary: "A platform header is missing its `requires` section.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0067",
+        title: "MODULE NOT IMPORTED",
+        summary: "A qualified name refers to a module that hasn't been imported.",
+        explanation: Some(include_str!("explanations/E0067.md")),
+    },
+    ErrorCode {
+        code: "E0068",
+        title: "MULTIPLE LIST REST PATTERNS",
+        summary: "A list pattern has more than one rest pattern `..`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0069",
+        title: "MULTIPLE RECORD BUILDERS",
+        summary: "A function is applied to more than one record builder.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0070",
+        title: "NAME NOT BOUND IN ALL PATTERNS",
+        summary: "A `when` branch with alternative patterns binds a name in some alternatives but not others.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0071",
+        title: "NAMING PROBLEM",
+        summary: "A name is not valid where it appears.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0072",
+        title: "NEED MORE INDENTATION",
+        summary: "Something needs to be indented further than it is.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0073",
+        title: "NESTED DATATYPE",
+        summary: "A recursive type alias refers to itself with different type arguments.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0074",
+        title: "NOT AN ABILITY",
+        summary: "Something is used as an ability, but it isn't one.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0075",
+        title: "NOT AN ABILITY MEMBER",
+        summary: "An ability implementation lists a name that isn't a member of the ability.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0076",
+        title: "NOT AN INLINE ALIAS",
+        summary: "An inline `as` alias in a type annotation is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0077",
+        title: "NOT END OF FILE",
+        summary: "The parser finished a module's contents but there was more text after it.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0078",
+        title: "NOT EXPOSED",
+        summary: "A name is used from another module, but that module doesn't expose it.",
+        explanation: Some(include_str!("explanations/E0078.md")),
+    },
+    ErrorCode {
+        code: "E0079",
+        title: "NUMBER OVERFLOWS SUFFIX",
+        summary: "A number literal is too big for the type its suffix asks for.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0080",
+        title: "NUMBER UNDERFLOWS SUFFIX",
+        summary: "A number literal is too small for the type its suffix asks for.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0081",
+        title: "OPAQUE TYPE APPLIED TO TOO MANY ARGS",
+        summary: "An opaque type wrapper `@Foo` is given more than one argument.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0082",
+        title: "OPAQUE TYPE DECLARED OUTSIDE SCOPE",
+        summary: "An opaque type wrapper `@Foo` is used outside the module that defines the opaque type.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0083",
+        title: "OPAQUE TYPE NOT APPLIED",
+        summary: "An opaque type wrapper `@Foo` is used without an argument.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0084",
+        title: "OPAQUE TYPE NOT DEFINED",
+        summary: "An opaque type wrapper `@Foo` refers to an opaque type that doesn't exist.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0085",
+        title: "OPTIONAL ABILITY IMPLEMENTATION",
+        summary: "An ability implementation is marked optional with `?`, which isn't allowed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0086",
+        title: "OVERAPPLIED CRASH",
+        summary: "`crash` is given more than one argument.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0087",
+        title: "OVERLOADED SPECIALIZATION",
+        summary: "An ability member specialization is claimed for more than one opaque type.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0088",
+        title: "PARSE PROBLEM",
+        summary: "The parser got stuck on something it didn't expect.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0089",
+        title: "PROBLEM IN RECORD PATTERN",
+        summary: "A record destructuring pattern is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0090",
+        title: "PROBLEM IN RECORD TYPE",
+        summary: "A record type is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0091",
+        title: "QUALIFIED ABILITY IMPLEMENTATION",
+        summary: "An ability implementation refers to a function in another module.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0092",
+        title: "QUALIFIED ALIAS NAME",
+        summary: "A type alias is defined with a qualified name, e.g. `Foo.Bar : ...`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0093",
+        title: "RECORD BUILDER IN MODULE PARAMS",
+        summary: "A record builder is used to pass module params.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0094",
+        title: "RECORD PARSE PROBLEM",
+        summary: "A record expression is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0095",
+        title: "RECORD UPDATE IN MODULE PARAMS",
+        summary: "A record update is used to pass module params.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0096",
+        title: "REDUNDANT PATTERN",
+        summary: "A `when` branch can never match, because the branches before it already match everything it would.",
+        explanation: Some(include_str!("explanations/E0096.md")),
+    },
+    ErrorCode {
+        code: "E0097",
+        title: "SPECIALIZATION NOT ON TOP-LEVEL",
+        summary: "An ability member is specialized somewhere other than the top level of a module.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0098",
+        title: "SYNTAX PROBLEM",
+        summary: "Something is syntactically valid, but not allowed where it appears.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0099",
+        title: "TAB CHARACTER",
+        summary: "The source contains a tab character; Roc only allows spaces for indentation.",
+        explanation: Some(include_str!("explanations/E0099.md")),
+    },
+    ErrorCode {
+        code: "E0100",
+        title: "TOO FEW ARGS",
+        summary: "A function or tag is applied to fewer arguments than it takes.",
+        explanation: Some(include_str!("explanations/E0100.md")),
+    },
+    ErrorCode {
+        code: "E0101",
+        title: "TOO FEW TYPE ARGUMENTS",
+        summary: "A type is given fewer type arguments than it takes.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0102",
+        title: "TOO MANY ARGS",
+        summary: "A function or tag is applied to more arguments than it takes.",
+        explanation: Some(include_str!("explanations/E0102.md")),
+    },
+    ErrorCode {
+        code: "E0103",
+        title: "TOO MANY TYPE ARGUMENTS",
+        summary: "A type is given more type arguments than it takes.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0104",
+        title: "TRAILING DOT",
+        summary: "A name ends with a dot, e.g. `Str.`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0105",
+        title: "TRAILING OPERATOR",
+        summary: "A binary operator is missing its right-hand side.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0106",
+        title: "TYPE ARGUMENT NOT LOWERCASE",
+        summary: "A type alias's parameter is not a lowercase type variable.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0107",
+        title: "TYPE MISMATCH",
+        summary: "Two types that need to be the same are different.",
+        explanation: Some(include_str!("explanations/E0107.md")),
+    },
+    ErrorCode {
+        code: "E0108",
+        title: "UNAPPLIED CRASH",
+        summary: "`crash` is used without a message.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0109",
+        title: "UNAPPLIED RECORD BUILDER",
+        summary: "A record builder is used without being passed to a function.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0110",
+        title: "UNDECLARED TYPE VARIABLE",
+        summary: "A type alias uses a type variable that isn't one of its parameters.",
+        explanation: Some(include_str!("explanations/E0110.md")),
+    },
+    ErrorCode {
+        code: "E0111",
+        title: "UNDERSCORE NOT ALLOWED HERE",
+        summary: "An underscore `_` is used in a type where it isn't allowed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0112",
+        title: "UNEXPECTED ARROW",
+        summary: "A `->` appears somewhere it isn't expected.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0113",
+        title: "UNEXPECTED COMMA",
+        summary: "A `,` appears somewhere it isn't expected.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0114",
+        title: "UNFINISHED ABILITY",
+        summary: "An ability definition is incomplete.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0115",
+        title: "UNFINISHED ARGUMENT LIST",
+        summary: "A lambda's argument list is incomplete.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0116",
+        title: "UNFINISHED FUNCTION",
+        summary: "A lambda is missing its body.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0117",
+        title: "UNFINISHED IF",
+        summary: "An `if` expression is missing its `then` or `else` branch.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0118",
+        title: "UNFINISHED IMPORT",
+        summary: "An `import` is incomplete.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0119",
+        title: "UNFINISHED INLINE ALIAS",
+        summary: "An inline `as` alias in a type annotation is incomplete.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0120",
+        title: "UNFINISHED LIST",
+        summary: "A list is missing its closing `]`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0121",
+        title: "UNFINISHED LIST PATTERN",
+        summary: "A list pattern is missing its closing `]`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0122",
+        title: "UNFINISHED PARENTHESES",
+        summary: "Parentheses are missing their closing `)`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0123",
+        title: "UNFINISHED PATTERN",
+        summary: "A pattern is incomplete.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0124",
+        title: "UNFINISHED RECORD PATTERN",
+        summary: "A record pattern is missing its closing `}`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0125",
+        title: "UNFINISHED RECORD TYPE",
+        summary: "A record type is missing its closing `}`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0126",
+        title: "UNFINISHED TAG UNION TYPE",
+        summary: "A tag union type is missing its closing `]`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0127",
+        title: "UNFINISHED TYPE",
+        summary: "A type annotation is incomplete.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0128",
+        title: "UNFINISHED WHEN",
+        summary: "A `when` expression is missing its `is` or its branches.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0129",
+        title: "UNKNOWN GENERATES FUNCTION",
+        summary: "A platform's `generates ... with` lists a function it doesn't define.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0130",
+        title: "UNKNOWN OPERATOR",
+        summary: "A sequence of symbols is not an operator Roc knows about.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0131",
+        title: "UNMATCHABLE PATTERN",
+        summary: "A pattern can never match any value of the type being matched on.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0132",
+        title: "UNNECESSARY DEFINITION",
+        summary: "A definition doesn't introduce any names, so it can't affect the program.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0133",
+        title: "UNNECESSARY IMPLEMENTATIONS",
+        summary: "An opaque type implements ability members explicitly for an ability that is derived.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0134",
+        title: "UNNECESSARY WILDCARD",
+        summary: "A tag union type ends in `*` in a place where it has no effect.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0135",
+        title: "UNRECOGNIZED NAME",
+        summary: "A name is used that isn't defined or imported.",
+        explanation: Some(include_str!("explanations/E0135.md")),
+    },
+    ErrorCode {
+        code: "E0136",
+        title: "UNSAFE PATTERN",
+        summary: "A `when` doesn't cover every possible value, or a destructure can fail.",
+        explanation: Some(include_str!("explanations/E0136.md")),
+    },
+    ErrorCode {
+        code: "E0137",
+        title: "UNUSED ARGUMENT",
+        summary: "A function argument is never used.",
+        explanation: Some(include_str!("explanations/E0137.md")),
+    },
+    ErrorCode {
+        code: "E0138",
+        title: "UNUSED DEFINITION",
+        summary: "A definition is never used.",
+        explanation: Some(include_str!("explanations/E0138.md")),
+    },
+    ErrorCode {
+        code: "E0139",
+        title: "UNUSED IMPORT",
+        summary: "An import is never used.",
+        explanation: Some(include_str!("explanations/E0139.md")),
+    },
+    ErrorCode {
+        code: "E0140",
+        title: "UNUSED TYPE ALIAS PARAMETER",
+        summary: "A type alias has a parameter that it never uses.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0141",
+        title: "WEIRD APP NAME",
+        summary: "The name of an app in its header is not a string.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0142",
+        title: "WEIRD ARROW",
+        summary: "An arrow is written incorrectly, e.g. `=>` instead of `->`.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0143",
+        title: "WEIRD CODE POINT",
+        summary: "A Unicode escape in a string is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0144",
+        title: "WEIRD ESCAPE",
+        summary: "A string contains a backslash escape Roc doesn't know about.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0145",
+        title: "WEIRD EXPOSES",
+        summary: "The `exposes` section of a header is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0146",
+        title: "WEIRD EXPOSING",
+        summary: "The `exposing` list of an import is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0147",
+        title: "WEIRD GENERATED TYPE NAME",
+        summary: "The type name in a platform's `generates` section is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0148",
+        title: "WEIRD GENERATES",
+        summary: "The `generates` section of a platform header is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0149",
+        title: "WEIRD IDENTIFIER",
+        summary: "An identifier is malformed, e.g. it mixes dots and other symbols.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0150",
+        title: "WEIRD IMPORTS",
+        summary: "The `imports` section of a header is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0151",
+        title: "WEIRD MODULE NAME",
+        summary: "A module name is not a valid uppercase name.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0152",
+        title: "WEIRD MODULE PARAMS",
+        summary: "The params of a module header are malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0153",
+        title: "WEIRD PACKAGES LIST",
+        summary: "The `packages` section of a header is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0154",
+        title: "WEIRD PROVIDES",
+        summary: "The `provides` section of a header is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0155",
+        title: "WEIRD QUALIFIED NAME",
+        summary: "A qualified name is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0156",
+        title: "WEIRD TAG NAME",
+        summary: "A tag name is malformed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0157",
+        title: "WILDCARD NOT ALLOWED HERE",
+        summary: "A `*` wildcard is used in a type where it isn't allowed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0158",
+        title: "WRONG SPECIALIZATION TYPE",
+        summary: "An ability member specialization has a type that doesn't match the ability member's type.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0159",
+        title: "EXPECT LEAKED MEMORY",
+        summary: "An `expect` passed, but didn't free everything it allocated (reported by `roc test --leak-check`).",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0160",
+        title: "FILE NOT FOUND",
+        summary: "A file needed to compile the program doesn't exist.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0161",
+        title: "FILE PERMISSION DENIED",
+        summary: "A file needed to compile the program can't be read because of its permissions.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0162",
+        title: "FILE PROBLEM",
+        summary: "A file needed to compile the program couldn't be read.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0163",
+        title: "FILE TOO LARGE",
+        summary: "A package being downloaded is larger than the maximum size Roc can handle.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0164",
+        title: "HOSTED SIGNATURE NOT AN EFFECT",
+        summary: "A definition in a hosted module has a type that isn't an effect.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0165",
+        title: "HTTP ERROR",
+        summary: "Downloading a package failed with an HTTP error.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0166",
+        title: "HTTPS MANDATORY",
+        summary: "A package URL doesn't use `https`, which is required for downloads.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0167",
+        title: "IMPORT CYCLE",
+        summary: "Modules import each other in a cycle.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0168",
+        title: "INCORRECT MODULE NAME",
+        summary: "A module's name doesn't match the name its path and imports say it should have.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0169",
+        title: "INVALID CONTENT HASH",
+        summary: "A downloaded package's contents don't match the hash in its URL.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0170",
+        title: "INVALID DOCS LINK",
+        summary: "A link in a doc comment refers to something that isn't exposed or isn't in scope.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0171",
+        title: "INVALID EXTENSION",
+        summary: "A package URL doesn't end in a supported archive extension.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0172",
+        title: "INVALID EXTENSION SUFFIX",
+        summary: "A package URL ends in an archive extension Roc doesn't support.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0173",
+        title: "INVALID FRAGMENT",
+        summary: "The fragment (the part after `#`) of a package URL doesn't name a `.roc` file.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0174",
+        title: "IO ERROR",
+        summary: "An I/O error happened while downloading or reading a package.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0175",
+        title: "MISLEADING CHARACTERS",
+        summary: "A package URL contains characters that look like others but aren't the same.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0176",
+        title: "MISSING PACKAGE HASH",
+        summary: "A package URL doesn't include the hash of the package's contents.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0177",
+        title: "MISSING PROVIDED DEFINITION",
+        summary: "An app or platform provides a name it doesn't define.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0178",
+        title: "MODULE NOT FOUND",
+        summary: "An imported module has no file where its name says it should be.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0179",
+        title: "MULTIPLE ENCODINGS",
+        summary: "The server sent a package with more than one content encoding.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0180",
+        title: "MULTIPLE PLATFORMS",
+        summary: "An app names more than one platform.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0181",
+        title: "NO PLATFORM",
+        summary: "An app's platform couldn't be found.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0182",
+        title: "NOT A ROC FILE",
+        summary: "A file given to Roc isn't a `.roc` file and doesn't start with a Roc shebang.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0183",
+        title: "NOTFOUND",
+        summary: "A package URL returned 404 Not Found.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0184",
+        title: "PROPERTY FAILED",
+        summary: "A property failed for some generated arguments (reported by `roc test --fuzz`).",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0185",
+        title: "SNAPSHOT MISMATCH",
+        summary: "A value checked by `Inspect.expectSnapshot` doesn't match its stored snapshot.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0186",
+        title: "UNEXPECTED PASS",
+        summary: "An `expect` marked `# roc-test: xfail` passed.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0187",
+        title: "UNSPECIFIED PLATFORM",
+        summary: "An app doesn't say which platform it uses.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0188",
+        title: "UNSUPPORTED ENCODING",
+        summary: "The server sent a package with a content encoding Roc doesn't support.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0189",
+        title: "UNSUPPORTED PROPERTY",
+        summary: "A property's arguments are of types `roc test --fuzz` can't generate.",
+        explanation: None,
+    },
+    ErrorCode {
+        code: "E0190",
+        title: "UNUSABLE SNAPSHOT",
+        summary: "The snapshot for an `Inspect.expectSnapshot` couldn't be read or written.",
+        explanation: None,
+    },
+];
+
+/// The code for reports with the given title, if that kind of problem has one.
+pub fn code_for_title(title: &str) -> Option<&'static str> {
+    // A few titles are pluralized depending on how many things the report is about.
+    let title = match title {
+        "DEFINITIONs ONLY USED IN RECURSION" => "DEFINITION ONLY USED IN RECURSION",
+        other => other,
+    };
+
+    ERROR_CODES
+        .iter()
+        .find(|error_code| error_code.title == title)
+        .map(|error_code| error_code.code)
+}
+
+/// Looks up a code the way a user might type it: `E0107`, `e0107`, or just `107`.
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    let digits = code
+        .strip_prefix('E')
+        .or_else(|| code.strip_prefix('e'))
+        .unwrap_or(code);
+    let number: u32 = digits.parse().ok()?;
+
+    ERROR_CODES
+        .iter()
+        .find(|error_code| error_code.code[1..].parse() == Ok(number))
+}
@@ -0,0 +1,207 @@
+//! Structural differences between two values that were expected to be equal, so that a
+//! failed `expect a == b` can point at the record fields and list elements that differ
+//! instead of leaving the reader to compare two large values by eye.
+use bumpalo::Bump;
+use roc_fmt::annotation::Formattable;
+use roc_parse::ast::{AssignedField, Expr};
+use roc_region::all::Loc;
+
+/// Don't bury the report under differences; the first few are usually enough to go on.
+const MAX_DIFFERENCES: usize = 10;
+
+/// One place where the two values differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    /// Where in the values this is, e.g. `.name` or `.items[2]`; empty if the values
+    /// differ as a whole
+    pub path: String,
+    pub kind: DifferenceKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DifferenceKind {
+    Changed {
+        left: String,
+        right: String,
+    },
+    /// A record field or list element that only the left value has
+    OnlyLeft(String),
+    /// A record field or list element that only the right value has
+    OnlyRight(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValueDiff {
+    /// At most [`MAX_DIFFERENCES`] of them
+    pub differences: Vec<Difference>,
+    /// How many differences did not fit
+    pub omitted: usize,
+    /// How many parts (fields, elements, payloads) of the values are the same
+    pub unchanged: usize,
+}
+
+/// All differences between two values, as rendered by the expect runner.
+pub fn diff_values(arena: &Bump, left: &Expr<'_>, right: &Expr<'_>) -> ValueDiff {
+    let mut differ = Differ {
+        arena,
+        diff: ValueDiff::default(),
+    };
+
+    differ.diff(&mut String::new(), left, right);
+
+    differ.diff
+}
+
+struct Differ<'r> {
+    arena: &'r Bump,
+    diff: ValueDiff,
+}
+
+impl<'r> Differ<'r> {
+    fn diff(&mut self, path: &mut String, left: &Expr<'_>, right: &Expr<'_>) {
+        use Expr::*;
+
+        if self.format(left) == self.format(right) {
+            self.diff.unchanged += 1;
+
+            return;
+        }
+
+        match (strip(left), strip(right)) {
+            (Record(left_fields), Record(right_fields)) => {
+                let left_fields: Vec<_> = left_fields.items.iter().filter_map(field).collect();
+                let right_fields: Vec<_> = right_fields.items.iter().filter_map(field).collect();
+
+                for (label, left_value) in left_fields.iter() {
+                    let len = path.len();
+                    path.push('.');
+                    path.push_str(label);
+
+                    match right_fields.iter().find(|(other, _)| other == label) {
+                        Some((_, right_value)) => self.diff(path, left_value, right_value),
+                        None => {
+                            self.push(path, DifferenceKind::OnlyLeft(self.format(left_value)));
+                        }
+                    }
+
+                    path.truncate(len);
+                }
+
+                for (label, right_value) in right_fields.iter() {
+                    if !left_fields.iter().any(|(other, _)| other == label) {
+                        let len = path.len();
+                        path.push('.');
+                        path.push_str(label);
+
+                        self.push(path, DifferenceKind::OnlyRight(self.format(right_value)));
+
+                        path.truncate(len);
+                    }
+                }
+            }
+            (List(left_elems), List(right_elems)) => {
+                self.diff_elements(path, left_elems.items, right_elems.items, |index| {
+                    format!("[{index}]")
+                });
+            }
+            (Tuple(left_elems), Tuple(right_elems)) => {
+                self.diff_elements(path, left_elems.items, right_elems.items, |index| {
+                    format!(".{index}")
+                });
+            }
+            (Apply(left_tag, left_args, _), Apply(right_tag, right_args, _))
+                if tag_name(&left_tag.value).is_some()
+                    && tag_name(&left_tag.value) == tag_name(&right_tag.value)
+                    && left_args.len() == right_args.len() =>
+            {
+                let name = tag_name(&left_tag.value).unwrap_or_default();
+
+                // e.g. the first payload of `Ok` is ` Ok[0]`
+                self.diff_elements(path, left_args, right_args, |index| {
+                    format!(" {name}[{index}]")
+                });
+            }
+            (left, right) => {
+                let left = self.format(left);
+                let right = self.format(right);
+
+                self.push(path, DifferenceKind::Changed { left, right });
+            }
+        }
+    }
+
+    fn diff_elements(
+        &mut self,
+        path: &mut String,
+        left: &[&Loc<Expr<'_>>],
+        right: &[&Loc<Expr<'_>>],
+        segment: impl Fn(usize) -> String,
+    ) {
+        for index in 0..left.len().max(right.len()) {
+            let len = path.len();
+            path.push_str(&segment(index));
+
+            match (left.get(index), right.get(index)) {
+                (Some(l), Some(r)) => self.diff(path, &l.value, &r.value),
+                (Some(l), None) => {
+                    self.push(path, DifferenceKind::OnlyLeft(self.format(&l.value)));
+                }
+                (None, Some(r)) => {
+                    self.push(path, DifferenceKind::OnlyRight(self.format(&r.value)));
+                }
+                (None, None) => unreachable!(),
+            }
+
+            path.truncate(len);
+        }
+    }
+
+    fn push(&mut self, path: &str, kind: DifferenceKind) {
+        if self.diff.differences.len() < MAX_DIFFERENCES {
+            self.diff.differences.push(Difference {
+                path: path.to_string(),
+                kind,
+            });
+        } else {
+            self.diff.omitted += 1;
+        }
+    }
+
+    fn format(&self, expr: &Expr<'_>) -> String {
+        let mut buf = roc_fmt::Buf::new_in(self.arena);
+        expr.format(&mut buf, 0);
+
+        buf.into_bump_str().to_string()
+    }
+}
+
+fn strip<'e, 'a>(expr: &'e Expr<'a>) -> &'e Expr<'a> {
+    match expr {
+        Expr::SpaceBefore(inner, _) | Expr::SpaceAfter(inner, _) | Expr::ParensAround(inner) => {
+            strip(inner)
+        }
+        _ => expr,
+    }
+}
+
+fn tag_name<'e>(expr: &'e Expr<'_>) -> Option<&'e str> {
+    match strip(expr) {
+        Expr::Tag(name) => Some(*name),
+        _ => None,
+    }
+}
+
+fn field<'a>(field: &Loc<AssignedField<'a, Expr<'a>>>) -> Option<(&'a str, &'a Expr<'a>)> {
+    fn help<'a>(field: &AssignedField<'a, Expr<'a>>) -> Option<(&'a str, &'a Expr<'a>)> {
+        match field {
+            AssignedField::RequiredValue(label, _, value)
+            | AssignedField::OptionalValue(label, _, value) => Some((label.value, &value.value)),
+            AssignedField::SpaceBefore(inner, _) | AssignedField::SpaceAfter(inner, _) => {
+                help(inner)
+            }
+            AssignedField::LabelOnly(_) | AssignedField::Malformed(_) => None,
+        }
+    }
+
+    help(&field.value)
+}
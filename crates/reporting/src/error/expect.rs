@@ -12,6 +12,42 @@ use roc_types::{
 
 use crate::report::{RenderTarget, RocDocAllocator, RocDocBuilder};
 
+/// An allocation that was still live when an expect finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leak {
+    pub size: usize,
+    pub alignment: u32,
+    /// The functions that led to the allocation, innermost first
+    pub frames: Vec<String>,
+}
+
+/// The directory, next to the file given to `roc test`, where `Inspect.expectSnapshot` keeps
+/// its snapshots. `roc format` leaves it alone, since everything in it is generated.
+pub const SNAPSHOT_DIR: &str = "roc-snapshots";
+
+/// Why an `Inspect.expectSnapshot` failed, see [Renderer::render_snapshot_failure]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotFailure {
+    /// The value no longer looks like its stored snapshot
+    Mismatch {
+        path: PathBuf,
+        stored: String,
+        actual: String,
+    },
+    /// The snapshot's file couldn't be read or written
+    Unusable { name: String, problem: String },
+}
+
+/// How a property failed, see [Renderer::render_property_failure]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyFailure<'a> {
+    ReturnedFalse,
+    /// With the message it crashed with
+    Crashed(&'a str),
+    /// An inline `expect` failed while the property ran
+    ExpectFailed,
+}
+
 pub struct Renderer<'a> {
     arena: &'a Bump,
     alloc: RocDocAllocator<'a>,
@@ -43,6 +79,10 @@ impl<'a> Renderer<'a> {
         }
     }
 
+    pub fn filename(&self) -> &std::path::Path {
+        &self.filename
+    }
+
     fn render_expr(&'a self, error_type: ErrorType) -> RocDocBuilder<'a> {
         use crate::error::r#type::error_type_to_doc;
 
@@ -72,6 +112,76 @@ impl<'a> Renderer<'a> {
         ])
     }
 
+    /// The positions in `symbols` of the two variables a failed `expect a == b` compared,
+    /// if we have values for both.
+    fn compared_lookups(
+        compared: Option<(Symbol, Symbol)>,
+        symbols: &[Symbol],
+    ) -> Option<(usize, usize)> {
+        let (left, right) = compared?;
+        let position = |compared| symbols.iter().position(|symbol| *symbol == compared);
+
+        Some((position(left)?, position(right)?))
+    }
+
+    fn render_differences(
+        &'a self,
+        (left_symbol, left): (Symbol, &Expr<'_>),
+        (right_symbol, right): (Symbol, &Expr<'_>),
+    ) -> Option<RocDocBuilder<'a>> {
+        use crate::error::diff::{diff_values, DifferenceKind};
+        use ven_pretty::DocAllocator;
+
+        let diff = diff_values(self.arena, left, right);
+
+        // When nothing about the values is the same (e.g. two different numbers, or two
+        // records whose every field differs), a diff would only repeat the values.
+        if diff.unchanged == 0 {
+            return None;
+        }
+
+        let left_name = left_symbol.as_str(self.alloc.interns);
+        let right_name = right_symbol.as_str(self.alloc.interns);
+
+        let lines = diff.differences.into_iter().map(|difference| {
+            let detail = match difference.kind {
+                DifferenceKind::Changed { left, right } => format!("{left} ≠ {right}"),
+                DifferenceKind::OnlyLeft(value) => format!("only in {left_name}: {value}"),
+                DifferenceKind::OnlyRight(value) => format!("only in {right_name}: {value}"),
+            };
+
+            self.alloc
+                .text(difference.path)
+                .annotate(crate::report::Annotation::Emphasized)
+                .append(self.alloc.text(": "))
+                .append(self.alloc.text(detail))
+        });
+
+        let omitted = diff.omitted;
+        let more = (omitted > 0).then(|| {
+            self.alloc.text(format!(
+                "…and {omitted} more {}.",
+                if omitted == 1 {
+                    "difference"
+                } else {
+                    "differences"
+                }
+            ))
+        });
+
+        Some(self.alloc.stack([
+            self.alloc.concat([
+                self.alloc.reflow("This is where "),
+                self.alloc.symbol_unqualified(left_symbol),
+                self.alloc.reflow(" and "),
+                self.alloc.symbol_unqualified(right_symbol),
+                self.alloc.reflow(" differ:"),
+            ]),
+            self.alloc.stack(lines.chain(more)).indent(4),
+        ]))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn render_lookups(
         &'a self,
         subs: &mut Subs,
@@ -80,9 +190,17 @@ impl<'a> Renderer<'a> {
         symbols: &[Symbol],
         variables: &[Variable],
         expressions: &[Expr<'_>],
+        comparison: Option<(usize, usize)>,
     ) -> RocDocBuilder<'a> {
         use ven_pretty::DocAllocator;
 
+        let differences = comparison.and_then(|(left, right)| {
+            self.render_differences(
+                (symbols[left], &expressions[left]),
+                (symbols[right], &expressions[right]),
+            )
+        });
+
         let it =
             symbols
                 .iter()
@@ -94,14 +212,18 @@ impl<'a> Renderer<'a> {
                 });
 
         if it.len() > 0 {
-            self.alloc.stack([
+            let mut docs = vec![
                 self.alloc.text("This expectation failed:"),
                 self.alloc.region(line_col_region),
                 self.alloc
                     .text("When it failed, these variables had these values:"),
                 self.alloc.stack(it),
-                self.alloc.text(""), // Blank line at the end
-            ])
+            ];
+
+            docs.extend(differences);
+            docs.push(self.alloc.text("")); // Blank line at the end
+
+            self.alloc.stack(docs)
         } else {
             self.alloc.stack([
                 self.alloc.text("This expectation failed:"),
@@ -142,6 +264,7 @@ impl<'a> Renderer<'a> {
         expressions: &[Expr<'_>],
         expect_region: Option<Region>,
         failure_region: Region,
+        compared: Option<(Symbol, Symbol)>,
     ) -> std::io::Result<()>
     where
         W: std::io::Write,
@@ -149,7 +272,15 @@ impl<'a> Renderer<'a> {
         use crate::report::Report;
 
         let line_col_region = self.to_line_col_region(expect_region, failure_region);
-        let doc = self.render_lookups(subs, line_col_region, symbols, variables, expressions);
+        let comparison = Self::compared_lookups(compared, symbols);
+        let doc = self.render_lookups(
+            subs,
+            line_col_region,
+            symbols,
+            variables,
+            expressions,
+            comparison,
+        );
 
         let report = Report {
             title: "EXPECT FAILED".into(),
@@ -240,4 +371,326 @@ impl<'a> Renderer<'a> {
 
         write!(writer, "{buf}")
     }
+
+    pub fn render_leaks<W>(
+        &self,
+        writer: &mut W,
+        leaks: &[Leak],
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(expect_region);
+
+        let allocations = if leaks.len() == 1 {
+            "1 allocation".to_string()
+        } else {
+            format!("{} allocations", leaks.len())
+        };
+
+        let leak_docs = leaks.iter().map(|leak| {
+            let frames = if leak.frames.is_empty() {
+                self.alloc.text("(no backtrace)").indent(4)
+            } else {
+                self.alloc
+                    .stack(
+                        leak.frames
+                            .iter()
+                            .map(|frame| self.alloc.text(frame.clone())),
+                    )
+                    .indent(4)
+            };
+
+            self.alloc.stack([
+                self.alloc.text(format!(
+                    "{} bytes, aligned to {}, allocated in:",
+                    leak.size, leak.alignment
+                )),
+                frames,
+            ])
+        });
+
+        let doc = self.alloc.stack([
+            self.alloc.concat([
+                self.alloc
+                    .reflow("This expectation passed, but it never freed "),
+                self.alloc.text(allocations),
+                self.alloc.reflow(" it made:"),
+            ]),
+            self.alloc.region(line_col_region),
+            self.alloc.stack(leak_docs),
+            self.alloc.reflow(
+                "Their reference counts never reached zero, so they were never freed. This is a bug in the compiler, the builtins, or code that manipulates reference counts by hand.",
+            ),
+        ]);
+
+        let report = Report {
+            title: "EXPECT LEAKED MEMORY".into(),
+            doc,
+            filename: self.filename.clone(),
+            severity: Severity::RuntimeError,
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
+
+    pub fn render_snapshot_failure<W>(
+        &self,
+        writer: &mut W,
+        failure: &SnapshotFailure,
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(expect_region);
+
+        let lines = |text: &str| {
+            self.alloc
+                .stack(text.lines().map(|line| self.alloc.text(line.to_string())))
+                .indent(4)
+        };
+
+        let (title, doc) = match failure {
+            SnapshotFailure::Mismatch {
+                path,
+                stored,
+                actual,
+            } => (
+                "SNAPSHOT MISMATCH",
+                self.alloc.stack([
+                    self.alloc
+                        .reflow("This expectation checks a value against its snapshot:"),
+                    self.alloc.region(line_col_region),
+                    self.alloc.concat([
+                        self.alloc.reflow("The snapshot in "),
+                        self.alloc.text(path.display().to_string()),
+                        self.alloc.reflow(" is:"),
+                    ]),
+                    lines(stored),
+                    self.alloc.reflow("But the value is now:"),
+                    lines(actual),
+                    self.alloc.concat([
+                        self.alloc.reflow("If the new value is right, run "),
+                        self.alloc.text("roc test --update-snapshots"),
+                        self.alloc.reflow(" to replace the snapshot."),
+                    ]),
+                ]),
+            ),
+            SnapshotFailure::Unusable { name, problem } => (
+                "UNUSABLE SNAPSHOT",
+                self.alloc.stack([
+                    self.alloc.concat([
+                        self.alloc
+                            .reflow("This expectation checks a value against the "),
+                        self.alloc.text(format!("{name:?}")),
+                        self.alloc.reflow(" snapshot:"),
+                    ]),
+                    self.alloc.region(line_col_region),
+                    self.alloc.reflow("But the snapshot can't be used:"),
+                    lines(problem),
+                ]),
+            ),
+        };
+
+        let report = Report {
+            title: title.into(),
+            doc,
+            filename: self.filename.clone(),
+            severity: Severity::RuntimeError,
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
+
+    pub fn render_unexpected_pass<W>(
+        &self,
+        writer: &mut W,
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(expect_region);
+
+        let doc = self.alloc.stack([
+            self.alloc.concat([
+                self.alloc.text("This expectation is marked "),
+                self.alloc.keyword("# roc-test: xfail"),
+                self.alloc.text(", but it passed:"),
+            ]),
+            self.alloc.region(line_col_region),
+            self.alloc.concat([
+                self.alloc
+                    .reflow("If it is supposed to pass now, remove the "),
+                self.alloc.keyword("xfail"),
+                self.alloc.reflow(" comment."),
+            ]),
+        ]);
+
+        let report = Report {
+            title: "UNEXPECTED PASS".into(),
+            doc,
+            filename: self.filename.clone(),
+            severity: Severity::RuntimeError,
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
+
+    /// Reports the smallest call found that makes a property fail, rendered as Roc code like
+    /// `fuzzAddCommutes 0 (-1)`.
+    pub fn render_property_failure<W>(
+        &self,
+        writer: &mut W,
+        property_region: Region,
+        call: &str,
+        runs: usize,
+        failure: PropertyFailure<'_>,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(property_region);
+
+        let runs = if runs == 1 {
+            "1 run".to_string()
+        } else {
+            format!("{runs} runs")
+        };
+
+        let outcome = match failure {
+            PropertyFailure::ReturnedFalse => self.alloc.concat([
+                self.alloc.reflow("It returned "),
+                self.alloc.keyword("Bool.false"),
+                self.alloc.text("."),
+            ]),
+            PropertyFailure::Crashed(message) => self.alloc.stack([
+                self.alloc.text("It crashed with this message:"),
+                self.alloc.text(message.to_string()).indent(4),
+            ]),
+            PropertyFailure::ExpectFailed => self
+                .alloc
+                .reflow("An expectation it evaluated failed, as reported below."),
+        };
+
+        let doc = self.alloc.stack([
+            self.alloc
+                .text(format!("This property failed after {runs}:")),
+            self.alloc.region(line_col_region),
+            self.alloc
+                .reflow("The smallest call I found that fails is:"),
+            self.alloc.text(call.to_string()).indent(4),
+            outcome,
+        ]);
+
+        let report = Report {
+            title: "PROPERTY FAILED".into(),
+            doc,
+            filename: self.filename.clone(),
+            severity: Severity::RuntimeError,
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
+
+    pub fn render_unsupported_property<W>(
+        &self,
+        writer: &mut W,
+        property_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(property_region);
+
+        let doc = self.alloc.stack([
+            self.alloc
+                .reflow("I don't know how to generate arguments for this property:"),
+            self.alloc.region(line_col_region),
+            self.alloc.concat([
+                self.alloc.reflow("A property can only take integers from "),
+                self.alloc.type_str("I8"),
+                self.alloc.reflow(" to "),
+                self.alloc.type_str("U64"),
+                self.alloc.reflow(", "),
+                self.alloc.type_str("F32"),
+                self.alloc.reflow(" and "),
+                self.alloc.type_str("F64"),
+                self.alloc.reflow(" fractions, and "),
+                self.alloc.type_str("Bool"),
+                self.alloc.reflow("s."),
+            ]),
+        ]);
+
+        let report = Report {
+            title: "UNSUPPORTED PROPERTY".into(),
+            doc,
+            filename: self.filename.clone(),
+            severity: Severity::RuntimeError,
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
 }
@@ -1,4 +1,6 @@
 pub mod canonicalize;
+pub mod code;
+pub mod diff;
 pub mod expect;
 pub mod parse;
 pub mod r#type;
@@ -204,8 +204,9 @@ fn to_expr_report<'a>(
                 alloc.region_with_subregion(lines.convert_region(surroundings), region),
                 alloc.concat([
                     alloc.reflow("Looks like you are trying to define a function. "),
-                    alloc.reflow("In roc, functions are always written as a lambda, like "),
-                    alloc.parser_suggestion("increment = \\n -> n + 1"),
+                    alloc.reflow("A function's name has to be a plain lowercase name, "),
+                    alloc.reflow("with its arguments on the same line, like "),
+                    alloc.parser_suggestion("increment n = n + 1"),
                     alloc.reflow("."),
                 ]),
             ]);
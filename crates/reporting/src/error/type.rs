@@ -1,21 +1,23 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::error::canonicalize::{to_circular_def_doc, CIRCULAR_DEF};
-use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{Annotation, CiWrite, Report, RocDocAllocator, RocDocBuilder};
 use itertools::EitherOrBoth;
 use itertools::Itertools;
+use roc_can::constraint::Provenance;
 use roc_can::expected::{Expected, PExpected};
 use roc_collections::all::{HumanIndex, MutSet, SendMap};
 use roc_collections::VecMap;
 use roc_error_macros::internal_error;
-use roc_exhaustive::{CtorName, ListArity};
+use roc_exhaustive::{CoveredBy, CtorName, ListArity};
 use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::{IdentStr, Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_problem::Severity;
 use roc_region::all::{LineInfo, Region};
 use roc_solve_problem::{
-    NotDerivableContext, NotDerivableEq, TypeError, UnderivableReason, Unfulfilled,
+    CycleLink, NotDerivableContext, NotDerivableEq, TypeCycle, TypeError, TypePathStep,
+    UnderivableReason, Unfulfilled,
 };
 use roc_std::RocDec;
 use roc_types::pretty_print::{Parens, WILDCARD};
@@ -55,13 +57,21 @@ pub fn type_problem<'b>(
         };
 
     match problem {
-        BadExpr(region, category, found, expected) => Some(to_expr_report(
-            alloc, lines, filename, severity, region, category, found, expected,
-        )),
+        BadExpr(region, category, found, expected, provenance) => {
+            let mut report = to_expr_report(
+                alloc, lines, filename, severity, region, category, found, expected,
+            );
+
+            if !provenance.is_empty() {
+                report.doc = alloc.stack([report.doc, provenance_to_doc(alloc, lines, provenance)]);
+            }
+
+            Some(report)
+        }
         BadPattern(region, category, found, expected) => Some(to_pattern_report(
             alloc, lines, filename, severity, region, category, found, expected,
         )),
-        CircularType(region, symbol, overall_type) => Some(to_circular_report(
+        CircularType(region, symbol, overall_type, cycle) => Some(to_circular_report(
             alloc,
             lines,
             filename,
@@ -69,6 +79,7 @@ pub fn type_problem<'b>(
             region,
             symbol,
             overall_type,
+            cycle,
         )),
         UnexposedLookup(_, symbol) => {
             let title = "UNRECOGNIZED NAME".to_string();
@@ -208,6 +219,37 @@ pub fn type_problem<'b>(
                 severity,
             })
         }
+        IncompleteAbilityImplementation {
+            region,
+            ability,
+            not_implemented,
+        } => {
+            let missing = not_implemented.into_iter().map(|(member, typ)| {
+                alloc.concat([
+                    alloc.symbol_unqualified(member),
+                    alloc.text(" : "),
+                    error_type_to_doc(alloc, typ),
+                ])
+            });
+
+            let stack = [
+                alloc.concat([
+                    alloc.reflow("This type does not fully implement the "),
+                    alloc.symbol_unqualified(ability),
+                    alloc.reflow(" ability:"),
+                ]),
+                alloc.region(lines.convert_region(region)),
+                alloc.reflow("The following necessary members are missing implementations:"),
+                alloc.type_block(alloc.stack(missing)),
+            ];
+
+            Some(Report {
+                title: "INCOMPLETE ABILITY IMPLEMENTATION".to_string(),
+                filename,
+                doc: alloc.stack(stack),
+                severity,
+            })
+        }
         IngestedFileBadUtf8(file_path, utf8_err) => {
             let stack = [
                 alloc.concat([
@@ -332,7 +374,7 @@ fn report_underivable_reason<'a>(
         UnderivableReason::SurfaceNotDerivable(context) => {
             underivable_hint(alloc, ability, context, typ)
         }
-        UnderivableReason::NestedNotDerivable(nested_typ, context) => {
+        UnderivableReason::NestedNotDerivable(nested_typ, context, path) => {
             let hint = underivable_hint(alloc, ability, context, &nested_typ);
             let reason = alloc.stack(
                 [
@@ -341,6 +383,7 @@ fn report_underivable_reason<'a>(
                     alloc.reflow("cannot be generated."),
                 ]
                 .into_iter()
+                .chain(underivable_path(alloc, &path))
                 .chain(hint),
             );
             Some(reason)
@@ -348,6 +391,46 @@ fn report_underivable_reason<'a>(
     }
 }
 
+/// Says where the type that blocked a derived implementation sits, innermost first, e.g.
+/// "It's found in the `f` field, inside the payload of the `Foo` tag."
+fn underivable_path<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    path: &[TypePathStep],
+) -> Option<RocDocBuilder<'b>> {
+    if path.is_empty() {
+        return None;
+    }
+
+    let steps = path.iter().rev().map(|step| match step {
+        TypePathStep::RecordField(field) => alloc.concat([
+            alloc.reflow("the "),
+            alloc.record_field(field.clone()),
+            alloc.reflow(" field"),
+        ]),
+        TypePathStep::TupleElem(index) => alloc.string(format!(
+            "the {} element of a tuple",
+            HumanIndex::zero_based(*index).ordinal()
+        )),
+        TypePathStep::TagPayload(tag_name) => alloc.concat([
+            alloc.reflow("the payload of the "),
+            alloc.tag_name(tag_name.clone()),
+            alloc.reflow(" tag"),
+        ]),
+        TypePathStep::TypeArg(symbol) => alloc.concat([
+            alloc.reflow("a type argument of "),
+            alloc.symbol_unqualified(*symbol),
+        ]),
+        TypePathStep::FunctionArg => alloc.reflow("a function argument"),
+        TypePathStep::FunctionReturn => alloc.reflow("a function's return type"),
+    });
+
+    Some(alloc.concat([
+        alloc.reflow("It's found in "),
+        alloc.intersperse(steps, alloc.reflow(", inside ")),
+        alloc.reflow("."),
+    ]))
+}
+
 fn underivable_hint<'b>(
     alloc: &'b RocDocAllocator<'b>,
     ability: Symbol,
@@ -1633,12 +1716,15 @@ fn type_comparison<'b>(
 ) -> RocDocBuilder<'b> {
     let comparison = to_comparison(alloc, actual, expected);
 
-    let mut lines = vec![
-        i_am_seeing,
-        comparison.actual,
-        instead_of,
-        comparison.expected,
-    ];
+    let mut lines = match side_by_side(alloc, &comparison.actual, &comparison.expected) {
+        Some(table) => vec![i_am_seeing, instead_of, table],
+        None => vec![
+            i_am_seeing,
+            comparison.actual,
+            instead_of,
+            comparison.expected,
+        ],
+    };
 
     if context_hints.is_some() {
         lines.push(alloc.concat(context_hints));
@@ -1653,6 +1739,84 @@ fn type_comparison<'b>(
     alloc.stack(lines)
 }
 
+/// When both types span several lines, put them next to each other (the actual type on the
+/// left, and the expected one on the right) so that the lines that differ are easy to spot.
+///
+/// Returns `None` when either type fits on one line, or when the two don't fit side by side.
+fn side_by_side<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    actual: &RocDocBuilder<'b>,
+    expected: &RocDocBuilder<'b>,
+) -> Option<RocDocBuilder<'b>> {
+    // Reports are rendered at this width, and type blocks are indented by 4
+    const WIDTH: usize = 70 - 4;
+    const SEPARATOR: &str = " │ ";
+
+    let render = |doc: &RocDocBuilder<'b>| {
+        let mut buf = String::new();
+        doc.1
+            .render_raw(70, &mut CiWrite::new(&mut buf))
+            .expect("rendering a type to a String can't fail");
+
+        buf.lines()
+            .map(|line| line.strip_prefix("    ").unwrap_or(line).to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let left = render(actual);
+    let right = render(expected);
+
+    if left.len() < 2 || right.len() < 2 {
+        return None;
+    }
+
+    let line_width = |lines: &[String]| {
+        lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let left_width = line_width(&left);
+
+    if left_width + SEPARATOR.chars().count() + line_width(&right) > WIDTH {
+        return None;
+    }
+
+    let rows = left.into_iter().zip_longest(right).map(|pair| {
+        let (left, right) = match pair {
+            EitherOrBoth::Both(left, right) => (left, right),
+            EitherOrBoth::Left(left) => (left, String::new()),
+            EitherOrBoth::Right(right) => (String::new(), right),
+        };
+
+        let padding = " ".repeat(left_width - left.chars().count());
+        let separator = if right.is_empty() {
+            SEPARATOR.trim_end()
+        } else {
+            SEPARATOR
+        };
+
+        let (left_doc, right_doc) = if left == right {
+            (alloc.string(left), alloc.string(right))
+        } else {
+            (
+                alloc.string(left).annotate(Annotation::DiffActual),
+                alloc.string(right).annotate(Annotation::DiffExpected),
+            )
+        };
+
+        alloc.concat([
+            left_doc,
+            alloc.text(padding),
+            alloc.text(separator),
+            right_doc,
+        ])
+    });
+
+    Some(alloc.type_block(alloc.vcat(rows)))
+}
+
 fn lone_type<'b>(
     alloc: &'b RocDocAllocator<'b>,
     actual: ErrorType,
@@ -2100,12 +2264,15 @@ fn pattern_type_comparison<'b>(
 ) -> RocDocBuilder<'b> {
     let comparison = to_comparison(alloc, actual, expected);
 
-    let mut lines = vec![
-        i_am_seeing,
-        comparison.actual,
-        instead_of,
-        comparison.expected,
-    ];
+    let mut lines = match side_by_side(alloc, &comparison.actual, &comparison.expected) {
+        Some(table) => vec![i_am_seeing, instead_of, table],
+        None => vec![
+            i_am_seeing,
+            comparison.actual,
+            instead_of,
+            comparison.expected,
+        ],
+    };
 
     lines.extend(problems_to_tip(
         alloc,
@@ -2160,27 +2327,67 @@ fn to_circular_report<'b>(
     region: roc_region::all::Region,
     symbol: Symbol,
     overall_type: ErrorType,
+    cycle: TypeCycle,
 ) -> Report<'b> {
+    let TypeCycle { name, typ, links } = cycle;
+
+    let mut docs = vec![
+        alloc
+            .reflow("I'm inferring a weird self-referential type for ")
+            .append(alloc.symbol_unqualified(symbol))
+            .append(alloc.text(":")),
+        alloc.region(lines.convert_region(region)),
+        alloc.stack([
+            alloc.reflow(
+                "Here is my best effort at writing down the type. \
+                You will see ∞ for parts of the type that repeat \
+                something already printed out infinitely.",
+            ),
+            alloc.type_block(to_doc(alloc, Parens::Unnecessary, overall_type).0),
+        ]),
+        alloc.stack([
+            alloc.reflow("The type goes in a circle here:"),
+            alloc.type_block(alloc.concat([
+                alloc.type_variable(name),
+                alloc.text(" = "),
+                to_doc(alloc, Parens::Unnecessary, typ).0,
+            ])),
+        ]),
+    ];
+
+    // Several parts of the cycle can come from the same expression; show each one only once.
+    let mut shown_regions = Vec::with_capacity(links.len());
+    for (link, link_region) in links {
+        let Some(link_region) = link_region else {
+            continue;
+        };
+        if shown_regions.contains(&link_region) {
+            continue;
+        }
+        shown_regions.push(link_region);
+
+        let link_doc = match link {
+            CycleLink::Type(symbol) => alloc.symbol_unqualified(symbol),
+            CycleLink::Function => alloc.text("function"),
+            CycleLink::Record => alloc.text("record"),
+            CycleLink::Tuple => alloc.text("tuple"),
+            CycleLink::TagUnion => alloc.text("tag union"),
+        };
+
+        docs.push(alloc.stack([
+            alloc.concat([
+                alloc.reflow("This "),
+                link_doc,
+                alloc.reflow(" comes from here:"),
+            ]),
+            alloc.region(lines.convert_region(link_region)),
+        ]));
+    }
+
     Report {
         title: "CIRCULAR TYPE".to_string(),
         filename,
-        doc: {
-            alloc.stack([
-                alloc
-                    .reflow("I'm inferring a weird self-referential type for ")
-                    .append(alloc.symbol_unqualified(symbol))
-                    .append(alloc.text(":")),
-                alloc.region(lines.convert_region(region)),
-                alloc.stack([
-                    alloc.reflow(
-                        "Here is my best effort at writing down the type. \
-                        You will see ∞ for parts of the type that repeat \
-                        something already printed out infinitely.",
-                    ),
-                    alloc.type_block(to_doc(alloc, Parens::Unnecessary, overall_type).0),
-                ]),
-            ])
-        },
+        doc: alloc.stack(docs),
         severity,
     }
 }
@@ -2456,7 +2663,7 @@ fn to_doc_help<'b>(
         Type(symbol, args) => report_text::apply(
             alloc,
             parens,
-            alloc.symbol_foreign_qualified(symbol),
+            alloc.type_name(symbol),
             args.into_iter()
                 .map(|arg| to_doc_help(ctx, gen_usages, alloc, Parens::InTypeParam, arg))
                 .collect(),
@@ -2477,7 +2684,7 @@ fn to_doc_help<'b>(
             report_text::apply(
                 alloc,
                 parens,
-                alloc.symbol_foreign_qualified(symbol),
+                alloc.type_name(symbol),
                 args.into_iter()
                     .map(|arg| to_doc_help(ctx, gen_usages, alloc, Parens::InTypeParam, arg))
                     .collect(),
@@ -2487,7 +2694,7 @@ fn to_doc_help<'b>(
         Alias(symbol, args, _, _) => report_text::apply(
             alloc,
             parens,
-            alloc.symbol_foreign_qualified(symbol),
+            alloc.type_name(symbol),
             args.into_iter()
                 .map(|arg| to_doc_help(ctx, gen_usages, alloc, Parens::InTypeParam, arg))
                 .collect(),
@@ -2757,7 +2964,7 @@ fn type_with_able_vars<'b>(
                 doc.push(alloc.text("&"));
             }
             doc.push(alloc.space());
-            doc.push(alloc.symbol_foreign_qualified(ability));
+            doc.push(alloc.type_name(ability));
         }
     }
 
@@ -2885,13 +3092,13 @@ fn to_diff<'b>(
             let left = report_text::apply(
                 alloc,
                 parens,
-                alloc.symbol_unqualified(symbol1),
+                alloc.type_name(symbol1),
                 args_diff.left,
             );
             let right = report_text::apply(
                 alloc,
                 parens,
-                alloc.symbol_unqualified(symbol2),
+                alloc.type_name(symbol2),
                 args_diff.right,
             );
 
@@ -2909,13 +3116,13 @@ fn to_diff<'b>(
             let left = report_text::apply(
                 alloc,
                 parens,
-                alloc.symbol_unqualified(symbol1),
+                alloc.type_name(symbol1),
                 args_diff.left,
             );
             let right = report_text::apply(
                 alloc,
                 parens,
-                alloc.symbol_unqualified(symbol2),
+                alloc.type_name(symbol2),
                 args_diff.right,
             );
 
@@ -4859,6 +5066,29 @@ fn report_record_field_typo<'b>(
     }
 }
 
+/// Explains where the expected type in a type mismatch came from.
+fn provenance_to_doc<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    provenance: Vec<Provenance>,
+) -> RocDocBuilder<'b> {
+    alloc.stack(
+        provenance
+            .into_iter()
+            .filter_map(|breadcrumb| match breadcrumb {
+                Provenance::None => None,
+                Provenance::Annotation(symbol, region) => Some(alloc.stack([
+                    alloc.concat([
+                        alloc.reflow("That requirement comes from the annotation on "),
+                        alloc.symbol_unqualified(symbol),
+                        alloc.reflow(":"),
+                    ]),
+                    alloc.region(lines.convert_region(region)),
+                ])),
+            }),
+    )
+}
+
 fn exhaustive_problem<'a>(
     alloc: &'a RocDocAllocator<'a>,
     lines: &LineInfo,
@@ -4948,7 +5178,32 @@ fn exhaustive_problem<'a>(
             overall_region,
             branch_region,
             index,
+            covered_by,
         } => {
+            let explanation = match covered_by {
+                CoveredBy::Pattern { index, .. } => alloc.concat([
+                    alloc.reflow("Any value of this shape will be handled by the "),
+                    alloc.string(index.ordinal()),
+                    alloc.reflow(" pattern, so this one should be removed."),
+                ]),
+                CoveredBy::UnguardedPattern { region, index } => alloc.stack([
+                    alloc.concat([
+                        alloc.reflow("The "),
+                        alloc.string(index.ordinal()),
+                        alloc.reflow(" pattern has no guard, and handles any value of this shape:"),
+                    ]),
+                    alloc.region(lines.convert_region(region)),
+                    alloc.reflow(
+                        "The guard is the only difference, so it will never be checked. \
+                        Move this pattern above that one, or remove it.",
+                    ),
+                ]),
+                CoveredBy::Patterns => alloc.reflow(
+                    "Any value of this shape will be handled by the patterns \
+                    before it, so this one should be removed.",
+                ),
+            };
+
             let doc = alloc.stack([
                 alloc.concat([
                     alloc.reflow("The "),
@@ -4959,10 +5214,7 @@ fn exhaustive_problem<'a>(
                     lines.convert_region(overall_region),
                     lines.convert_region(branch_region),
                 ),
-                alloc.reflow(
-                    "Any value of this shape will be handled by \
-                a previous pattern, so this one should be removed.",
-                ),
+                explanation,
             ]);
 
             Report {
@@ -5017,7 +5269,8 @@ pub fn unhandled_patterns_to_doc_block<'b>(
         .annotate(Annotation::TypeBlock)
 }
 
-fn exhaustive_pattern_to_doc<'b>(
+/// A pattern that a `when` or destructure doesn't handle, the way it'd be written in Roc
+pub fn exhaustive_pattern_to_doc<'b>(
     alloc: &'b RocDocAllocator<'b>,
     pattern: roc_exhaustive::Pattern,
 ) -> RocDocBuilder<'b> {
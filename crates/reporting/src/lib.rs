@@ -4,5 +4,7 @@
 #![allow(clippy::large_enum_variant)]
 
 pub mod cli;
+pub mod diagnostic;
 pub mod error;
 pub mod report;
+pub mod sarif;
@@ -3,6 +3,7 @@ use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, ModuleIds, PQModuleName, PackageQualified, Symbol};
 use roc_problem::Severity;
 use roc_region::all::LineColumnRegion;
+use roc_types::pretty_print::Qualification;
 use std::path::{Path, PathBuf};
 use std::{fmt, io};
 use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
@@ -220,6 +221,8 @@ pub struct Palette {
     pub typo: &'static str,
     pub typo_suggestion: &'static str,
     pub parser_suggestion: &'static str,
+    pub diff_actual: &'static str,
+    pub diff_expected: &'static str,
     pub bold: &'static str,
     pub underline: &'static str,
     pub reset: &'static str,
@@ -247,6 +250,8 @@ const fn default_palette_from_style_codes(codes: StyleCodes) -> Palette {
         typo: codes.yellow,
         typo_suggestion: codes.yellow,
         parser_suggestion: codes.yellow,
+        diff_actual: codes.red,
+        diff_expected: codes.green,
         bold: codes.bold,
         underline: codes.underline,
         reset: codes.reset,
@@ -316,6 +321,8 @@ pub struct RocDocAllocator<'a> {
     pub src_lines: &'a [&'a str],
     pub home: ModuleId,
     pub interns: &'a Interns,
+    /// How to write the names of types from other modules
+    pub qualification: Qualification,
 }
 
 pub type RocDocBuilder<'b> = DocBuilder<'b, RocDocAllocator<'b>, Annotation>;
@@ -352,6 +359,14 @@ impl<'a> RocDocAllocator<'a> {
             home,
             src_lines,
             interns,
+            qualification: Qualification::default(),
+        }
+    }
+
+    pub fn with_qualification(self, qualification: Qualification) -> Self {
+        RocDocAllocator {
+            qualification,
+            ..self
         }
     }
 
@@ -428,6 +443,17 @@ impl<'a> RocDocAllocator<'a> {
             .annotate(Annotation::Symbol)
         }
     }
+    /// The name of a type or ability, qualified according to [`Self::qualification`].
+    pub fn type_name(&'a self, symbol: Symbol) -> DocBuilder<'a, Self, Annotation> {
+        if self
+            .qualification
+            .qualifies(symbol, self.home, self.interns)
+        {
+            self.symbol_qualified(symbol)
+        } else {
+            self.symbol_unqualified(symbol)
+        }
+    }
     pub fn symbol_qualified(&'a self, symbol: Symbol) -> DocBuilder<'a, Self, Annotation> {
         text!(
             self,
@@ -884,6 +910,10 @@ pub enum Annotation {
     Tip,
     Header,
     ParserSuggestion,
+    /// The part of a type that doesn't match what was expected
+    DiffActual,
+    /// The part of an expected type that the actual type doesn't match
+    DiffExpected,
 }
 
 /// Render with minimal formatting
@@ -1094,6 +1124,12 @@ where
             ParserSuggestion => {
                 self.write_str(self.palette.parser_suggestion)?;
             }
+            DiffActual => {
+                self.write_str(self.palette.diff_actual)?;
+            }
+            DiffExpected => {
+                self.write_str(self.palette.diff_expected)?;
+            }
             TypeBlock | InlineTypeBlock | Tag | RecordField | TupleElem => { /* nothing yet */ }
         }
         self.style_stack.push(*annotation);
@@ -1108,7 +1144,8 @@ where
             Some(annotation) => match annotation {
                 Emphasized | Url | TypeVariable | Alias | Symbol | BinOp | UnaryOp | Error
                 | GutterBar | Ellipsis | Typo | TypoSuggestion | ParserSuggestion | Structure
-                | CodeBlock | PlainText | LineNumber | Tip | Module | Header | Keyword => {
+                | CodeBlock | PlainText | LineNumber | Tip | Module | Header | Keyword
+                | DiffActual | DiffExpected => {
                     self.write_str(self.palette.reset)?;
                 }
 
@@ -1727,3 +1764,56 @@ pub fn to_file_problem_report<'b>(
         }
     }
 }
+
+pub fn to_module_not_found_report_string(module_name: &str, searched: &[PathBuf]) -> String {
+    let src_lines: Vec<&str> = Vec::new();
+    let mut module_ids = ModuleIds::default();
+    let module_id = module_ids.get_or_insert(&module_name.into());
+    let interns = Interns::default();
+
+    let alloc = RocDocAllocator::new(&src_lines, module_id, &interns);
+
+    let mut buf = String::new();
+    let palette = DEFAULT_PALETTE;
+    let report = to_module_not_found_report(&alloc, module_name, searched);
+    report.render_color_terminal(&mut buf, &alloc, &palette);
+
+    buf
+}
+
+/// An imported module whose file isn't in any of the `searched` paths
+pub fn to_module_not_found_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    module_name: &str,
+    searched: &[PathBuf],
+) -> Report<'b> {
+    let nested_path = module_name.replace('.', "/");
+
+    let doc = alloc.stack([
+        alloc.concat([
+            alloc.reflow("The "),
+            alloc.module_name(module_name.into()),
+            alloc.reflow(" module is imported, but I couldn't find it. I searched:"),
+        ]),
+        alloc.stack(searched.iter().map(|path| {
+            alloc
+                .string(path.to_string_lossy().into_owned())
+                .annotate(Annotation::ParserSuggestion)
+                .indent(4)
+        })),
+        alloc.concat([
+            alloc.reflow("Each dot in a module name is a directory, starting from the directory of the app or package's main .roc file. So, "),
+            alloc.module_name(module_name.into()),
+            alloc.reflow(" should be in "),
+            alloc.string(format!("{nested_path}.roc")),
+            alloc.reflow(" there. Is there a typo in the module name?"),
+        ]),
+    ]);
+
+    Report {
+        filename: searched.first().cloned().unwrap_or_default(),
+        doc,
+        title: "MODULE NOT FOUND".to_string(),
+        severity: Severity::Fatal,
+    }
+}
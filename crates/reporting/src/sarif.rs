@@ -0,0 +1,101 @@
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) export,
+//! so that GitHub code scanning (and other tools that understand SARIF) can annotate pull
+//! requests with Roc's errors and warnings.
+use std::path::Path;
+
+use roc_problem::Severity;
+use serde_json::{json, Value};
+
+use crate::diagnostic::Diagnostic;
+use crate::error::code::lookup;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Renders all the given diagnostics as a single SARIF log with one run.
+pub fn sarif_log(diagnostics: &[Diagnostic], tool_version: &str) -> String {
+    let results: Vec<Value> = diagnostics.iter().map(result).collect();
+
+    let mut codes: Vec<&str> = diagnostics.iter().filter_map(|d| d.code).collect();
+    codes.sort_unstable();
+    codes.dedup();
+
+    let rules: Vec<Value> = codes
+        .into_iter()
+        .filter_map(lookup)
+        .map(|error_code| {
+            json!({
+                "id": error_code.code,
+                "name": error_code.title,
+                "shortDescription": { "text": error_code.summary },
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "roc",
+                    "informationUri": "https://www.roc-lang.org",
+                    "version": tool_version.trim(),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&log).expect("a JSON value can always be serialized")
+}
+
+fn result(diagnostic: &Diagnostic) -> Value {
+    let level = match diagnostic.severity {
+        Severity::Fatal | Severity::RuntimeError => "error",
+        Severity::Warning => "warning",
+    };
+
+    // Not every problem has a code, but code scanning needs some id to group results by.
+    let rule_id = match diagnostic.code {
+        Some(code) => code.to_string(),
+        None => diagnostic.title.clone(),
+    };
+
+    let text = if diagnostic.body.is_empty() {
+        diagnostic.title.clone()
+    } else {
+        diagnostic.body.clone()
+    };
+
+    let mut result = json!({
+        "ruleId": rule_id,
+        "level": level,
+        "message": { "text": text },
+    });
+
+    if let Some(filename) = diagnostic.filename.as_deref() {
+        let mut physical_location = json!({
+            "artifactLocation": { "uri": artifact_uri(filename) },
+        });
+
+        if let Some(region) = diagnostic.region {
+            physical_location["region"] = json!({
+                "startLine": region.start_line,
+                "startColumn": region.start_column,
+                "endLine": region.end_line,
+                "endColumn": region.end_column,
+            });
+        }
+
+        result["locations"] = json!([{ "physicalLocation": physical_location }]);
+    }
+
+    result
+}
+
+/// SARIF wants URIs, which always use forward slashes. Relative paths are kept relative,
+/// since code scanning resolves them against the root of the repository.
+fn artifact_uri(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,259 @@
+//! Heap allocation accounting, for finding the copies a Roc program makes because a value it
+//! changed wasn't unique.
+//!
+//! This is only compiled with the `alloc-stats` feature. The platform reports each call to its
+//! `roc_alloc`, `roc_realloc`, and `roc_dealloc` here with [`record_alloc`], [`record_realloc`],
+//! and [`record_dealloc`]. The Roc program allocates through those too, so this sees all of its
+//! allocations. `roc_std` also counts the reference count increments it makes itself, and the
+//! copies it makes when it's asked to change a value that's shared.
+//!
+//! Call [`print_report`] when the program exits to dump what was counted.
+use core::ffi::c_void;
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+/// How many of the most common allocation layouts the report lists
+const REPORTED_LAYOUTS: usize = 10;
+
+/// The size and alignment an allocation was requested with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AllocLayout {
+    pub size: usize,
+    pub alignment: u32,
+}
+
+/// Copies of a shared value that `roc_std` made so it could change one of them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SharedCopies {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub reallocations: u64,
+    pub deallocations: u64,
+    /// Bytes handed out by `roc_alloc` and `roc_realloc` over the whole run
+    pub total_bytes: u64,
+    /// Allocations that haven't been deallocated yet
+    pub live_allocations: usize,
+    pub live_bytes: usize,
+    /// The most bytes that were ever live at once
+    pub peak_live_bytes: usize,
+    pub allocations_by_layout: BTreeMap<AllocLayout, u64>,
+    /// Reference count increments made by `roc_std` (not by the Roc program), by type
+    pub increments_by_type: BTreeMap<&'static str, u64>,
+    pub shared_copies_by_type: BTreeMap<&'static str, SharedCopies>,
+}
+
+impl AllocStats {
+    const fn new() -> Self {
+        Self {
+            allocations: 0,
+            reallocations: 0,
+            deallocations: 0,
+            total_bytes: 0,
+            live_allocations: 0,
+            live_bytes: 0,
+            peak_live_bytes: 0,
+            allocations_by_layout: BTreeMap::new(),
+            increments_by_type: BTreeMap::new(),
+            shared_copies_by_type: BTreeMap::new(),
+        }
+    }
+}
+
+struct State {
+    stats: AllocStats,
+    /// The size of each live allocation, by address, since `roc_dealloc` isn't told the size
+    live: BTreeMap<usize, usize>,
+}
+
+impl State {
+    fn add_live(&mut self, ptr: *mut c_void, size: usize) {
+        if ptr.is_null() {
+            return;
+        }
+
+        self.live.insert(ptr as usize, size);
+
+        let stats = &mut self.stats;
+        stats.total_bytes += size as u64;
+        stats.live_allocations = self.live.len();
+        stats.live_bytes += size;
+        stats.peak_live_bytes = stats.peak_live_bytes.max(stats.live_bytes);
+    }
+
+    fn remove_live(&mut self, ptr: *mut c_void) {
+        if let Some(size) = self.live.remove(&(ptr as usize)) {
+            self.stats.live_allocations = self.live.len();
+            self.stats.live_bytes -= size;
+        }
+    }
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    stats: AllocStats::new(),
+    live: BTreeMap::new(),
+});
+
+fn state() -> MutexGuard<'static, State> {
+    // A panic while counting can't leave the counts in a state worth refusing to report.
+    STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Call this from the platform's `roc_alloc`, with the pointer it returns.
+pub fn record_alloc(ptr: *mut c_void, size: usize, alignment: u32) {
+    let mut state = state();
+
+    state.stats.allocations += 1;
+    *state
+        .stats
+        .allocations_by_layout
+        .entry(AllocLayout { size, alignment })
+        .or_default() += 1;
+    state.add_live(ptr, size);
+}
+
+/// Call this from the platform's `roc_realloc`, with the pointer it returns.
+pub fn record_realloc(old_ptr: *mut c_void, new_ptr: *mut c_void, new_size: usize) {
+    let mut state = state();
+
+    state.stats.reallocations += 1;
+    state.remove_live(old_ptr);
+    state.add_live(new_ptr, new_size);
+}
+
+/// Call this from the platform's `roc_dealloc`.
+pub fn record_dealloc(ptr: *mut c_void) {
+    let mut state = state();
+
+    state.stats.deallocations += 1;
+    state.remove_live(ptr);
+}
+
+pub(crate) fn record_increment<T: ?Sized>() {
+    *state()
+        .stats
+        .increments_by_type
+        .entry(type_name::<T>())
+        .or_default() += 1;
+}
+
+pub(crate) fn record_shared_copy<T: ?Sized>(bytes: usize) {
+    let mut state = state();
+    let copies = state
+        .stats
+        .shared_copies_by_type
+        .entry(type_name::<T>())
+        .or_default();
+
+    copies.count += 1;
+    copies.bytes += bytes as u64;
+}
+
+/// Everything counted so far
+pub fn stats() -> AllocStats {
+    state().stats.clone()
+}
+
+/// Prints everything counted so far to stderr.
+pub fn print_report() {
+    eprint!("{}", stats());
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "── ALLOCATIONS ──")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "allocations:        {} ({})",
+            self.allocations,
+            Bytes(self.total_bytes)
+        )?;
+        writeln!(f, "reallocations:      {}", self.reallocations)?;
+        writeln!(f, "deallocations:      {}", self.deallocations)?;
+        writeln!(
+            f,
+            "peak live:          {}",
+            Bytes(self.peak_live_bytes as u64)
+        )?;
+        writeln!(
+            f,
+            "still live:         {} ({})",
+            self.live_allocations,
+            Bytes(self.live_bytes as u64)
+        )?;
+
+        if !self.allocations_by_layout.is_empty() {
+            let mut layouts: Vec<_> = self.allocations_by_layout.iter().collect();
+            layouts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+            writeln!(f)?;
+            writeln!(f, "most allocated layouts:")?;
+            writeln!(f, "    {:>10}  {:>10}  {:>5}", "count", "size", "align")?;
+
+            for (layout, count) in layouts.into_iter().take(REPORTED_LAYOUTS) {
+                writeln!(
+                    f,
+                    "    {count:>10}  {:>10}  {:>5}",
+                    Bytes(layout.size as u64).to_string(),
+                    layout.alignment
+                )?;
+            }
+        }
+
+        if !self.shared_copies_by_type.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "copies made because a value being changed was shared:")?;
+
+            for (type_name, copies) in self.shared_copies_by_type.iter() {
+                writeln!(
+                    f,
+                    "    {type_name}: {} ({})",
+                    copies.count,
+                    Bytes(copies.bytes)
+                )?;
+            }
+        }
+
+        if !self.increments_by_type.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "reference count increments made by the platform:")?;
+
+            for (type_name, count) in self.increments_by_type.iter() {
+                writeln!(f, "    {type_name}: {count}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        write!(f, "{value:.1} {}", UNITS[unit])
+    }
+}
@@ -1,25 +1,31 @@
 //! Provides Rust representations of Roc data structures.
-// #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
 #![crate_type = "lib"]
 
-use arrayvec::ArrayString;
+extern crate alloc;
+
 use core::cmp::Ordering;
 use core::ffi::c_void;
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
 use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::Drop;
-use core::str;
 
+#[cfg(feature = "alloc-stats")]
+pub mod alloc_stats;
 mod roc_box;
+mod roc_dec;
 mod roc_dict;
+mod roc_host_closure;
 mod roc_list;
 mod roc_set;
 mod roc_str;
 mod storage;
 
 pub use roc_box::RocBox;
+pub use roc_dec::{ParseRocDecError, RocDec};
 pub use roc_dict::RocDict;
+pub use roc_host_closure::{RocHostClosure, RocHostFn};
 pub use roc_list::{RocList, SendSafeRocList};
 pub use roc_set::RocSet;
 pub use roc_str::{InteriorNulError, RocStr, SendSafeRocStr};
@@ -266,194 +272,6 @@ impl<T, E> Drop for RocResult<T, E> {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
-#[repr(C, align(16))]
-pub struct RocDec([u8; 16]);
-
-impl Debug for RocDec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("RocDec")
-            .field(&self.0)
-            .field(&self.to_str())
-            .finish()
-    }
-}
-
-impl RocDec {
-    pub const MIN: Self = Self(i128::MIN.to_ne_bytes());
-    pub const MAX: Self = Self(i128::MAX.to_ne_bytes());
-
-    const DECIMAL_PLACES: usize = 18;
-    const ONE_POINT_ZERO: i128 = 10i128.pow(Self::DECIMAL_PLACES as u32);
-    const MAX_DIGITS: usize = 39;
-    const MAX_STR_LENGTH: usize = Self::MAX_DIGITS + 2; // + 2 here to account for the sign & decimal dot
-
-    pub fn new(num: i128) -> Self {
-        Self(num.to_ne_bytes())
-    }
-
-    pub fn as_bits(&self) -> (i64, u64) {
-        let lower_bits = self.as_i128() as u64;
-        let upper_bits = (self.as_i128() >> 64) as i64;
-        (upper_bits, lower_bits)
-    }
-
-    #[allow(clippy::should_implement_trait)]
-    pub fn from_str(value: &str) -> Option<Self> {
-        // Split the string into the parts before and after the "."
-        let mut parts = value.split('.');
-
-        let before_point = match parts.next() {
-            Some(answer) => answer,
-            None => {
-                return None;
-            }
-        };
-
-        let opt_after_point = parts
-            .next()
-            .map(|answer| &answer[..Ord::min(answer.len(), Self::DECIMAL_PLACES)]);
-
-        // There should have only been one "." in the string!
-        if parts.next().is_some() {
-            return None;
-        }
-
-        // Calculate the low digits - the ones after the decimal point.
-        let lo = match opt_after_point {
-            Some(after_point) => {
-                match after_point.parse::<i128>() {
-                    Ok(answer) => {
-                        // Translate e.g. the 1 from 0.1 into 10000000000000000000
-                        // by "restoring" the elided trailing zeroes to the number!
-                        let trailing_zeroes = Self::DECIMAL_PLACES - after_point.len();
-                        let lo = answer * 10i128.pow(trailing_zeroes as u32);
-
-                        if !before_point.starts_with('-') {
-                            lo
-                        } else {
-                            -lo
-                        }
-                    }
-                    Err(_) => {
-                        return None;
-                    }
-                }
-            }
-            None => 0,
-        };
-
-        // Calculate the high digits - the ones before the decimal point.
-        let (is_pos, digits) = match before_point.chars().next() {
-            Some('+') => (true, &before_point[1..]),
-            Some('-') => (false, &before_point[1..]),
-            _ => (true, before_point),
-        };
-
-        let mut hi: i128 = 0;
-        macro_rules! adjust_hi {
-            ($op:ident) => {{
-                for digit in digits.chars() {
-                    if digit == '_' {
-                        continue;
-                    }
-
-                    let digit = digit.to_digit(10)?;
-                    hi = hi.checked_mul(10)?;
-                    hi = hi.$op(digit as _)?;
-                }
-            }};
-        }
-
-        if is_pos {
-            adjust_hi!(checked_add);
-        } else {
-            adjust_hi!(checked_sub);
-        }
-
-        match hi.checked_mul(Self::ONE_POINT_ZERO) {
-            Some(hi) => hi.checked_add(lo).map(|num| Self(num.to_ne_bytes())),
-            None => None,
-        }
-    }
-
-    /// This is private because RocDec being an i128 is an implementation detail
-    #[inline(always)]
-    fn as_i128(&self) -> i128 {
-        i128::from_ne_bytes(self.0)
-    }
-
-    pub fn from_ne_bytes(bytes: [u8; 16]) -> Self {
-        Self(bytes)
-    }
-
-    pub fn to_ne_bytes(&self) -> [u8; 16] {
-        self.0
-    }
-
-    fn to_str_helper(self, string: &mut ArrayString<{ Self::MAX_STR_LENGTH }>) -> &str {
-        use core::fmt::Write;
-
-        if self.as_i128() == 0 {
-            return "0";
-        }
-
-        // The :019 in the following write! is computed as Self::DECIMAL_PLACES + 1. If you change
-        // Self::DECIMAL_PLACES, this assert should remind you to change that format string as well.
-        static_assertions::const_assert!(RocDec::DECIMAL_PLACES + 1 == 19);
-
-        // By using the :019 format, we're guaranteeing that numbers less than 1, say 0.01234
-        // get their leading zeros placed in bytes for us. i.e. `string = b"0012340000000000000"`
-        write!(string, "{:019}", self.as_i128()).unwrap();
-
-        let decimal_location = string.len() - Self::DECIMAL_PLACES;
-        // skip trailing zeros
-        let last_nonzero_byte = string.trim_end_matches('0').len();
-
-        if last_nonzero_byte <= decimal_location {
-            // This means that we've removed trailing zeros and are left with an integer. Our
-            // convention is to print these without a decimal point or trailing zeros, so we're done.
-            string.truncate(decimal_location);
-            return string.as_str();
-        }
-
-        // otherwise, we're dealing with a fraction, and need to insert the decimal dot
-
-        // truncate all extra zeros off
-        string.truncate(last_nonzero_byte);
-
-        // push a dummy character so we have space for the decimal dot
-        string.push('$');
-
-        // Safety: at any time, the string only contains ascii characters, so it is always valid utf8
-        let bytes = unsafe { string.as_bytes_mut() };
-
-        // shift the fractional part by one
-        bytes.copy_within(decimal_location..last_nonzero_byte, decimal_location + 1);
-
-        // and put in the decimal dot in the right place
-        bytes[decimal_location] = b'.';
-
-        string.as_str()
-    }
-
-    pub fn to_str(&self) -> RocStr {
-        RocStr::from(self.to_str_helper(&mut ArrayString::new()))
-    }
-}
-
-impl From<i32> for RocDec {
-    fn from(value: i32) -> Self {
-        RocDec::from_ne_bytes((RocDec::ONE_POINT_ZERO * value as i128).to_ne_bytes())
-    }
-}
-
-impl fmt::Display for RocDec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.to_str_helper(&mut ArrayString::new()))
-    }
-}
-
 #[repr(C, align(16))]
 #[derive(Clone, Copy, Eq, Default)]
 pub struct I128([u8; 16]);
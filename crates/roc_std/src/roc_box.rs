@@ -1,6 +1,9 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
-use crate::{roc_alloc, roc_dealloc, storage::Storage};
+use crate::{
+    roc_alloc, roc_dealloc,
+    storage::{self, Storage},
+};
 use core::{
     cell::Cell,
     cmp::{self, Ordering},
@@ -15,6 +18,12 @@ pub struct RocBox<T> {
     contents: NonNull<T>,
 }
 
+// With atomic reference counts, a box can be shared like an `Arc<T>` (see [crate::storage]).
+#[cfg(feature = "atomic-refcount")]
+unsafe impl<T: Send + Sync> Send for RocBox<T> {}
+#[cfg(feature = "atomic-refcount")]
+unsafe impl<T: Send + Sync> Sync for RocBox<T> {}
+
 impl<T> RocBox<T> {
     pub fn new(contents: T) -> Self {
         let alignment = Self::alloc_alignment();
@@ -137,12 +146,13 @@ where
 impl<T> Clone for RocBox<T> {
     fn clone(&self) -> Self {
         let storage = self.storage();
-        let mut new_storage = storage.get();
 
         // Increment the reference count
-        if !new_storage.is_readonly() {
-            new_storage.increment_reference_count();
-            storage.set(new_storage);
+        storage::increment(storage);
+
+        #[cfg(feature = "alloc-stats")]
+        if !storage::load(storage).is_readonly() {
+            crate::alloc_stats::record_increment::<Self>();
         }
 
         Self {
@@ -157,10 +167,7 @@ impl<T> Drop for RocBox<T> {
         let contents = self.contents;
 
         // Decrease the list's reference count.
-        let mut new_storage = storage.get();
-        let needs_dealloc = new_storage.decrease();
-
-        if needs_dealloc {
+        if storage::decrement(storage) {
             unsafe {
                 // Drop the stored contents.
                 let contents_ptr = contents.as_ptr();
@@ -175,9 +182,6 @@ impl<T> Drop for RocBox<T> {
                     alignment as u32,
                 );
             }
-        } else if !new_storage.is_readonly() {
-            // Write the storage back.
-            storage.set(new_storage);
         }
     }
 }
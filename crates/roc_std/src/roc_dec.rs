@@ -0,0 +1,435 @@
+use arrayvec::ArrayString;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use core::str::FromStr;
+
+use crate::RocStr;
+
+/// Roc's `Dec`: a fixed-point decimal number with 18 digits after the decimal point,
+/// stored as an i128 that counts in units of 10^-18.
+///
+/// The arithmetic here works like it does in Roc. The operators panic on overflow and on
+/// division by zero, with the same messages, and the `checked_` methods return `None` instead.
+/// Multiplication and division round toward zero.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(C, align(16))]
+pub struct RocDec([u8; 16]);
+
+impl Debug for RocDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RocDec")
+            .field(&self.0)
+            .field(&self.to_str())
+            .finish()
+    }
+}
+
+impl RocDec {
+    pub const MIN: Self = Self(i128::MIN.to_ne_bytes());
+    pub const MAX: Self = Self(i128::MAX.to_ne_bytes());
+    pub const ZERO: Self = Self(0i128.to_ne_bytes());
+    pub const ONE: Self = Self(Self::ONE_POINT_ZERO.to_ne_bytes());
+
+    const DECIMAL_PLACES: usize = 18;
+    const ONE_POINT_ZERO: i128 = 10i128.pow(Self::DECIMAL_PLACES as u32);
+    const MAX_DIGITS: usize = 39;
+    const MAX_STR_LENGTH: usize = Self::MAX_DIGITS + 2; // + 2 here to account for the sign & decimal dot
+
+    pub fn new(num: i128) -> Self {
+        Self(num.to_ne_bytes())
+    }
+
+    pub fn as_bits(&self) -> (i64, u64) {
+        let lower_bits = self.as_i128() as u64;
+        let upper_bits = (self.as_i128() >> 64) as i64;
+        (upper_bits, lower_bits)
+    }
+
+    /// Parses a decimal number like `-1_000.25`, the way `Str.toDec` does. Digits past the
+    /// 18th after the decimal point are dropped.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Option<Self> {
+        if !value.bytes().any(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        // Split the string into the parts before and after the "."
+        let mut parts = value.split('.');
+
+        let before_point = match parts.next() {
+            Some(answer) => answer,
+            None => {
+                return None;
+            }
+        };
+
+        let opt_after_point = parts
+            .next()
+            .map(|answer| &answer[..Ord::min(answer.len(), Self::DECIMAL_PLACES)]);
+
+        // There should have only been one "." in the string!
+        if parts.next().is_some() {
+            return None;
+        }
+
+        // Calculate the low digits - the ones after the decimal point.
+        let lo = match opt_after_point {
+            Some(after_point) if !after_point.bytes().all(|byte| byte.is_ascii_digit()) => {
+                return None;
+            }
+            Some(after_point) => {
+                match after_point.parse::<i128>() {
+                    Ok(answer) => {
+                        // Translate e.g. the 1 from 0.1 into 10000000000000000000
+                        // by "restoring" the elided trailing zeroes to the number!
+                        let trailing_zeroes = Self::DECIMAL_PLACES - after_point.len();
+                        let lo = answer * 10i128.pow(trailing_zeroes as u32);
+
+                        if !before_point.starts_with('-') {
+                            lo
+                        } else {
+                            -lo
+                        }
+                    }
+                    Err(_) => {
+                        return None;
+                    }
+                }
+            }
+            None => 0,
+        };
+
+        // Calculate the high digits - the ones before the decimal point.
+        let (is_pos, digits) = match before_point.chars().next() {
+            Some('+') => (true, &before_point[1..]),
+            Some('-') => (false, &before_point[1..]),
+            _ => (true, before_point),
+        };
+
+        let mut hi: i128 = 0;
+        macro_rules! adjust_hi {
+            ($op:ident) => {{
+                for digit in digits.chars() {
+                    if digit == '_' {
+                        continue;
+                    }
+
+                    let digit = digit.to_digit(10)?;
+                    hi = hi.checked_mul(10)?;
+                    hi = hi.$op(digit as _)?;
+                }
+            }};
+        }
+
+        if is_pos {
+            adjust_hi!(checked_add);
+        } else {
+            adjust_hi!(checked_sub);
+        }
+
+        match hi.checked_mul(Self::ONE_POINT_ZERO) {
+            Some(hi) => hi.checked_add(lo).map(|num| Self(num.to_ne_bytes())),
+            None => None,
+        }
+    }
+
+    /// This is private because RocDec being an i128 is an implementation detail
+    #[inline(always)]
+    fn as_i128(&self) -> i128 {
+        i128::from_ne_bytes(self.0)
+    }
+
+    pub fn from_ne_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_ne_bytes(&self) -> [u8; 16] {
+        self.0
+    }
+
+    fn to_str_helper(self, string: &mut ArrayString<{ Self::MAX_STR_LENGTH }>) -> &str {
+        use core::fmt::Write;
+
+        if self.as_i128() == 0 {
+            return "0";
+        }
+
+        // The :019 in the following write! is computed as Self::DECIMAL_PLACES + 1. If you change
+        // Self::DECIMAL_PLACES, this assert should remind you to change that format string as well.
+        static_assertions::const_assert!(RocDec::DECIMAL_PLACES + 1 == 19);
+
+        // The sign goes first, so the zero padding below doesn't count it as a digit.
+        if self.as_i128() < 0 {
+            string.push('-');
+        }
+
+        // By using the :019 format, we're guaranteeing that numbers less than 1, say 0.01234
+        // get their leading zeros placed in bytes for us. i.e. `string = b"0012340000000000000"`
+        write!(string, "{:019}", self.as_i128().unsigned_abs()).unwrap();
+
+        let decimal_location = string.len() - Self::DECIMAL_PLACES;
+        // skip trailing zeros
+        let last_nonzero_byte = string.trim_end_matches('0').len();
+
+        if last_nonzero_byte <= decimal_location {
+            // This means that we've removed trailing zeros and are left with an integer. Our
+            // convention is to print these without a decimal point or trailing zeros, so we're done.
+            string.truncate(decimal_location);
+            return string.as_str();
+        }
+
+        // otherwise, we're dealing with a fraction, and need to insert the decimal dot
+
+        // truncate all extra zeros off
+        string.truncate(last_nonzero_byte);
+
+        // push a dummy character so we have space for the decimal dot
+        string.push('$');
+
+        // Safety: at any time, the string only contains ascii characters, so it is always valid utf8
+        let bytes = unsafe { string.as_bytes_mut() };
+
+        // shift the fractional part by one
+        bytes.copy_within(decimal_location..last_nonzero_byte, decimal_location + 1);
+
+        // and put in the decimal dot in the right place
+        bytes[decimal_location] = b'.';
+
+        string.as_str()
+    }
+
+    pub fn to_str(&self) -> RocStr {
+        RocStr::from(self.to_str_helper(&mut ArrayString::new()))
+    }
+
+    /// Converts an `F64` to a `Dec` like `Num.toDec` does, dropping any digits past the 18th
+    /// after the decimal point. Returns `None` for NaN, infinity, and numbers too big for a `Dec`.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        let scaled = value * Self::ONE_POINT_ZERO as f64;
+
+        // i128::MIN is -(2^127), and i128::MAX is just under 2^127.
+        if scaled >= i128::MIN as f64 && scaled < -(i128::MIN as f64) {
+            Some(Self::new(scaled as i128))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.as_i128() as f64 / Self::ONE_POINT_ZERO as f64
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.as_i128().checked_add(other.as_i128()).map(Self::new)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.as_i128().checked_sub(other.as_i128()).map(Self::new)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let is_answer_negative = (self.as_i128() < 0) != (other.as_i128() < 0);
+
+        // The product of the two i128s needs up to 254 bits before it's scaled back down.
+        let product = mul_u128(
+            self.as_i128().unsigned_abs(),
+            other.as_i128().unsigned_abs(),
+        );
+        let magnitude = div_u256_by_u128(product, Self::ONE_POINT_ZERO as u128)?;
+
+        Self::from_magnitude(magnitude, is_answer_negative)
+    }
+
+    /// Returns `None` when dividing by zero, as well as on overflow.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.as_i128() == 0 {
+            return None;
+        }
+
+        let is_answer_negative = (self.as_i128() < 0) != (other.as_i128() < 0);
+
+        let numerator = mul_u128(self.as_i128().unsigned_abs(), Self::ONE_POINT_ZERO as u128);
+        let magnitude = div_u256_by_u128(numerator, other.as_i128().unsigned_abs())?;
+
+        Self::from_magnitude(magnitude, is_answer_negative)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        self.as_i128().checked_neg().map(Self::new)
+    }
+
+    pub fn checked_abs(self) -> Option<Self> {
+        self.as_i128().checked_abs().map(Self::new)
+    }
+
+    fn from_magnitude(magnitude: u128, is_negative: bool) -> Option<Self> {
+        if !is_negative {
+            i128::try_from(magnitude).ok().map(Self::new)
+        } else if magnitude == i128::MIN.unsigned_abs() {
+            Some(Self::MIN)
+        } else {
+            i128::try_from(magnitude)
+                .ok()
+                .map(|magnitude| Self::new(-magnitude))
+        }
+    }
+}
+
+/// Multiplies two u128s into a u256, returned as its (high, low) halves.
+fn mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const LOW_64: u128 = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> 64, a & LOW_64);
+    let (b_hi, b_lo) = (b >> 64, b & LOW_64);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    // Each of these is at most (2^64 - 1), except lo_hi, which is at most (2^64 - 1)^2,
+    // so their sum fits in a u128.
+    let middle = (lo_lo >> 64) + (hi_lo & LOW_64) + lo_hi;
+
+    let hi = hi_hi + (hi_lo >> 64) + (middle >> 64);
+    let lo = (middle << 64) | (lo_lo & LOW_64);
+
+    (hi, lo)
+}
+
+/// Divides a u256 (as its high and low halves) by `divisor`, rounding down.
+/// Returns `None` if the answer doesn't fit in a u128.
+fn div_u256_by_u128((hi, lo): (u128, u128), divisor: u128) -> Option<u128> {
+    if hi >= divisor {
+        return None;
+    }
+
+    // Long division, one bit of `lo` at a time. The remainder always stays below `divisor`,
+    // but doubling it can take one bit more than a u128 has, so that bit is kept in `carry`.
+    let mut remainder = hi;
+    let mut quotient = 0;
+
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+
+        remainder = (remainder << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+
+    Some(quotient)
+}
+
+macro_rules! dec_from_int {
+    ($($int:ty),+) => {
+        $(
+            impl From<$int> for RocDec {
+                fn from(value: $int) -> Self {
+                    // Even u64::MAX fits in a Dec, so this can't overflow.
+                    Self::new(value as i128 * Self::ONE_POINT_ZERO)
+                }
+            }
+        )+
+    };
+}
+
+dec_from_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl Default for RocDec {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl PartialOrd for RocDec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RocDec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_i128().cmp(&other.as_i128())
+    }
+}
+
+impl Add for RocDec {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .unwrap_or_else(|| panic!("Decimal addition overflowed!"))
+    }
+}
+
+impl Sub for RocDec {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .unwrap_or_else(|| panic!("Decimal subtraction overflowed!"))
+    }
+}
+
+impl Mul for RocDec {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .unwrap_or_else(|| panic!("Decimal multiplication overflowed!"))
+    }
+}
+
+impl Div for RocDec {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other == Self::ZERO {
+            panic!("Decimal division by 0!");
+        }
+
+        self.checked_div(other)
+            .unwrap_or_else(|| panic!("Decimal division overflow!"))
+    }
+}
+
+impl Neg for RocDec {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg()
+            .unwrap_or_else(|| panic!("Decimal negation overflowed!"))
+    }
+}
+
+/// The error from parsing a string that isn't a valid `Dec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseRocDecError;
+
+impl fmt::Display for ParseRocDecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Dec literal")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseRocDecError {}
+
+impl FromStr for RocDec {
+    type Err = ParseRocDecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        RocDec::from_str(value).ok_or(ParseRocDecError)
+    }
+}
+
+impl fmt::Display for RocDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str_helper(&mut ArrayString::new()))
+    }
+}
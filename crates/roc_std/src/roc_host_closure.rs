@@ -0,0 +1,94 @@
+//! A host function that the host hands to Roc as an ordinary value, so a platform can give an
+//! app a capability (a logger, a database connection, and so on) at runtime, instead of every
+//! effect having to be a function in the platform's hosted module.
+//!
+//! In Roc, a host closure is a record of two `U64`s, which the platform usually names:
+//!
+//! ```roc
+//! Logger : { call : U64, data : U64 }
+//! ```
+//!
+//! `call` is the address of a C function (see [`RocHostFn`]), and `data` is the pointer that
+//! gets passed back to it. Roc code only stores and passes these along; to call one, the platform
+//! declares a hosted function that takes it, like `log : Logger, Str -> Effect {}`, whose
+//! implementation in the host is just [`RocHostClosure::call`]. Glue generates
+//! `RocHostClosure` for any record with exactly these two fields.
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use core::ffi::c_void;
+use core::mem;
+
+/// The calling convention of a host closure: `arg` points to the argument, and the answer
+/// gets written to `ret`. `data` is the pointer the closure was created with.
+pub type RocHostFn = unsafe extern "C" fn(data: *mut c_void, arg: *const c_void, ret: *mut c_void);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct RocHostClosure {
+    // These are u64 rather than pointers so the layout matches the Roc record on every target.
+    call: u64,
+    data: u64,
+}
+
+impl RocHostClosure {
+    /// # Safety
+    ///
+    /// `data` has to stay valid for as long as Roc can call this closure.
+    pub unsafe fn from_raw(call: RocHostFn, data: *mut c_void) -> Self {
+        Self {
+            call: call as usize as u64,
+            data: data as usize as u64,
+        }
+    }
+
+    /// Makes a host closure out of `f`, and passes it to `body`. The closure borrows `f`,
+    /// so it must not be called after `body` returns.
+    pub fn with<A, R, F, T>(f: &mut F, body: impl FnOnce(Self) -> T) -> T
+    where
+        F: FnMut(&A) -> R,
+    {
+        unsafe extern "C" fn trampoline<A, R, F: FnMut(&A) -> R>(
+            data: *mut c_void,
+            arg: *const c_void,
+            ret: *mut c_void,
+        ) {
+            let f = unsafe { &mut *data.cast::<F>() };
+            let answer = f(unsafe { &*arg.cast::<A>() });
+
+            unsafe { ret.cast::<R>().write(answer) };
+        }
+
+        let closure =
+            unsafe { Self::from_raw(trampoline::<A, R, F>, (f as *mut F).cast::<c_void>()) };
+
+        body(closure)
+    }
+
+    /// Whether this is the `Default` closure, which can't be called.
+    pub fn is_null(&self) -> bool {
+        self.call == 0
+    }
+
+    /// Calls the host function with `arg`, and returns its answer.
+    ///
+    /// # Safety
+    ///
+    /// The closure has to have come from the host, the host values it refers to must still
+    /// be alive, and `A` and `R` have to be the argument and answer types it was created with.
+    pub unsafe fn call<A, R>(&self, arg: &A) -> R {
+        assert!(!self.is_null(), "called a null host closure");
+
+        let call: RocHostFn = unsafe { mem::transmute(self.call as usize) };
+        let mut answer = mem::MaybeUninit::<R>::uninit();
+
+        unsafe {
+            call(
+                self.data as usize as *mut c_void,
+                (arg as *const A).cast(),
+                answer.as_mut_ptr().cast(),
+            );
+
+            answer.assume_init()
+        }
+    }
+}
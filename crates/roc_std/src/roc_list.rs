@@ -1,5 +1,6 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
+use core::ops::Range;
 use core::{
     cell::Cell,
     cmp::{self, Ordering},
@@ -12,9 +13,11 @@ use core::{
     ops::{Deref, DerefMut},
     ptr::{self, NonNull},
 };
-use std::ops::Range;
 
-use crate::{roc_alloc, roc_dealloc, roc_realloc, storage::Storage};
+use crate::{
+    roc_alloc, roc_dealloc, roc_realloc,
+    storage::{self, Storage},
+};
 
 #[cfg(feature = "serde")]
 use core::marker::PhantomData;
@@ -34,6 +37,13 @@ pub struct RocList<T> {
     capacity_or_ref_ptr: usize,
 }
 
+// With atomic reference counts, clones and drops on different threads can't race (see
+// [crate::storage]), so a list can be shared like an `Arc<[T]>`.
+#[cfg(feature = "atomic-refcount")]
+unsafe impl<T: Send + Sync> Send for RocList<T> {}
+#[cfg(feature = "atomic-refcount")]
+unsafe impl<T: Send + Sync> Sync for RocList<T> {}
+
 impl<T> RocList<T> {
     #[inline(always)]
     fn alloc_alignment() -> u32 {
@@ -205,7 +215,7 @@ impl<T> RocList<T> {
 
     pub(crate) fn storage(&self) -> Option<Storage> {
         self.elements_and_storage()
-            .map(|(_, storage)| storage.get())
+            .map(|(_, storage)| storage::load(storage))
     }
 
     /// Useful for doing memcpy on the elements. Returns NULL if list is empty.
@@ -226,7 +236,7 @@ impl<T> RocList<T> {
     #[allow(unused)]
     pub(crate) fn ptr_to_refcount(&self) -> *mut c_void {
         if self.is_seamless_slice() {
-            ((self.capacity_or_ref_ptr << 1) - std::mem::size_of::<usize>()) as *mut _
+            ((self.capacity_or_ref_ptr << 1) - core::mem::size_of::<usize>()) as *mut _
         } else {
             unsafe { self.ptr_to_first_elem().cast::<usize>().sub(1) as *mut _ }
         }
@@ -298,11 +308,7 @@ where
 
         let new_len = self.len() + slice.len();
         let non_null_elements = if let Some((elements, storage)) = self.elements_and_storage() {
-            // Decrement the list's refence count.
-            let mut copy = storage.get();
-            let is_unique = copy.decrease();
-
-            if is_unique {
+            if storage::load(storage).is_unique() {
                 // If we have enough capacity, we can add to the existing elements in-place.
                 if self.capacity() >= slice.len() {
                     elements
@@ -325,19 +331,28 @@ where
                     }))
                 }
             } else {
-                if !copy.is_readonly() {
-                    // Write the decremented reference count back.
-                    storage.set(copy);
-                }
-
-                // Allocate new memory.
-                self.capacity_or_ref_ptr = slice.len();
-                let new_elements = Self::elems_with_capacity(slice.len());
+                // Allocate new memory, with room for the old elements as well as the new ones.
+                let new_elements = Self::elems_with_capacity(new_len);
 
                 // Copy the old elements to the new allocation.
                 unsafe {
                     copy_nonoverlapping(elements.as_ptr(), new_elements.as_ptr(), self.len());
                 }
+
+                // Decrement the list's reference count. This is only the last reference if
+                // another thread dropped its reference since we checked, in which case the
+                // new allocation has taken over the elements (like in `reserve`).
+                if storage::decrement(storage) {
+                    unsafe {
+                        roc_dealloc(self.ptr_to_allocation(), Self::alloc_alignment());
+                    }
+                }
+
+                self.capacity_or_ref_ptr = new_len;
+
+                #[cfg(feature = "alloc-stats")]
+                crate::alloc_stats::record_shared_copy::<Self>(self.len() * mem::size_of::<T>());
+
                 // Clear the seamless slice bit since we now have clear ownership.
                 self.length = self.len();
 
@@ -383,23 +398,65 @@ impl<T> RocList<T> {
         if self.as_slice().get(range.start..range.end).is_none() {
             None
         } else {
-            // increment the refcount
-            std::mem::forget(self.clone());
+            Some(self.seamless_slice(range))
+        }
+    }
 
-            let element_ptr = self.as_slice()[range.start..]
-                .as_ptr()
-                .cast::<ManuallyDrop<T>>();
+    /// Returns up to `len` elements starting at `start`, like `List.sublist` in Roc.
+    /// Whatever part of that is out of bounds is left out, so this never panics.
+    ///
+    /// The elements aren't copied: the sublist is a seamless slice that shares this list's
+    /// allocation (and keeps it alive), which is how Roc represents the lists `List.sublist`
+    /// returns too. So this is a cheap way to pass a window of a large list to Roc.
+    pub fn sublist(&self, start: usize, len: usize) -> Self {
+        let start = start.min(self.len());
+        let end = start.saturating_add(len).min(self.len());
 
-            let capacity_or_ref_ptr =
-                (self.ptr_to_first_elem() as usize) >> 1 | isize::MIN as usize;
+        self.seamless_slice(start..end)
+    }
 
-            let roc_list = RocList {
-                elements: NonNull::new(element_ptr as *mut ManuallyDrop<T>),
-                length: range.end - range.start,
-                capacity_or_ref_ptr,
-            };
+    /// Splits the list in two at `index`, like [`slice::split_at`], without copying any
+    /// elements: both halves are seamless slices that share this list's allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    #[track_caller]
+    pub fn split_at(&self, index: usize) -> (Self, Self) {
+        assert!(index <= self.len(), "split index out of range");
 
-            Some(roc_list)
+        (
+            self.seamless_slice(0..index),
+            self.seamless_slice(index..self.len()),
+        )
+    }
+
+    /// Makes a seamless slice of the elements in `range`, which has to be in bounds.
+    fn seamless_slice(&self, range: Range<usize>) -> Self {
+        if range.is_empty() {
+            // Like `List.sublist`, don't hold on to the allocation for no elements.
+            return Self::empty();
+        }
+
+        // increment the refcount
+        core::mem::forget(self.clone());
+
+        let element_ptr = self.as_slice()[range.start..]
+            .as_ptr()
+            .cast::<ManuallyDrop<T>>();
+
+        // A seamless slice points to the elements of the list that owns the allocation,
+        // so a slice of a slice points to the same ones as the slice it came from.
+        let capacity_or_ref_ptr = if self.is_seamless_slice() {
+            self.capacity_or_ref_ptr
+        } else {
+            (self.ptr_to_first_elem() as usize) >> 1 | isize::MIN as usize
+        };
+
+        RocList {
+            elements: NonNull::new(element_ptr as *mut ManuallyDrop<T>),
+            length: range.end - range.start,
+            capacity_or_ref_ptr,
         }
     }
 
@@ -413,7 +470,7 @@ impl<T> RocList<T> {
 
         match self.elements_and_storage() {
             Some((elements, storage)) => {
-                if storage.get().is_unique() && !self.is_seamless_slice() {
+                if storage::load(storage).is_unique() && !self.is_seamless_slice() {
                     unsafe {
                         let old_alloc = self.ptr_to_allocation();
 
@@ -453,22 +510,18 @@ impl<T> RocList<T> {
                         copy_nonoverlapping(old_elements_ptr, new_elems.as_ptr(), self.len());
                     }
 
+                    #[cfg(feature = "alloc-stats")]
+                    crate::alloc_stats::record_shared_copy::<Self>(
+                        self.len() * mem::size_of::<T>(),
+                    );
+
                     // Decrease the current allocation's reference count.
-                    let mut new_storage = storage.get();
-
-                    if !new_storage.is_readonly() {
-                        let needs_dealloc = new_storage.decrease();
-
-                        if needs_dealloc {
-                            // Unlike in Drop, do *not* decrement the refcounts of all the elements!
-                            // The new allocation is referencing them, so instead of incrementing them all
-                            // all just to decrement them again here, we neither increment nor decrement them.
-                            unsafe {
-                                roc_dealloc(self.ptr_to_allocation(), Self::alloc_alignment());
-                            }
-                        } else {
-                            // Write the storage back.
-                            storage.set(new_storage);
+                    if storage::decrement(storage) {
+                        // Unlike in Drop, do *not* decrement the refcounts of all the elements!
+                        // The new allocation is referencing them, so instead of incrementing them all
+                        // all just to decrement them again here, we neither increment nor decrement them.
+                        unsafe {
+                            roc_dealloc(self.ptr_to_allocation(), Self::alloc_alignment());
                         }
                     }
                 }
@@ -604,11 +657,11 @@ impl<T> Clone for RocList<T> {
     fn clone(&self) -> Self {
         // Increment the reference count
         if let Some((_, storage)) = self.elements_and_storage() {
-            let mut new_storage = storage.get();
+            storage::increment(storage);
 
-            if !new_storage.is_readonly() {
-                new_storage.increment_reference_count();
-                storage.set(new_storage);
+            #[cfg(feature = "alloc-stats")]
+            if !storage::load(storage).is_readonly() {
+                crate::alloc_stats::record_increment::<Self>();
             }
         }
 
@@ -624,24 +677,15 @@ impl<T> Drop for RocList<T> {
     fn drop(&mut self) {
         if let Some((elements, storage)) = self.elements_and_storage() {
             // Decrease the list's reference count.
-            let mut new_storage = storage.get();
-
-            if !new_storage.is_readonly() {
-                let needs_dealloc = new_storage.decrease();
-
-                if needs_dealloc {
-                    unsafe {
-                        // Drop the stored elements.
-                        for index in 0..self.len() {
-                            ManuallyDrop::drop(&mut *elements.as_ptr().add(index));
-                        }
-
-                        // Release the memory.
-                        roc_dealloc(self.ptr_to_allocation(), Self::alloc_alignment());
+            if storage::decrement(storage) {
+                unsafe {
+                    // Drop the stored elements.
+                    for index in 0..self.len() {
+                        ManuallyDrop::drop(&mut *elements.as_ptr().add(index));
                     }
-                } else {
-                    // Write the storage back.
-                    storage.set(new_storage);
+
+                    // Release the memory.
+                    roc_dealloc(self.ptr_to_allocation(), Self::alloc_alignment());
                 }
             }
         }
@@ -933,6 +977,6 @@ mod tests {
         drop(new_x);
 
         // free the underlying memory
-        unsafe { crate::roc_dealloc(ptr, std::mem::align_of::<usize>() as u32) }
+        unsafe { crate::roc_dealloc(ptr, core::mem::align_of::<usize>() as u32) }
     }
 }
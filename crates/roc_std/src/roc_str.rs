@@ -8,6 +8,7 @@ use serde::{
 };
 
 use core::{
+    cell::Cell,
     cmp,
     convert::TryFrom,
     fmt,
@@ -17,15 +18,26 @@ use core::{
     ptr,
 };
 
+use core::{ops::Range, ptr::NonNull};
 #[cfg(feature = "std")]
 use std::ffi::{CStr, CString};
-use std::{ops::Range, ptr::NonNull};
 
-use crate::{roc_realloc, RocList};
+use crate::{
+    roc_realloc,
+    storage::{self, Storage},
+    RocList,
+};
 
 #[repr(transparent)]
 pub struct RocStr(RocStrInner);
 
+// A string is either small, and copied by value, or a list of bytes, which can be shared with
+// atomic reference counts (see [crate::storage]).
+#[cfg(feature = "atomic-refcount")]
+unsafe impl Send for RocStr {}
+#[cfg(feature = "atomic-refcount")]
+unsafe impl Sync for RocStr {}
+
 fn with_stack_bytes<F, E, T>(length: usize, closure: F) -> T
 where
     F: FnOnce(*mut E) -> T,
@@ -74,13 +86,27 @@ impl RocStr {
             Self(RocStrInner { small_string })
         } else {
             let heap_allocated = RocList::from_slice(slice);
-            let big_string = unsafe { std::mem::transmute(heap_allocated) };
+            let big_string = unsafe { core::mem::transmute(heap_allocated) };
             Self(RocStrInner {
                 heap_allocated: ManuallyDrop::new(big_string),
             })
         }
     }
 
+    /// Create a string from bytes, checking that they are valid UTF-8.
+    ///
+    /// Strings short enough to fit in a small string are stored inline;
+    /// anything longer is copied into a fresh heap allocation.
+    pub fn from_utf8(bytes: &[u8]) -> Result<Self, core::str::Utf8Error> {
+        core::str::from_utf8(bytes).map(RocStr::from)
+    }
+
+    /// Create a string from bytes, replacing any invalid UTF-8 sequences
+    /// with U+FFFD REPLACEMENT CHARACTER (like `String::from_utf8_lossy`).
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        RocStr::from(alloc::string::String::from_utf8_lossy(bytes).as_ref())
+    }
+
     /// # Safety
     ///
     /// - `bytes` must be allocated for `cap` bytes
@@ -91,7 +117,7 @@ impl RocStr {
     pub unsafe fn from_raw_parts(bytes: *mut u8, len: usize, cap: usize) -> Self {
         if len <= SmallString::CAPACITY {
             unsafe {
-                let slice = std::slice::from_raw_parts(bytes, len);
+                let slice = core::slice::from_raw_parts(bytes, len);
                 let small_string = SmallString::try_from_utf8_bytes(slice).unwrap_unchecked();
                 Self(RocStrInner { small_string })
             }
@@ -186,6 +212,12 @@ impl RocStr {
         self
     }
 
+    /// The UTF-8 bytes of this string, whether it is stored inline as a
+    /// small string or on the heap.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
     /// Create an empty RocStr with enough space preallocated to store
     /// the requested number of bytes.
     pub fn with_capacity(bytes: usize) -> Self {
@@ -214,7 +246,7 @@ impl RocStr {
                 let mut big_string = BigString::with_capacity(target_cap);
 
                 unsafe {
-                    std::ptr::copy_nonoverlapping(
+                    core::ptr::copy_nonoverlapping(
                         self.as_bytes().as_ptr(),
                         big_string.ptr_to_first_elem(),
                         self.len(),
@@ -265,7 +297,7 @@ impl RocStr {
             Some(RocStr(RocStrInner { small_string }))
         } else {
             // increment the refcount
-            std::mem::forget(self.clone());
+            core::mem::forget(self.clone());
 
             let big = unsafe { &self.0.heap_allocated };
             let ptr = unsafe { (self.as_bytes().as_ptr() as *mut u8).add(range.start) };
@@ -391,7 +423,7 @@ impl RocStr {
                             let elem_ptr = big_string.ptr_to_first_elem();
 
                             // memcpy the bytes into the stack allocation
-                            std::ptr::copy_nonoverlapping(elem_ptr, alloc_ptr, len);
+                            core::ptr::copy_nonoverlapping(elem_ptr, alloc_ptr, len);
 
                             terminate(alloc_ptr, len)
                         })
@@ -456,7 +488,7 @@ impl RocStr {
         self.with_terminator(terminator, |dest_ptr: *mut u16, str_slice: &str| {
             // Translate UTF-8 source bytes into UTF-16 and write them into the destination.
             for (index, wchar) in str_slice.encode_utf16().enumerate() {
-                unsafe { std::ptr::write_unaligned(dest_ptr.add(index), wchar) };
+                unsafe { core::ptr::write_unaligned(dest_ptr.add(index), wchar) };
             }
 
             func(dest_ptr, str_slice.len())
@@ -537,11 +569,10 @@ impl RocStr {
         terminator: E,
         func: F,
     ) -> A {
-        use crate::Storage;
         use core::mem::align_of;
 
         let terminate = |alloc_ptr: *mut E, str_slice: &str| unsafe {
-            std::ptr::write_unaligned(alloc_ptr.add(str_slice.len()), terminator);
+            core::ptr::write_unaligned(alloc_ptr.add(str_slice.len()), terminator);
 
             func(alloc_ptr, str_slice)
         };
@@ -621,7 +652,7 @@ impl RocStr {
     }
 }
 
-pub struct SplitWhitespace<'a>(std::iter::Peekable<std::str::CharIndices<'a>>, &'a RocStr);
+pub struct SplitWhitespace<'a>(core::iter::Peekable<core::str::CharIndices<'a>>, &'a RocStr);
 
 impl Iterator for SplitWhitespace<'_> {
     type Item = RocStr;
@@ -836,11 +867,15 @@ impl BigString {
     }
 
     fn as_bytes(&self) -> &[u8] {
-        unsafe { std::slice::from_raw_parts(self.ptr_to_first_elem(), self.len()) }
+        unsafe { core::slice::from_raw_parts(self.ptr_to_first_elem(), self.len()) }
     }
 
     fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(self.as_bytes()) }
+        unsafe { core::str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    fn storage(&self) -> &Cell<Storage> {
+        unsafe { &*self.ptr_to_refcount().cast::<Cell<Storage>>() }
     }
 
     fn is_unique(&self) -> bool {
@@ -848,10 +883,7 @@ impl BigString {
             return false;
         }
 
-        let ptr = self.ptr_to_refcount();
-        let rc = unsafe { std::ptr::read(ptr) as isize };
-
-        rc == isize::MIN
+        storage::load(self.storage()).is_unique()
     }
 
     fn is_readonly(&self) -> bool {
@@ -859,25 +891,23 @@ impl BigString {
             return true;
         }
 
-        let ptr = self.ptr_to_refcount();
-        let rc = unsafe { std::ptr::read(ptr) as isize };
-
-        rc == 0
+        storage::load(self.storage()).is_readonly()
     }
 
     fn set_readonly(&mut self) {
         assert_ne!(self.capacity(), 0);
 
         let ptr = self.ptr_to_refcount();
-        unsafe { std::ptr::write(ptr, 0) }
+        unsafe { core::ptr::write(ptr, 0) }
     }
 
-    fn inc(&mut self, n: usize) {
-        let ptr = self.ptr_to_refcount();
-        unsafe {
-            let value = std::ptr::read(ptr);
-            std::ptr::write(ptr, Ord::max(0, ((value as isize) + n as isize) as usize));
+    fn inc(&mut self) {
+        if self.capacity() == 0 {
+            // no valid allocation, elements pointer is dangling
+            return;
         }
+
+        storage::increment(self.storage());
     }
 
     fn dec(&mut self) {
@@ -886,21 +916,9 @@ impl BigString {
             return;
         }
 
-        let ptr = self.ptr_to_refcount();
-        unsafe {
-            let value = std::ptr::read(ptr) as isize;
-            match value {
-                0 => {
-                    // static lifetime, do nothing
-                }
-                isize::MIN => {
-                    // refcount becomes zero; free allocation
-                    crate::roc_dealloc(self.ptr_to_allocation().cast(), 1);
-                }
-                _ => {
-                    std::ptr::write(ptr, (value - 1) as usize);
-                }
-            }
+        if storage::decrement(self.storage()) {
+            // refcount becomes zero; free allocation
+            unsafe { crate::roc_dealloc(self.ptr_to_allocation().cast(), 1) };
         }
     }
 
@@ -920,7 +938,7 @@ impl BigString {
     ///
     /// May return a new BigString, if the provided one was not unique.
     fn reserve(&mut self, n: usize) {
-        let align = std::mem::size_of::<usize>();
+        let align = core::mem::size_of::<usize>();
         let desired_cap = self.len() + n;
         let desired_alloc = align + desired_cap;
 
@@ -946,15 +964,25 @@ impl BigString {
                 capacity_or_alloc_ptr: desired_cap,
             };
 
-            std::mem::swap(&mut this, self);
-            std::mem::forget(this);
+            core::mem::swap(&mut this, self);
+            core::mem::forget(this);
         } else {
             let ptr = unsafe { crate::roc_alloc(desired_alloc, align as _) } as *mut u8;
             let elements = unsafe { NonNull::new_unchecked(ptr.cast::<u8>().add(align)) };
 
             unsafe {
                 // Copy the old elements to the new allocation.
-                std::ptr::copy_nonoverlapping(self.ptr_to_first_elem(), ptr.add(align), self.len());
+                core::ptr::copy_nonoverlapping(
+                    self.ptr_to_first_elem(),
+                    ptr.add(align),
+                    self.len(),
+                );
+            }
+
+            // An empty BigString has no allocation yet, so there's nothing it could be sharing.
+            #[cfg(feature = "alloc-stats")]
+            if self.len() > 0 {
+                crate::alloc_stats::record_shared_copy::<RocStr>(self.len());
             }
 
             let mut this = Self {
@@ -963,8 +991,8 @@ impl BigString {
                 capacity_or_alloc_ptr: desired_cap,
             };
 
-            std::mem::swap(&mut this, self);
-            std::mem::drop(this);
+            core::mem::swap(&mut this, self);
+            core::mem::drop(this);
         }
     }
 }
@@ -977,7 +1005,12 @@ impl Clone for BigString {
             capacity_or_alloc_ptr: self.capacity_or_alloc_ptr,
         };
 
-        this.inc(1);
+        this.inc();
+
+        #[cfg(feature = "alloc-stats")]
+        if !self.is_readonly() {
+            crate::alloc_stats::record_increment::<RocStr>();
+        }
 
         this
     }
@@ -1,4 +1,14 @@
+//! The reference count at the start of every heap allocation Roc makes.
+//!
+//! By default, reference counts are changed with plain reads and writes, which is only correct
+//! if each value is used by one thread at a time. With the `atomic-refcount` feature, they're
+//! changed atomically instead, so values can be shared between threads. That makes every clone
+//! and drop slower, even without contention, and the Roc app has to be built by a `roc` with
+//! its own `atomic-refcount` feature, or the app and host won't agree.
+use core::cell::Cell;
 use core::num::NonZeroIsize;
+#[cfg(feature = "atomic-refcount")]
+use core::sync::atomic::{self, AtomicIsize, Ordering};
 
 /// # Safety
 ///
@@ -64,3 +74,95 @@ impl Storage {
         matches!(self, Self::ReferenceCounted(REFCOUNT_1))
     }
 }
+
+/// Reads the reference count in `storage`.
+///
+/// With the `atomic-refcount` feature, this is an atomic load, so it sees the changes other
+/// threads make to the reference count.
+pub(crate) fn load(storage: &Cell<Storage>) -> Storage {
+    #[cfg(feature = "atomic-refcount")]
+    {
+        match NonZeroIsize::new(as_atomic(storage).load(Ordering::Acquire)) {
+            Some(rc) => Storage::ReferenceCounted(rc),
+            None => Storage::Readonly,
+        }
+    }
+
+    #[cfg(not(feature = "atomic-refcount"))]
+    {
+        storage.get()
+    }
+}
+
+/// Increments the reference count in `storage`, unless it's readonly.
+pub(crate) fn increment(storage: &Cell<Storage>) {
+    #[cfg(feature = "atomic-refcount")]
+    {
+        let rc = as_atomic(storage);
+
+        // Nothing makes a readonly value reference counted again, so this can't race with it.
+        if rc.load(Ordering::Relaxed) != 0 {
+            // Like `Arc`, a new reference can only be made from an existing one, so nothing
+            // needs to be synchronized here.
+            rc.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[cfg(not(feature = "atomic-refcount"))]
+    {
+        let mut new_storage = storage.get();
+
+        if !new_storage.is_readonly() {
+            new_storage.increment_reference_count();
+            storage.set(new_storage);
+        }
+    }
+}
+
+/// Decrements the reference count in `storage`, unless it's readonly.
+///
+/// Returns `true` once there are no more references left, in which case the caller has to
+/// drop the contents and deallocate.
+pub(crate) fn decrement(storage: &Cell<Storage>) -> bool {
+    #[cfg(feature = "atomic-refcount")]
+    {
+        let rc = as_atomic(storage);
+
+        if rc.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
+
+        if rc.fetch_sub(1, Ordering::Release) == REFCOUNT_1.get() {
+            // Make sure every other thread is done with the contents before they're dropped.
+            atomic::fence(Ordering::Acquire);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    #[cfg(not(feature = "atomic-refcount"))]
+    {
+        let mut new_storage = storage.get();
+
+        if new_storage.is_readonly() {
+            return false;
+        }
+
+        let needs_dealloc = new_storage.decrease();
+
+        if !needs_dealloc {
+            storage.set(new_storage);
+        }
+
+        needs_dealloc
+    }
+}
+
+#[cfg(feature = "atomic-refcount")]
+fn as_atomic(storage: &Cell<Storage>) -> &AtomicIsize {
+    // Storage is an isize (see _ASSERT_STORAGE_SIZE), and AtomicIsize has the same
+    // in-memory representation as an isize.
+    unsafe { &*storage.as_ptr().cast::<AtomicIsize>() }
+}
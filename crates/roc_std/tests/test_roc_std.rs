@@ -12,7 +12,12 @@ const ROC_SMALL_STR_CAPACITY: usize = core::mem::size_of::<roc_std::RocStr>() -
 
 #[no_mangle]
 pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
-    libc::malloc(size)
+    let ptr = libc::malloc(size);
+
+    #[cfg(feature = "alloc-stats")]
+    roc_std::alloc_stats::record_alloc(ptr, size, _alignment);
+
+    ptr
 }
 
 #[no_mangle]
@@ -22,11 +27,19 @@ pub unsafe extern "C" fn roc_realloc(
     _old_size: usize,
     _alignment: u32,
 ) -> *mut c_void {
-    libc::realloc(c_ptr, new_size)
+    let new_ptr = libc::realloc(c_ptr, new_size);
+
+    #[cfg(feature = "alloc-stats")]
+    roc_std::alloc_stats::record_realloc(c_ptr, new_ptr, new_size);
+
+    new_ptr
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
+    #[cfg(feature = "alloc-stats")]
+    roc_std::alloc_stats::record_dealloc(c_ptr);
+
     libc::free(c_ptr)
 }
 
@@ -54,7 +67,11 @@ pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut
 
 #[cfg(test)]
 mod test_roc_std {
-    use roc_std::{RocBox, RocDec, RocList, RocResult, RocStr, SendSafeRocStr};
+    use core::ffi::c_void;
+    use roc_std::{
+        ParseRocDecError, RocBox, RocDec, RocHostClosure, RocList, RocResult, RocStr,
+        SendSafeRocStr,
+    };
 
     fn roc_str_byte_representation(string: &RocStr) -> [u8; RocStr::SIZE] {
         unsafe { core::mem::transmute_copy(string) }
@@ -307,6 +324,109 @@ mod test_roc_std {
         assert_eq!(format!("{example}"), "3.141592653589793238");
     }
 
+    #[test]
+    fn roc_dec_fmt_negative_fraction() {
+        let example = RocDec::from_str("-0.5").unwrap();
+        assert_eq!(format!("{example}"), "-0.5");
+
+        let example = RocDec::from_str("-0.000000000000000001").unwrap();
+        assert_eq!(format!("{example}"), "-0.000000000000000001");
+    }
+
+    #[test]
+    fn roc_dec_parse() {
+        assert_eq!(
+            "1.5".parse::<RocDec>(),
+            Ok(RocDec::from_str("1.5").unwrap())
+        );
+        assert_eq!("".parse::<RocDec>(), Err(ParseRocDecError));
+        assert_eq!(".".parse::<RocDec>(), Err(ParseRocDecError));
+        assert_eq!("1.-5".parse::<RocDec>(), Err(ParseRocDecError));
+        assert_eq!("1.2.3".parse::<RocDec>(), Err(ParseRocDecError));
+        assert_eq!("abc".parse::<RocDec>(), Err(ParseRocDecError));
+    }
+
+    #[test]
+    fn roc_dec_from_int() {
+        assert_eq!(RocDec::from(0u8), RocDec::ZERO);
+        assert_eq!(RocDec::from(1i32), RocDec::ONE);
+        assert_eq!(format!("{}", RocDec::from(-42i64)), "-42");
+        assert_eq!(
+            format!("{}", RocDec::from(u64::MAX)),
+            "18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn roc_dec_arithmetic() {
+        let dec = |s: &str| RocDec::from_str(s).unwrap();
+
+        assert_eq!(dec("1.25") + dec("2.5"), dec("3.75"));
+        assert_eq!(dec("1.25") - dec("2.5"), dec("-1.25"));
+        assert_eq!(dec("1.5") * dec("-2.5"), dec("-3.75"));
+        assert_eq!(dec("-7.5") * dec("-2"), dec("15"));
+        assert_eq!(dec("7.5") / dec("2.5"), dec("3"));
+        assert_eq!(-dec("7.5"), dec("-7.5"));
+
+        // Multiplication and division truncate toward zero, like they do in Roc.
+        assert_eq!(dec("1") / dec("3"), dec("0.333333333333333333"));
+        assert_eq!(dec("-2") / dec("3"), dec("-0.666666666666666666"));
+        assert_eq!(dec("0.000000000000000001") * dec("-0.5"), RocDec::ZERO);
+
+        assert!(dec("-0.5") < RocDec::ZERO);
+        assert!(RocDec::MIN < RocDec::MAX);
+        assert_eq!(RocDec::default(), RocDec::ZERO);
+    }
+
+    #[test]
+    fn roc_dec_checked_arithmetic() {
+        let two = RocDec::from(2u8);
+
+        assert_eq!(RocDec::MAX.checked_add(RocDec::ONE), None);
+        assert_eq!(RocDec::MIN.checked_sub(RocDec::ONE), None);
+        assert_eq!(RocDec::MAX.checked_mul(two), None);
+        assert_eq!(RocDec::MIN.checked_neg(), None);
+        assert_eq!(RocDec::ONE.checked_div(RocDec::ZERO), None);
+        assert_eq!(RocDec::MAX.checked_div(RocDec::new(1)), None);
+
+        assert_eq!(RocDec::MAX.checked_mul(RocDec::ONE), Some(RocDec::MAX));
+        assert_eq!(RocDec::MIN.checked_mul(RocDec::ONE), Some(RocDec::MIN));
+        assert_eq!(RocDec::MIN.checked_div(RocDec::ONE), Some(RocDec::MIN));
+        assert_eq!(
+            RocDec::MAX
+                .checked_div(two)
+                .and_then(|half| half.checked_mul(two)),
+            Some(RocDec::new(i128::MAX - 1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal addition overflowed!")]
+    fn roc_dec_add_overflow() {
+        let _ = RocDec::MAX + RocDec::ONE;
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal division by 0!")]
+    fn roc_dec_div_by_zero() {
+        let _ = RocDec::ONE / RocDec::ZERO;
+    }
+
+    #[test]
+    fn roc_dec_f64() {
+        assert_eq!(
+            RocDec::from_f64(0.5),
+            Some(RocDec::from_str("0.5").unwrap())
+        );
+        assert_eq!(RocDec::from_f64(-3.0), Some(RocDec::from(-3i8)));
+        assert_eq!(RocDec::from_f64(f64::NAN), None);
+        assert_eq!(RocDec::from_f64(f64::INFINITY), None);
+        assert_eq!(RocDec::from_f64(1e30), None);
+
+        assert_eq!(RocDec::from_str("-2.25").unwrap().to_f64(), -2.25);
+        assert_eq!(RocDec::ZERO.to_f64(), 0.0);
+    }
+
     #[test]
     fn safe_send_no_copy() {
         let x = RocStr::from("This is a long string but still unique. Yay!!!");
@@ -377,6 +497,58 @@ mod test_roc_std {
         // slice of slice
         let first = example.slice_range(0..5);
         assert_eq!(first.slice_range(0..3).as_slice(), b"cha");
+
+        // slice of a slice that doesn't start at the beginning
+        let middle = example.slice_range(6..17);
+        assert_eq!(middle.slice_range(5..11).as_slice(), b"ladder");
+    }
+
+    #[test]
+    fn sublist_shares_the_allocation() {
+        let example = RocList::from_slice(b"chaos is a ladder");
+
+        let sublist = example.sublist(6, 4);
+        assert_eq!(sublist.as_slice(), b"is a");
+        assert!(sublist.is_seamless_slice());
+        assert_eq!(
+            sublist.as_slice().as_ptr(),
+            example.as_slice()[6..].as_ptr()
+        );
+
+        // The sublist keeps the original allocation alive.
+        assert!(!example.is_unique());
+        drop(sublist);
+        assert!(example.is_unique());
+
+        // Like List.sublist, anything out of bounds is left out.
+        assert_eq!(example.sublist(11, 100).as_slice(), b"ladder");
+        assert_eq!(example.sublist(100, 1).as_slice(), b"");
+        assert!(example.is_unique());
+    }
+
+    #[test]
+    fn split_list_at() {
+        let example = RocList::from_slice(b"chaos is a ladder");
+
+        let (left, right) = example.split_at(5);
+        assert_eq!(left.as_slice(), b"chaos");
+        assert_eq!(right.as_slice(), b" is a ladder");
+
+        let (is_a, ladder) = right.split_at(5);
+        assert_eq!(is_a.as_slice(), b" is a");
+        assert_eq!(ladder.as_slice(), b" ladder");
+
+        let (everything, nothing) = example.split_at(example.len());
+        assert_eq!(everything.as_slice(), example.as_slice());
+        assert!(nothing.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "split index out of range")]
+    fn split_list_past_the_end() {
+        let example = RocList::from_slice(b"chaos");
+
+        example.split_at(6);
     }
 
     #[test]
@@ -436,6 +608,78 @@ mod test_roc_std {
             ]
         );
     }
+
+    #[test]
+    fn str_from_utf8() {
+        let small = RocStr::from_utf8(b"small").unwrap();
+        assert_eq!(small.as_bytes(), b"small");
+
+        let big_bytes = "a string that is too long to be a small string".as_bytes();
+        let big = RocStr::from_utf8(big_bytes).unwrap();
+        assert_eq!(big.as_bytes(), big_bytes);
+        assert_eq!(
+            big.as_str(),
+            "a string that is too long to be a small string"
+        );
+
+        assert!(RocStr::from_utf8(&[b'a', 0xFF, b'b']).is_err());
+    }
+
+    #[test]
+    fn str_from_utf8_lossy() {
+        let replaced = RocStr::from_utf8_lossy(&[b'a', 0xFF, b'b']);
+        assert_eq!(replaced.as_str(), "a\u{FFFD}b");
+
+        let valid = RocStr::from_utf8_lossy("valid".as_bytes());
+        assert_eq!(valid.as_str(), "valid");
+    }
+
+    #[test]
+    fn host_closure_calls_back_into_rust() {
+        let mut logged = Vec::new();
+        let mut log = |msg: &RocStr| {
+            logged.push(msg.to_string());
+            logged.len()
+        };
+
+        let answers = RocHostClosure::with(&mut log, |logger| unsafe {
+            let first: usize = logger.call(&RocStr::from("first"));
+            let second: usize = logger.call(&RocStr::from("second"));
+
+            (first, second)
+        });
+
+        assert_eq!(answers, (1, 2));
+        assert_eq!(logged, ["first", "second"]);
+    }
+
+    #[test]
+    fn host_closure_from_raw() {
+        unsafe extern "C" fn add(data: *mut c_void, arg: *const c_void, ret: *mut c_void) {
+            let total = &mut *data.cast::<i64>();
+
+            *total += *arg.cast::<i64>();
+            ret.cast::<i64>().write(*total);
+        }
+
+        let mut total = 40i64;
+        let closure = unsafe { RocHostClosure::from_raw(add, (&mut total as *mut i64).cast()) };
+
+        // Roc copies these around like any other record of two U64s.
+        let copy = closure;
+
+        assert_eq!(unsafe { copy.call::<i64, i64>(&2) }, 42);
+        assert_eq!(total, 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "called a null host closure")]
+    fn default_host_closure() {
+        let closure = RocHostClosure::default();
+
+        assert!(closure.is_null());
+        unsafe { closure.call::<(), ()>(&()) };
+    }
 }
 
 #[cfg(test)]
@@ -547,3 +791,102 @@ mod with_terminator {
         // verify_temp_c(&string_for_len(65), 64);
     }
 }
+
+#[cfg(all(test, feature = "alloc-stats"))]
+mod alloc_stats {
+    use core::any::type_name;
+    use roc_std::alloc_stats::{self, SharedCopies};
+    use roc_std::RocList;
+
+    // Tests run in parallel and share the counts, so each one uses its own element type.
+    #[derive(Clone)]
+    struct CopiedElem(u64);
+
+    #[derive(Clone)]
+    struct LiveElem(u64);
+
+    #[test]
+    fn counts_copies_of_shared_lists() {
+        let list = RocList::from_slice(&[CopiedElem(1), CopiedElem(2)]);
+        let mut shared = list.clone();
+
+        // `shared` isn't unique, so this has to copy both elements into a new allocation.
+        shared.extend_from_slice(&[CopiedElem(3)]);
+
+        let elems: Vec<u64> = shared.iter().map(|elem| elem.0).collect();
+        assert_eq!(elems, [1, 2, 3]);
+
+        let stats = alloc_stats::stats();
+        let name = type_name::<RocList<CopiedElem>>();
+
+        assert_eq!(stats.increments_by_type.get(name), Some(&1));
+        assert_eq!(
+            stats.shared_copies_by_type.get(name),
+            Some(&SharedCopies {
+                count: 1,
+                bytes: 2 * core::mem::size_of::<CopiedElem>() as u64,
+            })
+        );
+
+        let report = stats.to_string();
+        assert!(report.contains(name), "{report}");
+    }
+
+    #[test]
+    fn unique_lists_are_changed_in_place() {
+        let mut list = RocList::from_slice(&[LiveElem(1)]);
+        list.extend_from_slice(&[LiveElem(2)]);
+
+        let elems: Vec<u64> = list.iter().map(|elem| elem.0).collect();
+        assert_eq!(elems, [1, 2]);
+
+        let stats = alloc_stats::stats();
+        let name = type_name::<RocList<LiveElem>>();
+
+        assert_eq!(stats.increments_by_type.get(name), None);
+        assert_eq!(stats.shared_copies_by_type.get(name), None);
+        assert!(stats.peak_live_bytes >= stats.live_bytes);
+        assert!(stats.allocations > 0);
+    }
+}
+
+#[cfg(all(test, feature = "atomic-refcount"))]
+mod atomic_refcount {
+    use roc_std::{RocBox, RocList, RocStr};
+
+    #[test]
+    fn clones_on_many_threads() {
+        let list = RocList::from_slice(&[1u64, 2, 3]);
+        let string = RocStr::from("a string that's too long to be small");
+        let boxed = RocBox::new(42u64);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..10_000 {
+                        let list = list.clone();
+                        let string = string.clone();
+                        let boxed = boxed.clone();
+
+                        assert_eq!(list.as_slice(), &[1, 2, 3]);
+                        assert_eq!(string.as_str(), "a string that's too long to be small");
+                        assert_eq!(*boxed, 42);
+                    }
+                });
+            }
+        });
+
+        // Every clone was dropped, so none of the increments or decrements got lost.
+        assert!(list.is_unique());
+        assert!(string.is_unique());
+    }
+
+    #[test]
+    fn moves_to_another_thread() {
+        let list = RocList::from_slice(&[RocStr::from("moved")]);
+
+        let list = std::thread::spawn(move || list).join().unwrap();
+
+        assert_eq!(list[0].as_str(), "moved");
+    }
+}
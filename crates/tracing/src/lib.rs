@@ -10,70 +10,142 @@
 //! you should use the exposed members of `roc_tracing` for your tracing needs.
 //! This enables us to easily modify the tracing infrastructure without inducing sweeping changes.
 //!
-//! Tracing is only turned on in debug builds. Use the provided [setup_tracing] macro to turn on
+//! Logging is only turned on in debug builds. Use the provided [setup_tracing] macro to turn on
 //! tracing at an executable's entry point.
 //!
+//! The compiler's phases (loading, parsing, canonicalization, constraint generation, solving,
+//! mono, and code generation) are also wrapped in info-level spans. If ROC_TRACE=<filepath> is
+//! specified, those spans are written to <filepath> as a [Chrome trace], which can be opened in
+//! `chrome://tracing` or <https://ui.perfetto.dev>. Release builds only have these spans with the
+//! `trace` feature; otherwise, like the logs, they are compiled out.
+//!
 //! [directive-syntax]: https://docs.rs/tracing-subscriber/latest/tracing_subscriber/filter/struct.EnvFilter.html#directives
+//! [Chrome trace]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
 
 /// Sets up tracing of a Roc executable. The value of this macro must be bound to a variable that
 /// is not dropped until tracing has completed.
 ///
 /// This macro should only be invoked at an executable's entry point.
-/// Tracing will only be enabled in debug builds.
+/// Logging will only be enabled in debug builds.
 #[macro_export]
 macro_rules! setup_tracing {
     () => {
+        $crate::setup_tracing(cfg!(debug_assertions))
+    };
+}
+
+#[doc(hidden)]
+pub use tracing as __tracing;
+
+/// Whether release builds keep the spans (but not the logs), for ROC_TRACE.
+#[doc(hidden)]
+pub const SPANS_IN_RELEASE: bool = cfg!(feature = "trace");
+
+/// Like [tracing::debug], but compiled out of release builds.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
         if cfg!(debug_assertions) {
-            $crate::setup_tracing()
-        } else {
-            $crate::TracingGuards::NONE
+            $crate::__tracing::debug!($($arg)*)
         }
     };
 }
 
-pub use tracing::debug;
-pub use tracing::info;
+/// Like [tracing::info], but compiled out of release builds.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) {
+            $crate::__tracing::info!($($arg)*)
+        }
+    };
+}
+
+/// Like [tracing::info_span], but compiled out of release builds without the `trace` feature.
+#[macro_export]
+macro_rules! info_span {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) || $crate::SPANS_IN_RELEASE {
+            $crate::__tracing::info_span!($($arg)*)
+        } else {
+            $crate::__tracing::Span::none()
+        }
+    };
+}
 
 const ENV_FILTER: &str = "ROC_LOG";
 const LOGTO_VAR: &str = "ROC_LOGTO";
+const TRACE_VAR: &str = "ROC_TRACE";
 
-use tracing_subscriber::{fmt, prelude::*, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter, fmt, prelude::*, EnvFilter, Layer, Registry};
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
 
 /// Guards issued by the underlying library used for tracing.
 /// Must not be dropped until all tracing is complete.
 pub struct TracingGuards {
     _file_appender_guard: Option<tracing_appender::non_blocking::WorkerGuard>,
+    _chrome_trace_guard: Option<tracing_chrome::FlushGuard>,
 }
 
 impl TracingGuards {
     pub const NONE: TracingGuards = TracingGuards {
         _file_appender_guard: None,
+        _chrome_trace_guard: None,
     };
 }
 
+/// Turns on logging (if `with_logs` is set) and the Chrome trace (if ROC_TRACE is set).
 #[must_use]
-pub fn setup_tracing() -> TracingGuards {
-    if let Ok(file) = std::env::var(LOGTO_VAR) {
-        let _ = std::fs::remove_file(&file);
-        let file_appender = tracing_appender::rolling::never(".", file);
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-        let file_layer = fmt::Layer::default()
-            .with_writer(non_blocking)
-            .with_ansi(false)
-            .with_filter(EnvFilter::from_env(ENV_FILTER));
-
-        Registry::default().with(file_layer).init();
-
-        TracingGuards {
-            _file_appender_guard: Some(guard),
+pub fn setup_tracing(with_logs: bool) -> TracingGuards {
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+    let mut guards = TracingGuards::NONE;
+
+    if with_logs {
+        if let Ok(file) = std::env::var(LOGTO_VAR) {
+            let _ = std::fs::remove_file(&file);
+            let file_appender = tracing_appender::rolling::never(".", file);
+            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+            let file_layer = fmt::Layer::default()
+                .with_writer(non_blocking)
+                .with_ansi(false)
+                .with_filter(EnvFilter::from_env(ENV_FILTER));
+
+            layers.push(file_layer.boxed());
+            guards._file_appender_guard = Some(guard);
+        } else {
+            let stderr_layer = fmt::Layer::default()
+                .with_writer(std::io::stderr)
+                .with_filter(EnvFilter::from_env(ENV_FILTER));
+
+            layers.push(stderr_layer.boxed());
+        }
+    }
+
+    if let Some(file) = std::env::var_os(TRACE_VAR) {
+        if !cfg!(debug_assertions) && !SPANS_IN_RELEASE {
+            eprintln!(
+                "{TRACE_VAR} is set, but this is a release build without the `trace` feature, so the trace will be empty."
+            );
         }
-    } else {
-        let stderr_layer = fmt::Layer::default()
-            .with_writer(std::io::stderr)
-            .with_filter(EnvFilter::from_env(ENV_FILTER));
 
-        Registry::default().with(stderr_layer).init();
+        let (chrome_layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+            .file(file)
+            .include_args(true)
+            .build();
 
-        TracingGuards::NONE
+        // Only the spans are worth a place on the timeline; the log events would drown them out.
+        layers.push(
+            chrome_layer
+                .with_filter(filter::filter_fn(|metadata| metadata.is_span()))
+                .boxed(),
+        );
+        guards._chrome_trace_guard = Some(guard);
     }
+
+    if !layers.is_empty() {
+        Registry::default().with(layers).init();
+    }
+
+    guards
 }
@@ -109,6 +109,8 @@ fn valgrind_test_linux(source: &str) {
     match res_binary_path {
         Ok(BuiltFile {
             binary_path,
+            platform_path: _,
+            package_urls: _,
             problems,
             total_time: _,
             expect_metadata: _,
@@ -7,6 +7,8 @@ pub const MODULE_NAME: &str = "wasi_snapshot_preview1";
 
 pub struct WasiDispatcher<'a> {
     pub args: &'a [&'a [u8]],
+    /// Environment variables, each formatted as `KEY=VALUE`
+    pub env: &'a [&'a [u8]],
     pub rng: ThreadRng,
     pub files: Vec<WasiFile>,
 }
@@ -38,6 +40,7 @@ impl<'a> WasiDispatcher<'a> {
     pub fn new(args: &'a [&'a [u8]]) -> Self {
         WasiDispatcher {
             args,
+            env: &[],
             rng: thread_rng(),
             files: vec![
                 WasiFile::HostSystemFile,
@@ -57,18 +60,11 @@ impl<'a> WasiDispatcher<'a> {
         match function_name {
             "args_get" => {
                 // uint8_t ** argv,
-                let mut ptr_ptr_argv = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_ptr_argv = arguments[0].expect_i32().unwrap() as usize;
                 // uint8_t * argv_buf
-                let mut ptr_argv_buf = arguments[1].expect_i32().unwrap() as usize;
-
-                for arg in self.args {
-                    write_u32(memory, ptr_ptr_argv, ptr_argv_buf as u32);
-                    let bytes_target = &mut memory[ptr_argv_buf..][..arg.len()];
-                    bytes_target.copy_from_slice(arg);
-                    memory[ptr_argv_buf + arg.len()] = 0; // C string zero termination
-                    ptr_argv_buf += arg.len() + 1;
-                    ptr_ptr_argv += 4;
-                }
+                let ptr_argv_buf = arguments[1].expect_i32().unwrap() as usize;
+
+                write_c_strings(memory, self.args, ptr_ptr_argv, ptr_argv_buf);
 
                 success_code
             }
@@ -80,30 +76,29 @@ impl<'a> WasiDispatcher<'a> {
                 // size of string arguments buffer
                 let ptr_argv_buf_size = arguments[1].expect_i32().unwrap() as usize;
 
-                let argc = self.args.len() as u32;
-                write_u32(memory, ptr_argc, argc);
-
-                let argv_buf_size: u32 = self.args.iter().map(|a| 1 + a.len() as u32).sum();
-                write_u32(memory, ptr_argv_buf_size, argv_buf_size);
+                write_c_strings_sizes(memory, self.args, ptr_argc, ptr_argv_buf_size);
 
                 success_code
             }
             "environ_get" => {
-                // `environ_sizes_get` always reports 0 environment variables
-                // so we don't have to do anything here.
+                // uint8_t ** environ
+                let ptr_ptr_environ = arguments[0].expect_i32().unwrap() as usize;
+                // uint8_t * environ_buf
+                let ptr_environ_buf = arguments[1].expect_i32().unwrap() as usize;
+
+                write_c_strings(memory, self.env, ptr_ptr_environ, ptr_environ_buf);
 
                 success_code
             }
             "environ_sizes_get" => {
-                let num_env_ptr = arguments[0].expect_i32().unwrap() as usize;
-                let size_env_ptr = arguments[1].expect_i32().unwrap() as usize;
+                // (i32, i32) -> i32
 
-                // Calculate the total size required for environment variables
-                let total_size = 0;
-                let count = 0;
+                // number of environment variables
+                let ptr_count = arguments[0].expect_i32().unwrap() as usize;
+                // size of environment variables buffer
+                let ptr_buf_size = arguments[1].expect_i32().unwrap() as usize;
 
-                write_u32(memory, num_env_ptr, count);
-                write_u32(memory, size_env_ptr, total_size as u32);
+                write_c_strings_sizes(memory, self.env, ptr_count, ptr_buf_size);
 
                 success_code
             }
@@ -355,6 +350,32 @@ fn write_i32(memory: &mut [u8], addr: usize, value: i32) {
     memory[addr..][..4].copy_from_slice(&value.to_le_bytes());
 }
 
+/// Writes `strings` to `ptr_buf` as zero-terminated C strings, and a pointer to each one to
+/// the array at `ptr_ptrs`, the way `args_get` and `environ_get` do
+fn write_c_strings(memory: &mut [u8], strings: &[&[u8]], mut ptr_ptrs: usize, mut ptr_buf: usize) {
+    for string in strings {
+        write_u32(memory, ptr_ptrs, ptr_buf as u32);
+        let bytes_target = &mut memory[ptr_buf..][..string.len()];
+        bytes_target.copy_from_slice(string);
+        memory[ptr_buf + string.len()] = 0; // C string zero termination
+        ptr_buf += string.len() + 1;
+        ptr_ptrs += 4;
+    }
+}
+
+/// Writes how many `strings` there are, and how much space [write_c_strings] needs for them
+fn write_c_strings_sizes(
+    memory: &mut [u8],
+    strings: &[&[u8]],
+    ptr_count: usize,
+    ptr_buf_size: usize,
+) {
+    write_u32(memory, ptr_count, strings.len() as u32);
+
+    let buf_size: u32 = strings.iter().map(|s| 1 + s.len() as u32).sum();
+    write_u32(memory, ptr_buf_size, buf_size);
+}
+
 /// Error codes returned by functions.
 /// Not all of these error codes are returned by the functions provided by this
 /// API; some are used in higher-level library layers, and others are provided
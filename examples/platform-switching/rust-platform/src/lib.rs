@@ -13,7 +13,12 @@ extern "C" {
 
 #[no_mangle]
 pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
-    return libc::malloc(size);
+    let ptr = libc::malloc(size);
+
+    #[cfg(feature = "alloc-stats")]
+    roc_std::alloc_stats::record_alloc(ptr, size, _alignment);
+
+    ptr
 }
 
 #[no_mangle]
@@ -23,12 +28,20 @@ pub unsafe extern "C" fn roc_realloc(
     _old_size: usize,
     _alignment: u32,
 ) -> *mut c_void {
-    return libc::realloc(c_ptr, new_size);
+    let new_ptr = libc::realloc(c_ptr, new_size);
+
+    #[cfg(feature = "alloc-stats")]
+    roc_std::alloc_stats::record_realloc(c_ptr, new_ptr, new_size);
+
+    new_ptr
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
-    return libc::free(c_ptr);
+    #[cfg(feature = "alloc-stats")]
+    roc_std::alloc_stats::record_dealloc(c_ptr);
+
+    libc::free(c_ptr)
 }
 
 #[no_mangle]
@@ -93,6 +106,12 @@ pub extern "C" fn rust_main() -> i32 {
         panic!("Writing to stdout failed! {:?}", e);
     }
 
+    #[cfg(feature = "alloc-stats")]
+    {
+        drop(roc_str);
+        roc_std::alloc_stats::print_report();
+    }
+
     // Exit code
     0
 }
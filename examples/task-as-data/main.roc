@@ -0,0 +1,12 @@
+app [main] { pf: platform "platform/main.roc" }
+
+import pf.Stdin
+import pf.Stdout
+import pf.Task exposing [Task]
+
+main : Task {} I32
+main =
+    Stdout.line "What's your name?"
+    |> Task.await \{} -> Stdin.line
+    |> Task.await \name -> Stdout.line "Hi, $(name)!"
+    |> Task.mapErr \EndOfInput -> 1
@@ -0,0 +1,16 @@
+module [Command, Response]
+
+## Everything an app can ask the host to do. Since these are plain data, `roc glue` generates
+## them for the host, which can log them, check them in tests, and so on.
+Command : [
+    Exit I32,
+    ReadLine,
+    WriteLine Str,
+]
+
+## The host's answer to a `Command`. There's no answer to `Exit`, since nothing runs after it.
+Response : [
+    EndOfInput,
+    Line Str,
+    Written,
+]
@@ -0,0 +1,11 @@
+module [line]
+
+import Task exposing [Task]
+
+## Reads a line from standard input, without its line ending.
+line : Task Str [EndOfInput]
+line =
+    Task.perform ReadLine \response ->
+        when response is
+            Line str -> Ok str
+            _ -> Err EndOfInput
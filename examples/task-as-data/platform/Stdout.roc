@@ -0,0 +1,7 @@
+module [line]
+
+import Task exposing [Task]
+
+line : Str -> Task {} *
+line = \str ->
+    Task.perform (WriteLine str) \_ -> Ok {}
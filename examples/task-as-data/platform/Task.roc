@@ -0,0 +1,99 @@
+module [Task, ok, err, await, map, mapErr, onErr, attempt, loop, perform, interpret]
+
+import Command exposing [Command, Response]
+
+## A program that performs commands, as data: either it has finished, or it's waiting on
+## the response to a command, and the rest of the program is a function of that response.
+Task ok err := [
+    Finished (Result ok err),
+    Waiting Command (Response -> Task ok err),
+]
+
+ok : a -> Task a *
+ok = \a -> @Task (Finished (Ok a))
+
+err : a -> Task * a
+err = \a -> @Task (Finished (Err a))
+
+## A task that performs `command`, and turns the response into its result.
+## Modules like `Stdout` define their tasks with this.
+perform : Command, (Response -> Result a err) -> Task a err
+perform = \command, fromResponse ->
+    @Task (Waiting command \response -> @Task (Finished (fromResponse response)))
+
+attempt : Task a b, (Result a b -> Task c d) -> Task c d
+attempt = \@Task task, transform ->
+    when task is
+        Finished result -> transform result
+        Waiting command continue ->
+            @Task (Waiting command \response -> attempt (continue response) transform)
+
+await : Task a err, (a -> Task b err) -> Task b err
+await = \task, transform ->
+    attempt task \result ->
+        when result is
+            Ok a -> transform a
+            Err e -> err e
+
+onErr : Task ok a, (a -> Task ok b) -> Task ok b
+onErr = \task, transform ->
+    attempt task \result ->
+        when result is
+            Ok a -> ok a
+            Err e -> transform e
+
+map : Task a err, (a -> b) -> Task b err
+map = \task, transform ->
+    await task \a -> ok (transform a)
+
+mapErr : Task ok a, (a -> b) -> Task ok b
+mapErr = \task, transform ->
+    onErr task \e -> err (transform e)
+
+loop : state, (state -> Task [Step state, Done done] err) -> Task done err
+loop = \state, step ->
+    await (step state) \next ->
+        when next is
+            Step newState -> loop newState step
+            Done done -> ok done
+
+## Runs `task` as far as `responses` go, answering its commands with them in order, and returns
+## the first command there's no response for yet, or `finish` of its result if it gets that far.
+##
+## Running a task with the same responses always gets to the same command, so a host that saves
+## the responses can replay a run exactly, without performing any commands but the last.
+interpret : Task ok err, List Response, (Result ok err -> Command) -> Command
+interpret = \@Task task, responses, finish ->
+    when task is
+        Finished result -> finish result
+        Waiting command continue ->
+            when List.first responses is
+                Ok response -> interpret (continue response) (List.dropFirst responses 1) finish
+                Err ListWasEmpty -> command
+
+exit : Result {} I32 -> Command
+exit = \result ->
+    when result is
+        Ok {} -> Exit 0
+        Err code -> Exit code
+
+expect
+    task = perform ReadLine \_ -> Ok {}
+
+    interpret task [] exit == ReadLine
+
+expect
+    task =
+        perform ReadLine \_ -> Ok {}
+        |> await \{} -> perform (WriteLine "done") \_ -> Ok {}
+
+    interpret task [Line "hi"] exit == WriteLine "done"
+
+expect
+    task =
+        perform ReadLine \response ->
+            when response is
+                Line _ -> Ok {}
+                _ -> Err 1
+
+    interpret task [EndOfInput] exit == Exit 1
@@ -0,0 +1,15 @@
+platform "task-as-data"
+    requires {} { main : Task {} I32 }
+    exposes []
+    packages {}
+    imports [Task.{ Task }, Command.{ Command, Response }]
+    provides [mainForHost]
+
+# The host calls this with the responses to every command so far, and performs the command
+# it returns, until that command is `Exit`.
+mainForHost : List Response -> Command
+mainForHost = \responses ->
+    Task.interpret main responses \result ->
+        when result is
+            Ok {} -> Exit 0
+            Err code -> Exit code
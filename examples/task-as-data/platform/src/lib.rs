@@ -0,0 +1,116 @@
+#![allow(non_snake_case)]
+
+//! Runs the app one command at a time. `mainForHost` takes the responses to every command so
+//! far, and returns the next command to perform, so the responses are a complete record of a run.
+//!
+//! With ROC_RECORD set to a path, the responses are written there as the app runs. With
+//! ROC_REPLAY set to the path of such a recording, the app gets the recorded responses instead,
+//! and doesn't read stdin, so a run can be repeated exactly, e.g. in a test.
+
+use roc_app::{discriminant_Command, Command, Response};
+use roc_std::{RocList, RocStr};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, Write};
+
+#[no_mangle]
+pub extern "C" fn rust_main() -> i32 {
+    let mut replay = env::var_os("ROC_REPLAY").map(|path| {
+        let recording = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Could not read the recording {:?}: {}", path, err));
+
+        recording
+            .lines()
+            .map(decode_response)
+            .collect::<Vec<_>>()
+            .into_iter()
+    });
+
+    let mut record = env::var_os("ROC_RECORD").map(|path| {
+        File::create(&path)
+            .unwrap_or_else(|err| panic!("Could not create the recording {:?}: {}", path, err))
+    });
+
+    let mut responses = RocList::empty();
+
+    loop {
+        let command: Command = roc_app::mainForHost(responses.clone());
+
+        // Reading stdin is the only command that doesn't always get the same response.
+        let response = match command.discriminant() {
+            discriminant_Command::Exit => {
+                return command.unwrap_Exit();
+            }
+            discriminant_Command::ReadLine => None,
+            discriminant_Command::WriteLine => {
+                println!("{}", command.unwrap_WriteLine());
+
+                Some(Response::Written())
+            }
+        };
+
+        // On replay, commands still write to stdout (so the output can be compared),
+        // but every response comes from the recording.
+        let response = match &mut replay {
+            Some(recorded) => match recorded.next() {
+                Some(response) => response,
+                None => {
+                    eprintln!("The recording ended before the app did.");
+                    return 1;
+                }
+            },
+            None => response.unwrap_or_else(read_line),
+        };
+
+        if let Some(file) = &mut record {
+            if let Err(err) = writeln!(file, "{}", encode_response(&response)) {
+                panic!("Writing the recording failed! {:?}", err);
+            }
+        }
+
+        responses.push(response);
+    }
+}
+
+fn read_line() -> Response {
+    let mut line = String::new();
+
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) => Response::EndOfInput(),
+        Ok(_) => {
+            let line = line.strip_suffix('\n').unwrap_or(&line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            Response::Line(RocStr::from(line))
+        }
+        Err(err) => panic!("Reading from stdin failed! {:?}", err),
+    }
+}
+
+// A recording has one response per line. Lines read from stdin can't contain a newline,
+// so they don't need escaping.
+
+fn encode_response(response: &Response) -> String {
+    if response.is_EndOfInput() {
+        "end".to_string()
+    } else if response.is_Written() {
+        "written".to_string()
+    } else {
+        format!("line {}", response.clone().unwrap_Line())
+    }
+}
+
+fn decode_response(line: &str) -> Response {
+    match line {
+        "end" => Response::EndOfInput(),
+        "written" => Response::Written(),
+        _ => match line.strip_prefix("line ") {
+            Some(text) => Response::Line(RocStr::from(text)),
+            None => panic!("{:?} is not a response in the recording", line),
+        },
+    }
+}
+
+// Externs required by roc_std and by the Roc app, which `roc glue --externs rust` generates
+#[path = "../glue/roc_externs.rs"]
+mod roc_externs;
@@ -1,17 +1,12 @@
 const isOnMobile = window.innerWidth <= 1024;
 
-// The only way we can provide values to wasm_bindgen's generated code is to set globals
-window.js_create_app = js_create_app;
-window.js_run_app = js_run_app;
-window.js_get_result_and_memory = js_get_result_and_memory;
-
 // The only place we use console.error is in wasm_bindgen, where it gets a single string argument.
 console.error = function displayErrorInHistoryPanel(string) {
   const html = `<div class="panic">${string}</div>`;
   updateHistoryEntry(repl.inputHistoryIndex, false, html);
 };
 
-import * as roc_repl_wasm from "./repl/roc_repl_wasm.js";
+import * as roc from "./repl/roc_repl.js";
 
 const isHomepage = document.getElementById("homepage-repl-container") != null;
 
@@ -47,16 +42,6 @@ const repl = {
       show: `<p>Nicely done! This is an example of <a href=\"/tutorial#string-interpolation\">string interpolation</a>, which replaces part of a string with whatever you put inside the parentheses after a <code>$</code>.</p><p>Now that you’ve written a few <a href=\"/tutorial#naming-things\">expressions</a>, you can either continue exploring in this REPL, or move on to the <a href=\"/tutorial\">tutorial</a> to learn how to make full programs.<p><p><span class='welcome-to-roc'>Welcome to Roc!</span> <a href='/tutorial' class='btn-small'>${tutorialButtonSvg} Start Tutorial</a></p>`,
     },
   ],
-
-  textDecoder: new TextDecoder(),
-  textEncoder: new TextEncoder(),
-
-  compiler: null,
-  app: null,
-
-  // Temporary storage for the address of the result of running the user's code.
-  // Used while control flow returns to Rust to allocate space to copy the app's memory buffer.
-  result_addr: 0,
 };
 
 // Initialise
@@ -65,7 +50,7 @@ resetSourceInputHeight();
 repl.elemSourceInput.addEventListener("input", resetSourceInputHeight);
 repl.elemSourceInput.addEventListener("keydown", onInputKeydown);
 repl.elemSourceInput.addEventListener("keyup", onInputKeyup);
-roc_repl_wasm.default("/repl/roc_repl_wasm_bg.wasm").then(async (instance) => {
+roc.init("/repl/roc_repl_wasm_bg.wasm").then(async () => {
   const loadingMessage = repl.elemHistory.querySelector("#loading-message");
 
   if (loadingMessage != null) {
@@ -73,11 +58,10 @@ roc_repl_wasm.default("/repl/roc_repl_wasm_bg.wasm").then(async (instance) => {
   }
 
   repl.elemSourceInput.placeholder = "Enter some Roc code here.";
-  repl.compiler = instance;
 
   // Get help text from the compiler, and display it at top of the history panel
   try {
-    const helpText = await roc_repl_wasm.entrypoint_from_js(":help");
+    const helpText = await roc.evalHtml(":help");
     const helpElem = document.getElementById("help-text");
 
     if (helpElem != null) {
@@ -226,9 +210,10 @@ async function processInputQueue() {
       let outputText = "";
       let ok = true;
       try {
-        outputText = await roc_repl_wasm.entrypoint_from_js(inputText);
+        outputText = await roc.evalHtml(inputText);
       } catch (e) {
-        outputText = `${e}`;
+        // A crash in the compiled code rejects with its panic message
+        outputText = `<div class="panic">${e.message ?? e}</div>`;
         ok = false;
       }
 
@@ -240,109 +225,6 @@ async function processInputQueue() {
   }
 }
 
-// ----------------------------------------------------------------------------
-// Callbacks to JS from Rust
-// ----------------------------------------------------------------------------
-
-var ROC_PANIC_INFO = null;
-
-function send_panic_msg_to_js(rocstr_ptr, panic_tag) {
-  const { memory } = repl.app.exports;
-
-  const rocStrBytes = new Int8Array(memory.buffer, rocstr_ptr, 12);
-  const finalByte = rocStrBytes[11];
-
-  let stringBytes = "";
-  if (finalByte < 0) {
-    // small string
-
-    // bitwise ops on negative JS numbers are weird. This clears the bit that we
-    // use to indicate a small string. In rust it's `finalByte as u8 ^ 0b1000_0000`
-    const length = finalByte + 128;
-    stringBytes = new Uint8Array(memory.buffer, rocstr_ptr, length);
-  } else {
-    // big string
-    const rocStrWords = new Uint32Array(memory.buffer, rocstr_ptr, 3);
-    const [ptr, len, _cap] = rocStrWords;
-
-    const SEAMLESS_SLICE_BIT = 1 << 31;
-    const length = len & ~SEAMLESS_SLICE_BIT;
-
-    stringBytes = new Uint8Array(memory.buffer, ptr, length);
-  }
-
-  const decodedString = repl.textDecoder.decode(stringBytes);
-
-  ROC_PANIC_INFO = {
-    msg: decodedString,
-    panic_tag: panic_tag,
-  };
-}
-
-// Load Wasm code into the browser's virtual machine, so we can run it later.
-// This operation is async, so we call it before entering any code shared
-// with the command-line REPL, which is sync.
-async function js_create_app(wasm_module_bytes) {
-  const { instance } = await WebAssembly.instantiate(wasm_module_bytes, {
-    env: {
-      send_panic_msg_to_js: send_panic_msg_to_js,
-    },
-  });
-
-  // Keep the instance alive so we can run it later from shared REPL code
-  repl.app = instance;
-}
-
-// Call the `main` function of the user app, via the `wrapper` function.
-function js_run_app() {
-  const { wrapper, memory } = repl.app.exports;
-
-  // Run the user code, and remember the result address
-  // We'll pass it to Rust in the next callback
-  try {
-    repl.result_addr = wrapper();
-  } catch (e) {
-    // an exception could be that roc_panic was invoked,
-    // or some other crash (likely a compiler bug)
-    if (ROC_PANIC_INFO === null) {
-      throw e;
-    } else {
-      // when roc_panic set an error message, display it
-      const { msg, panic_tag } = ROC_PANIC_INFO;
-      ROC_PANIC_INFO = null;
-
-      console.error(format_roc_panic_message(msg, panic_tag));
-    }
-  }
-
-  // Tell Rust how much space to reserve for its copy of the app's memory buffer.
-  // We couldn't know that size until we actually ran the app.
-  return memory.buffer.byteLength;
-}
-
-function format_roc_panic_message(msg, panic_tag) {
-  switch (panic_tag) {
-    case 0: {
-      return `Roc failed with message: "${msg}"`;
-    }
-    case 1: {
-      return `User crash with message: "${msg}"`;
-    }
-    default: {
-      return `Got an invalid panic tag: "${panic_tag}"`;
-    }
-  }
-}
-
-// After Rust has allocated space for the app's memory buffer,
-// we copy it, and return the result address too
-function js_get_result_and_memory(buffer_alloc_addr) {
-  const appMemory = new Uint8Array(repl.app.exports.memory.buffer);
-  const compilerMemory = new Uint8Array(repl.compiler.memory.buffer);
-  compilerMemory.set(appMemory, buffer_alloc_addr);
-  return repl.result_addr;
-}
-
 // ----------------------------------------------------------------------------
 // Rendering
 // ----------------------------------------------------------------------------